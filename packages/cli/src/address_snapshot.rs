@@ -0,0 +1,220 @@
+//! The file an `AddressStore` is saved to, so a restart can restore millions of
+//! addresses without reading them back from `envio_addresses` one row at a time.
+//!
+//! Little-endian throughout: a fixed header (magic, format version, ecosystem,
+//! scope fingerprint, the block the file covers), the contract table the
+//! entries index into, then
+//! one record per address. Contracts are written by name with their start block
+//! rather than by the store's own indices, so a file stays readable by a store
+//! built from a config that lists its contracts in another order.
+//!
+//! The scope fingerprint hashes what the caller says the file is only valid
+//! for — the database schema and config it was saved under. The path is shared
+//! by every indexer run from one directory, so a file of another scope is
+//! treated as no file at all.
+//!
+//! The file is written beside its destination and renamed over it, so a reader
+//! only ever sees a complete snapshot or the previous one.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::field_columns::Ecosystem;
+
+const MAGIC: &[u8; 8] = b"ENVADDRS";
+/// Bumped on any layout change: a file of another version is never read.
+const FORMAT_VERSION: u32 = 3;
+
+pub(crate) type Fingerprint = [u8; 32];
+
+/// The header's fingerprint of a caller's scope string.
+pub(crate) fn fingerprint(scope: &str) -> Fingerprint {
+    Sha256::digest(scope.as_bytes()).into()
+}
+
+pub(crate) struct SnapshotContract {
+    pub name: String,
+    pub start_block: i64,
+}
+
+pub(crate) struct SnapshotEntry<K> {
+    pub key: K,
+    /// Index into the snapshot's own contract table.
+    pub contract_idx: u32,
+    pub registration_block: i64,
+    pub effective_start_block: i64,
+    pub end_block: Option<i64>,
+}
+
+/// How a missing end block is written: real ones are never negative.
+const NO_END_BLOCK: i64 = -1;
+
+pub(crate) struct Snapshot {
+    pub ecosystem_tag: u8,
+    pub block: i64,
+    pub contracts: Vec<SnapshotContract>,
+    pub entries: Vec<SnapshotEntry<Box<[u8]>>>,
+}
+
+/// The checksum setting only changes how a key renders, not the key itself, so
+/// EVM snapshots are interchangeable across it.
+pub(crate) fn ecosystem_tag(ecosystem: Ecosystem) -> u8 {
+    match ecosystem {
+        Ecosystem::Evm { .. } => 0,
+        Ecosystem::Svm => 1,
+        Ecosystem::Fuel => 2,
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Writes a snapshot to `path`, creating its directory when missing. `entries`
+/// is counted up front because the count precedes the records.
+pub(crate) fn write<'a>(
+    path: &Path,
+    ecosystem_tag: u8,
+    fingerprint: &Fingerprint,
+    block: i64,
+    contracts: &[SnapshotContract],
+    entries: impl ExactSizeIterator<Item = SnapshotEntry<&'a [u8]>>,
+) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp_path = path.with_extension("writing");
+    let mut out = BufWriter::new(File::create(&tmp_path)?);
+    out.write_all(MAGIC)?;
+    out.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    out.write_all(&[ecosystem_tag])?;
+    out.write_all(fingerprint)?;
+    out.write_all(&block.to_le_bytes())?;
+    out.write_all(&(contracts.len() as u32).to_le_bytes())?;
+    for contract in contracts {
+        out.write_all(&(contract.name.len() as u32).to_le_bytes())?;
+        out.write_all(contract.name.as_bytes())?;
+        out.write_all(&contract.start_block.to_le_bytes())?;
+    }
+    out.write_all(&(entries.len() as u64).to_le_bytes())?;
+    for entry in entries {
+        let key_len = u8::try_from(entry.key.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "address key over 255 bytes")
+        })?;
+        out.write_all(&[key_len])?;
+        out.write_all(entry.key)?;
+        out.write_all(&entry.contract_idx.to_le_bytes())?;
+        out.write_all(&entry.registration_block.to_le_bytes())?;
+        out.write_all(&entry.effective_start_block.to_le_bytes())?;
+        out.write_all(&entry.end_block.unwrap_or(NO_END_BLOCK).to_le_bytes())?;
+    }
+    let file = out.into_inner().map_err(|err| err.into_error())?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Reads a whole snapshot. A file that is truncated, of another format
+/// version or scope, or whose entries point past its contract table is an
+/// error — the caller falls back to the database rather than restoring half a
+/// store.
+pub(crate) fn read(path: &Path, fingerprint: &Fingerprint) -> io::Result<Snapshot> {
+    let mut input = BufReader::new(File::open(path)?);
+    let (ecosystem_tag, block) = read_header(&mut input, fingerprint)?;
+
+    let contract_count = read_u32(&mut input)?;
+    let mut contracts = Vec::with_capacity(contract_count as usize);
+    for _ in 0..contract_count {
+        let name_len = read_u32(&mut input)? as usize;
+        let mut name = vec![0; name_len];
+        input.read_exact(&mut name)?;
+        let name = String::from_utf8(name).map_err(|_| invalid("contract name isn't UTF-8"))?;
+        let start_block = read_i64(&mut input)?;
+        contracts.push(SnapshotContract { name, start_block });
+    }
+
+    let entry_count = read_u64(&mut input)?;
+    // Capped so a corrupt count can't reserve unbounded memory up front.
+    let mut entries = Vec::with_capacity(entry_count.min(1 << 20) as usize);
+    for _ in 0..entry_count {
+        let mut key_len = [0u8; 1];
+        input.read_exact(&mut key_len)?;
+        let mut key = vec![0; key_len[0] as usize];
+        input.read_exact(&mut key)?;
+        let contract_idx = read_u32(&mut input)?;
+        if contract_idx as usize >= contracts.len() {
+            return Err(invalid(format!(
+                "entry references contract {contract_idx} of {}",
+                contracts.len()
+            )));
+        }
+        entries.push(SnapshotEntry {
+            key: key.into_boxed_slice(),
+            contract_idx,
+            registration_block: read_i64(&mut input)?,
+            effective_start_block: read_i64(&mut input)?,
+            end_block: Some(read_i64(&mut input)?).filter(|&end| end != NO_END_BLOCK),
+        });
+    }
+    if input.read(&mut [0u8; 1])? != 0 {
+        return Err(invalid("trailing bytes after the last entry"));
+    }
+
+    Ok(Snapshot {
+        ecosystem_tag,
+        block,
+        contracts,
+        entries,
+    })
+}
+
+/// The block a snapshot covers, read from its header alone. `None` when there
+/// is no file, one this version can't read, or one of another scope.
+pub(crate) fn read_block(path: &Path, fingerprint: &Fingerprint) -> Option<i64> {
+    let mut input = BufReader::new(File::open(path).ok()?);
+    read_header(&mut input, fingerprint)
+        .ok()
+        .map(|(_, block)| block)
+}
+
+fn read_header(input: &mut impl Read, fingerprint: &Fingerprint) -> io::Result<(u8, i64)> {
+    let mut magic = [0u8; 8];
+    input.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid("not an address snapshot"));
+    }
+    let version = read_u32(input)?;
+    if version != FORMAT_VERSION {
+        return Err(invalid(format!(
+            "snapshot format version {version}, expected {FORMAT_VERSION}"
+        )));
+    }
+    let mut ecosystem_tag = [0u8; 1];
+    input.read_exact(&mut ecosystem_tag)?;
+    let mut file_fingerprint = Fingerprint::default();
+    input.read_exact(&mut file_fingerprint)?;
+    if &file_fingerprint != fingerprint {
+        return Err(invalid("it was saved under another schema or config"));
+    }
+    Ok((ecosystem_tag[0], read_i64(input)?))
+}
+
+fn read_u32(input: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    input.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(input: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_i64(input: &mut impl Read) -> io::Result<i64> {
+    let mut bytes = [0u8; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(i64::from_le_bytes(bytes))
+}
@@ -13,11 +13,14 @@
 //! counts) is computed once on first use and shared by every query the
 //! partition makes.
 
-use std::collections::HashMap;
-use std::sync::{Arc, OnceLock, RwLock, RwLockReadGuard};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, RwLock, RwLockReadGuard};
 
+use hypersync_client::format::{FilterWrapper, Hex};
 use napi_derive::napi;
 
+use crate::address_snapshot::{self, SnapshotContract, SnapshotEntry};
 use crate::field_columns::Ecosystem;
 
 /// Binary form of an address, the identity the store keys on.
@@ -86,6 +89,9 @@ struct Entry {
     contract_idx: u32,
     registration_block: i64,
     effective_start_block: i64,
+    /// The last block the address is indexed at, inclusive: the earlier of its
+    /// contract's TTL and a deregistration. `None` while it has no end.
+    end_block: Option<i64>,
     /// Rolled back past its registration block. The slot stays so live ids
     /// never shift; the key is unmapped so the address can be registered afresh.
     dead: bool,
@@ -96,6 +102,7 @@ pub struct StoreInner {
     contract_names: Vec<String>,
     contract_start_blocks: Vec<i64>,
     contract_depends_on_addresses: Vec<bool>,
+    contract_ttl_blocks: Vec<Option<i64>>,
     contract_idx_by_name: HashMap<String, u32>,
     entries: Vec<Entry>,
     id_by_key: HashMap<Key, u64>,
@@ -104,6 +111,13 @@ pub struct StoreInner {
     /// in registration order. Drained by `drain_for_write` when the batch
     /// covering their registration block is written.
     unwritten: Vec<u64>,
+    /// Ids whose end block a deregistration moved and the database hasn't
+    /// seen yet. Drained alongside `unwritten`, once the batch covering the
+    /// end block is written. May repeat an id; the drain reads each once.
+    unwritten_ends: Vec<u64>,
+    /// Bumped whenever an existing entry's end block changes, so a set can
+    /// keep its earliest end cached between changes.
+    ends_version: u64,
 }
 
 impl StoreInner {
@@ -128,18 +142,30 @@ impl StoreInner {
     }
 
     /// The gate every address-dependent registration applies to a routed item:
-    /// the address is registered for this contract and its effective start
-    /// block is at or before the item's block.
+    /// the address is registered for this contract, its effective start block
+    /// is at or before the item's block, and it hasn't ended before it.
     pub fn is_indexed_at(&self, key: &[u8], contract_idx: u32, block_number: i64) -> bool {
         match self.live_id(key) {
             Some(id) => {
                 let entry = self.entry(id);
-                entry.contract_idx == contract_idx && entry.effective_start_block <= block_number
+                entry.contract_idx == contract_idx
+                    && entry.effective_start_block <= block_number
+                    && entry.end_block.is_none_or(|end| block_number <= end)
             }
             None => false,
         }
     }
 
+    /// The end a dynamic registration gets from its contract's TTL alone —
+    /// what a rollback restores once it undoes a deregistration. Config
+    /// addresses never expire.
+    fn ttl_end_block(&self, contract_idx: u32, registration_block: i64) -> Option<i64> {
+        if registration_block == -1 {
+            return None;
+        }
+        self.contract_ttl_blocks[contract_idx as usize].map(|ttl| registration_block + ttl)
+    }
+
     pub fn contract_idx(&self, name: &str) -> Option<u32> {
         self.contract_idx_by_name.get(name).copied()
     }
@@ -182,17 +208,47 @@ pub struct AddressStoreContract {
     /// and for one whose events are all wildcard — either way its addresses are
     /// registered and persisted, but no partition is ever built from them.
     pub depends_on_addresses: bool,
+    /// How many blocks after its registration block a dynamically registered
+    /// address stays indexed; absent means until deregistered.
+    pub ttl_blocks: Option<i64>,
 }
 
 /// One address a batch asks the store to register.
 #[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct AddressRegistration {
     pub address: String,
     pub contract_name: String,
     /// -1 for a config address (not dynamically registered).
     pub registration_block: i64,
+    /// The end block the database holds for a seeded address. Left out for a
+    /// new registration, whose end comes from its contract's TTL.
+    pub end_block: Option<i64>,
+}
+
+/// One address a batch asks the store to stop indexing after `block`.
+#[napi(object)]
+pub struct AddressDeregistration {
+    pub address: String,
+    pub contract_name: String,
+    /// The last block the address is still indexed at.
+    pub block: i64,
+}
+
+/// What the store did with one deregistration, in the batch's order.
+#[napi(object)]
+pub struct DeregistrationVerdict {
+    /// `ended` | `alreadyEnded` | `notRegistered`
+    pub kind: String,
+    /// The end block the address had before — set for `alreadyEnded`, and for
+    /// an `ended` address whose TTL would have ended it later.
+    pub existing_end_block: Option<i64>,
 }
 
+pub const VERDICT_ENDED: &str = "ended";
+pub const VERDICT_ALREADY_ENDED: &str = "alreadyEnded";
+pub const VERDICT_NOT_REGISTERED: &str = "notRegistered";
+
 /// What the store did with one registration, in the batch's order. The caller
 /// turns `duplicate`/`conflict`/`invalid` into the user-facing warning.
 #[napi(object)]
@@ -234,20 +290,73 @@ pub struct AddressEntry {
     pub contract_name: String,
     pub registration_block: i64,
     pub effective_start_block: i64,
+    pub end_block: Option<i64>,
 }
 
-/// A drained registration paired with the checkpoint that must own its row.
+/// A drained registration or end, paired with the checkpoint that must own its
+/// row.
 #[napi(object)]
 pub struct DrainedAddress {
     pub address: String,
     pub contract_name: String,
     pub registration_block: i64,
+    /// The end the row carries: the TTL's for a registration, the
+    /// deregistration's for an end.
+    pub end_block: Option<i64>,
     /// Index into the `checkpoint_block_numbers` passed to `drain_for_write`.
     /// The ids themselves are bigints the caller already holds, so only the
     /// pairing crosses the boundary.
     pub checkpoint_idx: u32,
 }
 
+/// The registrations one fetch response's items make on their own, in item
+/// order, held until the response is applied (`AddressStore.registerPage`).
+/// Only a rejected one is ever read back, for its warning.
+#[napi]
+#[derive(Default)]
+pub struct RegistrationPage {
+    registrations: Vec<AddressRegistration>,
+}
+
+impl RegistrationPage {
+    pub(crate) fn new(registrations: Vec<AddressRegistration>) -> Self {
+        Self { registrations }
+    }
+}
+
+#[napi]
+impl RegistrationPage {
+    #[napi]
+    pub fn length(&self) -> u32 {
+        self.registrations.len() as u32
+    }
+
+    #[napi]
+    pub fn get(&self, index: u32) -> Option<AddressRegistration> {
+        self.registrations.get(index as usize).cloned()
+    }
+}
+
+/// What `loadSnapshot` restored for one contract, so the fetch state can build
+/// its partitions without the addresses ever crossing into JS.
+#[napi(object)]
+pub struct RestoredContract {
+    pub contract_name: String,
+    pub address_count: i64,
+    /// Addresses with a registration block — the ones a factory registered
+    /// rather than the config declared.
+    pub dynamic_count: i64,
+}
+
+#[napi(object)]
+pub struct RestoredSnapshot {
+    /// The block the snapshot covers: every address registered at or below it,
+    /// and nothing above.
+    pub block: i64,
+    /// Only contracts the snapshot held addresses for.
+    pub contracts: Vec<RestoredContract>,
+}
+
 /// Which of a contract's addresses `makeSet` should take.
 #[napi(object)]
 #[derive(Default)]
@@ -308,6 +417,14 @@ impl AddressStore {
         self.register_all(registrations, true)
     }
 
+    /// `register_batch` for the registrations a source found while routing a
+    /// page — a factory's creations — which never leave Rust. Applied with the
+    /// page's response, so a response dropped as stale registers nothing.
+    #[napi]
+    pub fn register_page(&self, page: &RegistrationPage) -> napi::Result<Vec<RegistrationVerdict>> {
+        self.register_all(page.registrations.clone(), true)
+    }
+
     /// `register_batch` for addresses the database already holds — config
     /// addresses and the dynamic ones a resume restores. Nothing is marked
     /// pending, so nothing is ever written back.
@@ -322,11 +439,14 @@ impl AddressStore {
     /// Drains the registrations awaiting persistence whose registration block is
     /// at or below `to_block_inclusive` — everything the batch being written
     /// covers — pairing each with the checkpoint at its registration block.
-    /// What sits above that block stays pending for a later batch.
+    /// Deregistrations follow the same way, keyed by their end block, after
+    /// every registration: a row registered and ended in one batch is written
+    /// twice, and the end is the later write. What sits above that block stays
+    /// pending for a later batch.
     ///
     /// A drained registration with no checkpoint at its block means it came from
     /// an event this batch never processed, which would write a row no rollback
-    /// could reach. That errors with the queue untouched, so the caller can fail
+    /// could reach. That errors with the queues untouched, so the caller can fail
     /// without the store having lied about what is still pending.
     #[napi]
     pub fn drain_for_write(
@@ -335,6 +455,20 @@ impl AddressStore {
         checkpoint_block_numbers: Vec<i64>,
     ) -> napi::Result<Vec<DrainedAddress>> {
         let mut store = self.inner.write().unwrap();
+        let checkpoint_idx_at =
+            |store: &StoreInner, entry: &Entry, block: i64| match checkpoint_block_numbers
+                .iter()
+                .position(|&b| b == block)
+            {
+                Some(idx) => Ok(idx as u32),
+                None => Err(napi::Error::from_reason(format!(
+                    "Registered address {} at block {} has no checkpoint in the batch that writes \
+                     it.",
+                    address_string(store.ecosystem, &entry.key),
+                    block
+                ))),
+            };
+
         let mut drained = Vec::new();
         let mut pending = Vec::new();
         for &id in store.unwritten.iter() {
@@ -343,34 +477,80 @@ impl AddressStore {
                 pending.push(id);
                 continue;
             }
-            let Some(checkpoint_idx) = checkpoint_block_numbers
-                .iter()
-                .position(|&block| block == entry.registration_block)
-            else {
-                return Err(napi::Error::from_reason(format!(
-                    "Registered address {} at block {} has no checkpoint in the batch that writes \
-                     it.",
-                    address_string(store.ecosystem, &entry.key),
-                    entry.registration_block
-                )));
-            };
             drained.push(DrainedAddress {
                 address: address_string(store.ecosystem, &entry.key),
                 contract_name: store.contract_name(entry.contract_idx).to_string(),
                 registration_block: entry.registration_block,
-                checkpoint_idx: checkpoint_idx as u32,
+                end_block: store.ttl_end_block(entry.contract_idx, entry.registration_block),
+                checkpoint_idx: checkpoint_idx_at(&store, entry, entry.registration_block)?,
+            });
+        }
+
+        let mut seen_ends = HashSet::new();
+        let mut pending_ends = Vec::new();
+        for &id in store.unwritten_ends.iter() {
+            if !seen_ends.insert(id) {
+                continue;
+            }
+            let entry = store.entry(id);
+            let end_block = entry
+                .end_block
+                .expect("an unwritten end belongs to an entry with an end block");
+            // An end can't land before the row it updates.
+            if end_block > to_block_inclusive || entry.registration_block > to_block_inclusive {
+                pending_ends.push(id);
+                continue;
+            }
+            drained.push(DrainedAddress {
+                address: address_string(store.ecosystem, &entry.key),
+                contract_name: store.contract_name(entry.contract_idx).to_string(),
+                registration_block: entry.registration_block,
+                end_block: Some(end_block),
+                checkpoint_idx: checkpoint_idx_at(&store, entry, end_block)?,
             });
         }
         store.unwritten = pending;
+        store.unwritten_ends = pending_ends;
         Ok(drained)
     }
 
-    /// How many registrations await persistence. Lets the write path skip
-    /// assembling a batch's checkpoints for the common chain that registered
-    /// nothing.
+    /// How many registrations and ends await persistence. Lets the write path
+    /// skip assembling a batch's checkpoints for the common chain that
+    /// registered nothing.
     #[napi]
     pub fn pending_count(&self) -> i64 {
-        self.read().unwritten.len() as i64
+        let store = self.read();
+        (store.unwritten.len() + store.unwritten_ends.len()) as i64
+    }
+
+    /// Ends every address in the batch after its `block`, so neither
+    /// `is_indexed_at` nor `AddressSet::contains_at` lets through an item above
+    /// it. An address keeps the earlier end when it already has one at or
+    /// before the block — its TTL or an earlier deregistration. The new end is
+    /// marked pending persistence, like a registration.
+    ///
+    /// Deregistering an address that isn't registered for the contract is a
+    /// `notRegistered` verdict rather than an error: a handler reacting to a
+    /// factory event can't always know the address was ever registered.
+    #[napi]
+    pub fn deregister_batch(
+        &self,
+        deregistrations: Vec<AddressDeregistration>,
+    ) -> napi::Result<Vec<DeregistrationVerdict>> {
+        let mut store = self.inner.write().unwrap();
+        for dereg in deregistrations.iter() {
+            if store.contract_idx(&dereg.contract_name).is_none() {
+                return Err(napi::Error::from_reason(format!(
+                    "Address {} deregistered for contract \"{}\", which the chain doesn't index.",
+                    dereg.address, dereg.contract_name
+                )));
+            }
+        }
+        let verdicts = deregistrations
+            .iter()
+            .map(|dereg| store.deregister_one(dereg))
+            .collect();
+        Ok(verdicts)
     }
 
     /// The registrations still awaiting persistence, in registration order. For
@@ -388,6 +568,7 @@ impl AddressStore {
                     contract_name: store.contract_name(entry.contract_idx).to_string(),
                     registration_block: entry.registration_block,
                     effective_start_block: entry.effective_start_block,
+                    end_block: entry.end_block,
                 }
             })
             .collect()
@@ -492,16 +673,30 @@ impl AddressStore {
     }
 
     /// Drops every address registered after `target_block`, returning how many
-    /// were dropped. Ids are tombstoned rather than reused, so a set built
-    /// before the rollback keeps pointing at the right entries; the fetch state
-    /// re-derives its partitions from filtered sets straight after.
+    /// were dropped, and undoes every deregistration after it — an address
+    /// whose end moved past the target falls back to its TTL's end. Ids are
+    /// tombstoned rather than reused, so a set built before the rollback keeps
+    /// pointing at the right entries; the fetch state re-derives its partitions
+    /// from filtered sets straight after.
     #[napi]
     pub fn rollback(&self, target_block: i64) -> i64 {
         let mut store = self.inner.write().unwrap();
         let mut removed = 0;
+        let mut ends_changed = false;
         for id in 0..store.entries.len() {
             let entry = &store.entries[id];
-            if entry.dead || entry.registration_block <= target_block {
+            if entry.dead {
+                continue;
+            }
+            if entry.registration_block <= target_block {
+                if entry.end_block.is_some_and(|end| end > target_block) {
+                    let ttl_end_block =
+                        store.ttl_end_block(entry.contract_idx, entry.registration_block);
+                    if entry.end_block != ttl_end_block {
+                        store.entries[id].end_block = ttl_end_block;
+                        ends_changed = true;
+                    }
+                }
                 continue;
             }
             let key = entry.key.clone();
@@ -518,9 +713,199 @@ impl AddressStore {
             .filter(|&id| !store.entry(id).dead)
             .collect();
         store.unwritten = live_unwritten;
+        // Likewise an end the rollback just undid: what the database should
+        // hold is the TTL's end, which the registration's own row carries.
+        let kept_ends = std::mem::take(&mut store.unwritten_ends)
+            .into_iter()
+            .filter(|&id| {
+                let entry = store.entry(id);
+                !entry.dead && entry.end_block.is_some_and(|end| end <= target_block)
+            })
+            .collect();
+        store.unwritten_ends = kept_ends;
+        if ends_changed || removed > 0 {
+            store.ends_version += 1;
+        }
         removed
     }
 
+    /// The fetchable addresses registered at or before `block` that end after
+    /// it — the ones a rollback to `block` must fetch again past it, even where
+    /// a partition had already dropped them for having ended. Taken before the
+    /// rollback, which resets a deregistered address's end.
+    #[napi]
+    pub fn make_set_ending_after(&self, block: i64) -> AddressSet {
+        let store = self.read();
+        let ids = store.sorted_live_ids(0, |entry| {
+            store.contract_depends_on_addresses[entry.contract_idx as usize]
+                && entry.registration_block <= block
+                && entry.end_block.is_some_and(|end| end > block)
+        });
+        drop(store);
+        AddressSet::new(self.inner.clone(), ids)
+    }
+
+    /// Writes every address the database holds at `block` to a snapshot file:
+    /// live entries registered at or below it, minus anything still awaiting
+    /// persistence. Returns how many were written. The caller passes the
+    /// chain's committed progress block, so the file mirrors `envio_addresses`
+    /// exactly as of that commit — an end above it is written as the TTL's,
+    /// which is what the row held then. `scope` names what the file is only
+    /// valid for; a read under any other scope ignores it.
+    #[napi]
+    pub fn save_snapshot(&self, path: String, scope: String, block: i64) -> napi::Result<i64> {
+        let store = self.read();
+        let unwritten: HashSet<u64> = store.unwritten.iter().copied().collect();
+        let unwritten_ends: HashSet<u64> = store.unwritten_ends.iter().copied().collect();
+        let entries: Vec<SnapshotEntry<&[u8]>> = store
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(id, entry)| {
+                !entry.dead
+                    && entry.registration_block <= block
+                    && !unwritten.contains(&(*id as u64))
+            })
+            .map(|(id, entry)| SnapshotEntry {
+                key: &*entry.key,
+                contract_idx: entry.contract_idx,
+                registration_block: entry.registration_block,
+                effective_start_block: entry.effective_start_block,
+                end_block: match entry.end_block {
+                    Some(end) if end <= block && !unwritten_ends.contains(&(id as u64)) => {
+                        Some(end)
+                    }
+                    _ => store.ttl_end_block(entry.contract_idx, entry.registration_block),
+                },
+            })
+            .collect();
+        let count = entries.len() as i64;
+        let contracts: Vec<SnapshotContract> = store
+            .contract_names
+            .iter()
+            .zip(store.contract_start_blocks.iter())
+            .map(|(name, &start_block)| SnapshotContract {
+                name: name.clone(),
+                start_block,
+            })
+            .collect();
+        address_snapshot::write(
+            Path::new(&path),
+            address_snapshot::ecosystem_tag(store.ecosystem),
+            &address_snapshot::fingerprint(&scope),
+            block,
+            &contracts,
+            entries.into_iter(),
+        )
+        .map_err(|err| {
+            napi::Error::from_reason(format!("Failed to write address snapshot {path}: {err}"))
+        })?;
+        Ok(count)
+    }
+
+    /// Restores a `saveSnapshot` file into this store, which must still be
+    /// empty. Restored addresses count as persisted, exactly like `seedBatch`,
+    /// and a later `seedBatch` of the same address adopts the database's end
+    /// block over the file's.
+    ///
+    /// The file is validated whole before anything is applied — its scope and
+    /// ecosystem, that every contract it uses is still declared, and that its keys are
+    /// well-formed and unique — so an error leaves the store empty. Whether the file's block still
+    /// matches the database is the caller's check, via `addressSnapshotBlock`.
+    #[napi]
+    pub fn load_snapshot(&self, path: String, scope: String) -> napi::Result<RestoredSnapshot> {
+        let fail = |reason: String| {
+            napi::Error::from_reason(format!("Invalid address snapshot {path}: {reason}"))
+        };
+        let snapshot =
+            address_snapshot::read(Path::new(&path), &address_snapshot::fingerprint(&scope))
+                .map_err(|err| fail(err.to_string()))?;
+
+        let mut store = self.inner.write().unwrap();
+        if !store.entries.is_empty() {
+            return Err(fail("the store already holds addresses".to_string()));
+        }
+        if snapshot.ecosystem_tag != address_snapshot::ecosystem_tag(store.ecosystem) {
+            return Err(fail("it was saved for another ecosystem".to_string()));
+        }
+
+        // Resolved lazily: a contract the config dropped is fine as long as
+        // the snapshot holds no addresses for it. A contract whose start block
+        // moved since the save gets its entries' effective start blocks derived
+        // afresh, exactly as seeding them from the database would.
+        let mut resolved: Vec<Option<(u32, bool)>> = vec![None; snapshot.contracts.len()];
+        let mut id_by_key = HashMap::with_capacity(snapshot.entries.len());
+        for (id, entry) in snapshot.entries.iter().enumerate() {
+            let snapshot_idx = entry.contract_idx as usize;
+            if resolved[snapshot_idx].is_none() {
+                let contract = &snapshot.contracts[snapshot_idx];
+                let idx = store.contract_idx(&contract.name).ok_or_else(|| {
+                    fail(format!(
+                        "contract \"{}\" is no longer declared",
+                        contract.name
+                    ))
+                })?;
+                let start_block_moved =
+                    store.contract_start_blocks[idx as usize] != contract.start_block;
+                resolved[snapshot_idx] = Some((idx, start_block_moved));
+            }
+            let well_formed = match store.ecosystem {
+                Ecosystem::Evm { .. } => entry.key.len() == 20,
+                Ecosystem::Fuel => entry.key.len() == 32,
+                Ecosystem::Svm => !entry.key.is_empty(),
+            };
+            if !well_formed {
+                return Err(fail(format!("malformed address key at entry {id}")));
+            }
+            if id_by_key.insert(entry.key.clone(), id as u64).is_some() {
+                return Err(fail(format!(
+                    "address {} appears twice",
+                    address_string(store.ecosystem, &entry.key)
+                )));
+            }
+        }
+
+        let mut dynamic_count_by_contract = vec![0u32; store.contract_names.len()];
+        for entry in snapshot.entries {
+            let (contract_idx, start_block_moved) = resolved[entry.contract_idx as usize]
+                .expect("every entry's contract was resolved above");
+            let effective_start_block = if start_block_moved {
+                derive_effective_start_block(
+                    entry.registration_block,
+                    store.contract_start_blocks[contract_idx as usize],
+                )
+            } else {
+                entry.effective_start_block
+            };
+            store.live_count_by_contract[contract_idx as usize] += 1;
+            if entry.registration_block != -1 {
+                dynamic_count_by_contract[contract_idx as usize] += 1;
+            }
+            store.entries.push(Entry {
+                key: entry.key,
+                contract_idx,
+                registration_block: entry.registration_block,
+                effective_start_block,
+                end_block: entry.end_block,
+                dead: false,
+            });
+        }
+        store.id_by_key = id_by_key;
+
+        let contracts = (0..store.contract_names.len())
+            .filter(|&idx| store.live_count_by_contract[idx] > 0)
+            .map(|idx| RestoredContract {
+                contract_name: store.contract_names[idx].clone(),
+                address_count: i64::from(store.live_count_by_contract[idx]),
+                dynamic_count: i64::from(dynamic_count_by_contract[idx]),
+            })
+            .collect();
+        Ok(RestoredSnapshot {
+            block: snapshot.block,
+            contracts,
+        })
+    }
+
     /// The entry an address is registered under, whichever contract holds it —
     /// addresses are unique chain-wide. `None` once rolled back.
     #[napi]
@@ -534,6 +919,7 @@ impl AddressStore {
             contract_name: store.contract_name(entry.contract_idx).to_string(),
             registration_block: entry.registration_block,
             effective_start_block: entry.effective_start_block,
+            end_block: entry.end_block,
         })
     }
 
@@ -553,12 +939,22 @@ impl AddressStore {
     }
 }
 
+/// The block an address snapshot covers, read from its header alone — what the
+/// resume checks against the chain's committed progress before deciding to
+/// load it. `None` when there's no file, or it's of another format version or
+/// was saved under another scope.
+#[napi]
+pub fn address_snapshot_block(path: String, scope: String) -> Option<i64> {
+    address_snapshot::read_block(Path::new(&path), &address_snapshot::fingerprint(&scope))
+}
+
 impl AddressStore {
     fn with_ecosystem(ecosystem: Ecosystem, contracts: Vec<AddressStoreContract>) -> Self {
         let mut contract_names = Vec::with_capacity(contracts.len());
         let mut contract_start_blocks = Vec::with_capacity(contracts.len());
         let mut contract_idx_by_name = HashMap::with_capacity(contracts.len());
         let mut contract_depends_on_addresses = Vec::with_capacity(contracts.len());
+        let mut contract_ttl_blocks = Vec::with_capacity(contracts.len());
         for contract in contracts {
             if contract_idx_by_name.contains_key(&contract.name) {
                 continue;
@@ -567,6 +963,7 @@ impl AddressStore {
             contract_names.push(contract.name);
             contract_start_blocks.push(contract.start_block.unwrap_or(0).max(0));
             contract_depends_on_addresses.push(contract.depends_on_addresses);
+            contract_ttl_blocks.push(contract.ttl_blocks.map(|ttl| ttl.max(0)));
         }
         let live_count_by_contract = vec![0u32; contract_names.len()];
         Self {
@@ -575,11 +972,14 @@ impl AddressStore {
                 contract_names,
                 contract_start_blocks,
                 contract_depends_on_addresses,
+                contract_ttl_blocks,
                 contract_idx_by_name,
                 entries: Vec::new(),
                 id_by_key: HashMap::new(),
                 live_count_by_contract,
                 unwritten: Vec::new(),
+                unwritten_ends: Vec::new(),
+                ends_version: 0,
             })),
         }
     }
@@ -646,20 +1046,35 @@ impl StoreInner {
         if let Some(id) = self.live_id(&key) {
             let entry = self.entry(id);
             let existing_contract_name = self.contract_name(entry.contract_idx).to_string();
+            let existing_effective_start_block = entry.effective_start_block;
             let kind = if existing_contract_name == reg.contract_name {
                 VERDICT_DUPLICATE
             } else {
                 VERDICT_CONFLICT
             };
+            // A resume from a snapshot seeds the rows whose end moved after the
+            // file was saved a second time; the database's end is the newer one.
+            if !track_unwritten
+                && kind == VERDICT_DUPLICATE
+                && entry.registration_block == reg.registration_block
+                && reg.end_block.is_some()
+                && entry.end_block != reg.end_block
+            {
+                self.entries[id as usize].end_block = reg.end_block;
+                self.ends_version += 1;
+            }
             return RegistrationVerdict {
                 kind: kind.to_string(),
                 fetchable: false,
                 effective_start_block,
-                existing_effective_start_block: Some(entry.effective_start_block),
+                existing_effective_start_block: Some(existing_effective_start_block),
                 existing_contract_name: Some(existing_contract_name),
             };
         }
 
+        let end_block = reg
+            .end_block
+            .or_else(|| self.ttl_end_block(contract_idx, reg.registration_block));
         let id = self.entries.len() as u64;
         self.id_by_key.insert(key.clone(), id);
         self.entries.push(Entry {
@@ -667,6 +1082,7 @@ impl StoreInner {
             contract_idx,
             registration_block: reg.registration_block,
             effective_start_block,
+            end_block,
             dead: false,
         });
         self.live_count_by_contract[contract_idx as usize] += 1;
@@ -681,6 +1097,36 @@ impl StoreInner {
             existing_effective_start_block: None,
         }
     }
+
+    /// Infallible for the same reason as `register_one`.
+    fn deregister_one(&mut self, dereg: &AddressDeregistration) -> DeregistrationVerdict {
+        let contract_idx = self
+            .contract_idx(&dereg.contract_name)
+            .expect("deregister_batch validates every contract name before applying the batch");
+        let id = address_key(self.ecosystem, &dereg.address)
+            .and_then(|key| self.live_id(&key))
+            .filter(|&id| self.entry(id).contract_idx == contract_idx);
+        let Some(id) = id else {
+            return DeregistrationVerdict {
+                kind: VERDICT_NOT_REGISTERED.to_string(),
+                existing_end_block: None,
+            };
+        };
+        let existing_end_block = self.entry(id).end_block;
+        if existing_end_block.is_some_and(|end| end <= dereg.block) {
+            return DeregistrationVerdict {
+                kind: VERDICT_ALREADY_ENDED.to_string(),
+                existing_end_block,
+            };
+        }
+        self.entries[id as usize].end_block = Some(dereg.block);
+        self.unwritten_ends.push(id);
+        self.ends_version += 1;
+        DeregistrationVerdict {
+            kind: VERDICT_ENDED.to_string(),
+            existing_end_block,
+        }
+    }
 }
 
 fn apply_window(ids: &[u64], offset: Option<i64>, limit: Option<i64>) -> Vec<u64> {
@@ -720,6 +1166,30 @@ pub struct ContractSlice {
     pub contract_idx: u32,
     pub addresses: Vec<String>,
     pub topics: Vec<String>,
+    address_filter: OnceLock<String>,
+}
+
+impl ContractSlice {
+    /// The addresses as HyperSync's hex-encoded bloom filter, built on first
+    /// use — only slices too large to list outright ever need one. EVM only:
+    /// the keys are read back from the rendered `0x` strings.
+    pub fn address_filter(&self) -> &str {
+        self.address_filter.get_or_init(|| {
+            let keys: Vec<[u8; 20]> = self
+                .addresses
+                .iter()
+                .map(|address| {
+                    let mut key = [0u8; 20];
+                    faster_hex::hex_decode(&address.as_bytes()[2..], &mut key)
+                        .expect("EVM addresses render as 20 bytes of 0x-prefixed hex");
+                    key
+                })
+                .collect();
+            FilterWrapper::from_keys(keys.iter().map(|key| key.as_slice()), None)
+                .expect("building a bloom filter from keys can't fail")
+                .encode_hex()
+        })
+    }
 }
 
 /// Everything derived from a set's ids, built once on first use. A partition
@@ -766,13 +1236,17 @@ impl SetCache {
 /// straight after, and a stale set that outlives that still can't fetch a dead
 /// address: `is_indexed_at` answers `false` for it, so every router drops the
 /// items it would bring back. Pruning here instead would shift the offsets
-/// `start_block_groups` hands to `slice` mid-flight.
+/// `start_block_groups` hands to `slice` mid-flight. An ended address is the
+/// same: the set keeps it until `filter_ended_before` is asked to drop it.
 #[napi]
 pub struct AddressSet {
     store: Arc<RwLock<StoreInner>>,
     /// Ordered by `(effectiveStartBlock, address bytes)`.
     ids: Arc<[u64]>,
     cache: OnceLock<Arc<SetCache>>,
+    /// `first_end_block`'s answer, with the store's `ends_version` it was
+    /// computed at.
+    first_end: Mutex<Option<(u64, Option<i64>)>>,
 }
 
 #[napi]
@@ -835,7 +1309,7 @@ impl AddressSet {
     #[napi]
     pub fn filter_by_contracts(&self, contract_names: Vec<String>) -> AddressSet {
         let store = self.store.read().unwrap();
-        let kept: std::collections::HashSet<u32> = contract_names
+        let kept: HashSet<u32> = contract_names
             .iter()
             .filter_map(|name| store.contract_idx(name))
             .collect();
@@ -868,6 +1342,69 @@ impl AddressSet {
         AddressSet::new(self.store.clone(), ids)
     }
 
+    /// The earliest end block among the set's live addresses, `None` when none
+    /// of them ends. Asked after every response of the partition holding the
+    /// set, so it's cached until an end in the store moves.
+    #[napi]
+    pub fn first_end_block(&self) -> Option<i64> {
+        let store = self.store.read().unwrap();
+        let mut first_end = self.first_end.lock().unwrap();
+        if let Some((version, first)) = *first_end {
+            if version == store.ends_version {
+                return first;
+            }
+        }
+        let first = self
+            .ids
+            .iter()
+            .filter_map(|&id| {
+                let entry = store.entry(id);
+                if entry.dead {
+                    None
+                } else {
+                    entry.end_block
+                }
+            })
+            .min();
+        *first_end = Some((store.ends_version, first));
+        first
+    }
+
+    /// Drops addresses that ended before `block` — nothing at or above it can
+    /// be indexed for them any more — and any the store has since tombstoned.
+    #[napi]
+    pub fn filter_ended_before(&self, block: i64) -> AddressSet {
+        let store = self.store.read().unwrap();
+        let ids: Vec<u64> = self
+            .ids
+            .iter()
+            .copied()
+            .filter(|&id| {
+                let entry = store.entry(id);
+                !entry.dead && entry.end_block.is_none_or(|end| end >= block)
+            })
+            .collect();
+        drop(store);
+        AddressSet::new(self.store.clone(), ids)
+    }
+
+    /// The addresses of this set that `other` doesn't hold, in set order.
+    #[napi]
+    pub fn exclude(&self, other: &AddressSet) -> AddressSet {
+        assert!(
+            Arc::ptr_eq(&self.store, &other.store),
+            "excluding an address set of a different store",
+        );
+        let excluded: HashSet<u64> = other.ids.iter().copied().collect();
+        let ids = self
+            .ids
+            .iter()
+            .copied()
+            .filter(|id| !excluded.contains(id))
+            .collect();
+        AddressSet::new(self.store.clone(), ids)
+    }
+
     /// Union with another set of the same store, keeping set order. Duplicate
     /// ids collapse, so merging overlapping partitions can't double-count.
     #[napi]
@@ -930,6 +1467,7 @@ impl AddressSet {
                     contract_name: store.contract_name(entry.contract_idx).to_string(),
                     registration_block: entry.registration_block,
                     effective_start_block: entry.effective_start_block,
+                    end_block: entry.end_block,
                 }
             })
             .collect()
@@ -942,6 +1480,7 @@ impl AddressSet {
             store,
             ids: ids.into(),
             cache: OnceLock::new(),
+            first_end: Mutex::new(None),
         }
     }
 
@@ -961,6 +1500,7 @@ impl AddressSet {
                             contract_idx: entry.contract_idx,
                             addresses: Vec::new(),
                             topics: Vec::new(),
+                            address_filter: OnceLock::new(),
                         });
                         contracts.len() - 1
                     });
@@ -1017,6 +1557,7 @@ pub(crate) mod test_support {
                     name: name.to_string(),
                     start_block: None,
                     depends_on_addresses: true,
+                    ttl_blocks: None,
                 })
                 .collect(),
         );
@@ -1029,6 +1570,7 @@ pub(crate) mod test_support {
                     // A config address: effective from block 0, so tests only
                     // opt into the temporal gate when they set a start block.
                     registration_block: -1,
+                    end_block: None,
                 })
             })
             .collect();
@@ -1094,6 +1636,7 @@ mod tests {
                 name: name.to_string(),
                 start_block: *start_block,
                 depends_on_addresses: true,
+                ttl_blocks: None,
             })
             .collect()
     }
@@ -1106,6 +1649,7 @@ mod tests {
             name: name.to_string(),
             start_block: None,
             depends_on_addresses: false,
+            ttl_blocks: None,
         }
     }
 
@@ -1114,6 +1658,7 @@ mod tests {
             address: address.to_string(),
             contract_name: contract_name.to_string(),
             registration_block,
+            end_block: None,
         }
     }
 
@@ -1572,6 +2117,201 @@ mod tests {
         );
     }
 
+    fn dereg(address: &str, contract_name: &str, block: i64) -> AddressDeregistration {
+        AddressDeregistration {
+            address: address.to_string(),
+            contract_name: contract_name.to_string(),
+            block,
+        }
+    }
+
+    /// `C` expires 50 blocks after an address is registered for it.
+    fn ttl_store() -> AddressStore {
+        let mut contracts = contracts(&[("C", Some(100)), ("D", None)]);
+        contracts[0].ttl_blocks = Some(50);
+        AddressStore::new_evm(false, contracts)
+    }
+
+    #[test]
+    fn deregistration_ends_the_gate_after_its_block() {
+        let store = store();
+        store.register_seed(vec![reg(A, "C", 300), reg(B, "C", 300)]);
+        let set = store.make_set_of(vec![A.to_string()]);
+        let verdicts = store
+            .deregister_batch(vec![
+                dereg(A, "C", 400),
+                // A later deregistration can't push the end back out.
+                dereg(A, "C", 500),
+                dereg(B, "D", 400),
+                dereg(C, "C", 400),
+            ])
+            .unwrap();
+        assert_eq!(
+            (
+                verdicts
+                    .iter()
+                    .map(|v| (v.kind.as_str(), v.existing_end_block))
+                    .collect::<Vec<_>>(),
+                store.is_indexed_at(A.to_string(), "C".to_string(), 400),
+                store.is_indexed_at(A.to_string(), "C".to_string(), 401),
+                set.contains_at(A.to_string(), "C".to_string(), 400),
+                set.contains_at(A.to_string(), "C".to_string(), 401),
+                store.is_indexed_at(B.to_string(), "C".to_string(), 401),
+            ),
+            (
+                vec![
+                    ("ended", None),
+                    ("alreadyEnded", Some(400)),
+                    ("notRegistered", None),
+                    ("notRegistered", None),
+                ],
+                true,
+                false,
+                true,
+                false,
+                true
+            )
+        );
+        // Still registered: an ended address isn't free to take again.
+        assert_eq!(
+            kinds(&store.register_batch(vec![reg(A, "D", 600)]).unwrap()),
+            vec!["conflict"]
+        );
+        assert!(store
+            .deregister_batch(vec![dereg(A, "Unknown", 400)])
+            .is_err());
+    }
+
+    #[test]
+    fn ttl_ends_a_dynamic_registration_but_not_a_config_address() {
+        let store = ttl_store();
+        store.register_seed(vec![reg(A, "C", -1)]);
+        store
+            .register_batch(vec![reg(B, "C", 200), reg(C, "D", 200)])
+            .unwrap();
+        assert_eq!(
+            (
+                store.get(A.to_string()).unwrap().end_block,
+                store.get(B.to_string()).unwrap().end_block,
+                store.get(C.to_string()).unwrap().end_block,
+                store.is_indexed_at(B.to_string(), "C".to_string(), 250),
+                store.is_indexed_at(B.to_string(), "C".to_string(), 251),
+            ),
+            (None, Some(250), None, true, false)
+        );
+        // Deregistering before the TTL runs out ends it earlier.
+        let verdicts = store.deregister_batch(vec![dereg(B, "C", 220)]).unwrap();
+        assert_eq!(
+            (
+                verdicts[0].kind.as_str(),
+                verdicts[0].existing_end_block,
+                store.get(B.to_string()).unwrap().end_block
+            ),
+            ("ended", Some(250), Some(220))
+        );
+    }
+
+    fn drained_ends(
+        store: &AddressStore,
+        to_block: i64,
+        checkpoints: &[i64],
+    ) -> Vec<(String, i64, Option<i64>, u32)> {
+        store
+            .drain_for_write(to_block, checkpoints.to_vec())
+            .unwrap()
+            .into_iter()
+            .map(|e| {
+                (
+                    e.address,
+                    e.registration_block,
+                    e.end_block,
+                    e.checkpoint_idx,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ends_drain_after_registrations_at_their_own_block() {
+        let store = ttl_store();
+        store.register_seed(vec![reg(A, "D", 100)]);
+        store.register_batch(vec![reg(B, "C", 200)]).unwrap();
+        store
+            .deregister_batch(vec![dereg(A, "D", 210), dereg(B, "C", 220)])
+            .unwrap();
+        assert_eq!(store.pending_count(), 3);
+
+        assert_eq!(
+            (
+                drained_ends(&store, 215, &[200, 210]),
+                drained_ends(&store, 300, &[220]),
+                store.pending_count(),
+            ),
+            (
+                vec![
+                    // The registration carries its TTL's end.
+                    (B.to_string(), 200, Some(250), 0),
+                    (A.to_string(), 100, Some(210), 1),
+                ],
+                vec![(B.to_string(), 200, Some(220), 0)],
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn rollback_undoes_deregistrations_above_the_target() {
+        let store = ttl_store();
+        store.register_seed(vec![reg(A, "D", 100), reg(B, "C", 200)]);
+        let set = store
+            .make_set("C".to_string(), None)
+            .merge(&store.make_set("D".to_string(), None));
+        assert_eq!(set.first_end_block(), Some(250));
+        store
+            .deregister_batch(vec![dereg(A, "D", 150), dereg(B, "C", 230)])
+            .unwrap();
+        assert_eq!(
+            (
+                set.first_end_block(),
+                set.filter_ended_before(200).addresses(),
+                set.filter_ended_before(240).addresses(),
+            ),
+            (Some(150), vec![B.to_string()], Vec::<String>::new())
+        );
+
+        store.rollback(210);
+        assert_eq!(
+            (
+                // A's end survives; B falls back to its TTL.
+                store.get(A.to_string()).unwrap().end_block,
+                store.get(B.to_string()).unwrap().end_block,
+                set.first_end_block(),
+                store.make_set_ending_after(210).addresses(),
+                // B's pending end was undone, so only A's is left to write.
+                drained_ends(&store, 210, &[150]),
+            ),
+            (
+                Some(150),
+                Some(250),
+                Some(150),
+                vec![B.to_string()],
+                vec![(A.to_string(), 100, Some(150), 0)],
+            )
+        );
+    }
+
+    #[test]
+    fn exclude_keeps_what_the_other_set_lacks() {
+        let store = store();
+        store.register_seed(vec![reg(A, "D", 100), reg(B, "D", 200), reg(C, "D", 300)]);
+        let all = store.make_set("D".to_string(), None);
+        let held = store.make_set_of(vec![B.to_string()]);
+        assert_eq!(
+            all.exclude(&held).addresses(),
+            vec![A.to_string(), C.to_string()]
+        );
+    }
+
     #[test]
     fn set_cache_indexes_owners_and_padded_topics() {
         let store = store();
@@ -1620,4 +2360,176 @@ mod tests {
             (vec!["added"], vec![program.to_string()], true)
         );
     }
+
+    /// The scope every snapshot test saves and reads under.
+    const SCOPE: &str = "public";
+
+    fn snapshot_path(dir: &tempdir::TempDir) -> String {
+        dir.path()
+            .join("addresses.bin")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn snapshot_round_trips_what_the_database_holds() {
+        let dir = tempdir::TempDir::new("address_snapshot").unwrap();
+        let path = snapshot_path(&dir);
+        let saved = store();
+        saved.register_seed(vec![reg(A, "C", -1), reg(B, "C", 150), reg(C, "D", 400)]);
+        // Above the snapshot block, or still pending: neither is a row the
+        // database holds at block 300.
+        saved
+            .register_batch(vec![reg(
+                "0x00000000000000000000000000000000000000dd",
+                "D",
+                250,
+            )])
+            .unwrap();
+        assert_eq!(
+            (
+                saved
+                    .save_snapshot(path.clone(), SCOPE.to_string(), 300)
+                    .unwrap(),
+                address_snapshot_block(path.clone(), SCOPE.to_string())
+            ),
+            (2, Some(300))
+        );
+
+        // Listed in another order, which a snapshot keyed by name survives.
+        let restored = AddressStore::new_evm(false, contracts(&[("D", None), ("C", Some(100))]));
+        let summary = restored.load_snapshot(path, SCOPE.to_string()).unwrap();
+        assert_eq!(
+            (
+                summary.block,
+                summary
+                    .contracts
+                    .iter()
+                    .map(|c| (c.contract_name.as_str(), c.address_count, c.dynamic_count))
+                    .collect::<Vec<_>>(),
+                set_entries(&restored.make_set("C".to_string(), None)),
+                restored.pending_count(),
+            ),
+            (
+                300,
+                vec![("C", 2, 1)],
+                vec![
+                    (A.to_string(), "C".to_string(), -1, 100),
+                    (B.to_string(), "C".to_string(), 150, 150),
+                ],
+                0,
+            )
+        );
+        // Restored addresses conflict like seeded ones.
+        assert_eq!(
+            kinds(&restored.register_seed(vec![reg(A, "D", 500)])),
+            vec!["conflict"]
+        );
+    }
+
+    #[test]
+    fn snapshot_keeps_only_the_ends_the_database_holds() {
+        let dir = tempdir::TempDir::new("address_snapshot").unwrap();
+        let path = snapshot_path(&dir);
+        let saved = ttl_store();
+        saved.register_seed(vec![reg(A, "D", 100), reg(B, "C", 200), reg(C, "D", 100)]);
+        saved
+            .deregister_batch(vec![
+                dereg(A, "D", 150),
+                dereg(B, "C", 240),
+                dereg(C, "D", 160),
+            ])
+            .unwrap();
+        // A's end is written at 150; B's and C's are still pending at 300.
+        saved.drain_for_write(150, vec![150]).unwrap();
+        saved
+            .save_snapshot(path.clone(), SCOPE.to_string(), 300)
+            .unwrap();
+
+        let restored = ttl_store();
+        restored.load_snapshot(path, SCOPE.to_string()).unwrap();
+        let ends = |store: &AddressStore| {
+            [A, B, C].map(|address| store.get(address.to_string()).unwrap().end_block)
+        };
+        assert_eq!(ends(&restored), [Some(150), Some(250), None]);
+        // The resume seeds the rows whose end moved after the snapshot again,
+        // and the database's end wins.
+        let verdicts = restored.register_seed(vec![
+            AddressRegistration {
+                end_block: Some(240),
+                ..reg(B, "C", 200)
+            },
+            AddressRegistration {
+                end_block: Some(160),
+                ..reg(C, "D", 100)
+            },
+        ]);
+        assert_eq!(
+            (kinds(&verdicts), ends(&restored)),
+            (
+                vec!["duplicate", "duplicate"],
+                [Some(150), Some(240), Some(160)]
+            )
+        );
+    }
+
+    #[test]
+    fn snapshot_is_rejected_when_the_store_cant_hold_it() {
+        let dir = tempdir::TempDir::new("address_snapshot").unwrap();
+        let path = snapshot_path(&dir);
+        let saved = store();
+        saved.register_seed(vec![reg(A, "C", 120)]);
+        saved
+            .save_snapshot(path.clone(), SCOPE.to_string(), 200)
+            .unwrap();
+
+        let dropped = AddressStore::new_evm(false, contracts(&[("D", None)]));
+        let other_ecosystem = AddressStore::new_fuel(contracts(&[("C", Some(100))]));
+        let not_empty = store();
+        not_empty.register_seed(vec![reg(B, "D", 10)]);
+        let errors = [dropped, other_ecosystem, not_empty]
+            .iter()
+            .map(|store| {
+                store
+                    .load_snapshot(path.clone(), SCOPE.to_string())
+                    .is_err()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec![true; 3]);
+        assert_eq!(
+            address_snapshot_block(
+                dir.path()
+                    .join("missing.bin")
+                    .to_string_lossy()
+                    .into_owned(),
+                SCOPE.to_string()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn snapshot_of_another_scope_is_ignored() {
+        let dir = tempdir::TempDir::new("address_snapshot").unwrap();
+        let path = snapshot_path(&dir);
+        let saved = store();
+        saved.register_seed(vec![reg(A, "C", 120)]);
+        saved
+            .save_snapshot(path.clone(), SCOPE.to_string(), 200)
+            .unwrap();
+
+        // Another schema indexing from the same directory shares the path.
+        let restored = store();
+        assert_eq!(
+            (
+                address_snapshot_block(path.clone(), "other".to_string()),
+                restored
+                    .load_snapshot(path, "other".to_string())
+                    .err()
+                    .map(|err| err.reason.contains("another schema or config")),
+                restored.size(),
+            ),
+            (None, Some(true), 0)
+        );
+    }
 }
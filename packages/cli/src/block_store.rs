@@ -12,15 +12,18 @@ use anyhow::{Context, Result};
 use hypersync_client::format::{Hash, Hex, Quantity};
 use hypersync_client::simple_types;
 use hypersync_client_solana::simple_types as solana_simple;
+use napi::bindgen_prelude::BigInt;
 use napi_derive::napi;
 use strum::VariantArray;
 
 use crate::evm_hypersync_source::map_err;
 use crate::evm_hypersync_source::types::{encode_address, map_bigint, map_i64};
-use crate::field_columns::{build_columns, bytes, field_names, Column, Columns, Ecosystem};
+use crate::field_columns::{
+    build_columns, bytes, field_names, masks_from_js, Column, Columns, Ecosystem,
+};
 use crate::field_table::{
     bytes_cells, fixed_from, hash_list_cells, hash_list_from, hex_full, hex_quantity, i64_cells,
-    i64_from, str_cells, str_from, u64_cells, u64_from, var_from, AnyCol, Table,
+    i64_from, str_cells, str_from, u64_cells, u64_from, var_from, AnyCol, FieldMask, Table,
 };
 
 /// EVM block field codes shared with ReScript by ordinal value. The order is the
@@ -199,11 +202,10 @@ fn decode_evm_block_field(
     field: EvmBlockField,
     scratch: &[Option<AnyCol>],
     block_numbers: &[i64],
-    masks: &[u64],
+    masks: &[FieldMask],
     should_checksum: bool,
 ) -> Result<Column> {
     use EvmBlockField::*;
-    let bit = 1u64 << (field as u32);
     let col = scratch[field as usize].as_ref();
     let len = block_numbers.len();
     Ok(match field {
@@ -213,7 +215,7 @@ fn decode_evm_block_field(
             block_numbers
                 .iter()
                 .zip(masks)
-                .map(|(&n, &m)| (m & bit != 0).then_some(n))
+                .map(|(&n, &m)| m.has(field as usize).then_some(n))
                 .collect(),
         ),
         Timestamp => Column::I64(bytes_cells(col, len, |b| map_i64(&Some(b)))?),
@@ -248,7 +250,7 @@ fn decode_evm_block_field(
 fn decode_evm_block_columns(
     scratch: &[Option<AnyCol>],
     block_numbers: &[i64],
-    masks: &[u64],
+    masks: &[FieldMask],
     should_checksum: bool,
 ) -> Result<Columns> {
     build_columns(
@@ -292,10 +294,9 @@ fn decode_svm_block_field(
     field: SvmBlockField,
     scratch: &[Option<AnyCol>],
     block_numbers: &[i64],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Result<Column> {
     use SvmBlockField::*;
-    let bit = 1u64 << (field as u32);
     let col = scratch[field as usize].as_ref();
     let len = block_numbers.len();
     Ok(match field {
@@ -305,7 +306,7 @@ fn decode_svm_block_field(
             block_numbers
                 .iter()
                 .zip(masks)
-                .map(|(&n, &m)| (m & bit != 0).then_some(n))
+                .map(|(&n, &m)| m.has(field as usize).then_some(n))
                 .collect(),
         ),
         Time => Column::I64(i64_cells(col, len)),
@@ -322,7 +323,7 @@ fn decode_svm_block_field(
 fn decode_svm_block_columns(
     scratch: &[Option<AnyCol>],
     block_numbers: &[i64],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Result<Columns> {
     build_columns(
         SvmBlockField::VARIANTS,
@@ -357,10 +358,9 @@ fn decode_fuel_block_field(
     field: FuelBlockField,
     scratch: &[Option<AnyCol>],
     block_numbers: &[i64],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Result<Column> {
     use FuelBlockField::*;
-    let bit = 1u64 << (field as u32);
     let col = scratch[field as usize].as_ref();
     let len = block_numbers.len();
     Ok(match field {
@@ -370,7 +370,7 @@ fn decode_fuel_block_field(
             block_numbers
                 .iter()
                 .zip(masks)
-                .map(|(&n, &m)| (m & bit != 0).then_some(n))
+                .map(|(&n, &m)| m.has(field as usize).then_some(n))
                 .collect(),
         ),
         Id => Column::Str(bytes_cells(col, len, |b| Ok(Some(hex_full(b))))?),
@@ -381,7 +381,7 @@ fn decode_fuel_block_field(
 fn decode_fuel_block_columns(
     scratch: &[Option<AnyCol>],
     block_numbers: &[i64],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Result<Columns> {
     build_columns(
         FuelBlockField::VARIANTS,
@@ -395,12 +395,14 @@ fn decode_fuel_block_columns(
 
 /// A sparse EVM block from JS for `fromJsEvm`. Only the reorg-relevant fields
 /// the JS callers actually send (RPC/simulate observations, seeded checkpoints):
-/// the key, its hash, and the timestamp. `hash` is a full 32-byte block hash.
+/// the key, its hash and parent hash, and the timestamp. Both hashes are full
+/// 32-byte block hashes.
 #[napi(object)]
 pub struct EvmBlockInput {
     pub number: i64,
     pub timestamp: Option<i64>,
     pub hash: Option<String>,
+    pub parent_hash: Option<String>,
 }
 
 /// A sparse SVM block from JS for `fromJsSvm`.
@@ -438,6 +440,7 @@ fn evm_input_to_simple(b: EvmBlockInput) -> Result<simple_types::Block> {
     Ok(simple_types::Block {
         number: Some(u64::try_from(b.number).context("block.number negative")?),
         hash: b.hash.as_deref().map(evm_input_hash).transpose()?,
+        parent_hash: b.parent_hash.as_deref().map(evm_input_hash).transpose()?,
         timestamp: b
             .timestamp
             .map(|t| Quantity::try_from(t).context("block.timestamp negative"))
@@ -568,8 +571,12 @@ impl BlockStore {
 
     /// Move every row from `page` into the persistent store, comparing hashes
     /// on the way: the lowest page block at or above `from_block` whose hash
-    /// differs from the stored one is reported as a reorg. A block without a
-    /// hash on either side is skipped. The source manager validates that `page`
+    /// differs from the stored one is reported as a reorg. So is a parent link
+    /// broken across the two stores — a page block whose parent hash isn't the
+    /// stored parent's hash, or the reverse — reported at the parent, so a
+    /// reorg is caught on the first page that crosses it even when no block
+    /// number was fetched twice. A block without a hash on either side is
+    /// skipped. The source manager validates that `page`
     /// has no response-internal conflict before calling this method. On a
     /// mismatch nothing is merged — the stored (scanned) hashes stay intact for
     /// rollback — unless `report_only` is set (detect-only mode), which merges
@@ -597,7 +604,10 @@ impl BlockStore {
         let mut dst = self.inner.lock().unwrap();
         let mut src = page.inner.lock().unwrap();
         let from = u64::try_from(from_block).unwrap_or(0);
-        let cross = self.first_cross_mismatch(&dst.table, &src.table, from);
+        let mut cross = self.first_cross_mismatch(&dst.table, &src.table, from);
+        if let Some(link) = self.first_broken_link(&dst.table, &src.table, from) {
+            record_conflict(&mut cross, link);
+        }
         debug_assert!(
             src.page.conflict.is_none(),
             "response stores must be validated before persistent merge"
@@ -608,14 +618,40 @@ impl BlockStore {
             // must not become persistent chain data, where the cursor coverage
             // could outlive the queried fork.
             src.page = ResponsePage::default();
+            dst.table
+                .spill_cold_rows()
+                .context("spilling blocks to disk")
+                .map_err(map_err)?;
         }
         Ok(cross)
     }
 
+    /// Page the persistent store's cold blocks out to the file at `path` once
+    /// they take more than `budget_bytes` in memory; `materialize` reads them
+    /// back. The block hash and parent link always stay in memory for reorg
+    /// checks. The file is recreated empty and removed when the store is
+    /// dropped.
+    #[napi]
+    pub fn enable_spill(&self, path: String, budget_bytes: i64) -> napi::Result<()> {
+        let reorg_fields = self.reorg_fields();
+        self.inner
+            .lock()
+            .unwrap()
+            .table
+            .enable_spill(
+                path.into(),
+                usize::try_from(budget_bytes).unwrap_or(0),
+                reorg_fields,
+            )
+            .context("opening the block spill file")
+            .map_err(map_err)
+    }
+
     /// Append a backend page to a logical response store. Unlike `merge`, this
-    /// always appends rows: an internal conflict invalidates the complete
-    /// response, so the caller will discard the aggregate and retry it. The
-    /// lowest conflict is retained only for diagnostics.
+    /// always appends rows: an internal conflict — a hash or a parent link the
+    /// two pages disagree on — invalidates the complete response, so the
+    /// caller will discard the aggregate and retry it. The lowest conflict is
+    /// retained only for diagnostics.
     #[napi]
     pub fn append_page(&self, page: &BlockStore) {
         if std::ptr::eq(self, page) {
@@ -632,6 +668,9 @@ impl BlockStore {
         if let Some(cross) = self.first_cross_mismatch(&dst.table, &src.table, 0) {
             record_conflict(&mut dst.page.conflict, cross);
         }
+        if let Some(link) = self.first_broken_link(&dst.table, &src.table, 0) {
+            record_conflict(&mut dst.page.conflict, link);
+        }
         if let Some(conflict) = src.page.conflict.take() {
             record_conflict(&mut dst.page.conflict, conflict);
         }
@@ -766,16 +805,16 @@ impl BlockStore {
 
     /// Bulk-materialise blocks in columnar form, one row per `block_numbers[i]`
     /// key, decoding only the fields whose bit is set in that row's own
-    /// `masks[i]`. Each mask is a JS number (`f64`) carrying a selection bitmask
-    /// over field codes. The lock is held only to gather the requested cells;
-    /// decoding runs after it is released, off the JS thread via
-    /// `block_in_place`. Missing keys yield an empty object. Result is aligned
-    /// with input.
+    /// `masks[i]`. Each mask is a JS `BigInt` carrying a selection bitmask over
+    /// field codes (see `masks_from_js`). The lock is held only to gather the
+    /// requested cells; decoding runs after it is released, off the JS thread
+    /// via `block_in_place`. Missing keys yield an empty object. Result is
+    /// aligned with input.
     #[napi(ts_return_type = "Promise<object[]>")]
     pub async fn materialize(
         &self,
         block_numbers: Vec<i64>,
-        masks: Vec<f64>,
+        masks: Vec<BigInt>,
     ) -> napi::Result<Columns> {
         // The two columns are zipped row-wise; a length mismatch would silently
         // truncate and misalign the result with the caller's items.
@@ -786,25 +825,25 @@ impl BlockStore {
                 masks.len()
             )));
         }
-        let masks: Vec<u64> = masks.iter().map(|&m| m as u64).collect();
+        let masks = masks_from_js(&masks)?;
 
         match self.ecosystem {
             Ecosystem::Evm { should_checksum } => {
-                let scratch = self.gather(&block_numbers, &masks);
+                let scratch = self.gather(&block_numbers, &masks).map_err(map_err)?;
                 tokio::task::block_in_place(|| {
                     decode_evm_block_columns(&scratch, &block_numbers, &masks, should_checksum)
                 })
                 .map_err(map_err)
             }
             Ecosystem::Svm => {
-                let scratch = self.gather(&block_numbers, &masks);
+                let scratch = self.gather(&block_numbers, &masks).map_err(map_err)?;
                 tokio::task::block_in_place(|| {
                     decode_svm_block_columns(&scratch, &block_numbers, &masks)
                 })
                 .map_err(map_err)
             }
             Ecosystem::Fuel => {
-                let scratch = self.gather(&block_numbers, &masks);
+                let scratch = self.gather(&block_numbers, &masks).map_err(map_err)?;
                 tokio::task::block_in_place(|| {
                     decode_fuel_block_columns(&scratch, &block_numbers, &masks)
                 })
@@ -814,16 +853,16 @@ impl BlockStore {
     }
 
     /// Drop blocks at or below `up_to_block` (already processed), except that
-    /// blocks at or above `keep_hashes_from` keep their hash — still needed for
-    /// reorg detection until they leave the reorg threshold.
+    /// blocks at or above `keep_hashes_from` keep their hash and parent link —
+    /// still needed for reorg detection until they leave the reorg threshold.
     #[napi]
     pub fn prune(&self, up_to_block: i64, keep_hashes_from: i64) {
         if let Ok(up_to) = u64::try_from(up_to_block) {
             let keep_from = u64::try_from(keep_hashes_from).unwrap_or(0);
-            self.inner.lock().unwrap().table.prune_keeping_field(
+            self.inner.lock().unwrap().table.prune_keeping_fields(
                 up_to,
                 keep_from,
-                self.hash_field(),
+                FieldMask::of(self.reorg_fields().iter().copied()),
             );
         }
     }
@@ -892,6 +931,88 @@ impl BlockStore {
         }
     }
 
+    /// The fields reorg detection reads: the hash, plus the parent link where
+    /// the ecosystem's blocks carry one. Pinned in memory and kept by `prune`.
+    fn reorg_fields(&self) -> &'static [usize] {
+        match self.ecosystem {
+            Ecosystem::Evm { .. } => &[
+                EvmBlockField::Hash as usize,
+                EvmBlockField::ParentHash as usize,
+            ],
+            Ecosystem::Svm => &[
+                SvmBlockField::Hash as usize,
+                SvmBlockField::ParentHash as usize,
+                SvmBlockField::ParentSlot as usize,
+            ],
+            Ecosystem::Fuel => &[FuelBlockField::Id as usize],
+        }
+    }
+
+    /// The block `key`'s row links to: its parent's number and the parent hash
+    /// it carries. EVM parents are always the previous number; SVM slots can be
+    /// skipped, so the row names its parent slot. Fuel blocks carry no link.
+    fn parent_of<'a>(&self, table: &'a Table<u64>, key: u64) -> Option<(u64, &'a [u8])> {
+        match self.ecosystem {
+            Ecosystem::Evm { .. } => Some((
+                key.checked_sub(1)?,
+                table.field_bytes(&key, EvmBlockField::ParentHash as usize)?,
+            )),
+            Ecosystem::Svm => Some((
+                table.field_u64(&key, SvmBlockField::ParentSlot as usize)?,
+                table.field_bytes(&key, SvmBlockField::ParentHash as usize)?,
+            )),
+            Ecosystem::Fuel => None,
+        }
+    }
+
+    /// `child`'s row in `children` against its parent's hash in `parents`:
+    /// the parent's number, its hash and the parent hash `child` carries, when
+    /// the two disagree. Nothing when either side is missing.
+    fn broken_link<'a>(
+        &self,
+        parents: &'a Table<u64>,
+        children: &'a Table<u64>,
+        child: u64,
+    ) -> Option<(u64, &'a [u8], &'a [u8])> {
+        let (parent, claimed) = self.parent_of(children, child)?;
+        let hash = parents.field_bytes(&parent, self.hash_field())?;
+        (hash != claimed).then_some((parent, hash, claimed))
+    }
+
+    /// The lowest parent at or above `from` whose link between the two tables
+    /// is broken: a `src` block naming a parent `dst` holds another hash for,
+    /// or a `dst` block whose parent `src` brings with another hash. Links
+    /// within one table are checked as its rows are inserted.
+    fn first_broken_link(
+        &self,
+        dst: &Table<u64>,
+        src: &Table<u64>,
+        from: u64,
+    ) -> Option<HashMismatch> {
+        let mut lowest = None;
+        for key in src.keys_with_field(from, u64::MAX, self.hash_field()) {
+            let received_child = self.broken_link(dst, src, key);
+            let stored_child = dst
+                .key_after(&key)
+                .and_then(|&child| self.broken_link(src, dst, child))
+                .map(|(parent, received, stored)| (parent, stored, received));
+            for (parent, stored, received) in [received_child, stored_child].into_iter().flatten() {
+                if parent >= from {
+                    record_conflict(&mut lowest, self.hash_mismatch(parent, stored, received));
+                }
+            }
+        }
+        lowest
+    }
+
+    fn hash_mismatch(&self, key: u64, stored: &[u8], received: &[u8]) -> HashMismatch {
+        HashMismatch {
+            block_number: key as i64,
+            stored_hash: self.hash_display(stored),
+            received_hash: self.hash_display(received),
+        }
+    }
+
     /// A stored hash cell in the shape JS knows it by: hex for the byte-backed
     /// EVM/Fuel hashes, the raw base58 string for SVM.
     /// The lowest block at or above `from` that both tables carry a hash for,
@@ -917,7 +1038,7 @@ impl BlockStore {
         }
     }
 
-    fn gather(&self, block_numbers: &[i64], masks: &[u64]) -> Vec<Option<AnyCol>> {
+    fn gather(&self, block_numbers: &[i64], masks: &[FieldMask]) -> Result<Vec<Option<AnyCol>>> {
         let keys: Vec<Option<u64>> = block_numbers
             .iter()
             .map(|&n| u64::try_from(n).ok())
@@ -927,6 +1048,7 @@ impl BlockStore {
             .unwrap()
             .table
             .gather_scratch(&keys, masks)
+            .context("reading spilled blocks")
     }
 
     /// Merge one response's EVM blocks into the table (called by the HyperSync
@@ -947,14 +1069,30 @@ impl BlockStore {
     }
 
     /// Merge a batch, first recording any hash conflict it introduces (against
-    /// the table or within the batch itself), keeping the lowest block number.
+    /// the table or within the batch itself), then any parent link it breaks,
+    /// keeping the lowest block number.
     fn insert_watching_hash(&self, keys: Vec<u64>, cols: Vec<Option<AnyCol>>) {
         let field = self.hash_field();
         let mut inner = self.inner.lock().unwrap();
         let conflict = inner
             .table
             .detect_field_conflict(&keys, cols[field].as_ref(), field);
-        inner.table.merge_batch(keys, cols);
+        inner.table.merge_batch(keys.clone(), cols);
+        let Inner { table, page } = &mut *inner;
+        for &key in &keys {
+            let links = [
+                self.broken_link(table, table, key),
+                table
+                    .key_after(&key)
+                    .and_then(|&child| self.broken_link(table, table, child)),
+            ];
+            for (parent, stored, received) in links.into_iter().flatten() {
+                record_conflict(
+                    &mut page.conflict,
+                    self.hash_mismatch(parent, stored, received),
+                );
+            }
+        }
         if let Some((key, stored, received)) = conflict {
             record_conflict(
                 &mut inner.page.conflict,
@@ -1110,6 +1248,7 @@ pub fn fuel_block_field_names() -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field_columns::js_masks;
     use hypersync_client::format::{Hash, Quantity};
 
     fn raw_evm_block(number: u64) -> simple_types::Block {
@@ -1151,9 +1290,9 @@ mod tests {
         block.gas_used = Some(Quantity::from(99u64));
         store.insert_evm_blocks(vec![block]);
 
-        let mask = bit(EvmBlockField::GasUsed) as f64;
+        let mask = bit(EvmBlockField::GasUsed);
         let cols = store
-            .materialize(vec![1], vec![mask])
+            .materialize(vec![1], js_masks(&[mask]))
             .await
             .expect("materialize");
 
@@ -1174,9 +1313,9 @@ mod tests {
         let store = BlockStore::new_evm(false);
         store.insert_evm_blocks(vec![raw_evm_block(3)]);
 
-        let mask = bit(EvmBlockField::Number) as f64;
+        let mask = bit(EvmBlockField::Number);
         let cols = store
-            .materialize(vec![7, 3], vec![mask, mask])
+            .materialize(vec![7, 3], js_masks(&[mask, mask]))
             .await
             .expect("materialize");
         match column(&cols, "number") {
@@ -1199,9 +1338,9 @@ mod tests {
         block2.gas_used = Some(Quantity::from(200u64));
         store.insert_evm_blocks(vec![block1, block2]);
 
-        let mask = bit(EvmBlockField::GasUsed) as f64;
+        let mask = bit(EvmBlockField::GasUsed);
         let cols = store
-            .materialize(vec![1, 2], vec![mask, 0.])
+            .materialize(vec![1, 2], js_masks(&[mask, 0]))
             .await
             .expect("materialize");
         match column(&cols, "gasUsed") {
@@ -1221,12 +1360,12 @@ mod tests {
 
         // A production-shaped mask: the config-extended trio plus a selected
         // extra field, all decoded from the one stored row.
-        let mask = (bit(EvmBlockField::Number)
+        let mask = bit(EvmBlockField::Number)
             | bit(EvmBlockField::Timestamp)
             | bit(EvmBlockField::Hash)
-            | bit(EvmBlockField::GasUsed)) as f64;
+            | bit(EvmBlockField::GasUsed);
         let cols = store
-            .materialize(vec![10], vec![mask])
+            .materialize(vec![10], js_masks(&[mask]))
             .await
             .expect("materialize");
         let summary = (
@@ -1268,9 +1407,9 @@ mod tests {
         with_gas.gas_used = Some(Quantity::from(42u64));
         store.insert_evm_blocks(vec![with_gas]);
 
-        let mask = (bit(EvmBlockField::Hash) | bit(EvmBlockField::GasUsed)) as f64;
+        let mask = bit(EvmBlockField::Hash) | bit(EvmBlockField::GasUsed);
         let cols = store
-            .materialize(vec![20], vec![mask])
+            .materialize(vec![20], js_masks(&[mask]))
             .await
             .expect("materialize");
         let summary = (
@@ -1293,12 +1432,12 @@ mod tests {
         block.block_height = Some(777);
         store.insert_svm_blocks(vec![block]);
 
-        let mask = ((1u64 << (SvmBlockField::Slot as u32))
+        let mask = (1u64 << (SvmBlockField::Slot as u32))
             | (1u64 << (SvmBlockField::Hash as u32))
             | (1u64 << (SvmBlockField::Time as u32))
-            | (1u64 << (SvmBlockField::Height as u32))) as f64;
+            | (1u64 << (SvmBlockField::Height as u32));
         let cols = store
-            .materialize(vec![9], vec![mask])
+            .materialize(vec![9], js_masks(&[mask]))
             .await
             .expect("materialize");
         let summary = (
@@ -1345,15 +1484,15 @@ mod tests {
             .collect();
         store.insert_evm_blocks(blocks);
 
-        let mask = bit(EvmBlockField::Timestamp) as f64;
+        let mask = bit(EvmBlockField::Timestamp);
         store.prune(10, 11);
         let after_prune = store
-            .materialize(vec![10, 20, 30], vec![mask, mask, mask])
+            .materialize(vec![10, 20, 30], js_masks(&[mask, mask, mask]))
             .await
             .expect("materialize");
         store.rollback(20);
         let after_rollback = store
-            .materialize(vec![10, 20, 30], vec![mask, mask, mask])
+            .materialize(vec![10, 20, 30], js_masks(&[mask, mask, mask]))
             .await
             .expect("materialize");
 
@@ -1387,9 +1526,9 @@ mod tests {
         page2.insert_evm_blocks(vec![second]);
         persistent.merge(&page2, 0, false).unwrap();
 
-        let mask = bit(EvmBlockField::Timestamp) as f64;
+        let mask = bit(EvmBlockField::Timestamp);
         let cols = persistent
-            .materialize(vec![20], vec![mask])
+            .materialize(vec![20], js_masks(&[mask]))
             .await
             .expect("materialize");
         match column(&cols, "timestamp") {
@@ -1603,9 +1742,9 @@ mod tests {
         );
 
         // The kept rows are hash-only: timestamp no longer materialises.
-        let mask = (bit(EvmBlockField::Timestamp) | bit(EvmBlockField::Hash)) as f64;
+        let mask = bit(EvmBlockField::Timestamp) | bit(EvmBlockField::Hash);
         let cols = store
-            .materialize(vec![20], vec![mask])
+            .materialize(vec![20], js_masks(&[mask]))
             .await
             .expect("materialize");
         assert_eq!(
@@ -1632,6 +1771,7 @@ mod tests {
             vec![EvmBlockInput {
                 number: 10,
                 hash: Some("0x0b64".to_string()),
+                parent_hash: None,
                 timestamp: None,
             }],
             false,
@@ -1812,6 +1952,106 @@ mod tests {
         );
     }
 
+    fn linked_evm_block(number: u64, byte: u8, parent_byte: u8) -> simple_types::Block {
+        let mut b = hashed_evm_block(number, byte);
+        b.parent_hash = Some(Hash::from([parent_byte; 32]));
+        b
+    }
+
+    #[test]
+    fn merge_reports_a_broken_parent_link_at_the_parent() {
+        // The page never re-fetches block 10, but block 11 names another
+        // parent: the reorg shows on this page, not a later overlapping one.
+        let persistent = evm_page(vec![hashed_evm_block(10, 0x10)]);
+        let page = evm_page(vec![linked_evm_block(11, 0x11, 0xaa)]);
+        let mismatch = persistent
+            .merge(&page, 0, false)
+            .unwrap()
+            .expect("mismatch");
+        assert_eq!(
+            (
+                mismatch.block_number,
+                mismatch.stored_hash,
+                mismatch.received_hash
+            ),
+            (
+                10,
+                format!("0x{}", "10".repeat(32)),
+                format!("0x{}", "aa".repeat(32))
+            )
+        );
+        assert_eq!(persistent.get_hash(11), None);
+
+        // A link whose parent is below the reorg threshold isn't compared.
+        assert!(persistent.merge(&page, 11, false).unwrap().is_none());
+    }
+
+    #[test]
+    fn merge_checks_a_stored_childs_link_after_prune() {
+        // Block 11 is processed and pruned to its reorg fields; a page that
+        // brings its parent with another hash still breaks the kept link.
+        let persistent = evm_page(vec![linked_evm_block(11, 0x11, 0x10)]);
+        persistent.prune(11, 0);
+        let page = evm_page(vec![hashed_evm_block(10, 0xaa)]);
+        let mismatch = persistent
+            .merge(&page, 0, false)
+            .unwrap()
+            .expect("mismatch");
+        assert_eq!(
+            (
+                mismatch.block_number,
+                mismatch.stored_hash,
+                mismatch.received_hash
+            ),
+            (
+                10,
+                format!("0x{}", "10".repeat(32)),
+                format!("0x{}", "aa".repeat(32))
+            )
+        );
+
+        // A matching parent merges cleanly.
+        let page = evm_page(vec![hashed_evm_block(10, 0x10)]);
+        assert!(persistent.merge(&page, 0, false).unwrap().is_none());
+    }
+
+    #[test]
+    fn broken_links_within_a_response_are_conflicts() {
+        let page = evm_page(vec![
+            hashed_evm_block(10, 0x10),
+            linked_evm_block(11, 0x11, 0x10),
+            linked_evm_block(12, 0x12, 0xbb),
+        ]);
+        let conflict = page.response_conflict().expect("in-page conflict");
+        assert_eq!(conflict.block_number, 11);
+
+        let aggregate = evm_page(vec![hashed_evm_block(20, 0x20)]);
+        aggregate.append_page(&evm_page(vec![linked_evm_block(21, 0x21, 0xcc)]));
+        let conflict = aggregate.response_conflict().expect("cross-page conflict");
+        assert_eq!(conflict.block_number, 20);
+    }
+
+    #[test]
+    fn svm_links_follow_the_parent_slot_across_skipped_slots() {
+        let persistent = BlockStore::new_svm();
+        persistent.insert_svm_blocks(vec![raw_svm_block(10)]);
+        // Slot 11 was skipped: slot 12's parent is slot 10.
+        let page = BlockStore::new_svm();
+        page.insert_svm_blocks(vec![solana_simple::Block {
+            parent_slot: Some(10),
+            parent_blockhash: Some("11111111111111111111111111111111".parse().unwrap()),
+            ..raw_svm_block(12)
+        }]);
+        let mismatch = persistent
+            .merge(&page, 0, false)
+            .unwrap()
+            .expect("mismatch");
+        assert_eq!(
+            (mismatch.block_number, mismatch.stored_hash),
+            (10, BLOCK_HASH.to_string())
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn fuel_store_materializes_and_detects() {
         let page = BlockStore::new_fuel();
@@ -1824,11 +2064,11 @@ mod tests {
         let persistent = BlockStore::new_fuel();
         assert!(persistent.merge(&page, 0, false).unwrap().is_none());
 
-        let mask = ((1u64 << (FuelBlockField::Height as u32))
+        let mask = (1u64 << (FuelBlockField::Height as u32))
             | (1u64 << (FuelBlockField::Time as u32))
-            | (1u64 << (FuelBlockField::Id as u32))) as f64;
+            | (1u64 << (FuelBlockField::Id as u32));
         let cols = persistent
-            .materialize(vec![5], vec![mask])
+            .materialize(vec![5], js_masks(&[mask]))
             .await
             .expect("materialize");
         let summary = (
@@ -1885,23 +2125,24 @@ mod tests {
                     number: 7,
                     timestamp: Some(999),
                     hash: Some(format!("0x{}", "ab".repeat(32))),
+                    parent_hash: None,
                 },
                 // A hash-only guard row (no timestamp).
                 EvmBlockInput {
                     number: 8,
                     timestamp: None,
                     hash: Some(format!("0x{}", "cd".repeat(32))),
+                    parent_hash: Some(format!("0x{}", "ab".repeat(32))),
                 },
             ],
             false,
         )
         .expect("fromJs");
 
-        let mask = (bit(EvmBlockField::Number)
-            | bit(EvmBlockField::Timestamp)
-            | bit(EvmBlockField::Hash)) as f64;
+        let mask =
+            bit(EvmBlockField::Number) | bit(EvmBlockField::Timestamp) | bit(EvmBlockField::Hash);
         let cols = store
-            .materialize(vec![7], vec![mask])
+            .materialize(vec![7], js_masks(&[mask]))
             .await
             .expect("materialize");
         let summary = (
@@ -1923,6 +2164,8 @@ mod tests {
                 Some(format!("0x{}", "cd".repeat(32)))
             )
         );
+        // Block 8's parent hash is block 7's hash: an intact link.
+        assert!(store.response_conflict().is_none());
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -1939,9 +2182,9 @@ mod tests {
 
         // The head block materialises with its timestamp; the seam block below
         // the range carries only the parent hash the guard reports.
-        let mask = (bit(EvmBlockField::Timestamp) | bit(EvmBlockField::Hash)) as f64;
+        let mask = bit(EvmBlockField::Timestamp) | bit(EvmBlockField::Hash);
         let cols = page
-            .materialize(vec![20, 10], vec![mask, mask])
+            .materialize(vec![20, 10], js_masks(&[mask, mask]))
             .await
             .expect("materialize");
         assert_eq!(
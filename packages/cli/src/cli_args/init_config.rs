@@ -61,7 +61,10 @@ pub mod evm {
                     .map(|event| EventConfig {
                         event: EvmAbi::event_signature_from_abi_event(&event),
                         name: None,
+                        type_: None,
                         field_selection: None,
+                        where_: None,
+                        register: None,
                     })
                     .collect();
 
@@ -74,6 +77,8 @@ pub mod evm {
                             abi_file_path: None,
                             handler: None,
                             events: events.clone(),
+                            functions: None,
+                            factory: None,
                         },
                     };
 
@@ -94,6 +99,8 @@ pub mod evm {
                         abi_file_path: None,
                         handler: None,
                         events,
+                        functions: None,
+                        factory: None,
                     })
                 };
 
@@ -134,6 +141,7 @@ pub mod evm {
                                 start_block: selected_chain.network.get_start_block(),
                                 end_block,
                                 max_reorg_depth: None,
+                                finality: None,
                                 block_lag: None,
                                 contracts: Some(Vec::new()),
                             }
@@ -144,6 +152,7 @@ pub mod evm {
                         address,
                         config: config.clone(),
                         start_block: None,
+                        ttl_blocks: None,
                     };
 
                     chain.contracts.get_or_insert_with(Vec::new).push(contract);
@@ -296,6 +305,7 @@ pub mod fuel {
                                         events: selected_contract.selected_events.clone(),
                                     }),
                                     start_block: None,
+                                    ttl_blocks: None,
                                 })
                                 .collect(),
                         ),
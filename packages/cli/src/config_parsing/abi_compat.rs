@@ -5,7 +5,10 @@
 //! ethers-based implementation.
 
 use alloy_dyn_abi::DynSolType;
-use alloy_json_abi::{Event as AlloyEvent, EventParam as AlloyEventParam, Param as AlloyParam};
+use alloy_json_abi::{
+    Event as AlloyEvent, EventParam as AlloyEventParam, Function as AlloyFunction,
+    Param as AlloyParam,
+};
 use anyhow::{anyhow, Context, Result};
 use std::str::FromStr;
 
@@ -202,14 +205,25 @@ pub fn parse_event_signature_to_alloy(sig: &str) -> Result<AlloyEvent> {
     sig_parser::parse(sig)
 }
 
-/// Human-readable event signature parser.
+/// Parse a human-readable function signature (`function transfer(address to,
+/// uint256 amount)`) into an `AlloyFunction`. Visibility and mutability
+/// keywords and a `returns (...)` clause are accepted; outputs are kept, but
+/// only the inputs matter for indexing calls.
+pub fn parse_function_signature_to_alloy(sig: &str) -> Result<AlloyFunction> {
+    sig_parser::parse_function(sig)
+}
+
+/// Human-readable event and function signature parser.
 ///
 /// Grammar: `[event] Name(param,*)[ anonymous]` where each param is
 /// `type [indexed] [name]` and `type` is either a leaf identifier, a tuple
 /// `(component,*)`, or any of those followed by array suffixes.
 /// Tuple components allow names; top-level params allow `indexed`.
+/// Functions are `[function] Name(param,*)[ modifier]*[ returns (param,*)]`,
+/// with no `indexed` params.
 mod sig_parser {
-    use super::{AlloyEvent, AlloyEventParam, AlloyParam};
+    use super::{AlloyEvent, AlloyEventParam, AlloyFunction, AlloyParam};
+    use alloy_json_abi::StateMutability;
     use anyhow::{anyhow, bail, Context, Result};
 
     pub fn parse(sig: &str) -> Result<AlloyEvent> {
@@ -256,6 +270,72 @@ mod sig_parser {
         })
     }
 
+    pub fn parse_function(sig: &str) -> Result<AlloyFunction> {
+        let mut p = Cursor::new(sig);
+        p.skip_ws();
+        if p.try_keyword("function") {
+            p.skip_ws();
+        }
+        let name = p
+            .ident()
+            .ok_or_else(|| anyhow!("expected function name in '{}'", sig))?
+            .to_string();
+        let inputs = parse_param_list(&mut p, sig)?;
+        let mut state_mutability = StateMutability::NonPayable;
+        let mut outputs = Vec::new();
+        loop {
+            p.skip_ws();
+            if p.try_keyword("returns") {
+                outputs = parse_param_list(&mut p, sig)?;
+            } else if p.try_keyword("payable") {
+                state_mutability = StateMutability::Payable;
+            } else if p.try_keyword("view") {
+                state_mutability = StateMutability::View;
+            } else if p.try_keyword("pure") {
+                state_mutability = StateMutability::Pure;
+            } else if !(p.try_keyword("external")
+                || p.try_keyword("public")
+                || p.try_keyword("nonpayable"))
+            {
+                break;
+            }
+        }
+        if !p.eof() {
+            bail!(
+                "unexpected trailing input '{}' in function signature '{}'",
+                p.rest(),
+                sig
+            );
+        }
+        Ok(AlloyFunction {
+            name,
+            inputs,
+            outputs,
+            state_mutability,
+        })
+    }
+
+    fn parse_param_list(p: &mut Cursor, sig: &str) -> Result<Vec<AlloyParam>> {
+        p.skip_ws();
+        p.expect('(', sig)?;
+        let mut params = Vec::new();
+        p.skip_ws();
+        if !p.peek_is(')') {
+            loop {
+                params.push(parse_param(p, sig)?);
+                p.skip_ws();
+                if p.peek_is(',') {
+                    p.bump();
+                    p.skip_ws();
+                    continue;
+                }
+                break;
+            }
+        }
+        p.expect(')', sig)?;
+        Ok(params)
+    }
+
     fn parse_event_param(p: &mut Cursor, sig: &str) -> Result<AlloyEventParam> {
         p.skip_ws();
         let (ty, components) = parse_type(p, sig)?;
@@ -498,6 +578,30 @@ mod tests {
         assert!(!event.inputs[2].indexed);
     }
 
+    #[test]
+    fn test_parse_function_signature() {
+        let function = parse_function_signature_to_alloy(
+            "function transfer(address to, uint256 amount) external returns (bool)",
+        )
+        .unwrap();
+        assert_eq!(
+            (
+                function.signature(),
+                function.selector().to_string(),
+                function.inputs[0].name.as_str(),
+                function.outputs.len(),
+            ),
+            (
+                "transfer(address,uint256)".to_string(),
+                "0xa9059cbb".to_string(),
+                "to",
+                1
+            )
+        );
+        assert!(parse_function_signature_to_alloy("deposit() payable").is_ok());
+        assert!(parse_function_signature_to_alloy("transfer(address to) oops").is_err());
+    }
+
     #[test]
     fn test_parse_event_with_tuple() {
         // Bare signatures have no component names, so tuple fields are unnamed.
@@ -0,0 +1,1000 @@
+//! Anchor IDL loader.
+//!
+//! Reads both Anchor IDL specs into the Borsh schema types the SVM source
+//! decodes with:
+//!
+//! - **0.30+**: instructions, events and accounts ship inline
+//!   `discriminator` arrays; events and accounts describe their fields as a
+//!   struct of the same name under `types`; defined-type refs are
+//!   `{"defined": {"name": T, "generics": [...]}}`; pubkeys are `"pubkey"`.
+//! - **0.29 (legacy)**: discriminators are derived from the item name;
+//!   events inline their `fields` and accounts their `type`; defined-type
+//!   refs are `{"defined": T}`; pubkeys are `"publicKey"`.
+//!
+//! Generic types are monomorphized: every distinct instantiation becomes its
+//! own entry in `defined_types`, named like `Pair<u64, 4>`. PDA seeds,
+//! account relations and docs describe how a client *builds* an instruction
+//! and play no part in decoding, so they're skipped. `COption<T>` has no
+//! Borsh counterpart and is registered as a `tag`/`value` struct that
+//! [`collapse_coptions`] folds back into an optional value after decoding.
+//! Anything the decoder can't represent (256-bit integers, custom
+//! serialization, bytemuck types with padding) is an error naming where it
+//! appears, rather than a type that silently fails to decode at runtime.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Bound;
+
+use anyhow::{anyhow, Context, Result};
+use heck::ToSnakeCase;
+use hypersync_client_solana::decode::{
+    EnumVariant, FieldType, InstructionSchema, NamedAccount, NamedField,
+};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::system_config::SvmIdlLayout;
+
+/// Everything codegen needs from one Anchor IDL.
+#[derive(Debug)]
+pub struct AnchorIdl {
+    /// Instructions in declared order, discriminators filled in.
+    pub instructions: Vec<InstructionSchema>,
+    /// Every named type, generic instantiations included. Legacy account
+    /// structs are registered here too, since legacy IDLs may reference
+    /// them from `types`.
+    pub defined_types: BTreeMap<String, FieldType>,
+    pub events: BTreeMap<String, SvmIdlLayout>,
+    /// Account types keyed by name. Their data is the discriminator followed
+    /// by the struct of the same name.
+    pub accounts: BTreeMap<String, SvmIdlLayout>,
+}
+
+/// Parse an Anchor IDL (0.30+ or legacy 0.29) from its JSON text.
+pub fn parse(raw: &str) -> Result<AnchorIdl> {
+    let root: Value = serde_json::from_str(raw).context("the IDL isn't valid JSON")?;
+    let root = root
+        .as_object()
+        .ok_or_else(|| anyhow!("expected a JSON object at the IDL root"))?;
+
+    let mut declarations = BTreeMap::new();
+    let legacy_accounts = root
+        .get("accounts")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|account| account.get("type").is_some());
+    for declaration in items(root.get("types")).chain(legacy_accounts) {
+        let name = item_name(declaration, "types")?;
+        if declarations.insert(name, declaration).is_some() {
+            return Err(anyhow!("type '{name}' is declared more than once"));
+        }
+    }
+    let mut types = TypeRegistry {
+        declarations,
+        resolved: BTreeMap::new(),
+        pending: HashSet::new(),
+        bytemuck: Vec::new(),
+    };
+    let monomorphic = types
+        .declarations
+        .iter()
+        .filter(|(_, declaration)| generic_params(declaration).is_empty())
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<_>>();
+    for name in monomorphic {
+        types.resolve_defined(&name, Vec::new(), &format!("types.{name}"))?;
+    }
+
+    let instructions = items(root.get("instructions"))
+        .map(|instruction| types.instruction(instruction))
+        .collect::<Result<Vec<_>>>()?;
+
+    let events = types.layouts(root.get("events"), "events", anchor_event_discriminator)?;
+    let accounts = types.layouts(
+        root.get("accounts"),
+        "accounts",
+        anchor_account_discriminator,
+    )?;
+
+    types.check_bytemuck()?;
+    Ok(AnchorIdl {
+        instructions,
+        defined_types: types.resolved,
+        events,
+        accounts,
+    })
+}
+
+/// Anchor's event discriminator: `sha256("event:<Name>")[..8]`, the struct
+/// name taken as declared. Modern IDLs also ship it inline.
+pub fn anchor_event_discriminator(name: &str) -> Vec<u8> {
+    Sha256::digest(format!("event:{name}").as_bytes())[..8].to_vec()
+}
+
+/// Anchor's account discriminator: `sha256("account:<Name>")[..8]`, the
+/// struct name taken as declared. Modern IDLs also ship it inline.
+pub fn anchor_account_discriminator(name: &str) -> Vec<u8> {
+    Sha256::digest(format!("account:{name}").as_bytes())[..8].to_vec()
+}
+
+/// Anchor's legacy instruction discriminator:
+/// `sha256("global:<snake_case name>")[..8]`, snake-cased by `heck` as Anchor
+/// does (`HTTPServer` is `http_server`).
+fn anchor_instruction_discriminator(name: &str) -> Vec<u8> {
+    Sha256::digest(format!("global:{}", name.to_snake_case()).as_bytes())[..8].to_vec()
+}
+
+/// Prefix of the `defined_types` keys `COption<T>`s are registered under.
+const COPTION: &str = "COption<";
+
+/// The value type of a `COption<T>` the loader registered as `name`, or
+/// `None` when `name` is any other type.
+pub fn coption_value<'a>(
+    name: &str,
+    defined_types: &'a BTreeMap<String, FieldType>,
+) -> Option<&'a FieldType> {
+    if !name.starts_with(COPTION) {
+        return None;
+    }
+    match defined_types.get(name)? {
+        FieldType::Struct(fields) => match fields.as_slice() {
+            [tag, value]
+                if tag.name == "tag" && tag.ty == FieldType::U32 && value.name == "value" =>
+            {
+                Some(&value.ty)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Replace every `COption<T>` in `value`, decoded as `ty`, by its `value`, or
+/// null when its tag is 0. A no-op for schemas without one.
+pub fn collapse_coptions(
+    ty: &FieldType,
+    defined_types: &BTreeMap<String, FieldType>,
+    value: &mut Value,
+) {
+    let has_coptions = defined_types
+        .range::<str, _>((Bound::Included(COPTION), Bound::Unbounded))
+        .next()
+        .is_some_and(|(name, _)| name.starts_with(COPTION));
+    if has_coptions {
+        collapse(ty, defined_types, value);
+    }
+}
+
+fn collapse(ty: &FieldType, defined_types: &BTreeMap<String, FieldType>, value: &mut Value) {
+    let collapse_fields = |fields: &[NamedField], value: &mut Value| {
+        if let Some(object) = value.as_object_mut() {
+            for field in fields {
+                if let Some(value) = object.get_mut(&field.name) {
+                    collapse(&field.ty, defined_types, value);
+                }
+            }
+        }
+    };
+    match ty {
+        FieldType::Defined(name) => match coption_value(name, defined_types) {
+            Some(inner) => {
+                let present = value.get("tag").and_then(Value::as_u64) != Some(0);
+                *value = match value.get_mut("value").filter(|_| present) {
+                    Some(inner_value) => {
+                        let mut inner_value = inner_value.take();
+                        collapse(inner, defined_types, &mut inner_value);
+                        inner_value
+                    }
+                    None => Value::Null,
+                };
+            }
+            None => {
+                if let Some(resolved) = defined_types.get(name) {
+                    collapse(resolved, defined_types, value);
+                }
+            }
+        },
+        FieldType::Option(inner) => collapse(inner, defined_types, value),
+        FieldType::Vec(inner) | FieldType::Array { ty: inner, .. } => {
+            for item in value.as_array_mut().into_iter().flatten() {
+                collapse(inner, defined_types, item);
+            }
+        }
+        FieldType::Struct(fields) => collapse_fields(fields, value),
+        FieldType::Enum(variants) => {
+            for variant in variants {
+                if let (Some(fields), Some(body)) = (&variant.fields, value.get_mut(&variant.name))
+                {
+                    collapse_fields(fields, body);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn items(node: Option<&Value>) -> impl Iterator<Item = &Value> {
+    node.and_then(Value::as_array).into_iter().flatten()
+}
+
+fn item_name<'a>(item: &'a Value, section: &str) -> Result<&'a str> {
+    item.get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("an entry under `{section}` has no name"))
+}
+
+fn discriminator_bytes(node: &Value, path: &str) -> Result<Vec<u8>> {
+    let bytes = serde_json::from_value::<Vec<u8>>(node.clone())
+        .with_context(|| format!("{path}: discriminator must be an array of bytes"))?;
+    if bytes.is_empty() {
+        return Err(anyhow!("{path}: discriminator is empty"));
+    }
+    Ok(bytes)
+}
+
+fn generic_params(declaration: &Value) -> Vec<&Value> {
+    items(declaration.get("generics")).collect()
+}
+
+/// What a type-declaration's generic parameters are bound to while one
+/// instantiation is being parsed.
+type Generics = BTreeMap<String, GenericArg>;
+
+#[derive(Clone, Debug)]
+enum GenericArg {
+    Type(FieldType),
+    Const(usize),
+}
+
+impl GenericArg {
+    /// How the argument appears in an instantiation's name.
+    fn label(&self) -> String {
+        match self {
+            GenericArg::Type(ty) => type_label(ty),
+            GenericArg::Const(value) => value.to_string(),
+        }
+    }
+}
+
+fn type_label(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Bool => "bool".into(),
+        FieldType::U8 => "u8".into(),
+        FieldType::U16 => "u16".into(),
+        FieldType::U32 => "u32".into(),
+        FieldType::U64 => "u64".into(),
+        FieldType::U128 => "u128".into(),
+        FieldType::I8 => "i8".into(),
+        FieldType::I16 => "i16".into(),
+        FieldType::I32 => "i32".into(),
+        FieldType::I64 => "i64".into(),
+        FieldType::I128 => "i128".into(),
+        FieldType::F32 => "f32".into(),
+        FieldType::F64 => "f64".into(),
+        FieldType::String => "string".into(),
+        FieldType::Bytes => "bytes".into(),
+        FieldType::Pubkey => "pubkey".into(),
+        FieldType::Option(inner) => format!("Option<{}>", type_label(inner)),
+        FieldType::Vec(inner) => format!("Vec<{}>", type_label(inner)),
+        FieldType::Array { ty, len } => format!("[{}; {len}]", type_label(ty)),
+        FieldType::Struct(_) => "struct".into(),
+        FieldType::Enum(_) => "enum".into(),
+        FieldType::Defined(name) => name.clone(),
+    }
+}
+
+struct TypeRegistry<'a> {
+    /// Raw declarations by name, generic ones included.
+    declarations: BTreeMap<&'a str, &'a Value>,
+    /// Parsed types by (instantiation) name.
+    resolved: BTreeMap<String, FieldType>,
+    /// Instantiations being parsed, so recursive types resolve to a
+    /// `Defined` reference instead of looping.
+    pending: HashSet<String>,
+    /// Instantiations declared with `serialization: bytemuck`, checked once
+    /// everything they reference has resolved.
+    bytemuck: Vec<String>,
+}
+
+impl<'a> TypeRegistry<'a> {
+    /// Parse (once) the declaration `name` instantiated with `args`, and
+    /// return the `defined_types` key it's registered under.
+    fn resolve_defined(&mut self, name: &str, args: Vec<GenericArg>, path: &str) -> Result<String> {
+        let declaration = *self.declarations.get(name).ok_or_else(|| {
+            anyhow!("{path}: references type '{name}', which the IDL doesn't declare")
+        })?;
+        let params = generic_params(declaration);
+        if params.len() != args.len() {
+            return Err(anyhow!(
+                "{path}: type '{name}' takes {} generic argument(s), got {}",
+                params.len(),
+                args.len()
+            ));
+        }
+        let key = if args.is_empty() {
+            name.to_string()
+        } else {
+            let labels = args.iter().map(GenericArg::label).collect::<Vec<_>>();
+            format!("{name}<{}>", labels.join(", "))
+        };
+        if self.resolved.contains_key(&key) || !self.pending.insert(key.clone()) {
+            return Ok(key);
+        }
+
+        let mut generics = Generics::new();
+        for (param, arg) in params.into_iter().zip(args) {
+            let param_name = param
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("types.{name}: a generic parameter has no name"))?;
+            let kind = param.get("kind").and_then(Value::as_str);
+            match (kind, &arg) {
+                (Some("type"), GenericArg::Type(_)) | (Some("const"), GenericArg::Const(_)) => {}
+                _ => {
+                    return Err(anyhow!(
+                        "{path}: generic argument for '{param_name}' of '{name}' doesn't match \
+                         its kind ({})",
+                        kind.unwrap_or("missing")
+                    ))
+                }
+            }
+            generics.insert(param_name.to_string(), arg);
+        }
+
+        let type_path = format!("types.{key}");
+        let body = declaration
+            .get("type")
+            .ok_or_else(|| anyhow!("{type_path}: missing `type`"))?;
+        let ty = self.type_declaration(body, &generics, &type_path)?;
+        match declaration.get("serialization") {
+            None => {}
+            Some(Value::String(s)) if s == "borsh" => {}
+            Some(Value::String(s)) if s == "bytemuck" => self.bytemuck.push(key.clone()),
+            Some(Value::String(s)) if s == "bytemuckunsafe" => {
+                return Err(anyhow!(
+                    "{type_path}: `bytemuckunsafe` serialization isn't supported; the type may \
+                     contain padding bytes the decoder can't locate"
+                ))
+            }
+            Some(other) => {
+                return Err(anyhow!(
+                    "{type_path}: serialization {other} isn't supported; only Borsh and \
+                     bytemuck types can be decoded"
+                ))
+            }
+        }
+        self.pending.remove(&key);
+        self.resolved.insert(key.clone(), ty);
+        Ok(key)
+    }
+
+    /// Register `COption<inner>` and return its `defined_types` key. The
+    /// decoder reads it as the struct it's laid out as: a u32 LE tag, then
+    /// `inner`, present (zeroed) whatever the tag.
+    fn coption(&mut self, inner: FieldType) -> String {
+        let key = format!("{COPTION}{}>", type_label(&inner));
+        self.resolved.entry(key.clone()).or_insert_with(|| {
+            FieldType::Struct(vec![
+                NamedField {
+                    name: "tag".to_string(),
+                    ty: FieldType::U32,
+                },
+                NamedField {
+                    name: "value".to_string(),
+                    ty: inner,
+                },
+            ])
+        });
+        key
+    }
+
+    /// The `type` of a declaration: a struct, an enum or an alias.
+    fn type_declaration(
+        &mut self,
+        body: &Value,
+        generics: &Generics,
+        path: &str,
+    ) -> Result<FieldType> {
+        match body.get("kind").and_then(Value::as_str) {
+            Some("struct") => Ok(FieldType::Struct(match body.get("fields") {
+                Some(fields) => self.named_fields(fields, generics, path)?,
+                None => Vec::new(),
+            })),
+            Some("enum") => {
+                let variants = items(body.get("variants"))
+                    .map(|variant| {
+                        let name = item_name(variant, path)?;
+                        let fields = match variant.get("fields") {
+                            None | Some(Value::Null) => None,
+                            Some(fields) => Some(self.named_fields(
+                                fields,
+                                generics,
+                                &format!("{path}.{name}"),
+                            )?),
+                        };
+                        Ok(EnumVariant {
+                            name: name.to_string(),
+                            fields,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if variants.len() > 256 {
+                    return Err(anyhow!(
+                        "{path}: enum has {} variants; Borsh tags variants with a single byte",
+                        variants.len()
+                    ));
+                }
+                Ok(FieldType::Enum(variants))
+            }
+            Some("type") => {
+                let alias = body
+                    .get("alias")
+                    .ok_or_else(|| anyhow!("{path}: type alias is missing `alias`"))?;
+                self.field_type(alias, generics, &format!("{path}.alias"))
+            }
+            Some(other) => Err(anyhow!(
+                "{path}: unknown type kind '{other}' (expected struct, enum or type)"
+            )),
+            None => Err(anyhow!("{path}: type is missing `kind`")),
+        }
+    }
+
+    /// Struct or enum-variant fields: named (`{name, type}`), or positional
+    /// for tuple structs and variants, which get the names `_0`, `_1`, ...
+    fn named_fields(
+        &mut self,
+        node: &Value,
+        generics: &Generics,
+        path: &str,
+    ) -> Result<Vec<NamedField>> {
+        let fields = node
+            .as_array()
+            .ok_or_else(|| anyhow!("{path}: `fields` must be an array"))?;
+        fields
+            .iter()
+            .enumerate()
+            .map(
+                |(i, field)| match field.get("name").and_then(Value::as_str) {
+                    Some(name) => {
+                        let ty = field
+                            .get("type")
+                            .ok_or_else(|| anyhow!("{path}.{name}: missing `type`"))?;
+                        Ok(NamedField {
+                            name: name.to_string(),
+                            ty: self.field_type(ty, generics, &format!("{path}.{name}"))?,
+                        })
+                    }
+                    None => Ok(NamedField {
+                        name: format!("_{i}"),
+                        ty: self.field_type(field, generics, &format!("{path}[{i}]"))?,
+                    }),
+                },
+            )
+            .collect()
+    }
+
+    fn field_type(&mut self, node: &Value, generics: &Generics, path: &str) -> Result<FieldType> {
+        if let Some(name) = node.as_str() {
+            return self.primitive(name, path);
+        }
+        let Some(obj) = node.as_object() else {
+            return Err(anyhow!("{path}: unsupported type {node}"));
+        };
+        if let Some(inner) = obj.get("option") {
+            return Ok(FieldType::Option(Box::new(self.field_type(
+                inner,
+                generics,
+                &format!("{path}.option"),
+            )?)));
+        }
+        if let Some(inner) = obj.get("vec") {
+            return Ok(FieldType::Vec(Box::new(self.field_type(
+                inner,
+                generics,
+                &format!("{path}.vec"),
+            )?)));
+        }
+        if let Some(array) = obj.get("array").and_then(Value::as_array) {
+            let [inner, len] = array.as_slice() else {
+                return Err(anyhow!("{path}: `array` must be [type, length]"));
+            };
+            let ty = self.field_type(inner, generics, &format!("{path}.array"))?;
+            let len = match (len.as_u64(), len.get("generic").and_then(Value::as_str)) {
+                (Some(len), _) => len as usize,
+                (None, Some(param)) => match generics.get(param) {
+                    Some(GenericArg::Const(len)) => *len,
+                    _ => {
+                        return Err(anyhow!(
+                            "{path}: array length '{param}' isn't a const generic"
+                        ))
+                    }
+                },
+                _ => return Err(anyhow!("{path}: unsupported array length {len}")),
+            };
+            return Ok(FieldType::Array {
+                ty: Box::new(ty),
+                len,
+            });
+        }
+        if let Some(defined) = obj.get("defined") {
+            let (name, args) = match defined {
+                Value::String(name) => (name.as_str(), Vec::new()),
+                Value::Object(defined) => {
+                    let name = defined
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| anyhow!("{path}: `defined` has no name"))?;
+                    let args = items(defined.get("generics"))
+                        .map(|arg| self.generic_arg(arg, generics, path))
+                        .collect::<Result<Vec<_>>>()?;
+                    (name, args)
+                }
+                other => return Err(anyhow!("{path}: unsupported `defined` {other}")),
+            };
+            return Ok(FieldType::Defined(self.resolve_defined(name, args, path)?));
+        }
+        if let Some(param) = obj.get("generic").and_then(Value::as_str) {
+            return match generics.get(param) {
+                Some(GenericArg::Type(ty)) => Ok(ty.clone()),
+                Some(GenericArg::Const(_)) => {
+                    Err(anyhow!("{path}: const generic '{param}' used as a type"))
+                }
+                None => Err(anyhow!("{path}: unbound generic parameter '{param}'")),
+            };
+        }
+        if let Some(inner) = obj.get("coption") {
+            let inner = self.field_type(inner, generics, &format!("{path}.coption"))?;
+            return Ok(FieldType::Defined(self.coption(inner)));
+        }
+        Err(anyhow!("{path}: unsupported type {node}"))
+    }
+
+    fn generic_arg(&mut self, arg: &Value, generics: &Generics, path: &str) -> Result<GenericArg> {
+        match arg.get("kind").and_then(Value::as_str) {
+            Some("type") => {
+                let ty = arg
+                    .get("type")
+                    .ok_or_else(|| anyhow!("{path}: generic type argument is missing `type`"))?;
+                Ok(GenericArg::Type(self.field_type(ty, generics, path)?))
+            }
+            Some("const") => {
+                let value = arg.get("value").and_then(Value::as_str).unwrap_or_default();
+                value
+                    .parse()
+                    .map(GenericArg::Const)
+                    .map_err(|_| anyhow!("{path}: const generic argument '{value}' isn't a length"))
+            }
+            _ => Err(anyhow!("{path}: unsupported generic argument {arg}")),
+        }
+    }
+
+    fn primitive(&mut self, name: &str, path: &str) -> Result<FieldType> {
+        Ok(match name {
+            "bool" => FieldType::Bool,
+            "u8" => FieldType::U8,
+            "u16" => FieldType::U16,
+            "u32" => FieldType::U32,
+            "u64" => FieldType::U64,
+            "u128" => FieldType::U128,
+            "i8" => FieldType::I8,
+            "i16" => FieldType::I16,
+            "i32" => FieldType::I32,
+            "i64" => FieldType::I64,
+            "i128" => FieldType::I128,
+            "f32" => FieldType::F32,
+            "f64" => FieldType::F64,
+            "string" => FieldType::String,
+            "bytes" => FieldType::Bytes,
+            "pubkey" | "publicKey" => FieldType::Pubkey,
+            "u256" | "i256" => {
+                return Err(anyhow!(
+                    "{path}: 256-bit integers ('{name}') aren't supported"
+                ))
+            }
+            // Some generators write a bare type name instead of `defined`.
+            other if self.declarations.contains_key(other) => {
+                FieldType::Defined(self.resolve_defined(other, Vec::new(), path)?)
+            }
+            other => return Err(anyhow!("{path}: unknown type '{other}'")),
+        })
+    }
+
+    fn instruction(&mut self, instruction: &Value) -> Result<InstructionSchema> {
+        let name = item_name(instruction, "instructions")?;
+        let path = format!("instructions.{name}");
+        let discriminator = match instruction.get("discriminator") {
+            Some(bytes) => discriminator_bytes(bytes, &path)?,
+            None => anchor_instruction_discriminator(name),
+        };
+        let mut accounts = Vec::new();
+        flatten_accounts(instruction.get("accounts"), &mut accounts, &path)?;
+        let args = match instruction.get("args") {
+            Some(args) => self.named_fields(args, &Generics::new(), &format!("{path}.args"))?,
+            None => Vec::new(),
+        };
+        Ok(InstructionSchema {
+            name: name.to_string(),
+            discriminator,
+            accounts,
+            args,
+        })
+    }
+
+    /// Events and accounts: a discriminator followed by a struct. The struct is
+    /// inlined as `fields` (legacy events) or declared under `types` with the
+    /// item's name (legacy accounts are registered there, so both specs' accounts
+    /// resolve the same way).
+    fn layouts(
+        &mut self,
+        node: Option<&Value>,
+        section: &str,
+        derive_discriminator: fn(&str) -> Vec<u8>,
+    ) -> Result<BTreeMap<String, SvmIdlLayout>> {
+        let mut layouts = BTreeMap::new();
+        for item in items(node) {
+            let name = item_name(item, section)?;
+            let path = format!("{section}.{name}");
+            let discriminator = match item.get("discriminator") {
+                Some(bytes) => discriminator_bytes(bytes, &path)?,
+                None => derive_discriminator(name),
+            };
+            let fields = match item.get("fields") {
+                Some(fields) => self.named_fields(fields, &Generics::new(), &path)?,
+                None => match self.resolved.get(name) {
+                    Some(FieldType::Struct(fields)) => fields.clone(),
+                    Some(_) => return Err(anyhow!("{path}: type '{name}' isn't a struct")),
+                    None => {
+                        return Err(anyhow!(
+                            "{path}: the IDL has no struct named '{name}' under `types` to take \
+                             the fields from"
+                        ))
+                    }
+                },
+            };
+            layouts.insert(
+                name.to_string(),
+                SvmIdlLayout {
+                    discriminator,
+                    fields,
+                },
+            );
+        }
+        Ok(layouts)
+    }
+
+    /// Zero-copy types are read straight out of memory, so they decode like
+    /// a Borsh struct only when every field is fixed-size plain data.
+    fn check_bytemuck(&self) -> Result<()> {
+        for name in &self.bytemuck {
+            self.check_plain_data(&self.resolved[name], &format!("types.{name}"))?;
+        }
+        Ok(())
+    }
+
+    fn check_plain_data(&self, ty: &FieldType, path: &str) -> Result<()> {
+        match ty {
+            FieldType::U8
+            | FieldType::U16
+            | FieldType::U32
+            | FieldType::U64
+            | FieldType::U128
+            | FieldType::I8
+            | FieldType::I16
+            | FieldType::I32
+            | FieldType::I64
+            | FieldType::I128
+            | FieldType::F32
+            | FieldType::F64
+            | FieldType::Pubkey => Ok(()),
+            FieldType::Array { ty, .. } => self.check_plain_data(ty, path),
+            FieldType::Struct(fields) => fields
+                .iter()
+                .try_for_each(|f| self.check_plain_data(&f.ty, &format!("{path}.{}", f.name))),
+            FieldType::Defined(name) => match self.resolved.get(name) {
+                Some(ty) => self.check_plain_data(ty, &format!("{path} ({name})")),
+                None => Err(anyhow!("{path}: references unresolved type '{name}'")),
+            },
+            other => Err(anyhow!(
+                "{path}: a bytemuck (zero-copy) type can only hold fixed-size plain data, found \
+                 {}",
+                type_label(other)
+            )),
+        }
+    }
+}
+
+/// Instruction accounts in the order they're passed. Composite groups
+/// (`{name, accounts: [...]}`) are inlined where they appear, as Anchor does
+/// at the call site.
+fn flatten_accounts(node: Option<&Value>, out: &mut Vec<NamedAccount>, path: &str) -> Result<()> {
+    for account in items(node) {
+        if account.get("accounts").is_some() {
+            flatten_accounts(account.get("accounts"), out, path)?;
+            continue;
+        }
+        let name = item_name(account, &format!("{path}.accounts"))?;
+        let flag = |modern: &str, legacy: &str| {
+            account
+                .get(modern)
+                .or_else(|| account.get(legacy))
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        };
+        out.push(NamedAccount {
+            name: name.to_string(),
+            writable: flag("writable", "isMut"),
+            signer: flag("signer", "isSigner"),
+            optional: flag("optional", "isOptional"),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypersync_client_solana::decode::decode_top_level;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn field(name: &str, ty: FieldType) -> NamedField {
+        NamedField {
+            name: name.to_string(),
+            ty,
+        }
+    }
+
+    #[test]
+    fn reads_a_modern_idl() {
+        let idl = parse(
+            r#"{
+              "address": "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+              "instructions": [{
+                "name": "swap",
+                "discriminator": [1, 2, 3, 4, 5, 6, 7, 8],
+                "accounts": [
+                  { "name": "user", "signer": true },
+                  { "name": "pool", "accounts": [
+                    { "name": "vault", "writable": true, "pda": { "seeds": [] } }
+                  ] }
+                ],
+                "args": [{ "name": "amount", "type": "u64" }]
+              }],
+              "events": [{ "name": "Swapped", "discriminator": [9, 9, 9, 9, 9, 9, 9, 9] }],
+              "accounts": [{ "name": "Swapped", "discriminator": [7, 7, 7, 7, 7, 7, 7, 7] }],
+              "types": [{
+                "name": "Swapped",
+                "type": { "kind": "struct", "fields": [{ "name": "user", "type": "pubkey" }] }
+              }]
+            }"#,
+        )
+        .expect("parse");
+
+        let swap = &idl.instructions[0];
+        assert_eq!(swap.discriminator, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        let accounts = swap
+            .accounts
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(accounts, vec!["user", "vault"]);
+        assert!(swap.accounts[1].writable);
+        assert_eq!(swap.args, vec![field("amount", FieldType::U64)]);
+        assert_eq!(idl.events["Swapped"].discriminator, vec![9; 8]);
+        assert_eq!(
+            idl.events["Swapped"].fields,
+            vec![field("user", FieldType::Pubkey)]
+        );
+        assert_eq!(idl.accounts["Swapped"].discriminator, vec![7; 8]);
+    }
+
+    #[test]
+    fn reads_a_legacy_idl() {
+        let idl = parse(
+            r#"{
+              "version": "0.1.0",
+              "name": "vault",
+              "instructions": [{
+                "name": "depositFunds",
+                "accounts": [{ "name": "owner", "isMut": true, "isSigner": true }],
+                "args": [{ "name": "receipt", "type": { "defined": "Receipt" } }]
+              }],
+              "accounts": [{
+                "name": "Receipt",
+                "type": { "kind": "struct", "fields": [{ "name": "owner", "type": "publicKey" }] }
+              }],
+              "events": [{
+                "name": "Deposited",
+                "fields": [{ "name": "amount", "type": "u64", "index": false }]
+              }]
+            }"#,
+        )
+        .expect("parse");
+
+        let deposit = &idl.instructions[0];
+        assert_eq!(
+            deposit.discriminator,
+            Sha256::digest(b"global:deposit_funds")[..8].to_vec()
+        );
+        assert!(deposit.accounts[0].writable && deposit.accounts[0].signer);
+        assert_eq!(
+            idl.defined_types["Receipt"],
+            FieldType::Struct(vec![field("owner", FieldType::Pubkey)])
+        );
+        let deposited = &idl.events["Deposited"];
+        assert_eq!(
+            deposited.discriminator,
+            anchor_event_discriminator("Deposited")
+        );
+        assert_eq!(deposited.fields, vec![field("amount", FieldType::U64)]);
+        let receipt = &idl.accounts["Receipt"];
+        assert_eq!(
+            receipt.discriminator,
+            Sha256::digest(b"account:Receipt")[..8].to_vec()
+        );
+        assert_eq!(receipt.fields, vec![field("owner", FieldType::Pubkey)]);
+    }
+
+    #[test]
+    fn monomorphizes_generic_types() {
+        let idl = parse(
+            r#"{
+              "instructions": [{
+                "name": "store",
+                "discriminator": [0, 0, 0, 0, 0, 0, 0, 1],
+                "args": [{ "name": "pair", "type": { "defined": {
+                  "name": "Pair",
+                  "generics": [{ "kind": "type", "type": "u64" }, { "kind": "const", "value": "4" }]
+                } } }]
+              }],
+              "types": [{
+                "name": "Pair",
+                "generics": [{ "kind": "type", "name": "T" }, { "kind": "const", "name": "N", "type": "usize" }],
+                "type": { "kind": "struct", "fields": [
+                  { "name": "value", "type": { "generic": "T" } },
+                  { "name": "tags", "type": { "array": ["u8", { "generic": "N" }] } }
+                ] }
+              }]
+            }"#,
+        )
+        .expect("parse");
+
+        assert_eq!(
+            idl.instructions[0].args,
+            vec![field(
+                "pair",
+                FieldType::Defined("Pair<u64, 4>".to_string())
+            )]
+        );
+        assert_eq!(
+            idl.defined_types["Pair<u64, 4>"],
+            FieldType::Struct(vec![
+                field("value", FieldType::U64),
+                field(
+                    "tags",
+                    FieldType::Array {
+                        ty: Box::new(FieldType::U8),
+                        len: 4
+                    }
+                ),
+            ])
+        );
+        // The uninstantiated template isn't a decodable type on its own.
+        assert!(!idl.defined_types.contains_key("Pair"));
+    }
+
+    #[test]
+    fn reads_tuple_fields_and_aliases() {
+        let idl = parse(
+            r#"{
+              "instructions": [],
+              "types": [
+                { "name": "Amount", "type": { "kind": "type", "alias": "u64" } },
+                { "name": "Point", "type": { "kind": "struct", "fields": ["i32", "i32"] } }
+              ]
+            }"#,
+        )
+        .expect("parse");
+
+        assert_eq!(idl.defined_types["Amount"], FieldType::U64);
+        assert_eq!(
+            idl.defined_types["Point"],
+            FieldType::Struct(vec![
+                field("_0", FieldType::I32),
+                field("_1", FieldType::I32)
+            ])
+        );
+    }
+
+    #[test]
+    fn accepts_plain_data_bytemuck_types_only() {
+        let zero_copy = |field_type: &str| {
+            format!(
+                r#"{{
+                  "instructions": [],
+                  "types": [{{
+                    "name": "Oracle",
+                    "serialization": "bytemuck",
+                    "repr": {{ "kind": "c" }},
+                    "type": {{ "kind": "struct", "fields": [{{ "name": "price", "type": {field_type} }}] }}
+                  }}]
+                }}"#
+            )
+        };
+        parse(&zero_copy(r#"{ "array": ["u64", 4] }"#)).expect("fixed-size fields");
+        let err = parse(&zero_copy(r#""string""#)).unwrap_err();
+        assert!(
+            err.to_string().contains("types.Oracle.price"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_constructs_with_their_path() {
+        let with_arg = |ty: &str| {
+            format!(
+                r#"{{ "instructions": [{{
+                  "name": "init",
+                  "discriminator": [1],
+                  "args": [{{ "name": "authority", "type": {ty} }}]
+                }}] }}"#
+            )
+        };
+        for (ty, expected) in [
+            (r#""u256""#, "256-bit integers"),
+            (
+                r#"{ "defined": "Missing" }"#,
+                "which the IDL doesn't declare",
+            ),
+        ] {
+            let err = parse(&with_arg(ty)).unwrap_err().to_string();
+            assert!(
+                err.starts_with("instructions.init.args.authority") && err.contains(expected),
+                "unexpected error for {ty}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_coption_as_an_optional_value() {
+        let idl = parse(
+            r#"{ "instructions": [{
+              "name": "setAuthority",
+              "discriminator": [1],
+              "args": [{ "name": "authority", "type": { "coption": "pubkey" } }]
+            }] }"#,
+        )
+        .expect("parse");
+        let layout = FieldType::Struct(idl.instructions[0].args.clone());
+        let authority = [7u8; 32];
+        let decode = |tag: u8| {
+            let mut data = vec![tag, 0, 0, 0];
+            data.extend_from_slice(&authority);
+            let mut args = decode_top_level(&layout, &idl.defined_types, &data).expect("decode");
+            collapse_coptions(&layout, &idl.defined_types, &mut args);
+            args
+        };
+
+        assert_eq!(decode(0), json!({ "authority": null }));
+        assert_eq!(
+            decode(1),
+            json!({ "authority": bs58::encode(authority).into_string() })
+        );
+    }
+
+    #[test]
+    fn legacy_instruction_names_are_snake_cased_as_anchor_does() {
+        for (name, snake_case) in [
+            ("initializePool", "initialize_pool"),
+            ("initialize_pool", "initialize_pool"),
+            ("HTTPServer", "http_server"),
+            ("initV2", "init_v2"),
+        ] {
+            assert_eq!(
+                anchor_instruction_discriminator(name),
+                Sha256::digest(format!("global:{snake_case}").as_bytes())[..8].to_vec(),
+                "discriminator of {name}"
+            );
+        }
+    }
+}
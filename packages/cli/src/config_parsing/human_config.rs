@@ -322,6 +322,15 @@ pub struct ChainContract<T> {
                        greater than the chain start_block for more specific indexing."
     )]
     pub start_block: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "The number of blocks a dynamically registered address of this contract \
+                       stays indexed for, counted from the block that registered it. Suits \
+                       short-lived contracts such as auctions or vaults that close. If not \
+                       specified, an address stays indexed until a contractRegister handler \
+                       removes it. Addresses listed in the config never expire."
+    )]
+    pub ttl_blocks: Option<u64>,
     #[serde(flatten)]
     //If this is "None" it should be expected that
     //there is a global config for the contract
@@ -509,10 +518,13 @@ pub mod evm {
 
     // `RpcTransactionField` is the subset an RPC-synced chain can deliver:
     // every field `eth_getTransactionByHash` or `eth_getTransactionReceipt`
-    // returns, which is all of them except the two array-shaped ones that have
-    // no parser in the runtime's field registry (`RpcSource.res`). Kept in step
-    // with that registry by `RpcFieldSelection_test.res`.
-    #[subenum(RpcTransactionField)]
+    // returns, which is all of them except the two array-shaped ones the
+    // runtime's `RpcSource.rpcTransactionFields` leaves out. Kept in step with
+    // that set by `RpcFieldSelection_test.res`.
+    // `HyperSyncTransactionField` is the subset HyperSync serves: all but
+    // `isSystemTx`, `operatorFeeScalar` and the zkSync batch fields, the
+    // `local_only` ones of the HyperSync query's field mapping.
+    #[subenum(RpcTransactionField, HyperSyncTransactionField)]
     #[derive(
         Debug,
         Serialize,
@@ -528,68 +540,91 @@ pub mod evm {
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     #[strum(serialize_all = "camelCase")]
     pub enum TransactionField {
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         TransactionIndex,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Hash,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         From,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         To,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Gas,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         GasPrice,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         MaxPriorityFeePerGas,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         MaxFeePerGas,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         CumulativeGasUsed,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         EffectiveGasPrice,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         GasUsed,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Input,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Nonce,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Value,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         V,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         R,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         S,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         ContractAddress,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         LogsBloom,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Root,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Status,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         YParity,
+        #[subenum(HyperSyncTransactionField)]
         AccessList,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         MaxFeePerBlobGas,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         BlobVersionedHashes,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         Type,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         L1Fee,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         L1GasPrice,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         L1GasUsed,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         L1FeeScalar,
-        #[subenum(RpcTransactionField)]
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
         GasUsedForL1,
+        #[subenum(HyperSyncTransactionField)]
         AuthorizationList,
+        // OP Stack deposit transactions
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
+        SourceHash,
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
+        Mint,
+        #[subenum(RpcTransactionField)]
+        IsSystemTx,
+        // OP Stack Ecotone/Fjord/Isthmus fee parameters
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
+        L1BaseFeeScalar,
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
+        L1BlobBaseFee,
+        #[subenum(RpcTransactionField, HyperSyncTransactionField)]
+        L1BlobBaseFeeScalar,
+        #[subenum(RpcTransactionField)]
+        OperatorFeeScalar,
+        // zkSync
+        #[subenum(RpcTransactionField)]
+        L1BatchNumber,
+        #[subenum(RpcTransactionField)]
+        L1BatchTxIndex,
         // We want to encourage the use of context.chain.id instead
         // ChainId,
         //These values are available by default on the block
@@ -699,6 +734,21 @@ pub mod evm {
                                   polling for detecting new blocks.")]
         pub ws: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Optional further endpoint URLs served by this same RPC source. Each \
+                           request is routed to whichever endpoint is currently healthiest \
+                           (lowest recent latency and error rate), so one provider degrading \
+                           doesn't stall indexing. The headers apply to every endpoint."
+        )]
+        pub additional_urls: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "If set, a request still unanswered after this many milliseconds is \
+                           also sent to the next healthiest endpoint, and the first response \
+                           wins. Requires additional_urls."
+        )]
+        pub hedge_after_millis: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "Optional HTTP headers sent with every request to this RPC endpoint, \
                            e.g. an Authorization bearer token for gated endpoints. Values support \
@@ -736,6 +786,13 @@ pub mod evm {
         #[schemars(description = "How long to wait before cancelling an RPC request")]
         pub query_timeout_millis: Option<u32>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "The maximum number of calls sent in one JSON-RPC batch request. \
+                           Batching cuts the number of HTTP requests made to the provider; set \
+                           to 1 for providers that don't support batch requests. Default is 100."
+        )]
+        pub max_batch_size: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "How frequently (in milliseconds) to check for new blocks in realtime. \
                            Default is 1000ms. Note: Setting this higher than block time does not \
@@ -779,6 +836,13 @@ pub mod evm {
         )]
         pub max_reorg_depth: Option<u32>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "How the indexer decides which blocks can no longer be reorged: \
+                           block hashes and rollback checkpoints at or below that height are \
+                           dropped. Defaults to `depth`."
+        )]
+        pub finality: Option<Finality>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "The number of blocks behind the chain head that the indexer should \
                            lag. Useful for avoiding reorg issues by indexing slightly behind the \
@@ -795,6 +859,29 @@ pub mod evm {
         pub contracts: Option<Vec<ChainContract<ContractConfig>>>,
     }
 
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Finality {
+        #[schemars(
+            description = "Treat blocks more than `max_reorg_depth` below the chain head \
+                                  as final."
+        )]
+        Depth,
+        #[schemars(description = "Treat the chain's `safe` block, from \
+                                  `eth_getBlockByNumber(\"safe\")`, as final. Requires an RPC.")]
+        Safe,
+        #[schemars(description = "Treat the chain's `finalized` block, from \
+                                  `eth_getBlockByNumber(\"finalized\")`, as final. Requires an \
+                                  RPC.")]
+        Finalized,
+        #[schemars(
+            description = "Treat blocks below HyperSync's rollback guard, the oldest \
+                                  block HyperSync may still roll back, as final. Requires \
+                                  HyperSync."
+        )]
+        RollbackGuard,
+    }
+
     #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
     #[serde(deny_unknown_fields)]
     pub struct ContractConfig {
@@ -811,6 +898,184 @@ pub mod evm {
         pub handler: Option<String>,
         #[schemars(description = "A list of events that should be indexed on this contract")]
         pub events: Vec<EventConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "A list of functions whose transactions to this contract should be \
+                           indexed, with their calldata decoded into the handler's params"
+        )]
+        pub functions: Option<Vec<FunctionConfig>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Registers this contract's addresses automatically as another \
+                           configured contract deploys them, without a contractRegister handler. \
+                           For factories that emit no creation event"
+        )]
+        pub factory: Option<FactoryConfig>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct FactoryConfig {
+        #[schemars(
+            description = "Name of the configured contract that deploys this one. Every contract \
+                           its addresses create is registered from the creating block"
+        )]
+        pub deployer: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Where creations are read from: `trace` (default) sees contracts the \
+                           deployer creates from its own code, CREATE and CREATE2 alike; \
+                           `transaction` sees contract-creating transactions the deployer sends, \
+                           for deployer EOAs"
+        )]
+        pub source: Option<FactorySource>,
+    }
+
+    #[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, JsonSchema)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub enum FactorySource {
+        Trace,
+        Transaction,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct FunctionConfig {
+        #[schemars(description = "The human readable signature of a function 'eg. \
+                                  transfer(address to, uint256 amount)' OR a reference to the \
+                                  name of a function in a json ABI file defined in your \
+                                  contract config. Transactions sent directly to the \
+                                  contract with this function's selector are indexed, \
+                                  successful ones unless `where` says otherwise.")]
+        pub function: String,
+        #[schemars(
+            description = "Name of the function in the HyperIndex generated code. When \
+                           ommitted, the function's name will be used. Should be unique per \
+                           contract, events included"
+        )]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Select the block and transaction fields to include in the specific \
+                           function"
+        )]
+        pub field_selection: Option<FieldSelection>,
+        #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Conditions on the transactions to index. Without a `transaction.status`, \
+                           only successful transactions are indexed; set it to `failure` to index \
+                           reverted ones instead"
+        )]
+        pub where_: Option<EventWhere>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct EventWhere {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Conditions on the decoded non-indexed params, by param name. Items \
+                           failing them are dropped before they reach the handler. Filter \
+                           indexed params with the `where` option of `indexer.onEvent` instead, \
+                           which narrows the query itself"
+        )]
+        pub params: Option<std::collections::BTreeMap<String, ParamCondition>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Conditions on the transaction the event belongs to")]
+        pub transaction: Option<TransactionWhere>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct ParamCondition {
+        #[serde(rename = "_eq", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "The param equals this value")]
+        pub eq: Option<ParamLiteral>,
+        #[serde(rename = "_in", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "The param is one of these values")]
+        pub in_: Option<Vec<ParamLiteral>>,
+        #[serde(rename = "_gte", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Inclusive lower bound, for int and uint params")]
+        pub gte: Option<ParamLiteral>,
+        #[serde(rename = "_lte", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Inclusive upper bound, for int and uint params")]
+        pub lte: Option<ParamLiteral>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(untagged)]
+    #[schemars(
+        description = "A param value: a bool, an integer, or a string for addresses, bytes, \
+                       strings and integers past 2^63"
+    )]
+    pub enum ParamLiteral {
+        Bool(bool),
+        Integer(i64),
+        String(String),
+    }
+
+    impl std::fmt::Display for ParamLiteral {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ParamLiteral::Bool(value) => write!(f, "{value}"),
+                ParamLiteral::Integer(value) => write!(f, "{value}"),
+                ParamLiteral::String(value) => write!(f, "{value}"),
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct TransactionWhere {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Only index transactions with this receipt status. Logs only exist in \
+                           successful transactions, so `failure` applies to functions and calls"
+        )]
+        pub status: Option<TransactionStatus>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Bounds on the gas the transaction used")]
+        pub gas_used: Option<QuantityRange>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Bounds on the value the transaction sent, in wei")]
+        pub value: Option<QuantityRange>,
+    }
+
+    #[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, JsonSchema)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub enum TransactionStatus {
+        Success,
+        Failure,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct QuantityRange {
+        #[serde(rename = "_gte", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Inclusive lower bound")]
+        pub gte: Option<Quantity>,
+        #[serde(rename = "_lte", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Inclusive upper bound")]
+        pub lte: Option<Quantity>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(untagged)]
+    #[schemars(
+        description = "A non-negative integer. Write amounts past 2^64 (such as wei values) as a \
+                       decimal string"
+    )]
+    pub enum Quantity {
+        Integer(u64),
+        Decimal(String),
+    }
+
+    #[derive(Debug, Serialize, Clone, Deserialize, PartialEq, JsonSchema)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub enum EventType {
+        Log,
+        Call,
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -820,8 +1085,20 @@ pub mod evm {
                                   Transfer(address indexed from, address indexed to, uint256 \
                                   value)' OR a reference to the name of an event in a json ABI \
                                   file defined in your contract config. A provided signature \
-                                  will take precedence over what is defined in the json ABI")]
+                                  will take precedence over what is defined in the json ABI. \
+                                  With `type: call`, the signature (or ABI name) of a function \
+                                  instead, eg. 'transfer(address to, uint256 amount)'")]
         pub event: String,
+        #[serde(rename = "type")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "What to index: `log` (default) indexes the event's logs, `call` \
+                           indexes every successful call of the function to the contract, \
+                           internal calls included. Calls are read from traces, so the chain's \
+                           HyperSync must serve traces, or its RPC must support `trace_filter` \
+                           or `debug_traceBlockByNumber`."
+        )]
+        pub type_: Option<EventType>,
         #[schemars(
             description = "Name of the event in the HyperIndex generated code. When ommitted, the \
                            event field will be used. Should be unique per contract"
@@ -834,6 +1111,25 @@ pub mod evm {
                            event"
         )]
         pub field_selection: Option<FieldSelection>,
+        #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
+        #[schemars(description = "Conditions on the events to index")]
+        pub where_: Option<EventWhere>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Registers the address in one of the event's params as a contract, \
+                           without a contractRegister handler. Eg. `{contract: Pair, from: pair}` \
+                           on a factory's PairCreated event"
+        )]
+        pub register: Option<EventRegisterConfig>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct EventRegisterConfig {
+        #[schemars(description = "Name of the configured contract to register the address as")]
+        pub contract: String,
+        #[schemars(description = "Name of the address param holding the address to register")]
+        pub from: String,
     }
 }
 
@@ -1058,8 +1354,9 @@ pub mod svm {
         #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
             description = "RPC endpoint URL for connecting to the Svm cluster to fetch blockchain \
-                           data. Required unless `experimental` is set, in which case it is \
-                           ignored in favour of the experimental HyperSync source."
+                           data. Required unless `experimental` is set, in which case \
+                           HyperSync serves instructions and the RPC is only used to read the \
+                           state of the programs' `accounts`, which HyperSync doesn't provide."
         )]
         pub rpc: Option<String>,
         #[schemars(
@@ -1102,8 +1399,12 @@ pub mod svm {
             description = "A unique project-wide name for this program (used in generated code)."
         )]
         pub name: String,
-        #[schemars(description = "Base58-encoded program id (32 bytes).")]
-        pub program_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Base58-encoded program id (32 bytes). May be omitted with a `schema`, \
+                           which defaults it to the id of the program the schema describes."
+        )]
+        pub program_id: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(description = "Optional relative path to a file where handlers are \
                                   registered for the given program. If not provided, handlers \
@@ -1111,14 +1412,175 @@ pub mod svm {
         pub handler: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         #[schemars(
-            description = "Optional path (relative to config.yaml) to an Anchor IDL JSON file. \
-                           When present, codegen parses the IDL and derives `accounts`/`args` for \
-                           every named instruction. Mutually exclusive with per-instruction \
-                           `accounts`/`args` overrides."
+            description = "Optional path (relative to config.yaml) to an Anchor IDL JSON file, in \
+                           either the 0.30+ or the legacy 0.29 format. When present, codegen \
+                           parses the IDL and derives `accounts`/`args` for every named \
+                           instruction, and fills in the `discriminator` of instructions that \
+                           omit it. Mutually exclusive with per-instruction `accounts`/`args` \
+                           overrides."
         )]
         pub idl: Option<String>,
-        #[schemars(description = "A list of instructions that should be indexed on this program.")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "A schema bundled with envio to decode the program's instructions with, \
+                           for common native programs that have no Anchor IDL. Instructions are \
+                           then declared by name alone. Mutually exclusive with `idl`."
+        )]
+        pub schema: Option<BundledSchema>,
+        #[serde(default)]
+        #[schemars(
+            description = "A list of instructions that should be indexed on this program. With an \
+                           `idl` or a `schema` and no `events` or `accounts`, leaving it empty \
+                           indexes every instruction the schema declares."
+        )]
         pub instructions: Vec<Instruction>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Anchor events the program emits, either through `emit!` (a base64 \
+                           `Program data:` log line) or `emit_cpi!` (a self-CPI instruction). \
+                           Each event gets its own handler, registered with `onEvent`."
+        )]
+        pub events: Option<Vec<Event>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Anchor account types owned by the program. Whenever one of the \
+                           program's instructions writes an account of such a type, its handler, \
+                           registered with `onAccount`, receives the decoded account state. \
+                           HyperSync doesn't serve account data yet, so this is for development \
+                           only: the state is read over the chain's `rpc` as it is at the time of \
+                           the read, and only with ENVIO_SVM_ACCOUNT_STATE_FROM_RPC=true."
+        )]
+        pub accounts: Option<Vec<Account>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "What to do when an instruction, event or account of this program fails to \
+                           decode against its Borsh schema, e.g. after the program was upgraded \
+                           without updating the IDL. Defaults to `ignore`."
+        )]
+        pub decode_errors: Option<DecodeErrors>,
+    }
+
+    impl Program {
+        /// The declared `program_id`, or the one of the bundled `schema`.
+        /// `None` only for a program validation rejects.
+        pub fn program_id(&self) -> Option<&str> {
+            self.program_id
+                .as_deref()
+                .or_else(|| self.schema.map(BundledSchema::program_id))
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum BundledSchema {
+        #[schemars(
+            description = "SPL Token (TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA): `Transfer`, \
+                           `TransferChecked`, `MintTo`, `Burn`, `SetAuthority`, etc."
+        )]
+        SplToken,
+        #[schemars(
+            description = "SPL Token-2022 (TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb): the SPL \
+                           Token instructions plus the extension ones, e.g. \
+                           `TransferCheckedWithFee` or `InitializeMetadataPointer`."
+        )]
+        #[serde(rename = "spl_token_2022")]
+        SplToken2022,
+        #[schemars(description = "Associated Token Account \
+                           (ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL): `Create`, \
+                           `CreateIdempotent` and `RecoverNested`.")]
+        AssociatedTokenAccount,
+        #[schemars(
+            description = "System Program (11111111111111111111111111111111): `Transfer`, \
+                           `CreateAccount`, `Assign`, `Allocate` and the nonce instructions."
+        )]
+        SystemProgram,
+        #[schemars(
+            description = "Metaplex Token Metadata (metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s)."
+        )]
+        MetaplexTokenMetadata,
+    }
+
+    impl BundledSchema {
+        pub fn program_id(self) -> &'static str {
+            use crate::config_parsing::svm_bundled_schemas as bundled;
+            match self {
+                BundledSchema::SplToken => bundled::SPL_TOKEN_PROGRAM_ID,
+                BundledSchema::SplToken2022 => bundled::SPL_TOKEN_2022_PROGRAM_ID,
+                BundledSchema::AssociatedTokenAccount => {
+                    bundled::ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID
+                }
+                BundledSchema::SystemProgram => bundled::SYSTEM_PROGRAM_ID,
+                BundledSchema::MetaplexTokenMetadata => bundled::METAPLEX_TOKEN_METADATA_PROGRAM_ID,
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+    #[serde(rename_all = "lowercase")]
+    pub enum DecodeErrors {
+        #[default]
+        #[schemars(
+            description = "Deliver the item to its handler without decoded `args`/`params`."
+        )]
+        Ignore,
+        #[schemars(
+            description = "Like `ignore`, but log a warning with the failure's reason, \
+                                  byte offset and instruction path."
+        )]
+        Warn,
+        #[schemars(description = "Stop the indexer on the first item that fails to decode.")]
+        Fail,
+    }
+
+    /// One Anchor event, matched by its 8-byte discriminator and decoded with
+    /// its Borsh field layout.
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct Event {
+        #[schemars(
+            description = "Name of the event struct, as declared in the program (and its IDL). \
+                           Should be unique per program."
+        )]
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Hex-encoded 8-byte event discriminator (\"0x\" optional). Defaults to \
+                           the IDL's value, or to Anchor's `sha256(\"event:<name>\")[..8]` when \
+                           the program has no `idl`."
+        )]
+        pub discriminator: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Optional Borsh field layout of the event, in declared order. Required \
+                           when the program has no `idl`; mutually exclusive with it otherwise."
+        )]
+        pub fields: Option<Vec<ArgDef>>,
+    }
+
+    /// One Anchor account type, matched by the 8-byte discriminator that
+    /// prefixes the account's data and decoded with its Borsh field layout.
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    pub struct Account {
+        #[schemars(
+            description = "Name of the account struct, as declared in the program (and its IDL). \
+                           Should be unique per program."
+        )]
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Hex-encoded 8-byte account discriminator (\"0x\" optional). Defaults \
+                           to the IDL's value, or to Anchor's `sha256(\"account:<name>\")[..8]` \
+                           when the program has no `idl`."
+        )]
+        pub discriminator: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(
+            description = "Optional Borsh field layout of the account, in declared order. \
+                           Required when the program has no `idl`; mutually exclusive with it \
+                           otherwise."
+        )]
+        pub fields: Option<Vec<ArgDef>>,
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -1405,10 +1867,13 @@ events: []
                 "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()
             ]),
             start_block: None,
+            ttl_blocks: None,
             config: Some(ContractConfig {
                 abi_file_path: None,
                 handler: Some("./src/EventHandler.js".to_string()),
                 events: vec![],
+                functions: None,
+                factory: None,
             }),
         };
 
@@ -1428,10 +1893,13 @@ events: []
             name: "Contract1".to_string(),
             address: vec![].into(),
             start_block: None,
+            ttl_blocks: None,
             config: Some(ContractConfig {
                 abi_file_path: None,
                 handler: Some("./src/EventHandler.js".to_string()),
                 events: vec![],
+                functions: None,
+                factory: None,
             }),
         };
 
@@ -1452,10 +1920,13 @@ events: []
             name: "Contract1".to_string(),
             address: vec!["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()].into(),
             start_block: None,
+            ttl_blocks: None,
             config: Some(ContractConfig {
                 abi_file_path: None,
                 handler: Some("./src/EventHandler.js".to_string()),
                 events: vec![],
+                functions: None,
+                factory: None,
             }),
         };
 
@@ -1476,6 +1947,27 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
                 "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()
             ]),
             start_block: None,
+            ttl_blocks: None,
+            config: None,
+        };
+
+        assert_eq!(expected, deserialized);
+    }
+
+    #[test]
+    fn test_deserialize_global_contract_with_ttl_blocks() {
+        let yaml = r#"
+name: Auction
+address: []
+ttl_blocks: 7200
+    "#;
+
+        let deserialized: ChainContract<ContractConfig> = serde_yaml::from_str(yaml).unwrap();
+        let expected = ChainContract {
+            name: "Auction".to_string(),
+            address: NormalizedList::from(vec![]),
+            start_block: None,
+            ttl_blocks: Some(7200),
             config: None,
         };
 
@@ -1594,6 +2086,7 @@ address: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
                         .to_string()
                         .into(),
                     start_block: None,
+                    ttl_blocks: None,
                     config: Some(fuel::ContractConfig {
                         abi_file_path: "../abis/greeter-abi.json".to_string(),
                         handler: Some("./src/EventHandlers.js".to_string()),
@@ -1724,9 +2217,10 @@ chains:
                 program,
                 &Program {
                     name: "TokenMetadata".to_string(),
-                    program_id: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".to_string(),
+                    program_id: Some("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".to_string()),
                     handler: None,
                     idl: None,
+                    schema: None,
                     instructions: vec![
                         Instruction {
                             name: "CreateMetadataAccountV3".to_string(),
@@ -1750,8 +2244,54 @@ chains:
                             args: None,
                         },
                     ],
+                    events: None,
+                    accounts: None,
+                    decode_errors: None,
                 }
             );
         }
+
+        #[test]
+        fn deserialize_program_events() {
+            let yaml = r#"
+name: TokenMetadata
+program_id: metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s
+instructions: []
+events:
+  - name: Traded
+  - name: Minted
+    discriminator: "0x0102030405060708"
+    fields:
+      - name: amount
+        type: u64
+      - name: owner
+        type: pubkey
+"#;
+            let program: Program = serde_yaml::from_str(yaml).unwrap();
+            assert_eq!(
+                program.events,
+                Some(vec![
+                    Event {
+                        name: "Traded".to_string(),
+                        discriminator: None,
+                        fields: None,
+                    },
+                    Event {
+                        name: "Minted".to_string(),
+                        discriminator: Some("0x0102030405060708".to_string()),
+                        fields: Some(vec![
+                            ArgDef {
+                                name: "amount".to_string(),
+                                ty: ArgType::Primitive(ArgPrimitive::U64),
+                            },
+                            ArgDef {
+                                name: "owner".to_string(),
+                                ty: ArgType::Primitive(ArgPrimitive::Pubkey),
+                            },
+                        ]),
+                    },
+                ])
+            );
+        }
     }
 }
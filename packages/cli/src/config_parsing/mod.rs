@@ -1,4 +1,5 @@
 pub mod abi_compat;
+pub mod anchor_idl;
 pub mod chain_helpers;
 pub mod contract_import;
 pub mod entity_parsing;
@@ -8,5 +9,6 @@ pub mod field_types;
 pub mod human_config;
 pub mod hypersync_endpoints;
 pub mod public_config;
+pub mod svm_bundled_schemas;
 pub mod system_config;
 pub mod validation;
@@ -1,10 +1,16 @@
 use super::{
     entity_parsing::{self, IndexFieldDirection},
     field_types,
-    human_config::{self, evm::For, ColumnNameFormat},
+    human_config::{
+        self,
+        evm::{FactorySource, Finality, For},
+        svm::DecodeErrors,
+        ColumnNameFormat,
+    },
     system_config::{
-        self, field_type_to_arg_type, named_field_to_arg_def, Abi, ChainIdMode, Ecosystem,
-        EventKind, FuelEventKind, SvmAbi, SvmSchemaSource, SystemConfig,
+        self, field_type_to_arg_type, named_field_to_arg_def, Abi, ChainIdMode, Contract,
+        Ecosystem, EventKind, EventRegister, Factory, FuelEventKind, ParamWhere, QuantityBounds,
+        SvmAbi, SvmSchemaSource, SystemConfig, TransactionWhere,
     },
 };
 use crate::{config_parsing::chain_helpers::Network, utils::text::Capitalize};
@@ -293,6 +299,8 @@ struct RpcConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     ws: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    additional_urls: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     headers: Option<std::collections::BTreeMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     initial_block_interval: Option<u32>,
@@ -309,6 +317,10 @@ struct RpcConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     query_timeout_millis: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    max_batch_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hedge_after_millis: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     polling_interval: Option<u32>,
 }
 
@@ -322,6 +334,8 @@ struct ChainConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_reorg_depth: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    finality: Option<Finality>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     block_lag: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     hypersync: Option<String>,
@@ -340,6 +354,8 @@ struct ChainContractConfig {
     addresses: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     start_block: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl_blocks: Option<u64>,
 }
 
 #[derive(Serialize, Debug)]
@@ -412,9 +428,132 @@ struct ContractEventItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     transaction_fields: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_where: Option<TransactionWhereItem>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    params_where: Vec<ParamWhereItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    register: Option<RegisterItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     svm: Option<SvmEventItem>,
 }
 
+/// Registers the address in the item's `param` for `contract` as the item is
+/// fetched, without a contractRegister handler.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RegisterItem {
+    contract: String,
+    param: String,
+}
+
+impl From<&EventRegister> for RegisterItem {
+    fn from(register: &EventRegister) -> Self {
+        Self {
+            contract: register.contract.clone(),
+            param: register.param.clone(),
+        }
+    }
+}
+
+/// The param a creation item carries the created address in.
+const CREATED_ADDRESS_PARAM: &str = "address";
+
+/// The handler-less item a deployer gets per contract it's the factory of: the
+/// creations its addresses make, each registering the created address.
+fn creation_event_item(created: &Contract, factory: &Factory) -> ContractEventItem {
+    ContractEventItem {
+        name: format!("create({})", created.name),
+        sighash: String::new(),
+        params: vec![EventParam {
+            name: CREATED_ADDRESS_PARAM.to_string(),
+            abi_type: "address".to_string(),
+            indexed: false,
+            components: None,
+        }],
+        kind: Some(
+            match factory.source {
+                FactorySource::Trace => "create",
+                FactorySource::Transaction => "deploy",
+            }
+            .to_string(),
+        ),
+        block_fields: None,
+        transaction_fields: None,
+        transaction_where: None,
+        params_where: vec![],
+        register: Some(RegisterItem {
+            contract: created.name.clone(),
+            param: CREATED_ADDRESS_PARAM.to_string(),
+        }),
+        svm: None,
+    }
+}
+
+/// Quantities as decimal strings: wei values overflow JS numbers.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TransactionWhereItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_used: Option<QuantityRangeItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<QuantityRangeItem>,
+}
+
+#[derive(Serialize, Debug)]
+struct QuantityRangeItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gte: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lte: Option<String>,
+}
+
+impl From<&QuantityBounds> for QuantityRangeItem {
+    fn from(range: &QuantityBounds) -> Self {
+        Self {
+            gte: range.gte.map(|v| v.to_string()),
+            lte: range.lte.map(|v| v.to_string()),
+        }
+    }
+}
+
+impl From<&TransactionWhere> for TransactionWhereItem {
+    fn from(transaction_where: &TransactionWhere) -> Self {
+        Self {
+            status: transaction_where.status,
+            gas_used: transaction_where.gas_used.as_ref().map(Into::into),
+            value: transaction_where.value.as_ref().map(Into::into),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ParamWhereItem {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eq: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    one_of: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gte: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lte: Option<String>,
+}
+
+impl From<&ParamWhere> for ParamWhereItem {
+    fn from(param_where: &ParamWhere) -> Self {
+        Self {
+            name: param_where.name.clone(),
+            eq: param_where.eq.clone(),
+            one_of: param_where.one_of.clone(),
+            gte: param_where.gte.clone(),
+            lte: param_where.lte.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct SvmEventItem {
@@ -449,6 +588,9 @@ struct SvmAbiJson {
     /// `"anchorIdl"`, `"bundled"`, or `"inline"`. Carried for diagnostics; the
     /// runtime treats all three identically.
     source: &'static str,
+    /// Omitted for the default `ignore`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decode_errors: Option<DecodeErrors>,
 }
 
 #[derive(Serialize, Debug)]
@@ -512,6 +654,7 @@ impl SystemConfig {
                                     ),
                                 },
                                 ws: rpc.ws.clone(),
+                                additional_urls: rpc.additional_urls.clone(),
                                 headers: rpc.headers.clone(),
                                 initial_block_interval: rpc.initial_block_interval,
                                 backoff_multiplicative: rpc.backoff_multiplicative,
@@ -520,6 +663,8 @@ impl SystemConfig {
                                 backoff_millis: rpc.backoff_millis,
                                 fallback_stall_timeout: rpc.fallback_stall_timeout,
                                 query_timeout_millis: rpc.query_timeout_millis,
+                                max_batch_size: rpc.max_batch_size,
+                                hedge_after_millis: rpc.hedge_after_millis,
                                 polling_interval: rpc.polling_interval,
                             })
                             .collect();
@@ -543,6 +688,7 @@ impl SystemConfig {
                             ChainContractConfig {
                                 addresses: nc.addresses.clone(),
                                 start_block: nc.start_block,
+                                ttl_blocks: nc.ttl_blocks,
                             },
                         )
                     })
@@ -555,6 +701,7 @@ impl SystemConfig {
                         start_block: network.start_block,
                         end_block: network.end_block,
                         max_reorg_depth: network.max_reorg_depth,
+                        finality: network.finality,
                         block_lag: network.block_lag,
                         hypersync,
                         rpcs,
@@ -566,138 +713,203 @@ impl SystemConfig {
             .collect();
 
         // Build contracts map
-        let contracts: BTreeMap<&str, ContractConfig> =
-            cfg.contracts
-                .values()
-                .map(|contract| -> Result<(&str, ContractConfig)> {
-                    let abi_raw = match &contract.abi {
-                        Abi::Evm(abi) => {
-                            let abi_value: serde_json::Value = serde_json::from_str(&abi.raw)?;
-                            let abi_compact = serde_json::to_string(&abi_value)?;
-                            serde_json::value::RawValue::from_string(abi_compact)?
-                        }
-                        Abi::Fuel(abi) => {
-                            let abi_value: serde_json::Value = serde_json::from_str(&abi.raw)?;
-                            let abi_compact = serde_json::to_string(&abi_value)?;
-                            serde_json::value::RawValue::from_string(abi_compact)?
-                        }
-                        Abi::Svm(_) => serde_json::value::RawValue::from_string("null".into())?,
-                    };
+        let contracts: BTreeMap<&str, ContractConfig> = cfg
+            .contracts
+            .values()
+            .map(|contract| -> Result<(&str, ContractConfig)> {
+                let abi_raw = match &contract.abi {
+                    Abi::Evm(abi) => {
+                        let abi_value: serde_json::Value = serde_json::from_str(&abi.raw)?;
+                        let abi_compact = serde_json::to_string(&abi_value)?;
+                        serde_json::value::RawValue::from_string(abi_compact)?
+                    }
+                    Abi::Fuel(abi) => {
+                        let abi_value: serde_json::Value = serde_json::from_str(&abi.raw)?;
+                        let abi_compact = serde_json::to_string(&abi_value)?;
+                        serde_json::value::RawValue::from_string(abi_compact)?
+                    }
+                    Abi::Svm(_) => serde_json::value::RawValue::from_string("null".into())?,
+                };
 
-                    let events: Vec<ContractEventItem> = contract
-                        .events
-                        .iter()
-                        .map(|e| {
-                            let (params, kind, svm) = match &e.kind {
-                                EventKind::Params(event_params) => {
-                                    let params = event_params
+                let mut events: Vec<ContractEventItem> = contract
+                    .events
+                    .iter()
+                    .map(|e| {
+                        let (params, kind, svm) = match &e.kind {
+                            EventKind::Params(event_params)
+                            | EventKind::Call(event_params)
+                            | EventKind::Transaction(event_params) => {
+                                let params = event_params
+                                    .iter()
+                                    .map(|p| EventParam {
+                                        name: p.name.clone(),
+                                        abi_type: p.kind.to_signature_string(),
+                                        indexed: p.indexed,
+                                        // Indexed structs/tuples are delivered as keccak256
+                                        // topic hashes, not decoded tuples, so the runtime
+                                        // can't rebuild a named record from them. Skip the
+                                        // component metadata so the decoder takes the legacy
+                                        // path and leaves the value as the raw hash.
+                                        components: if p.indexed {
+                                            None
+                                        } else {
+                                            abi_type_to_components(&p.kind)
+                                        },
+                                    })
+                                    .collect();
+                                let kind = match e.kind {
+                                    EventKind::Call(_) => Some("call".to_string()),
+                                    EventKind::Transaction(_) => Some("transaction".to_string()),
+                                    _ => None,
+                                };
+                                (params, kind, None)
+                            }
+                            EventKind::Fuel(fuel_kind) => {
+                                let kind_str = match fuel_kind {
+                                    FuelEventKind::LogData(_) => "logData",
+                                    FuelEventKind::Mint => "mint",
+                                    FuelEventKind::Burn => "burn",
+                                    FuelEventKind::Transfer => "transfer",
+                                    FuelEventKind::Call => "call",
+                                };
+                                (vec![], Some(kind_str.to_string()), None)
+                            }
+                            EventKind::Svm(svm_kind) => {
+                                let svm_item = SvmEventItem {
+                                    discriminator: svm_kind.discriminator.clone(),
+                                    discriminator_byte_len: svm_kind.discriminator_byte_len,
+                                    account_filters: svm_kind
+                                        .account_filters
                                         .iter()
-                                        .map(|p| EventParam {
-                                            name: p.name.clone(),
-                                            abi_type: p.kind.to_signature_string(),
-                                            indexed: p.indexed,
-                                            // Indexed structs/tuples are delivered as keccak256
-                                            // topic hashes, not decoded tuples, so the runtime
-                                            // can't rebuild a named record from them. Skip the
-                                            // component metadata so the decoder takes the legacy
-                                            // path and leaves the value as the raw hash.
-                                            components: if p.indexed {
-                                                None
-                                            } else {
-                                                abi_type_to_components(&p.kind)
-                                            },
+                                        .map(|group| {
+                                            group
+                                                .iter()
+                                                .map(|af| SvmAccountFilterJson {
+                                                    position: af.position,
+                                                    values: af.values.clone(),
+                                                })
+                                                .collect()
                                         })
-                                        .collect();
-                                    (params, None, None)
-                                }
-                                EventKind::Fuel(fuel_kind) => {
-                                    let kind_str = match fuel_kind {
-                                        FuelEventKind::LogData(_) => "logData",
-                                        FuelEventKind::Mint => "mint",
-                                        FuelEventKind::Burn => "burn",
-                                        FuelEventKind::Transfer => "transfer",
-                                        FuelEventKind::Call => "call",
-                                    };
-                                    (vec![], Some(kind_str.to_string()), None)
-                                }
-                                EventKind::Svm(svm_kind) => {
-                                    let svm_item = SvmEventItem {
-                                        discriminator: svm_kind.discriminator.clone(),
-                                        discriminator_byte_len: svm_kind.discriminator_byte_len,
-                                        account_filters: svm_kind
-                                            .account_filters
-                                            .iter()
-                                            .map(|group| {
-                                                group
-                                                    .iter()
-                                                    .map(|af| SvmAccountFilterJson {
-                                                        position: af.position,
-                                                        values: af.values.clone(),
-                                                    })
-                                                    .collect()
-                                            })
-                                            .collect(),
-                                        is_inner: svm_kind.is_inner,
-                                        accounts: svm_kind.accounts.clone(),
-                                        args: svm_kind
-                                            .args
-                                            .iter()
-                                            .map(named_field_to_arg_def)
-                                            .collect(),
-                                    };
-                                    (vec![], Some("svmInstruction".to_string()), Some(svm_item))
-                                }
-                            };
-                            ContractEventItem {
-                                name: e.name.clone(),
-                                sighash: e.sighash.clone(),
-                                params,
-                                kind,
-                                block_fields: e.field_selection.as_ref().map(|fs| {
-                                    fs.block_fields.iter().map(|f| f.name.clone()).collect()
-                                }),
-                                transaction_fields: e.field_selection.as_ref().map(|fs| {
-                                    fs.transaction_fields
+                                        .collect(),
+                                    is_inner: svm_kind.is_inner,
+                                    accounts: svm_kind.accounts.clone(),
+                                    args: svm_kind
+                                        .args
                                         .iter()
-                                        .map(|f| f.name.clone())
-                                        .collect()
-                                }),
-                                svm,
+                                        .map(named_field_to_arg_def)
+                                        .collect(),
+                                };
+                                (vec![], Some("svmInstruction".to_string()), Some(svm_item))
                             }
-                        })
-                        .collect();
-                    let svm_abi = match &contract.abi {
-                        Abi::Svm(SvmAbi {
-                            program_id,
-                            instructions: _,
-                            defined_types,
-                            source,
-                        }) => Some(SvmAbiJson {
-                            program_id: program_id.clone(),
-                            defined_types: defined_types
-                                .iter()
-                                .map(|(name, ty)| (name.clone(), field_type_to_arg_type(ty)))
-                                .collect(),
-                            source: match source {
-                                SvmSchemaSource::AnchorIdl { .. } => "anchorIdl",
-                                SvmSchemaSource::Bundled { .. } => "bundled",
-                                SvmSchemaSource::Inline => "inline",
-                            },
-                        }),
-                        _ => None,
-                    };
-
-                    Ok((
-                        contract.name.as_str(),
-                        ContractConfig {
-                            abi: abi_raw,
-                            handler: contract.handler_path.clone(),
-                            events,
-                            svm_abi,
+                            // Events reuse the instruction descriptor: the
+                            // discriminator to match and the Borsh layout of
+                            // what follows it.
+                            EventKind::SvmEvent(event_kind) => {
+                                let svm_item = SvmEventItem {
+                                    discriminator: Some(event_kind.discriminator.clone()),
+                                    discriminator_byte_len: 8,
+                                    account_filters: vec![],
+                                    is_inner: None,
+                                    accounts: vec![],
+                                    args: event_kind
+                                        .fields
+                                        .iter()
+                                        .map(named_field_to_arg_def)
+                                        .collect(),
+                                };
+                                (vec![], Some("svmEvent".to_string()), Some(svm_item))
+                            }
+                            // Accounts likewise: the discriminator their data
+                            // starts with and the layout of the rest.
+                            EventKind::SvmAccount(account_kind) => {
+                                let svm_item = SvmEventItem {
+                                    discriminator: Some(account_kind.discriminator.clone()),
+                                    discriminator_byte_len: 8,
+                                    account_filters: vec![],
+                                    is_inner: None,
+                                    accounts: vec![],
+                                    args: account_kind
+                                        .fields
+                                        .iter()
+                                        .map(named_field_to_arg_def)
+                                        .collect(),
+                                };
+                                (vec![], Some("svmAccount".to_string()), Some(svm_item))
+                            }
+                        };
+                        ContractEventItem {
+                            name: e.name.clone(),
+                            sighash: e.sighash.clone(),
+                            params,
+                            kind,
+                            block_fields: e
+                                .field_selection
+                                .as_ref()
+                                .map(|fs| fs.block_fields.iter().map(|f| f.name.clone()).collect()),
+                            transaction_fields: e.field_selection.as_ref().map(|fs| {
+                                fs.transaction_fields
+                                    .iter()
+                                    .map(|f| f.name.clone())
+                                    .collect()
+                            }),
+                            transaction_where: e.transaction_where.as_ref().map(Into::into),
+                            params_where: e.params_where.iter().map(Into::into).collect(),
+                            register: e.register.as_ref().map(Into::into),
+                            svm,
+                        }
+                    })
+                    .collect();
+                let mut created: Vec<(&Contract, &Factory)> = cfg
+                    .contracts
+                    .values()
+                    .filter_map(|c| Some((c, c.factory.as_ref()?)))
+                    .filter(|(_, factory)| factory.deployer == contract.name)
+                    .collect();
+                created.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+                events.extend(
+                    created
+                        .into_iter()
+                        .map(|(created, factory)| creation_event_item(created, factory)),
+                );
+                let svm_abi = match &contract.abi {
+                    Abi::Svm(SvmAbi {
+                        program_id,
+                        instructions: _,
+                        events: _,
+                        accounts: _,
+                        defined_types,
+                        source,
+                        decode_errors,
+                    }) => Some(SvmAbiJson {
+                        program_id: program_id.clone(),
+                        defined_types: defined_types
+                            .iter()
+                            .map(|(name, ty)| (name.clone(), field_type_to_arg_type(ty)))
+                            .collect(),
+                        source: match source {
+                            SvmSchemaSource::AnchorIdl { .. } => "anchorIdl",
+                            SvmSchemaSource::Bundled { .. } => "bundled",
+                            SvmSchemaSource::Inline => "inline",
                         },
-                    ))
-                })
-                .collect::<Result<_>>()?;
+                        decode_errors: match decode_errors {
+                            DecodeErrors::Ignore => None,
+                            mode => Some(*mode),
+                        },
+                    }),
+                    _ => None,
+                };
+
+                Ok((
+                    contract.name.as_str(),
+                    ContractConfig {
+                        abi: abi_raw,
+                        handler: contract.handler_path.clone(),
+                        events,
+                        svm_abi,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
 
         // Build ecosystem config
         let (evm, fuel, svm) = match cfg.get_ecosystem() {
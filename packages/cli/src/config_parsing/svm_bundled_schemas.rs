@@ -0,0 +1,769 @@
+//! Bundled `ProgramSchema`s for the native programs most indexers touch,
+//! selected in config with a program's `schema`. `hypersync_client_solana`
+//! only ships Metaplex Token Metadata; these follow its layout.
+//!
+//! Instruction data of these programs isn't Borsh, but for the instructions
+//! bundled here it lines up with it: little-endian integers, `COption<Pubkey>`
+//! packed as a 1-byte tag plus the key, and `u8` enums. Instructions whose
+//! data doesn't (System Program's seeded variants carry a bincode string with
+//! a `u64` length, Token-2022's `Reallocate`/`GetAccountDataSize` and SPL's
+//! `UiAmountToAmount` end in an unprefixed tail, the confidential-transfer
+//! extensions carry zero-knowledge proofs) are left out; declare them with
+//! inline `accounts`/`args` if needed.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use hypersync_client_solana::decode::{
+    EnumVariant, FieldType, InstructionSchema, NamedAccount, NamedField, ProgramSchema,
+};
+
+pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const SPL_TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID: &str =
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const METAPLEX_TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// SPL Token: every instruction but `UiAmountToAmount`, keyed by its 1-byte
+/// tag.
+pub fn spl_token() -> &'static ProgramSchema {
+    static CACHED: OnceLock<ProgramSchema> = OnceLock::new();
+    CACHED.get_or_init(|| {
+        let mut ix = token_instructions(&SPL_AUTHORITY_TYPES);
+        push(
+            &mut ix,
+            &[21],
+            "GetAccountDataSize",
+            &[acc("mint", R)],
+            vec![],
+        );
+        ProgramSchema::build(SPL_TOKEN_PROGRAM_ID.to_string(), ix, BTreeMap::new())
+    })
+}
+
+/// SPL Token-2022: the SPL Token instruction set plus the extension
+/// instructions, which are keyed by their 2-byte `[extension, instruction]`
+/// tag.
+pub fn spl_token_2022() -> &'static ProgramSchema {
+    static CACHED: OnceLock<ProgramSchema> = OnceLock::new();
+    CACHED.get_or_init(|| {
+        let mut ix = token_instructions(&TOKEN_2022_AUTHORITY_TYPES);
+        let mint = || acc("mint", W);
+        let mint_and_authority = || vec![acc("mint", W), acc("authority", S)];
+        let account_and_owner = || vec![acc("account", W), acc("owner", S)];
+
+        push(
+            &mut ix,
+            &[25],
+            "InitializeMintCloseAuthority",
+            &[mint()],
+            vec![field("close_authority", optional_pubkey())],
+        );
+
+        push(
+            &mut ix,
+            &[26, 0],
+            "InitializeTransferFeeConfig",
+            &[mint()],
+            vec![
+                field("transfer_fee_config_authority", optional_pubkey()),
+                field("withdraw_withheld_authority", optional_pubkey()),
+                field("transfer_fee_basis_points", FieldType::U16),
+                field("maximum_fee", FieldType::U64),
+            ],
+        );
+        push(
+            &mut ix,
+            &[26, 1],
+            "TransferCheckedWithFee",
+            &[
+                acc("source", W),
+                acc("mint", R),
+                acc("destination", W),
+                acc("authority", S),
+            ],
+            vec![
+                field("amount", FieldType::U64),
+                field("decimals", FieldType::U8),
+                field("fee", FieldType::U64),
+            ],
+        );
+        push(
+            &mut ix,
+            &[26, 2],
+            "WithdrawWithheldTokensFromMint",
+            &[mint(), acc("destination", W), acc("authority", S)],
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[26, 3],
+            "WithdrawWithheldTokensFromAccounts",
+            &[acc("mint", R), acc("destination", W), acc("authority", S)],
+            vec![field("num_token_accounts", FieldType::U8)],
+        );
+        push(
+            &mut ix,
+            &[26, 4],
+            "HarvestWithheldTokensToMint",
+            &[mint()],
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[26, 5],
+            "SetTransferFee",
+            &mint_and_authority(),
+            vec![
+                field("transfer_fee_basis_points", FieldType::U16),
+                field("maximum_fee", FieldType::U64),
+            ],
+        );
+
+        let account_state =
+            || FieldType::Enum(unit_variants(&["Uninitialized", "Initialized", "Frozen"]));
+        push(
+            &mut ix,
+            &[28, 0],
+            "InitializeDefaultAccountState",
+            &[mint()],
+            vec![field("state", account_state())],
+        );
+        push(
+            &mut ix,
+            &[28, 1],
+            "UpdateDefaultAccountState",
+            &[mint(), acc("freeze_authority", S)],
+            vec![field("state", account_state())],
+        );
+
+        push(
+            &mut ix,
+            &[30, 0],
+            "EnableRequiredMemoTransfers",
+            &account_and_owner(),
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[30, 1],
+            "DisableRequiredMemoTransfers",
+            &account_and_owner(),
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[31],
+            "CreateNativeMint",
+            &[
+                acc("payer", WS),
+                acc("native_mint", W),
+                acc("system_program", R),
+            ],
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[32],
+            "InitializeNonTransferableMint",
+            &[mint()],
+            vec![],
+        );
+
+        // Extension authorities are `OptionalNonZeroPubkey`s: 32 bytes, all
+        // zero (rendered as the System Program id) for none.
+        push(
+            &mut ix,
+            &[33, 0],
+            "InitializeInterestBearingMint",
+            &[mint()],
+            vec![
+                field("rate_authority", FieldType::Pubkey),
+                field("rate", FieldType::I16),
+            ],
+        );
+        push(
+            &mut ix,
+            &[33, 1],
+            "UpdateInterestBearingMintRate",
+            &[mint(), acc("rate_authority", S)],
+            vec![field("rate", FieldType::I16)],
+        );
+
+        push(
+            &mut ix,
+            &[34, 0],
+            "EnableCpiGuard",
+            &account_and_owner(),
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[34, 1],
+            "DisableCpiGuard",
+            &account_and_owner(),
+            vec![],
+        );
+        push(
+            &mut ix,
+            &[35],
+            "InitializePermanentDelegate",
+            &[mint()],
+            vec![field("delegate", FieldType::Pubkey)],
+        );
+
+        push(
+            &mut ix,
+            &[36, 0],
+            "InitializeTransferHook",
+            &[mint()],
+            vec![
+                field("authority", FieldType::Pubkey),
+                field("program_id", FieldType::Pubkey),
+            ],
+        );
+        push(
+            &mut ix,
+            &[36, 1],
+            "UpdateTransferHook",
+            &mint_and_authority(),
+            vec![field("program_id", FieldType::Pubkey)],
+        );
+        push(
+            &mut ix,
+            &[38],
+            "WithdrawExcessLamports",
+            &[acc("source", W), acc("destination", W), acc("authority", S)],
+            vec![],
+        );
+
+        for (extension, pointer, address) in [
+            (39, "MetadataPointer", "metadata_address"),
+            (40, "GroupPointer", "group_address"),
+            (41, "GroupMemberPointer", "member_address"),
+        ] {
+            push(
+                &mut ix,
+                &[extension, 0],
+                &format!("Initialize{pointer}"),
+                &[mint()],
+                vec![
+                    field("authority", FieldType::Pubkey),
+                    field(address, FieldType::Pubkey),
+                ],
+            );
+            push(
+                &mut ix,
+                &[extension, 1],
+                &format!("Update{pointer}"),
+                &mint_and_authority(),
+                vec![field(address, FieldType::Pubkey)],
+            );
+        }
+
+        push(
+            &mut ix,
+            &[43, 0],
+            "InitializeScaledUiAmount",
+            &[mint()],
+            vec![
+                field("authority", FieldType::Pubkey),
+                field("multiplier", FieldType::F64),
+            ],
+        );
+        push(
+            &mut ix,
+            &[43, 1],
+            "UpdateScaledUiAmountMultiplier",
+            &mint_and_authority(),
+            vec![
+                field("multiplier", FieldType::F64),
+                field("effective_timestamp", FieldType::I64),
+            ],
+        );
+
+        push(
+            &mut ix,
+            &[44, 0],
+            "InitializePausable",
+            &[mint()],
+            vec![field("authority", FieldType::Pubkey)],
+        );
+        push(&mut ix, &[44, 1], "Pause", &mint_and_authority(), vec![]);
+        push(&mut ix, &[44, 2], "Resume", &mint_and_authority(), vec![]);
+
+        ProgramSchema::build(SPL_TOKEN_2022_PROGRAM_ID.to_string(), ix, BTreeMap::new())
+    })
+}
+
+/// Associated Token Account program. The legacy `Create`, sent with empty
+/// data, has no tag to match on; clients have sent `0` since v1.1.
+pub fn associated_token_account() -> &'static ProgramSchema {
+    static CACHED: OnceLock<ProgramSchema> = OnceLock::new();
+    CACHED.get_or_init(|| {
+        let mut ix = BTreeMap::new();
+        let create_accounts = [
+            acc("funding_account", WS),
+            acc("associated_token_account", W),
+            acc("wallet", R),
+            acc("mint", R),
+            acc("system_program", R),
+            acc("token_program", R),
+        ];
+        push(&mut ix, &[0], "Create", &create_accounts, vec![]);
+        push(&mut ix, &[1], "CreateIdempotent", &create_accounts, vec![]);
+        push(
+            &mut ix,
+            &[2],
+            "RecoverNested",
+            &[
+                acc("nested_associated_token_account", W),
+                acc("nested_mint", R),
+                acc("destination_associated_token_account", W),
+                acc("owner_associated_token_account", R),
+                acc("owner_mint", R),
+                acc("wallet", WS),
+                acc("token_program", R),
+            ],
+            vec![],
+        );
+        ProgramSchema::build(
+            ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID.to_string(),
+            ix,
+            BTreeMap::new(),
+        )
+    })
+}
+
+/// System Program, keyed by the little-endian `u32` variant index of its
+/// bincode-encoded instructions.
+pub fn system_program() -> &'static ProgramSchema {
+    static CACHED: OnceLock<ProgramSchema> = OnceLock::new();
+    CACHED.get_or_init(|| {
+        let mut ix = BTreeMap::new();
+        let mut push_system =
+            |index: u8, name: &str, accounts: &[NamedAccount], args: Vec<NamedField>| {
+                push(&mut ix, &[index, 0, 0, 0], name, accounts, args)
+            };
+        push_system(
+            0,
+            "CreateAccount",
+            &[acc("funding_account", WS), acc("new_account", WS)],
+            vec![
+                field("lamports", FieldType::U64),
+                field("space", FieldType::U64),
+                field("owner", FieldType::Pubkey),
+            ],
+        );
+        push_system(
+            1,
+            "Assign",
+            &[acc("assigned_account", WS)],
+            vec![field("owner", FieldType::Pubkey)],
+        );
+        push_system(
+            2,
+            "Transfer",
+            &[acc("funding_account", WS), acc("recipient_account", W)],
+            vec![field("lamports", FieldType::U64)],
+        );
+        push_system(
+            4,
+            "AdvanceNonceAccount",
+            &[
+                acc("nonce_account", W),
+                acc("recent_blockhashes_sysvar", R),
+                acc("nonce_authority", S),
+            ],
+            vec![],
+        );
+        push_system(
+            5,
+            "WithdrawNonceAccount",
+            &[
+                acc("nonce_account", W),
+                acc("recipient_account", W),
+                acc("recent_blockhashes_sysvar", R),
+                acc("rent_sysvar", R),
+                acc("nonce_authority", S),
+            ],
+            vec![field("lamports", FieldType::U64)],
+        );
+        push_system(
+            6,
+            "InitializeNonceAccount",
+            &[
+                acc("nonce_account", W),
+                acc("recent_blockhashes_sysvar", R),
+                acc("rent_sysvar", R),
+            ],
+            vec![field("nonce_authority", FieldType::Pubkey)],
+        );
+        push_system(
+            7,
+            "AuthorizeNonceAccount",
+            &[acc("nonce_account", W), acc("nonce_authority", S)],
+            vec![field("new_nonce_authority", FieldType::Pubkey)],
+        );
+        push_system(
+            8,
+            "Allocate",
+            &[acc("new_account", WS)],
+            vec![field("space", FieldType::U64)],
+        );
+        push_system(
+            12,
+            "UpgradeNonceAccount",
+            &[acc("nonce_account", W)],
+            vec![],
+        );
+        ProgramSchema::build(SYSTEM_PROGRAM_ID.to_string(), ix, BTreeMap::new())
+    })
+}
+
+const SPL_AUTHORITY_TYPES: [&str; 4] = [
+    "MintTokens",
+    "FreezeAccount",
+    "AccountOwner",
+    "CloseAccount",
+];
+
+const TOKEN_2022_AUTHORITY_TYPES: [&str; 17] = [
+    "MintTokens",
+    "FreezeAccount",
+    "AccountOwner",
+    "CloseAccount",
+    "TransferFeeConfig",
+    "WithheldWithdraw",
+    "CloseMint",
+    "InterestRate",
+    "PermanentDelegate",
+    "ConfidentialTransferMint",
+    "TransferHookProgramId",
+    "ConfidentialTransferFeeConfig",
+    "MetadataPointer",
+    "GroupPointer",
+    "GroupMemberPointer",
+    "ScaledUiAmount",
+    "Pause",
+];
+
+/// The instructions SPL Token and Token-2022 share, 0 through 23 minus the
+/// ones with an unprefixed tail. Multisig signers follow the listed accounts
+/// and come back as extra accounts.
+fn token_instructions(authority_types: &[&str]) -> BTreeMap<Vec<u8>, InstructionSchema> {
+    let mut ix = BTreeMap::new();
+    let amount = || field("amount", FieldType::U64);
+    let decimals = || field("decimals", FieldType::U8);
+    let initialize_mint_args = || {
+        vec![
+            decimals(),
+            field("mint_authority", FieldType::Pubkey),
+            field("freeze_authority", optional_pubkey()),
+        ]
+    };
+
+    push(
+        &mut ix,
+        &[0],
+        "InitializeMint",
+        &[acc("mint", W), acc("rent_sysvar", R)],
+        initialize_mint_args(),
+    );
+    push(
+        &mut ix,
+        &[1],
+        "InitializeAccount",
+        &[
+            acc("account", W),
+            acc("mint", R),
+            acc("owner", R),
+            acc("rent_sysvar", R),
+        ],
+        vec![],
+    );
+    push(
+        &mut ix,
+        &[2],
+        "InitializeMultisig",
+        &[acc("multisig", W), acc("rent_sysvar", R)],
+        vec![field("m", FieldType::U8)],
+    );
+    push(
+        &mut ix,
+        &[3],
+        "Transfer",
+        &[acc("source", W), acc("destination", W), acc("authority", S)],
+        vec![amount()],
+    );
+    push(
+        &mut ix,
+        &[4],
+        "Approve",
+        &[acc("source", W), acc("delegate", R), acc("owner", S)],
+        vec![amount()],
+    );
+    push(
+        &mut ix,
+        &[5],
+        "Revoke",
+        &[acc("source", W), acc("owner", S)],
+        vec![],
+    );
+    push(
+        &mut ix,
+        &[6],
+        "SetAuthority",
+        &[acc("owned", W), acc("current_authority", S)],
+        vec![
+            field(
+                "authority_type",
+                FieldType::Enum(unit_variants(authority_types)),
+            ),
+            field("new_authority", optional_pubkey()),
+        ],
+    );
+    push(
+        &mut ix,
+        &[7],
+        "MintTo",
+        &[acc("mint", W), acc("account", W), acc("mint_authority", S)],
+        vec![amount()],
+    );
+    push(
+        &mut ix,
+        &[8],
+        "Burn",
+        &[acc("account", W), acc("mint", W), acc("authority", S)],
+        vec![amount()],
+    );
+    push(
+        &mut ix,
+        &[9],
+        "CloseAccount",
+        &[
+            acc("account", W),
+            acc("destination", W),
+            acc("authority", S),
+        ],
+        vec![],
+    );
+    push(
+        &mut ix,
+        &[10],
+        "FreezeAccount",
+        &[acc("account", W), acc("mint", R), acc("authority", S)],
+        vec![],
+    );
+    push(
+        &mut ix,
+        &[11],
+        "ThawAccount",
+        &[acc("account", W), acc("mint", R), acc("authority", S)],
+        vec![],
+    );
+    push(
+        &mut ix,
+        &[12],
+        "TransferChecked",
+        &[
+            acc("source", W),
+            acc("mint", R),
+            acc("destination", W),
+            acc("authority", S),
+        ],
+        vec![amount(), decimals()],
+    );
+    push(
+        &mut ix,
+        &[13],
+        "ApproveChecked",
+        &[
+            acc("source", W),
+            acc("mint", R),
+            acc("delegate", R),
+            acc("owner", S),
+        ],
+        vec![amount(), decimals()],
+    );
+    push(
+        &mut ix,
+        &[14],
+        "MintToChecked",
+        &[acc("mint", W), acc("account", W), acc("mint_authority", S)],
+        vec![amount(), decimals()],
+    );
+    push(
+        &mut ix,
+        &[15],
+        "BurnChecked",
+        &[acc("account", W), acc("mint", W), acc("authority", S)],
+        vec![amount(), decimals()],
+    );
+    push(
+        &mut ix,
+        &[16],
+        "InitializeAccount2",
+        &[acc("account", W), acc("mint", R), acc("rent_sysvar", R)],
+        vec![field("owner", FieldType::Pubkey)],
+    );
+    push(&mut ix, &[17], "SyncNative", &[acc("account", W)], vec![]);
+    push(
+        &mut ix,
+        &[18],
+        "InitializeAccount3",
+        &[acc("account", W), acc("mint", R)],
+        vec![field("owner", FieldType::Pubkey)],
+    );
+    push(
+        &mut ix,
+        &[19],
+        "InitializeMultisig2",
+        &[acc("multisig", W)],
+        vec![field("m", FieldType::U8)],
+    );
+    push(
+        &mut ix,
+        &[20],
+        "InitializeMint2",
+        &[acc("mint", W)],
+        initialize_mint_args(),
+    );
+    push(
+        &mut ix,
+        &[22],
+        "InitializeImmutableOwner",
+        &[acc("account", W)],
+        vec![],
+    );
+    push(
+        &mut ix,
+        &[23],
+        "AmountToUiAmount",
+        &[acc("mint", R)],
+        vec![amount()],
+    );
+    ix
+}
+
+/// Account access: read-only, writable, signer, or writable signer.
+#[derive(Clone, Copy)]
+enum Access {
+    R,
+    W,
+    S,
+    WS,
+}
+use Access::{R, S, W, WS};
+
+fn acc(name: &str, access: Access) -> NamedAccount {
+    NamedAccount {
+        name: name.to_string(),
+        writable: matches!(access, W | WS),
+        signer: matches!(access, S | WS),
+        optional: false,
+    }
+}
+
+fn field(name: &str, ty: FieldType) -> NamedField {
+    NamedField {
+        name: name.to_string(),
+        ty,
+    }
+}
+
+/// `COption<Pubkey>` as the token programs pack it into instruction data.
+fn optional_pubkey() -> FieldType {
+    FieldType::Option(Box::new(FieldType::Pubkey))
+}
+
+fn unit_variants(names: &[&str]) -> Vec<EnumVariant> {
+    names
+        .iter()
+        .map(|name| EnumVariant {
+            name: name.to_string(),
+            fields: None,
+        })
+        .collect()
+}
+
+fn push(
+    ix: &mut BTreeMap<Vec<u8>, InstructionSchema>,
+    discriminator: &[u8],
+    name: &str,
+    accounts: &[NamedAccount],
+    args: Vec<NamedField>,
+) {
+    ix.insert(
+        discriminator.to_vec(),
+        InstructionSchema {
+            name: name.to_string(),
+            discriminator: discriminator.to_vec(),
+            accounts: accounts.to_vec(),
+            args,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypersync_client_solana::decode::decode_instruction;
+    use hypersync_client_solana::simple_types::InstructionCall;
+
+    fn call(program: &ProgramSchema, data: Vec<u8>, accounts: usize) -> InstructionCall {
+        InstructionCall {
+            executing_account: Some(program.program_id.parse().unwrap()),
+            account_arguments: Some(vec![SYSTEM_PROGRAM_ID.parse().unwrap(); accounts]),
+            data: Some(data),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_spl_transfer_checked() {
+        let schema = spl_token();
+        let mut data = vec![12];
+        data.extend(1_000_000u64.to_le_bytes());
+        data.push(6);
+        let decoded = decode_instruction(schema, &call(schema, data, 4)).unwrap();
+        assert_eq!(decoded.name, "TransferChecked");
+        assert_eq!(
+            decoded.args,
+            serde_json::json!({"amount": "1000000", "decimals": 6})
+        );
+    }
+
+    #[test]
+    fn token_2022_extensions_take_the_longer_tag() {
+        let schema = spl_token_2022();
+        let mut data = vec![26, 1];
+        data.extend(500u64.to_le_bytes());
+        data.push(9);
+        data.extend(5u64.to_le_bytes());
+        let decoded = decode_instruction(schema, &call(schema, data, 4)).unwrap();
+        assert_eq!(decoded.name, "TransferCheckedWithFee");
+
+        // The base set still decodes on its 1-byte tag.
+        let mut data = vec![6, 2, 0];
+        let decoded = decode_instruction(schema, &call(schema, data.clone(), 2)).unwrap();
+        assert_eq!(decoded.name, "SetAuthority");
+        data[1] = 16;
+        let decoded = decode_instruction(schema, &call(schema, data, 2)).unwrap();
+        assert_eq!(
+            decoded.args["authority_type"],
+            serde_json::json!({"Pause": {}})
+        );
+    }
+
+    #[test]
+    fn decodes_system_transfer() {
+        let schema = system_program();
+        let mut data = vec![2, 0, 0, 0];
+        data.extend(42u64.to_le_bytes());
+        let decoded = decode_instruction(schema, &call(schema, data, 2)).unwrap();
+        assert_eq!(decoded.name, "Transfer");
+        assert_eq!(decoded.args, serde_json::json!({"lamports": "42"}));
+    }
+}
@@ -5,10 +5,14 @@ use super::{
     human_config::{
         self,
         evm::{
-            Chain as EvmChain, EventConfig as EvmEventConfig, For, HumanConfig as EvmConfig, Rpc,
-            RpcSelection,
+            Chain as EvmChain, EventConfig as EvmEventConfig, EventRegisterConfig,
+            EventType as EvmEventType, EventWhere as EvmEventWhere, FactoryConfig, FactorySource,
+            Finality, For, FunctionConfig as EvmFunctionConfig, HumanConfig as EvmConfig,
+            ParamLiteral, Quantity, QuantityRange, Rpc, RpcSelection,
+            TransactionStatus as EvmTransactionStatus,
         },
         fuel::{EventConfig as FuelEventConfig, HumanConfig as FuelConfig},
+        svm::DecodeErrors,
         HumanConfig,
     },
     hypersync_endpoints,
@@ -16,19 +20,23 @@ use super::{
 };
 use crate::utils::dotenv::{self, EnvMap};
 use crate::{
-    config_parsing::human_config::evm::RpcTransactionField,
+    config_parsing::human_config::evm::{HyperSyncTransactionField, RpcTransactionField},
     constants::{links, project_paths::DEFAULT_SCHEMA_PATH},
     evm::abi::AbiOrNestedAbi,
     fuel::abi::{FuelAbi, BURN_EVENT_NAME, CALL_EVENT_NAME, MINT_EVENT_NAME, TRANSFER_EVENT_NAME},
     project_paths::{path_utils, ParsedProjectPaths},
     type_schema::TypeIdent,
-    utils::unique_hashmap,
+    utils::{text::Capitalize, unique_hashmap},
 };
-use alloy_json_abi::{Event as AlloyEvent, JsonAbi};
+use alloy_dyn_abi::DynSolType;
+use alloy_json_abi::{Event as AlloyEvent, Function as AlloyFunction, JsonAbi};
 use anyhow::{anyhow, Context, Result};
+use heck::ToSnakeCase;
 use itertools::Itertools;
 
-use super::abi_compat::EventParam;
+use super::abi_compat::{AbiType, EventParam};
+use super::anchor_idl;
+use super::svm_bundled_schemas;
 use regex::Regex;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
@@ -37,9 +45,9 @@ use std::{
 };
 
 use hypersync_client_solana::decode::{
-    metaplex_token_metadata, schema_from_anchor_idl_json, EnumVariant as SvmEnumVariant,
-    FieldType as SvmFieldType, InstructionSchema as SvmInstructionSchema,
-    NamedField as SvmNamedField, ProgramSchema as SvmProgramSchema,
+    metaplex_token_metadata, EnumVariant as SvmEnumVariant, FieldType as SvmFieldType,
+    InstructionSchema as SvmInstructionSchema, NamedField as SvmNamedField,
+    ProgramSchema as SvmProgramSchema,
 };
 
 type ContractNameKey = String;
@@ -1099,23 +1107,29 @@ impl SystemConfig {
                 validation::validate_deserialized_config_yaml(evm_config)?;
 
                 let has_rpc_src = evm_config.chains.iter().any(evm_chain_has_rpc_src);
+                let has_hypersync_src = evm_config.chains.iter().any(evm_chain_has_hypersync_src);
 
                 //Add all global contracts
                 if let Some(global_contracts) = &evm_config.contracts {
                     for g_contract in global_contracts {
-                        let contract_has_rpc_src = evm_config.chains.iter().any(|chain| {
-                            evm_chain_has_rpc_src(chain)
-                                && chain.contracts.as_ref().is_some_and(|contracts| {
-                                    contracts
-                                        .iter()
-                                        .any(|contract| contract.name == g_contract.name)
-                                })
+                        let contract_chains = evm_config.chains.iter().filter(|chain| {
+                            chain.contracts.as_ref().is_some_and(|contracts| {
+                                contracts
+                                    .iter()
+                                    .any(|contract| contract.name == g_contract.name)
+                            })
                         });
+                        let contract_has_rpc_src =
+                            contract_chains.clone().any(evm_chain_has_rpc_src);
+                        let contract_has_hypersync_src =
+                            contract_chains.clone().any(evm_chain_has_hypersync_src);
                         let (events, evm_abi) = Event::from_evm_events_config(
                             g_contract.config.events.clone(),
+                            g_contract.config.functions.as_deref().unwrap_or_default(),
                             &g_contract.config.abi_file_path,
                             source,
                             contract_has_rpc_src,
+                            contract_has_hypersync_src,
                         )
                         .context(format!(
                             "Failed parsing abi types for events in global contract {}",
@@ -1128,7 +1142,8 @@ impl SystemConfig {
                             events,
                             Abi::Evm(evm_abi),
                         )
-                        .context("Failed parsing globally defined contract")?;
+                        .context("Failed parsing globally defined contract")?
+                        .with_factory(g_contract.config.factory.as_ref());
 
                         //Check if contract exists
                         unique_hashmap::try_insert(&mut contracts, contract.name.clone(), contract)
@@ -1138,15 +1153,18 @@ impl SystemConfig {
 
                 for network in &evm_config.chains {
                     let network_has_rpc_src = evm_chain_has_rpc_src(network);
+                    let network_has_hypersync_src = evm_chain_has_hypersync_src(network);
                     for contract in network.contracts.clone().unwrap_or_default() {
                         //Add values for local contract
                         match contract.config {
                             Some(l_contract) => {
                                 let (events, evm_abi) = Event::from_evm_events_config(
                                     l_contract.events,
+                                    l_contract.functions.as_deref().unwrap_or_default(),
                                     &l_contract.abi_file_path,
                                     source,
                                     network_has_rpc_src,
+                                    network_has_hypersync_src,
                                 )
                                 .context(format!(
                                     "Failed parsing abi types for events in contract {} on \
@@ -1164,7 +1182,8 @@ impl SystemConfig {
                                     "Failed parsing locally defined network contract at network \
                                      id {}",
                                     network.id
-                                ))?;
+                                ))?
+                                .with_factory(l_contract.factory.as_ref());
 
                                 //Check if contract exists
                                 unique_hashmap::try_insert(
@@ -1195,6 +1214,10 @@ impl SystemConfig {
                     }
 
                     let sync_source = DataSource::from_evm_network_config(network.clone())?;
+                    if let Some(finality) = network.finality {
+                        validate_finality(finality, &sync_source, network_has_rpc_src)
+                            .context(format!("Invalid finality for chain {}", network.id))?;
+                    }
 
                     let contracts: Vec<ChainContract> = network
                         .contracts
@@ -1206,6 +1229,7 @@ impl SystemConfig {
                             name: c.name,
                             addresses: c.address.into(),
                             start_block: c.start_block,
+                            ttl_blocks: c.ttl_blocks,
                         })
                         .collect();
 
@@ -1215,6 +1239,7 @@ impl SystemConfig {
                         max_reorg_depth: network
                             .max_reorg_depth
                             .or_else(|| get_max_reorg_depth_from_id(network.id)),
+                        finality: network.finality,
                         block_lag: network.block_lag,
                         start_block: network.start_block,
                         end_block: network.end_block,
@@ -1234,8 +1259,11 @@ impl SystemConfig {
                         },
                     ),
                     has_rpc_src,
+                    has_hypersync_src,
                 )?;
 
+                validate_registrations(&contracts)?;
+
                 let chain_id_mode = ChainIdMode::resolve(&chains)?;
 
                 Ok(SystemConfig {
@@ -1368,6 +1396,7 @@ impl SystemConfig {
                             name: c.name,
                             addresses: c.address.into(),
                             start_block: c.start_block,
+                            ttl_blocks: c.ttl_blocks,
                         })
                         .collect();
 
@@ -1377,6 +1406,7 @@ impl SystemConfig {
                         start_block: network.start_block,
                         end_block: network.end_block,
                         max_reorg_depth: network.max_reorg_depth,
+                        finality: None,
                         block_lag: network.block_lag,
                         sync_source,
                         contracts,
@@ -1433,11 +1463,11 @@ impl SystemConfig {
                             resolve_program_schema(program, source).with_context(|| {
                                 format!(
                                     "Resolving Borsh schema for program '{}' ({})",
-                                    program.name, program.program_id
+                                    program.name,
+                                    program.program_id().unwrap_or_default()
                                 )
                             })?;
-                        let events = program
-                            .instructions
+                        let mut events = program_instructions(program, &svm_abi)?
                             .iter()
                             .map(|instr| -> Result<Event> {
                                 let (normalized_discriminator, byte_len) =
@@ -1485,10 +1515,69 @@ impl SystemConfig {
                                     sighash: normalized_discriminator.clone().unwrap_or_default(),
                                     event_signature: String::new(),
                                     field_selection: None,
+                                    transaction_where: None,
+                                    params_where: vec![],
+                                    register: None,
                                 })
                             })
                             .collect::<Result<Vec<_>>>()?;
+                        let anchor_layouts = program
+                            .events
+                            .iter()
+                            .flatten()
+                            .map(|event| {
+                                (
+                                    AnchorLayoutKind::Event,
+                                    &event.name,
+                                    event.discriminator.as_deref(),
+                                    event.fields.as_ref(),
+                                )
+                            })
+                            .chain(program.accounts.iter().flatten().map(|account| {
+                                (
+                                    AnchorLayoutKind::Account,
+                                    &account.name,
+                                    account.discriminator.as_deref(),
+                                    account.fields.as_ref(),
+                                )
+                            }))
+                            .map(|(kind, name, discriminator, fields)| -> Result<Event> {
+                                let (discriminator, fields) = resolve_anchor_layout(
+                                    kind,
+                                    name,
+                                    discriminator,
+                                    fields,
+                                    &svm_abi,
+                                )
+                                .with_context(|| {
+                                    format!("Layout for {} '{name}'", kind.label().to_lowercase())
+                                })?;
+                                let discriminator =
+                                    discriminator.iter().fold(String::from("0x"), |hex, byte| {
+                                        hex + &format!("{byte:02x}")
+                                    });
+                                let layout = SvmAnchorEventKind {
+                                    discriminator: discriminator.clone(),
+                                    fields,
+                                };
+                                Ok(Event {
+                                    name: name.clone(),
+                                    sighash: discriminator,
+                                    kind: match kind {
+                                        AnchorLayoutKind::Event => EventKind::SvmEvent(layout),
+                                        AnchorLayoutKind::Account => EventKind::SvmAccount(layout),
+                                    },
+                                    event_signature: String::new(),
+                                    field_selection: None,
+                                    transaction_where: None,
+                                    params_where: vec![],
+                                    register: None,
+                                })
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        events.extend(anchor_layouts);
 
+                        let program_id = svm_abi.program_id.clone();
                         let contract = Contract::new(
                             program.name.clone(),
                             program.handler.clone(),
@@ -1498,8 +1587,9 @@ impl SystemConfig {
                         contracts.insert(contract.name.clone(), contract.clone());
                         chain_contracts.push(ChainContract {
                             name: program.name.clone(),
-                            addresses: vec![program.program_id.clone()],
+                            addresses: vec![program_id],
                             start_block: None,
+                            ttl_blocks: None,
                         });
                     }
 
@@ -1509,6 +1599,7 @@ impl SystemConfig {
                         start_block: network.start_block,
                         end_block: network.end_block,
                         max_reorg_depth: None,
+                        finality: None,
                         block_lag: network.block_lag,
                         sync_source,
                         contracts: chain_contracts,
@@ -1687,9 +1778,37 @@ fn default_rpc_for(chain: &EvmChain) -> For {
     }
 }
 
+/// The block-tag finalities are read from an RPC and the rollback guard comes
+/// with HyperSync responses, so each needs that source on the chain.
+fn validate_finality(finality: Finality, sync_source: &DataSource, has_rpc: bool) -> Result<()> {
+    match finality {
+        Finality::Depth => Ok(()),
+        Finality::Safe | Finality::Finalized if !has_rpc => Err(anyhow!(
+            "`finality: {}` reads the block tag from an RPC, but the chain has no `rpc` \
+             configured.",
+            if finality == Finality::Safe {
+                "safe"
+            } else {
+                "finalized"
+            }
+        )),
+        Finality::Safe | Finality::Finalized => Ok(()),
+        Finality::RollbackGuard => match sync_source {
+            DataSource::Evm {
+                main: MainEvmDataSource::HyperSync { .. },
+                ..
+            } => Ok(()),
+            _ => Err(anyhow!(
+                "`finality: rollback_guard` comes from HyperSync responses, but the chain syncs \
+                 from an RPC."
+            )),
+        },
+    }
+}
+
 /// Whether any of a chain's data reaches the indexer over RPC. Every RPC counts,
-/// whatever it's `for`: a fallback or realtime source parses events with the
-/// same field registry a sync one does, so a field RPC can't deliver would go
+/// whatever it's `for`: a fallback or realtime source fills the same fields a
+/// sync one does, so a field RPC can't deliver would go
 /// missing for whichever blocks that source served.
 fn evm_chain_has_rpc_src(chain: &EvmChain) -> bool {
     match &chain.rpc {
@@ -1699,6 +1818,19 @@ fn evm_chain_has_rpc_src(chain: &EvmChain) -> bool {
     }
 }
 
+/// Whether HyperSync syncs the chain's history: no RPC is `for: sync`, either
+/// explicitly or by default.
+fn evm_chain_has_hypersync_src(chain: &EvmChain) -> bool {
+    let default_for = default_rpc_for(chain);
+    let syncs = |rpc: &Rpc| rpc.source_for.as_ref().unwrap_or(&default_for) == &For::Sync;
+    match &chain.rpc {
+        Some(RpcSelection::Url(_)) => default_for != For::Sync,
+        Some(RpcSelection::Single(rpc)) => !syncs(rpc),
+        Some(RpcSelection::List(rpcs)) => !rpcs.iter().any(syncs),
+        None => true,
+    }
+}
+
 impl DataSource {
     fn from_evm_network_config(network: EvmChain) -> Result<Self> {
         let default_for = default_rpc_for(&network);
@@ -1723,6 +1855,9 @@ impl DataSource {
                 backoff_millis: None,
                 fallback_stall_timeout: None,
                 query_timeout_millis: None,
+                max_batch_size: None,
+                additional_urls: None,
+                hedge_after_millis: None,
                 polling_interval: None,
             }],
             Some(RpcSelection::Single(rpc)) => vec![resolve_for(rpc)],
@@ -1750,9 +1885,43 @@ impl DataSource {
                     }
                     None => None,
                 };
+                let additional_urls = match &rpc.additional_urls {
+                    Some(urls) => Some(
+                        urls.iter()
+                            .map(|additional_url| {
+                                parse_url(additional_url).ok_or_else(|| {
+                                    anyhow!(
+                                        "The RPC url \"{}\" is incorrect format. The RPC url \
+                                         needs to start with either http:// or https://",
+                                        additional_url
+                                    )
+                                })
+                            })
+                            .collect::<Result<Vec<_>>>()?,
+                    ),
+                    None => None,
+                };
+                if rpc.hedge_after_millis.is_some()
+                    && additional_urls.as_ref().is_none_or(|urls| urls.is_empty())
+                {
+                    return Err(anyhow!(
+                        "The RPC \"{}\" sets hedge_after_millis without additional_urls. \
+                         Hedging sends slow requests to another endpoint, so it needs at \
+                         least one additional url.",
+                        url
+                    ));
+                }
+                if rpc.max_batch_size == Some(0) {
+                    return Err(anyhow!(
+                        "The RPC \"{}\" sets max_batch_size to 0. Every batch request carries \
+                         at least one call; set it to 1 to disable batching.",
+                        url
+                    ));
+                }
                 rpcs.push(Rpc {
                     url,
                     ws,
+                    additional_urls,
                     ..rpc.clone()
                 })
               }
@@ -1811,6 +1980,9 @@ pub struct Chain {
     pub start_block: u64,
     pub end_block: Option<u64>,
     pub max_reorg_depth: Option<u32>,
+    /// EVM only: how the blocks that can no longer reorg are decided. `None`
+    /// is `Finality::Depth`.
+    pub finality: Option<Finality>,
     pub block_lag: Option<u32>,
     pub contracts: Vec<ChainContract>,
 }
@@ -1820,6 +1992,7 @@ pub struct ChainContract {
     pub name: ContractNameKey,
     pub addresses: Vec<String>,
     pub start_block: Option<u64>,
+    pub ttl_blocks: Option<u64>,
 }
 
 impl ChainContract {
@@ -1866,6 +2039,21 @@ impl EvmAbi {
         )
     }
 
+    /// A function's signature in the same form as an event's, inputs named:
+    /// `transfer(address to, uint256 amount)`.
+    pub fn function_signature_from_abi_function(abi_function: &AlloyFunction) -> String {
+        format!(
+            "{}({})",
+            abi_function.name,
+            abi_function
+                .inputs
+                .iter()
+                .map(|input| format!("{} {}", input.selector_type(), input.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
     pub fn get_event_signatures(&self) -> Vec<String> {
         self.typed
             .events()
@@ -1908,35 +2096,54 @@ impl EvmAbi {
     }
 }
 
-/// Base58 program id for the bundled Metaplex Token Metadata schema. Kept
-/// here (rather than imported from the upstream crate) so a future bundled
-/// schema can be added by appending a row to the `bundled_program_schemas`
-/// table without leaking strings across the module boundary.
-const METAPLEX_TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
-
-/// One row in the bundled-programs table: `(program_id, source_name,
-/// accessor returning the upstream `ProgramSchema`)`.
+/// One row in the bundled-programs table: `(schema, source_name, accessor
+/// returning the `ProgramSchema`)`.
 type BundledProgramRow = (
-    &'static str,
+    human_config::svm::BundledSchema,
     &'static str,
     fn() -> &'static SvmProgramSchema,
 );
 
-/// Table of bundled programs. Lookup by base58 `program_id`. To add a
-/// program: ship a `ProgramSchema` constant in `hypersync_client_solana`,
-/// expose a public accessor, then add a row here.
+/// Table of bundled programs, selected by a program's `schema` or looked up
+/// by its base58 `program_id`. To add a program: ship a `ProgramSchema` in
+/// `svm_bundled_schemas` (or `hypersync_client_solana`), add a
+/// `BundledSchema` variant, then add a row here.
 fn bundled_program_schemas() -> Vec<BundledProgramRow> {
-    vec![(
-        METAPLEX_TOKEN_METADATA_PROGRAM_ID,
-        "metaplex_token_metadata",
-        metaplex_token_metadata,
-    )]
+    use human_config::svm::BundledSchema;
+    vec![
+        (
+            BundledSchema::MetaplexTokenMetadata,
+            "metaplex_token_metadata",
+            metaplex_token_metadata,
+        ),
+        (
+            BundledSchema::SplToken,
+            "spl_token",
+            svm_bundled_schemas::spl_token,
+        ),
+        (
+            BundledSchema::SplToken2022,
+            "spl_token_2022",
+            svm_bundled_schemas::spl_token_2022,
+        ),
+        (
+            BundledSchema::AssociatedTokenAccount,
+            "associated_token_account",
+            svm_bundled_schemas::associated_token_account,
+        ),
+        (
+            BundledSchema::SystemProgram,
+            "system_program",
+            svm_bundled_schemas::system_program,
+        ),
+    ]
 }
 
 fn resolve_program_schema(
     program: &human_config::svm::Program,
     source: &dyn ConfigSource,
 ) -> Result<SvmAbi> {
+    let program_id = program.program_id();
     let any_instruction_carries_schema = program
         .instructions
         .iter()
@@ -1953,41 +2160,132 @@ fn resolve_program_schema(
         let resolved = source
             .read_project_relative_file(idl_path)
             .with_context(|| format!("reading IDL at '{idl_path}'"))?;
-        let schema = schema_from_anchor_idl_json(&resolved.raw)
+        let idl = anchor_idl::parse(&resolved.raw)
             .with_context(|| format!("parsing IDL at '{}'", resolved.path.display()))?;
+        let mut instructions: BTreeMap<Vec<u8>, SvmInstructionSchema> = BTreeMap::new();
+        for instruction in idl.instructions {
+            if let Some(other) = instructions.get(&instruction.discriminator) {
+                return Err(anyhow!(
+                    "IDL at '{}': instructions '{}' and '{}' share a discriminator",
+                    resolved.path.display(),
+                    other.name,
+                    instruction.name
+                ));
+            }
+            instructions.insert(instruction.discriminator.clone(), instruction);
+        }
         return Ok(SvmAbi {
-            program_id: program.program_id.clone(),
-            instructions: schema.instructions,
-            defined_types: schema.defined_types,
+            program_id: program_id.unwrap_or_default().to_string(),
+            instructions,
+            defined_types: idl.defined_types,
+            events: idl.events,
+            accounts: idl.accounts,
             source: SvmSchemaSource::AnchorIdl {
                 path: idl_path.to_string(),
             },
+            decode_errors: program.decode_errors.unwrap_or_default(),
         });
     }
 
-    if !any_instruction_carries_schema {
-        if let Some((_, name, getter)) = bundled_program_schemas()
-            .into_iter()
-            .find(|(pid, _, _)| *pid == program.program_id.as_str())
-        {
-            let schema = getter();
-            return Ok(SvmAbi {
-                program_id: program.program_id.clone(),
-                instructions: schema.instructions.clone(),
-                defined_types: schema.defined_types.clone(),
-                source: SvmSchemaSource::Bundled { name },
-            });
-        }
+    // An explicit `schema` always applies (per-instruction overrides still
+    // win in `resolve_instruction_layout`); one matched on the program id
+    // only when no instruction carries its own layout.
+    let bundled = bundled_program_schemas()
+        .into_iter()
+        .find(|(schema, _, _)| match program.schema {
+            Some(selected) => *schema == selected,
+            None => !any_instruction_carries_schema && Some(schema.program_id()) == program_id,
+        });
+    if let Some((_, name, getter)) = bundled {
+        let schema = getter();
+        return Ok(SvmAbi {
+            program_id: program_id.unwrap_or_default().to_string(),
+            instructions: schema.instructions.clone(),
+            defined_types: schema.defined_types.clone(),
+            events: BTreeMap::new(),
+            accounts: BTreeMap::new(),
+            source: SvmSchemaSource::Bundled { name },
+            decode_errors: program.decode_errors.unwrap_or_default(),
+        });
     }
 
     Ok(SvmAbi {
-        program_id: program.program_id.clone(),
+        program_id: program_id.unwrap_or_default().to_string(),
         instructions: BTreeMap::new(),
         defined_types: BTreeMap::new(),
+        events: BTreeMap::new(),
+        accounts: BTreeMap::new(),
         source: SvmSchemaSource::Inline,
+        decode_errors: program.decode_errors.unwrap_or_default(),
     })
 }
 
+/// The instructions to index, with an Anchor IDL or a bundled schema filling
+/// in what the YAML leaves out: an instruction listed without a
+/// `discriminator` takes the one of the schema instruction with the same name
+/// (compared in snake_case, so either IDL spec's naming matches), and a
+/// program listing no instructions, events or accounts at all indexes every
+/// instruction the schema declares. A name the schema doesn't declare is an
+/// error rather than an instruction matching everything the program runs.
+/// With an inline schema the YAML is taken as written.
+fn program_instructions(
+    program: &human_config::svm::Program,
+    abi: &SvmAbi,
+) -> Result<Vec<human_config::svm::Instruction>> {
+    if abi.source == SvmSchemaSource::Inline {
+        return Ok(program.instructions.clone());
+    }
+    let to_hex = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .fold(String::from("0x"), |hex, byte| hex + &format!("{byte:02x}"))
+    };
+    if program.instructions.is_empty() && program.events.is_none() && program.accounts.is_none() {
+        return Ok(abi
+            .instructions
+            .values()
+            .map(|schema| human_config::svm::Instruction {
+                name: schema.name.clone(),
+                discriminator: Some(to_hex(&schema.discriminator)),
+                is_inner: None,
+                account_filters: None,
+                accounts: None,
+                args: None,
+            })
+            .collect());
+    }
+    program
+        .instructions
+        .iter()
+        .map(|instr| {
+            let mut instr = instr.clone();
+            if instr.discriminator.is_none() {
+                let name = instr.name.to_snake_case();
+                let schema = abi
+                    .instructions
+                    .values()
+                    .find(|schema| schema.name.to_snake_case() == name)
+                    .ok_or_else(|| {
+                        let available = abi
+                            .instructions
+                            .values()
+                            .map(|schema| schema.name.as_str())
+                            .collect::<Vec<_>>();
+                        anyhow!(
+                            "Program '{}' has no instruction named '{}'; give it a \
+                             `discriminator`, or use one of: {}",
+                            program.name,
+                            instr.name,
+                            available.join(", ")
+                        )
+                    })?;
+                instr.discriminator = Some(to_hex(&schema.discriminator));
+            }
+            Ok(instr)
+        })
+        .collect()
+}
+
 /// Resolve per-instruction `(accounts, args)` from one of:
 /// 1. YAML per-instruction `accounts`/`args` overrides (highest priority).
 /// 2. The matching `InstructionSchema` on the program's resolved schema
@@ -2037,6 +2335,84 @@ fn disc_to_bytes(disc: Option<&str>) -> Result<Option<Vec<u8>>> {
     Ok(Some(bytes))
 }
 
+/// The discriminator-prefixed Anchor payloads a program declares next to its
+/// instructions.
+#[derive(Debug, Clone, Copy)]
+enum AnchorLayoutKind {
+    Event,
+    Account,
+}
+
+impl AnchorLayoutKind {
+    fn label(self) -> &'static str {
+        match self {
+            AnchorLayoutKind::Event => "Event",
+            AnchorLayoutKind::Account => "Account",
+        }
+    }
+
+    fn idl_layouts(self, abi: &SvmAbi) -> &BTreeMap<String, SvmIdlLayout> {
+        match self {
+            AnchorLayoutKind::Event => &abi.events,
+            AnchorLayoutKind::Account => &abi.accounts,
+        }
+    }
+
+    fn derive_discriminator(self, name: &str) -> Vec<u8> {
+        match self {
+            AnchorLayoutKind::Event => anchor_idl::anchor_event_discriminator(name),
+            AnchorLayoutKind::Account => anchor_idl::anchor_account_discriminator(name),
+        }
+    }
+}
+
+/// Resolve an event's or account's `(discriminator, fields)`. The layout
+/// comes from YAML `fields` or, with an `idl`, from the IDL's item of the same
+/// name; the discriminator from YAML, then the IDL, then Anchor's derivation.
+fn resolve_anchor_layout(
+    kind: AnchorLayoutKind,
+    name: &str,
+    discriminator: Option<&str>,
+    fields: Option<&Vec<human_config::svm::ArgDef>>,
+    abi: &SvmAbi,
+) -> Result<(Vec<u8>, Vec<SvmNamedField>)> {
+    let label = kind.label();
+    let idl_layout = kind.idl_layouts(abi).get(name);
+    let fields = match (fields, idl_layout) {
+        (Some(_), _) if matches!(abi.source, SvmSchemaSource::AnchorIdl { .. }) => {
+            return Err(anyhow!(
+                "{label} '{name}': `fields` is mutually exclusive with the program's `idl`; the \
+                 layout is read from the IDL."
+            ))
+        }
+        (Some(fields), _) => fields
+            .iter()
+            .map(yaml_arg_to_named_field)
+            .collect::<Result<Vec<_>>>()?,
+        (None, Some(idl_layout)) => idl_layout.fields.clone(),
+        (None, None) => {
+            return Err(anyhow!(
+                "{label} '{name}' has no layout: declare its `fields`, or add the program's \
+                 Anchor IDL under `idl`."
+            ))
+        }
+    };
+    let discriminator = match disc_to_bytes(discriminator)? {
+        Some(bytes) => bytes,
+        None => idl_layout
+            .map(|layout| layout.discriminator.clone())
+            .unwrap_or_else(|| kind.derive_discriminator(name)),
+    };
+    if discriminator.len() != 8 {
+        return Err(anyhow!(
+            "{label} '{name}': the IDL gives a {}-byte discriminator; Anchor {}s are matched on 8",
+            discriminator.len(),
+            label.to_lowercase()
+        ));
+    }
+    Ok((discriminator, fields))
+}
+
 fn yaml_arg_to_named_field(arg: &human_config::svm::ArgDef) -> Result<SvmNamedField> {
     Ok(SvmNamedField {
         name: arg.name.clone(),
@@ -2187,14 +2563,32 @@ pub struct SvmAbi {
     /// from an Anchor IDL's `types:` block, the bundled-schema registry, or
     /// empty for hand-written ad-hoc schemas.
     pub defined_types: BTreeMap<String, SvmFieldType>,
+    /// Anchor events declared in the IDL, keyed by name. Empty for bundled and
+    /// inline schemas.
+    pub events: BTreeMap<String, SvmIdlLayout>,
+    /// Anchor account types declared in the IDL, keyed by name. Empty for
+    /// bundled and inline schemas.
+    pub accounts: BTreeMap<String, SvmIdlLayout>,
     pub source: SvmSchemaSource,
+    /// How the runtime treats an instruction, event or account that fails to decode
+    /// against this schema.
+    pub decode_errors: DecodeErrors,
+}
+
+/// An Anchor event or account type: the 8-byte discriminator its payload
+/// starts with, then the Borsh fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvmIdlLayout {
+    pub discriminator: Vec<u8>,
+    pub fields: Vec<SvmNamedField>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SvmSchemaSource {
     /// User-supplied `idl: <path>` parsed at codegen time.
     AnchorIdl { path: String },
-    /// `program_id` matched a bundled `ProgramSchema` (e.g. Metaplex).
+    /// A bundled `ProgramSchema` (e.g. SPL Token), selected by `schema` or
+    /// matched on `program_id`.
     Bundled { name: &'static str },
     /// Hand-written per-instruction `accounts`/`args` in YAML.
     Inline,
@@ -2220,6 +2614,67 @@ pub struct Contract {
     pub handler_path: Option<String>,
     pub abi: Abi,
     pub events: Vec<Event>,
+    /// Set when the contract's addresses are registered from another
+    /// contract's creations rather than by a handler.
+    pub factory: Option<Factory>,
+}
+
+/// A contract registered from the creations of its `deployer`, another
+/// configured contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factory {
+    pub deployer: ContractNameKey,
+    pub source: FactorySource,
+}
+
+impl From<&FactoryConfig> for Factory {
+    fn from(config: &FactoryConfig) -> Self {
+        Self {
+            deployer: config.deployer.clone(),
+            source: config.source.unwrap_or(FactorySource::Trace),
+        }
+    }
+}
+
+/// Every `factory.deployer` must name another configured contract: its
+/// creations are read by that contract's addresses. Every event's
+/// `register.contract` must name a configured contract too.
+fn validate_registrations(contracts: &ContractMap) -> Result<()> {
+    for contract in contracts.values() {
+        for event in &contract.events {
+            if let Some(register) = &event.register {
+                if !contracts.contains_key(&register.contract) {
+                    return Err(anyhow!(
+                        "Event {}.{} registers its \"{}\" param as the contract \"{}\", which \
+                         isn't a configured contract.",
+                        contract.name,
+                        event.name,
+                        register.param,
+                        register.contract,
+                    ));
+                }
+            }
+        }
+        let Some(factory) = &contract.factory else {
+            continue;
+        };
+        if factory.deployer == contract.name {
+            return Err(anyhow!(
+                "Contract {} names itself as its factory deployer. The deployer is the contract \
+                 that creates it.",
+                contract.name,
+            ));
+        }
+        if !contracts.contains_key(&factory.deployer) {
+            return Err(anyhow!(
+                "Contract {} has the factory deployer \"{}\", which isn't a configured contract. \
+                 Add the deployer to the contracts, with the addresses to watch for creations.",
+                contract.name,
+                factory.deployer,
+            ));
+        }
+    }
+    Ok(())
 }
 
 impl Contract {
@@ -2258,6 +2713,22 @@ impl Contract {
             }
         }
 
+        // Codegen capitalizes the name into the handler module, so `Transfer`
+        // and `transfer` (an event and a function, typically) would collide
+        // there even though their names differ.
+        let mut seen_by_module: HashMap<String, &str> = HashMap::new();
+        for event in &events {
+            if let Some(existing) = seen_by_module.insert(event.name.capitalize(), &event.name) {
+                return Err(anyhow!(
+                    "Contract {name} has \"{existing}\" and \"{}\", which generate the same \
+                     \"{}\" handler module. Give one of them a unique name with the \"name\" \
+                     field.",
+                    event.name,
+                    event.name.capitalize(),
+                ));
+            }
+        }
+
         // Two events on one contract that share a dispatch key are
         // indistinguishable at routing time — one log/instruction would decode
         // to both — so reject them here. The key mirrors the runtime `eventId`:
@@ -2273,6 +2744,10 @@ impl Contract {
                     let indexed_count = params.iter().filter(|p| p.indexed).count();
                     Some(format!("{}_{}", event.sighash, indexed_count))
                 }
+                // A call never matches a log, so its selector gets its own key
+                // space rather than colliding with an event's topic0.
+                EventKind::Call(_) => Some(format!("{}_call", event.sighash)),
+                EventKind::Transaction(_) => Some(format!("{}_transaction", event.sighash)),
                 // The router decodes the discriminator to bytes before matching,
                 // so `0x0f` and `0x0F` collide — lowercase before keying.
                 EventKind::Svm(svm) => Some(
@@ -2281,6 +2756,15 @@ impl Contract {
                         .map(|d| d.to_lowercase())
                         .unwrap_or_else(|| "none".to_string()),
                 ),
+                // Events match log lines and event-CPI payloads, never an
+                // instruction's own discriminator.
+                EventKind::SvmEvent(svm) => {
+                    Some(format!("{}_event", svm.discriminator.to_lowercase()))
+                }
+                // Accounts match the data of written accounts.
+                EventKind::SvmAccount(svm) => {
+                    Some(format!("{}_account", svm.discriminator.to_lowercase()))
+                }
                 EventKind::Fuel(_) => Some(event.sighash.clone()),
             };
             if let Some(dispatch_key) = dispatch_key {
@@ -2303,9 +2787,17 @@ impl Contract {
             events,
             handler_path,
             abi,
+            factory: None,
         })
     }
 
+    fn with_factory(self, factory: Option<&FactoryConfig>) -> Self {
+        Self {
+            factory: factory.map(Factory::from),
+            ..self
+        }
+    }
+
     pub fn get_chain_ids(&self, system_config: &SystemConfig) -> Vec<u64> {
         system_config
             .get_chains()
@@ -2358,11 +2850,33 @@ pub struct SvmEventKind {
     pub args: Vec<SvmNamedField>,
 }
 
+/// An Anchor event of an SVM program, emitted through `emit!` (a base64
+/// `Program data:` log line) or `emit_cpi!` (a self-CPI instruction), or an
+/// Anchor account type the program owns. All of them carry the discriminator
+/// followed by the Borsh-encoded fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvmAnchorEventKind {
+    /// Hex-encoded 8-byte discriminator, `0x`-prefixed.
+    pub discriminator: String,
+    /// Borsh field layout in declared order.
+    pub fields: Vec<SvmNamedField>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum EventKind {
     Params(Vec<EventParam>),
+    /// Calls of a function to the contract, read from traces. The params are
+    /// the function's inputs (never indexed) and `sighash` is its 4-byte
+    /// selector.
+    Call(Vec<EventParam>),
+    /// Transactions sent to the contract with a function's selector, from
+    /// `functions:`. Params and `sighash` as for `Call`.
+    Transaction(Vec<EventParam>),
     Fuel(FuelEventKind),
     Svm(SvmEventKind),
+    SvmEvent(SvmAnchorEventKind),
+    /// Writes to accounts of one of the program's Anchor account types.
+    SvmAccount(SvmAnchorEventKind),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2374,6 +2888,12 @@ pub struct Event {
     /// Only set for EVM events; empty for Fuel events.
     pub event_signature: String,
     pub field_selection: Option<FieldSelection>,
+    /// EVM only: the `where.transaction` conditions from the config.
+    pub transaction_where: Option<TransactionWhere>,
+    /// EVM only: the `where.params` conditions from the config.
+    pub params_where: Vec<ParamWhere>,
+    /// EVM only: the contract each item's address param is registered as.
+    pub register: Option<EventRegister>,
 }
 
 impl Event {
@@ -2452,6 +2972,57 @@ impl Event {
         }
     }
 
+    fn get_abi_function(function_string: &str, opt_abi: &Option<EvmAbi>) -> Result<AlloyFunction> {
+        let function_string = &Self::normalize_event_signature(function_string);
+
+        if function_string.contains('(') {
+            crate::config_parsing::abi_compat::parse_function_signature_to_alloy(function_string)
+                .map_err(|err| {
+                    anyhow!(
+                        "Unable to parse function signature {} due to the following error: {}. \
+                         Please refer to our docs on how to correctly define a human readable \
+                         ABI.",
+                        function_string,
+                        err
+                    )
+                })
+        } else {
+            match opt_abi {
+                Some(abi) => abi
+                    .typed
+                    .function(function_string)
+                    .and_then(|functions| functions.first())
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Function {} not found in ABI file", function_string)),
+                None => Err(anyhow!(
+                    "No abi file provided for function {}",
+                    function_string
+                )),
+            }
+        }
+    }
+
+    /// A function's inputs as call params. Unnamed inputs are named by
+    /// position, like unnamed event params.
+    fn convert_function_params(function: &AlloyFunction) -> Result<Vec<EventParam>> {
+        function
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                Ok(EventParam {
+                    name: if param.name.is_empty() {
+                        format!("_{}", i)
+                    } else {
+                        param.name.clone()
+                    },
+                    kind: AbiType::from_alloy_param(param)?,
+                    indexed: false,
+                })
+            })
+            .collect()
+    }
+
     /// Convert alloy EventParam to our abi_compat EventParam
     fn convert_event_params(alloy_event: &AlloyEvent) -> Result<Vec<EventParam>> {
         alloy_event
@@ -2475,9 +3046,11 @@ impl Event {
 
     fn from_evm_events_config(
         events_config: Vec<EvmEventConfig>,
+        functions_config: &[EvmFunctionConfig],
         abi_file_path: &Option<String>,
         source: &dyn ConfigSource,
         has_rpc_src: bool,
+        has_hypersync_src: bool,
     ) -> Result<(Vec<Self>, EvmAbi)> {
         let abi_from_file = EvmAbi::from_source(abi_file_path, source)?;
 
@@ -2485,6 +3058,51 @@ impl Event {
         let mut events_abi = JsonAbi::new();
 
         for event_config in events_config.iter() {
+            let field_selection = match event_config.field_selection {
+                Some(ref selection_config) => {
+                    Some(FieldSelection::try_from_config_field_selection(
+                        selection_config.clone(),
+                        has_rpc_src,
+                        has_hypersync_src,
+                    )?)
+                }
+                None => None,
+            };
+
+            if event_config.type_ == Some(EvmEventType::Call) {
+                let function = Event::get_abi_function(&event_config.event, &abi_from_file)?;
+                let params = Event::convert_function_params(&function)?;
+                let params_where = ParamWhere::from_config(
+                    event_config.where_.as_ref(),
+                    &params,
+                    &event_config.event,
+                )?;
+                events.push(Event {
+                    name: event_config.name.clone().unwrap_or(function.name.clone()),
+                    kind: EventKind::Call(params.clone()),
+                    sighash: function.selector().to_string(),
+                    event_signature: EvmAbi::function_signature_from_abi_function(&function),
+                    field_selection,
+                    transaction_where: TransactionWhere::from_config(
+                        event_config.where_.as_ref(),
+                        &event_config.event,
+                        true,
+                    )?,
+                    params_where,
+                    register: EventRegister::from_config(
+                        event_config.register.as_ref(),
+                        &params,
+                        &event_config.event,
+                    )?,
+                });
+                events_abi
+                    .functions
+                    .entry(function.name.clone())
+                    .or_default()
+                    .push(function);
+                continue;
+            }
+
             let alloy_event = Event::get_abi_event(&event_config.event, &abi_from_file)?;
             // Use alloy's selector() method which computes keccak256 of the signature
             // Note: selector() returns B256 which formats as lowercase hex with 0x prefix
@@ -2497,6 +3115,16 @@ impl Event {
             // Convert alloy params to our abi_compat EventParam
             let normalized_unnamed_params: Vec<EventParam> =
                 Event::convert_event_params(&alloy_event)?;
+            let params_where = ParamWhere::from_config(
+                event_config.where_.as_ref(),
+                &normalized_unnamed_params,
+                &event_config.event,
+            )?;
+            let register = EventRegister::from_config(
+                event_config.register.as_ref(),
+                &normalized_unnamed_params,
+                &event_config.event,
+            )?;
 
             // Add the event to the ABI (alloy_event is already properly formatted)
             events_abi
@@ -2509,16 +3137,58 @@ impl Event {
                 kind: EventKind::Params(normalized_unnamed_params),
                 sighash,
                 event_signature,
-                field_selection: match event_config.field_selection {
+                field_selection,
+                transaction_where: TransactionWhere::from_config(
+                    event_config.where_.as_ref(),
+                    &event_config.event,
+                    false,
+                )?,
+                params_where,
+                register,
+            })
+        }
+
+        for function_config in functions_config {
+            let function = Event::get_abi_function(&function_config.function, &abi_from_file)?;
+            let params = Event::convert_function_params(&function)?;
+            let params_where = ParamWhere::from_config(
+                function_config.where_.as_ref(),
+                &params,
+                &function_config.function,
+            )?;
+            events.push(Event {
+                name: function_config
+                    .name
+                    .clone()
+                    .unwrap_or(function.name.clone()),
+                kind: EventKind::Transaction(params),
+                sighash: function.selector().to_string(),
+                event_signature: EvmAbi::function_signature_from_abi_function(&function),
+                field_selection: match function_config.field_selection {
                     Some(ref selection_config) => {
                         Some(FieldSelection::try_from_config_field_selection(
                             selection_config.clone(),
                             has_rpc_src,
+                            has_hypersync_src,
                         )?)
                     }
                     None => None,
                 },
-            })
+                transaction_where: TransactionWhere::from_config(
+                    function_config.where_.as_ref(),
+                    &function_config.function,
+                    true,
+                )?,
+                params_where,
+                register: None,
+            });
+            let overloads = events_abi
+                .functions
+                .entry(function.name.clone())
+                .or_default();
+            if !overloads.contains(&function) {
+                overloads.push(function);
+            }
         }
 
         let events_abi_raw = serde_json::to_string(&events_abi)
@@ -2597,6 +3267,9 @@ impl Event {
                         sighash: log.id,
                         event_signature: String::new(),
                         field_selection: None,
+                        transaction_where: None,
+                        params_where: vec![],
+                        register: None,
                     }
                 }
                 EventType::Mint => Event {
@@ -2605,6 +3278,9 @@ impl Event {
                     sighash: "mint".to_string(),
                     event_signature: String::new(),
                     field_selection: None,
+                    transaction_where: None,
+                    params_where: vec![],
+                    register: None,
                 },
                 EventType::Burn => Event {
                     name: event_config.name.clone(),
@@ -2612,6 +3288,9 @@ impl Event {
                     sighash: "burn".to_string(),
                     event_signature: String::new(),
                     field_selection: None,
+                    transaction_where: None,
+                    params_where: vec![],
+                    register: None,
                 },
                 EventType::Transfer => Event {
                     name: event_config.name.clone(),
@@ -2619,6 +3298,9 @@ impl Event {
                     sighash: "transfer".to_string(),
                     event_signature: String::new(),
                     field_selection: None,
+                    transaction_where: None,
+                    params_where: vec![],
+                    register: None,
                 },
                 EventType::Call => Event {
                     name: event_config.name.clone(),
@@ -2626,6 +3308,9 @@ impl Event {
                     sighash: "call".to_string(),
                     event_signature: String::new(),
                     field_selection: None,
+                    transaction_where: None,
+                    params_where: vec![],
+                    register: None,
                 },
             };
 
@@ -2655,6 +3340,231 @@ impl Ord for SelectedField {
     }
 }
 
+/// Conditions on the transaction an EVM event's items belong to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionWhere {
+    /// The receipt status: 1 succeeded, 0 reverted.
+    pub status: Option<u8>,
+    pub gas_used: Option<QuantityBounds>,
+    pub value: Option<QuantityBounds>,
+}
+
+/// A `where.params` condition on one decoded param. Values are kept as written
+/// (checked to parse as the param's type) and decoded again by the sources.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamWhere {
+    pub name: String,
+    pub eq: Option<String>,
+    pub one_of: Option<Vec<String>>,
+    pub gte: Option<String>,
+    pub lte: Option<String>,
+}
+
+/// An event's `register`: each of its items registers the address in `param`
+/// as `contract`, from the item's block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventRegister {
+    pub contract: ContractNameKey,
+    pub param: String,
+}
+
+impl EventRegister {
+    fn from_config(
+        config: Option<&EventRegisterConfig>,
+        params: &[EventParam],
+        event: &str,
+    ) -> Result<Option<Self>> {
+        let Some(config) = config else {
+            return Ok(None);
+        };
+        let param = params
+            .iter()
+            .find(|p| p.name == config.from)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Event \"{event}\" registers from \"{}\", which is not one of its params: {}",
+                    config.from,
+                    params.iter().map(|p| p.name.as_str()).join(", ")
+                )
+            })?;
+        if param.kind != AbiType::Address {
+            return Err(anyhow!(
+                "Param \"{}\" of event \"{event}\" has type {}; `register.from` needs an \
+                 address param",
+                config.from,
+                param.kind.to_signature_string()
+            ));
+        }
+        Ok(Some(Self {
+            contract: config.contract.clone(),
+            param: config.from.clone(),
+        }))
+    }
+}
+
+impl ParamWhere {
+    fn from_config(
+        config: Option<&EvmEventWhere>,
+        params: &[EventParam],
+        event: &str,
+    ) -> Result<Vec<Self>> {
+        let Some(conditions) = config.and_then(|w| w.params.as_ref()) else {
+            return Ok(vec![]);
+        };
+        conditions
+            .iter()
+            .map(|(name, condition)| {
+                let param = params.iter().find(|p| &p.name == name).ok_or_else(|| {
+                    anyhow!(
+                        "Event \"{event}\" has a `where.params` condition on \"{name}\", which \
+                         is not one of its params: {}",
+                        params.iter().map(|p| p.name.as_str()).join(", ")
+                    )
+                })?;
+                if param.indexed {
+                    return Err(anyhow!(
+                        "Param \"{name}\" of event \"{event}\" is indexed. Filter it with the \
+                         `where` option of `indexer.onEvent` instead, which narrows the query \
+                         itself"
+                    ));
+                }
+                let is_integer = matches!(param.kind, AbiType::Uint(_) | AbiType::Int(_));
+                if !is_integer
+                    && !matches!(
+                        param.kind,
+                        AbiType::Bool
+                            | AbiType::Address
+                            | AbiType::String
+                            | AbiType::Bytes
+                            | AbiType::FixedBytes(_)
+                    )
+                {
+                    return Err(anyhow!(
+                        "Param \"{name}\" of event \"{event}\" has type {}, which `where.params` \
+                         can't filter. Only bool, int, uint, address, string and bytes params \
+                         are supported",
+                        param.kind.to_signature_string()
+                    ));
+                }
+                if !is_integer && (condition.gte.is_some() || condition.lte.is_some()) {
+                    return Err(anyhow!(
+                        "Param \"{name}\" of event \"{event}\" has type {}; `_gte` and `_lte` \
+                         only apply to int and uint params",
+                        param.kind.to_signature_string()
+                    ));
+                }
+                let ty = DynSolType::parse(&param.kind.to_signature_string())
+                    .context("Failed parsing param type")?;
+                let value = |literal: &ParamLiteral, op: &str| -> Result<String> {
+                    let value = literal.to_string();
+                    ty.coerce_str(&value).map_err(|_| {
+                        anyhow!(
+                            "Event \"{event}\" has an invalid `where.params.{name}.{op}` value \
+                             \"{value}\" for a param of type {}",
+                            param.kind.to_signature_string()
+                        )
+                    })?;
+                    Ok(value)
+                };
+                Ok(Self {
+                    name: name.clone(),
+                    eq: condition.eq.as_ref().map(|v| value(v, "_eq")).transpose()?,
+                    one_of: condition
+                        .in_
+                        .as_ref()
+                        .map(|values| values.iter().map(|v| value(v, "_in")).collect())
+                        .transpose()?,
+                    gte: condition
+                        .gte
+                        .as_ref()
+                        .map(|v| value(v, "_gte"))
+                        .transpose()?,
+                    lte: condition
+                        .lte
+                        .as_ref()
+                        .map(|v| value(v, "_lte"))
+                        .transpose()?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Inclusive bounds of a `where.transaction` quantity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantityBounds {
+    pub gte: Option<u128>,
+    pub lte: Option<u128>,
+}
+
+impl QuantityBounds {
+    fn from_config(range: &QuantityRange, event: &str, name: &str) -> Result<Self> {
+        let parse = |quantity: &Option<Quantity>, op: &str| -> Result<Option<u128>> {
+            match quantity {
+                None => Ok(None),
+                Some(Quantity::Integer(value)) => Ok(Some(u128::from(*value))),
+                Some(Quantity::Decimal(value)) => value.parse().map(Some).map_err(|_| {
+                    anyhow!(
+                        "Event \"{event}\" has an invalid `where.transaction.{name}.{op}` \
+                         \"{value}\". Expected a non-negative integer"
+                    )
+                }),
+            }
+        };
+        let bounds = Self {
+            gte: parse(&range.gte, "_gte")?,
+            lte: parse(&range.lte, "_lte")?,
+        };
+        if let (Some(gte), Some(lte)) = (bounds.gte, bounds.lte) {
+            if gte > lte {
+                return Err(anyhow!(
+                    "Event \"{event}\" has an empty `where.transaction.{name}` range: _gte \
+                     {gte} is above _lte {lte}"
+                ));
+            }
+        }
+        Ok(bounds)
+    }
+}
+
+impl TransactionWhere {
+    /// `can_fail` is whether the event can come from a reverted transaction at
+    /// all: calls and functions can, logs can't — a reverted transaction
+    /// emits none.
+    fn from_config(
+        config: Option<&EvmEventWhere>,
+        event: &str,
+        can_fail: bool,
+    ) -> Result<Option<Self>> {
+        let Some(config) = config.and_then(|w| w.transaction.as_ref()) else {
+            return Ok(None);
+        };
+        if config.status == Some(EvmTransactionStatus::Failure) && !can_fail {
+            return Err(anyhow!(
+                "Event \"{event}\" filters on `where.transaction.status: failure`, but logs are \
+                 only emitted by successful transactions. Index the function instead to \
+                 monitor failed calls."
+            ));
+        }
+        Ok(Some(Self {
+            status: config.status.map(|status| match status {
+                EvmTransactionStatus::Success => 1,
+                EvmTransactionStatus::Failure => 0,
+            }),
+            gas_used: config
+                .gas_used
+                .as_ref()
+                .map(|range| QuantityBounds::from_config(range, event, "gas_used"))
+                .transpose()?,
+            value: config
+                .value
+                .as_ref()
+                .map(|range| QuantityBounds::from_config(range, event, "value"))
+                .transpose()?,
+        }))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct FieldSelection {
     pub transaction_fields: Vec<SelectedField>,
@@ -2801,6 +3711,15 @@ impl FieldSelection {
                     TransactionField::AuthorizationList => {
                         TypeIdent::option(TypeIdent::array(TypeIdent::Unknown))
                     }
+                    TransactionField::SourceHash => TypeIdent::option(TypeIdent::String),
+                    TransactionField::Mint => TypeIdent::option(TypeIdent::BigInt),
+                    TransactionField::IsSystemTx => TypeIdent::option(TypeIdent::Bool),
+                    TransactionField::L1BaseFeeScalar => TypeIdent::option(TypeIdent::BigInt),
+                    TransactionField::L1BlobBaseFee => TypeIdent::option(TypeIdent::BigInt),
+                    TransactionField::L1BlobBaseFeeScalar => TypeIdent::option(TypeIdent::BigInt),
+                    TransactionField::OperatorFeeScalar => TypeIdent::option(TypeIdent::BigInt),
+                    TransactionField::L1BatchNumber => TypeIdent::option(TypeIdent::Int),
+                    TransactionField::L1BatchTxIndex => TypeIdent::option(TypeIdent::Int),
                 };
                 SelectedField {
                     name: field.to_string(),
@@ -2816,6 +3735,8 @@ impl FieldSelection {
         field_selection_cfg: human_config::evm::FieldSelection,
         // For validating transaction field selection with rpc
         has_rpc_src: bool,
+        // and with HyperSync
+        has_hypersync_src: bool,
     ) -> Result<Self> {
         use human_config::evm::BlockField;
         use human_config::evm::TransactionField;
@@ -2843,8 +3764,8 @@ impl FieldSelection {
         }
 
         // Every block field is derivable from `eth_getBlockByNumber`, so only
-        // transactions have an RPC-unavailable set: the two whose complex array
-        // shape has no parser in `RpcSource`'s field registry.
+        // transactions have an RPC-unavailable set: the two array-shaped ones
+        // `RpcSource.rpcTransactionFields` leaves out.
         //
         // The runtime re-checks this over every registration in
         // `HandlerRegister.validateRpcFieldSelection`, which is the only check
@@ -2866,6 +3787,24 @@ impl FieldSelection {
             }
         }
 
+        // HyperSync would leave the fields it doesn't serve null for every
+        // block it syncs, so they need the chain's history synced from an RPC.
+        if has_hypersync_src {
+            let invalid_hypersync_tx_fields: Vec<_> = transaction_fields
+                .iter()
+                .filter(|&field| HyperSyncTransactionField::try_from(field.clone()).is_err())
+                .cloned()
+                .collect();
+
+            if !invalid_hypersync_tx_fields.is_empty() {
+                return Err(anyhow!(
+                    "The following selected transaction_fields are unavailable for indexing via \
+                     HyperSync: {}. Sync the chain from an RPC (`for: sync`) to select them.",
+                    invalid_hypersync_tx_fields.iter().join(", ")
+                ));
+            }
+        }
+
         let mut selected_block_fields = vec![];
 
         type Res = TypeIdent;
@@ -2948,6 +3887,15 @@ impl FieldSelection {
                     name: "HyperSyncClient.ResponseTypes.authorizationList".to_string(),
                     type_params: vec![],
                 }))),
+                Tx::SourceHash => Res::option(Res::String),
+                Tx::Mint => Res::option(Res::BigInt),
+                Tx::IsSystemTx => Res::option(Res::Bool),
+                Tx::L1BaseFeeScalar => Res::option(Res::BigInt),
+                Tx::L1BlobBaseFee => Res::option(Res::BigInt),
+                Tx::L1BlobBaseFeeScalar => Res::option(Res::BigInt),
+                Tx::OperatorFeeScalar => Res::option(Res::BigInt),
+                Tx::L1BatchNumber => Res::option(Res::Int),
+                Tx::L1BatchTxIndex => Res::option(Res::Int),
             };
             selected_transaction_fields.push(SelectedField {
                 name: transaction_field.to_string(),
@@ -3031,6 +3979,567 @@ mod test {
         assert_eq!(ids, vec![SOLANA_MAINNET_CHAIN_ID, SOLANA_DEVNET_CHAIN_ID]);
     }
 
+    #[test]
+    fn evm_functions_become_transaction_events_keyed_by_selector() {
+        use super::EventKind;
+
+        let yaml = r#"
+name: functions
+chains:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: Token
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Transfer(address indexed from, address indexed to, uint256 value)
+        functions:
+          - function: transfer(address to, uint256 amount)
+            name: TransferCall
+          - function: approve(address, uint256)
+"#;
+        let parse = |yaml: &str| {
+            SystemConfig::parse_yaml(
+                yaml,
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+        };
+        let config = parse(yaml).expect("evm config");
+        let contract = config.contracts.get("Token").expect("contract");
+        let summary: Vec<(&str, &str, bool)> = contract
+            .events
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.sighash.as_str(),
+                    matches!(e.kind, EventKind::Transaction(_)),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Transfer",
+                    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    false
+                ),
+                ("TransferCall", "0xa9059cbb", true),
+                ("approve", "0x095ea7b3", true),
+            ]
+        );
+        // Unnamed, the function's `transfer` module would shadow the event's.
+        let Err(err) = parse(&yaml.replace("            name: TransferCall\n", "")) else {
+            panic!("expected a module collision");
+        };
+        assert!(format!("{err:#}").contains("same \"Transfer\" handler module"));
+        match &contract.events[2].kind {
+            EventKind::Transaction(params) => assert_eq!(
+                params.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+                vec!["_0", "_1"]
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn evm_where_transaction_parses_and_rejects_failed_logs() {
+        use super::{QuantityBounds, TransactionWhere};
+
+        let yaml = r#"
+name: failed-calls
+chains:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: Token
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Transfer(address indexed from, address indexed to, uint256 value)
+            where:
+              transaction:
+                value:
+                  _gte: "1000000000000000000000"
+        functions:
+          - function: transfer(address to, uint256 amount)
+            name: TransferCall
+            where:
+              transaction:
+                status: failure
+                gas_used:
+                  _lte: 100000
+"#;
+        let parse = |yaml: &str| {
+            SystemConfig::parse_yaml(
+                yaml,
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+        };
+        let config = parse(yaml).expect("evm config");
+        let contract = config.contracts.get("Token").expect("contract");
+        assert_eq!(
+            contract
+                .events
+                .iter()
+                .map(|e| e.transaction_where.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                Some(TransactionWhere {
+                    status: None,
+                    gas_used: None,
+                    value: Some(QuantityBounds {
+                        gte: Some(1_000_000_000_000_000_000_000),
+                        lte: None,
+                    }),
+                }),
+                Some(TransactionWhere {
+                    status: Some(0),
+                    gas_used: Some(QuantityBounds {
+                        gte: None,
+                        lte: Some(100_000),
+                    }),
+                    value: None,
+                }),
+            ]
+        );
+
+        let failed_logs = yaml.replace(
+            "                value:\n                  _gte: \"1000000000000000000000\"\n",
+            "                status: failure\n",
+        );
+        let Err(err) = parse(&failed_logs) else {
+            panic!("expected failed logs to be rejected");
+        };
+        assert!(format!("{err:#}").contains("only emitted by successful transactions"));
+
+        let Err(err) = parse(&yaml.replace("_lte: 100000", "_lte: 1\n                  _gte: 2"))
+        else {
+            panic!("expected an empty range to be rejected");
+        };
+        assert!(format!("{err:#}").contains("empty `where.transaction.gas_used` range"));
+    }
+
+    #[test]
+    fn evm_factory_resolves_and_validates_the_deployer() {
+        use super::Factory;
+        use crate::config_parsing::human_config::evm::FactorySource;
+
+        let yaml = r#"
+name: factory
+chains:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: Factory
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Owner(address owner)
+      - name: Pair
+        factory:
+          deployer: Factory
+        events:
+          - event: Sync(uint112 reserve0, uint112 reserve1)
+"#;
+        let parse = |yaml: &str| {
+            SystemConfig::parse_yaml(
+                yaml,
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+        };
+        let config = parse(yaml).expect("evm config");
+        assert_eq!(
+            config.contracts.get("Factory").expect("factory").factory,
+            None
+        );
+        assert_eq!(
+            config.contracts.get("Pair").expect("pair").factory,
+            Some(Factory {
+                deployer: "Factory".to_string(),
+                source: FactorySource::Trace,
+            })
+        );
+
+        let rejected = |yaml: String, message: &str| {
+            let Err(err) = parse(&yaml) else {
+                panic!("expected the config to be rejected: {message}");
+            };
+            assert!(format!("{err:#}").contains(message), "{err:#}");
+        };
+        rejected(
+            yaml.replace("deployer: Factory", "deployer: Pair"),
+            "names itself as its factory deployer",
+        );
+        rejected(
+            yaml.replace("deployer: Factory", "deployer: Router"),
+            "isn't a configured contract",
+        );
+    }
+
+    #[test]
+    fn evm_finality_requires_its_source() {
+        use crate::config_parsing::human_config::evm::Finality;
+
+        let yaml = |chain: &str| {
+            format!(
+                r#"
+name: finality
+chains:
+  - id: 1
+    start_block: 0
+{chain}
+    contracts:
+      - name: Token
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Transfer(address indexed from, address indexed to, uint256 value)
+"#
+            )
+        };
+        let parse = |chain: &str| {
+            SystemConfig::parse_yaml(
+                &yaml(chain),
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+            .map(|config| config.chains.get(&1).expect("chain 1").finality)
+        };
+
+        assert_eq!(parse("").unwrap(), None);
+        assert_eq!(
+            parse("    finality: rollback_guard").unwrap(),
+            Some(Finality::RollbackGuard)
+        );
+        assert_eq!(
+            parse("    finality: finalized\n    rpc: https://eth.example.com").unwrap(),
+            Some(Finality::Finalized)
+        );
+
+        let rejected = |chain: &str, message: &str| {
+            let Err(err) = parse(chain) else {
+                panic!("expected the config to be rejected: {message}");
+            };
+            assert!(format!("{err:#}").contains(message), "{err:#}");
+        };
+        rejected("    finality: safe", "the chain has no `rpc` configured");
+        rejected(
+            "    finality: rollback_guard\n    rpc:\n      url: https://eth.example.com\n      for: sync",
+            "the chain syncs from an RPC",
+        );
+    }
+
+    #[test]
+    fn evm_rpc_only_transaction_fields_need_an_rpc_synced_chain() {
+        let yaml = |field_selection: &str, rpc: &str, event_field_selection: &str| {
+            format!(
+                r#"
+name: fields
+{field_selection}
+chains:
+  - id: 10
+    start_block: 0
+{rpc}
+    contracts:
+      - name: Token
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Transfer(address indexed from, address indexed to, uint256 value)
+{event_field_selection}
+"#
+            )
+        };
+        let parse = |field_selection: &str, rpc: &str, event_field_selection: &str| {
+            SystemConfig::parse_yaml(
+                &yaml(field_selection, rpc, event_field_selection),
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+        };
+        let global = "field_selection:\n  transaction_fields: [hash, isSystemTx]";
+        let per_event =
+            "            field_selection:\n              transaction_fields: [l1BatchNumber]";
+        let rpc_sync = "    rpc:\n      url: https://op.example.com\n      for: sync";
+        let rpc_fallback = "    rpc: https://op.example.com";
+
+        parse(global, rpc_sync, "").expect("RPC-synced chain");
+        parse("", rpc_sync, per_event).expect("RPC-synced chain");
+
+        let rejected = |field_selection: &str, rpc: &str, event_field_selection: &str, field| {
+            let Err(err) = parse(field_selection, rpc, event_field_selection) else {
+                panic!("expected {field} to be rejected on a HyperSync-synced chain");
+            };
+            assert!(
+                format!("{err:#}")
+                    .contains(&format!("unavailable for indexing via HyperSync: {field}.")),
+                "{err:#}"
+            );
+        };
+        rejected(global, "", "", "isSystemTx");
+        rejected(global, rpc_fallback, "", "isSystemTx");
+        rejected("", "", per_event, "l1BatchNumber");
+    }
+
+    fn parse_evm_rpc(rpc: &str) -> anyhow::Result<Vec<super::Rpc>> {
+        let yaml = format!(
+            r#"
+name: rpc
+chains:
+  - id: 1
+    start_block: 0
+    rpc:
+      url: https://eth.example.com/
+      for: sync
+{rpc}
+    contracts:
+      - name: Token
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Transfer(address indexed from, address indexed to, uint256 value)
+"#
+        );
+        let config = SystemConfig::parse_yaml(
+            &yaml,
+            Some("type Foo @entity { id: ID! }"),
+            &HashMap::new(),
+            &HashMap::new(),
+            false,
+        )?;
+        match &config.chains.get(&1).expect("chain 1").sync_source {
+            super::DataSource::Evm { rpcs, .. } => Ok(rpcs.clone()),
+            other => panic!("expected an EVM data source, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn evm_rpc_additional_urls_and_hedging_parse() {
+        let rpcs = parse_evm_rpc(
+            "      additional_urls: [https://eth-a.example.com/, http://eth-b.example.com]\n      \
+             hedge_after_millis: 300",
+        )
+        .unwrap();
+        assert_eq!(
+            rpcs.iter()
+                .map(|rpc| (
+                    rpc.url.as_str(),
+                    rpc.additional_urls.clone(),
+                    rpc.hedge_after_millis
+                ))
+                .collect::<Vec<_>>(),
+            vec![(
+                "https://eth.example.com",
+                Some(vec![
+                    "https://eth-a.example.com".to_string(),
+                    "http://eth-b.example.com".to_string()
+                ]),
+                Some(300)
+            )]
+        );
+
+        let plain = parse_evm_rpc("").unwrap();
+        assert_eq!(
+            (
+                plain[0].additional_urls.clone(),
+                plain[0].hedge_after_millis
+            ),
+            (None, None)
+        );
+
+        let rejected = |rpc: &str, message: &str| {
+            let Err(err) = parse_evm_rpc(rpc) else {
+                panic!("expected the config to be rejected: {message}");
+            };
+            assert!(format!("{err:#}").contains(message), "{err:#}");
+        };
+        rejected(
+            "      hedge_after_millis: 300",
+            "sets hedge_after_millis without additional_urls",
+        );
+        rejected(
+            "      additional_urls: []\n      hedge_after_millis: 300",
+            "sets hedge_after_millis without additional_urls",
+        );
+        rejected(
+            "      additional_urls: [eth-a.example.com]",
+            "The RPC url \"eth-a.example.com\" is incorrect format",
+        );
+    }
+
+    #[test]
+    fn evm_rpc_max_batch_size_parses() {
+        let batch_size = |rpc: &str| parse_evm_rpc(rpc).map(|rpcs| rpcs[0].max_batch_size);
+        assert_eq!(batch_size("").unwrap(), None);
+        assert_eq!(batch_size("      max_batch_size: 1").unwrap(), Some(1));
+        assert_eq!(batch_size("      max_batch_size: 500").unwrap(), Some(500));
+
+        let rejected = |rpc: &str, message: &str| {
+            let Err(err) = batch_size(rpc) else {
+                panic!("expected the config to be rejected: {message}");
+            };
+            assert!(format!("{err:#}").contains(message), "{err:#}");
+        };
+        rejected("      max_batch_size: 0", "sets max_batch_size to 0");
+        rejected("      max_batch_size: -1", "untagged enum RpcSelection");
+    }
+
+    #[test]
+    fn evm_event_register_validates_the_param_and_contract() {
+        use super::EventRegister;
+
+        let yaml = r#"
+name: register
+chains:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: Factory
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: PairCreated(address indexed token0, address indexed token1, address pair, uint256 index)
+            register:
+              contract: Pair
+              from: pair
+      - name: Pair
+        events:
+          - event: Sync(uint112 reserve0, uint112 reserve1)
+"#;
+        let parse = |yaml: &str| {
+            SystemConfig::parse_yaml(
+                yaml,
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+        };
+        let config = parse(yaml).expect("evm config");
+        assert_eq!(
+            config.contracts.get("Factory").expect("factory").events[0].register,
+            Some(EventRegister {
+                contract: "Pair".to_string(),
+                param: "pair".to_string(),
+            })
+        );
+
+        let rejected = |yaml: String, message: &str| {
+            let Err(err) = parse(&yaml) else {
+                panic!("expected the config to be rejected: {message}");
+            };
+            assert!(format!("{err:#}").contains(message), "{err:#}");
+        };
+        rejected(
+            yaml.replace("from: pair", "from: index"),
+            "`register.from` needs an address param",
+        );
+        rejected(
+            yaml.replace("from: pair", "from: pool"),
+            "registers from \"pool\", which is not one of its params",
+        );
+        rejected(
+            yaml.replace("contract: Pair", "contract: Pool"),
+            "isn't a configured contract",
+        );
+    }
+
+    #[test]
+    fn evm_where_params_validates_against_the_event_params() {
+        use super::ParamWhere;
+
+        let yaml = r#"
+name: large-transfers
+chains:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: Token
+        address: "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"
+        events:
+          - event: Transfer(address indexed from, address indexed to, uint256 value)
+            where:
+              params:
+                value:
+                  _gte: "1000000000000000000000"
+        functions:
+          - function: transfer(address to, uint256 amount)
+            name: TransferCall
+            where:
+              params:
+                to:
+                  _in: ["0x2E645469f354BB4F5c8a05B3b30A929361cf77eC"]
+"#;
+        let parse = |yaml: &str| {
+            SystemConfig::parse_yaml(
+                yaml,
+                Some("type Foo @entity { id: ID! }"),
+                &HashMap::new(),
+                &HashMap::new(),
+                false,
+            )
+        };
+        let config = parse(yaml).expect("evm config");
+        let contract = config.contracts.get("Token").expect("contract");
+        assert_eq!(
+            contract
+                .events
+                .iter()
+                .map(|e| e.params_where.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![ParamWhere {
+                    name: "value".to_string(),
+                    eq: None,
+                    one_of: None,
+                    gte: Some("1000000000000000000000".to_string()),
+                    lte: None,
+                }],
+                vec![ParamWhere {
+                    name: "to".to_string(),
+                    eq: None,
+                    one_of: Some(vec![
+                        "0x2E645469f354BB4F5c8a05B3b30A929361cf77eC".to_string()
+                    ]),
+                    gte: None,
+                    lte: None,
+                }],
+            ]
+        );
+
+        let rejected = |yaml: String, message: &str| {
+            let Err(err) = parse(&yaml) else {
+                panic!("expected the config to be rejected: {message}");
+            };
+            assert!(format!("{err:#}").contains(message), "{err:#}");
+        };
+        rejected(
+            yaml.replace("                value:\n", "                to:\n"),
+            "is indexed",
+        );
+        rejected(
+            yaml.replace("                value:\n", "                amount:\n"),
+            "is not one of its params",
+        );
+        rejected(
+            yaml.replace("\"1000000000000000000000\"", "\"lots\""),
+            "invalid `where.params.value._gte` value",
+        );
+        rejected(
+            yaml.replace("_in: [", "_gte: 1\n                  _in: ["),
+            "only apply to int and uint params",
+        );
+    }
+
     #[test]
     fn in_memory_fuel_abi_matches_filesystem_public_config() {
         let test_dir = format!("{}/test", env!("CARGO_MANIFEST_DIR"));
@@ -3902,5 +5411,142 @@ type Foo {
                 } if url == "https://solana.hypersync.xyz"
             ));
         }
+
+        /// A bundled `schema` supplies the program id, the discriminators of
+        /// instructions declared by name, and all instructions when none are.
+        #[test]
+        fn fills_instructions_from_a_bundled_schema() {
+            use crate::config_parsing::human_config::svm::Program;
+            use crate::config_parsing::system_config::{
+                program_instructions, resolve_instruction_layout, resolve_program_schema,
+                MemoryConfigSource, SvmSchemaSource,
+            };
+            use std::collections::HashMap;
+
+            let mut program: Program =
+                serde_yaml::from_str("name: Token\nschema: spl_token\n").unwrap();
+            let (env, files) = (HashMap::new(), HashMap::new());
+            let source = MemoryConfigSource::new(None, &env, &files, false);
+            let abi = resolve_program_schema(&program, &source).unwrap();
+            assert_eq!(
+                abi.program_id,
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            );
+            assert_eq!(abi.source, SvmSchemaSource::Bundled { name: "spl_token" });
+            assert_eq!(
+                program_instructions(&program, &abi).unwrap().len(),
+                abi.instructions.len()
+            );
+
+            program.instructions = serde_yaml::from_str("[{name: TransferChecked}]").unwrap();
+            let instructions = program_instructions(&program, &abi).unwrap();
+            assert_eq!(instructions[0].discriminator.as_deref(), Some("0x0c"));
+            let (accounts, args) = resolve_instruction_layout(&instructions[0], &abi).unwrap();
+            assert_eq!(accounts, ["source", "mint", "destination", "authority"]);
+            assert_eq!(
+                args.iter().map(|arg| arg.name.as_str()).collect::<Vec<_>>(),
+                ["amount", "decimals"]
+            );
+
+            program.instructions = serde_yaml::from_str("[{name: Transfer2}]").unwrap();
+            let err = program_instructions(&program, &abi)
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.starts_with("Program 'Token' has no instruction named 'Transfer2'"),
+                "unexpected error: {err}"
+            );
+        }
+
+        /// With an IDL, instructions missing a discriminator take the IDL's
+        /// (matched across naming styles), and an empty list means all of them.
+        #[test]
+        fn fills_instructions_from_the_idl() {
+            use crate::config_parsing::anchor_idl;
+            use crate::config_parsing::human_config::svm::{DecodeErrors, Instruction, Program};
+            use crate::config_parsing::system_config::{
+                program_instructions, SvmAbi, SvmSchemaSource,
+            };
+
+            let idl = anchor_idl::parse(
+                r#"{ "instructions": [
+                  { "name": "initialize_pool", "discriminator": [1, 1, 1, 1, 1, 1, 1, 1] },
+                  { "name": "swap", "discriminator": [2, 2, 2, 2, 2, 2, 2, 2] }
+                ] }"#,
+            )
+            .expect("idl");
+            let abi = SvmAbi {
+                program_id: "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8".to_string(),
+                instructions: idl
+                    .instructions
+                    .into_iter()
+                    .map(|ix| (ix.discriminator.clone(), ix))
+                    .collect(),
+                defined_types: idl.defined_types,
+                events: idl.events,
+                accounts: idl.accounts,
+                source: SvmSchemaSource::AnchorIdl {
+                    path: "idl.json".to_string(),
+                },
+                decode_errors: DecodeErrors::Ignore,
+            };
+            let instruction = |name: &str| Instruction {
+                name: name.to_string(),
+                discriminator: None,
+                is_inner: None,
+                account_filters: None,
+                accounts: None,
+                args: None,
+            };
+            let mut program = Program {
+                name: "Amm".to_string(),
+                program_id: Some(abi.program_id.clone()),
+                handler: None,
+                idl: Some("idl.json".to_string()),
+                schema: None,
+                instructions: vec![instruction("initializePool")],
+                events: None,
+                accounts: None,
+                decode_errors: None,
+            };
+            let discriminators = |program: &Program| {
+                program_instructions(program, &abi)
+                    .unwrap()
+                    .into_iter()
+                    .map(|i| (i.name, i.discriminator))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(
+                discriminators(&program),
+                vec![(
+                    "initializePool".to_string(),
+                    Some("0x0101010101010101".to_string())
+                ),]
+            );
+
+            // Not in the IDL: an error, not a match-everything instruction.
+            program.instructions.push(instruction("catchAll"));
+            let err = program_instructions(&program, &abi)
+                .unwrap_err()
+                .to_string();
+            assert_eq!(
+                err,
+                "Program 'Amm' has no instruction named 'catchAll'; give it a `discriminator`, \
+                 or use one of: initialize_pool, swap"
+            );
+
+            program.instructions.clear();
+            assert_eq!(
+                discriminators(&program),
+                vec![
+                    (
+                        "initialize_pool".to_string(),
+                        Some("0x0101010101010101".to_string())
+                    ),
+                    ("swap".to_string(), Some("0x0202020202020202".to_string())),
+                ]
+            );
+        }
     }
 }
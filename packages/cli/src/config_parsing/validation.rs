@@ -265,12 +265,25 @@ pub fn validate_deserialized_svm_config_yaml(
             .map(|e| e.programs.as_slice())
             .unwrap_or(&[]);
         for program in programs {
-            if !is_valid_solana_pubkey(&program.program_id) {
+            let Some(program_id) = program.program_id() else {
+                return Err(anyhow!(
+                    "Program {:?} has no program_id; declare one, or select a bundled `schema`",
+                    program.name
+                ));
+            };
+            if !is_valid_solana_pubkey(program_id) {
                 return Err(anyhow!(
                     "Program {:?} has an invalid program_id {:?}: must be a base58-encoded \
                      32-byte Solana pubkey",
                     program.name,
-                    program.program_id
+                    program_id
+                ));
+            }
+            if program.idl.is_some() && program.schema.is_some() {
+                return Err(anyhow!(
+                    "Program {:?} sets both `idl` and `schema`; its instructions are decoded with \
+                     one of them, so keep only one",
+                    program.name
                 ));
             }
             all_program_names.push(program.name.clone());
@@ -353,6 +366,90 @@ pub fn validate_deserialized_svm_config_yaml(
                     }
                 }
             }
+
+            let mut event_names = std::collections::HashSet::new();
+            for event in program.events.iter().flatten() {
+                if !event_names.insert(event.name.clone()) {
+                    return Err(anyhow!(
+                        "Program {:?} declares the event {:?} more than once",
+                        program.name,
+                        event.name
+                    ));
+                }
+                // Handlers are keyed on (program, name), so an event can't
+                // share its name with one of the program's instructions.
+                if program
+                    .instructions
+                    .iter()
+                    .any(|instr| instr.name == event.name)
+                {
+                    return Err(anyhow!(
+                        "Program {:?} declares {:?} as both an instruction and an event; rename \
+                         one of them",
+                        program.name,
+                        event.name
+                    ));
+                }
+                if let Some(discriminator) = &event.discriminator {
+                    let hex = discriminator.strip_prefix("0x").unwrap_or(discriminator);
+                    if hex.len() != 16 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        return Err(anyhow!(
+                            "Event {:?} in program {:?} has the discriminator {:?}; an Anchor \
+                             event discriminator is 8 bytes (16 hex digits after stripping a \
+                             `0x` prefix)",
+                            event.name,
+                            program.name,
+                            discriminator
+                        ));
+                    }
+                }
+            }
+
+            let mut account_names = std::collections::HashSet::new();
+            for account in program.accounts.iter().flatten() {
+                if !account_names.insert(account.name.clone()) {
+                    return Err(anyhow!(
+                        "Program {:?} declares the account {:?} more than once",
+                        program.name,
+                        account.name
+                    ));
+                }
+                if program
+                    .instructions
+                    .iter()
+                    .any(|instr| instr.name == account.name)
+                    || event_names.contains(&account.name)
+                {
+                    return Err(anyhow!(
+                        "Program {:?} declares {:?} as an account and as an instruction or \
+                         event; rename one of them",
+                        program.name,
+                        account.name
+                    ));
+                }
+                if let Some(discriminator) = &account.discriminator {
+                    let hex = discriminator.strip_prefix("0x").unwrap_or(discriminator);
+                    if hex.len() != 16 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        return Err(anyhow!(
+                            "Account {:?} in program {:?} has the discriminator {:?}; an Anchor \
+                             account discriminator is 8 bytes (16 hex digits after stripping a \
+                             `0x` prefix)",
+                            account.name,
+                            program.name,
+                            discriminator
+                        ));
+                    }
+                }
+            }
+            // HyperSync serves instructions but not account data, so the
+            // account state is read over the chain's RPC.
+            if !account_names.is_empty() && chain.rpc.is_none() {
+                return Err(anyhow!(
+                    "Program {:?} declares `accounts`, which need the chain's `rpc` to read \
+                     account data; HyperSync doesn't serve it",
+                    program.name
+                ));
+            }
         }
     }
 
@@ -717,5 +814,72 @@ chains:
             );
             validate_deserialized_svm_config_yaml(&cfg).unwrap();
         }
+
+        #[test]
+        fn validation_requires_a_program_id_or_a_bundled_schema() {
+            let yaml = |program: &str| {
+                format!(
+                    r#"
+name: x
+ecosystem: svm
+chains:
+  - id: solana
+    start_block: 0
+    experimental:
+      hypersync_config:
+        url: https://solana.hypersync.xyz
+      programs:
+        - name: P
+          {program}
+"#
+                )
+            };
+            let err = validate_deserialized_svm_config_yaml(&parse(&yaml("handler: x.ts")))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("has no program_id"), "{err}");
+            let err = validate_deserialized_svm_config_yaml(&parse(&yaml(
+                "schema: spl_token\n          idl: idl.json",
+            )))
+            .unwrap_err()
+            .to_string();
+            assert!(err.contains("both `idl` and `schema`"), "{err}");
+            validate_deserialized_svm_config_yaml(&parse(&yaml("schema: spl_token_2022"))).unwrap();
+        }
+
+        #[test]
+        fn validation_requires_rpc_for_program_accounts() {
+            let yaml = |rpc: &str| {
+                format!(
+                    r#"
+name: x
+ecosystem: svm
+chains:
+  - id: solana
+    {rpc}
+    start_block: 0
+    experimental:
+      hypersync_config:
+        url: https://solana.hypersync.xyz
+      programs:
+        - name: P
+          program_id: metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s
+          accounts:
+            - name: Pool
+              fields:
+                - name: amount
+                  type: u64
+"#
+                )
+            };
+            let err = validate_deserialized_svm_config_yaml(&parse(&yaml("")))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("need the chain's `rpc`"), "{err}");
+            validate_deserialized_svm_config_yaml(&parse(&yaml(
+                "rpc: https://api.mainnet-beta.solana.com",
+            )))
+            .unwrap();
+        }
     }
 }
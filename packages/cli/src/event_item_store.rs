@@ -0,0 +1,506 @@
+//! Fetched EVM event items, kept columnar in Rust until a batch takes them.
+//! A `Table` keyed by each item's place in the chain — block, log index, call
+//! path, registration — so the table's own order is the processing order and
+//! taking the next batch is a walk from its front. A re-fetched item lands on
+//! its existing key, so overlapping partition responses never duplicate it.
+//! Decoded params are stored encoded and only become JS values in `pop_batch`.
+
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use napi::bindgen_prelude::BigInt;
+use napi_derive::napi;
+use strum::VariantArray;
+
+use crate::evm_hypersync_source::map_err;
+use crate::evm_hypersync_source::types::ParamValue;
+use crate::evm_hypersync_source::EventItem;
+use crate::field_table::{str_from, u64_from, var_from, AnyCol, Table};
+
+/// An item's place in the processing order: block, log index (the transaction
+/// index for calls), call path (`None` for logs), then registration, so one
+/// log routed to two registrations is two items.
+type ItemKey = (i64, i64, Option<Box<[i64]>>, i64);
+
+/// The lowest key of `block`: every item of a lower block sorts before it.
+fn block_start(block: i64) -> ItemKey {
+    (block, i64::MIN, None, i64::MIN)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, VariantArray)]
+#[repr(usize)]
+enum ItemField {
+    SrcAddress = 0,
+    TransactionIndex = 1,
+    Params = 2,
+}
+
+/// Popped items as aligned columns, in processing order. The caller zips them
+/// into its own item objects.
+#[napi(object)]
+pub struct EventItemBatch {
+    pub block_numbers: Vec<i64>,
+    pub log_indexes: Vec<i64>,
+    pub transaction_indexes: Vec<i64>,
+    pub registration_indexes: Vec<i64>,
+    pub src_addresses: Vec<String>,
+    pub params: Vec<ParamValue>,
+    /// Set on call and transaction items only; see `EventItem::trace_address`.
+    pub trace_addresses: Vec<Option<Vec<i64>>>,
+}
+
+#[napi]
+pub struct EventItemStore {
+    inner: Mutex<Table<ItemKey>>,
+}
+
+#[napi]
+impl EventItemStore {
+    /// Empty store. Used for both fetch-response pages and a per-chain queue.
+    #[napi(factory)]
+    pub fn new_evm() -> Self {
+        Self {
+            inner: Mutex::new(Table::new(ItemField::VARIANTS.len())),
+        }
+    }
+
+    /// Move every item from `page` into this store. An item already queued
+    /// under the same key is replaced by the page's copy.
+    #[napi]
+    pub fn merge(&self, page: &EventItemStore) {
+        // Merging a store into itself would lock the same Mutex twice (deadlock).
+        if std::ptr::eq(self, page) {
+            return;
+        }
+        let mut src = page.inner.lock().unwrap();
+        self.inner.lock().unwrap().append_from(&mut src);
+    }
+
+    #[napi]
+    pub fn len(&self) -> u32 {
+        self.inner.lock().unwrap().keys().count() as u32
+    }
+
+    /// Block of the first queued item.
+    #[napi]
+    pub fn first_block_number(&self) -> Option<i64> {
+        self.inner.lock().unwrap().keys().next().map(|key| key.0)
+    }
+
+    /// Number of queued items at or below `up_to_block`.
+    #[napi]
+    pub fn ready_count(&self, up_to_block: i64) -> u32 {
+        self.inner
+            .lock()
+            .unwrap()
+            .keys()
+            .take_while(|key| key.0 <= up_to_block)
+            .count() as u32
+    }
+
+    /// Remove and return the first items in processing order: those at or
+    /// below `up_to_block` (every item when `None`), at most `max_items` of
+    /// them — except that a block is never split, so the last block taken is
+    /// taken whole.
+    #[napi]
+    pub fn pop_batch(
+        &self,
+        up_to_block: Option<i64>,
+        max_items: Option<u32>,
+    ) -> napi::Result<EventItemBatch> {
+        let mut table = self.inner.lock().unwrap();
+        let mut taken: Vec<ItemKey> = Vec::new();
+        for key in table.keys() {
+            if up_to_block.is_some_and(|up_to| key.0 > up_to) {
+                break;
+            }
+            let is_full = max_items.is_some_and(|max| taken.len() >= max as usize);
+            if is_full && taken.last().is_none_or(|last| last.0 != key.0) {
+                break;
+            }
+            taken.push(key.clone());
+        }
+        let batch = read_batch(&table, &taken).map_err(map_err)?;
+        for key in &taken {
+            table.remove(key);
+        }
+        Ok(batch)
+    }
+
+    /// Copies of the items routed to any of `registration_indexes`, in
+    /// processing order. The items stay queued.
+    #[napi]
+    pub fn peek_registrations(
+        &self,
+        registration_indexes: Vec<i64>,
+    ) -> napi::Result<EventItemBatch> {
+        let table = self.inner.lock().unwrap();
+        let keys: Vec<ItemKey> = table
+            .keys()
+            .filter(|key| registration_indexes.contains(&key.3))
+            .cloned()
+            .collect();
+        read_batch(&table, &keys).map_err(map_err)
+    }
+
+    /// Remove and return the items above `after_block`, in processing order.
+    #[napi]
+    pub fn pop_above(&self, after_block: i64) -> napi::Result<EventItemBatch> {
+        let from = block_start(after_block.saturating_add(1));
+        let mut table = self.inner.lock().unwrap();
+        let taken: Vec<ItemKey> = table.keys().filter(|key| **key >= from).cloned().collect();
+        let batch = read_batch(&table, &taken).map_err(map_err)?;
+        for key in &taken {
+            table.remove(key);
+        }
+        Ok(batch)
+    }
+
+    /// Drop items at or below `up_to_block`.
+    #[napi]
+    pub fn prune(&self, up_to_block: i64) {
+        let below = block_start(up_to_block.saturating_add(1));
+        let mut table = self.inner.lock().unwrap();
+        let dead: Vec<ItemKey> = table
+            .keys()
+            .take_while(|key| **key < below)
+            .cloned()
+            .collect();
+        for key in &dead {
+            table.remove(key);
+        }
+    }
+
+    /// Drop items above `target_block` (rolled back).
+    #[napi]
+    pub fn rollback(&self, target_block: i64) {
+        let from = block_start(target_block.saturating_add(1));
+        let mut table = self.inner.lock().unwrap();
+        let dead: Vec<ItemKey> = table.keys().filter(|key| **key >= from).cloned().collect();
+        for key in &dead {
+            table.remove(key);
+        }
+    }
+}
+
+impl EventItemStore {
+    /// A response page holding `items`. Not exposed to JS: items are only
+    /// created by a source's response conversion.
+    pub(crate) fn from_items(items: Vec<EventItem>) -> Self {
+        let store = Self::new_evm();
+        if items.is_empty() {
+            return store;
+        }
+        let params: Vec<Vec<u8>> = items
+            .iter()
+            .map(|item| {
+                let mut out = Vec::new();
+                encode_param(&item.params, &mut out);
+                out
+            })
+            .collect();
+        let cols: Vec<Option<AnyCol>> = ItemField::VARIANTS
+            .iter()
+            .map(|field| match field {
+                ItemField::SrcAddress => str_from(&items, |item| Some(item.src_address.as_str())),
+                ItemField::TransactionIndex => {
+                    u64_from(&items, |item| u64::try_from(item.transaction_index).ok())
+                }
+                ItemField::Params => var_from(&params, |p| Some(p.as_slice())),
+            })
+            .collect();
+        let keys = items
+            .into_iter()
+            .map(|item| {
+                (
+                    item.block_number,
+                    item.log_index,
+                    item.trace_address.map(Vec::into_boxed_slice),
+                    item.on_event_registration_index,
+                )
+            })
+            .collect();
+        store.inner.lock().unwrap().merge_batch(keys, cols);
+        store
+    }
+}
+
+fn read_batch(table: &Table<ItemKey>, keys: &[ItemKey]) -> Result<EventItemBatch> {
+    let mut batch = EventItemBatch {
+        block_numbers: Vec::with_capacity(keys.len()),
+        log_indexes: Vec::with_capacity(keys.len()),
+        transaction_indexes: Vec::with_capacity(keys.len()),
+        registration_indexes: Vec::with_capacity(keys.len()),
+        src_addresses: Vec::with_capacity(keys.len()),
+        params: Vec::with_capacity(keys.len()),
+        trace_addresses: Vec::with_capacity(keys.len()),
+    };
+    for key in keys {
+        let (block_number, log_index, trace_address, registration_index) = key;
+        let src_address = table
+            .field_bytes(key, ItemField::SrcAddress as usize)
+            .context("queued item without a srcAddress")?;
+        let transaction_index = table
+            .field_u64(key, ItemField::TransactionIndex as usize)
+            .context("queued item without a transactionIndex")?;
+        let mut params = table
+            .field_bytes(key, ItemField::Params as usize)
+            .context("queued item without params")?;
+        batch.block_numbers.push(*block_number);
+        batch.log_indexes.push(*log_index);
+        batch.transaction_indexes.push(transaction_index as i64);
+        batch.registration_indexes.push(*registration_index);
+        batch
+            .src_addresses
+            .push(String::from_utf8_lossy(src_address).into_owned());
+        batch.params.push(decode_param(&mut params)?);
+        batch
+            .trace_addresses
+            .push(trace_address.as_ref().map(|path| path.to_vec()));
+    }
+    Ok(batch)
+}
+
+const TAG_FALSE: u8 = 0;
+const TAG_TRUE: u8 = 1;
+const TAG_BIGINT: u8 = 2;
+const TAG_STR: u8 = 3;
+const TAG_ARR: u8 = 4;
+const TAG_OBJ: u8 = 5;
+
+/// Tag byte, then the value; lengths and counts are little-endian `u32`s.
+fn encode_param(value: &ParamValue, out: &mut Vec<u8>) {
+    fn encode_str(s: &str, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        out.extend_from_slice(s.as_bytes());
+    }
+    match value {
+        ParamValue::Bool(false) => out.push(TAG_FALSE),
+        ParamValue::Bool(true) => out.push(TAG_TRUE),
+        ParamValue::BigInt(v) => {
+            out.push(TAG_BIGINT);
+            out.push(v.sign_bit as u8);
+            out.extend_from_slice(&(v.words.len() as u32).to_le_bytes());
+            for word in &v.words {
+                out.extend_from_slice(&word.to_le_bytes());
+            }
+        }
+        ParamValue::Str(s) => {
+            out.push(TAG_STR);
+            encode_str(s, out);
+        }
+        ParamValue::Arr(items) => {
+            out.push(TAG_ARR);
+            out.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            for item in items {
+                encode_param(item, out);
+            }
+        }
+        ParamValue::Obj(entries) => {
+            out.push(TAG_OBJ);
+            out.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+            for (name, item) in entries {
+                encode_str(name, out);
+                encode_param(item, out);
+            }
+        }
+    }
+}
+
+fn decode_param(input: &mut &[u8]) -> Result<ParamValue> {
+    fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+        anyhow::ensure!(input.len() >= len, "truncated encoded param");
+        let (head, rest) = input.split_at(len);
+        *input = rest;
+        Ok(head)
+    }
+    fn take_u32(input: &mut &[u8]) -> Result<usize> {
+        Ok(u32::from_le_bytes(take(input, 4)?.try_into().unwrap()) as usize)
+    }
+    fn take_str(input: &mut &[u8]) -> Result<String> {
+        let len = take_u32(input)?;
+        String::from_utf8(take(input, len)?.to_vec()).context("encoded param isn't UTF-8")
+    }
+    Ok(match take(input, 1)?[0] {
+        TAG_FALSE => ParamValue::Bool(false),
+        TAG_TRUE => ParamValue::Bool(true),
+        TAG_BIGINT => {
+            let sign_bit = take(input, 1)?[0] != 0;
+            let count = take_u32(input)?;
+            let words = take(input, count * 8)?
+                .chunks_exact(8)
+                .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+                .collect();
+            ParamValue::BigInt(BigInt { sign_bit, words })
+        }
+        TAG_STR => ParamValue::Str(take_str(input)?),
+        TAG_ARR => {
+            let count = take_u32(input)?;
+            let items = (0..count)
+                .map(|_| decode_param(input))
+                .collect::<Result<_>>()?;
+            ParamValue::Arr(items)
+        }
+        TAG_OBJ => {
+            let count = take_u32(input)?;
+            let entries = (0..count)
+                .map(|_| Ok((take_str(input)?, decode_param(input)?)))
+                .collect::<Result<_>>()?;
+            ParamValue::Obj(entries)
+        }
+        tag => anyhow::bail!("unknown encoded param tag {tag}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(block_number: i64, log_index: i64, registration: i64) -> EventItem {
+        EventItem {
+            log_index,
+            src_address: format!("0x{:040x}", block_number),
+            block_number,
+            transaction_index: log_index / 2,
+            on_event_registration_index: registration,
+            params: ParamValue::Obj(vec![
+                ("from".to_string(), ParamValue::Str("0xabc".to_string())),
+                (
+                    "value".to_string(),
+                    ParamValue::BigInt(BigInt {
+                        sign_bit: false,
+                        words: vec![block_number as u64],
+                    }),
+                ),
+                (
+                    "flags".to_string(),
+                    ParamValue::Arr(vec![ParamValue::Bool(true), ParamValue::Bool(false)]),
+                ),
+            ]),
+            trace_address: None,
+        }
+    }
+
+    fn positions(batch: &EventItemBatch) -> Vec<(i64, i64, i64)> {
+        (0..batch.block_numbers.len())
+            .map(|i| {
+                (
+                    batch.block_numbers[i],
+                    batch.log_indexes[i],
+                    batch.registration_indexes[i],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merged_pages_pop_in_processing_order_without_duplicates() {
+        let store = EventItemStore::new_evm();
+        store.merge(&EventItemStore::from_items(vec![
+            item(12, 0, 0),
+            item(10, 3, 0),
+        ]));
+        // An overlapping re-fetch: block 10's log again, plus a second
+        // registration for the same log.
+        store.merge(&EventItemStore::from_items(vec![
+            item(10, 3, 1),
+            item(10, 3, 0),
+            item(11, 1, 0),
+        ]));
+
+        assert_eq!(store.len(), 4);
+        let batch = store.pop_batch(None, None).unwrap();
+        assert_eq!(
+            positions(&batch),
+            vec![(10, 3, 0), (10, 3, 1), (11, 1, 0), (12, 0, 0)]
+        );
+        assert_eq!(store.len(), 0);
+    }
+
+    #[test]
+    fn pop_batch_stops_at_the_ready_block_and_never_splits_one() {
+        let store = EventItemStore::from_items(vec![
+            item(10, 0, 0),
+            item(10, 1, 0),
+            item(10, 2, 0),
+            item(11, 0, 0),
+            item(12, 0, 0),
+        ]);
+        assert_eq!(store.ready_count(11), 4);
+
+        // Two items asked for, but block 10 has three.
+        let batch = store.pop_batch(Some(11), Some(2)).unwrap();
+        assert_eq!(batch.block_numbers, vec![10, 10, 10]);
+
+        let batch = store.pop_batch(Some(11), Some(2)).unwrap();
+        assert_eq!(batch.block_numbers, vec![11]);
+        assert_eq!(store.first_block_number(), Some(12));
+    }
+
+    #[test]
+    fn popped_items_keep_their_fields() {
+        let mut call = item(10, 4, 2);
+        call.trace_address = Some(vec![0, 1]);
+        let store = EventItemStore::from_items(vec![call, item(10, 4, 0)]);
+
+        let batch = store.pop_batch(None, None).unwrap();
+        assert_eq!(batch.trace_addresses, vec![None, Some(vec![0, 1])]);
+        assert_eq!(batch.transaction_indexes, vec![2, 2]);
+        assert_eq!(batch.src_addresses[0], format!("0x{:040x}", 10));
+
+        let mut expected = Vec::new();
+        encode_param(&item(10, 4, 0).params, &mut expected);
+        let mut decoded = Vec::new();
+        encode_param(&batch.params[0], &mut decoded);
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn prune_and_rollback_drop_by_block() {
+        let store = EventItemStore::from_items(vec![
+            item(10, 0, 0),
+            item(11, 0, 0),
+            item(12, 0, 0),
+            item(13, 0, 0),
+        ]);
+        store.prune(10);
+        store.rollback(12);
+        let batch = store.pop_batch(None, None).unwrap();
+        assert_eq!(batch.block_numbers, vec![11, 12]);
+    }
+
+    #[test]
+    fn peek_registrations_leaves_the_items_queued() {
+        let store = EventItemStore::from_items(vec![
+            item(10, 0, 0),
+            item(10, 0, 1),
+            item(11, 2, 2),
+            item(12, 0, 1),
+        ]);
+        let peeked = store.peek_registrations(vec![1, 2]).unwrap();
+        assert_eq!(positions(&peeked), vec![(10, 0, 1), (11, 2, 2), (12, 0, 1)]);
+        assert_eq!(store.len(), 4);
+    }
+
+    #[test]
+    fn pop_above_takes_the_later_blocks() {
+        let store = EventItemStore::from_items(vec![
+            item(10, 0, 0),
+            item(11, 0, 0),
+            item(11, 1, 0),
+            item(12, 0, 0),
+        ]);
+        let batch = store.pop_above(10).unwrap();
+        assert_eq!(batch.block_numbers, vec![11, 11, 12]);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.first_block_number(), Some(10));
+    }
+
+    #[test]
+    fn a_truncated_param_is_an_error() {
+        let mut encoded = Vec::new();
+        encode_param(&ParamValue::Str("transfer".to_string()), &mut encoded);
+        encoded.truncate(encoded.len() - 1);
+        assert!(decode_param(&mut encoded.as_slice()).is_err());
+    }
+}
@@ -19,6 +19,9 @@ pub struct ClientConfig {
     /// or a directive like "hypersync_client=debug"). RUST_LOG env var takes
     /// precedence. Only the first client's value takes effect.
     pub log_level: Option<String>,
+    /// Debug mode: every response page is written under this directory as
+    /// Arrow IPC files, one per table. See `response_capture`.
+    pub capture_dir: Option<String>,
 }
 
 impl From<ClientConfig> for hypersync_client::ClientConfig {
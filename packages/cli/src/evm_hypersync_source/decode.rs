@@ -1,16 +1,17 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use alloy_dyn_abi::{DecodedEvent, DynSolEvent, DynSolType};
+use alloy_dyn_abi::{DecodedEvent, DynSolEvent, DynSolType, DynSolValue};
 use alloy_primitives::B256;
 use anyhow::{Context, Result};
 use hypersync_client::format::{Data, Hex, LogArgument};
 use hypersync_client::simple_types;
 
-use crate::address_store::{AddressStore, SetCache, StoreInner};
+use crate::address_store::{AddressRegistration, AddressStore, SetCache, StoreInner};
+use crate::evm_hypersync_source::param_where::ParamFilters;
 use crate::evm_hypersync_source::selection::TopicSelectionInput;
 use crate::evm_hypersync_source::types::{
-    sol_value_to_param, Log, OnEventRegistrationInput, ParamMeta, ParamValue,
+    sol_value_to_param, EvmEventKind, Log, OnEventRegistrationInput, ParamMeta, ParamValue,
 };
 
 /// One topic position's constraint, resolved from a registration's `where`.
@@ -31,7 +32,9 @@ enum TopicConstraint {
 
 /// The emitter facts every address gate reads off a log: its binary address,
 /// the contract this partition's set says owns that address (`None` when the
-/// partition doesn't hold it), and the log's block.
+/// partition doesn't hold it), and the log's block. That ownership lookup is
+/// the exact check behind a bloom-filtered query: a false-positive emitter the
+/// server let through has no owner, so only wildcards see it.
 pub(crate) struct LogAddress<'a> {
     pub key: &'a [u8],
     pub contract_name: Option<&'a str>,
@@ -133,15 +136,13 @@ impl TopicFilters {
     }
 }
 
-/// Everything needed to match a log against one registration and decode it
-/// under that registration's own ABI declaration. Registrations sharing an
-/// event signature stay fully independent — each carries its own decoder, so
-/// they may name params differently and even split indexed/body params
-/// differently.
+/// Everything needed to match a log (or, for a call registration, a trace)
+/// against one registration and decode it under that registration's own ABI
+/// declaration. Registrations sharing an event signature stay fully
+/// independent — each carries its own decoder, so they may name params
+/// differently and even split indexed/body params differently.
 struct OnEventRegistration {
     index: i64,
-    sighash: [u8; 32],
-    topic_count: u8,
     contract_name: String,
     /// This registration's contract in the chain's address store, resolved once
     /// at construction so every per-log gate is an index compare.
@@ -149,52 +150,138 @@ struct OnEventRegistration {
     is_wildcard: bool,
     /// Earliest block this registration accepts; `None` is unrestricted.
     start_block: Option<i64>,
-    topic_filters: TopicFilters,
     params: Vec<ParamMeta>,
-    decoder: DynSolEvent,
+    /// The `where.params` conditions, checked on the decoded values.
+    param_filters: ParamFilters,
+    /// The contract a routed item's address param is registered for, with the
+    /// param's name.
+    register: Option<(String, String)>,
+    kind: RegistrationKind,
+}
+
+/// Where a call was read from. A call registration routes only traces, a
+/// transaction registration only transactions, even for the same selector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CallSource {
+    Trace,
+    Transaction,
+}
+
+/// The matching and decoding state that differs between a log and a call
+/// registration, resolved once at construction.
+enum RegistrationKind {
+    Log {
+        sighash: [u8; 32],
+        topic_count: u8,
+        topic_filters: TopicFilters,
+        decoder: DynSolEvent,
+    },
+    /// A call's params are the function inputs, ABI-encoded after the 4-byte
+    /// selector. Calls carry no topics, so a `where` on params never applies.
+    Call {
+        source: CallSource,
+        selector: [u8; 4],
+        decoder: DynSolType,
+    },
+    /// A contract the registration's addresses create, read from a create
+    /// trace or a deploying transaction. Its one param is the created address.
+    Creation { source: CallSource },
 }
 
 impl OnEventRegistration {
     fn parse(ep: &OnEventRegistrationInput, store: &StoreInner) -> Result<Self> {
-        let sighash = LogArgument::decode_hex(&ep.sighash).context("decode sighash hex")?;
-        let topic_count: u8 =
-            u8::try_from(ep.topic_count).context("topic_count out of u8 range")?;
-        anyhow::ensure!(
-            (1..=4).contains(&topic_count),
-            "topic_count must be 1..=4, got {topic_count}",
-        );
         let contract_idx = store.contract_idx(&ep.contract_name).with_context(|| {
             format!(
                 "Contract {} is missing from the chain's address store",
                 ep.contract_name
             )
         })?;
+        let kind = match ep.kind.unwrap_or_default() {
+            EvmEventKind::Log => {
+                let sighash = LogArgument::decode_hex(&ep.sighash).context("decode sighash hex")?;
+                let topic_count: u8 =
+                    u8::try_from(ep.topic_count).context("topic_count out of u8 range")?;
+                anyhow::ensure!(
+                    (1..=4).contains(&topic_count),
+                    "topic_count must be 1..=4, got {topic_count}",
+                );
+                RegistrationKind::Log {
+                    sighash: **sighash,
+                    topic_count,
+                    topic_filters: TopicFilters::parse(&ep.topic_selections)
+                        .context("parse topic filters")?,
+                    decoder: build_event_decoder(**sighash, &ep.params).context("build decoder")?,
+                }
+            }
+            kind @ (EvmEventKind::Call | EvmEventKind::Transaction) => {
+                let selector = Data::decode_hex(&ep.sighash).context("decode selector hex")?;
+                let selector = <[u8; 4]>::try_from(&selector[..]).with_context(|| {
+                    format!(
+                        "call selector must be 4 bytes, got {} bytes",
+                        selector.len()
+                    )
+                })?;
+                RegistrationKind::Call {
+                    source: if kind == EvmEventKind::Call {
+                        CallSource::Trace
+                    } else {
+                        CallSource::Transaction
+                    },
+                    selector,
+                    decoder: build_call_decoder(&ep.params).context("build decoder")?,
+                }
+            }
+            kind @ (EvmEventKind::Create | EvmEventKind::Deploy) => {
+                anyhow::ensure!(
+                    matches!(ep.params.as_slice(), [param] if param.abi_type == "address"),
+                    "a creation takes exactly one address param, the created contract",
+                );
+                RegistrationKind::Creation {
+                    source: if kind == EvmEventKind::Create {
+                        CallSource::Trace
+                    } else {
+                        CallSource::Transaction
+                    },
+                }
+            }
+        };
+        let register = ep
+            .register
+            .as_ref()
+            .map(|register| -> Result<_> {
+                anyhow::ensure!(
+                    store.contract_idx(&register.contract_name).is_some(),
+                    "register names contract {}, which is missing from the chain's address store",
+                    register.contract_name
+                );
+                anyhow::ensure!(
+                    ep.params
+                        .iter()
+                        .any(|param| param.name == register.param && param.abi_type == "address"),
+                    "register reads param {}, which isn't an address param",
+                    register.param
+                );
+                Ok((register.contract_name.clone(), register.param.clone()))
+            })
+            .transpose()?;
         Ok(Self {
             index: ep.index,
-            sighash: **sighash,
-            topic_count,
             contract_name: ep.contract_name.clone(),
             contract_idx,
             is_wildcard: ep.is_wildcard,
             start_block: ep.start_block,
-            topic_filters: TopicFilters::parse(&ep.topic_selections)
-                .context("parse topic filters")?,
             params: ep.params.clone(),
-            decoder: build_event_decoder(**sighash, &ep.params).context("build decoder")?,
+            param_filters: ParamFilters::parse(ep.params_where.as_deref(), &ep.params)
+                .context("parse where.params")?,
+            register,
+            kind,
         })
     }
 
-    /// Whether a log belongs to this registration: same event signature
-    /// (topic0 + topic count), at or after the registration's own start block,
-    /// an allowed emitter, and the registration's topic filters.
-    ///
-    /// Emitter rules. A wildcard registration accepts any address. A
-    /// contract-bound one accepts only an address this partition's set holds for
-    /// its own contract (`address.contract_name`), registered at or before the
-    /// log's block — the temporal half matters even when the partition fetched
-    /// the address server-side, because a merged partition's addresses don't all
-    /// start at the same block. A client-filtered contract has none of its
-    /// addresses in the query, so there the store answers ownership on its own.
+    /// Whether a log belongs to this registration: a log registration with the
+    /// same event signature (topic0 + topic count), at or after the
+    /// registration's own start block, an allowed emitter, and the
+    /// registration's topic filters.
     #[allow(clippy::too_many_arguments)]
     fn matches(
         &self,
@@ -206,13 +293,19 @@ impl OnEventRegistration {
         cache: &SetCache,
         store: &StoreInner,
     ) -> bool {
-        self.sighash == *topic0
-            && self.topic_count == topic_count
-            && crate::registration_start_block::has_started(self.start_block, address.block_number)
-            && (self.is_wildcard
-                || ((force_wildcard || address.contract_name == Some(self.contract_name.as_str()))
-                    && store.is_indexed_at(address.key, self.contract_idx, address.block_number)))
-            && self.topic_filters.matches(
+        let RegistrationKind::Log {
+            sighash,
+            topic_count: reg_topic_count,
+            topic_filters,
+            ..
+        } = &self.kind
+        else {
+            return false;
+        };
+        sighash == topic0
+            && *reg_topic_count == topic_count
+            && self.accepts(address, force_wildcard, store)
+            && topic_filters.matches(
                 topics,
                 &self.contract_name,
                 self.contract_idx,
@@ -222,6 +315,60 @@ impl OnEventRegistration {
                 store,
             )
     }
+
+    /// Whether a call belongs to this registration: a call registration of the
+    /// call's source with the same selector, and a callee (`address`) the
+    /// registration accepts.
+    fn matches_call(
+        &self,
+        call_source: CallSource,
+        input_selector: &[u8],
+        address: &LogAddress,
+        force_wildcard: bool,
+        store: &StoreInner,
+    ) -> bool {
+        let RegistrationKind::Call {
+            source, selector, ..
+        } = &self.kind
+        else {
+            return false;
+        };
+        *source == call_source
+            && selector.as_slice() == input_selector
+            && self.accepts(address, force_wildcard, store)
+    }
+
+    /// Whether a creation belongs to this registration: a creation
+    /// registration of the creation's source, and a deployer (`address`) the
+    /// registration accepts.
+    fn matches_creation(
+        &self,
+        creation_source: CallSource,
+        address: &LogAddress,
+        force_wildcard: bool,
+        store: &StoreInner,
+    ) -> bool {
+        let RegistrationKind::Creation { source } = &self.kind else {
+            return false;
+        };
+        *source == creation_source && self.accepts(address, force_wildcard, store)
+    }
+
+    /// The start-block and emitter gates shared by logs and calls.
+    ///
+    /// Emitter rules. A wildcard registration accepts any address. A
+    /// contract-bound one accepts only an address this partition's set holds for
+    /// its own contract (`address.contract_name`), registered at or before the
+    /// log's block — the temporal half matters even when the partition fetched
+    /// the address server-side, because a merged partition's addresses don't all
+    /// start at the same block. A client-filtered contract has none of its
+    /// addresses in the query, so there the store answers ownership on its own.
+    fn accepts(&self, address: &LogAddress, force_wildcard: bool, store: &StoreInner) -> bool {
+        crate::registration_start_block::has_started(self.start_block, address.block_number)
+            && (self.is_wildcard
+                || ((force_wildcard || address.contract_name == Some(self.contract_name.as_str()))
+                    && store.is_indexed_at(address.key, self.contract_idx, address.block_number)))
+    }
 }
 
 /// All registrations passed at client construction, keyed by their
@@ -376,7 +523,8 @@ impl SelectionDecoder {
     ///
     /// Same-signature registrations may declare different indexed/body splits,
     /// and the log's bytes need not be valid under every declaration — a match
-    /// that fails to decode (or to name its params) just contributes no item.
+    /// that fails to decode (or to name its params) just contributes no item,
+    /// as does one whose decoded body fails the registration's `where.params`.
     /// A decode failure is benign whether or not a sibling in the selection
     /// happens to decode: a wildcard registration routinely fetches foreign
     /// same-signature logs whose indexed split its own declaration can't read,
@@ -416,16 +564,22 @@ impl SelectionDecoder {
             ) {
                 continue;
             }
-            let decoded = reg.decoder.decode_log_parts(
+            let RegistrationKind::Log { decoder, .. } = &reg.kind else {
+                continue;
+            };
+            let decoded = decoder.decode_log_parts(
                 topics
                     .iter()
                     .take_while(|t| t.is_some())
                     .map(|t| t.as_ref().unwrap().into()),
                 data,
             );
-            let fields = decoded.ok().and_then(|decoded| {
-                apply_names(decoded, &reg.params, self.checksummed_addresses).ok()
-            });
+            let fields = decoded
+                .ok()
+                .filter(|decoded| reg.param_filters.accepts(&decoded.body))
+                .and_then(|decoded| {
+                    apply_names(decoded, &reg.params, self.checksummed_addresses).ok()
+                });
             if let Some(fields) = fields {
                 routed.push(RoutedEvent {
                     index: reg.index,
@@ -435,6 +589,122 @@ impl SelectionDecoder {
         }
         Ok(routed)
     }
+
+    /// Fans a call out to every call registration of the selection it matches,
+    /// decoding its input under each match's own declaration. `address` is the
+    /// callee; for a transaction, its `to`. Like a log, an input that fails to
+    /// decode under a declaration, or fails its `where.params`, contributes no
+    /// item; input shorter than a selector routes nowhere.
+    pub(crate) fn route_and_decode_call(
+        &self,
+        source: CallSource,
+        input: &[u8],
+        address: &LogAddress,
+        store: &StoreInner,
+    ) -> Vec<RoutedEvent> {
+        let Some((selector, args)) = input.split_first_chunk::<4>() else {
+            return Vec::new();
+        };
+        let mut routed = Vec::new();
+        for sel in &self.registrations {
+            let reg = &sel.registration;
+            if !reg.matches_call(source, selector, address, sel.force_wildcard, store) {
+                continue;
+            }
+            let RegistrationKind::Call { decoder, .. } = &reg.kind else {
+                continue;
+            };
+            let fields = decoder
+                .abi_decode_params(args)
+                .ok()
+                .and_then(|decoded| match decoded {
+                    DynSolValue::Tuple(values) if reg.param_filters.accepts(&values) => {
+                        Some(name_values(values, &reg.params, self.checksummed_addresses))
+                    }
+                    _ => None,
+                });
+            if let Some(fields) = fields {
+                routed.push(RoutedEvent {
+                    index: reg.index,
+                    params: ParamValue::Obj(fields),
+                });
+            }
+        }
+        routed
+    }
+
+    /// Fans a creation out to every creation registration of the selection it
+    /// matches. `address` is the deployer; `created` the created contract,
+    /// which is the item's one param and is checked against its
+    /// `where.params`.
+    pub(crate) fn route_creation(
+        &self,
+        source: CallSource,
+        created: &[u8; 20],
+        address: &LogAddress,
+        store: &StoreInner,
+    ) -> Vec<RoutedEvent> {
+        let value = DynSolValue::Address(created.into());
+        let mut routed = Vec::new();
+        for sel in &self.registrations {
+            let reg = &sel.registration;
+            if !reg.matches_creation(source, address, sel.force_wildcard, store)
+                || !reg.param_filters.accepts(std::slice::from_ref(&value))
+            {
+                continue;
+            }
+            routed.push(RoutedEvent {
+                index: reg.index,
+                params: ParamValue::Obj(name_values(
+                    vec![value.clone()],
+                    &reg.params,
+                    self.checksummed_addresses,
+                )),
+            });
+        }
+        routed
+    }
+
+    /// The registrations a page's routed items make — `(registration index,
+    /// block number, params)` per item, in item order — for the selection's
+    /// registrations with a `register`. An item whose param isn't an address
+    /// string registers nothing.
+    pub(crate) fn registrations<'a>(
+        &self,
+        items: impl IntoIterator<Item = (i64, i64, &'a ParamValue)>,
+    ) -> Vec<AddressRegistration> {
+        if self
+            .registrations
+            .iter()
+            .all(|sel| sel.registration.register.is_none())
+        {
+            return Vec::new();
+        }
+        items
+            .into_iter()
+            .filter_map(|(index, block_number, params)| {
+                let sel = self
+                    .registrations
+                    .binary_search_by_key(&index, |sel| sel.registration.index)
+                    .ok()
+                    .map(|position| &self.registrations[position])?;
+                let (contract_name, param) = sel.registration.register.as_ref()?;
+                let ParamValue::Obj(fields) = params else {
+                    return None;
+                };
+                let address = fields.iter().find_map(|(name, value)| match value {
+                    ParamValue::Str(address) if name == param => Some(address.clone()),
+                    _ => None,
+                })?;
+                Some(AddressRegistration {
+                    address,
+                    contract_name: contract_name.clone(),
+                    registration_block: block_number,
+                    end_block: None,
+                })
+            })
+            .collect()
+    }
 }
 
 pub(crate) struct RoutedEvent {
@@ -467,6 +737,37 @@ fn apply_names(
         .collect()
 }
 
+/// Names a call's decoded inputs positionally; call params are never indexed.
+fn name_values(
+    values: Vec<DynSolValue>,
+    params: &[ParamMeta],
+    checksummed_addresses: bool,
+) -> Vec<(String, ParamValue)> {
+    params
+        .iter()
+        .zip(values)
+        .map(|(param, value)| {
+            (
+                param.name.clone(),
+                sol_value_to_param(value, param.components.as_deref(), checksummed_addresses),
+            )
+        })
+        .collect()
+}
+
+/// The decoder for a call's inputs: their types as one tuple, the shape
+/// `abi_decode_params` reads calldata (past the selector) as.
+fn build_call_decoder(params: &[ParamMeta]) -> Result<DynSolType> {
+    params
+        .iter()
+        .map(|param| {
+            DynSolType::parse(&param.abi_type)
+                .with_context(|| format!("parse abi type {}", param.abi_type))
+        })
+        .collect::<Result<_>>()
+        .map(DynSolType::Tuple)
+}
+
 /// Build the positional decoder for one registration. The decoder's topic0 is
 /// pinned to the on-chain sighash the registration carries rather than derived
 /// from a signature string, so an event surfaced to handlers under a different
@@ -576,6 +877,7 @@ mod tests {
     ) -> OnEventRegistrationInput {
         OnEventRegistrationInput {
             index,
+            kind: None,
             sighash: sighash.to_string(),
             topic_count: 1,
             event_name: "E".to_string(),
@@ -587,6 +889,9 @@ mod tests {
             block_fields: vec![],
             transaction_fields: vec![],
             params: vec![pm("value", "uint256", false)],
+            transaction_where: None,
+            params_where: None,
+            register: None,
         }
     }
 
@@ -680,6 +985,47 @@ mod tests {
         assert!(format!("{err:#}").contains("Unknown registration index 7"));
     }
 
+    #[test]
+    fn register_param_becomes_an_address_registration() {
+        use alloy_dyn_abi::DynSolValue;
+        use alloy_primitives::{hex, Address};
+
+        let mut reg = value_reg(3, "Factory", false, VALID_SIGHASH);
+        reg.params = vec![pm("pair", "address", false)];
+        reg.register = Some(crate::evm_hypersync_source::types::RegisterInput {
+            contract_name: "Pair".to_string(),
+            param: "pair".to_string(),
+        });
+        let address_store = store(&["Factory", "Pair"], Some("Factory"));
+
+        let mut not_an_address = value_reg(3, "Factory", false, VALID_SIGHASH);
+        not_an_address.register = reg.register.clone();
+        let err = Decoder::from_registrations(&[not_an_address], false, &address_store)
+            .err()
+            .unwrap();
+        assert!(format!("{err:#}").contains("isn't an address param"));
+
+        let core = Decoder::from_registrations(&[reg], false, &address_store).unwrap();
+        let decoder = selection_of(&core, &[3], &Default::default()).unwrap();
+        let data =
+            DynSolValue::Tuple(vec![DynSolValue::Address(Address::from([0xcc; 20]))]).abi_encode();
+        let log = Log {
+            topics: vec![Some(VALID_SIGHASH.to_string())],
+            data: Some(format!("0x{}", hex::encode(data))),
+            ..Default::default()
+        };
+        let routed = route(&decoder, &log, &owned("Factory"));
+        assert_eq!(
+            decoder.registrations(routed.iter().map(|r| (r.index, 42, &r.params))),
+            vec![crate::address_store::AddressRegistration {
+                address: format!("0x{}", "cc".repeat(20)),
+                contract_name: "Pair".to_string(),
+                registration_block: 42,
+                end_block: None,
+            }]
+        );
+    }
+
     // Regression for issue #1285: an event surfaced to handlers under a name
     // that differs from its on-chain name must still decode. The decoder keys
     // on the on-chain sighash, not the keccak of the display name.
@@ -696,6 +1042,7 @@ mod tests {
         let core = Decoder::from_registrations(
             &[OnEventRegistrationInput {
                 index: 7,
+                kind: None,
                 sighash: real_sighash.clone(),
                 topic_count: 1,
                 event_name: "ApprovalRenamed".to_string(),
@@ -707,6 +1054,9 @@ mod tests {
                 block_fields: vec![],
                 transaction_fields: vec![],
                 params: vec![pm("owner", "address", false), pm("value", "uint256", false)],
+                transaction_where: None,
+                params_where: None,
+                register: None,
             }],
             false,
             &store(&["TestContract"], Some("TestContract")),
@@ -773,6 +1123,196 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bloom_false_positive_reaches_wildcards_only() {
+        use crate::evm_hypersync_source::selection::ADDRESS_FILTER_MIN_ADDRESSES;
+        use hypersync_client::format::{FilterWrapper, Hex};
+
+        let big: Vec<String> = (1..=ADDRESS_FILTER_MIN_ADDRESSES)
+            .map(|n| format!("0x{n:040x}"))
+            .collect();
+        let big_refs: Vec<&str> = big.iter().map(String::as_str).collect();
+        let address_store = evm_store(&[("Big", &big_refs), ("W", &[])]);
+        let core = Decoder::from_registrations(
+            &[
+                value_reg(0, "Big", false, VALID_SIGHASH),
+                value_reg(1, "W", true, VALID_SIGHASH),
+            ],
+            false,
+            &address_store,
+        )
+        .unwrap();
+        let decoder = selection_of(&core, &[0, 1], &Default::default()).unwrap();
+
+        // The emitter the server would let through: an address outside the
+        // contract that its query's bloom filter still accepts.
+        let set = crate::address_store::test_support::full_set(core.store_handle());
+        let cache = set.cache();
+        let filter =
+            FilterWrapper::decode_hex(cache.slice("Big").unwrap().address_filter()).unwrap();
+        let false_positive = (1u64 << 32..)
+            .map(|n| {
+                let mut key = [0u8; 20];
+                key[12..].copy_from_slice(&n.to_be_bytes());
+                key
+            })
+            .find(|key| filter.contains_hash(xxhash_rust::xxh3::xxh3_64(key)))
+            .unwrap();
+        let address = LogAddress {
+            key: &false_positive,
+            contract_name: cache.owner_of(&false_positive),
+            block_number: 0,
+        };
+
+        assert_eq!(address.contract_name, None);
+        assert_eq!(
+            routed_indexes(&route(&decoder, &value_log(VALID_SIGHASH), &address)),
+            vec![1]
+        );
+    }
+
+    const TRANSFER_SELECTOR: &str = "0xa9059cbb";
+
+    /// A `transfer(address to, uint256 amount)` call registration.
+    fn transfer_call_reg(
+        index: i64,
+        contract_name: &str,
+        is_wildcard: bool,
+    ) -> OnEventRegistrationInput {
+        OnEventRegistrationInput {
+            kind: Some(EvmEventKind::Call),
+            sighash: TRANSFER_SELECTOR.to_string(),
+            topic_count: 0,
+            topic_selections: no_filter_selection(TRANSFER_SELECTOR),
+            params: vec![pm("to", "address", false), pm("amount", "uint256", false)],
+            ..value_reg(index, contract_name, is_wildcard, VALID_SIGHASH)
+        }
+    }
+
+    fn transfer_input(selector: &str) -> Vec<u8> {
+        use alloy_dyn_abi::DynSolValue;
+        use alloy_primitives::{hex, Address, U256};
+        let mut input = hex::decode(selector).unwrap();
+        input.extend(
+            DynSolValue::Tuple(vec![
+                DynSolValue::Address(Address::from([0xcc; 20])),
+                DynSolValue::Uint(U256::from(5u64), 256),
+            ])
+            .abi_encode_params(),
+        );
+        input
+    }
+
+    fn route_call(
+        decoder: &SelectionDecoder,
+        input: &[u8],
+        address: &LogAddress,
+    ) -> Vec<RoutedEvent> {
+        route_call_from(decoder, CallSource::Trace, input, address)
+    }
+
+    fn route_call_from(
+        decoder: &SelectionDecoder,
+        source: CallSource,
+        input: &[u8],
+        address: &LogAddress,
+    ) -> Vec<RoutedEvent> {
+        let store = decoder.lock_store();
+        decoder.route_and_decode_call(source, input, address, &store)
+    }
+
+    #[test]
+    fn call_routes_by_selector_and_callee_and_decodes_inputs() {
+        let core = Decoder::from_registrations(
+            &[
+                transfer_call_reg(0, "Owned", false),
+                transfer_call_reg(1, "W", true),
+                value_reg(2, "Owned", false, VALID_SIGHASH),
+            ],
+            false,
+            &store(&["Owned", "W"], Some("Owned")),
+        )
+        .unwrap();
+        let decoder = selection_of(&core, &[0, 1, 2], &Default::default()).unwrap();
+        let input = transfer_input(TRANSFER_SELECTOR);
+
+        let routed = route_call(&decoder, &input, &owned("Owned"));
+        assert_eq!(routed_indexes(&routed), vec![0, 1]);
+        match &routed[0].params {
+            ParamValue::Obj(fields) => match fields.as_slice() {
+                [(to, ParamValue::Str(to_hex)), (amount, ParamValue::BigInt(_))]
+                    if to == "to" && amount == "amount" =>
+                {
+                    assert_eq!(to_hex, "0xcccccccccccccccccccccccccccccccccccccccc");
+                }
+                _ => panic!("unexpected decoded fields"),
+            },
+            _ => panic!("expected an object of params"),
+        }
+
+        assert_eq!(
+            (
+                // An unowned callee reaches the wildcard only.
+                routed_indexes(&route_call(&decoder, &input, &unowned())),
+                // Another function's selector, and input too short for any.
+                routed_indexes(&route_call(
+                    &decoder,
+                    &transfer_input("0x23b872dd"),
+                    &owned("Owned")
+                )),
+                routed_indexes(&route_call(&decoder, &input[..3], &owned("Owned"))),
+                // A log never routes to a call registration.
+                routed_indexes(&route(&decoder, &value_log(VALID_SIGHASH), &owned("Owned"))),
+            ),
+            (vec![1], vec![], vec![], vec![2])
+        );
+    }
+
+    #[test]
+    fn transaction_and_call_registrations_route_only_their_own_source() {
+        let core = Decoder::from_registrations(
+            &[
+                transfer_call_reg(0, "Owned", false),
+                OnEventRegistrationInput {
+                    kind: Some(EvmEventKind::Transaction),
+                    ..transfer_call_reg(1, "Owned", false)
+                },
+            ],
+            false,
+            &store(&["Owned"], Some("Owned")),
+        )
+        .unwrap();
+        let decoder = selection_of(&core, &[0, 1], &Default::default()).unwrap();
+        let input = transfer_input(TRANSFER_SELECTOR);
+        assert_eq!(
+            (
+                routed_indexes(&route_call_from(
+                    &decoder,
+                    CallSource::Trace,
+                    &input,
+                    &owned("Owned")
+                )),
+                routed_indexes(&route_call_from(
+                    &decoder,
+                    CallSource::Transaction,
+                    &input,
+                    &owned("Owned")
+                )),
+            ),
+            (vec![0], vec![1])
+        );
+    }
+
+    #[test]
+    fn call_registration_rejects_a_non_selector_sighash() {
+        let mut reg = transfer_call_reg(0, "C", false);
+        reg.sighash = VALID_SIGHASH.to_string();
+        let err = Decoder::from_registrations(&[reg], false, &store(&["C"], None))
+            .err()
+            .unwrap();
+        assert!(format!("{err:#}").contains("call selector must be 4 bytes"));
+    }
+
     #[test]
     fn registration_start_block_holds_back_only_its_own_registration() {
         // Two registrations of one event on one contract: one unrestricted, one
@@ -853,12 +1393,14 @@ mod tests {
                 name: "Owned".to_string(),
                 start_block: None,
                 depends_on_addresses: true,
+                ttl_blocks: None,
             }],
         );
         address_store.register_seed(vec![crate::address_store::AddressRegistration {
             address: EMITTER.to_string(),
             contract_name: "Owned".to_string(),
             registration_block: 100,
+            end_block: None,
         }]);
         let core = Decoder::from_registrations(
             &[value_reg(0, "Owned", false, VALID_SIGHASH)],
@@ -1068,12 +1610,14 @@ mod tests {
                 name: "C".to_string(),
                 start_block: None,
                 depends_on_addresses: true,
+                ttl_blocks: None,
             }],
         );
         address_store.register_seed(vec![crate::address_store::AddressRegistration {
             address: EMITTER.to_string(),
             contract_name: "C".to_string(),
             registration_block: 100,
+            end_block: None,
         }]);
         let core =
             Decoder::from_registrations(&[marker_reg(0, "C")], false, &address_store).unwrap();
@@ -1,31 +1,41 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Once;
 
 use anyhow::{Context, Result};
 use hypersync_client::{simple_types, RateLimitResponse};
 use napi_derive::napi;
 
-use crate::address_store::{AddressSet, AddressStore, SetCache};
+use crate::address_store::{AddressSet, AddressStore, RegistrationPage, SetCache};
 use crate::block_hash_pagination::{paginate_block_hashes, HashPage};
 use crate::block_store::BlockStore;
+use crate::event_item_store::EventItemStore;
 use crate::request_stats::{rate_limited_err, RequestStat};
+use crate::response_capture::ResponseCapture;
 use crate::transaction_store::TransactionStore;
 
 mod config;
 pub(crate) mod decode;
-mod query;
+pub(crate) mod param_where;
+pub(crate) mod query;
 pub(crate) mod selection;
+pub(crate) mod transaction_where;
 pub(crate) mod types;
 
 use config::ClientConfig;
-use decode::{Decoder, LogAddress, SelectionDecoder};
-use query::{BlockField, LogField, LogFilter, LogSelection, Query, TransactionField};
-use selection::{BuiltLogSelection, SelectionBuilder};
+use decode::{CallSource, Decoder, LogAddress, SelectionDecoder};
+use query::{
+    BlockField, JoinMode, LogField, LogFilter, LogSelection, Query, TraceField, TraceFilter,
+    TransactionField, TransactionFilter,
+};
+use selection::{BuiltCallSelection, BuiltCreationSelection, BuiltLogSelection, SelectionBuilder};
+use transaction_where::TransactionWheres;
 use types::{encode_address, Block, OnEventRegistrationInput, ParamValue, RollbackGuard};
 
 static LOGGER_INIT: Once = Once::new();
 
-fn init_logger(log_level: Option<&str>) {
+/// Sets up `env_logger` once per process: `RUST_LOG` wins, then the first
+/// level passed in; with neither, the addon logs nothing.
+pub(crate) fn init_logger(log_level: Option<&str>) {
     LOGGER_INIT.call_once(|| {
         if std::env::var("RUST_LOG").is_ok() {
             env_logger::init();
@@ -45,23 +55,94 @@ pub struct EvmHyperSyncClient {
     enable_checksum_addresses: bool,
     decoder: Decoder,
     selection_builder: SelectionBuilder,
+    capture: Option<ResponseCapture>,
 }
 
 impl EvmHyperSyncClient {
-    /// Execute one raw HyperSync page. Public methods decide how much of the
-    /// response to convert for their specific caller.
-    async fn get_raw(&self, query: Query) -> napi::Result<hypersync_client::QueryResponse> {
-        let query = query.try_into().context("parse query").map_err(map_err)?;
+    /// Run one query, surfacing a rate limit to the source manager rather than
+    /// sleeping it out inside the call. The page arrives as Arrow, so it's
+    /// captured as received before converting to rows.
+    async fn run_query(
+        &self,
+        query: &hypersync_client::net_types::Query,
+    ) -> napi::Result<hypersync_client::QueryResponse> {
         let res = self
             .inner
-            .get_with_rate_limit(&query)
+            .get_arrow_with_rate_limit(query)
             .await
             .context("run inner query")
             .map_err(map_err)?;
-        match res {
-            RateLimitResponse::Success { response, .. } => Ok(response),
-            RateLimitResponse::RateLimited(info) => Err(make_rate_limit_err(&info)),
+        let response = match res {
+            RateLimitResponse::Success { response, .. } => response,
+            RateLimitResponse::RateLimited(info) => return Err(make_rate_limit_err(&info)),
+        };
+        if let Some(capture) = &self.capture {
+            let data = &response.data;
+            capture.write_arrow(
+                query.from_block,
+                response.next_block,
+                [
+                    ("blocks", data.blocks.as_slice()),
+                    ("transactions", data.transactions.as_slice()),
+                    ("logs", data.logs.as_slice()),
+                    ("traces", data.traces.as_slice()),
+                ],
+            );
         }
+        hypersync_client::QueryResponse::try_from(&response)
+            .context("convert arrow response")
+            .map_err(map_err)
+    }
+
+    /// Execute one raw HyperSync page. Public methods decide how much of the
+    /// response to convert for their specific caller.
+    async fn get_raw(&self, query: Query) -> napi::Result<hypersync_client::QueryResponse> {
+        let query = query.try_into().context("parse query").map_err(map_err)?;
+        self.run_query(&query).await
+    }
+
+    /// The transactions of a page's calls. The default join mode never joins a
+    /// trace to its transaction, so the ones the logs didn't already bring in
+    /// are fetched by hash over the page's range, paginating until it's covered.
+    async fn get_trace_transactions(
+        &self,
+        from_block: i64,
+        to_block_exclusive: i64,
+        hashes: Vec<String>,
+        transaction_fields: Vec<TransactionField>,
+    ) -> napi::Result<Vec<simple_types::Transaction>> {
+        let mut transactions = Vec::new();
+        let mut from_block = from_block;
+        while from_block < to_block_exclusive {
+            let query = Query {
+                from_block,
+                to_block: Some(to_block_exclusive),
+                transactions: Some(vec![napi::bindgen_prelude::Either::B(TransactionFilter {
+                    hash: Some(hashes.clone()),
+                    ..Default::default()
+                })]),
+                field_selection: query::FieldSelection {
+                    transaction: Some(transaction_fields.clone()),
+                    ..Default::default()
+                },
+                join_mode: Some(JoinMode::JoinNothing),
+                ..Default::default()
+            };
+            let response = self.get_raw(query).await?;
+            let next_block: i64 = response
+                .next_block
+                .try_into()
+                .context("convert next_block")
+                .map_err(map_err)?;
+            transactions.extend(response.data.transactions.into_iter().flatten());
+            if next_block <= from_block {
+                return Err(map_err(anyhow::anyhow!(
+                    "Trace transaction query made no progress from block {from_block}"
+                )));
+            }
+            from_block = next_block;
+        }
+        Ok(transactions)
     }
 }
 
@@ -77,6 +158,7 @@ impl EvmHyperSyncClient {
         init_logger(cfg.log_level.as_deref());
 
         let enable_checksum_addresses = cfg.enable_checksum_addresses.unwrap_or_default();
+        let capture = ResponseCapture::new(cfg.capture_dir.clone());
 
         let decoder = Decoder::from_registrations(
             &event_registrations,
@@ -99,6 +181,7 @@ impl EvmHyperSyncClient {
             enable_checksum_addresses,
             decoder,
             selection_builder,
+            capture,
         })
     }
 
@@ -168,7 +251,13 @@ impl EvmHyperSyncClient {
         &self,
         params: EventItemsQuery,
         address_set: &AddressSet,
-    ) -> napi::Result<(EventItemsResponse, TransactionStore, BlockStore)> {
+    ) -> napi::Result<(
+        EventItemsResponse,
+        EventItemStore,
+        TransactionStore,
+        BlockStore,
+        RegistrationPage,
+    )> {
         let client_filtered = crate::client_filtered_contracts::ClientFilteredContracts::from_vec(
             params.client_filtered_contracts.unwrap_or_default(),
         );
@@ -197,7 +286,13 @@ impl EvmHyperSyncClient {
                 block_fields.push(field);
             }
         }
-        let validated_block_fields = block_fields;
+        let validated_block_fields = block_fields.clone();
+        // The parent hash is fetched for the block store's parent-link check
+        // but never required: a block without one just leaves its link
+        // unchecked.
+        if !block_fields.contains(&BlockField::ParentHash) {
+            block_fields.push(BlockField::ParentHash);
+        }
 
         let mut transaction_fields = requested_transaction_fields.clone();
         // Transactions are accumulated into the store keyed by
@@ -214,6 +309,41 @@ impl EvmHyperSyncClient {
             }
         }
 
+        // A transaction registration routes on the transaction itself: its
+        // callee and its input. Its status is part of its `where.transaction`,
+        // whose fields are already in the selection.
+        let has_transactions = !built.transaction_selections.is_empty();
+        if has_transactions {
+            for field in [
+                TransactionField::To,
+                TransactionField::Input,
+                TransactionField::BlockNumber,
+                TransactionField::TransactionIndex,
+            ] {
+                if !transaction_fields.contains(&field) {
+                    transaction_fields.push(field);
+                }
+            }
+        }
+        // A deploy routes on its sender and the contract it created.
+        let has_deploys = !built.deploy_selections.is_empty();
+        if has_deploys {
+            for field in [
+                TransactionField::From,
+                TransactionField::ContractAddress,
+                TransactionField::BlockNumber,
+                TransactionField::TransactionIndex,
+            ] {
+                if !transaction_fields.contains(&field) {
+                    transaction_fields.push(field);
+                }
+            }
+        }
+
+        let has_calls = !built.trace_selections.is_empty();
+        let has_creations = !built.creation_selections.is_empty();
+        let has_traces = has_calls || has_creations;
+        let transaction_fields_for_query = transaction_fields.clone();
         let query = Query {
             from_block: params.from_block,
             to_block: params.to_block.map(|b| b + 1),
@@ -224,10 +354,36 @@ impl EvmHyperSyncClient {
                     .map(log_selection_from_built)
                     .collect(),
             ),
+            transactions: (has_transactions || has_deploys).then(|| {
+                built
+                    .transaction_selections
+                    .into_iter()
+                    .map(transaction_selection_from_built)
+                    .chain(
+                        built
+                            .deploy_selections
+                            .into_iter()
+                            .map(deploy_selection_from_built),
+                    )
+                    .collect()
+            }),
+            traces: has_traces.then(|| {
+                built
+                    .trace_selections
+                    .into_iter()
+                    .map(trace_selection_from_built)
+                    .chain(
+                        built
+                            .creation_selections
+                            .into_iter()
+                            .map(creation_selection_from_built),
+                    )
+                    .collect()
+            }),
             max_num_logs: params.max_num_logs,
             field_selection: query::FieldSelection {
-                block: Some(validated_block_fields.clone()),
-                transaction: Some(transaction_fields),
+                block: Some(block_fields.clone()),
+                transaction: Some(transaction_fields.clone()),
                 // Everything get_event_items reads off the log: decode inputs,
                 // the flattened item fields, and the transaction-store keys.
                 log: Some(vec![
@@ -241,22 +397,70 @@ impl EvmHyperSyncClient {
                     LogField::BlockNumber,
                     LogField::TransactionIndex,
                 ]),
+                // Everything read off a call: the routing and decode inputs,
+                // its position for ordering, and the transaction-store keys. A
+                // creation routes on its type, deployer and created address.
+                trace: has_traces.then(|| {
+                    let mut fields = vec![
+                        TraceField::To,
+                        TraceField::Input,
+                        TraceField::Error,
+                        TraceField::BlockNumber,
+                        TraceField::TransactionPosition,
+                        TraceField::TransactionHash,
+                        TraceField::TraceAddress,
+                    ];
+                    if has_creations {
+                        fields.extend([TraceField::Type, TraceField::From, TraceField::Address]);
+                    }
+                    fields
+                }),
             },
             ..Default::default()
         };
 
         let query = query.try_into().context("parse query").map_err(map_err)?;
-        let res = self
-            .inner
-            .get_with_rate_limit(&query)
-            .await
-            .context("run inner query")
-            .map_err(map_err)?;
-
-        let response = match res {
-            RateLimitResponse::Success { response, .. } => response,
-            RateLimitResponse::RateLimited(info) => return Err(make_rate_limit_err(&info)),
-        };
+        let mut response = self.run_query(&query).await?;
+
+        if has_traces && !requested_transaction_fields.is_empty() {
+            let joined: HashSet<_> = response
+                .data
+                .transactions
+                .iter()
+                .flatten()
+                .filter_map(|tx| tx.hash.clone())
+                .collect();
+            let mut hashes: Vec<String> = Vec::new();
+            for trace in response.data.traces.iter().flatten() {
+                if trace.error.is_some() {
+                    continue;
+                }
+                if let Some(hash) = trace.transaction_hash.as_ref() {
+                    if !joined.contains(hash) {
+                        let hash = hypersync_client::format::Hex::encode_hex(hash);
+                        if !hashes.contains(&hash) {
+                            hashes.push(hash);
+                        }
+                    }
+                }
+            }
+            if !hashes.is_empty() {
+                let to_block_exclusive: i64 = response
+                    .next_block
+                    .try_into()
+                    .context("convert next_block")
+                    .map_err(map_err)?;
+                let transactions = self
+                    .get_trace_transactions(
+                        params.from_block,
+                        to_block_exclusive,
+                        hashes,
+                        transaction_fields_for_query,
+                    )
+                    .await?;
+                response.data.transactions.push(transactions);
+            }
+        }
 
         let transaction_store = TransactionStore::new_evm(self.enable_checksum_addresses);
         let block_store = BlockStore::new_evm(self.enable_checksum_addresses);
@@ -265,16 +469,27 @@ impl EvmHyperSyncClient {
                 response.data.blocks,
                 response.data.transactions,
                 response.data.logs,
+                response.data.traces,
                 &selection_decoder,
                 self.enable_checksum_addresses,
                 &validated_block_fields,
                 &requested_transaction_fields,
+                &built.transaction_wheres,
                 &transaction_store,
                 &block_store,
                 &set_cache,
             )
         })
         .map_err(convert_error_to_napi)?;
+        let registration_page =
+            RegistrationPage::new(selection_decoder.registrations(items.iter().map(|item| {
+                (
+                    item.on_event_registration_index,
+                    item.block_number,
+                    &item.params,
+                )
+            })));
+        let item_store = EventItemStore::from_items(items);
 
         let rollback_guard = response
             .rollback_guard
@@ -306,9 +521,15 @@ impl EvmHyperSyncClient {
                 .try_into()
                 .context("convert next_block")
                 .map_err(map_err)?,
-            items,
+            finalized_block: rollback_guard.as_ref().map(|g| g.first_block_number - 1),
         };
-        Ok((event_items, transaction_store, block_store))
+        Ok((
+            event_items,
+            item_store,
+            transaction_store,
+            block_store,
+            registration_page,
+        ))
     }
 }
 
@@ -330,11 +551,61 @@ pub struct EventItemsQuery {
     pub client_filtered_contracts: Option<Vec<String>>,
 }
 
+fn transaction_selection_from_built(
+    built: BuiltCallSelection,
+) -> napi::bindgen_prelude::Either<query::TransactionSelection, TransactionFilter> {
+    napi::bindgen_prelude::Either::B(TransactionFilter {
+        to: Some(built.addresses),
+        sighash: Some(built.sighashes),
+        status: built.status.map(i64::from),
+        ..Default::default()
+    })
+}
+
+fn trace_selection_from_built(
+    built: BuiltCallSelection,
+) -> napi::bindgen_prelude::Either<query::TraceSelection, TraceFilter> {
+    // Plain calls only: a delegatecall runs the callee's code on the caller's
+    // behalf, so it isn't a call to the callee contract.
+    napi::bindgen_prelude::Either::B(TraceFilter {
+        to: Some(built.addresses),
+        sighash: Some(built.sighashes),
+        type_: Some(vec!["call".to_string()]),
+        call_type: Some(vec!["call".to_string()]),
+        ..Default::default()
+    })
+}
+
+/// Contracts created by the deployers. The created address is only known once
+/// the creation succeeded, so a reverted one is dropped when routing.
+fn creation_selection_from_built(
+    built: BuiltCreationSelection,
+) -> napi::bindgen_prelude::Either<query::TraceSelection, TraceFilter> {
+    napi::bindgen_prelude::Either::B(TraceFilter {
+        from: Some(built.deployers),
+        type_: Some(vec!["create".to_string()]),
+        ..Default::default()
+    })
+}
+
+/// Every transaction the deployers send: no filter selects contract creations
+/// alone, so routing keeps the ones that created a contract.
+fn deploy_selection_from_built(
+    built: BuiltCreationSelection,
+) -> napi::bindgen_prelude::Either<query::TransactionSelection, TransactionFilter> {
+    napi::bindgen_prelude::Either::B(TransactionFilter {
+        from: Some(built.deployers),
+        status: built.status.map(i64::from),
+        ..Default::default()
+    })
+}
+
 fn log_selection_from_built(
     built: BuiltLogSelection,
 ) -> napi::bindgen_prelude::Either<LogSelection, LogFilter> {
     napi::bindgen_prelude::Either::B(LogFilter {
         address: Some(built.addresses),
+        address_filter: built.address_filter,
         topics: Some(built.topics),
     })
 }
@@ -355,12 +626,13 @@ pub struct QueryResponse {
     pub rollback_guard: Option<RollbackGuard>,
 }
 
-#[napi(object)]
+/// One routed log, call, or transaction of a response. Never crosses the
+/// boundary itself: the page's items go to JS in an `EventItemStore`.
 pub struct EventItem {
     pub log_index: i64,
     pub src_address: String,
     /// Block this log belongs to. The block itself is carried once, deduplicated,
-    /// in `EventItemsResponse.blocks` — the caller joins on this number.
+    /// in the page's `BlockStore` — the caller joins on this number.
     pub block_number: i64,
     /// Key into the per-chain `TransactionStore` (paired with the block number);
     /// the transaction itself is materialised field-by-field on demand.
@@ -369,6 +641,10 @@ pub struct EventItem {
     /// constructor. Logs that route nowhere never cross the boundary.
     pub on_event_registration_index: i64,
     pub params: ParamValue,
+    /// Set on call and transaction items only: the call's position in its
+    /// transaction's call tree, empty for the transaction itself. `log_index`
+    /// then holds the transaction index.
+    pub trace_address: Option<Vec<i64>>,
 }
 
 /// The always-needed block fields, surfaced per block number so the consumer can
@@ -377,7 +653,10 @@ pub struct EventItem {
 pub struct EventItemsResponse {
     pub archive_height: Option<i64>,
     pub next_block: i64,
-    pub items: Vec<EventItem>,
+    /// The highest block HyperSync can no longer roll back: the one below its
+    /// rollback guard's first in-memory block. `None` when the response carried
+    /// no guard, as for a range the server holds no in-memory blocks of.
+    pub finalized_block: Option<i64>,
 }
 
 fn convert_response(
@@ -451,10 +730,12 @@ fn process_response(
     blocks: Vec<Vec<simple_types::Block>>,
     transactions: Vec<Vec<simple_types::Transaction>>,
     logs: Vec<Vec<simple_types::Log>>,
+    traces: Vec<Vec<simple_types::Trace>>,
     decoder: &SelectionDecoder,
     should_checksum: bool,
     validated_block_fields: &[BlockField],
     requested_transaction_fields: &[TransactionField],
+    transaction_wheres: &TransactionWheres,
     transaction_store: &TransactionStore,
     block_store: &BlockStore,
     set_cache: &SetCache,
@@ -501,9 +782,177 @@ fn process_response(
                     transaction_index: flat.transaction_index,
                     on_event_registration_index: routed.index,
                     params: routed.params,
+                    trace_address: None,
+                });
+            }
+        }
+        for trace in traces.into_iter().flatten() {
+            // A reverted call's (or creation's) effects never happened.
+            if trace.error.is_some() {
+                continue;
+            }
+            if trace.type_.as_deref() == Some("create") {
+                let (Some(from), Some(created)) = (trace.from.as_ref(), trace.address.as_ref())
+                else {
+                    continue;
+                };
+                let flat =
+                    flatten_trace_for_js(&trace, from, should_checksum).context("mapping trace")?;
+                let address = LogAddress {
+                    key: from.as_slice(),
+                    contract_name: set_cache.owner_of(from.as_slice()),
+                    block_number: flat.block_number,
+                };
+                let routed =
+                    decoder.route_creation(CallSource::Trace, created, &address, &address_store);
+                if routed.is_empty() {
+                    continue;
+                }
+                let (block_key, _) = flat.transaction_key;
+                referenced_blocks.insert(block_key);
+                referenced_transactions.insert(flat.transaction_key);
+                for routed in routed {
+                    items.push(EventItem {
+                        log_index: flat.transaction_index,
+                        src_address: flat.src_address.clone(),
+                        block_number: flat.block_number,
+                        transaction_index: flat.transaction_index,
+                        on_event_registration_index: routed.index,
+                        params: routed.params,
+                        trace_address: Some(flat.trace_address.clone()),
+                    });
+                }
+                continue;
+            }
+            let (Some(to), Some(input)) = (trace.to.as_ref(), trace.input.as_ref()) else {
+                continue;
+            };
+            let flat =
+                flatten_trace_for_js(&trace, to, should_checksum).context("mapping trace")?;
+            let address = LogAddress {
+                key: to.as_slice(),
+                contract_name: set_cache.owner_of(to.as_slice()),
+                block_number: flat.block_number,
+            };
+            let routed =
+                decoder.route_and_decode_call(CallSource::Trace, input, &address, &address_store);
+            if routed.is_empty() {
+                continue;
+            }
+            let (block_key, _) = flat.transaction_key;
+            referenced_blocks.insert(block_key);
+            referenced_transactions.insert(flat.transaction_key);
+            for routed in routed {
+                items.push(EventItem {
+                    log_index: flat.transaction_index,
+                    src_address: flat.src_address.clone(),
+                    block_number: flat.block_number,
+                    transaction_index: flat.transaction_index,
+                    on_event_registration_index: routed.index,
+                    params: routed.params,
+                    trace_address: Some(flat.trace_address.clone()),
                 });
             }
         }
+        // A transaction joined to a log or a call comes back too; it routes
+        // only if it matches a transaction or deploy registration itself. Keys
+        // dedupe the rows fetched for calls after the main query.
+        let mut routed_transactions: HashSet<(u64, u32)> = HashSet::new();
+        for tx in transactions.iter().flatten() {
+            if let (Some(from), Some(created)) = (tx.from.as_ref(), tx.contract_address.as_ref()) {
+                let flat = flatten_transaction_for_js(tx, from, should_checksum)
+                    .context("mapping transaction")?;
+                if !routed_transactions.insert(flat.transaction_key) {
+                    continue;
+                }
+                let address = LogAddress {
+                    key: from.as_slice(),
+                    contract_name: set_cache.owner_of(from.as_slice()),
+                    block_number: flat.block_number,
+                };
+                let routed = decoder.route_creation(
+                    CallSource::Transaction,
+                    created,
+                    &address,
+                    &address_store,
+                );
+                let (block_key, _) = flat.transaction_key;
+                if !routed.is_empty() {
+                    referenced_blocks.insert(block_key);
+                    referenced_transactions.insert(flat.transaction_key);
+                }
+                for routed in routed {
+                    items.push(EventItem {
+                        log_index: flat.transaction_index,
+                        src_address: flat.src_address.clone(),
+                        block_number: flat.block_number,
+                        transaction_index: flat.transaction_index,
+                        on_event_registration_index: routed.index,
+                        params: routed.params,
+                        trace_address: Some(Vec::new()),
+                    });
+                }
+                continue;
+            }
+            let (Some(to), Some(input)) = (tx.to.as_ref(), tx.input.as_ref()) else {
+                continue;
+            };
+            let flat = flatten_transaction_for_js(tx, to, should_checksum)
+                .context("mapping transaction")?;
+            if !routed_transactions.insert(flat.transaction_key) {
+                continue;
+            }
+            let address = LogAddress {
+                key: to.as_slice(),
+                contract_name: set_cache.owner_of(to.as_slice()),
+                block_number: flat.block_number,
+            };
+            let routed = decoder.route_and_decode_call(
+                CallSource::Transaction,
+                input,
+                &address,
+                &address_store,
+            );
+            if routed.is_empty() {
+                continue;
+            }
+            let (block_key, _) = flat.transaction_key;
+            referenced_blocks.insert(block_key);
+            referenced_transactions.insert(flat.transaction_key);
+            for routed in routed {
+                items.push(EventItem {
+                    log_index: flat.transaction_index,
+                    src_address: flat.src_address.clone(),
+                    block_number: flat.block_number,
+                    transaction_index: flat.transaction_index,
+                    on_event_registration_index: routed.index,
+                    params: routed.params,
+                    // The transaction is the root of its own call tree.
+                    trace_address: Some(Vec::new()),
+                });
+            }
+        }
+    }
+
+    // `where.transaction` judges each item by its joined transaction, so the
+    // references are recollected from the items that pass.
+    if !transaction_wheres.is_empty() {
+        let by_key: HashMap<(u64, u32), &simple_types::Transaction> = transactions
+            .iter()
+            .flatten()
+            .filter_map(|tx| Some((transaction_key(tx.block_number?, tx.transaction_index?), tx)))
+            .collect();
+        items.retain(|item| {
+            let key = transaction_key(item.block_number as u64, item.transaction_index as u64);
+            transaction_wheres.accepts(item.on_event_registration_index, by_key.get(&key).copied())
+        });
+        referenced_blocks.clear();
+        referenced_transactions.clear();
+        for item in &items {
+            let key = transaction_key(item.block_number as u64, item.transaction_index as u64);
+            referenced_blocks.insert(key.0);
+            referenced_transactions.insert(key);
+        }
     }
 
     // Accumulate transactions into the store keyed by (blockNumber, txIndex).
@@ -588,8 +1037,8 @@ fn process_response(
 
     // Full fields for referenced blocks, whose trio and any selected fields
     // decode from the store like any other field. Blocks whose logs were all
-    // dropped by client-side routing keep a hash-only row so every returned
-    // header still backs reorg detection.
+    // dropped by client-side routing keep a hash-only row, with its parent
+    // hash, so every returned header still backs reorg detection.
     let store_blocks: Vec<simple_types::Block> = response_blocks
         .into_iter()
         .map(|b| {
@@ -601,6 +1050,7 @@ fn process_response(
                 simple_types::Block {
                     number: b.number,
                     hash: b.hash,
+                    parent_hash: b.parent_hash,
                     ..Default::default()
                 }
             }
@@ -662,6 +1112,76 @@ fn flatten_log_for_js(
     })
 }
 
+/// A call's flattened JS fields, plus its transaction-store key.
+struct FlatTrace {
+    src_address: String,
+    block_number: i64,
+    transaction_index: i64,
+    trace_address: Vec<i64>,
+    transaction_key: (u64, u32),
+}
+
+fn flatten_trace_for_js(
+    trace: &simple_types::Trace,
+    to: &hypersync_client::format::Address,
+    should_checksum: bool,
+) -> Result<FlatTrace> {
+    // Force-selected in the query's trace field selection, like a log's keys.
+    let raw_block_number = trace.block_number.context("trace.blockNumber missing")?;
+    let raw_transaction_index = trace
+        .transaction_position
+        .context("trace.transactionPosition missing")?;
+    let trace_address = trace
+        .trace_address
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|&i| i64::try_from(i))
+        .collect::<std::result::Result<_, _>>()
+        .context("trace.traceAddress overflow")?;
+    Ok(FlatTrace {
+        src_address: encode_address(to, should_checksum),
+        block_number: raw_block_number
+            .try_into()
+            .context("trace.blockNumber overflow")?,
+        transaction_index: raw_transaction_index
+            .try_into()
+            .context("trace.transactionPosition overflow")?,
+        trace_address,
+        transaction_key: transaction_key(raw_block_number, raw_transaction_index),
+    })
+}
+
+/// A transaction item's flattened JS fields, plus its transaction-store key.
+struct FlatTransaction {
+    src_address: String,
+    block_number: i64,
+    transaction_index: i64,
+    transaction_key: (u64, u32),
+}
+
+fn flatten_transaction_for_js(
+    tx: &simple_types::Transaction,
+    to: &hypersync_client::format::Address,
+    should_checksum: bool,
+) -> Result<FlatTransaction> {
+    // Force-selected whenever the query selects transactions.
+    let raw_block_number = tx.block_number.context("transaction.blockNumber missing")?;
+    let raw_transaction_index = tx
+        .transaction_index
+        .context("transaction.transactionIndex missing")?;
+    Ok(FlatTransaction {
+        src_address: encode_address(to, should_checksum),
+        block_number: u64::from(raw_block_number)
+            .try_into()
+            .context("transaction.blockNumber overflow")?,
+        transaction_index: u64::from(raw_transaction_index)
+            .try_into()
+            .context("transaction.transactionIndex overflow")?,
+        transaction_key: transaction_key(raw_block_number, raw_transaction_index),
+    })
+}
+
 /// Failure modes specific to event-items conversion. `MissingFields` is the
 /// shape the JS side recognizes and treats as `ImpossibleForTheQuery`;
 /// `Other` falls through to the generic napi error path.
@@ -748,7 +1268,10 @@ fn transaction_field_missing(
     match field {
         GasPrice | V | R | S | YParity | MaxPriorityFeePerGas | MaxFeePerGas | MaxFeePerBlobGas
         | BlobVersionedHashes | ContractAddress | Root | Status | L1Fee | L1GasPrice
-        | L1GasUsed | L1FeeScalar | GasUsedForL1 | From | To | Type => None,
+        | L1GasUsed | L1FeeScalar | GasUsedForL1 | From | To | Type | L1BaseFeeScalar
+        | L1BlobBaseFee | L1BlobBaseFeeScalar | Mint | SourceHash => None,
+        // Never requested from HyperSync (see `query`), so never on the response.
+        IsSystemTx | OperatorFeeScalar | L1BatchNumber | L1BatchTxIndex => None,
         BlockHash => tx.block_hash.is_none().then_some("blockHash"),
         BlockNumber => tx.block_number.is_none().then_some("blockNumber"),
         Gas => tx.gas.is_none().then_some("gas"),
@@ -776,16 +1299,11 @@ fn transaction_field_missing(
         // Enum variants not represented on simple_types::Transaction in this
         // crate version — treat as never-missing.
         L1BlockNumber
-        | L1BaseFeeScalar
-        | L1BlobBaseFee
-        | L1BlobBaseFeeScalar
         | Sighash
         | BlobGasPrice
         | BlobGasUsed
         | DepositNonce
-        | DepositReceiptVersion
-        | Mint
-        | SourceHash => None,
+        | DepositReceiptVersion => None,
     }
 }
 
@@ -804,8 +1322,11 @@ pub(crate) fn map_err(e: anyhow::Error) -> napi::Error {
 mod tests {
     use super::*;
     use crate::address_store::test_support::{evm_store, set_of};
+    use crate::field_columns::js_masks;
     use crate::field_columns::test_support::str_column;
+    use hypersync_client::format::TransactionStatus;
     use hypersync_client::simple_types;
+    use transaction_where::TransactionWhere;
 
     fn empty_decoder() -> SelectionDecoder {
         Decoder::from_registrations(&[], false, &evm_store(&[]))
@@ -829,6 +1350,7 @@ mod tests {
             &[
                 crate::evm_hypersync_source::types::OnEventRegistrationInput {
                     index: 0,
+                    kind: None,
                     sighash: format!("0x{}", "00".repeat(32)),
                     topic_count: 1,
                     event_name: "Zero".to_string(),
@@ -849,6 +1371,9 @@ mod tests {
                     block_fields: vec![],
                     transaction_fields: vec![],
                     params: vec![],
+                    transaction_where: None,
+                    params_where: None,
+                    register: None,
                 },
             ],
             false,
@@ -883,6 +1408,160 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transactions_route_to_transaction_registrations_unless_reverted() {
+        let registration = || crate::evm_hypersync_source::types::OnEventRegistrationInput {
+            index: 0,
+            kind: Some(types::EvmEventKind::Transaction),
+            sighash: "0xa9059cbb".to_string(),
+            topic_count: 0,
+            event_name: "transfer".to_string(),
+            contract_name: "Token".to_string(),
+            is_wildcard: true,
+            depends_on_addresses: false,
+            start_block: None,
+            topic_selections: vec![],
+            block_fields: vec![],
+            transaction_fields: vec![],
+            params: vec![types::ParamMeta {
+                name: "amount".to_string(),
+                abi_type: "uint256".to_string(),
+                indexed: false,
+                components: None,
+            }],
+            transaction_where: None,
+            params_where: None,
+            register: None,
+        };
+        let decoder =
+            Decoder::from_registrations(&[registration()], false, &evm_store(&[("Token", &[])]))
+                .unwrap()
+                .selection(&[0], &Default::default(), empty_set().cache().clone())
+                .unwrap();
+        let tx = |index: u64, selector: [u8; 4], status| {
+            let mut input = selector.to_vec();
+            input.extend([0u8; 32]);
+            simple_types::Transaction {
+                block_number: Some(1u64.into()),
+                transaction_index: Some(index.into()),
+                to: Some(Default::default()),
+                input: Some(input.into()),
+                status: Some(status),
+                ..Default::default()
+            }
+        };
+        let transfer = [0xa9, 0x05, 0x9c, 0xbb];
+        let block = simple_types::Block {
+            number: Some(1),
+            hash: Some(Default::default()),
+            timestamp: Some(Default::default()),
+            ..Default::default()
+        };
+        // No `where.transaction`: successful transactions only.
+        let mut wheres = TransactionWheres::default();
+        wheres.insert(
+            0,
+            TransactionWhere::from_registration(&registration())
+                .unwrap()
+                .unwrap(),
+        );
+
+        let items = process_response(
+            vec![vec![block]],
+            vec![vec![
+                tx(0, transfer, TransactionStatus::Failure),
+                tx(1, [0x09, 0x5e, 0xa7, 0xb3], TransactionStatus::Success),
+                tx(2, transfer, TransactionStatus::Success),
+            ]],
+            vec![],
+            vec![],
+            &decoder,
+            false,
+            REQUIRED_BLOCK_FIELDS,
+            &[TransactionField::Status],
+            &wheres,
+            &TransactionStore::new_evm(false),
+            &BlockStore::new_evm(false),
+            empty_set().cache(),
+        )
+        .expect("expected success");
+
+        assert_eq!(
+            items
+                .iter()
+                .map(|i| (i.transaction_index, i.log_index, i.trace_address.clone()))
+                .collect::<Vec<_>>(),
+            vec![(2, 2, Some(vec![]))]
+        );
+    }
+
+    #[test]
+    fn logs_whose_transaction_fails_their_where_are_dropped() {
+        let mut wheres = TransactionWheres::default();
+        wheres.insert(
+            0,
+            TransactionWhere::from_registration(&OnEventRegistrationInput {
+                transaction_where: Some(types::TransactionWhereInput {
+                    status: None,
+                    gas_used: None,
+                    value: Some(types::QuantityRangeInput {
+                        gte: Some("100".to_string()),
+                        lte: None,
+                    }),
+                }),
+                index: 0,
+                kind: None,
+                sighash: String::new(),
+                topic_count: 1,
+                event_name: "Zero".to_string(),
+                contract_name: "Zero".to_string(),
+                is_wildcard: true,
+                depends_on_addresses: false,
+                start_block: None,
+                topic_selections: vec![],
+                block_fields: vec![],
+                transaction_fields: vec![],
+                params: vec![],
+                params_where: None,
+                register: None,
+            })
+            .unwrap()
+            .unwrap(),
+        );
+        let block = |number: u64| simple_types::Block {
+            number: Some(number),
+            hash: Some(Default::default()),
+            timestamp: Some(Default::default()),
+            ..Default::default()
+        };
+        let tx = |block_number: u64, value: u64| simple_types::Transaction {
+            block_number: Some(block_number.into()),
+            transaction_index: Some(0u64.into()),
+            value: Some(value.into()),
+            ..Default::default()
+        };
+        let items = process_response(
+            vec![vec![block(1), block(2)]],
+            vec![vec![tx(1, 99), tx(2, 100)]],
+            vec![vec![full_log(1), full_log(2)]],
+            vec![],
+            &zero_event_decoder(),
+            false,
+            REQUIRED_BLOCK_FIELDS,
+            &[TransactionField::Value],
+            &wheres,
+            &TransactionStore::new_evm(false),
+            &BlockStore::new_evm(false),
+            empty_set().cache(),
+        )
+        .expect("expected success");
+
+        assert_eq!(
+            items.iter().map(|i| i.block_number).collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+
     #[test]
     fn missing_block_field_returns_typed_error() {
         // The server returned no block for the log but the user asked for
@@ -891,10 +1570,12 @@ mod tests {
             vec![],
             vec![],
             vec![vec![full_log(1)]],
+            vec![],
             &zero_event_decoder(),
             false,
             &[BlockField::Number, BlockField::Hash, BlockField::Timestamp],
             &[],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -919,10 +1600,12 @@ mod tests {
             vec![vec![block]],
             vec![],
             vec![vec![full_log(1)]],
+            vec![],
             &empty_decoder(),
             false,
             &[BlockField::Number, BlockField::Hash, BlockField::Timestamp],
             &[],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -952,10 +1635,12 @@ mod tests {
             vec![vec![block]],
             vec![],
             vec![vec![full_log(1)]],
+            vec![],
             &empty_decoder(),
             false,
             REQUIRED_BLOCK_FIELDS,
             &[],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -984,6 +1669,7 @@ mod tests {
             vec![vec![block]],
             vec![],
             vec![vec![full_log(1)]],
+            vec![],
             &zero_event_decoder(),
             false,
             &[
@@ -993,6 +1679,7 @@ mod tests {
                 BlockField::BaseFeePerGas,
             ],
             &[],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -1016,10 +1703,12 @@ mod tests {
             vec![vec![block]],
             vec![vec![tx]],
             vec![vec![full_log(1)]],
+            vec![],
             &zero_event_decoder(),
             false,
             &[BlockField::Number, BlockField::Hash, BlockField::Timestamp],
             &[TransactionField::Hash],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -1047,10 +1736,12 @@ mod tests {
             vec![vec![block]],
             vec![],
             vec![vec![full_log(1)]],
+            vec![],
             &zero_event_decoder(),
             false,
             &[BlockField::Number, BlockField::Hash, BlockField::Timestamp],
             &[TransactionField::Hash],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -1085,10 +1776,12 @@ mod tests {
             vec![vec![block]],
             vec![vec![tx]],
             vec![vec![full_log(7)]],
+            vec![],
             &zero_event_decoder(),
             false,
             &[BlockField::Number, BlockField::Hash, BlockField::Timestamp],
             &[TransactionField::BlockNumber],
+            &TransactionWheres::default(),
             &store,
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -1127,10 +1820,12 @@ mod tests {
             vec![vec![block(1), block(2)]],
             vec![vec![tx(1), tx(2)]],
             vec![vec![full_log(1), unrouted_log(2)]],
+            vec![],
             &zero_event_decoder(),
             false,
             REQUIRED_BLOCK_FIELDS,
             &[TransactionField::Hash],
+            &TransactionWheres::default(),
             &transaction_store,
             &block_store,
             empty_set().cache(),
@@ -1141,14 +1836,14 @@ mod tests {
             .materialize(
                 vec![1, 2],
                 vec![0, 0],
-                vec![(1u64 << (crate::transaction_store::EvmTxField::Hash as u32)) as f64; 2],
+                js_masks(&[(1u64 << (crate::transaction_store::EvmTxField::Hash as u32)); 2]),
             )
             .await
             .expect("materialize transactions");
         let stored_block_hashes = block_store
             .materialize(
                 vec![1, 2],
-                vec![(1u64 << (crate::block_store::EvmBlockField::Hash as u32)) as f64; 2],
+                js_masks(&[(1u64 << (crate::block_store::EvmBlockField::Hash as u32)); 2]),
             )
             .await
             .expect("materialize blocks");
@@ -1181,10 +1876,12 @@ mod tests {
             vec![],
             vec![],
             vec![vec![unrouted_log(1)]],
+            vec![],
             &zero_event_decoder(),
             false,
             REQUIRED_BLOCK_FIELDS,
             &[TransactionField::Hash],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -1210,6 +1907,7 @@ mod tests {
             vec![vec![block(1), block(2)]],
             vec![],
             vec![vec![full_log(1), unrouted_log(2)]],
+            vec![],
             &zero_event_decoder(),
             false,
             &[
@@ -1219,6 +1917,7 @@ mod tests {
                 BlockField::GasUsed,
             ],
             &[],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
@@ -1253,10 +1952,12 @@ mod tests {
                 keyless,
             ]],
             vec![vec![full_log(1)]],
+            vec![],
             &zero_event_decoder(),
             false,
             REQUIRED_BLOCK_FIELDS,
             &[TransactionField::Hash],
+            &TransactionWheres::default(),
             &TransactionStore::new_evm(false),
             &BlockStore::new_evm(false),
             empty_set().cache(),
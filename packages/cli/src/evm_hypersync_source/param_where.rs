@@ -0,0 +1,224 @@
+//! Registrations' `where.params`: conditions on decoded non-indexed params.
+//! Indexed params are narrowed by the query's topic filters; a body param
+//! only exists once the log's data is decoded, so its conditions run in the
+//! decoder, right after decoding, and an item failing them never crosses the
+//! napi boundary.
+
+use std::cmp::Ordering;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use anyhow::{Context, Result};
+
+use super::types::{ParamMeta, ParamWhereInput};
+
+/// One param's conditions, by its position among the decoded values it reads.
+struct ParamFilter {
+    position: usize,
+    eq: Option<DynSolValue>,
+    one_of: Option<Vec<DynSolValue>>,
+    gte: Option<DynSolValue>,
+    lte: Option<DynSolValue>,
+}
+
+impl ParamFilter {
+    fn matches(&self, value: &DynSolValue) -> bool {
+        self.eq.as_ref().is_none_or(|eq| eq == value)
+            && self
+                .one_of
+                .as_ref()
+                .is_none_or(|values| values.contains(value))
+            && self
+                .gte
+                .as_ref()
+                .is_none_or(|gte| compare(value, gte).is_some_and(Ordering::is_ge))
+            && self
+                .lte
+                .as_ref()
+                .is_none_or(|lte| compare(value, lte).is_some_and(Ordering::is_le))
+    }
+}
+
+/// Orders two integers of the same signedness; anything else is unordered.
+fn compare(value: &DynSolValue, bound: &DynSolValue) -> Option<Ordering> {
+    match (value, bound) {
+        (DynSolValue::Uint(value, _), DynSolValue::Uint(bound, _)) => Some(value.cmp(bound)),
+        (DynSolValue::Int(value, _), DynSolValue::Int(bound, _)) => Some(value.cmp(bound)),
+        _ => None,
+    }
+}
+
+/// A registration's param conditions; every one must hold.
+pub(crate) struct ParamFilters(Vec<ParamFilter>);
+
+impl ParamFilters {
+    /// Resolves each condition's param to its position among the values the
+    /// registration decodes: the body params of a log, every param of a call.
+    /// A condition on an indexed param is rejected — the topic filters of
+    /// `where` already express it, server-side.
+    pub(crate) fn parse(inputs: Option<&[ParamWhereInput]>, params: &[ParamMeta]) -> Result<Self> {
+        inputs
+            .unwrap_or_default()
+            .iter()
+            .map(|input| {
+                let (position, param) = params
+                    .iter()
+                    .filter(|param| !param.indexed)
+                    .enumerate()
+                    .find(|(_, param)| param.name == input.name)
+                    .with_context(|| {
+                        format!("where.params names {}, not a non-indexed param", input.name)
+                    })?;
+                let ty = DynSolType::parse(&param.abi_type)
+                    .with_context(|| format!("parse abi type {}", param.abi_type))?;
+                let value = |literal: &String| {
+                    ty.coerce_str(literal).with_context(|| {
+                        format!("Invalid where.params.{} value \"{literal}\"", input.name)
+                    })
+                };
+                let is_integer = matches!(ty, DynSolType::Uint(_) | DynSolType::Int(_));
+                anyhow::ensure!(
+                    is_integer || (input.gte.is_none() && input.lte.is_none()),
+                    "where.params.{} bounds an {} param; only int and uint params take bounds",
+                    input.name,
+                    param.abi_type
+                );
+                Ok(ParamFilter {
+                    position,
+                    eq: input.eq.as_ref().map(value).transpose()?,
+                    one_of: input
+                        .one_of
+                        .as_ref()
+                        .map(|values| values.iter().map(value).collect())
+                        .transpose()?,
+                    gte: input.gte.as_ref().map(value).transpose()?,
+                    lte: input.lte.as_ref().map(value).transpose()?,
+                })
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Whether decoded values pass every condition. A value missing from
+    /// `values` fails its condition.
+    pub(crate) fn accepts(&self, values: &[DynSolValue]) -> bool {
+        self.0.iter().all(|filter| {
+            values
+                .get(filter.position)
+                .is_some_and(|value| filter.matches(value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+
+    fn pm(name: &str, abi_type: &str, indexed: bool) -> ParamMeta {
+        ParamMeta {
+            name: name.to_string(),
+            abi_type: abi_type.to_string(),
+            indexed,
+            components: None,
+        }
+    }
+
+    fn input(name: &str) -> ParamWhereInput {
+        ParamWhereInput {
+            name: name.to_string(),
+            eq: None,
+            one_of: None,
+            gte: None,
+            lte: None,
+        }
+    }
+
+    const RECIPIENT: &str = "0x00000000000000000000000000000000000000aa";
+
+    #[test]
+    fn filters_body_params_by_range_and_membership() {
+        let params = [
+            pm("from", "address", true),
+            pm("to", "address", false),
+            pm("value", "uint256", false),
+        ];
+        let filters = ParamFilters::parse(
+            Some(&[
+                ParamWhereInput {
+                    one_of: Some(vec![RECIPIENT.to_string()]),
+                    ..input("to")
+                },
+                ParamWhereInput {
+                    gte: Some("100".to_string()),
+                    lte: Some("200".to_string()),
+                    ..input("value")
+                },
+            ]),
+            &params,
+        )
+        .unwrap();
+        let body = |to: &str, value: u64| {
+            [
+                DynSolValue::Address(to.parse::<Address>().unwrap()),
+                DynSolValue::Uint(U256::from(value), 256),
+            ]
+        };
+        assert!(filters.accepts(&body(RECIPIENT, 100)));
+        assert!(filters.accepts(&body(RECIPIENT, 200)));
+        assert!(!filters.accepts(&body(RECIPIENT, 99)));
+        assert!(!filters.accepts(&body(RECIPIENT, 201)));
+        assert!(!filters.accepts(&body("0x00000000000000000000000000000000000000bb", 150)));
+        assert!(!filters.accepts(&[]));
+        assert!(ParamFilters::parse(None, &params).unwrap().accepts(&[]));
+    }
+
+    #[test]
+    fn signed_bounds_and_equality() {
+        let params = [pm("delta", "int24", false), pm("active", "bool", false)];
+        let filters = ParamFilters::parse(
+            Some(&[
+                ParamWhereInput {
+                    lte: Some("-10".to_string()),
+                    ..input("delta")
+                },
+                ParamWhereInput {
+                    eq: Some("true".to_string()),
+                    ..input("active")
+                },
+            ]),
+            &params,
+        )
+        .unwrap();
+        let values = |delta: &str, active: bool| {
+            [
+                DynSolType::Int(24).coerce_str(delta).unwrap(),
+                DynSolValue::Bool(active),
+            ]
+        };
+        assert!(filters.accepts(&values("-11", true)));
+        assert!(!filters.accepts(&values("-9", true)));
+        assert!(!filters.accepts(&values("-11", false)));
+    }
+
+    #[test]
+    fn rejects_indexed_unknown_and_malformed_conditions() {
+        let params = [pm("from", "address", true), pm("memo", "string", false)];
+        let parse = |input: ParamWhereInput| ParamFilters::parse(Some(&[input]), &params);
+        assert!(parse(ParamWhereInput {
+            eq: Some(RECIPIENT.to_string()),
+            ..input("from")
+        })
+        .is_err());
+        assert!(parse(input("missing")).is_err());
+        assert!(parse(ParamWhereInput {
+            gte: Some("1".to_string()),
+            ..input("memo")
+        })
+        .is_err());
+        assert!(parse(ParamWhereInput {
+            eq: Some("gm".to_string()),
+            ..input("memo")
+        })
+        .is_ok());
+    }
+}
@@ -10,6 +10,9 @@ pub struct LogFilter {
     /// Address of the contract, any logs that has any of these addresses will be returned.
     /// Empty means match all.
     pub address: Option<Vec<String>>,
+    /// Hex-encoded bloom filter of addresses, for address sets too large to list.
+    /// The server may return logs of addresses outside the set it was built from.
+    pub address_filter: Option<String>,
     /// Topics to match, each member of the top level array is another array, if the nth topic matches any
     ///  topic specified in topics[n] the log will be returned. Empty means match all.
     pub topics: Option<Vec<Vec<String>>>,
@@ -64,6 +67,40 @@ pub struct TransactionSelection {
     pub exclude: Option<TransactionFilter>,
 }
 
+/// Filter for selecting traces based on addresses, call kind and sighash
+#[napi(object)]
+#[derive(Default, Clone, Debug)]
+pub struct TraceFilter {
+    /// Address the call originates from. Empty means match all.
+    pub from: Option<Vec<String>>,
+    /// Address the call goes to. Keep in mind that this has an and relationship with from filter.
+    ///  Empty means match all.
+    pub to: Option<Vec<String>>,
+    /// Address of a created or destroyed contract. Empty means match all.
+    pub address: Option<Vec<String>>,
+    /// If trace.call_type matches any of these (`call`, `delegatecall`, `staticcall`, ...), the trace
+    ///  will be returned. Empty means match all.
+    pub call_type: Option<Vec<String>>,
+    /// If trace.reward_type matches any of these, the trace will be returned. Empty means match all.
+    pub reward_type: Option<Vec<String>>,
+    /// If trace.type matches any of these (`call`, `create`, `suicide`, `reward`), the trace will be
+    ///  returned. Empty means match all.
+    #[napi(js_name = "type")]
+    pub type_: Option<Vec<String>>,
+    /// If first 4 bytes of trace input matches any of these, trace will be returned. Empty means match all.
+    pub sighash: Option<Vec<String>>,
+}
+
+/// Selection criteria for traces with include and exclude filters
+#[napi(object)]
+#[derive(Default, Clone, Debug)]
+pub struct TraceSelection {
+    /// Traces that match this filter will be included
+    pub include: TraceFilter,
+    /// Traces that match this filter will be excluded
+    pub exclude: Option<TraceFilter>,
+}
+
 /// Selection criteria for transaction authorization lists
 #[napi(object)]
 #[derive(Default, Clone, Debug)]
@@ -84,6 +121,8 @@ pub struct FieldSelection {
     pub transaction: Option<Vec<TransactionField>>,
     /// Log fields to include in the response
     pub log: Option<Vec<LogField>>,
+    /// Trace fields to include in the response
+    pub trace: Option<Vec<TraceField>>,
 }
 
 /// Available fields for block data
@@ -170,6 +209,12 @@ pub enum TransactionField {
     DepositReceiptVersion,
     Mint,
     SourceHash,
+    // Served by RPC only: HyperSync has no column for these, so they never
+    // reach its query (see the conversion below).
+    IsSystemTx,
+    OperatorFeeScalar,
+    L1BatchNumber,
+    L1BatchTxIndex,
 }
 
 /// Available fields for log data
@@ -190,6 +235,37 @@ pub enum LogField {
     Topic3,
 }
 
+/// Available fields for trace data
+#[napi(string_enum)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TraceField {
+    TransactionHash,
+    BlockHash,
+    BlockNumber,
+    TransactionPosition,
+    Type,
+    Error,
+    From,
+    To,
+    Author,
+    Gas,
+    GasUsed,
+    ActionAddress,
+    Address,
+    Balance,
+    CallType,
+    Code,
+    Init,
+    Input,
+    Output,
+    RefundAddress,
+    RewardType,
+    Sighash,
+    Subtraces,
+    TraceAddress,
+    Value,
+}
+
 /// Filter for selecting blocks based on hash and miner
 #[napi(object)]
 #[derive(Default, Clone, Debug)]
@@ -245,6 +321,9 @@ pub struct Query {
     /// List of transaction selections, the query will return transactions that match any of these selections and
     ///  it will return transactions that are related to the returned logs.
     pub transactions: Option<Vec<Either<TransactionSelection, TransactionFilter>>>,
+    /// List of trace selections, the query will return traces that match any of these selections and
+    ///  it will return traces that are related to the returned logs.
+    pub traces: Option<Vec<Either<TraceSelection, TraceFilter>>>,
     /// List of block selections, the query will return blocks that match any of these selections
     pub blocks: Option<Vec<Either<BlockSelection, BlockFilter>>>,
     /// Weather to include all blocks regardless of if they are related to a returned transaction or log. Normally
@@ -263,6 +342,9 @@ pub struct Query {
     /// Maximum number of logs that should be returned, the server might return more logs than this number but
     ///  it won't overshoot by too much.
     pub max_num_logs: Option<i64>,
+    /// Maximum number of traces that should be returned, the server might return more traces than this number but
+    ///  it won't overshoot by too much.
+    pub max_num_traces: Option<i64>,
     /// Selects join mode for the query,
     /// Default: join in this order logs -> transactions -> traces -> blocks
     /// JoinAll: join everything to everything. For example if logSelection matches log0, we get the
@@ -306,6 +388,21 @@ impl TryFrom<Query> for net_types::Query {
             Vec::new()
         };
 
+        let traces = if let Some(trace_filters) = query.traces {
+            trace_filters
+                .into_iter()
+                .map(|either| match either {
+                    Either::A(selection) => net_types::TraceSelection::try_from(selection),
+                    Either::B(filter) => {
+                        let net_filter = net_types::TraceFilter::try_from(filter)?;
+                        Ok(net_types::TraceSelection::new(net_filter))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            Vec::new()
+        };
+
         let blocks = if let Some(block_filters) = query.blocks {
             block_filters
                 .into_iter()
@@ -346,20 +443,24 @@ impl TryFrom<Query> for net_types::Query {
             .max_num_logs
             .map(|n| usize::try_from(n).context("max_num_logs must be >= 0"))
             .transpose()?;
+        let max_num_traces = query
+            .max_num_traces
+            .map(|n| usize::try_from(n).context("max_num_traces must be >= 0"))
+            .transpose()?;
 
         Ok(net_types::Query {
             from_block,
             to_block,
             logs,
             transactions,
-            traces: Vec::new(),
+            traces,
             blocks,
             include_all_blocks: query.include_all_blocks.unwrap_or(false),
             field_selection,
             max_num_blocks,
             max_num_transactions,
             max_num_logs,
-            max_num_traces: None,
+            max_num_traces,
             join_mode,
         })
     }
@@ -389,9 +490,15 @@ impl TryFrom<LogFilter> for net_types::LogFilter {
             }
         }
 
+        let address_filter = filter
+            .address_filter
+            .map(|filter| hypersync_client::format::FilterWrapper::decode_hex(&filter))
+            .transpose()
+            .context("Failed to parse address filter")?;
+
         Ok(net_types::LogFilter {
             address,
-            address_filter: None,
+            address_filter,
             topics,
         })
     }
@@ -482,6 +589,39 @@ impl TryFrom<TransactionSelection> for net_types::TransactionSelection {
     }
 }
 
+impl TryFrom<TraceFilter> for net_types::TraceFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: TraceFilter) -> Result<net_types::TraceFilter> {
+        Ok(net_types::TraceFilter {
+            from: map_optional_vec(filter.from).context("Failed to convert from")?,
+            from_filter: None,
+            to: map_optional_vec(filter.to).context("Failed to convert to")?,
+            to_filter: None,
+            address: map_optional_vec(filter.address).context("Failed to convert address")?,
+            address_filter: None,
+            call_type: filter.call_type.unwrap_or_default(),
+            reward_type: filter.reward_type.unwrap_or_default(),
+            type_: filter.type_.unwrap_or_default(),
+            sighash: map_optional_vec(filter.sighash).context("Failed to convert sighash")?,
+        })
+    }
+}
+
+impl TryFrom<TraceSelection> for net_types::TraceSelection {
+    type Error = anyhow::Error;
+
+    fn try_from(selection: TraceSelection) -> Result<net_types::TraceSelection> {
+        let include = net_types::TraceFilter::try_from(selection.include)?;
+        let exclude = selection
+            .exclude
+            .map(net_types::TraceFilter::try_from)
+            .transpose()?;
+
+        Ok(net_types::TraceSelection { include, exclude })
+    }
+}
+
 impl TryFrom<BlockFilter> for net_types::BlockFilter {
     type Error = anyhow::Error;
 
@@ -539,6 +679,24 @@ macro_rules! field_enum_convert {
             }
         }
     };
+    // A local enum with variants the remote lacks: converting one of those to
+    // the remote fails, handing the field back.
+    ($local:ty, $remote:ty, [$($variant:ident),* $(,)?], local_only: [$($extra:ident),* $(,)?]) => {
+        impl TryFrom<$local> for $remote {
+            type Error = $local;
+            fn try_from(field: $local) -> std::result::Result<Self, $local> {
+                match field {
+                    $( <$local>::$variant => Ok(<$remote>::$variant), )*
+                    $( <$local>::$extra => Err(field), )*
+                }
+            }
+        }
+        impl From<$remote> for $local {
+            fn from(field: $remote) -> Self {
+                match field { $( <$remote>::$variant => <$local>::$variant, )* }
+            }
+        }
+    };
 }
 
 field_enum_convert!(
@@ -626,7 +784,8 @@ field_enum_convert!(
         DepositReceiptVersion,
         Mint,
         SourceHash,
-    ]
+    ],
+    local_only: [IsSystemTx, OperatorFeeScalar, L1BatchNumber, L1BatchTxIndex]
 );
 
 field_enum_convert!(
@@ -648,6 +807,38 @@ field_enum_convert!(
     ]
 );
 
+field_enum_convert!(
+    TraceField,
+    net_types::TraceField,
+    [
+        TransactionHash,
+        BlockHash,
+        BlockNumber,
+        TransactionPosition,
+        Type,
+        Error,
+        From,
+        To,
+        Author,
+        Gas,
+        GasUsed,
+        ActionAddress,
+        Address,
+        Balance,
+        CallType,
+        Code,
+        Init,
+        Input,
+        Output,
+        RefundAddress,
+        RewardType,
+        Sighash,
+        Subtraces,
+        TraceAddress,
+        Value,
+    ]
+);
+
 impl TryFrom<FieldSelection> for net_types::FieldSelection {
     type Error = anyhow::Error;
 
@@ -664,7 +855,8 @@ impl TryFrom<FieldSelection> for net_types::FieldSelection {
             .transaction
             .unwrap_or_default()
             .into_iter()
-            .map(net_types::TransactionField::from)
+            // RPC-only fields have no HyperSync column; the store leaves them null.
+            .filter_map(|field| net_types::TransactionField::try_from(field).ok())
             .collect::<BTreeSet<_>>();
         let log = selection
             .log
@@ -672,12 +864,18 @@ impl TryFrom<FieldSelection> for net_types::FieldSelection {
             .into_iter()
             .map(net_types::LogField::from)
             .collect::<BTreeSet<_>>();
+        let trace = selection
+            .trace
+            .unwrap_or_default()
+            .into_iter()
+            .map(net_types::TraceField::from)
+            .collect::<BTreeSet<_>>();
 
         Ok(net_types::FieldSelection {
             block,
             transaction,
             log,
-            trace: BTreeSet::new(),
+            trace,
         })
     }
 }
@@ -719,6 +917,7 @@ impl From<net_types::Query> for Query {
             to_block: query.to_block.map(|b| b as i64),
             logs: map_selections(query.logs),
             transactions: map_selections(query.transactions),
+            traces: map_selections(query.traces),
             blocks: map_selections(query.blocks),
             include_all_blocks: if query.include_all_blocks {
                 Some(true)
@@ -729,6 +928,7 @@ impl From<net_types::Query> for Query {
             max_num_blocks: query.max_num_blocks.map(|n| n as i64),
             max_num_transactions: query.max_num_transactions.map(|n| n as i64),
             max_num_logs: query.max_num_logs.map(|n| n as i64),
+            max_num_traces: query.max_num_traces.map(|n| n as i64),
             join_mode,
         }
     }
@@ -761,6 +961,7 @@ impl From<net_types::LogFilter> for LogFilter {
 
         LogFilter {
             address: map_maybe_hex_vec(filter.address),
+            address_filter: filter.address_filter.as_ref().map(Hex::encode_hex),
             topics,
         }
     }
@@ -834,6 +1035,37 @@ impl From<net_types::TransactionSelection> for TransactionSelection {
     }
 }
 
+impl From<net_types::TraceFilter> for TraceFilter {
+    fn from(filter: net_types::TraceFilter) -> TraceFilter {
+        fn non_empty(v: Vec<String>) -> Option<Vec<String>> {
+            if v.is_empty() {
+                None
+            } else {
+                Some(v)
+            }
+        }
+
+        TraceFilter {
+            from: map_maybe_hex_vec(filter.from),
+            to: map_maybe_hex_vec(filter.to),
+            address: map_maybe_hex_vec(filter.address),
+            call_type: non_empty(filter.call_type),
+            reward_type: non_empty(filter.reward_type),
+            type_: non_empty(filter.type_),
+            sighash: map_maybe_hex_vec(filter.sighash),
+        }
+    }
+}
+
+impl From<net_types::TraceSelection> for TraceSelection {
+    fn from(selection: net_types::TraceSelection) -> Self {
+        Self {
+            include: selection.include.into(),
+            exclude: selection.exclude.map(Into::into),
+        }
+    }
+}
+
 impl From<net_types::BlockFilter> for BlockFilter {
     fn from(filter: net_types::BlockFilter) -> BlockFilter {
         BlockFilter {
@@ -869,6 +1101,7 @@ impl From<net_types::FieldSelection> for FieldSelection {
             block: map_into(selection.block),
             transaction: map_into(selection.transaction),
             log: map_into(selection.log),
+            trace: map_into(selection.trace),
         }
     }
 }
@@ -1,10 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use anyhow::{Context, Result};
 use napi_derive::napi;
 
 use crate::address_store::AddressSet;
+use crate::evm_hypersync_source::decode::CallSource;
 use crate::evm_hypersync_source::query::{BlockField, TransactionField};
+use crate::evm_hypersync_source::transaction_where::{TransactionWhere, TransactionWheres};
+use crate::evm_hypersync_source::types::EvmEventKind;
 
 /// Topic positions 1..3: static topic values, or `None` — the "currently
 /// registered addresses of this contract" marker, expanded to padded address
@@ -19,15 +22,155 @@ pub struct TopicSelectionInput {
     pub topic3: Option<Vec<String>>,
 }
 
+/// A contract with at least this many addresses in a partition's set is sent
+/// as a bloom filter instead of an address list: about two bytes per address
+/// instead of forty-two, at the price of the odd false-positive emitter, which
+/// routing drops because the set doesn't own it.
+pub(crate) const ADDRESS_FILTER_MIN_ADDRESSES: usize = 1_000;
+
+/// `ADDRESS_FILTER_MIN_ADDRESSES` for `FetchState`: a contract that size goes
+/// out as one filter whatever its address count, so it's exempt from the
+/// partition and client-filter caps sized for address lists.
+#[napi]
+pub fn address_filter_min_addresses() -> u32 {
+    ADDRESS_FILTER_MIN_ADDRESSES as u32
+}
+
 /// One log selection of a built query: `addresses` scopes the selection to
-/// specific emitters (empty = any address), `topics` is the 4-position topic
+/// specific emitters (empty = any address), `address_filter` to the emitters a
+/// hex-encoded bloom filter may contain, and `topics` is the 4-position topic
 /// filter (empty position = match any).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BuiltLogSelection {
     pub addresses: Vec<String>,
+    pub address_filter: Option<String>,
     pub topics: Vec<Vec<String>>,
 }
 
+/// One call selection of a built query: calls (or transactions) to one of
+/// `addresses` (empty = any callee) whose input starts with one of the 4-byte
+/// `sighashes`. A transaction selection also narrows on the receipt `status`
+/// (1 succeeded, 0 reverted; `None` = either).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltCallSelection {
+    pub addresses: Vec<String>,
+    pub sighashes: Vec<String>,
+    pub status: Option<u8>,
+}
+
+/// One creation selection of a built query: contracts created by one of
+/// `deployers` (empty = any deployer). A deploy selection also narrows on the
+/// receipt `status`, like a transaction selection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltCreationSelection {
+    pub deployers: Vec<String>,
+    pub status: Option<u8>,
+}
+
+/// How a log selection scopes its emitters.
+#[derive(Clone, Copy)]
+enum Emitters<'a> {
+    Any,
+    Listed(&'a [String]),
+    /// A contract's slice too large to list, as its hex bloom filter.
+    Filtered(&'a str),
+}
+
+/// One source's creation registrations: whether any is address-free, and the
+/// deployer contracts of the address-bound ones, in first-appearance order.
+#[derive(Default)]
+struct CreationBuckets<'a> {
+    any_deployer: bool,
+    contracts: Vec<&'a str>,
+}
+
+impl<'a> CreationBuckets<'a> {
+    fn push(&mut self, reg: &'a RegistrationSelection, address_bound: bool) {
+        if !address_bound {
+            self.any_deployer = true;
+        } else if !self.contracts.contains(&reg.contract_name.as_str()) {
+            self.contracts.push(reg.contract_name.as_str());
+        }
+    }
+
+    /// An address-free registration already selects every creation, so the
+    /// per-contract selections would only repeat it.
+    fn into_selections(
+        self,
+        cache: &crate::address_store::SetCache,
+        status: Option<u8>,
+    ) -> Vec<BuiltCreationSelection> {
+        if self.any_deployer {
+            return vec![BuiltCreationSelection {
+                deployers: Vec::new(),
+                status,
+            }];
+        }
+        self.contracts
+            .into_iter()
+            .filter_map(|contract_name| {
+                let slice = cache.slice(contract_name)?;
+                (!slice.addresses.is_empty()).then(|| BuiltCreationSelection {
+                    deployers: slice.addresses.clone(),
+                    status,
+                })
+            })
+            .collect()
+    }
+}
+
+/// One source's call registrations bucketed like log selections: address-free
+/// selectors pool together, address-bound ones group per contract.
+#[derive(Default)]
+struct CallBuckets<'a> {
+    no_address: Vec<String>,
+    by_contract: HashMap<&'a str, Vec<String>>,
+}
+
+impl<'a> CallBuckets<'a> {
+    fn push(&mut self, reg: &'a RegistrationSelection, selector: &str, address_bound: bool) {
+        let selectors = if address_bound {
+            self.by_contract
+                .entry(reg.contract_name.as_str())
+                .or_default()
+        } else {
+            &mut self.no_address
+        };
+        if !selectors.iter().any(|s| s == selector) {
+            selectors.push(selector.to_string());
+        }
+    }
+
+    fn into_selections(
+        mut self,
+        cache: &crate::address_store::SetCache,
+        status: Option<u8>,
+    ) -> Vec<BuiltCallSelection> {
+        let mut selections: Vec<BuiltCallSelection> = Vec::new();
+        if !self.no_address.is_empty() {
+            selections.push(BuiltCallSelection {
+                addresses: Vec::new(),
+                sighashes: self.no_address,
+                status,
+            });
+        }
+        // Sorted for the same byte-stable query as the log selections.
+        let mut contracts: Vec<&str> = self.by_contract.keys().copied().collect();
+        contracts.sort_unstable();
+        for contract_name in contracts {
+            match cache.slice(contract_name) {
+                Some(slice) if !slice.addresses.is_empty() => selections.push(BuiltCallSelection {
+                    addresses: slice.addresses.clone(),
+                    sighashes: self.by_contract.remove(contract_name).unwrap_or_default(),
+                    status,
+                }),
+                _ => continue,
+            }
+        }
+        selections
+    }
+}
+
 #[derive(Clone)]
 enum TopicFilter {
     Values(Vec<String>),
@@ -91,9 +234,16 @@ struct RegistrationSelection {
     contract_name: String,
     is_wildcard: bool,
     depends_on_addresses: bool,
+    /// The source and function selector of a call or transaction
+    /// registration; `None` for a log.
+    call_selector: Option<(CallSource, String)>,
+    /// The source of a creation registration: create traces, or deploying
+    /// transactions.
+    creation: Option<CallSource>,
     topic_selections: Vec<TopicSelection>,
     block_fields: Vec<BlockField>,
     transaction_fields: Vec<TransactionField>,
+    transaction_where: Option<TransactionWhere>,
 }
 
 /// Fold selections without topic1..3 filters into one selection combining
@@ -134,12 +284,29 @@ fn compress(selections: Vec<MaterializedTopicSelection>) -> Vec<MaterializedTopi
 /// and current addresses.
 pub(crate) struct BuiltSelection {
     pub log_selections: Vec<BuiltLogSelection>,
+    /// Call registrations' selections, fetched from traces. Empty when the
+    /// selection has no call registration, so log-only queries request no
+    /// traces.
+    pub trace_selections: Vec<BuiltCallSelection>,
+    /// Transaction registrations' selections, matched on the transaction's
+    /// `to` and selector. Empty when the selection has none.
+    pub transaction_selections: Vec<BuiltCallSelection>,
+    /// Creation registrations' selections, fetched from create traces by
+    /// their deployer. Empty when the selection has none.
+    pub creation_selections: Vec<BuiltCreationSelection>,
+    /// Deploy registrations' selections, matched on the contract-creating
+    /// transaction's sender. Empty when the selection has none.
+    pub deploy_selections: Vec<BuiltCreationSelection>,
     /// Union over the selection's registrations; unsorted, deduplicated.
     pub block_fields: Vec<BlockField>,
-    /// Union over the selection's registrations; `TransactionIndex` excluded —
-    /// it's read off the log (the store key), and requesting it alone would
-    /// pull the whole transaction table for nothing.
+    /// Union over the selection's registrations, plus whatever their
+    /// `where.transaction` reads; `TransactionIndex` excluded — it's read off
+    /// the log (the store key), and requesting it alone would pull the whole
+    /// transaction table for nothing.
     pub transaction_fields: Vec<TransactionField>,
+    /// The `where.transaction` of the selection's registrations, checked on
+    /// each routed item's transaction.
+    pub transaction_wheres: TransactionWheres,
 }
 
 /// Builds per-query log selections from the registrations passed at client
@@ -160,6 +327,18 @@ impl SelectionBuilder {
                 contract_name: reg.contract_name.clone(),
                 is_wildcard: reg.is_wildcard,
                 depends_on_addresses: reg.depends_on_addresses,
+                call_selector: match reg.kind.unwrap_or_default() {
+                    EvmEventKind::Call => Some((CallSource::Trace, reg.sighash.clone())),
+                    EvmEventKind::Transaction => {
+                        Some((CallSource::Transaction, reg.sighash.clone()))
+                    }
+                    EvmEventKind::Log | EvmEventKind::Create | EvmEventKind::Deploy => None,
+                },
+                creation: match reg.kind.unwrap_or_default() {
+                    EvmEventKind::Create => Some(CallSource::Trace),
+                    EvmEventKind::Deploy => Some(CallSource::Transaction),
+                    EvmEventKind::Log | EvmEventKind::Call | EvmEventKind::Transaction => None,
+                },
                 topic_selections: reg
                     .topic_selections
                     .iter()
@@ -172,6 +351,7 @@ impl SelectionBuilder {
                     .collect(),
                 block_fields: reg.block_fields.clone(),
                 transaction_fields: reg.transaction_fields.clone(),
+                transaction_where: TransactionWhere::from_registration(reg)?,
             };
             anyhow::ensure!(
                 map.insert(reg.index, parsed).is_none(),
@@ -197,11 +377,17 @@ impl SelectionBuilder {
         let mut no_address: Vec<MaterializedTopicSelection> = Vec::new();
         let mut by_contract: HashMap<&str, Vec<&TopicSelection>> = HashMap::new();
         let mut wildcard_by_contract: HashMap<&str, Vec<&TopicSelection>> = HashMap::new();
+        let mut trace_calls = CallBuckets::default();
+        // Transactions additionally split by status, one filter each.
+        let mut transaction_calls: BTreeMap<Option<u8>, CallBuckets> = BTreeMap::new();
+        let mut creations = CreationBuckets::default();
+        let mut deploys: BTreeMap<Option<u8>, CreationBuckets> = BTreeMap::new();
         // First-appearance order of address-bound contracts, so the built
         // query is byte-stable across calls (query caching keys on it).
         let mut ordered_contracts: Vec<&str> = Vec::new();
         let mut block_fields: Vec<BlockField> = Vec::new();
         let mut transaction_fields: Vec<TransactionField> = Vec::new();
+        let mut transaction_wheres = TransactionWheres::default();
 
         for id in registration_indexes {
             let reg = self
@@ -213,13 +399,54 @@ impl SelectionBuilder {
                     block_fields.push(field);
                 }
             }
-            for &field in &reg.transaction_fields {
+            let where_fields = reg
+                .transaction_where
+                .as_ref()
+                .map(TransactionWhere::fields)
+                .unwrap_or_default();
+            for &field in reg.transaction_fields.iter().chain(&where_fields) {
                 if field != TransactionField::TransactionIndex
                     && !transaction_fields.contains(&field)
                 {
                     transaction_fields.push(field);
                 }
             }
+            if let Some(transaction_where) = &reg.transaction_where {
+                transaction_wheres.insert(*id, transaction_where.clone());
+            }
+            // A call or a creation has no topics to fold addresses into, so a
+            // wildcard one is address-free whatever its `where`.
+            let address_bound = reg.depends_on_addresses
+                && !reg.is_wildcard
+                && !client_filtered.applies(&reg.contract_name);
+            if let Some(source) = reg.creation {
+                let buckets = match source {
+                    CallSource::Trace => &mut creations,
+                    CallSource::Transaction => deploys
+                        .entry(
+                            reg.transaction_where
+                                .as_ref()
+                                .and_then(TransactionWhere::status_code),
+                        )
+                        .or_default(),
+                };
+                buckets.push(reg, address_bound);
+                continue;
+            }
+            if let Some((source, selector)) = &reg.call_selector {
+                let buckets = match source {
+                    CallSource::Trace => &mut trace_calls,
+                    CallSource::Transaction => transaction_calls
+                        .entry(
+                            reg.transaction_where
+                                .as_ref()
+                                .and_then(TransactionWhere::status_code),
+                        )
+                        .or_default(),
+                };
+                buckets.push(reg, selector, address_bound);
+                continue;
+            }
             if reg.depends_on_addresses && !client_filtered.applies(&reg.contract_name) {
                 if !ordered_contracts.contains(&reg.contract_name.as_str()) {
                     ordered_contracts.push(reg.contract_name.as_str());
@@ -244,16 +471,22 @@ impl SelectionBuilder {
 
         let mut log_selections: Vec<BuiltLogSelection> = Vec::new();
         let mut push_selections =
-            |addresses: &[String], selections: Vec<MaterializedTopicSelection>| {
+            |emitters: Emitters, selections: Vec<MaterializedTopicSelection>| {
                 for selection in compress(selections) {
+                    let (addresses, address_filter) = match emitters {
+                        Emitters::Any => (Vec::new(), None),
+                        Emitters::Listed(addresses) => (addresses.to_vec(), None),
+                        Emitters::Filtered(filter) => (Vec::new(), Some(filter.to_string())),
+                    };
                     log_selections.push(BuiltLogSelection {
-                        addresses: addresses.to_vec(),
+                        addresses,
+                        address_filter,
                         topics: selection.into_topics(),
                     });
                 }
             };
 
-        push_selections(&[], no_address);
+        push_selections(Emitters::Any, no_address);
 
         // The set's per-contract slices carry both the address strings the
         // filter needs and their padded topic forms, materialised once when the
@@ -265,8 +498,13 @@ impl SelectionBuilder {
                 _ => continue,
             };
             if let Some(selections) = by_contract.get(contract_name) {
+                let emitters = if slice.addresses.len() >= ADDRESS_FILTER_MIN_ADDRESSES {
+                    Emitters::Filtered(slice.address_filter())
+                } else {
+                    Emitters::Listed(&slice.addresses)
+                };
                 push_selections(
-                    &slice.addresses,
+                    emitters,
                     selections
                         .iter()
                         .map(|ts| ts.materialize(&slice.topics))
@@ -275,7 +513,7 @@ impl SelectionBuilder {
             }
             if let Some(selections) = wildcard_by_contract.get(contract_name) {
                 push_selections(
-                    &[],
+                    Emitters::Any,
                     selections
                         .iter()
                         .map(|ts| ts.materialize(&slice.topics))
@@ -286,8 +524,19 @@ impl SelectionBuilder {
 
         Ok(BuiltSelection {
             log_selections,
+            trace_selections: trace_calls.into_selections(cache, None),
+            transaction_selections: transaction_calls
+                .into_iter()
+                .flat_map(|(status, buckets)| buckets.into_selections(cache, status))
+                .collect(),
+            creation_selections: creations.into_selections(cache, None),
+            deploy_selections: deploys
+                .into_iter()
+                .flat_map(|(status, buckets)| buckets.into_selections(cache, status))
+                .collect(),
             block_fields,
             transaction_fields,
+            transaction_wheres,
         })
     }
 }
@@ -296,7 +545,7 @@ impl SelectionBuilder {
 mod tests {
     use super::*;
     use crate::address_store::test_support::{evm_store, set_of};
-    use crate::evm_hypersync_source::types::OnEventRegistrationInput;
+    use crate::evm_hypersync_source::types::{OnEventRegistrationInput, TransactionWhereInput};
 
     const SIGHASH_A: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
     const SIGHASH_B: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
@@ -317,6 +566,7 @@ mod tests {
     ) -> OnEventRegistrationInput {
         OnEventRegistrationInput {
             index: id,
+            kind: None,
             sighash: sighash.to_string(),
             topic_count: 1,
             event_name: "E".to_string(),
@@ -333,6 +583,9 @@ mod tests {
             }],
             block_fields: vec![],
             transaction_fields: vec![],
+            transaction_where: None,
+            params_where: None,
+            register: None,
         }
     }
 
@@ -364,6 +617,7 @@ mod tests {
             (
                 vec![BuiltLogSelection {
                     addresses: vec![ADDR.to_string()],
+                    address_filter: None,
                     topics: vec![
                         vec![SIGHASH_A.to_string(), SIGHASH_B.to_string()],
                         vec![],
@@ -394,11 +648,60 @@ mod tests {
             built.log_selections,
             vec![BuiltLogSelection {
                 addresses: vec![],
+                address_filter: None,
                 topics: vec![vec![SIGHASH_A.to_string()], vec![], vec![], vec![]],
             }]
         );
     }
 
+    /// `count` distinct addresses, in the order a set of them sorts.
+    fn many_addresses(count: usize) -> Vec<String> {
+        (1..=count).map(|n| format!("0x{n:040x}")).collect()
+    }
+
+    #[test]
+    fn large_contract_is_sent_as_a_bloom_filter() {
+        use hypersync_client::format::{FilterWrapper, Hex};
+
+        let builder = SelectionBuilder::from_registrations(&[
+            reg(0, SIGHASH_A, "Big", false, true, Some(vec![])),
+            reg(1, SIGHASH_B, "Small", false, true, Some(vec![])),
+        ])
+        .unwrap();
+        let big = many_addresses(ADDRESS_FILTER_MIN_ADDRESSES);
+        let big_refs: Vec<&str> = big.iter().map(String::as_str).collect();
+        let (_store, set) = addresses(&[("Big", &big_refs), ("Small", &[ADDR])]);
+        let built = builder.build(&[0, 1], &set, &Default::default()).unwrap();
+
+        let keys: Vec<[u8; 20]> = big
+            .iter()
+            .map(|address| {
+                let mut key = [0u8; 20];
+                faster_hex::hex_decode(&address.as_bytes()[2..], &mut key).unwrap();
+                key
+            })
+            .collect();
+        let expected_filter = FilterWrapper::from_keys(keys.iter().map(|key| key.as_slice()), None)
+            .unwrap()
+            .encode_hex();
+        assert_eq!(
+            built.log_selections,
+            vec![
+                BuiltLogSelection {
+                    addresses: vec![],
+                    address_filter: Some(expected_filter),
+                    topics: vec![vec![SIGHASH_A.to_string()], vec![], vec![], vec![]],
+                },
+                // Below the threshold a contract's addresses are still listed.
+                BuiltLogSelection {
+                    addresses: vec![ADDR.to_string()],
+                    address_filter: None,
+                    topics: vec![vec![SIGHASH_B.to_string()], vec![], vec![], vec![]],
+                },
+            ]
+        );
+    }
+
     #[test]
     fn contract_addresses_marker_expands_to_padded_topics() {
         // Wildcard event filtering an indexed param by the contract's own
@@ -414,6 +717,7 @@ mod tests {
             built.log_selections,
             vec![BuiltLogSelection {
                 addresses: vec![],
+                address_filter: None,
                 topics: vec![
                     vec![SIGHASH_A.to_string()],
                     vec![ADDR_TOPIC.to_string()],
@@ -457,10 +761,12 @@ mod tests {
             vec![
                 BuiltLogSelection {
                     addresses: vec![],
+                    address_filter: None,
                     topics: vec![vec![SIGHASH_A.to_string()], vec![], vec![], vec![]],
                 },
                 BuiltLogSelection {
                     addresses: vec![],
+                    address_filter: None,
                     topics: vec![
                         vec![SIGHASH_B.to_string()],
                         vec![ADDR_TOPIC.to_string()],
@@ -489,6 +795,144 @@ mod tests {
         assert_eq!(built.log_selections, vec![]);
     }
 
+    #[test]
+    fn call_registrations_build_trace_selections_not_log_selections() {
+        let call = |id, sighash: &str, contract_name: &str, is_wildcard| OnEventRegistrationInput {
+            kind: Some(EvmEventKind::Call),
+            ..reg(
+                id,
+                sighash,
+                contract_name,
+                is_wildcard,
+                !is_wildcard,
+                Some(vec![]),
+            )
+        };
+        let builder = SelectionBuilder::from_registrations(&[
+            call(0, "0xa9059cbb", "C", false),
+            call(1, "0x23b872dd", "C", false),
+            call(2, "0x095ea7b3", "W", true),
+            reg(3, SIGHASH_A, "C", false, true, Some(vec![])),
+        ])
+        .unwrap();
+        let (_store, set) = addresses(&[("C", &[ADDR]), ("W", &[])]);
+        let built = builder
+            .build(&[0, 1, 2, 3], &set, &Default::default())
+            .unwrap();
+        assert_eq!(
+            (built.trace_selections, built.log_selections.len()),
+            (
+                vec![
+                    BuiltCallSelection {
+                        addresses: vec![],
+                        sighashes: vec!["0x095ea7b3".to_string()],
+                        status: None,
+                    },
+                    BuiltCallSelection {
+                        addresses: vec![ADDR.to_string()],
+                        sighashes: vec!["0xa9059cbb".to_string(), "0x23b872dd".to_string()],
+                        status: None,
+                    },
+                ],
+                1,
+            )
+        );
+    }
+
+    #[test]
+    fn transaction_registrations_build_transaction_selections() {
+        let with_kind = |kind, id| OnEventRegistrationInput {
+            kind: Some(kind),
+            ..reg(id, "0xa9059cbb", "C", false, true, Some(vec![]))
+        };
+        let failed = OnEventRegistrationInput {
+            transaction_where: Some(TransactionWhereInput {
+                status: Some(0),
+                gas_used: None,
+                value: None,
+            }),
+            ..with_kind(EvmEventKind::Transaction, 2)
+        };
+        let builder = SelectionBuilder::from_registrations(&[
+            with_kind(EvmEventKind::Transaction, 0),
+            with_kind(EvmEventKind::Call, 1),
+            failed,
+        ])
+        .unwrap();
+        let (_store, set) = addresses(&[("C", &[ADDR])]);
+        let built = builder
+            .build(&[0, 1, 2], &set, &Default::default())
+            .unwrap();
+        let selection = |status| BuiltCallSelection {
+            addresses: vec![ADDR.to_string()],
+            sighashes: vec!["0xa9059cbb".to_string()],
+            status,
+        };
+        assert_eq!(
+            (
+                built.transaction_selections,
+                built.trace_selections,
+                built.transaction_fields
+            ),
+            (
+                vec![selection(Some(0)), selection(Some(1))],
+                vec![selection(None)],
+                vec![TransactionField::Status]
+            )
+        );
+    }
+
+    #[test]
+    fn creation_registrations_select_by_deployer() {
+        let with_kind = |kind, id, contract_name: &str| OnEventRegistrationInput {
+            kind: Some(kind),
+            ..reg(id, "", contract_name, false, true, Some(vec![]))
+        };
+        let builder = SelectionBuilder::from_registrations(&[
+            with_kind(EvmEventKind::Create, 0, "Factory"),
+            with_kind(EvmEventKind::Create, 1, "Factory"),
+            with_kind(EvmEventKind::Deploy, 2, "Deployer"),
+            with_kind(EvmEventKind::Create, 3, "Empty"),
+        ])
+        .unwrap();
+        let (_store, set) = addresses(&[("Factory", &[ADDR]), ("Deployer", &[]), ("Empty", &[])]);
+        let built = builder
+            .build(&[0, 1, 2, 3], &set, &Default::default())
+            .unwrap();
+        assert_eq!(
+            (
+                built.creation_selections,
+                built.deploy_selections,
+                built.log_selections.len(),
+                built.trace_selections.len(),
+            ),
+            (
+                vec![BuiltCreationSelection {
+                    deployers: vec![ADDR.to_string()],
+                    status: None,
+                }],
+                vec![],
+                0,
+                0,
+            )
+        );
+
+        // A deploy only ever selects transactions that succeeded: a reverted
+        // one created nothing.
+        let (_store, set) = addresses(&[("Factory", &[]), ("Deployer", &[ADDR]), ("Empty", &[])]);
+        let built = builder.build(&[2], &set, &Default::default()).unwrap();
+        assert_eq!(
+            (built.deploy_selections, built.transaction_fields),
+            (
+                vec![BuiltCreationSelection {
+                    deployers: vec![ADDR.to_string()],
+                    status: Some(1),
+                }],
+                vec![TransactionField::Status],
+            )
+        );
+    }
+
     #[test]
     fn client_filtered_contract_builds_address_free_selection() {
         // A non-wildcard, address-dependent registration whose contract is
@@ -514,6 +958,7 @@ mod tests {
             built.log_selections,
             vec![BuiltLogSelection {
                 addresses: vec![],
+                address_filter: None,
                 topics: vec![vec![SIGHASH_A.to_string()], vec![], vec![], vec![]],
             }]
         );
@@ -538,6 +983,7 @@ mod tests {
             built.log_selections,
             vec![BuiltLogSelection {
                 addresses: vec![],
+                address_filter: None,
                 topics: vec![vec![SIGHASH_A.to_string()], vec![], vec![], vec![]],
             }]
         );
@@ -559,6 +1005,7 @@ mod tests {
             built.log_selections,
             vec![BuiltLogSelection {
                 addresses: vec![ADDR.to_string()],
+                address_filter: None,
                 topics: vec![vec![SIGHASH_B.to_string()], vec![], vec![], vec![]],
             }]
         );
@@ -0,0 +1,272 @@
+//! Registrations' `where.transaction`: the receipt status and gas used / value
+//! bounds an item's transaction must meet. A transaction registration's status
+//! compiles into its `TransactionFilter`; everything else — and every
+//! condition on a log or a call, whose selections can't name their
+//! transaction — is checked on the joined transaction once items are routed,
+//! by both sources alike.
+
+use std::collections::HashMap;
+
+use alloy_primitives::U256;
+use anyhow::{Context, Result};
+use hypersync_client::format::{Quantity, TransactionStatus};
+use hypersync_client::simple_types;
+
+use super::query::TransactionField;
+use super::types::{EvmEventKind, OnEventRegistrationInput, QuantityRangeInput};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct QuantityRange {
+    gte: Option<U256>,
+    lte: Option<U256>,
+}
+
+impl QuantityRange {
+    fn parse(input: &QuantityRangeInput, name: &str) -> Result<Self> {
+        let bound = |value: &Option<String>, op: &str| -> Result<Option<U256>> {
+            value
+                .as_deref()
+                .map(|v| {
+                    v.parse::<U256>()
+                        .with_context(|| format!("Invalid {name}.{op} bound \"{v}\""))
+                })
+                .transpose()
+        };
+        Ok(Self {
+            gte: bound(&input.gte, "_gte")?,
+            lte: bound(&input.lte, "_lte")?,
+        })
+    }
+
+    /// An absent quantity is outside any range.
+    fn contains(&self, quantity: Option<&Quantity>) -> bool {
+        let Some(value) = quantity.and_then(|q| U256::try_from_be_slice(q.as_ref())) else {
+            return false;
+        };
+        self.gte.is_none_or(|gte| value >= gte) && self.lte.is_none_or(|lte| value <= lte)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TransactionWhere {
+    status: Option<TransactionStatus>,
+    gas_used: Option<QuantityRange>,
+    value: Option<QuantityRange>,
+}
+
+impl TransactionWhere {
+    /// The registration's conditions; `None` when it has none. A transaction
+    /// registration that names no status only matches successful
+    /// transactions — a reverted transaction's call never happened, so
+    /// monitoring failed calls is an explicit `status: failure`. A deploy
+    /// registration likewise: a reverted deployment created nothing.
+    pub(crate) fn from_registration(reg: &OnEventRegistrationInput) -> Result<Option<Self>> {
+        let input = reg.transaction_where.as_ref();
+        let status = match input.and_then(|w| w.status) {
+            Some(1) => Some(TransactionStatus::Success),
+            Some(0) => Some(TransactionStatus::Failure),
+            Some(other) => anyhow::bail!(
+                "Invalid transaction status {other} for event {}, expected 0 or 1",
+                reg.event_name
+            ),
+            None => matches!(
+                reg.kind,
+                Some(EvmEventKind::Transaction | EvmEventKind::Deploy)
+            )
+            .then_some(TransactionStatus::Success),
+        };
+        let range = |range: Option<&QuantityRangeInput>, name: &str| {
+            range
+                .map(|r| QuantityRange::parse(r, name))
+                .transpose()
+                .with_context(|| format!("Failed parsing where.transaction of {}", reg.event_name))
+        };
+        let parsed = Self {
+            status,
+            gas_used: range(input.and_then(|w| w.gas_used.as_ref()), "gasUsed")?,
+            value: range(input.and_then(|w| w.value.as_ref()), "value")?,
+        };
+        Ok(
+            (parsed.status.is_some() || parsed.gas_used.is_some() || parsed.value.is_some())
+                .then_some(parsed),
+        )
+    }
+
+    /// The status as `TransactionFilter` spells it.
+    pub(crate) fn status_code(&self) -> Option<u8> {
+        self.status.map(|status| match status {
+            TransactionStatus::Success => 1,
+            TransactionStatus::Failure => 0,
+        })
+    }
+
+    /// The transaction fields checking the conditions reads.
+    pub(crate) fn fields(&self) -> Vec<TransactionField> {
+        let mut fields = Vec::new();
+        if self.status.is_some() {
+            fields.push(TransactionField::Status);
+        }
+        if self.gas_used.is_some() {
+            fields.push(TransactionField::GasUsed);
+        }
+        if self.value.is_some() {
+            fields.push(TransactionField::Value);
+        }
+        fields
+    }
+
+    pub(crate) fn matches(&self, tx: &simple_types::Transaction) -> bool {
+        self.status.is_none_or(|status| tx.status == Some(status))
+            && self
+                .gas_used
+                .is_none_or(|range| range.contains(tx.gas_used.as_ref()))
+            && self
+                .value
+                .is_none_or(|range| range.contains(tx.value.as_ref()))
+    }
+}
+
+/// The conditions of one query's registrations, by registration index.
+#[derive(Default)]
+pub(crate) struct TransactionWheres(HashMap<i64, TransactionWhere>);
+
+impl TransactionWheres {
+    pub(crate) fn insert(&mut self, index: i64, transaction_where: TransactionWhere) {
+        self.0.insert(index, transaction_where);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether an item routed to registration `index` keeps its place. An item
+    /// whose transaction didn't come back is kept: the page's coverage check
+    /// reports the missing transaction instead.
+    pub(crate) fn accepts(&self, index: i64, tx: Option<&simple_types::Transaction>) -> bool {
+        match (self.0.get(&index), tx) {
+            (Some(transaction_where), Some(tx)) => transaction_where.matches(tx),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm_hypersync_source::types::TransactionWhereInput;
+
+    fn reg(
+        kind: Option<EvmEventKind>,
+        transaction_where: Option<TransactionWhereInput>,
+    ) -> OnEventRegistrationInput {
+        OnEventRegistrationInput {
+            index: 0,
+            kind,
+            sighash: "0xa9059cbb".to_string(),
+            topic_count: 0,
+            event_name: "transfer".to_string(),
+            contract_name: "Token".to_string(),
+            is_wildcard: false,
+            depends_on_addresses: true,
+            start_block: None,
+            params: vec![],
+            topic_selections: vec![],
+            block_fields: vec![],
+            transaction_fields: vec![],
+            transaction_where,
+            params_where: None,
+            register: None,
+        }
+    }
+
+    fn tx(status: TransactionStatus, gas_used: u64, value: u64) -> simple_types::Transaction {
+        simple_types::Transaction {
+            status: Some(status),
+            gas_used: Some(gas_used.into()),
+            value: Some(value.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transaction_registrations_default_to_successful_transactions() {
+        assert_eq!(
+            TransactionWhere::from_registration(&reg(None, None)).unwrap(),
+            None
+        );
+        let default =
+            TransactionWhere::from_registration(&reg(Some(EvmEventKind::Transaction), None))
+                .unwrap()
+                .unwrap();
+        assert_eq!(default.status_code(), Some(1));
+        assert_eq!(default.fields(), vec![TransactionField::Status]);
+
+        let failed = TransactionWhere::from_registration(&reg(
+            Some(EvmEventKind::Transaction),
+            Some(TransactionWhereInput {
+                status: Some(0),
+                gas_used: None,
+                value: None,
+            }),
+        ))
+        .unwrap()
+        .unwrap();
+        assert!(failed.matches(&tx(TransactionStatus::Failure, 0, 0)));
+        assert!(!failed.matches(&tx(TransactionStatus::Success, 0, 0)));
+    }
+
+    #[test]
+    fn ranges_bound_gas_used_and_value_inclusively() {
+        let parsed = TransactionWhere::from_registration(&reg(
+            None,
+            Some(TransactionWhereInput {
+                status: None,
+                gas_used: Some(QuantityRangeInput {
+                    gte: None,
+                    lte: Some("21000".to_string()),
+                }),
+                value: Some(QuantityRangeInput {
+                    gte: Some("1000000000000000000".to_string()),
+                    lte: None,
+                }),
+            }),
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            parsed.fields(),
+            vec![TransactionField::GasUsed, TransactionField::Value]
+        );
+        let ether = 1_000_000_000_000_000_000;
+        assert!(parsed.matches(&tx(TransactionStatus::Success, 21000, ether)));
+        assert!(parsed.matches(&tx(TransactionStatus::Failure, 21000, ether)));
+        assert!(!parsed.matches(&tx(TransactionStatus::Success, 21001, ether)));
+        assert!(!parsed.matches(&tx(TransactionStatus::Success, 21000, ether - 1)));
+        assert!(!parsed.matches(&simple_types::Transaction::default()));
+
+        let mut wheres = TransactionWheres::default();
+        wheres.insert(0, parsed);
+        assert!(wheres.accepts(0, None));
+        assert!(wheres.accepts(1, Some(&simple_types::Transaction::default())));
+        assert!(!wheres.accepts(0, Some(&simple_types::Transaction::default())));
+    }
+
+    #[test]
+    fn rejects_malformed_bounds_and_statuses() {
+        let bad_status = TransactionWhereInput {
+            status: Some(2),
+            gas_used: None,
+            value: None,
+        };
+        assert!(TransactionWhere::from_registration(&reg(None, Some(bad_status))).is_err());
+        let bad_bound = TransactionWhereInput {
+            status: None,
+            gas_used: None,
+            value: Some(QuantityRangeInput {
+                gte: Some("1e18".to_string()),
+                lte: None,
+            }),
+        };
+        assert!(TransactionWhere::from_registration(&reg(None, Some(bad_bound))).is_err());
+    }
+}
@@ -289,6 +289,21 @@ pub struct ParamMeta {
     pub components: Option<Vec<ParamMeta>>,
 }
 
+/// What an EVM registration is read from: event logs, calls to the contract
+/// taken from traces, or transactions sent to the contract. `Create` and
+/// `Deploy` read the contracts the contract's addresses create: from create
+/// traces, and from contract-creating transactions they send.
+#[napi(string_enum)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum EvmEventKind {
+    #[default]
+    Log,
+    Call,
+    Transaction,
+    Create,
+    Deploy,
+}
+
 /// The full per-(event, chain) registration crossing the boundary once at
 /// client construction: decode metadata (`sighash`/`topic_count`/`params`),
 /// routing identity (`id`/`contract_name`/`is_wildcard`), and the fetch state
@@ -298,6 +313,11 @@ pub struct OnEventRegistrationInput {
     /// Chain-scoped sequential registration index; returned on every routed
     /// item so JS resolves the registration by array index.
     pub index: i64,
+    /// Absent is a log. A call's or transaction's `sighash` is its 4-byte
+    /// selector, and its `topic_count`/`topic_selections` are ignored; a
+    /// creation's `sighash` is ignored too, and its one param is the created
+    /// address.
+    pub kind: Option<EvmEventKind>,
     pub sighash: String,
     pub topic_count: i32,
     pub event_name: String,
@@ -318,6 +338,56 @@ pub struct OnEventRegistrationInput {
     /// Transaction fields this event's handler reads (HyperSync field
     /// selection).
     pub transaction_fields: Vec<crate::evm_hypersync_source::query::TransactionField>,
+    /// The event's `where.transaction` from `config.yaml`; absent matches any
+    /// transaction (a transaction registration: any successful one).
+    pub transaction_where: Option<TransactionWhereInput>,
+    /// The event's `where.params` from `config.yaml`, checked on the decoded
+    /// params; absent keeps every item.
+    pub params_where: Option<Vec<ParamWhereInput>>,
+    /// Registers the address in one of the decoded params for another
+    /// contract as each item is routed; absent registers nothing.
+    pub register: Option<RegisterInput>,
+}
+
+/// The contract an item's address param is registered for, by param name.
+#[napi(object)]
+#[derive(Clone)]
+pub struct RegisterInput {
+    pub contract_name: String,
+    pub param: String,
+}
+
+/// A condition on one decoded, non-indexed param. Values are written as
+/// `DynSolType::coerce_str` reads them for the param's type; every condition
+/// given must hold.
+#[napi(object)]
+#[derive(Clone)]
+pub struct ParamWhereInput {
+    pub name: String,
+    pub eq: Option<String>,
+    pub one_of: Option<Vec<String>>,
+    /// Inclusive bounds; int and uint params only.
+    pub gte: Option<String>,
+    pub lte: Option<String>,
+}
+
+/// Conditions on the transaction an item belongs to. `status` is the receipt
+/// status: 1 succeeded, 0 reverted.
+#[napi(object)]
+#[derive(Clone)]
+pub struct TransactionWhereInput {
+    pub status: Option<u32>,
+    pub gas_used: Option<QuantityRangeInput>,
+    pub value: Option<QuantityRangeInput>,
+}
+
+/// Inclusive bounds on a quantity, as decimal strings — wei values overflow JS
+/// numbers.
+#[napi(object)]
+#[derive(Clone)]
+pub struct QuantityRangeInput {
+    pub gte: Option<String>,
+    pub lte: Option<String>,
 }
 
 pub enum ParamValue {
@@ -26,6 +26,20 @@ pub fn is_response_too_large_message(message: &str) -> bool {
     TOO_LARGE_PATTERNS.iter().any(|re| re.is_match(message))
 }
 
+// A provider that doesn't serve a method at all: the JSON-RPC "method not
+// found" code, or the wording providers use when they answer it with a
+// generic code instead.
+static METHOD_UNSUPPORTED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(method .*(not found|does not exist|is not available|not supported)|unsupported method|not whitelisted)",
+    )
+    .unwrap()
+});
+
+pub fn is_method_unsupported(code: i64, message: &str) -> bool {
+    code == -32601 || METHOD_UNSUPPORTED.is_match(message)
+}
+
 // Unknown provider: "retry with the range 123-456"
 static SUGGESTED_RANGE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"retry with the range (\d+)-(\d+)").unwrap());
@@ -207,4 +221,18 @@ mod tests {
         ));
         assert!(!is_response_too_large_message("rate limited"));
     }
+
+    #[test]
+    fn method_unsupported_by_code_or_wording() {
+        assert!(is_method_unsupported(-32601, "whatever"));
+        assert!(is_method_unsupported(
+            -32000,
+            "the method trace_filter does not exist/is not available"
+        ));
+        assert!(is_method_unsupported(
+            -32600,
+            "Unsupported method: trace_filter"
+        ));
+        assert!(!is_method_unsupported(-32000, "execution timeout"));
+    }
 }
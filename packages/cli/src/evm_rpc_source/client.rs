@@ -1,16 +1,34 @@
 use anyhow::{Context, Result};
+use futures_util::stream::{self, StreamExt};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::json;
 use serde_json::value::RawValue;
 use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::request_stats::RequestStat;
+
+/// Upper bound on HTTP requests in flight at once for a single batched call.
+/// A page can reference hundreds of transactions; even batched, firing every
+/// chunk at once trips provider rate limits long before it saves any latency.
+const MAX_CONCURRENT_REQUESTS: usize = 16;
 
 /// JSON-RPC level errors are kept separate from transport/parse failures:
 /// provider error messages carry block-range hints the caller inspects.
 #[derive(Debug)]
 pub enum RpcError {
-    JsonRpc { code: i64, message: String },
+    JsonRpc {
+        code: i64,
+        message: String,
+    },
+    /// A `null` result for a block or transaction the page references: the
+    /// provider doesn't have it yet. Retried after a backoff, never treated as
+    /// a failure of the queried range.
+    NotFound {
+        message: String,
+    },
     Other(anyhow::Error),
 }
 
@@ -37,9 +55,34 @@ struct JsonRpcResponse {
     error: Option<JsonRpcErrorObject>,
 }
 
+/// One entry of a batch response. Providers may answer a batch in any order,
+/// so entries are matched back to their calls by `id`. Spelled out rather than
+/// flattening `JsonRpcResponse`: serde buffers flattened fields, which
+/// `RawValue` can't be read from.
+#[derive(Deserialize)]
+struct JsonRpcBatchEntry {
+    id: Option<u64>,
+    #[serde(default, deserialize_with = "raw_value_as_some")]
+    result: Option<Box<RawValue>>,
+    error: Option<JsonRpcErrorObject>,
+}
+
+impl JsonRpcBatchEntry {
+    fn into_response(self) -> JsonRpcResponse {
+        JsonRpcResponse {
+            result: self.result,
+            error: self.error,
+        }
+    }
+}
+
+/// Per-call results of a batched call, or the error that failed it whole.
+pub(super) type BatchResult<T> = Result<Vec<Result<T, RpcError>>, RpcError>;
+
 pub struct JsonRpcClient {
     http: reqwest::Client,
     url: String,
+    max_batch_size: usize,
 }
 
 impl JsonRpcClient {
@@ -47,11 +90,18 @@ impl JsonRpcClient {
         120_000
     }
 
+    /// `max_batch_size` caps the calls sent in one JSON-RPC batch payload; 1
+    /// disables batching for providers that don't support it.
     pub fn new(
         url: String,
         http_req_timeout_millis: u64,
         headers: Option<HashMap<String, String>>,
+        max_batch_size: usize,
     ) -> Result<Self> {
+        anyhow::ensure!(
+            max_batch_size > 0,
+            "maxBatchSize must be positive, got {max_batch_size}"
+        );
         let mut builder = reqwest::Client::builder()
             .timeout(std::time::Duration::from_millis(http_req_timeout_millis));
         if let Some(headers) = headers {
@@ -66,7 +116,11 @@ impl JsonRpcClient {
             builder = builder.default_headers(header_map);
         }
         let http = builder.build().context("build http client")?;
-        Ok(Self { http, url })
+        Ok(Self {
+            http,
+            url,
+            max_batch_size,
+        })
     }
 
     pub async fn request<T: DeserializeOwned>(
@@ -80,10 +134,105 @@ impl JsonRpcClient {
             "id": 1,
             "jsonrpc": "2.0",
         });
+        let (status, bytes) = self.post(method, &body).await?;
+        let parsed: JsonRpcResponse = parse_body(method, status, &bytes)?;
+        parse_response(method, status, parsed)
+    }
+
+    /// Calls `method` once per entry of `params`, packing the calls into
+    /// JSON-RPC batch payloads of at most `max_batch_size` calls so a page
+    /// costs one HTTP request per chunk rather than one per call. Results come
+    /// back in `params` order. An error confined to one call (a JSON-RPC error
+    /// entry, a missing entry, an unparsable result) lands in that call's
+    /// slot; a failure of a whole HTTP request (transport, non-JSON body, a
+    /// batch-level error object) fails the call. Every chunk settles before
+    /// returning, so `stats` gets one entry per HTTP request sent, even when
+    /// one of them fails.
+    pub async fn batch_request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+        stats: &mut Vec<RequestStat>,
+    ) -> BatchResult<T> {
+        let chunks: Vec<Vec<serde_json::Value>> = params
+            .chunks(self.max_batch_size)
+            .map(<[_]>::to_vec)
+            .collect();
+        let results: Vec<(BatchResult<T>, f64)> = stream::iter(chunks)
+            .map(|chunk| async move {
+                let started = Instant::now();
+                let result = self.send_chunk::<T>(method, chunk).await;
+                (result, started.elapsed().as_secs_f64())
+            })
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut out = Vec::with_capacity(params.len());
+        let mut first_err = None;
+        for (result, seconds) in results {
+            stats.push(RequestStat {
+                method: method.to_string(),
+                seconds,
+            });
+            match result {
+                Ok(chunk) => out.extend(chunk),
+                Err(e) => {
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(out),
+        }
+    }
+
+    /// Sends one chunk of a batched call. A single call goes out as a plain
+    /// request, so providers without batch support still serve single-call
+    /// pages (and `max_batch_size: 1`).
+    async fn send_chunk<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        chunk: Vec<serde_json::Value>,
+    ) -> BatchResult<T> {
+        if chunk.len() == 1 {
+            let params = chunk.into_iter().next().expect("chunk has one call");
+            // Only a JSON-RPC error is scoped to the call; anything else means
+            // the HTTP request itself failed.
+            return match self.request::<T>(method, params).await {
+                Ok(value) => Ok(vec![Ok(value)]),
+                Err(e @ RpcError::JsonRpc { .. }) => Ok(vec![Err(e)]),
+                Err(e) => Err(e),
+            };
+        }
+
+        let calls = chunk.len();
+        let body: Vec<serde_json::Value> = chunk
+            .into_iter()
+            .enumerate()
+            .map(|(id, params)| {
+                json!({
+                    "method": method,
+                    "params": params,
+                    "id": id,
+                    "jsonrpc": "2.0",
+                })
+            })
+            .collect();
+        let (status, bytes) = self.post(method, &json!(body)).await?;
+        demux_batch(method, status, &bytes, calls)
+    }
+
+    async fn post(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> Result<(reqwest::StatusCode, impl std::ops::Deref<Target = [u8]>), RpcError> {
         let response = self
             .http
             .post(&self.url)
-            .json(&body)
+            .json(body)
             .send()
             .await
             .with_context(|| format!("send {method} request"))
@@ -95,39 +244,88 @@ impl JsonRpcClient {
             .await
             .with_context(|| format!("read {method} response body"))
             .map_err(RpcError::Other)?;
+        Ok((status, bytes))
+    }
+}
 
-        // Providers report JSON-RPC errors under non-200 statuses too (e.g.
-        // 429/400), so parse the body first and fall back to the HTTP status
-        // only when there's no JSON-RPC envelope to read.
-        let parsed: JsonRpcResponse = match serde_json::from_slice(&bytes) {
-            Ok(parsed) => parsed,
-            Err(e) => {
-                let snippet = String::from_utf8_lossy(&bytes[..bytes.len().min(512)]).into_owned();
-                return Err(RpcError::Other(anyhow::anyhow!(
-                    "invalid JSON-RPC response for {method} (HTTP {status}): {e}; body: {snippet}"
-                )));
-            }
-        };
+// Providers report JSON-RPC errors under non-200 statuses too (e.g. 429/400),
+// so parse the body first and fall back to the HTTP status only when there's
+// no JSON-RPC envelope to read.
+fn parse_body<R: DeserializeOwned>(
+    method: &str,
+    status: reqwest::StatusCode,
+    bytes: &[u8],
+) -> Result<R, RpcError> {
+    serde_json::from_slice(bytes).map_err(|e| {
+        let snippet = String::from_utf8_lossy(&bytes[..bytes.len().min(512)]).into_owned();
+        RpcError::Other(anyhow::anyhow!(
+            "invalid JSON-RPC response for {method} (HTTP {status}): {e}; body: {snippet}"
+        ))
+    })
+}
 
-        if let Some(error) = parsed.error {
-            return Err(RpcError::JsonRpc {
-                code: error.code,
-                message: error.message,
-            });
+/// Split a batch response body back into per-call results, in call order (the
+/// calls were sent with ids `0..calls`).
+fn demux_batch<T: DeserializeOwned>(
+    method: &str,
+    status: reqwest::StatusCode,
+    bytes: &[u8],
+    calls: usize,
+) -> Result<Vec<Result<T, RpcError>>, RpcError> {
+    // A batch is answered with an array, but a provider that rejects the batch
+    // as a whole (rate limit, batches unsupported, too many calls) answers
+    // with a single error object instead.
+    let is_array = bytes.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'[');
+    if !is_array {
+        let parsed: JsonRpcResponse = parse_body(method, status, bytes)?;
+        return Err(
+            match parse_response::<serde_json::Value>(method, status, parsed) {
+                Err(e) => e,
+                Ok(_) => RpcError::Other(anyhow::anyhow!(
+                    "JSON-RPC batch of {calls} {method} calls (HTTP {status}) was answered with \
+                     a single response"
+                )),
+            },
+        );
+    }
+    let entries: Vec<JsonRpcBatchEntry> = parse_body(method, status, bytes)?;
+
+    let mut slots: Vec<Option<JsonRpcResponse>> = (0..calls).map(|_| None).collect();
+    for entry in entries {
+        if let Some(slot) = entry.id.and_then(|id| slots.get_mut(id as usize)) {
+            *slot = Some(entry.into_response());
         }
-        match parsed.result {
-            Some(result) => serde_json::from_str(result.get())
-                .with_context(|| format!("parse {method} result"))
-                .map_err(RpcError::Other),
+    }
+    Ok(slots
+        .into_iter()
+        .enumerate()
+        .map(|(id, slot)| match slot {
+            Some(parsed) => parse_response(method, status, parsed),
             None => Err(RpcError::Other(anyhow::anyhow!(
-                "JSON-RPC response for {method} (HTTP {status}) has neither result nor error"
+                "JSON-RPC batch response for {method} (HTTP {status}) has no entry for id {id}"
             ))),
-        }
-    }
+        })
+        .collect())
+}
 
-    pub async fn get_height(&self) -> Result<u64, RpcError> {
-        let result: String = self.request("eth_blockNumber", json!([])).await?;
-        parse_hex_u64(&result).map_err(RpcError::Other)
+fn parse_response<T: DeserializeOwned>(
+    method: &str,
+    status: reqwest::StatusCode,
+    parsed: JsonRpcResponse,
+) -> Result<T, RpcError> {
+    if let Some(error) = parsed.error {
+        return Err(RpcError::JsonRpc {
+            code: error.code,
+            message: error.message,
+        });
+    }
+    match parsed.result {
+        Some(result) => serde_json::from_str(result.get())
+            .with_context(|| format!("parse {method} result"))
+            .map_err(RpcError::Other),
+        None => Err(RpcError::Other(anyhow::anyhow!(
+            "JSON-RPC response for {method} (HTTP {status}) has neither result nor error"
+        ))),
     }
 }
 
@@ -141,7 +339,8 @@ pub fn parse_hex_u64(s: &str) -> Result<u64> {
 
 // HTTP and JSON-RPC envelope behavior (success, error bodies, non-200
 // statuses) is covered end-to-end through the napi layer in
-// scenarios/test_codegen/test/lib_tests/EvmRpcClient_test.res.
+// scenarios/test_codegen/test/lib_tests/EvmRpcClient_test.res; batch
+// demultiplexing is covered here.
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +356,59 @@ mod tests {
         );
         assert_eq!(results, (0, 436, 436, true, true));
     }
+
+    fn demux(body: &str, calls: usize) -> Result<Vec<Result<String, RpcError>>, RpcError> {
+        demux_batch(
+            "eth_blockNumber",
+            reqwest::StatusCode::OK,
+            body.as_bytes(),
+            calls,
+        )
+    }
+
+    #[test]
+    fn demux_batch_orders_by_id_and_keeps_partial_errors() {
+        let results = demux(
+            r#"[
+                {"jsonrpc":"2.0","id":2,"result":"0x3"},
+                {"jsonrpc":"2.0","id":0,"result":"0x1"},
+                {"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"rate limited"}}
+            ]"#,
+            4,
+        )
+        .unwrap();
+        let summary: Vec<String> = results
+            .into_iter()
+            .map(|r| match r {
+                Ok(v) => v,
+                Err(RpcError::JsonRpc { code, message }) => format!("{code}: {message}"),
+                Err(RpcError::NotFound { message }) => format!("not found: {message}"),
+                Err(RpcError::Other(e)) => format!("other: {e}"),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "0x1".to_string(),
+                "-32005: rate limited".to_string(),
+                "0x3".to_string(),
+                "other: JSON-RPC batch response for eth_blockNumber (HTTP 200 OK) has no entry \
+                 for id 3"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn demux_batch_fails_whole_batch_on_single_error_object() {
+        let err = demux(
+            r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32600,"message":"batch too large"}}"#,
+            2,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            RpcError::JsonRpc { code: -32600, ref message } if message == "batch too large"
+        ));
+    }
 }
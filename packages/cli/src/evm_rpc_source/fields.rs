@@ -0,0 +1,1128 @@
+//! Block and transaction fields for the RPC source. `eth_getLogs` carries only
+//! the log, so the blocks and transactions a page's routed items join to are
+//! fetched here — `eth_getBlockByNumber` per block, `eth_getTransactionByHash`
+//! and/or `eth_getTransactionReceipt` per transaction depending on the fields
+//! the selection asked for, sent as JSON-RPC batches — and merged into the
+//! page's `BlockStore` and `TransactionStore`, the same stores the HyperSync
+//! source fills. Handlers then see identical `EvmBlockField`/`EvmTxField`
+//! values whichever source served the range.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Context;
+use hypersync_client::format::{
+    AccessList, Address, Authorization, BloomFilter, Data, FixedSizeData, Hash, Hex, Quantity,
+    TransactionStatus, TransactionType, UInt,
+};
+use hypersync_client::simple_types;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::client::RpcError;
+use super::pool::ProviderPool;
+use super::RpcEventItem;
+use crate::block_store::BlockStore;
+use crate::evm_hypersync_source::query::TransactionField;
+use crate::evm_hypersync_source::transaction_where::TransactionWheres;
+use crate::request_stats::RequestStat;
+use crate::transaction_store::{EvmTxExtras, TransactionStore};
+
+/// Which RPC response a transaction field is read from. Mirrors the split the
+/// providers impose: `eth_getTransactionByHash` has the signed payload,
+/// `eth_getTransactionReceipt` has the execution outcome, and a few fields
+/// appear on both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TxFieldSource {
+    /// Read off the log itself (or the store key); no request needed.
+    Log,
+    Transaction,
+    Receipt,
+    Both,
+}
+
+fn tx_field_source(field: TransactionField) -> TxFieldSource {
+    use TransactionField::*;
+    match field {
+        BlockHash | BlockNumber | Hash | TransactionIndex => TxFieldSource::Log,
+        Gas | GasPrice | Input | Nonce | Value | V | R | S | YParity | MaxPriorityFeePerGas
+        | MaxFeePerGas | ChainId | AccessList | AuthorizationList | MaxFeePerBlobGas
+        | BlobVersionedHashes | Sighash | Mint | SourceHash | IsSystemTx => {
+            TxFieldSource::Transaction
+        }
+        CumulativeGasUsed
+        | EffectiveGasPrice
+        | GasUsed
+        | ContractAddress
+        | LogsBloom
+        | Root
+        | Status
+        | L1Fee
+        | L1BlockNumber
+        | L1GasPrice
+        | L1GasUsed
+        | L1FeeScalar
+        | L1BaseFeeScalar
+        | L1BlobBaseFee
+        | L1BlobBaseFeeScalar
+        | GasUsedForL1
+        | BlobGasPrice
+        | BlobGasUsed
+        | DepositNonce
+        | DepositReceiptVersion
+        | OperatorFeeScalar => TxFieldSource::Receipt,
+        From | To | Type | L1BatchNumber | L1BatchTxIndex => TxFieldSource::Both,
+    }
+}
+
+/// The per-transaction requests a page's selected transaction fields need.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct TxPlan {
+    transactions: bool,
+    receipts: bool,
+}
+
+impl TxPlan {
+    pub(super) fn new(fields: &[TransactionField]) -> Self {
+        let mut plan = TxPlan::default();
+        let mut needs_both = false;
+        for &field in fields {
+            match tx_field_source(field) {
+                TxFieldSource::Log => {}
+                TxFieldSource::Transaction => plan.transactions = true,
+                TxFieldSource::Receipt => plan.receipts = true,
+                TxFieldSource::Both => needs_both = true,
+            }
+        }
+        // A field both responses carry rides along with whichever one is
+        // already fetched; only on its own does it cost a request.
+        if needs_both && !plan.transactions && !plan.receipts {
+            plan.transactions = true;
+        }
+        plan
+    }
+}
+
+/// Block fields as `eth_getBlockByNumber` serialises them. Every field is
+/// optional: chains differ in which header fields they report, and a block the
+/// selection never reads must not fail the page over a field it lacks.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RpcBlock {
+    number: Option<UInt>,
+    hash: Option<Hash>,
+    parent_hash: Option<Hash>,
+    // Quantity rather than the 8-byte `Nonce`: some chains serialise it
+    // without leading zeros.
+    nonce: Option<Quantity>,
+    sha3_uncles: Option<Hash>,
+    logs_bloom: Option<BloomFilter>,
+    transactions_root: Option<Hash>,
+    state_root: Option<Hash>,
+    receipts_root: Option<Hash>,
+    miner: Option<Address>,
+    difficulty: Option<Quantity>,
+    total_difficulty: Option<Quantity>,
+    extra_data: Option<Data>,
+    size: Option<Quantity>,
+    gas_limit: Option<Quantity>,
+    gas_used: Option<Quantity>,
+    timestamp: Option<Quantity>,
+    uncles: Option<Vec<Hash>>,
+    base_fee_per_gas: Option<Quantity>,
+    blob_gas_used: Option<Quantity>,
+    excess_blob_gas: Option<Quantity>,
+    parent_beacon_block_root: Option<Hash>,
+    withdrawals_root: Option<Hash>,
+    l1_block_number: Option<UInt>,
+    send_count: Option<Quantity>,
+    send_root: Option<Hash>,
+    mix_hash: Option<Hash>,
+}
+
+impl RpcBlock {
+    pub(super) fn into_simple(self) -> anyhow::Result<simple_types::Block> {
+        let nonce = self
+            .nonce
+            .map(|n| left_pad::<8>(n.as_ref()).context("block.nonce wider than 8 bytes"))
+            .transpose()?;
+        Ok(simple_types::Block {
+            number: Some(u64::from(self.number.context("block.number missing")?)),
+            hash: self.hash,
+            parent_hash: self.parent_hash,
+            nonce,
+            sha3_uncles: self.sha3_uncles,
+            logs_bloom: self.logs_bloom,
+            transactions_root: self.transactions_root,
+            state_root: self.state_root,
+            receipts_root: self.receipts_root,
+            miner: self.miner,
+            difficulty: self.difficulty,
+            total_difficulty: self.total_difficulty,
+            extra_data: self.extra_data,
+            size: self.size,
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            timestamp: self.timestamp,
+            uncles: self.uncles,
+            base_fee_per_gas: self.base_fee_per_gas,
+            blob_gas_used: self.blob_gas_used,
+            excess_blob_gas: self.excess_blob_gas,
+            parent_beacon_block_root: self.parent_beacon_block_root,
+            withdrawals_root: self.withdrawals_root,
+            withdrawals: None,
+            l1_block_number: self.l1_block_number,
+            send_count: self.send_count,
+            send_root: self.send_root,
+            mix_hash: self.mix_hash,
+        })
+    }
+}
+
+fn left_pad<const N: usize>(bytes: &[u8]) -> Option<FixedSizeData<N>> {
+    let bytes = match bytes.iter().position(|&b| b != 0) {
+        Some(first) => &bytes[first..],
+        None => &[],
+    };
+    if bytes.len() > N {
+        return None;
+    }
+    let mut buf = [0u8; N];
+    buf[N - bytes.len()..].copy_from_slice(bytes);
+    Some(FixedSizeData::from(buf))
+}
+
+/// The `eth_getTransactionByHash` fields the store keeps.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransaction {
+    from: Option<Address>,
+    to: Option<Address>,
+    gas: Option<Quantity>,
+    gas_price: Option<Quantity>,
+    input: Option<Data>,
+    nonce: Option<Quantity>,
+    value: Option<Quantity>,
+    v: Option<Quantity>,
+    r: Option<Quantity>,
+    s: Option<Quantity>,
+    y_parity: Option<Quantity>,
+    max_priority_fee_per_gas: Option<Quantity>,
+    max_fee_per_gas: Option<Quantity>,
+    chain_id: Option<Quantity>,
+    access_list: Option<Vec<AccessList>>,
+    authorization_list: Option<Vec<Authorization>>,
+    max_fee_per_blob_gas: Option<Quantity>,
+    blob_versioned_hashes: Option<Vec<Hash>>,
+    #[serde(rename = "type")]
+    type_: Option<TransactionType>,
+    mint: Option<Quantity>,
+    source_hash: Option<Hash>,
+    is_system_tx: Option<bool>,
+    l1_batch_number: Option<UInt>,
+    l1_batch_tx_index: Option<UInt>,
+}
+
+/// The `eth_getTransactionReceipt` fields the store keeps.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcReceipt {
+    from: Option<Address>,
+    to: Option<Address>,
+    cumulative_gas_used: Option<Quantity>,
+    effective_gas_price: Option<Quantity>,
+    gas_used: Option<Quantity>,
+    contract_address: Option<Address>,
+    logs_bloom: Option<BloomFilter>,
+    #[serde(rename = "type")]
+    type_: Option<TransactionType>,
+    root: Option<Hash>,
+    status: Option<TransactionStatus>,
+    l1_fee: Option<Quantity>,
+    l1_gas_price: Option<Quantity>,
+    l1_gas_used: Option<Quantity>,
+    // A decimal float printed as a string, e.g. "0.684".
+    l1_fee_scalar: Option<String>,
+    gas_used_for_l1: Option<Quantity>,
+    blob_gas_price: Option<Quantity>,
+    blob_gas_used: Option<Quantity>,
+    deposit_nonce: Option<Quantity>,
+    deposit_receipt_version: Option<Quantity>,
+    l1_base_fee_scalar: Option<Quantity>,
+    l1_blob_base_fee: Option<Quantity>,
+    l1_blob_base_fee_scalar: Option<Quantity>,
+    l1_block_number: Option<Quantity>,
+    operator_fee_scalar: Option<Quantity>,
+    l1_batch_number: Option<UInt>,
+    l1_batch_tx_index: Option<UInt>,
+}
+
+/// A routed transaction's identity, all of it read off the log.
+struct TxRef {
+    block_number: u64,
+    transaction_index: u64,
+    hash: Hash,
+    block_hash: Hash,
+}
+
+/// Assemble one store row from the log-derived key and whichever responses
+/// were fetched. A receipt without `effectiveGasPrice` (pre-EIP-1559 chains,
+/// e.g. Optimism before Bedrock) prices the transaction the legacy way, so its
+/// `gasPrice` is the effective price — the same substitution HyperSync serves.
+/// When the selection reads `effectiveGasPrice` and neither is present, the
+/// page fails rather than handing the handler a missing field.
+fn assemble_transaction(
+    tx_ref: &TxRef,
+    tx: Option<RpcTransaction>,
+    receipt: Option<RpcReceipt>,
+    needs_effective_gas_price: bool,
+) -> anyhow::Result<(simple_types::Transaction, EvmTxExtras)> {
+    let mut out = simple_types::Transaction {
+        block_hash: Some(tx_ref.block_hash.clone()),
+        block_number: Some(UInt::from(tx_ref.block_number)),
+        hash: Some(tx_ref.hash.clone()),
+        transaction_index: Some(UInt::from(tx_ref.transaction_index)),
+        ..Default::default()
+    };
+    let mut extras = EvmTxExtras::default();
+    if let Some(tx) = tx {
+        out.sighash = tx
+            .input
+            .as_ref()
+            .filter(|input| input.len() >= 4)
+            .map(|input| Data::from(&input[..4]));
+        out.from = tx.from;
+        out.to = tx.to;
+        out.gas = tx.gas;
+        out.gas_price = tx.gas_price;
+        out.input = tx.input;
+        out.nonce = tx.nonce;
+        out.value = tx.value;
+        out.v = tx.v;
+        out.r = tx.r;
+        out.s = tx.s;
+        out.y_parity = tx.y_parity;
+        out.max_priority_fee_per_gas = tx.max_priority_fee_per_gas;
+        out.max_fee_per_gas = tx.max_fee_per_gas;
+        out.chain_id = tx.chain_id;
+        out.access_list = tx.access_list;
+        out.authorization_list = tx.authorization_list;
+        out.max_fee_per_blob_gas = tx.max_fee_per_blob_gas;
+        out.blob_versioned_hashes = tx.blob_versioned_hashes;
+        out.type_ = tx.type_;
+        out.mint = tx.mint;
+        out.source_hash = tx.source_hash;
+        extras.is_system_tx = tx.is_system_tx;
+        extras.l1_batch_number = tx.l1_batch_number.map(u64::from);
+        extras.l1_batch_tx_index = tx.l1_batch_tx_index.map(u64::from);
+    }
+    if let Some(receipt) = receipt {
+        out.from = out.from.or(receipt.from);
+        out.to = out.to.or(receipt.to);
+        out.type_ = out.type_.or(receipt.type_);
+        out.cumulative_gas_used = receipt.cumulative_gas_used;
+        out.effective_gas_price = receipt
+            .effective_gas_price
+            .or_else(|| out.gas_price.clone());
+        if needs_effective_gas_price && out.effective_gas_price.is_none() {
+            anyhow::bail!(
+                "Neither \"effectiveGasPrice\" nor \"gasPrice\" is present in the RPC \
+                 response for the transaction. Remove \"effectiveGasPrice\" from the field \
+                 selection, or index this chain via HyperSync."
+            );
+        }
+        out.gas_used = receipt.gas_used;
+        out.contract_address = receipt.contract_address;
+        out.logs_bloom = receipt.logs_bloom;
+        out.root = receipt.root;
+        out.status = receipt.status;
+        out.l1_fee = receipt.l1_fee;
+        out.l1_gas_price = receipt.l1_gas_price;
+        out.l1_gas_used = receipt.l1_gas_used;
+        out.l1_fee_scalar = receipt
+            .l1_fee_scalar
+            .map(|s| {
+                s.parse::<f64>()
+                    .with_context(|| format!("receipt.l1FeeScalar {s:?} is not a decimal"))
+            })
+            .transpose()?;
+        out.gas_used_for_l1 = receipt.gas_used_for_l1;
+        out.blob_gas_price = receipt.blob_gas_price;
+        out.blob_gas_used = receipt.blob_gas_used;
+        out.deposit_nonce = receipt.deposit_nonce;
+        out.deposit_receipt_version = receipt.deposit_receipt_version;
+        out.l1_base_fee_scalar = receipt.l1_base_fee_scalar;
+        out.l1_blob_base_fee = receipt.l1_blob_base_fee;
+        out.l1_blob_base_fee_scalar = receipt.l1_blob_base_fee_scalar;
+        out.l1_block_number = receipt.l1_block_number;
+        extras.operator_fee_scalar = receipt.operator_fee_scalar;
+        extras.l1_batch_number = extras
+            .l1_batch_number
+            .or(receipt.l1_batch_number.map(u64::from));
+        extras.l1_batch_tx_index = extras
+            .l1_batch_tx_index
+            .or(receipt.l1_batch_tx_index.map(u64::from));
+    }
+    Ok((out, extras))
+}
+
+/// Issue one call per params entry as batched requests (see
+/// `ProviderPool::batch_request`) and collect results in input order. A
+/// `null` result — the provider doesn't know the block or transaction yet,
+/// typically a load-balanced node lagging the one that served `eth_getLogs` —
+/// is `RpcError::NotFound`: the page is retried after a backoff.
+pub(super) async fn fetch_all<T: DeserializeOwned>(
+    client: &ProviderPool,
+    method: &'static str,
+    params: Vec<serde_json::Value>,
+    stats: &mut Vec<RequestStat>,
+) -> Result<Vec<T>, RpcError> {
+    let results = client
+        .batch_request::<Option<T>>(method, params.clone(), stats)
+        .await?;
+    require_found(method, &params, results)
+}
+
+fn require_found<T>(
+    method: &str,
+    params: &[serde_json::Value],
+    results: Vec<Result<Option<T>, RpcError>>,
+) -> Result<Vec<T>, RpcError> {
+    results
+        .into_iter()
+        .zip(params)
+        .map(|(result, params)| {
+            result?.ok_or_else(|| RpcError::NotFound {
+                message: not_found_message(method, params),
+            })
+        })
+        .collect()
+}
+
+/// Names what a `null` result was for, in the wording the retry message
+/// carries.
+fn not_found_message(method: &str, params: &serde_json::Value) -> String {
+    let id = match &params[0] {
+        serde_json::Value::String(id) => id.clone(),
+        other => other.to_string(),
+    };
+    match method {
+        "eth_getBlockByNumber" => {
+            let number = id
+                .strip_prefix("0x")
+                .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                .map_or(id.clone(), |n| n.to_string());
+            format!("Block not found for number: {number}")
+        }
+        "eth_getTransactionByHash" => format!("Transaction not found for hash: {id}"),
+        "eth_getTransactionReceipt" => format!("Transaction receipt not found for hash: {id}"),
+        _ => format!("{method} returned null for {id}"),
+    }
+}
+
+/// Fetch every block and transaction the page's items reference and merge them
+/// into the page stores. Blocks are always fetched — every item reads its
+/// block's number, timestamp and hash — while transactions are fetched only
+/// for the responses `plan` calls for. A call item read without its block hash
+/// gets it from its fetched block. Items whose transaction fails their
+/// registration's `where.transaction` are dropped once the transactions are
+/// in. Returns the requests' timings, which also travel with the error on
+/// failure.
+pub(super) async fn fetch_joins(
+    client: &ProviderPool,
+    items: &mut Vec<RpcEventItem>,
+    plan: TxPlan,
+    transaction_fields: &[TransactionField],
+    transaction_wheres: &TransactionWheres,
+    transaction_store: &TransactionStore,
+    block_store: &BlockStore,
+) -> Result<Vec<RequestStat>, (RpcError, Vec<RequestStat>)> {
+    let mut stats = Vec::new();
+    match fetch_joins_inner(
+        client,
+        items,
+        plan,
+        transaction_fields,
+        transaction_wheres,
+        transaction_store,
+        block_store,
+        &mut stats,
+    )
+    .await
+    {
+        Ok(()) => Ok(stats),
+        Err(e) => Err((e, stats)),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn fetch_joins_inner(
+    client: &ProviderPool,
+    items: &mut Vec<RpcEventItem>,
+    plan: TxPlan,
+    transaction_fields: &[TransactionField],
+    transaction_wheres: &TransactionWheres,
+    transaction_store: &TransactionStore,
+    block_store: &BlockStore,
+    stats: &mut Vec<RequestStat>,
+) -> Result<(), RpcError> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let block_numbers: BTreeSet<i64> = items.iter().map(|item| item.log.block_number).collect();
+    let raw_blocks: Vec<RpcBlock> = fetch_all(
+        client,
+        "eth_getBlockByNumber",
+        block_numbers
+            .iter()
+            .map(|n| json!([format!("0x{n:x}"), false]))
+            .collect(),
+        stats,
+    )
+    .await?;
+    let blocks = raw_blocks
+        .into_iter()
+        .map(RpcBlock::into_simple)
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(RpcError::Other)?;
+    if items.iter().any(|item| item.log.block_hash.is_empty()) {
+        let hashes: BTreeMap<i64, String> = blocks
+            .iter()
+            .filter_map(|b| Some((b.number? as i64, b.hash.as_ref()?.encode_hex())))
+            .collect();
+        for item in items
+            .iter_mut()
+            .filter(|item| item.log.block_hash.is_empty())
+        {
+            item.log.block_hash = hashes
+                .get(&item.log.block_number)
+                .cloned()
+                .context("block.hash missing")
+                .map_err(RpcError::Other)?;
+        }
+    }
+    block_store.insert_evm_blocks(blocks);
+
+    if transaction_fields.is_empty() {
+        return Ok(());
+    }
+    // Many logs share a transaction; key by the store key so each is fetched
+    // once.
+    let mut tx_refs: BTreeMap<(i64, i64), TxRef> = BTreeMap::new();
+    for item in items.iter() {
+        let log = &item.log;
+        let key = (log.block_number, log.transaction_index);
+        if tx_refs.contains_key(&key) {
+            continue;
+        }
+        let hash = Hash::decode_hex(&log.transaction_hash)
+            .context("decode log.transactionHash")
+            .map_err(RpcError::Other)?;
+        let block_hash = Hash::decode_hex(&log.block_hash)
+            .context("decode log.blockHash")
+            .map_err(RpcError::Other)?;
+        tx_refs.insert(
+            key,
+            TxRef {
+                block_number: log.block_number as u64,
+                transaction_index: log.transaction_index as u64,
+                hash,
+                block_hash,
+            },
+        );
+    }
+    let hash_params =
+        || -> Vec<serde_json::Value> { tx_refs.values().map(|r| json!([r.hash])).collect() };
+
+    let mut txs: Vec<Option<RpcTransaction>> = if plan.transactions {
+        fetch_all(client, "eth_getTransactionByHash", hash_params(), stats)
+            .await?
+            .into_iter()
+            .map(Some)
+            .collect()
+    } else {
+        tx_refs.values().map(|_| None).collect()
+    };
+    let receipts: Vec<Option<RpcReceipt>> = if plan.receipts {
+        fetch_all(client, "eth_getTransactionReceipt", hash_params(), stats)
+            .await?
+            .into_iter()
+            .map(Some)
+            .collect()
+    } else {
+        tx_refs.values().map(|_| None).collect()
+    };
+
+    // The `gasPrice` fallback for a receipt without `effectiveGasPrice` needs
+    // the transaction; fetch it only for the receipts that lack the field.
+    let needs_effective_gas_price =
+        transaction_fields.contains(&TransactionField::EffectiveGasPrice);
+    if needs_effective_gas_price && !plan.transactions {
+        let lacking: Vec<usize> = receipts
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.as_ref().is_some_and(|r| r.effective_gas_price.is_none()))
+            .map(|(i, _)| i)
+            .collect();
+        if !lacking.is_empty() {
+            let refs: Vec<&TxRef> = tx_refs.values().collect();
+            let fetched: Vec<RpcTransaction> = fetch_all(
+                client,
+                "eth_getTransactionByHash",
+                lacking.iter().map(|&i| json!([refs[i].hash])).collect(),
+                stats,
+            )
+            .await?;
+            for (i, tx) in lacking.into_iter().zip(fetched) {
+                txs[i] = Some(tx);
+            }
+        }
+    }
+
+    let rows = tx_refs
+        .values()
+        .zip(txs)
+        .zip(receipts)
+        .map(|((tx_ref, tx), receipt)| {
+            assemble_transaction(tx_ref, tx, receipt, needs_effective_gas_price)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(RpcError::Other)?;
+    if !transaction_wheres.is_empty() {
+        let by_key: BTreeMap<&(i64, i64), &simple_types::Transaction> =
+            tx_refs.keys().zip(rows.iter().map(|(tx, _)| tx)).collect();
+        items.retain(|item| {
+            let key = (item.log.block_number, item.log.transaction_index);
+            transaction_wheres.accepts(item.on_event_registration_index, by_key.get(&key).copied())
+        });
+    }
+    transaction_store.insert_evm_rpc_txs(rows);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_fetches_only_the_responses_the_fields_need() {
+        use TransactionField::*;
+        let plans = (
+            TxPlan::new(&[]),
+            TxPlan::new(&[Hash, TransactionIndex]),
+            TxPlan::new(&[Input]),
+            TxPlan::new(&[GasUsed, Status]),
+            TxPlan::new(&[From, To]),
+            TxPlan::new(&[From, GasUsed]),
+            TxPlan::new(&[Value, Status]),
+        );
+        let t = |transactions, receipts| TxPlan {
+            transactions,
+            receipts,
+        };
+        assert_eq!(
+            plans,
+            (
+                t(false, false),
+                t(false, false),
+                t(true, false),
+                t(false, true),
+                t(true, false),
+                t(false, true),
+                t(true, true),
+            )
+        );
+    }
+
+    #[test]
+    fn block_converts_from_rpc_json() {
+        let raw: RpcBlock = serde_json::from_value(json!({
+            "number": "0x10",
+            "hash": format!("0x{}", "ab".repeat(32)),
+            "parentHash": format!("0x{}", "cd".repeat(32)),
+            "timestamp": "0x5f5e100",
+            "nonce": "0x0",
+            "miner": format!("0x{}", "11".repeat(20)),
+            "l1BlockNumber": "0x7",
+        }))
+        .unwrap();
+        let block = raw.into_simple().unwrap();
+        assert_eq!(block.number, Some(16));
+        assert_eq!(block.timestamp, Some(Quantity::from(100_000_000u64)));
+        assert_eq!(
+            block.nonce.as_ref().map(|n| n.as_ref().to_vec()),
+            Some(vec![0; 8])
+        );
+        assert_eq!(block.l1_block_number, Some(UInt::from(7u64)));
+        assert!(block.base_fee_per_gas.is_none());
+    }
+
+    #[test]
+    fn transaction_merges_log_key_transaction_and_receipt() {
+        let tx_ref = TxRef {
+            block_number: 5,
+            transaction_index: 2,
+            hash: Hash::from([1u8; 32]),
+            block_hash: Hash::from([2u8; 32]),
+        };
+        let tx: RpcTransaction = serde_json::from_value(json!({
+            "from": format!("0x{}", "aa".repeat(20)),
+            "gasPrice": "0x64",
+            "input": "0xa9059cbb0000",
+            "value": "0x1",
+        }))
+        .unwrap();
+        let receipt: RpcReceipt = serde_json::from_value(json!({
+            "to": format!("0x{}", "bb".repeat(20)),
+            "gasUsed": "0x5208",
+            "status": "0x0",
+            "l1FeeScalar": "0.684",
+        }))
+        .unwrap();
+        let (row, _) = assemble_transaction(&tx_ref, Some(tx), Some(receipt), true).unwrap();
+        assert_eq!(row.block_number, Some(UInt::from(5u64)));
+        assert_eq!(row.transaction_index, Some(UInt::from(2u64)));
+        assert_eq!(row.hash, Some(Hash::from([1u8; 32])));
+        assert_eq!(row.from, Some(Address::from([0xaa; 20])));
+        assert_eq!(row.to, Some(Address::from([0xbb; 20])));
+        assert_eq!(
+            row.sighash.map(|s| s.as_ref().to_vec()),
+            Some(vec![0xa9, 0x05, 0x9c, 0xbb])
+        );
+        assert_eq!(row.status, Some(TransactionStatus::Failure));
+        assert_eq!(row.l1_fee_scalar, Some(0.684));
+        // No `effectiveGasPrice` on the receipt: the legacy `gasPrice` stands in.
+        assert_eq!(row.effective_gas_price, Some(Quantity::from(100u64)));
+    }
+
+    #[test]
+    fn transaction_reads_deposit_and_batch_fields() {
+        let tx_ref = TxRef {
+            block_number: 9,
+            transaction_index: 0,
+            hash: Hash::from([3u8; 32]),
+            block_hash: Hash::from([4u8; 32]),
+        };
+        let tx: RpcTransaction = serde_json::from_value(json!({
+            "sourceHash": format!("0x{}", "cc".repeat(32)),
+            "mint": "0xde0b6b3a7640000",
+            "isSystemTx": false,
+            "l1BatchNumber": "0x2a",
+        }))
+        .unwrap();
+        let receipt: RpcReceipt = serde_json::from_value(json!({
+            "l1BaseFeeScalar": "0x558",
+            "l1BlobBaseFee": "0x1",
+            "l1BlobBaseFeeScalar": "0xc5fc5",
+            "operatorFeeScalar": "0x0",
+            "l1BatchNumber": "0x2a",
+            "l1BatchTxIndex": "0x3",
+        }))
+        .unwrap();
+        let (row, extras) = assemble_transaction(&tx_ref, Some(tx), Some(receipt), false).unwrap();
+        assert_eq!(row.source_hash, Some(Hash::from([0xcc; 32])));
+        assert_eq!(row.mint, Some(Quantity::from(1_000_000_000_000_000_000u64)));
+        assert_eq!(row.l1_base_fee_scalar, Some(Quantity::from(0x558u64)));
+        assert_eq!(
+            row.l1_blob_base_fee_scalar,
+            Some(Quantity::from(0xc5fc5u64))
+        );
+        assert_eq!(
+            (
+                extras.is_system_tx,
+                extras.operator_fee_scalar,
+                extras.l1_batch_number,
+                extras.l1_batch_tx_index,
+            ),
+            (Some(false), Some(Quantity::from(0u64)), Some(42), Some(3))
+        );
+    }
+
+    fn tx_ref() -> TxRef {
+        TxRef {
+            block_number: 0x1e240,
+            transaction_index: 1,
+            hash: Hash::from([0xab; 32]),
+            block_hash: Hash::from([0xcd; 32]),
+        }
+    }
+
+    #[test]
+    fn transaction_without_responses_is_read_off_the_log() {
+        // `hash` and `transactionIndex` alone plan no request at all.
+        assert_eq!(
+            TxPlan::new(&[TransactionField::Hash, TransactionField::TransactionIndex]),
+            TxPlan::default()
+        );
+        let (row, extras) = assemble_transaction(&tx_ref(), None, None, false).unwrap();
+        assert_eq!(row.hash, Some(Hash::from([0xab; 32])));
+        assert_eq!(row.transaction_index, Some(UInt::from(1u64)));
+        assert_eq!(row.block_number, Some(UInt::from(0x1e240u64)));
+        assert!(row.from.is_none() && row.gas_used.is_none());
+        assert!(extras.is_system_tx.is_none() && extras.l1_batch_number.is_none());
+    }
+
+    #[test]
+    fn receipt_fields_need_no_transaction() {
+        let receipt: RpcReceipt = serde_json::from_value(json!({
+            "gasUsed": "0x5208",
+            "cumulativeGasUsed": "0xa410",
+            "effectiveGasPrice": "0x3b9aca00",
+        }))
+        .unwrap();
+        let (row, _) = assemble_transaction(&tx_ref(), None, Some(receipt), false).unwrap();
+        assert_eq!(row.gas_used, Some(Quantity::from(0x5208u64)));
+        assert_eq!(row.cumulative_gas_used, Some(Quantity::from(0xa410u64)));
+        assert_eq!(
+            row.effective_gas_price,
+            Some(Quantity::from(1_000_000_000u64))
+        );
+        assert!(row.gas_price.is_none() && row.input.is_none());
+    }
+
+    #[test]
+    fn rpc_json_tolerates_unknown_and_null_fields() {
+        let block: RpcBlock = serde_json::from_value(json!({
+            "number": "0x1e240",
+            "timestamp": "0x5f5e100",
+            "baseFeePerGas": null,
+            "someChainSpecificField": {"nested": true},
+        }))
+        .unwrap();
+        let block = block.into_simple().unwrap();
+        assert_eq!(block.number, Some(0x1e240));
+        assert!(block.base_fee_per_gas.is_none());
+
+        let tx: RpcTransaction = serde_json::from_value(json!({
+            "gasPrice": "0x64",
+            "to": null,
+            "yetAnotherField": "0x1",
+        }))
+        .unwrap();
+        let receipt: RpcReceipt = serde_json::from_value(json!({
+            "status": "0x1",
+            "logs": [],
+        }))
+        .unwrap();
+        let (row, _) = assemble_transaction(&tx_ref(), Some(tx), Some(receipt), false).unwrap();
+        assert_eq!(row.gas_price, Some(Quantity::from(100u64)));
+        assert!(row.to.is_none());
+        assert_eq!(row.status, Some(TransactionStatus::Success));
+    }
+
+    #[test]
+    fn rpc_json_with_malformed_values_fails() {
+        assert!(serde_json::from_value::<RpcBlock>(json!({
+            "number": "0x1",
+            "timestamp": "not a quantity",
+        }))
+        .is_err());
+        assert!(serde_json::from_value::<RpcTransaction>(json!({
+            "from": "not an address",
+        }))
+        .is_err());
+        // A block without its number can't be keyed in the store.
+        let numberless: RpcBlock = serde_json::from_value(json!({"timestamp": "0x1"})).unwrap();
+        assert!(numberless.into_simple().is_err());
+        // l1FeeScalar is a decimal string, not hex.
+        let receipt: RpcReceipt = serde_json::from_value(json!({"l1FeeScalar": "0x1g"})).unwrap();
+        assert!(assemble_transaction(&tx_ref(), None, Some(receipt), false).is_err());
+    }
+
+    #[test]
+    fn null_results_are_not_found_with_what_was_missing() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let not_found = |method, params: serde_json::Value| match require_found::<u64>(
+            method,
+            &[json!(1), params],
+            vec![Ok(Some(1)), Ok(None)],
+        ) {
+            Err(RpcError::NotFound { message }) => message,
+            other => panic!("expected NotFound, got {other:?}"),
+        };
+        assert_eq!(
+            not_found("eth_getBlockByNumber", json!(["0x64", false])),
+            "Block not found for number: 100"
+        );
+        assert_eq!(
+            not_found("eth_getTransactionByHash", json!([hash])),
+            format!("Transaction not found for hash: {hash}")
+        );
+        assert_eq!(
+            not_found("eth_getTransactionReceipt", json!([hash])),
+            format!("Transaction receipt not found for hash: {hash}")
+        );
+
+        // Provider errors pass through untouched; every call found is Ok.
+        assert!(matches!(
+            require_found::<u64>(
+                "eth_getTransactionReceipt",
+                &[json!([hash])],
+                vec![Err(RpcError::JsonRpc {
+                    code: -32000,
+                    message: "boom".to_string()
+                })]
+            ),
+            Err(RpcError::JsonRpc { .. })
+        ));
+        assert_eq!(
+            require_found(
+                "eth_getBlockByNumber",
+                &[json!(["0x1"])],
+                vec![Ok(Some(7u64))]
+            )
+            .unwrap(),
+            vec![7]
+        );
+    }
+
+    #[test]
+    fn nonce_left_pads_and_rejects_overflow() {
+        assert_eq!(
+            left_pad::<8>(&[0x01, 0x02]).map(|n| n.as_ref().to_vec()),
+            Some(vec![0, 0, 0, 0, 0, 0, 1, 2])
+        );
+        assert!(left_pad::<8>(&[1; 9]).is_none());
+        assert_eq!(
+            left_pad::<8>(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 7]).map(|n| n.as_ref().to_vec()),
+            Some(vec![0, 0, 0, 0, 0, 0, 0, 7])
+        );
+    }
+
+    #[test]
+    fn effective_gas_price_falls_back_to_gas_price_or_fails() {
+        let receipt = || -> RpcReceipt {
+            serde_json::from_value(json!({"gasUsed": "0x26aed", "status": "0x1"})).unwrap()
+        };
+        let tx: RpcTransaction = serde_json::from_value(json!({"gasPrice": "0xf4240"})).unwrap();
+        let (row, _) = assemble_transaction(&tx_ref(), Some(tx), Some(receipt()), true).unwrap();
+        assert_eq!(
+            (row.effective_gas_price, row.gas_used, row.status),
+            (
+                Some(Quantity::from(1_000_000u64)),
+                Some(Quantity::from(158_445u64)),
+                Some(TransactionStatus::Success)
+            )
+        );
+
+        // Neither price on either response: an explicit error, not a silently
+        // missing field.
+        let err = assemble_transaction(&tx_ref(), None, Some(receipt()), true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Neither \"effectiveGasPrice\" nor \"gasPrice\" is present in the RPC response for \
+             the transaction. Remove \"effectiveGasPrice\" from the field selection, or index \
+             this chain via HyperSync."
+        );
+        // Unselected, the missing price is no concern of the page.
+        let (row, _) = assemble_transaction(&tx_ref(), None, Some(receipt()), false).unwrap();
+        assert!(row.effective_gas_price.is_none());
+    }
+
+    #[test]
+    fn contract_creation_has_no_to() {
+        let creator = format!("0x{}", "aa".repeat(20));
+        let tx: RpcTransaction = serde_json::from_value(json!({
+            "from": creator,
+            "to": null,
+            "input": "0x6080",
+        }))
+        .unwrap();
+        let receipt: RpcReceipt = serde_json::from_value(json!({
+            "from": creator,
+            "to": null,
+            "contractAddress": format!("0x{}", "cc".repeat(20)),
+        }))
+        .unwrap();
+        let (row, _) = assemble_transaction(&tx_ref(), Some(tx), Some(receipt), false).unwrap();
+        assert!(row.to.is_none());
+        assert_eq!(row.from, Some(Address::from([0xaa; 20])));
+        assert_eq!(row.contract_address, Some(Address::from([0xcc; 20])));
+        // Two bytes of input are too few for a sighash.
+        assert!(row.sighash.is_none());
+    }
+
+    #[test]
+    fn transaction_fields_skip_the_receipt_and_ignore_extra_fields() {
+        use TransactionField::*;
+        assert_eq!(
+            TxPlan::new(&[Gas, Input, Nonce]),
+            TxPlan {
+                transactions: true,
+                receipts: false
+            }
+        );
+        let tx: RpcTransaction = serde_json::from_value(json!({
+            "gas": "0x5208",
+            "nonce": "0x2a",
+            "input": "0x",
+            "unknownField": "some value",
+            "anotherUnknown": 42,
+            "nested": {"deep": [1, 2]},
+        }))
+        .unwrap();
+        let (row, _) = assemble_transaction(&tx_ref(), Some(tx), None, false).unwrap();
+        assert_eq!(
+            (row.gas, row.nonce, row.gas_used),
+            (
+                Some(Quantity::from(21_000u64)),
+                Some(Quantity::from(42u64)),
+                None
+            )
+        );
+        assert!(
+            serde_json::from_value::<RpcTransaction>(json!({"gas": "not-a-hex-value"})).is_err()
+        );
+        assert!(serde_json::from_value::<RpcBlock>(json!({"gasUsed": "not-a-hex-value"})).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rpc_addresses_render_lowercase_or_checksummed() {
+        use crate::block_store::EvmBlockField;
+        use crate::field_columns::js_masks;
+        use crate::field_columns::test_support::str_column;
+        use crate::transaction_store::EvmTxField;
+
+        let from = "0x95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5";
+        let contract = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let tx_ref = tx_ref();
+        let block_number = tx_ref.block_number as i64;
+        let tx_mask =
+            (1u64 << EvmTxField::From as u32) | (1u64 << EvmTxField::ContractAddress as u32);
+        let block_mask = 1u64 << EvmBlockField::Miner as u32;
+
+        let mut rendered = Vec::new();
+        for should_checksum in [false, true] {
+            let tx: RpcTransaction = serde_json::from_value(json!({"from": from})).unwrap();
+            let receipt: RpcReceipt =
+                serde_json::from_value(json!({"contractAddress": contract})).unwrap();
+            let row = assemble_transaction(&tx_ref, Some(tx), Some(receipt), false).unwrap();
+            let txs = TransactionStore::new_evm(should_checksum);
+            txs.insert_evm_rpc_txs(vec![row]);
+            let cols = txs
+                .materialize(vec![block_number], vec![1], js_masks(&[tx_mask]))
+                .await
+                .unwrap();
+
+            let block: RpcBlock = serde_json::from_value(json!({
+                "number": "0x1e240",
+                "miner": from,
+            }))
+            .unwrap();
+            let blocks = BlockStore::new_evm(should_checksum);
+            blocks.insert_evm_blocks(vec![block.into_simple().unwrap()]);
+            let block_cols = blocks
+                .materialize(vec![block_number], js_masks(&[block_mask]))
+                .await
+                .unwrap();
+
+            rendered.push((
+                str_column(&cols, "from"),
+                str_column(&cols, "contractAddress"),
+                str_column(&block_cols, "miner"),
+            ));
+        }
+        let one = |s: &str| vec![Some(s.to_string())];
+        assert_eq!(
+            rendered,
+            vec![
+                (
+                    one(&from.to_lowercase()),
+                    one(&contract.to_lowercase()),
+                    one(&from.to_lowercase())
+                ),
+                (one(from), one(contract), one(from)),
+            ]
+        );
+    }
+
+    /// `eth_getBlockByNumber` carries every block field the config can select,
+    /// including those with no HyperSync equivalent. Pinned against the real
+    /// mainnet response for block 21758655, trimmed of its `transactions`,
+    /// `withdrawals` and `logsBloom` payloads.
+    #[test]
+    fn block_reads_every_selectable_field_off_a_mainnet_response() {
+        let raw: RpcBlock = serde_json::from_value(json!({
+            "baseFeePerGas": "0xc0f55feb",
+            "blobGasUsed": "0xc0000",
+            "excessBlobGas": "0x3e00000",
+            "parentBeaconBlockRoot": "0xaf040950a84627a7cc2dd0884a830df31986bf0a8672da50e5f8560f9cfa1233",
+            "withdrawalsRoot": "0x8cdf00ffc923f4bedfe91ec276c860451f98c8cdcfe8e2cee54fdd6c7a521009",
+            "mixHash": "0xfea0481418a65cc4b9e4c5d5960f639aa5d9b649474bf7231ce8105d63570f74",
+            "difficulty": "0x0",
+            "extraData": "0x546974616e2028746974616e6275696c6465722e78797a29",
+            "gasLimit": "0x1cf2651",
+            "gasUsed": "0x1686262",
+            "hash": "0x806a18dd9f7bb88e35e08658783c556974ea46a222f1f85a0bccb1da31bbde5f",
+            "miner": "0x4838b106fce9647bdf1e7877bf73ce8b0bad5f97",
+            "nonce": "0x0000000000000000",
+            "number": "0x14c02bf",
+            "parentHash": "0x58ebb0c939bed8e69d7e3519f579b028338613050986d0a3e8770de2c7ec2949",
+            "receiptsRoot": "0x9d845c74774f03ed99d16af07747dbfe7d1825392360b901ed1d72087819cbcf",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size": "0x1be72",
+            "stateRoot": "0x26174afbfa3756ef8ef51ec7b10b74b857531bdeb4b96dd217b819c6a84b4449",
+            "timestamp": "0x679f5ccb",
+            "totalDifficulty": "0xc70d815d562d3cfa955",
+            "transactionsRoot": "0x4adb6fa9a977f828f28575406e90b48a636920f8fa05877aa5aae7cbc3afb7c6",
+            "uncles": [],
+            "transactions": ["0x123"],
+        }))
+        .unwrap();
+        let block = raw.into_simple().unwrap();
+        let qty = |v: &Option<Quantity>| {
+            v.as_ref().map(|q| {
+                q.as_ref()
+                    .iter()
+                    .fold(0u128, |n, &b| n << 8 | u128::from(b))
+            })
+        };
+        assert_eq!(
+            (
+                block.number,
+                qty(&block.timestamp),
+                qty(&block.gas_used),
+                qty(&block.gas_limit),
+                qty(&block.base_fee_per_gas),
+                qty(&block.size),
+                qty(&block.blob_gas_used),
+                qty(&block.excess_blob_gas),
+                qty(&block.total_difficulty),
+                qty(&block.difficulty),
+            ),
+            (
+                Some(21_758_655),
+                Some(1_738_497_227),
+                Some(23_618_146),
+                Some(30_352_977),
+                Some(3_237_306_347),
+                Some(114_290),
+                Some(786_432),
+                Some(65_011_712),
+                Some(58_750_003_716_598_352_816_469),
+                Some(0),
+            )
+        );
+        assert_eq!(
+            [
+                block.sha3_uncles.as_ref().map(Hex::encode_hex),
+                block.transactions_root.as_ref().map(Hex::encode_hex),
+                block.receipts_root.as_ref().map(Hex::encode_hex),
+                block.mix_hash.as_ref().map(Hex::encode_hex),
+                block.withdrawals_root.as_ref().map(Hex::encode_hex),
+                block.parent_beacon_block_root.as_ref().map(Hex::encode_hex),
+            ],
+            [
+                "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                "0x4adb6fa9a977f828f28575406e90b48a636920f8fa05877aa5aae7cbc3afb7c6",
+                "0x9d845c74774f03ed99d16af07747dbfe7d1825392360b901ed1d72087819cbcf",
+                "0xfea0481418a65cc4b9e4c5d5960f639aa5d9b649474bf7231ce8105d63570f74",
+                "0x8cdf00ffc923f4bedfe91ec276c860451f98c8cdcfe8e2cee54fdd6c7a521009",
+                "0xaf040950a84627a7cc2dd0884a830df31986bf0a8672da50e5f8560f9cfa1233",
+            ]
+            .map(|s| Some(s.to_string()))
+        );
+        assert_eq!(block.uncles, Some(vec![]));
+    }
+}
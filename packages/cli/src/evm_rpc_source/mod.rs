@@ -3,27 +3,45 @@ use napi_derive::napi;
 use serde::Deserialize;
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
-use std::time::{Duration, Instant};
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
 
 mod classify;
 mod client;
+mod fields;
 mod interval;
+mod pool;
+mod traces;
+mod transactions;
+mod ws;
 
-use crate::address_store::{AddressSet, AddressStore, SetCache};
-use crate::evm_hypersync_source::decode::{Decoder, LogAddress, SelectionDecoder};
-use crate::evm_hypersync_source::selection::{BuiltLogSelection, SelectionBuilder};
+use crate::address_store::{AddressSet, AddressStore, RegistrationPage, SetCache};
+use crate::block_store::BlockStore;
+use crate::evm_hypersync_source::decode::{CallSource, Decoder, LogAddress, SelectionDecoder};
+use crate::evm_hypersync_source::selection::{BuiltLogSelection, BuiltSelection, SelectionBuilder};
 use crate::evm_hypersync_source::types::{
     encode_address, Log as DecoderLog, OnEventRegistrationInput, ParamValue,
 };
 use crate::request_stats::RequestStat;
+use crate::transaction_store::TransactionStore;
 use classify::{is_response_too_large_message, suggested_block_interval_from_message};
 use client::{parse_hex_u64, JsonRpcClient, RpcError};
+use fields::TxPlan;
 use hypersync_client::format::Hex;
 use interval::{IntervalState, SyncConfig};
+use pool::ProviderPool;
 
 #[napi(object)]
 pub struct EvmRpcClientConfig {
     pub url: String,
+    /// Further endpoints for the same source. Requests route to whichever
+    /// endpoint is healthiest (see `pool`); all share `headers`.
+    pub additional_urls: Option<Vec<String>>,
+    /// Hedge a request to the runner-up endpoint once it has been in flight
+    /// this long. Only applies with `additional_urls`.
+    pub hedge_after_millis: Option<i64>,
+    /// WebSocket endpoint for `subscribeHeads`; sent the same `headers`.
+    pub ws: Option<String>,
     pub http_req_timeout_millis: Option<i64>,
     pub headers: Option<HashMap<String, String>>,
     // Sync-tuning knobs for the paging AIMD state (see `interval::SyncConfig`).
@@ -35,11 +53,14 @@ pub struct EvmRpcClientConfig {
     pub interval_ceiling: i64,
     pub backoff_millis: i64,
     pub query_timeout_millis: i64,
+    /// Most calls packed into one JSON-RPC batch payload; 1 disables batching.
+    pub max_batch_size: i64,
 }
 
 /// A log returned from `eth_getLogs`, with hex quantities decoded to integers.
 /// Field names cross the napi boundary as camelCase, matching the ReScript
-/// `Rpc.GetLogs.log` record.
+/// `Rpc.GetLogs.log` record. A call or transaction item reuses it: `address`
+/// is the callee, `topics` is empty and `log_index` is the transaction index.
 // Only the fields the ReScript side reads cross the boundary. `data` is consumed
 // by the decoder on the Rust side (see `to_decoder_log`) and `removed` is unused,
 // so neither is carried here.
@@ -62,6 +83,9 @@ pub struct RpcEventItem {
     /// constructor. Logs that route nowhere are dropped before the boundary.
     pub on_event_registration_index: i64,
     pub params: ParamValue,
+    /// Set on call and transaction items only: the call's position in its
+    /// transaction's call tree, empty for the transaction itself.
+    pub trace_address: Option<Vec<i64>>,
 }
 
 /// Raw `eth_getLogs` entry as the provider serialises it: integer fields are
@@ -146,11 +170,17 @@ pub struct NextPageResponse {
 
 #[napi]
 pub struct EvmRpcClient {
-    inner: JsonRpcClient,
+    inner: ProviderPool,
+    ws: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    checksum_addresses: bool,
     decoder: Decoder,
     selection_builder: SelectionBuilder,
     sync_config: SyncConfig,
     intervals: IntervalState,
+    /// Set once the provider rejects `trace_filter`; calls are then read with
+    /// `debug_traceBlockByNumber` for the rest of the run.
+    trace_filter_unsupported: AtomicBool,
 }
 
 #[napi]
@@ -168,8 +198,31 @@ impl EvmRpcClient {
             .map_or(JsonRpcClient::default_http_req_timeout_millis(), |v| {
                 v as u64
             });
-        let inner =
-            JsonRpcClient::new(cfg.url, http_req_timeout_millis, cfg.headers).map_err(map_err)?;
+        let max_batch_size = usize::try_from(cfg.max_batch_size)
+            .context("maxBatchSize must be non-negative")
+            .map_err(map_err)?;
+        let inner = std::iter::once(cfg.url)
+            .chain(cfg.additional_urls.unwrap_or_default())
+            .map(|url| {
+                JsonRpcClient::new(
+                    url,
+                    http_req_timeout_millis,
+                    cfg.headers.clone(),
+                    max_batch_size,
+                )
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(map_err)?;
+        let hedge_after = cfg
+            .hedge_after_millis
+            .map(|v| {
+                u64::try_from(v)
+                    .context("hedgeAfterMillis must be non-negative")
+                    .map(Duration::from_millis)
+            })
+            .transpose()
+            .map_err(map_err)?;
+        let inner = ProviderPool::new(inner, hedge_after);
         let decoder =
             Decoder::from_registrations(&event_registrations, checksum_addresses, address_store)
                 .context("build decoder")
@@ -211,10 +264,14 @@ impl EvmRpcClient {
         };
         Ok(EvmRpcClient {
             inner,
+            ws: cfg.ws,
+            headers: cfg.headers,
+            checksum_addresses,
             decoder,
             selection_builder,
             sync_config,
             intervals: IntervalState::new(),
+            trace_filter_unsupported: AtomicBool::new(false),
         })
     }
 
@@ -227,19 +284,45 @@ impl EvmRpcClient {
             .map_err(map_err)
     }
 
+    /// Streams new heads from the configured `ws` endpoint (see `ws`) until
+    /// `unsubscribe` is called or the stream gives up after repeated failures.
+    #[napi(ts_args_type = "onHead: (height: number, blockStore: BlockStore) => void")]
+    pub fn subscribe_heads(&self, on_head: ws::OnHead) -> napi::Result<ws::HeadSubscription> {
+        let url = self
+            .ws
+            .clone()
+            .context("no WebSocket endpoint configured for this RPC")
+            .map_err(map_err)?;
+        Ok(ws::subscribe(
+            ws::HeadStreamConfig {
+                url,
+                headers: self.headers.clone(),
+                checksum_addresses: self.checksum_addresses,
+            },
+            on_head,
+        ))
+    }
+
     /// Decides the actual `toBlock` from this partition's AIMD-suggested
     /// interval, fans out one `eth_getLogs` per selection, dedups the merged
-    /// results by `(blockNumber, logIndex)`, and races the whole thing against
-    /// `queryTimeoutMillis`. On success, grows the partition's interval when
-    /// the full suggested range was applied. On failure (timeout, RPC error,
-    /// or a "too many logs" style response), shrinks/backs off and throws a
-    /// structured retry decision (see `retry_decision_to_napi`).
+    /// results by `(blockNumber, logIndex)`, fetches the blocks and
+    /// transactions the routed items join to into the page stores, and races
+    /// the whole thing against `queryTimeoutMillis`. On success, grows the
+    /// partition's interval when the full suggested range was applied. On
+    /// failure (timeout, RPC error, or a "too many logs" style response),
+    /// shrinks/backs off and throws a structured retry decision (see
+    /// `retry_decision_to_napi`).
     #[napi]
     pub async fn get_next_page(
         &self,
         params: NextPageParams,
         address_set: &AddressSet,
-    ) -> napi::Result<NextPageResponse> {
+    ) -> napi::Result<(
+        NextPageResponse,
+        TransactionStore,
+        BlockStore,
+        RegistrationPage,
+    )> {
         if params.from_block < 0 || params.to_block_ceiling < 0 {
             return Err(map_err(anyhow::anyhow!(
                 "block bounds must be non-negative, got from_block={}, to_block_ceiling={}",
@@ -270,7 +353,8 @@ impl EvmRpcClient {
             .selection_builder
             .build(&params.registration_indexes, address_set, &client_filtered)
             .map_err(map_err)?;
-        let log_selections = built.log_selections;
+        let transaction_fields = built.transaction_fields.clone();
+        let tx_plan = TxPlan::new(&transaction_fields);
         let set_cache = address_set.cache().clone();
         let selection_decoder = std::sync::Arc::new(
             self.decoder
@@ -281,17 +365,34 @@ impl EvmRpcClient {
                 )
                 .map_err(map_err)?,
         );
+        let transaction_store = TransactionStore::new_evm(self.checksum_addresses);
+        let block_store = BlockStore::new_evm(self.checksum_addresses);
         let timeout = Duration::from_millis(self.sync_config.query_timeout_millis);
-        let page_result = tokio::time::timeout(
-            timeout,
-            self.fetch_page(
-                from_block,
-                to_block,
-                &log_selections,
-                &set_cache,
-                &selection_decoder,
-            ),
-        )
+        let page_result = tokio::time::timeout(timeout, async {
+            let (mut items, mut request_stats) = self
+                .fetch_page(from_block, to_block, &built, &set_cache, &selection_decoder)
+                .await?;
+            let join_result = fields::fetch_joins(
+                &self.inner,
+                &mut items,
+                tx_plan,
+                &transaction_fields,
+                &built.transaction_wheres,
+                &transaction_store,
+                &block_store,
+            )
+            .await;
+            match join_result {
+                Ok(join_stats) => {
+                    request_stats.extend(join_stats);
+                    Ok((items, request_stats))
+                }
+                Err((e, join_stats)) => {
+                    request_stats.extend(join_stats);
+                    Err((e, request_stats))
+                }
+            }
+        })
         .await;
 
         match page_result {
@@ -309,16 +410,35 @@ impl EvmRpcClient {
                         source_max,
                     );
                 }
-                Ok(NextPageResponse {
-                    items,
-                    to_block: to_block as i64,
-                    request_stats,
-                })
+                let registration_page = RegistrationPage::new(selection_decoder.registrations(
+                    items.iter().map(|item| {
+                        (
+                            item.on_event_registration_index,
+                            item.log.block_number,
+                            &item.params,
+                        )
+                    }),
+                ));
+                Ok((
+                    NextPageResponse {
+                        items,
+                        to_block: to_block as i64,
+                        request_stats,
+                    },
+                    transaction_store,
+                    block_store,
+                    registration_page,
+                ))
+            }
+            // The range isn't at fault: retry it whole once the lagging node
+            // catches up.
+            Ok(Err((RpcError::NotFound { message }, request_stats))) => {
+                Err(data_not_found_to_napi(to_block, &message, request_stats))
             }
             Ok(Err((rpc_err, request_stats))) => {
                 let message = match &rpc_err {
                     RpcError::JsonRpc { message, .. } => Some(message.as_str()),
-                    RpcError::Other(_) => None,
+                    RpcError::NotFound { .. } | RpcError::Other(_) => None,
                 };
                 Err(self.retry_error(
                     &params.partition_id,
@@ -399,156 +519,359 @@ impl EvmRpcClient {
         retry_decision_to_napi(to_block, message, retry, request_stats)
     }
 
-    /// Fans out one `eth_getLogs` per selection concurrently, deduping the
-    /// merged results by `(blockNumber, logIndex, registrationIndex)` — a log
-    /// can satisfy more than one selection (an event's `where` OR-groups, or
-    /// several registrations sharing a signature) and routing fans one log out
-    /// to several registrations, so only exact repeats are dropped. Waits for
-    /// every selection to settle (unlike `Promise.all`'s fail-fast) so every
-    /// request's timing is still captured for `requestStats` even when one of
-    /// them errors.
+    /// Sends one `eth_getLogs` per selection as a single JSON-RPC batch,
+    /// deduping the merged results by `(blockNumber, logIndex,
+    /// registrationIndex)` — a log can satisfy more than one selection (an
+    /// event's `where` OR-groups, or several registrations sharing a
+    /// signature) and routing fans one log out to several registrations, so
+    /// only exact repeats are dropped. Every HTTP request's timing is captured
+    /// for `requestStats` even when one of the calls errors. Call
+    /// registrations' calls are read from traces (see `traces`) and
+    /// transaction registrations' from blocks (see `transactions`) after the
+    /// logs, each alongside the contract creations of factory registrations
+    /// with the same source.
     async fn fetch_page(
         &self,
         from_block: u64,
         to_block: u64,
-        selections: &[BuiltLogSelection],
+        built: &BuiltSelection,
         set_cache: &std::sync::Arc<SetCache>,
         selection_decoder: &std::sync::Arc<SelectionDecoder>,
     ) -> Result<(Vec<RpcEventItem>, Vec<RequestStat>), (RpcError, Vec<RequestStat>)> {
-        if selections.is_empty() {
-            return Ok((Vec::new(), Vec::new()));
-        }
-
-        let results = futures_util::future::join_all(selections.iter().map(|selection| async {
-            let started = Instant::now();
-            let result = self
-                .fetch_logs_raw(
-                    from_block as i64,
-                    to_block as i64,
-                    selection,
+        let (mut items, mut stats) = self
+            .fetch_logs(
+                from_block,
+                to_block,
+                &built.log_selections,
+                set_cache,
+                selection_decoder,
+            )
+            .await?;
+        for source in [CallSource::Trace, CallSource::Transaction] {
+            let fetched = match source {
+                CallSource::Trace => traces::fetch_traces(
+                    &self.inner,
+                    from_block,
+                    to_block,
+                    &built.trace_selections,
+                    &built.creation_selections,
+                    &self.trace_filter_unsupported,
+                    &mut stats,
+                )
+                .await
+                .map(|traced| (traced.calls, traced.creations)),
+                CallSource::Transaction => {
+                    transactions::fetch_transactions(
+                        &self.inner,
+                        from_block,
+                        to_block,
+                        &built.transaction_selections,
+                        &built.deploy_selections,
+                        &mut stats,
+                    )
+                    .await
+                }
+            };
+            let (calls, creations) = match fetched {
+                Ok(fetched) => fetched,
+                Err(e) => return Err((e, stats)),
+            };
+            if !calls.is_empty() {
+                match decode_calls(source, calls, set_cache.clone(), selection_decoder.clone())
+                    .await
+                {
+                    Ok(call_items) => items.extend(call_items),
+                    Err(e) => return Err((e, stats)),
+                }
+            }
+            if !creations.is_empty() {
+                match decode_creations(
+                    source,
+                    creations,
                     set_cache.clone(),
                     selection_decoder.clone(),
                 )
-                .await;
-            (result, started.elapsed().as_secs_f64())
-        }))
-        .await;
-
-        let mut items = Vec::new();
-        let mut stats = Vec::with_capacity(results.len());
-        let mut seen: HashSet<(i64, i64, i64)> = HashSet::new();
-        let mut first_err = None;
-        for (result, seconds) in results {
-            stats.push(RequestStat {
-                method: "eth_getLogs".to_string(),
-                seconds,
-            });
-            match result {
-                Ok(page_items) => {
-                    for item in page_items {
-                        if seen.insert((
-                            item.log.block_number,
-                            item.log.log_index,
-                            item.on_event_registration_index,
-                        )) {
-                            items.push(item);
-                        }
-                    }
-                }
-                Err(e) => {
-                    if first_err.is_none() {
-                        first_err = Some(e);
-                    }
+                .await
+                {
+                    Ok(creation_items) => items.extend(creation_items),
+                    Err(e) => return Err((e, stats)),
                 }
             }
         }
-        match first_err {
-            Some(e) => Err((e, stats)),
-            None => Ok((items, stats)),
-        }
+        Ok((items, stats))
     }
 
-    async fn fetch_logs_raw(
+    async fn fetch_logs(
         &self,
-        from_block: i64,
-        to_block: i64,
-        selection: &BuiltLogSelection,
-        set_cache: std::sync::Arc<SetCache>,
-        decoder: std::sync::Arc<SelectionDecoder>,
-    ) -> Result<Vec<RpcEventItem>, RpcError> {
-        // eth_getLogs topic filters: `null` matches any value at a position;
-        // trailing match-any positions are trimmed entirely.
-        let mut topics: Vec<Option<&Vec<String>>> = selection
-            .topics
+        from_block: u64,
+        to_block: u64,
+        selections: &[BuiltLogSelection],
+        set_cache: &std::sync::Arc<SetCache>,
+        selection_decoder: &std::sync::Arc<SelectionDecoder>,
+    ) -> Result<(Vec<RpcEventItem>, Vec<RequestStat>), (RpcError, Vec<RequestStat>)> {
+        if selections.is_empty() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let mut stats = Vec::new();
+        let params = selections
             .iter()
-            .map(|values| {
-                if values.is_empty() {
-                    None
-                } else {
-                    Some(values)
-                }
-            })
+            .map(|selection| json!([log_filter(from_block, to_block, selection)]))
             .collect();
-        while matches!(topics.last(), Some(None)) {
-            topics.pop();
-        }
-        let mut filter = json!({
-            "fromBlock": format!("0x{:x}", from_block),
-            "toBlock": format!("0x{:x}", to_block),
-            "topics": topics,
-        });
-        if !selection.addresses.is_empty() {
-            filter["address"] = json!(selection.addresses);
-        }
+        let results = match self
+            .inner
+            .batch_request::<Vec<RawLog>>("eth_getLogs", params, &mut stats)
+            .await
+        {
+            Ok(results) => results,
+            Err(e) => return Err((e, stats)),
+        };
+        // Surface the first failed call as the page's error, like a failed
+        // request did before batching; its message drives the retry decision.
+        let raw_logs = match results.into_iter().collect::<Result<Vec<_>, _>>() {
+            Ok(raw_logs) => raw_logs,
+            Err(e) => return Err((e, stats)),
+        };
 
-        let raw_logs: Vec<RawLog> = self.inner.request("eth_getLogs", json!([filter])).await?;
-
-        // Decoding is CPU-bound ABI work; keep it off the libuv async thread.
-        tokio::task::spawn_blocking(move || {
-            let should_checksum = decoder.checksummed_addresses();
-            let address_store = decoder.lock_store();
-            let mut items = Vec::new();
-            for raw in raw_logs {
-                let (address_key, address) = raw.address_bytes_and_string(should_checksum)?;
-                let block_number = parse_hex_u64(&raw.block_number)
-                    .context("log.blockNumber")?
-                    .try_into()
-                    .context("log.blockNumber exceeds i64::MAX")?;
-                let log_address = LogAddress {
-                    key: &address_key,
-                    contract_name: set_cache.owner_of(&address_key),
-                    block_number,
+        let mut items = Vec::new();
+        let mut seen: HashSet<(i64, i64, i64)> = HashSet::new();
+        for raw_logs in raw_logs {
+            let page_items =
+                match decode_logs(raw_logs, set_cache.clone(), selection_decoder.clone()).await {
+                    Ok(page_items) => page_items,
+                    Err(e) => return Err((e, stats)),
                 };
-                // Per-registration decode failures are dropped inside
-                // `route_and_decode`; only structurally malformed logs error,
-                // and those propagate like on the HyperSync path.
-                let routed = decoder.route_and_decode_napi(
-                    &raw.to_decoder_log(),
-                    &log_address,
-                    &address_store,
-                )?;
-                if routed.is_empty() {
-                    continue;
-                }
-                let log = raw.into_rpc_log(address)?;
-                for routed in routed {
-                    items.push(RpcEventItem {
-                        log: log.clone(),
-                        on_event_registration_index: routed.index,
-                        params: routed.params,
-                    });
+            for item in page_items {
+                if seen.insert((
+                    item.log.block_number,
+                    item.log.log_index,
+                    item.on_event_registration_index,
+                )) {
+                    items.push(item);
                 }
             }
-            Ok(items)
+        }
+        Ok((items, stats))
+    }
+}
+
+/// The `eth_getLogs` filter object for one selection over `from..=to`.
+fn log_filter(from_block: u64, to_block: u64, selection: &BuiltLogSelection) -> serde_json::Value {
+    // eth_getLogs topic filters: `null` matches any value at a position;
+    // trailing match-any positions are trimmed entirely.
+    let mut topics: Vec<Option<&Vec<String>>> = selection
+        .topics
+        .iter()
+        .map(|values| {
+            if values.is_empty() {
+                None
+            } else {
+                Some(values)
+            }
         })
-        .await
-        .map_err(|e| {
-            RpcError::Other(anyhow::anyhow!(
-                "eth_getLogs decode worker join failure: {e}"
-            ))
-        })?
-        .map_err(RpcError::Other)
+        .collect();
+    while matches!(topics.last(), Some(None)) {
+        topics.pop();
+    }
+    let mut filter = json!({
+        "fromBlock": format!("0x{:x}", from_block),
+        "toBlock": format!("0x{:x}", to_block),
+        "topics": topics,
+    });
+    if !selection.addresses.is_empty() {
+        filter["address"] = json!(selection.addresses);
     }
+    filter
+}
+
+/// Route and decode one selection's `eth_getLogs` result into event items.
+async fn decode_logs(
+    raw_logs: Vec<RawLog>,
+    set_cache: std::sync::Arc<SetCache>,
+    decoder: std::sync::Arc<SelectionDecoder>,
+) -> Result<Vec<RpcEventItem>, RpcError> {
+    // Decoding is CPU-bound ABI work; keep it off the libuv async thread.
+    tokio::task::spawn_blocking(move || {
+        let should_checksum = decoder.checksummed_addresses();
+        let address_store = decoder.lock_store();
+        let mut items = Vec::new();
+        for raw in raw_logs {
+            let (address_key, address) = raw.address_bytes_and_string(should_checksum)?;
+            let block_number = parse_hex_u64(&raw.block_number)
+                .context("log.blockNumber")?
+                .try_into()
+                .context("log.blockNumber exceeds i64::MAX")?;
+            let log_address = LogAddress {
+                key: &address_key,
+                contract_name: set_cache.owner_of(&address_key),
+                block_number,
+            };
+            // Per-registration decode failures are dropped inside
+            // `route_and_decode`; only structurally malformed logs error,
+            // and those propagate like on the HyperSync path.
+            let routed = decoder.route_and_decode_napi(
+                &raw.to_decoder_log(),
+                &log_address,
+                &address_store,
+            )?;
+            if routed.is_empty() {
+                continue;
+            }
+            let log = raw.into_rpc_log(address)?;
+            for routed in routed {
+                items.push(RpcEventItem {
+                    log: log.clone(),
+                    on_event_registration_index: routed.index,
+                    params: routed.params,
+                    trace_address: None,
+                });
+            }
+        }
+        Ok(items)
+    })
+    .await
+    .map_err(|e| {
+        RpcError::Other(anyhow::anyhow!(
+            "eth_getLogs decode worker join failure: {e}"
+        ))
+    })?
+    .map_err(RpcError::Other)
+}
+
+/// Route and decode a page's calls (or transactions) into event items, like
+/// `decode_logs`.
+async fn decode_calls(
+    source: CallSource,
+    calls: Vec<traces::RawCall>,
+    set_cache: std::sync::Arc<SetCache>,
+    decoder: std::sync::Arc<SelectionDecoder>,
+) -> Result<Vec<RpcEventItem>, RpcError> {
+    tokio::task::spawn_blocking(move || {
+        let should_checksum = decoder.checksummed_addresses();
+        let address_store = decoder.lock_store();
+        let mut items = Vec::new();
+        for call in calls {
+            let to = hypersync_client::format::Address::decode_hex(&call.to)
+                .context("decode call.to hex")?;
+            let input = hypersync_client::format::Data::decode_hex(&call.input)
+                .context("decode call.input hex")?;
+            let block_number: i64 = call
+                .block_number
+                .try_into()
+                .context("call.blockNumber exceeds i64::MAX")?;
+            let transaction_index: i64 = call
+                .transaction_index
+                .try_into()
+                .context("call.transactionIndex exceeds i64::MAX")?;
+            let call_address = LogAddress {
+                key: to.as_slice(),
+                contract_name: set_cache.owner_of(to.as_slice()),
+                block_number,
+            };
+            let routed =
+                decoder.route_and_decode_call(source, &input, &call_address, &address_store);
+            if routed.is_empty() {
+                continue;
+            }
+            let trace_address = call
+                .trace_address
+                .iter()
+                .map(|&i| i64::try_from(i))
+                .collect::<Result<Vec<_>, _>>()
+                .context("call.traceAddress exceeds i64::MAX")?;
+            let log = RpcLog {
+                address: encode_address(&to, should_checksum),
+                topics: Vec::new(),
+                block_number,
+                transaction_hash: call.transaction_hash,
+                transaction_index,
+                block_hash: call.block_hash,
+                log_index: transaction_index,
+            };
+            for routed in routed {
+                items.push(RpcEventItem {
+                    log: log.clone(),
+                    on_event_registration_index: routed.index,
+                    params: routed.params,
+                    trace_address: Some(trace_address.clone()),
+                });
+            }
+        }
+        Ok(items)
+    })
+    .await
+    .map_err(|e| RpcError::Other(anyhow::anyhow!("trace decode worker join failure: {e}")))?
+    .map_err(RpcError::Other)
+}
+
+/// Route a page's contract creations into event items, like `decode_calls`.
+/// The item's address is the deployer's, the contract the creation event
+/// belongs to.
+async fn decode_creations(
+    source: CallSource,
+    creations: Vec<traces::RawCreation>,
+    set_cache: std::sync::Arc<SetCache>,
+    decoder: std::sync::Arc<SelectionDecoder>,
+) -> Result<Vec<RpcEventItem>, RpcError> {
+    tokio::task::spawn_blocking(move || {
+        let should_checksum = decoder.checksummed_addresses();
+        let address_store = decoder.lock_store();
+        let mut items = Vec::new();
+        for creation in creations {
+            let from = hypersync_client::format::Address::decode_hex(&creation.from)
+                .context("decode creation.from hex")?;
+            let created = hypersync_client::format::Address::decode_hex(&creation.address)
+                .context("decode creation.address hex")?;
+            let block_number: i64 = creation
+                .block_number
+                .try_into()
+                .context("creation.blockNumber exceeds i64::MAX")?;
+            let transaction_index: i64 = creation
+                .transaction_index
+                .try_into()
+                .context("creation.transactionIndex exceeds i64::MAX")?;
+            let deployer = LogAddress {
+                key: from.as_slice(),
+                contract_name: set_cache.owner_of(from.as_slice()),
+                block_number,
+            };
+            let created: &[u8; 20] = created
+                .as_slice()
+                .try_into()
+                .context("creation.address is not 20 bytes")?;
+            let routed = decoder.route_creation(source, created, &deployer, &address_store);
+            if routed.is_empty() {
+                continue;
+            }
+            let trace_address = creation
+                .trace_address
+                .iter()
+                .map(|&i| i64::try_from(i))
+                .collect::<Result<Vec<_>, _>>()
+                .context("creation.traceAddress exceeds i64::MAX")?;
+            let log = RpcLog {
+                address: encode_address(&from, should_checksum),
+                topics: Vec::new(),
+                block_number,
+                transaction_hash: creation.transaction_hash,
+                transaction_index,
+                block_hash: creation.block_hash,
+                log_index: transaction_index,
+            };
+            for routed in routed {
+                items.push(RpcEventItem {
+                    log: log.clone(),
+                    on_event_registration_index: routed.index,
+                    params: routed.params,
+                    trace_address: Some(trace_address.clone()),
+                });
+            }
+        }
+        Ok(items)
+    })
+    .await
+    .map_err(|e| RpcError::Other(anyhow::anyhow!("creation decode worker join failure: {e}")))?
+    .map_err(RpcError::Other)
 }
 
 enum RetryDecision {
@@ -599,6 +922,29 @@ fn retry_decision_to_napi(
     napi::Error::new(napi::Status::GenericFailure, payload)
 }
 
+/// Encodes a page's missing block or transaction as a JSON payload in the
+/// napi error's message: `{"kind":"DataNotFound","attemptedToBlock":...,
+/// "message":...,"requestStats":[...]}`. The backoff grows with the caller's
+/// retry count, so it's left to the ReScript side.
+fn data_not_found_to_napi(
+    attempted_to_block: u64,
+    message: &str,
+    request_stats: Vec<RequestStat>,
+) -> napi::Error {
+    let request_stats_json: Vec<_> = request_stats
+        .into_iter()
+        .map(|s| json!({"method": s.method, "seconds": s.seconds}))
+        .collect();
+    let payload = json!({
+        "kind": "DataNotFound",
+        "attemptedToBlock": attempted_to_block,
+        "message": message,
+        "requestStats": request_stats_json,
+    })
+    .to_string();
+    napi::Error::new(napi::Status::GenericFailure, payload)
+}
+
 /// Encodes JSON-RPC errors as a JSON payload in the napi error's message.
 /// The ReScript side parses it back into a structured exception, keeping
 /// the provider's code and message intact across the boundary.
@@ -613,6 +959,7 @@ fn rpc_error_to_napi(e: RpcError) -> napi::Error {
             .to_string();
             napi::Error::from_reason(payload)
         }
+        RpcError::NotFound { message } => napi::Error::from_reason(message),
         RpcError::Other(e) => map_err(e),
     }
 }
@@ -0,0 +1,350 @@
+//! Request routing across an RPC source's endpoints. Each endpoint keeps a
+//! health estimate fed by the same timings reported as `RequestStat`s —
+//! smoothed latency and error rate — and every call goes to the endpoint that
+//! currently scores best. With `hedge_after` set, a call still unanswered after
+//! that long is also sent to the runner-up and the first healthy answer wins,
+//! so a provider that degrades without hard-failing costs one hedge delay
+//! rather than a query timeout. A single-endpoint pool behaves exactly like
+//! its `JsonRpcClient`.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+
+use super::classify::{is_response_too_large_message, suggested_block_interval_from_message};
+use super::client::{BatchResult, JsonRpcClient, RpcError};
+use crate::request_stats::RequestStat;
+
+/// Weight of the newest sample in the smoothed latency and error rate.
+const SMOOTHING: f64 = 0.3;
+/// How much a fully failing endpoint's latency is inflated by: at an error
+/// rate of 1, the score is `(1 + ERROR_PENALTY)` times the latency.
+const ERROR_PENALTY: f64 = 10.0;
+/// Error rates decay by half over this period without new samples, so an
+/// endpoint that stopped receiving traffic after failing is tried again once
+/// it has had time to recover.
+const ERROR_HALF_LIFE: Duration = Duration::from_secs(60);
+/// Latency assumed for a failed request that returned quickly, so a fast
+/// failure never scores better than a slow success.
+const FAILURE_LATENCY_FLOOR_SECONDS: f64 = 1.0;
+
+#[derive(Default)]
+struct Health {
+    /// `None` until the first sample; unmeasured endpoints rank first so every
+    /// endpoint gets measured.
+    latency: Option<f64>,
+    error_rate: f64,
+    updated: Option<Instant>,
+}
+
+impl Health {
+    fn decayed_error_rate(&self, now: Instant) -> f64 {
+        match self.updated {
+            Some(updated) => {
+                let half_lives = now.saturating_duration_since(updated).as_secs_f64()
+                    / ERROR_HALF_LIFE.as_secs_f64();
+                self.error_rate * 0.5f64.powf(half_lives)
+            }
+            None => 0.0,
+        }
+    }
+
+    fn score(&self, now: Instant) -> f64 {
+        match self.latency {
+            Some(latency) => latency * (1.0 + ERROR_PENALTY * self.decayed_error_rate(now)),
+            None => 0.0,
+        }
+    }
+
+    fn record(&mut self, seconds: f64, healthy: bool, now: Instant) {
+        let seconds = if healthy {
+            seconds
+        } else {
+            seconds.max(FAILURE_LATENCY_FLOOR_SECONDS)
+        };
+        let error = if healthy { 0.0 } else { 1.0 };
+        self.error_rate = self.decayed_error_rate(now) * (1.0 - SMOOTHING) + error * SMOOTHING;
+        self.latency = Some(match self.latency {
+            Some(latency) => latency * (1.0 - SMOOTHING) + seconds * SMOOTHING,
+            None => seconds,
+        });
+        self.updated = Some(now);
+    }
+}
+
+struct Endpoint {
+    client: JsonRpcClient,
+    health: Mutex<Health>,
+}
+
+pub(super) struct ProviderPool {
+    endpoints: Vec<Endpoint>,
+    hedge_after: Option<Duration>,
+}
+
+/// Whether a call's outcome reflects on the endpoint. Errors shaped by the
+/// query itself — a block range over the provider's cap, too many logs — would
+/// fail the same way anywhere, so they don't count against it.
+fn is_healthy<T>(result: &BatchResult<T>) -> bool {
+    let is_query_error = |e: &RpcError| match e {
+        RpcError::JsonRpc { message, .. } => {
+            is_response_too_large_message(message)
+                || suggested_block_interval_from_message(message).is_some()
+        }
+        RpcError::NotFound { .. } | RpcError::Other(_) => false,
+    };
+    match result {
+        Ok(results) => results
+            .iter()
+            .all(|r| r.as_ref().err().is_none_or(is_query_error)),
+        Err(e) => is_query_error(e),
+    }
+}
+
+impl ProviderPool {
+    pub(super) fn new(clients: Vec<JsonRpcClient>, hedge_after: Option<Duration>) -> Self {
+        assert!(!clients.is_empty(), "a provider pool needs an endpoint");
+        ProviderPool {
+            endpoints: clients
+                .into_iter()
+                .map(|client| Endpoint {
+                    client,
+                    health: Mutex::new(Health::default()),
+                })
+                .collect(),
+            hedge_after,
+        }
+    }
+
+    /// The best-scoring endpoint and the runner-up, if there's more than one.
+    fn ranked(&self) -> (usize, Option<usize>) {
+        let now = Instant::now();
+        let mut scores: Vec<(f64, usize)> = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.health.lock().unwrap().score(now), i))
+            .collect();
+        scores.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        (scores[0].1, scores.get(1).map(|s| s.1))
+    }
+
+    fn record(&self, endpoint: usize, seconds: f64, healthy: bool) {
+        self.endpoints[endpoint]
+            .health
+            .lock()
+            .unwrap()
+            .record(seconds, healthy, Instant::now());
+    }
+
+    async fn attempt<T: DeserializeOwned>(
+        &self,
+        endpoint: usize,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> (BatchResult<T>, Vec<RequestStat>) {
+        let started = Instant::now();
+        let mut stats = Vec::new();
+        let result = self.endpoints[endpoint]
+            .client
+            .batch_request(method, params, &mut stats)
+            .await;
+        self.record(
+            endpoint,
+            started.elapsed().as_secs_f64(),
+            is_healthy(&result),
+        );
+        (result, stats)
+    }
+
+    pub(super) async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, RpcError> {
+        let mut stats = Vec::new();
+        self.batch_request(method, vec![params], &mut stats)
+            .await?
+            .pop()
+            .expect("one result per call")
+    }
+
+    /// `JsonRpcClient::batch_request` on the healthiest endpoint, hedged to
+    /// the runner-up when `hedge_after` elapses first. A hedged-away request
+    /// is cancelled and its endpoint charged like a timed-out one: a failure
+    /// taking the time it had run so far, since a request sent `hedge_after`
+    /// later still beat it.
+    pub(super) async fn batch_request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+        stats: &mut Vec<RequestStat>,
+    ) -> BatchResult<T> {
+        let (best, runner_up) = self.ranked();
+        let (Some(hedge_after), Some(runner_up)) = (self.hedge_after, runner_up) else {
+            let (result, attempt_stats) = self.attempt(best, method, params).await;
+            stats.extend(attempt_stats);
+            return result;
+        };
+
+        let started = Instant::now();
+        let primary = self.attempt(best, method, params.clone());
+        tokio::pin!(primary);
+        let (result, attempt_stats) = tokio::select! {
+            out = &mut primary => out,
+            _ = tokio::time::sleep(hedge_after) => {
+                let hedge = self.attempt(runner_up, method, params);
+                tokio::pin!(hedge);
+                // First healthy answer wins; an unhealthy one waits for the
+                // other, which is returned whatever its outcome.
+                tokio::select! {
+                    (result, mut first_stats) = &mut primary => {
+                        if is_healthy(&result) {
+                            (result, first_stats)
+                        } else {
+                            let (result, second_stats) = hedge.await;
+                            first_stats.extend(second_stats);
+                            (result, first_stats)
+                        }
+                    }
+                    (result, mut first_stats) = &mut hedge => {
+                        if is_healthy(&result) {
+                            self.record(best, started.elapsed().as_secs_f64(), false);
+                            (result, first_stats)
+                        } else {
+                            let (result, second_stats) = primary.await;
+                            first_stats.extend(second_stats);
+                            (result, first_stats)
+                        }
+                    }
+                }
+            }
+        };
+        stats.extend(attempt_stats);
+        result
+    }
+
+    pub(super) async fn get_height(&self) -> Result<u64, RpcError> {
+        let result: String = self
+            .request("eth_blockNumber", serde_json::json!([]))
+            .await?;
+        super::client::parse_hex_u64(&result).map_err(RpcError::Other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn pool(urls: usize) -> ProviderPool {
+        ProviderPool::new(
+            (0..urls)
+                .map(|i| {
+                    JsonRpcClient::new(format!("http://endpoint-{i}"), 1_000, None, 1).unwrap()
+                })
+                .collect(),
+            None,
+        )
+    }
+
+    #[test]
+    fn ranks_unmeasured_then_fastest_and_penalizes_errors() {
+        let pool = pool(3);
+        // Every endpoint unmeasured: input order breaks the tie.
+        assert_eq!(pool.ranked(), (0, Some(1)));
+
+        pool.record(0, 0.5, true);
+        pool.record(1, 0.2, true);
+        // Endpoint 2 is still unmeasured, so it's tried before the others.
+        assert_eq!(pool.ranked(), (2, Some(1)));
+
+        pool.record(2, 0.1, false);
+        // A fast failure is charged at least the failure floor, and errors
+        // inflate the score further.
+        assert_eq!(pool.ranked(), (1, Some(0)));
+    }
+
+    #[test]
+    fn error_rate_decays_without_new_samples() {
+        let mut health = Health::default();
+        let now = Instant::now();
+        health.record(1.0, false, now);
+        let fresh = health.decayed_error_rate(now);
+        let later = health.decayed_error_rate(now + ERROR_HALF_LIFE);
+        assert!((later - fresh / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn query_shaped_errors_do_not_count_against_the_endpoint() {
+        let too_large: BatchResult<()> = Ok(vec![Err(RpcError::JsonRpc {
+            code: -32005,
+            message: "query returned more than 10000 results".to_string(),
+        })]);
+        let rate_limited: BatchResult<()> = Ok(vec![Err(RpcError::JsonRpc {
+            code: 429,
+            message: "rate limit exceeded".to_string(),
+        })]);
+        let transport: BatchResult<()> = Err(RpcError::Other(anyhow::anyhow!("timed out")));
+        assert_eq!(
+            (
+                is_healthy(&too_large),
+                is_healthy(&rate_limited),
+                is_healthy(&transport)
+            ),
+            (true, false, false)
+        );
+    }
+
+    /// A JSON-RPC endpoint answering every call with `result` after `delay`.
+    async fn serve(delay: Duration, result: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    tokio::time::sleep(delay).await;
+                    let body = format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{result}"}}"#);
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn hedge_win_charges_the_cancelled_primary() {
+        let stalled = serve(Duration::from_secs(5), "0x1").await;
+        let fast = serve(Duration::ZERO, "0x2").await;
+        let pool = ProviderPool::new(
+            [stalled, fast]
+                .into_iter()
+                .map(|url| JsonRpcClient::new(url, 10_000, None, 1).unwrap())
+                .collect(),
+            Some(Duration::from_millis(50)),
+        );
+
+        // Both unmeasured: the stalled endpoint ranks first and is hedged away.
+        assert_eq!(pool.get_height().await.unwrap(), 2);
+        let now = Instant::now();
+        let health = |i: usize| {
+            let health = pool.endpoints[i].health.lock().unwrap();
+            (
+                health.latency.is_some(),
+                health.decayed_error_rate(now) > 0.0,
+            )
+        };
+        assert_eq!((health(0), health(1)), ((true, true), (true, false)));
+        assert_eq!(pool.ranked(), (1, Some(0)));
+    }
+}
@@ -0,0 +1,665 @@
+//! Calls for `type: call` registrations on the RPC source. `eth_getLogs` sees
+//! no calls, so they're read from traces: `trace_filter` (Erigon, Nethermind,
+//! Reth and most archive providers) with one request per trace selection, or,
+//! once a provider has rejected that method, `debug_traceBlockByNumber` with
+//! geth's `callTracer`, one request per block. Both are sent as JSON-RPC
+//! batches and flattened into the same `RawCall` rows, so routing never knows
+//! which method served the range. Contract creations for factory registrations
+//! come out of the same traces, as `RawCreation` rows.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::classify::is_method_unsupported;
+use super::client::RpcError;
+use super::pool::ProviderPool;
+use crate::evm_hypersync_source::selection::{BuiltCallSelection, BuiltCreationSelection};
+use crate::request_stats::RequestStat;
+
+/// One successful plain call, flattened out of its transaction's call tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct RawCall {
+    pub to: String,
+    pub input: String,
+    pub block_number: u64,
+    /// Empty when the method doesn't report it (`debug_traceBlockByNumber`);
+    /// `fields::fetch_joins` fills it from the fetched block.
+    pub block_hash: String,
+    pub transaction_hash: String,
+    pub transaction_index: u64,
+    pub trace_address: Vec<u64>,
+}
+
+/// One successful contract creation, flattened out of its transaction's call
+/// tree: `from` created `address`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct RawCreation {
+    pub from: String,
+    pub address: String,
+    pub block_number: u64,
+    /// Empty when the method doesn't report it, like `RawCall::block_hash`.
+    pub block_hash: String,
+    pub transaction_hash: String,
+    pub transaction_index: u64,
+    pub trace_address: Vec<u64>,
+}
+
+/// What a page's traces hold for its selections.
+#[derive(Default)]
+pub(super) struct Traced {
+    pub calls: Vec<RawCall>,
+    pub creations: Vec<RawCreation>,
+}
+
+/// A `trace_filter` entry (the Parity/OpenEthereum trace format).
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FilterTrace {
+    action: FilterAction,
+    block_hash: String,
+    block_number: u64,
+    // Absent on block and uncle reward traces.
+    transaction_hash: Option<String>,
+    transaction_position: Option<u64>,
+    trace_address: Vec<u64>,
+    #[serde(rename = "type")]
+    type_: String,
+    error: Option<String>,
+    // Absent on reverted traces.
+    result: Option<FilterResult>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FilterAction {
+    call_type: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    input: Option<String>,
+}
+
+/// Only a creation's result is read: the created address.
+#[derive(Deserialize)]
+struct FilterResult {
+    address: Option<String>,
+}
+
+/// One transaction of a `debug_traceBlockByNumber` result, in block order.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockTxTrace {
+    tx_hash: Option<String>,
+    result: CallFrame,
+}
+
+/// A `callTracer` frame. Its `calls` are its subcalls, in execution order.
+#[derive(Deserialize)]
+struct CallFrame {
+    #[serde(rename = "type")]
+    type_: String,
+    from: Option<String>,
+    to: Option<String>,
+    input: Option<String>,
+    error: Option<String>,
+    #[serde(default)]
+    calls: Vec<CallFrame>,
+}
+
+/// Whether a trace entry is a plain call that succeeded. A delegatecall runs
+/// the callee's code on the caller's behalf, so it isn't a call to the callee
+/// contract; a reverted call's effects never happened. The trace formats don't
+/// mark a reverted frame's subcalls, so callers also drop everything below one.
+fn is_successful_call(type_: &str, error: &Option<String>) -> bool {
+    type_.eq_ignore_ascii_case("call") && error.is_none()
+}
+
+/// Whether a trace entry is a contract creation that succeeded, by either
+/// opcode.
+fn is_successful_creation(type_: &str, error: &Option<String>) -> bool {
+    (type_.eq_ignore_ascii_case("create") || type_.eq_ignore_ascii_case("create2"))
+        && error.is_none()
+}
+
+impl FilterTrace {
+    fn into_creation(self) -> Option<RawCreation> {
+        if !is_successful_creation(&self.type_, &self.error) {
+            return None;
+        }
+        Some(RawCreation {
+            from: self.action.from?,
+            address: self.result?.address?,
+            block_number: self.block_number,
+            block_hash: self.block_hash,
+            transaction_hash: self.transaction_hash?,
+            transaction_index: self.transaction_position?,
+            trace_address: self.trace_address,
+        })
+    }
+
+    fn into_call(self) -> Option<RawCall> {
+        let call_type = self.action.call_type.as_deref().unwrap_or("call");
+        if self.type_ != "call" || !is_successful_call(call_type, &self.error) {
+            return None;
+        }
+        Some(RawCall {
+            to: self.action.to?,
+            input: self.action.input?,
+            block_number: self.block_number,
+            block_hash: self.block_hash,
+            transaction_hash: self.transaction_hash?,
+            transaction_index: self.transaction_position?,
+            trace_address: self.trace_address,
+        })
+    }
+}
+
+/// Flattens one transaction's call tree depth-first, so the rows come out in
+/// execution order, each with the trace address `trace_filter` would give it.
+/// A creation frame's `to` is the contract it created. Nothing below a
+/// reverted frame is kept: its subcalls were rolled back with it.
+fn flatten_frame(
+    frame: CallFrame,
+    trace_address: &mut Vec<u64>,
+    tx: &RawCall,
+    traced: &mut Traced,
+) {
+    if frame.error.is_some() {
+        return;
+    }
+    if is_successful_call(&frame.type_, &frame.error) {
+        if let (Some(to), Some(input)) = (frame.to, frame.input) {
+            traced.calls.push(RawCall {
+                to,
+                input,
+                trace_address: trace_address.clone(),
+                ..tx.clone()
+            });
+        }
+    } else if is_successful_creation(&frame.type_, &frame.error) {
+        if let (Some(from), Some(address)) = (frame.from, frame.to) {
+            traced.creations.push(RawCreation {
+                from,
+                address,
+                block_number: tx.block_number,
+                block_hash: tx.block_hash.clone(),
+                transaction_hash: tx.transaction_hash.clone(),
+                transaction_index: tx.transaction_index,
+                trace_address: trace_address.clone(),
+            });
+        }
+    }
+    for (i, sub) in frame.calls.into_iter().enumerate() {
+        trace_address.push(i as u64);
+        flatten_frame(sub, trace_address, tx, traced);
+        trace_address.pop();
+    }
+}
+
+fn flatten_block(block_number: u64, txs: Vec<BlockTxTrace>) -> Result<Traced, RpcError> {
+    let mut traced = Traced::default();
+    for (transaction_index, tx) in txs.into_iter().enumerate() {
+        let transaction_hash = tx.tx_hash.ok_or_else(|| {
+            RpcError::Other(anyhow::anyhow!(
+                "debug_traceBlockByNumber returned a trace without txHash for block \
+                 {block_number}. The node is too old to index calls from; use a provider \
+                 that supports trace_filter"
+            ))
+        })?;
+        let template = RawCall {
+            to: String::new(),
+            input: String::new(),
+            block_number,
+            block_hash: String::new(),
+            transaction_hash,
+            transaction_index: transaction_index as u64,
+            trace_address: Vec::new(),
+        };
+        flatten_frame(tx.result, &mut Vec::new(), &template, &mut traced);
+    }
+    Ok(traced)
+}
+
+/// Every successful plain call over `from..=to` to the call selections'
+/// callees (any callee for an address-free selection), and every successful
+/// creation by the creation selections' deployers. Routing re-applies the
+/// selector and address rules, so the `debug_traceBlockByNumber` fallback,
+/// which can't filter, returns every call and creation of the range. Repeats
+/// (a trace matching two selections) are dropped.
+pub(super) async fn fetch_traces(
+    client: &ProviderPool,
+    from_block: u64,
+    to_block: u64,
+    call_selections: &[BuiltCallSelection],
+    creation_selections: &[BuiltCreationSelection],
+    trace_filter_unsupported: &AtomicBool,
+    stats: &mut Vec<RequestStat>,
+) -> Result<Traced, RpcError> {
+    if call_selections.is_empty() && creation_selections.is_empty() {
+        return Ok(Traced::default());
+    }
+    if !trace_filter_unsupported.load(Ordering::Relaxed) {
+        match fetch_with_trace_filter(
+            client,
+            from_block,
+            to_block,
+            call_selections,
+            creation_selections,
+            stats,
+        )
+        .await
+        {
+            Err(RpcError::JsonRpc { code, message }) if is_method_unsupported(code, &message) => {
+                trace_filter_unsupported.store(true, Ordering::Relaxed);
+            }
+            result => return result,
+        }
+    }
+    let mut traced = fetch_with_debug_trace(client, from_block, to_block, stats).await?;
+    if call_selections.is_empty() {
+        traced.calls.clear();
+    }
+    if creation_selections.is_empty() {
+        traced.creations.clear();
+    }
+    Ok(traced)
+}
+
+/// One `trace_filter` per selection, in one batch: the call selections' by
+/// callee, then the creation selections' by deployer.
+async fn fetch_with_trace_filter(
+    client: &ProviderPool,
+    from_block: u64,
+    to_block: u64,
+    call_selections: &[BuiltCallSelection],
+    creation_selections: &[BuiltCreationSelection],
+    stats: &mut Vec<RequestStat>,
+) -> Result<Traced, RpcError> {
+    let filter = |key: &str, addresses: &[String]| {
+        let mut filter = json!({
+            "fromBlock": format!("0x{:x}", from_block),
+            "toBlock": format!("0x{:x}", to_block),
+        });
+        if !addresses.is_empty() {
+            filter[key] = json!(addresses);
+        }
+        json!([filter])
+    };
+    let params = call_selections
+        .iter()
+        .map(|selection| filter("toAddress", &selection.addresses))
+        .chain(
+            creation_selections
+                .iter()
+                .map(|selection| filter("fromAddress", &selection.deployers)),
+        )
+        .collect();
+    let results = client
+        .batch_request::<Vec<FilterTrace>>("trace_filter", params, stats)
+        .await?;
+    let mut seen: HashSet<(String, Vec<u64>)> = HashSet::new();
+    let mut traced = Traced::default();
+    for (i, traces) in results.into_iter().enumerate() {
+        for trace in traces? {
+            if i < call_selections.len() {
+                if let Some(call) = trace.into_call() {
+                    if seen.insert((call.transaction_hash.clone(), call.trace_address.clone())) {
+                        traced.calls.push(call);
+                    }
+                }
+            } else if let Some(creation) = trace.into_creation() {
+                if seen.insert((
+                    creation.transaction_hash.clone(),
+                    creation.trace_address.clone(),
+                )) {
+                    traced.creations.push(creation);
+                }
+            }
+        }
+    }
+    drop_reverted_subtraces(client, &mut traced, stats).await?;
+    Ok(traced)
+}
+
+/// `trace_filter` only returns the traces its filter matches, so a matched
+/// call nested in a frame that reverted comes back looking like any other.
+/// Reads the whole trace of every transaction holding a nested row and drops
+/// the rows below a reverted frame.
+async fn drop_reverted_subtraces(
+    client: &ProviderPool,
+    traced: &mut Traced,
+    stats: &mut Vec<RequestStat>,
+) -> Result<(), RpcError> {
+    let nested: BTreeSet<String> = traced
+        .calls
+        .iter()
+        .map(|call| (&call.transaction_hash, &call.trace_address))
+        .chain(
+            traced
+                .creations
+                .iter()
+                .map(|creation| (&creation.transaction_hash, &creation.trace_address)),
+        )
+        .filter(|(_, trace_address)| !trace_address.is_empty())
+        .map(|(transaction_hash, _)| transaction_hash.clone())
+        .collect();
+    if nested.is_empty() {
+        return Ok(());
+    }
+    let params = nested.iter().map(|hash| json!([hash])).collect();
+    let results = client
+        .batch_request::<Vec<FilterTrace>>("trace_transaction", params, stats)
+        .await?;
+    let mut reverted = HashMap::new();
+    for (hash, traces) in nested.into_iter().zip(results) {
+        reverted.insert(hash, reverted_frames(traces?));
+    }
+    retain_outside_reverted_frames(traced, &reverted);
+    Ok(())
+}
+
+/// The trace addresses of a transaction's reverted frames.
+fn reverted_frames(traces: Vec<FilterTrace>) -> Vec<Vec<u64>> {
+    traces
+        .into_iter()
+        .filter(|trace| trace.error.is_some())
+        .map(|trace| trace.trace_address)
+        .collect()
+}
+
+/// Drops the rows lying below one of their transaction's reverted frames.
+fn retain_outside_reverted_frames(traced: &mut Traced, reverted: &HashMap<String, Vec<Vec<u64>>>) {
+    let below_reverted = |transaction_hash: &String, trace_address: &[u64]| {
+        reverted.get(transaction_hash).is_some_and(|frames| {
+            frames
+                .iter()
+                .any(|frame| frame.len() < trace_address.len() && trace_address.starts_with(frame))
+        })
+    };
+    traced
+        .calls
+        .retain(|call| !below_reverted(&call.transaction_hash, &call.trace_address));
+    traced
+        .creations
+        .retain(|creation| !below_reverted(&creation.transaction_hash, &creation.trace_address));
+}
+
+async fn fetch_with_debug_trace(
+    client: &ProviderPool,
+    from_block: u64,
+    to_block: u64,
+    stats: &mut Vec<RequestStat>,
+) -> Result<Traced, RpcError> {
+    let params = (from_block..=to_block)
+        .map(|n| json!([format!("0x{n:x}"), {"tracer": "callTracer"}]))
+        .collect();
+    let results = client
+        .batch_request::<Vec<BlockTxTrace>>("debug_traceBlockByNumber", params, stats)
+        .await?;
+    let mut traced = Traced::default();
+    for (block_number, txs) in (from_block..=to_block).zip(results) {
+        let block = flatten_block(block_number, txs?)?;
+        traced.calls.extend(block.calls);
+        traced.creations.extend(block.creations);
+    }
+    Ok(traced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(byte: &str) -> String {
+        format!("0x{}", byte.repeat(20))
+    }
+
+    #[test]
+    fn trace_filter_keeps_only_successful_plain_calls() {
+        let traces: Vec<FilterTrace> = serde_json::from_value(json!([
+            {
+                "action": {"callType": "call", "to": addr("aa"), "input": "0xa9059cbb"},
+                "blockHash": "0x01", "blockNumber": 7, "transactionHash": "0x02",
+                "transactionPosition": 3, "traceAddress": [0, 1], "type": "call",
+            },
+            {
+                "action": {"callType": "delegatecall", "to": addr("aa"), "input": "0xa9059cbb"},
+                "blockHash": "0x01", "blockNumber": 7, "transactionHash": "0x02",
+                "transactionPosition": 3, "traceAddress": [0, 2], "type": "call",
+            },
+            {
+                "action": {"callType": "call", "to": addr("aa"), "input": "0xa9059cbb"},
+                "blockHash": "0x01", "blockNumber": 7, "transactionHash": "0x02",
+                "transactionPosition": 3, "traceAddress": [1], "type": "call",
+                "error": "Reverted",
+            },
+            {
+                "action": {"author": addr("bb"), "rewardType": "block"},
+                "blockHash": "0x01", "blockNumber": 7, "traceAddress": [], "type": "reward",
+            },
+        ]))
+        .unwrap();
+        let calls: Vec<RawCall> = traces
+            .into_iter()
+            .filter_map(FilterTrace::into_call)
+            .collect();
+        assert_eq!(
+            calls,
+            vec![RawCall {
+                to: addr("aa"),
+                input: "0xa9059cbb".to_string(),
+                block_number: 7,
+                block_hash: "0x01".to_string(),
+                transaction_hash: "0x02".to_string(),
+                transaction_index: 3,
+                trace_address: vec![0, 1],
+            }]
+        );
+    }
+
+    #[test]
+    fn call_tracer_flattens_depth_first_with_trace_addresses() {
+        let txs: Vec<BlockTxTrace> = serde_json::from_value(json!([
+            {
+                "txHash": "0x0a",
+                "result": {
+                    "type": "CALL", "to": addr("aa"), "input": "0x01",
+                    "calls": [
+                        {"type": "STATICCALL", "to": addr("bb"), "input": "0x02"},
+                        {
+                            "type": "CALL", "to": addr("cc"), "input": "0x03",
+                            "calls": [{"type": "CALL", "to": addr("dd"), "input": "0x04"}],
+                        },
+                        {"type": "CALL", "to": addr("ee"), "input": "0x05", "error": "execution reverted"},
+                    ],
+                },
+            },
+            {"txHash": "0x0b", "result": {"type": "CREATE", "to": addr("ff"), "input": "0x06"}},
+        ]))
+        .unwrap();
+        let calls = flatten_block(9, txs).unwrap().calls;
+        let summary: Vec<(String, u64, Vec<u64>)> = calls
+            .iter()
+            .map(|c| {
+                (
+                    c.input.clone(),
+                    c.transaction_index,
+                    c.trace_address.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("0x01".to_string(), 0, vec![]),
+                ("0x03".to_string(), 0, vec![1]),
+                ("0x04".to_string(), 0, vec![1, 0]),
+            ]
+        );
+        assert!(calls
+            .iter()
+            .all(|c| c.block_number == 9 && c.block_hash.is_empty()));
+    }
+
+    #[test]
+    fn creations_come_out_of_both_trace_formats() {
+        let trace: FilterTrace = serde_json::from_value(json!({
+            "action": {"from": addr("aa"), "init": "0x60"},
+            "result": {"address": addr("bb"), "code": "0x"},
+            "blockNumber": 7,
+            "blockHash": "0x01",
+            "transactionHash": "0x02",
+            "transactionPosition": 3,
+            "traceAddress": [0],
+            "type": "create",
+        }))
+        .unwrap();
+        let expected = RawCreation {
+            from: addr("aa"),
+            address: addr("bb"),
+            block_number: 7,
+            block_hash: "0x01".to_string(),
+            transaction_hash: "0x02".to_string(),
+            transaction_index: 3,
+            trace_address: vec![0],
+        };
+        assert_eq!(trace.into_creation(), Some(expected.clone()));
+
+        let txs: Vec<BlockTxTrace> = serde_json::from_value(json!([
+            {"txHash": "0x00", "result": {"type": "CALL", "to": addr("aa"), "input": "0x01"}},
+            {"txHash": "0x00", "result": {"type": "CALL", "to": addr("aa"), "input": "0x01"}},
+            {"txHash": "0x00", "result": {"type": "CALL", "to": addr("aa"), "input": "0x01"}},
+            {
+                "txHash": "0x02",
+                "result": {
+                    "type": "CALL", "from": addr("ee"), "to": addr("aa"), "input": "0x01",
+                    "calls": [
+                        {"type": "CREATE2", "from": addr("aa"), "to": addr("bb"), "input": "0x60"},
+                        {"type": "CREATE", "from": addr("aa"), "to": addr("cc"), "input": "0x60", "error": "out of gas"},
+                    ],
+                },
+            },
+        ]))
+        .unwrap();
+        let traced = flatten_block(7, txs).unwrap();
+        assert_eq!(
+            traced.creations,
+            vec![RawCreation {
+                block_hash: String::new(),
+                ..expected
+            }]
+        );
+    }
+
+    #[test]
+    fn call_tracer_drops_everything_below_a_reverted_frame() {
+        let txs: Vec<BlockTxTrace> = serde_json::from_value(json!([
+            {
+                "txHash": "0x0a",
+                "result": {
+                    "type": "CALL", "to": addr("aa"), "input": "0x01",
+                    "calls": [
+                        {
+                            "type": "CALL", "to": addr("bb"), "input": "0x02", "error": "execution reverted",
+                            "calls": [
+                                {"type": "CALL", "to": addr("cc"), "input": "0x03"},
+                                {"type": "CREATE", "from": addr("bb"), "to": addr("dd"), "input": "0x60"},
+                            ],
+                        },
+                        {"type": "CALL", "to": addr("ee"), "input": "0x04"},
+                    ],
+                },
+            },
+            {
+                "txHash": "0x0b",
+                "result": {
+                    "type": "CALL", "to": addr("aa"), "input": "0x05", "error": "execution reverted",
+                    "calls": [{"type": "CALL", "to": addr("cc"), "input": "0x06"}],
+                },
+            },
+        ]))
+        .unwrap();
+        let traced = flatten_block(9, txs).unwrap();
+        let inputs: Vec<&str> = traced.calls.iter().map(|c| c.input.as_str()).collect();
+        assert_eq!(inputs, vec!["0x01", "0x04"]);
+        assert!(traced.creations.is_empty());
+    }
+
+    #[test]
+    fn trace_filter_rows_below_a_reverted_frame_are_dropped() {
+        let call = |transaction_hash: &str, trace_address: Vec<u64>| RawCall {
+            to: addr("cc"),
+            input: "0x03".to_string(),
+            block_number: 7,
+            block_hash: "0x01".to_string(),
+            transaction_hash: transaction_hash.to_string(),
+            transaction_index: 0,
+            trace_address,
+        };
+        let mut traced = Traced {
+            calls: vec![
+                call("0x0a", vec![0, 0]),
+                call("0x0a", vec![1, 0]),
+                call("0x0a", vec![0]),
+                call("0x0b", vec![0]),
+            ],
+            creations: vec![RawCreation {
+                from: addr("cc"),
+                address: addr("dd"),
+                block_number: 7,
+                block_hash: "0x01".to_string(),
+                transaction_hash: "0x0a".to_string(),
+                transaction_index: 0,
+                trace_address: vec![0, 0, 2],
+            }],
+        };
+        // `trace_transaction` of 0x0a: its first subcall reverted. The whole
+        // of 0x0b reverted.
+        let trace = |trace_address: Vec<u64>, error: Option<&str>| {
+            json!({
+                "action": {"callType": "call", "to": addr("cc"), "input": "0x03"},
+                "blockHash": "0x01", "blockNumber": 7, "transactionHash": "0x0a",
+                "transactionPosition": 0, "traceAddress": trace_address, "type": "call",
+                "error": error,
+            })
+        };
+        let tx_a: Vec<FilterTrace> = serde_json::from_value(json!([
+            trace(vec![], None),
+            trace(vec![0], Some("Reverted")),
+            trace(vec![0, 0], None),
+            trace(vec![1], None),
+            trace(vec![1, 0], None),
+        ]))
+        .unwrap();
+        let tx_b: Vec<FilterTrace> = serde_json::from_value(json!([
+            trace(vec![], Some("Reverted")),
+            trace(vec![0], None)
+        ]))
+        .unwrap();
+        let reverted = HashMap::from([
+            ("0x0a".to_string(), reverted_frames(tx_a)),
+            ("0x0b".to_string(), reverted_frames(tx_b)),
+        ]);
+        retain_outside_reverted_frames(&mut traced, &reverted);
+
+        let kept: Vec<(&str, &[u64])> = traced
+            .calls
+            .iter()
+            .map(|c| (c.transaction_hash.as_str(), c.trace_address.as_slice()))
+            .collect();
+        // The reverted frame itself was already dropped by `into_call`; only
+        // what's below one goes here.
+        assert_eq!(kept, vec![("0x0a", &[1, 0][..]), ("0x0a", &[0][..])]);
+        assert!(traced.creations.is_empty());
+    }
+
+    #[test]
+    fn call_tracer_without_tx_hash_errors() {
+        let txs: Vec<BlockTxTrace> = serde_json::from_value(json!([
+            {"result": {"type": "CALL", "to": addr("aa"), "input": "0x01"}},
+        ]))
+        .unwrap();
+        assert!(flatten_block(1, txs).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Transactions for `functions:` registrations on the RPC source. No RPC method
+//! filters transactions by callee and selector, so every block of the range is
+//! fetched with its transactions (`eth_getBlockByNumber` with full
+//! transactions) and matched here. Matches come out as the same `RawCall` rows
+//! as traced calls, with the empty trace address of a call tree's root; their
+//! status is judged with the rest of the `where.transaction` once the joins
+//! fetch the receipts. Factory registrations with `source: transaction` read
+//! the same blocks for deployments, as `RawCreation` rows.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::client::{parse_hex_u64, RpcError};
+use super::fields::fetch_all;
+use super::pool::ProviderPool;
+use super::traces::{RawCall, RawCreation};
+use crate::evm_hypersync_source::selection::{BuiltCallSelection, BuiltCreationSelection};
+use crate::request_stats::RequestStat;
+
+/// An `eth_getBlockByNumber` result with full transaction objects; only the
+/// fields matching reads.
+#[derive(Deserialize)]
+struct BlockWithTransactions {
+    hash: String,
+    number: String,
+    transactions: Vec<BlockTransaction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockTransaction {
+    hash: String,
+    from: String,
+    nonce: String,
+    // Absent on contract creations.
+    to: Option<String>,
+    input: String,
+    transaction_index: String,
+}
+
+/// Whether a transaction to `to` with `input` falls in one of the selections:
+/// a callee the selection lists (any, when it lists none) and one of its
+/// selectors. Addresses compare case-insensitively, since the set may hold
+/// checksummed ones.
+fn selected(selections: &[BuiltCallSelection], to: &str, input: &str) -> bool {
+    let Some(selector) = input.get(..10) else {
+        return false;
+    };
+    selections.iter().any(|selection| {
+        selection
+            .sighashes
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(selector))
+            && (selection.addresses.is_empty()
+                || selection
+                    .addresses
+                    .iter()
+                    .any(|a| a.eq_ignore_ascii_case(to)))
+    })
+}
+
+fn matching_calls(
+    blocks: Vec<BlockWithTransactions>,
+    selections: &[BuiltCallSelection],
+) -> anyhow::Result<Vec<RawCall>> {
+    let mut calls = Vec::new();
+    for block in blocks {
+        let block_number = parse_hex_u64(&block.number)?;
+        for tx in block.transactions {
+            let Some(to) = tx.to else { continue };
+            if !selected(selections, &to, &tx.input) {
+                continue;
+            }
+            calls.push(RawCall {
+                to,
+                input: tx.input,
+                block_number,
+                block_hash: block.hash.clone(),
+                transaction_hash: tx.hash,
+                transaction_index: parse_hex_u64(&tx.transaction_index)?,
+                trace_address: Vec::new(),
+            });
+        }
+    }
+    Ok(calls)
+}
+
+/// The deployments among the blocks' transactions by one of the selections'
+/// deployers (anyone, when a selection lists none). A creation transaction's
+/// contract address follows from its sender and nonce, so no receipt is
+/// needed; a reverted deployment is dropped with the `where.transaction`
+/// status once the joins fetch the receipts.
+fn matching_deploys(
+    blocks: &[BlockWithTransactions],
+    selections: &[BuiltCreationSelection],
+) -> anyhow::Result<Vec<RawCreation>> {
+    let mut creations = Vec::new();
+    for block in blocks {
+        let block_number = parse_hex_u64(&block.number)?;
+        for tx in &block.transactions {
+            if tx.to.is_some()
+                || !selections.iter().any(|selection| {
+                    selection.deployers.is_empty()
+                        || selection
+                            .deployers
+                            .iter()
+                            .any(|d| d.eq_ignore_ascii_case(&tx.from))
+                })
+            {
+                continue;
+            }
+            let from: alloy_primitives::Address = tx.from.parse()?;
+            creations.push(RawCreation {
+                from: tx.from.clone(),
+                address: from.create(parse_hex_u64(&tx.nonce)?).to_string(),
+                block_number,
+                block_hash: block.hash.clone(),
+                transaction_hash: tx.hash.clone(),
+                transaction_index: parse_hex_u64(&tx.transaction_index)?,
+                trace_address: Vec::new(),
+            });
+        }
+    }
+    Ok(creations)
+}
+
+/// Every transaction over `from..=to` the call selections match, and every
+/// deployment by the deploy selections' deployers, out of one fetch of the
+/// range's blocks. Routing re-applies the selector and address rules, so this
+/// only narrows what gets decoded.
+pub(super) async fn fetch_transactions(
+    client: &ProviderPool,
+    from_block: u64,
+    to_block: u64,
+    selections: &[BuiltCallSelection],
+    deploy_selections: &[BuiltCreationSelection],
+    stats: &mut Vec<RequestStat>,
+) -> Result<(Vec<RawCall>, Vec<RawCreation>), RpcError> {
+    if selections.is_empty() && deploy_selections.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let blocks: Vec<BlockWithTransactions> = fetch_all(
+        client,
+        "eth_getBlockByNumber",
+        (from_block..=to_block)
+            .map(|n| json!([format!("0x{n:x}"), true]))
+            .collect(),
+        stats,
+    )
+    .await?;
+    let creations = if deploy_selections.is_empty() {
+        Vec::new()
+    } else {
+        matching_deploys(&blocks, deploy_selections).map_err(RpcError::Other)?
+    };
+    let calls = matching_calls(blocks, selections).map_err(RpcError::Other)?;
+    Ok((calls, creations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployer() -> String {
+        format!("0x{}", "de".repeat(20))
+    }
+
+    #[test]
+    fn matches_transactions_by_callee_and_selector() {
+        let callee = format!("0x{}", "aa".repeat(20));
+        let blocks: Vec<BlockWithTransactions> = serde_json::from_value(json!([{
+            "hash": "0x01",
+            "number": "0x7",
+            "transactions": [
+                {"from": deployer(), "nonce": "0x0", "hash": "0x0a", "to": callee.to_uppercase().replace("0X", "0x"), "input": "0xa9059cbb0000", "transactionIndex": "0x0"},
+                {"from": deployer(), "nonce": "0x0", "hash": "0x0b", "to": callee, "input": "0x23b872dd", "transactionIndex": "0x1"},
+                {"from": deployer(), "nonce": "0x0", "hash": "0x0c", "to": format!("0x{}", "bb".repeat(20)), "input": "0xa9059cbb", "transactionIndex": "0x2"},
+                {"from": deployer(), "nonce": "0x0", "hash": "0x0d", "to": null, "input": "0xa9059cbb", "transactionIndex": "0x3"},
+                {"from": deployer(), "nonce": "0x0", "hash": "0x0e", "to": callee, "input": "0xa9", "transactionIndex": "0x4"},
+            ],
+        }]))
+        .unwrap();
+        let selections = [BuiltCallSelection {
+            addresses: vec![callee],
+            sighashes: vec!["0xa9059cbb".to_string()],
+            status: Some(1),
+        }];
+        let calls = matching_calls(blocks, &selections).unwrap();
+        assert_eq!(
+            calls
+                .iter()
+                .map(|c| (
+                    c.transaction_hash.as_str(),
+                    c.block_number,
+                    c.transaction_index,
+                    c.trace_address.is_empty()
+                ))
+                .collect::<Vec<_>>(),
+            vec![("0x0a", 7, 0, true)]
+        );
+    }
+
+    #[test]
+    fn derives_deployed_addresses_from_sender_and_nonce() {
+        let blocks: Vec<BlockWithTransactions> = serde_json::from_value(json!([{
+            "hash": "0x01",
+            "number": "0x7",
+            "transactions": [
+                {"from": deployer(), "nonce": "0x0", "hash": "0x0a", "to": null, "input": "0x60", "transactionIndex": "0x0"},
+                {"from": deployer(), "nonce": "0x1", "hash": "0x0b", "to": deployer(), "input": "0x", "transactionIndex": "0x1"},
+                {"from": format!("0x{}", "bb".repeat(20)), "nonce": "0x0", "hash": "0x0c", "to": null, "input": "0x60", "transactionIndex": "0x2"},
+            ],
+        }]))
+        .unwrap();
+        let selections = [BuiltCreationSelection {
+            deployers: vec![deployer().to_uppercase().replace("0X", "0x")],
+            status: Some(1),
+        }];
+        let creations = matching_deploys(&blocks, &selections).unwrap();
+        let expected = deployer()
+            .parse::<alloy_primitives::Address>()
+            .unwrap()
+            .create(0);
+        assert_eq!(
+            creations
+                .iter()
+                .map(|c| (c.transaction_hash.as_str(), c.address.clone()))
+                .collect::<Vec<_>>(),
+            vec![("0x0a", expected.to_string())]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Realtime head tracking over WebSocket. Subscribes to
+//! `eth_subscribe("newHeads")` and pushes every new head to JS as its height
+//! plus a one-block `BlockStore` page (the header, and its parent's hash as a
+//! minimal extra row), so the RPC source learns of a block without polling
+//! `eth_blockNumber` and already holds its hash when the range reaching it is
+//! fetched. Dropped connections reconnect with exponential backoff; after
+//! `RETRY_COUNT` consecutive failures the stream stops and `SourceManager`'s
+//! stale-subscription fallback takes over with HTTP polling.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use futures_util::{SinkExt, StreamExt};
+use napi::bindgen_prelude::FnArgs;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::Status;
+use napi_derive::napi;
+use serde::Deserialize;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+
+use super::fields::RpcBlock;
+use crate::block_store::BlockStore;
+use hypersync_client::simple_types;
+
+/// Consecutive failed connections (or subscription errors) before giving up.
+const RETRY_COUNT: u32 = 9;
+const BASE_BACKOFF_MILLIS: u64 = 125;
+/// Reconnect if no head arrives within this period. Detects silently dropped
+/// server-side subscriptions.
+const STALE_TIMEOUT: Duration = Duration::from_secs(60);
+
+const SUBSCRIBE_REQUEST: &str =
+    r#"{"jsonrpc":"2.0","id":1,"method":"eth_subscribe","params":["newHeads"]}"#;
+
+/// Called with `(height, page)` for every head. Weak, so an open subscription
+/// never keeps the process alive on its own.
+pub(super) type OnHead = ThreadsafeFunction<
+    FnArgs<(i64, BlockStore)>,
+    (),
+    FnArgs<(i64, BlockStore)>,
+    Status,
+    false,
+    true,
+>;
+
+pub(super) struct HeadStreamConfig {
+    pub url: String,
+    pub headers: Option<HashMap<String, String>>,
+    pub checksum_addresses: bool,
+}
+
+/// Handle to a running head stream; `unsubscribe` stops it and closes the
+/// connection.
+#[napi]
+pub struct HeadSubscription {
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[napi]
+impl HeadSubscription {
+    #[napi]
+    pub fn unsubscribe(&self) {
+        self.task.abort();
+    }
+}
+
+pub(super) fn subscribe(cfg: HeadStreamConfig, on_head: OnHead) -> HeadSubscription {
+    let task = napi::bindgen_prelude::spawn(run(cfg, on_head));
+    HeadSubscription { task }
+}
+
+async fn run(cfg: HeadStreamConfig, on_head: OnHead) {
+    let mut error_count = 0;
+    loop {
+        if stream_heads(&cfg, &on_head, &mut error_count)
+            .await
+            .is_err()
+        {
+            error_count += 1;
+        }
+        if error_count >= RETRY_COUNT {
+            return;
+        }
+        let backoff = BASE_BACKOFF_MILLIS << error_count;
+        tokio::time::sleep(Duration::from_millis(backoff)).await;
+    }
+}
+
+/// Runs one connection until it closes (`Ok`) or fails (`Err`). Any head
+/// received resets `error_count`, so only back-to-back failures exhaust the
+/// retries.
+async fn stream_heads(
+    cfg: &HeadStreamConfig,
+    on_head: &OnHead,
+    error_count: &mut u32,
+) -> anyhow::Result<()> {
+    let mut request = cfg
+        .url
+        .as_str()
+        .into_client_request()
+        .context("build WebSocket request")?;
+    for (name, value) in cfg.headers.iter().flatten() {
+        let header_name = HeaderName::try_from(name.as_str())
+            .with_context(|| format!("invalid RPC header name {name:?}"))?;
+        let header_value = HeaderValue::try_from(value.as_str())
+            .with_context(|| format!("invalid value for RPC header {name:?}"))?;
+        request.headers_mut().insert(header_name, header_value);
+    }
+    let (mut socket, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .context("connect WebSocket")?;
+    socket
+        .send(Message::text(SUBSCRIBE_REQUEST))
+        .await
+        .context("send eth_subscribe")?;
+
+    loop {
+        let message = match tokio::time::timeout(STALE_TIMEOUT, socket.next()).await {
+            // Stale or closed: reconnect without counting it as a failure.
+            Err(_) | Ok(None) => return Ok(()),
+            Ok(Some(message)) => message.context("read WebSocket message")?,
+        };
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => return Ok(()),
+            _ => continue,
+        };
+        match parse_message(&text) {
+            WsMessage::NewHead(block) => {
+                *error_count = 0;
+                let (height, page) = head_page(*block, cfg.checksum_addresses)?;
+                on_head.call(
+                    FnArgs::from((height, page)),
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+            WsMessage::Error(message) => anyhow::bail!("eth_subscribe failed: {message}"),
+            // Subscription confirmations and anything unrecognized.
+            WsMessage::Other => {}
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Notification {
+    method: String,
+    params: NotificationParams,
+}
+
+#[derive(Deserialize)]
+struct NotificationParams {
+    result: RpcBlock,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorObject,
+}
+
+#[derive(Deserialize)]
+struct ErrorObject {
+    message: String,
+}
+
+enum WsMessage {
+    NewHead(Box<RpcBlock>),
+    Error(String),
+    Other,
+}
+
+fn parse_message(text: &str) -> WsMessage {
+    if let Ok(notification) = serde_json::from_str::<Notification>(text) {
+        if notification.method == "eth_subscription" {
+            return WsMessage::NewHead(Box::new(notification.params.result));
+        }
+    }
+    match serde_json::from_str::<ErrorResponse>(text) {
+        Ok(response) => WsMessage::Error(response.error.message),
+        Err(_) => WsMessage::Other,
+    }
+}
+
+/// The head's page: the header itself and, like the block rows the RPC source
+/// builds from `eth_getBlockByNumber`, its parent's hash as an extra row.
+fn head_page(block: RpcBlock, checksum_addresses: bool) -> anyhow::Result<(i64, BlockStore)> {
+    let block = block.into_simple()?;
+    let number = block.number.context("newHeads block.number missing")?;
+    let height = i64::try_from(number).context("newHeads block.number exceeds i64::MAX")?;
+    let mut blocks = Vec::with_capacity(2);
+    if let (Some(parent_number), Some(parent_hash)) =
+        (number.checked_sub(1), block.parent_hash.clone())
+    {
+        blocks.push(simple_types::Block {
+            number: Some(parent_number),
+            hash: Some(parent_hash),
+            ..Default::default()
+        });
+    }
+    blocks.push(block);
+    let page = BlockStore::new_evm(checksum_addresses);
+    page.insert_evm_blocks(blocks);
+    Ok((height, page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_heads_confirmations_and_errors() {
+        let head = parse_message(
+            r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":"0x1",
+            "result":{"number":"0x1b4","hash":"0x0101010101010101010101010101010101010101010101010101010101010101",
+            "parentHash":"0x0202020202020202020202020202020202020202020202020202020202020202",
+            "timestamp":"0x64"}}}"#,
+        );
+        let WsMessage::NewHead(block) = head else {
+            panic!("expected a head");
+        };
+        let (height, page) = head_page(*block, false).unwrap();
+        assert_eq!(height, 436);
+        assert_eq!(
+            (page.get_hash(436), page.get_hash(435)),
+            (
+                Some(format!("0x{}", "01".repeat(32))),
+                Some(format!("0x{}", "02".repeat(32)))
+            )
+        );
+
+        assert!(matches!(
+            parse_message(r#"{"jsonrpc":"2.0","id":1,"result":"0xabc"}"#),
+            WsMessage::Other
+        ));
+        assert!(matches!(
+            parse_message(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"not supported"}}"#
+            ),
+            WsMessage::Error(message) if message == "not supported"
+        ));
+    }
+}
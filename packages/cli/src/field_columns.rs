@@ -13,6 +13,7 @@ use napi_derive::napi;
 use crate::evm_hypersync_source::types::{
     AccessList as AccessListItem, Authorization as AuthorizationItem,
 };
+use crate::field_table::{FieldMask, MAX_FIELDS};
 
 /// Ecosystem selecting `materialize`'s decoder, shared by every per-chain field
 /// store (`TransactionStore`, `BlockStore`). A store is per-chain, hence
@@ -41,6 +42,31 @@ pub fn field_names<F: Copy>(
     variants.iter().map(|&f| name(f).to_string()).collect()
 }
 
+/// Per-row selection masks from `materialize`'s JS `BigInt`s (bit `code` set ⇔
+/// field selected). A negative mask, or one selecting a code the store can't
+/// hold, is a caller bug rather than an empty selection.
+pub fn masks_from_js(masks: &[BigInt]) -> napi::Result<Vec<FieldMask>> {
+    masks
+        .iter()
+        .map(|m| {
+            if m.sign_bit {
+                return Err(napi::Error::from_reason("field mask must not be negative"));
+            }
+            FieldMask::from_words(&m.words).ok_or_else(|| {
+                napi::Error::from_reason(format!(
+                    "field mask selects a field code of {MAX_FIELDS} or above"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// `materialize`'s JS mask argument for the given bitmasks.
+#[cfg(test)]
+pub(crate) fn js_masks(masks: &[u64]) -> Vec<BigInt> {
+    masks.iter().map(|&m| BigInt::from(m)).collect()
+}
+
 #[napi(object)]
 #[derive(Clone)]
 pub struct SvmLamportsOut {
@@ -207,16 +233,16 @@ impl ToNapiValue for Columns {
 /// message.
 pub fn build_columns<F: Copy>(
     variants: &'static [F],
-    masks: &[u64],
+    masks: &[FieldMask],
     len: usize,
     ordinal: impl Fn(F) -> u32,
     name: impl Fn(F) -> &'static str,
     decode: impl Fn(F) -> Result<Column>,
 ) -> Result<Columns> {
-    let union = masks.iter().fold(0u64, |acc, &m| acc | m);
+    let union = masks.iter().fold(FieldMask::NONE, |acc, &m| acc | m);
     let mut columns: Vec<(&'static str, Column)> = Vec::new();
     for &field in variants {
-        if union & (1u64 << ordinal(field)) == 0 {
+        if !union.has(ordinal(field) as usize) {
             continue;
         }
         let field_name = name(field);
@@ -0,0 +1,131 @@
+//! The append-only file a `field_table::Table` pages cold cells out to once its
+//! in-memory budget is exceeded. Records are written once and never updated in
+//! place: a cell merged again, a pruned row or a rolled-back one just leaves its
+//! record dead, and the file is compacted once dead records make up most of it.
+//! The file belongs to one process run — it is truncated when opened and
+//! removed on drop, so nothing in it survives a restart.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Below this size a file isn't worth compacting, however much of it is dead.
+const MIN_COMPACT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Where one record sits in the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SpillRef {
+    offset: u64,
+    len: u32,
+}
+
+pub(crate) struct SpillFile {
+    // Reads go through the shared reference a gather holds, so the cursor
+    // needs its own lock.
+    file: Mutex<File>,
+    path: PathBuf,
+    end: u64,
+    dead: u64,
+}
+
+impl SpillFile {
+    /// Opens `path` empty, creating its directory when missing.
+    pub(crate) fn create(path: PathBuf) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = open_truncated(&path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+            path,
+            end: 0,
+            dead: 0,
+        })
+    }
+
+    pub(crate) fn append(&mut self, record: &[u8]) -> io::Result<SpillRef> {
+        let len = u32::try_from(record.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "spill record over 4 GiB"))?;
+        let file = self.file.get_mut().unwrap();
+        file.seek(SeekFrom::Start(self.end))?;
+        file.write_all(record)?;
+        let at = SpillRef {
+            offset: self.end,
+            len,
+        };
+        self.end += u64::from(len);
+        Ok(at)
+    }
+
+    pub(crate) fn read(&self, at: SpillRef) -> io::Result<Vec<u8>> {
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start(at.offset))?;
+        let mut record = vec![0; at.len as usize];
+        file.read_exact(&mut record)?;
+        Ok(record)
+    }
+
+    /// Marks a record dead: nothing reads it again.
+    pub(crate) fn release(&mut self, at: SpillRef) {
+        self.dead += u64::from(at.len);
+    }
+
+    /// Drops every record at once, for a table left with nothing spilled. If
+    /// the file can't be truncated, its records are all counted dead instead,
+    /// so the next compaction reclaims them.
+    pub(crate) fn reset(&mut self) {
+        if self.end == 0 {
+            return;
+        }
+        if self.file.get_mut().unwrap().set_len(0).is_ok() {
+            self.end = 0;
+            self.dead = 0;
+        } else {
+            self.dead = self.end;
+        }
+    }
+
+    /// Whether dead records make up most of a file big enough to bother.
+    pub(crate) fn should_compact(&self) -> bool {
+        self.end >= MIN_COMPACT_BYTES && self.dead * 2 > self.end
+    }
+
+    /// Rewrites the live records into a fresh file, in the order given,
+    /// repointing each ref to its new place.
+    pub(crate) fn compact<'a>(
+        &mut self,
+        live: impl Iterator<Item = &'a mut SpillRef>,
+    ) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("compacting");
+        let mut tmp = open_truncated(&tmp_path)?;
+        let mut end = 0;
+        for at in live {
+            let record = self.read(*at)?;
+            tmp.write_all(&record)?;
+            at.offset = end;
+            end += u64::from(at.len);
+        }
+        tmp.flush()?;
+        fs::rename(&tmp_path, &self.path)?;
+        *self.file.get_mut().unwrap() = tmp;
+        self.end = end;
+        self.dead = 0;
+        Ok(())
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn open_truncated(path: &PathBuf) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
@@ -4,11 +4,19 @@
 //! overlapping partitions overwrites in place instead of accumulating a copy
 //! per response, and a batch that only carries a subset of fields unions it
 //! into whatever the key already has (newest write per field wins).
+//!
+//! A persistent table can also page its cold cells out to disk past an
+//! in-memory budget (see `enable_spill`).
 
 use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not};
+use std::path::PathBuf;
 
 use hypersync_client::format;
 
+use crate::field_spill::{SpillFile, SpillRef};
+
 /// Validity bitmap: one bit per row, set when the row's cell holds a value.
 pub(crate) struct BitVec {
     words: Vec<u64>,
@@ -300,6 +308,35 @@ impl AnyCol {
             AnyCol::AuthLists(c) => c.push(None),
         }
     }
+
+    /// Append a cell read back from a spill record, as `StoreCol::encode_cell`
+    /// wrote it.
+    fn push_encoded(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("spilled cell: {e}"))
+        };
+        match self {
+            AnyCol::Var(c) => c.push(Some(bytes)),
+            AnyCol::Str(c) => c.push(Some(std::str::from_utf8(bytes).map_err(|e| invalid(&e))?)),
+            AnyCol::HashList(c) => c.push(Some(
+                bytes
+                    .chunks_exact(32)
+                    .map(|h| h.try_into().unwrap())
+                    .collect(),
+            )),
+            AnyCol::StrList(c) => c.push(Some(
+                serde_json::from_slice(bytes).map_err(|e| invalid(&e))?,
+            )),
+            AnyCol::AccessLists(c) => c.push(Some(
+                serde_json::from_slice(bytes).map_err(|e| invalid(&e))?,
+            )),
+            AnyCol::AuthLists(c) => c.push(Some(
+                serde_json::from_slice(bytes).map_err(|e| invalid(&e))?,
+            )),
+            _ => return Err(invalid(&"column kind never spills")),
+        }
+        Ok(())
+    }
 }
 
 /// One field's column, addressed by table slot. Variable-width and list cells
@@ -491,6 +528,211 @@ impl StoreCol {
             _ => panic!("expected a byte-backed column"),
         }
     }
+
+    /// Whether the column's cells are individually boxed, so paging one out
+    /// frees memory. Numeric and fixed-width columns are dense per slot and
+    /// never spill.
+    fn spillable(&self) -> bool {
+        !matches!(
+            self,
+            StoreCol::U64(_)
+                | StoreCol::I64(_)
+                | StoreCol::F64(_)
+                | StoreCol::Bool(_)
+                | StoreCol::Fixed { .. }
+        )
+    }
+
+    /// Heap bytes a boxed cell holds, roughly; what the spill budget counts.
+    fn cell_size(&self, slot: usize) -> usize {
+        match self {
+            StoreCol::Var(v) => v[slot].as_ref().map_or(0, |b| b.len()),
+            StoreCol::Str(v) => v[slot].as_ref().map_or(0, |s| s.len()),
+            StoreCol::StrList(v) => v[slot].as_ref().map_or(0, |l| {
+                l.iter()
+                    .map(|s| s.len() + std::mem::size_of::<String>())
+                    .sum()
+            }),
+            StoreCol::HashList(v) => v[slot].as_ref().map_or(0, |l| l.len() * 32),
+            StoreCol::AccessLists(v) => v[slot].as_ref().map_or(0, |l| {
+                l.iter()
+                    .map(|a| {
+                        std::mem::size_of::<format::AccessList>()
+                            + a.storage_keys.as_ref().map_or(0, |k| k.len() * 32)
+                    })
+                    .sum()
+            }),
+            StoreCol::AuthLists(v) => v[slot].as_ref().map_or(0, |l| {
+                l.len() * std::mem::size_of::<format::Authorization>()
+            }),
+            _ => 0,
+        }
+    }
+
+    /// Append `slot`'s boxed cell to a spill record. Caller must check the
+    /// row's mask bit and `spillable` first.
+    fn encode_cell(&self, slot: usize, out: &mut Vec<u8>) {
+        match self {
+            StoreCol::Var(v) => out.extend_from_slice(v[slot].as_deref().unwrap_or_default()),
+            StoreCol::Str(v) => {
+                out.extend_from_slice(v[slot].as_deref().unwrap_or_default().as_bytes())
+            }
+            StoreCol::HashList(v) => {
+                for hash in v[slot].as_deref().unwrap_or_default() {
+                    out.extend_from_slice(hash);
+                }
+            }
+            StoreCol::StrList(v) => serde_json::to_writer(&mut *out, &v[slot]).unwrap(),
+            StoreCol::AccessLists(v) => serde_json::to_writer(&mut *out, &v[slot]).unwrap(),
+            StoreCol::AuthLists(v) => serde_json::to_writer(&mut *out, &v[slot]).unwrap(),
+            _ => panic!("column kind never spills"),
+        }
+    }
+}
+
+/// Words in a `FieldMask`. Raising it, up to the 4 words a spilled record's
+/// one-byte field tag can address, is all a table needs to hold more fields;
+/// every mask carries this many words whatever its table uses.
+const MASK_WORDS: usize = 2;
+
+/// Most fields a `Table` holds: one bit each in a `FieldMask`.
+pub(crate) const MAX_FIELDS: usize = 64 * MASK_WORDS;
+
+/// A set of field codes — the fields a row holds, or the ones a caller selects.
+/// Bit `f % 64` of word `f / 64` is field `f`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FieldMask([u64; MASK_WORDS]);
+
+impl FieldMask {
+    pub(crate) const NONE: Self = Self([0; MASK_WORDS]);
+    const ALL: Self = Self([u64::MAX; MASK_WORDS]);
+
+    pub(crate) fn bit(field: usize) -> Self {
+        let mut mask = Self::NONE;
+        mask.0[field / 64] = 1 << (field % 64);
+        mask
+    }
+
+    pub(crate) fn of(fields: impl IntoIterator<Item = usize>) -> Self {
+        fields
+            .into_iter()
+            .fold(Self::NONE, |acc, f| acc | Self::bit(f))
+    }
+
+    /// From little-endian 64-bit words (a JS `BigInt`'s); `None` when a bit at
+    /// or past `MAX_FIELDS` is set.
+    pub(crate) fn from_words(words: &[u64]) -> Option<Self> {
+        let (head, rest) = words.split_at(words.len().min(MASK_WORDS));
+        if rest.iter().any(|&w| w != 0) {
+            return None;
+        }
+        let mut mask = Self::NONE;
+        mask.0[..head.len()].copy_from_slice(head);
+        Some(mask)
+    }
+
+    pub(crate) fn has(self, field: usize) -> bool {
+        self.0[field / 64] & (1 << (field % 64)) != 0
+    }
+
+    pub(crate) fn is_empty(self) -> bool {
+        self == Self::NONE
+    }
+
+    pub(crate) fn intersects(self, other: Self) -> bool {
+        !(self & other).is_empty()
+    }
+}
+
+impl BitOr for FieldMask {
+    type Output = Self;
+    fn bitor(mut self, other: Self) -> Self {
+        self |= other;
+        self
+    }
+}
+
+impl BitOrAssign for FieldMask {
+    fn bitor_assign(&mut self, other: Self) {
+        for (w, o) in self.0.iter_mut().zip(other.0) {
+            *w |= o;
+        }
+    }
+}
+
+impl BitAnd for FieldMask {
+    type Output = Self;
+    fn bitand(mut self, other: Self) -> Self {
+        for (w, o) in self.0.iter_mut().zip(other.0) {
+            *w &= o;
+        }
+        self
+    }
+}
+
+impl Not for FieldMask {
+    type Output = Self;
+    fn not(mut self) -> Self {
+        for w in &mut self.0 {
+            *w = !*w;
+        }
+        self
+    }
+}
+
+/// A slot whose cells were paged out: its record, and which of the record's
+/// fields are still read from it. A field merged again since is read from
+/// memory, and the record dies once no field is left.
+struct SpilledRow {
+    at: SpillRef,
+    fields: FieldMask,
+}
+
+/// A table's disk tier. Past `budget_bytes` of boxed cells in memory, the rows
+/// with the highest keys — the last the processor reaches — are paged out, and
+/// `gather_scratch` reads them back.
+struct Spill {
+    file: SpillFile,
+    budget_bytes: usize,
+    /// Fields that never spill: the ones read by reference (`field_bytes`).
+    pinned: FieldMask,
+    /// Per slot, the heap bytes of its in-memory spillable cells.
+    row_bytes: Vec<usize>,
+    mem_bytes: usize,
+    rows: HashMap<u32, SpilledRow>,
+}
+
+impl Spill {
+    fn drop_fields(&mut self, slot: u32, fields: FieldMask) {
+        if let Some(row) = self.rows.get_mut(&slot) {
+            row.fields = row.fields & !fields;
+            if row.fields.is_empty() {
+                let at = row.at;
+                self.rows.remove(&slot);
+                self.file.release(at);
+            }
+        }
+    }
+
+    fn spilled_fields(&self, slot: u32) -> FieldMask {
+        self.rows
+            .get(&slot)
+            .map_or(FieldMask::NONE, |row| row.fields)
+    }
+}
+
+/// One field's cell in a spill record: a sequence of `(field: u8, len: u32 LE,
+/// cell)` entries.
+fn record_cell(mut record: &[u8], field: usize) -> Option<&[u8]> {
+    while record.len() >= 5 {
+        let len = u32::from_le_bytes(record[1..5].try_into().unwrap()) as usize;
+        let (cell, rest) = record[5..].split_at(len);
+        if record[0] as usize == field {
+            return Some(cell);
+        }
+        record = rest;
+    }
+    None
 }
 
 /// Merge-on-insert columnar table: one slot per distinct key. `by_key` backs
@@ -500,18 +742,21 @@ impl StoreCol {
 pub(crate) struct Table<K> {
     by_key: HashMap<K, u32>,
     order: BTreeMap<K, u32>,
-    masks: Vec<u64>,
+    masks: Vec<FieldMask>,
     cols: Vec<Option<StoreCol>>,
     free: Vec<u32>,
     len: usize,
     n_fields: usize,
+    spill: Option<Spill>,
 }
 
 impl<K: Ord + Clone + std::hash::Hash> Table<K> {
     pub(crate) fn new(n_fields: usize) -> Self {
-        // Field masks are single u64 bitsets; a 65th field would silently
-        // corrupt them in release builds.
-        assert!(n_fields <= 64, "Table supports at most 64 fields");
+        // A field past the mask width would silently alias another's bit.
+        assert!(
+            n_fields <= MAX_FIELDS,
+            "Table supports at most {MAX_FIELDS} fields"
+        );
         Self {
             by_key: HashMap::new(),
             order: BTreeMap::new(),
@@ -520,6 +765,7 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
             free: Vec::new(),
             len: 0,
             n_fields,
+            spill: None,
         }
     }
 
@@ -532,7 +778,10 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
                 for col in self.cols.iter_mut().flatten() {
                     col.push_empty();
                 }
-                self.masks.push(0);
+                self.masks.push(FieldMask::NONE);
+                if let Some(spill) = &mut self.spill {
+                    spill.row_bytes.push(0);
+                }
                 slot
             }
         }
@@ -562,8 +811,110 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
         for col in self.cols.iter_mut().flatten() {
             col.clear(slot as usize);
         }
-        self.masks[slot as usize] = 0;
+        self.masks[slot as usize] = FieldMask::NONE;
         self.free.push(slot);
+        if let Some(spill) = &mut self.spill {
+            spill.drop_fields(slot, FieldMask::ALL);
+            spill.mem_bytes -= std::mem::take(&mut spill.row_bytes[slot as usize]);
+        }
+    }
+
+    /// Re-count `slot`'s in-memory spillable cells after `fields` were written
+    /// to (or cleared in) memory: the record no longer holds those.
+    fn account_row(&mut self, slot: u32, fields: FieldMask) {
+        let Some(spill) = &mut self.spill else {
+            return;
+        };
+        spill.drop_fields(slot, fields);
+        let in_memory = self.masks[slot as usize] & !spill.pinned & !spill.spilled_fields(slot);
+        let bytes = (0..self.n_fields)
+            .filter(|&f| in_memory.has(f))
+            .filter_map(|f| self.cols[f].as_ref())
+            .map(|col| col.cell_size(slot as usize))
+            .sum();
+        let row_bytes = &mut spill.row_bytes[slot as usize];
+        spill.mem_bytes = spill.mem_bytes - *row_bytes + bytes;
+        *row_bytes = bytes;
+    }
+
+    /// Page cold cells out to `path` from now on, whenever the table holds
+    /// more than `budget_bytes` of them in memory. `pinned` fields always stay
+    /// in memory: `field_bytes` reads them by reference.
+    pub(crate) fn enable_spill(
+        &mut self,
+        path: PathBuf,
+        budget_bytes: usize,
+        pinned: &[usize],
+    ) -> io::Result<()> {
+        self.spill = Some(Spill {
+            file: SpillFile::create(path)?,
+            budget_bytes,
+            pinned: FieldMask::of(pinned.iter().copied()),
+            row_bytes: vec![0; self.len],
+            mem_bytes: 0,
+            rows: HashMap::new(),
+        });
+        let slots: Vec<u32> = self.order.values().copied().collect();
+        for slot in slots {
+            self.account_row(slot, FieldMask::NONE);
+        }
+        Ok(())
+    }
+
+    /// Bring the table back under its spill budget, paging out the spillable
+    /// cells of the rows with the highest keys first. A row some of whose
+    /// fields were merged again after it spilled keeps those in memory. A
+    /// no-op without `enable_spill`.
+    pub(crate) fn spill_cold_rows(&mut self) -> io::Result<()> {
+        let Some(spill) = &mut self.spill else {
+            return Ok(());
+        };
+        if spill.rows.is_empty() {
+            spill.file.reset();
+        }
+        // A spilled cell is tagged with its field code in one byte.
+        const _: () = assert!(MAX_FIELDS <= 256);
+        let mut record = Vec::new();
+        for &slot in self.order.values().rev() {
+            if spill.mem_bytes <= spill.budget_bytes {
+                break;
+            }
+            let s = slot as usize;
+            if spill.row_bytes[s] == 0 || spill.rows.contains_key(&slot) {
+                continue;
+            }
+            record.clear();
+            let mut fields = FieldMask::NONE;
+            for f in 0..self.n_fields {
+                let Some(col) = self.cols[f].as_ref() else {
+                    continue;
+                };
+                if !(self.masks[s] & !spill.pinned).has(f) || !col.spillable() {
+                    continue;
+                }
+                record.push(f as u8);
+                let len_at = record.len();
+                record.extend_from_slice(&[0; 4]);
+                col.encode_cell(s, &mut record);
+                let len = (record.len() - len_at - 4) as u32;
+                record[len_at..len_at + 4].copy_from_slice(&len.to_le_bytes());
+                fields |= FieldMask::bit(f);
+            }
+            let at = spill.file.append(&record)?;
+            for f in 0..self.n_fields {
+                if fields.has(f) {
+                    self.cols[f].as_mut().unwrap().clear(s);
+                }
+            }
+            spill.rows.insert(slot, SpilledRow { at, fields });
+            spill.mem_bytes -= std::mem::take(&mut spill.row_bytes[s]);
+        }
+        if spill.file.should_compact() {
+            spill
+                .file
+                .compact(spill.rows.values_mut().map(|row| &mut row.at))?;
+        }
+        Ok(())
     }
 
     /// Merge one batch's rows into the table, per field: a field the batch
@@ -574,21 +925,26 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
         debug_assert_eq!(cols.len(), self.n_fields);
         for (row, key) in keys.into_iter().enumerate() {
             let slot = self.slot_for(key) as usize;
+            let mut written = FieldMask::NONE;
             for (f, col_opt) in cols.iter().enumerate() {
                 if let Some(col) = col_opt {
                     if col.is_valid(row) {
                         self.ensure_col(f, col);
                         self.cols[f].as_mut().unwrap().set_from(slot, col, row);
-                        self.masks[slot] |= 1u64 << f;
+                        self.masks[slot] |= FieldMask::bit(f);
+                        written |= FieldMask::bit(f);
                     }
                 }
             }
+            self.account_row(slot as u32, written);
         }
     }
 
     /// Move every live row from `other` into this table (a fetch-response page
     /// merging into the persistent per-chain table). `other` is left empty.
     pub(crate) fn append_from(&mut self, other: &mut Table<K>) {
+        // Pages are short-lived and never spill, so every cell is in memory.
+        assert!(other.spill.is_none(), "cannot append from a spilling table");
         let live_keys: Vec<K> = other.order.keys().cloned().collect();
         if live_keys.is_empty() {
             return;
@@ -599,7 +955,7 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
                 other.cols[f].as_ref().map(|col| {
                     let mut out = col.new_scratch();
                     for &slot in &slots {
-                        if other.masks[slot as usize] & (1u64 << f) != 0 {
+                        if other.masks[slot as usize].has(f) {
                             col.copy_to(&mut out, slot as usize);
                         } else {
                             out.push_missing();
@@ -617,14 +973,37 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
     /// field.
     pub(crate) fn field_bytes(&self, key: &K, field: usize) -> Option<&[u8]> {
         let &slot = self.by_key.get(key)?;
-        if self.masks[slot as usize] & (1u64 << field) == 0 {
+        if !self.masks[slot as usize].has(field) {
             return None;
         }
+        debug_assert!(
+            self.spill
+                .as_ref()
+                .is_none_or(|spill| !spill.spilled_fields(slot).has(field)),
+            "field_bytes reads an unpinned field of a spilled row"
+        );
         self.cols[field]
             .as_ref()
             .and_then(|c| c.cell_bytes(slot as usize))
     }
 
+    /// A numeric `field` of `key`'s row, if the row exists and carries it.
+    pub(crate) fn field_u64(&self, key: &K, field: usize) -> Option<u64> {
+        let &slot = self.by_key.get(key)?;
+        if !self.masks[slot as usize].has(field) {
+            return None;
+        }
+        self.cols[field].as_ref().map(|c| c.u64_cell(slot as usize))
+    }
+
+    /// The lowest key above `key`, whether or not `key` itself has a row.
+    pub(crate) fn key_after(&self, key: &K) -> Option<&K> {
+        self.order
+            .range((std::ops::Bound::Excluded(key), std::ops::Bound::Unbounded))
+            .next()
+            .map(|(k, _)| k)
+    }
+
     /// Lowest key `>= from` carrying `field` in both tables whose cells differ,
     /// with the two conflicting values.
     pub(crate) fn first_field_mismatch(
@@ -677,16 +1056,17 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
         best
     }
 
-    /// Strip every field but `field` from `slot`, leaving a hash-only row still
-    /// readable for reorg detection after the rest of the row is gone.
-    fn reduce_row_to_field(&mut self, slot: u32, field: usize) {
+    /// Strip every field outside `keep` from `slot`, leaving a hash-only row
+    /// still readable for reorg detection after the rest of the row is gone.
+    fn reduce_row_to_fields(&mut self, slot: u32, keep: FieldMask) {
         let mask = self.masks[slot as usize];
         for f in 0..self.n_fields {
-            if f != field && mask & (1u64 << f) != 0 {
+            if !keep.has(f) && mask.has(f) {
                 self.cols[f].as_mut().unwrap().clear(slot as usize);
             }
         }
-        self.masks[slot as usize] = 1u64 << field;
+        self.masks[slot as usize] = mask & keep;
+        self.account_row(slot, !keep);
     }
 
     fn drop_row(&mut self, key: &K, slot: u32) {
@@ -695,16 +1075,27 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
         self.free_slot(slot);
     }
 
+    /// Every live key, ascending.
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &K> {
+        self.order.keys()
+    }
+
+    /// Drop `key`'s row, if any (taken by a consumer).
+    pub(crate) fn remove(&mut self, key: &K) {
+        if let Some(&slot) = self.by_key.get(key) {
+            self.drop_row(key, slot);
+        }
+    }
+
     /// Drop rows with keys `<= up_to` (processed), except rows with keys
-    /// `>= keep_from` that carry `field`: those are reduced to that one field,
-    /// so it stays readable after the rest of the row is gone.
-    pub(crate) fn prune_keeping_field(&mut self, up_to: K, keep_from: K, field: usize) {
-        let bit = 1u64 << field;
+    /// `>= keep_from` that carry one of the `keep` fields: those are reduced to
+    /// them, so they stay readable after the rest of the row is gone.
+    pub(crate) fn prune_keeping_fields(&mut self, up_to: K, keep_from: K, keep: FieldMask) {
         let pruned: Vec<K> = self.order.range(..=up_to).map(|(k, _)| k.clone()).collect();
         for k in pruned {
             let slot = self.by_key[&k];
-            if k >= keep_from && self.masks[slot as usize] & bit != 0 {
-                self.reduce_row_to_field(slot, field);
+            if k >= keep_from && self.masks[slot as usize].intersects(keep) {
+                self.reduce_row_to_fields(slot, keep);
             } else {
                 self.drop_row(&k, slot);
             }
@@ -713,10 +1104,9 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
 
     /// Keys in `[from, below)` whose row carries `field`, ascending.
     pub(crate) fn keys_with_field(&self, from: K, below: K, field: usize) -> Vec<K> {
-        let bit = 1u64 << field;
         self.order
             .range(from..below)
-            .filter(|(_, &slot)| self.masks[slot as usize] & bit != 0)
+            .filter(|(_, &slot)| self.masks[slot as usize].has(field))
             .map(|(k, _)| k.clone())
             .collect()
     }
@@ -759,36 +1149,71 @@ impl<K: Ord + Clone + std::hash::Hash> Table<K> {
         self.cols = (0..self.n_fields).map(|_| None).collect();
         self.free.clear();
         self.len = 0;
+        if let Some(spill) = &mut self.spill {
+            spill.row_bytes.clear();
+            spill.mem_bytes = 0;
+            spill.rows.clear();
+            spill.file.reset();
+        }
     }
 
     /// The per-field scratch a `materialize` call decodes from: resolves each
     /// requested key to its slot once, then gathers a column per field whose
     /// bit is set in any row's mask, respecting both the caller's per-row mask
-    /// and the slot's own field presence.
-    pub(crate) fn gather_scratch(&self, keys: &[Option<K>], masks: &[u64]) -> Vec<Option<AnyCol>> {
+    /// and the slot's own field presence. Spilled cells are read back from
+    /// disk, one record read per row.
+    pub(crate) fn gather_scratch(
+        &self,
+        keys: &[Option<K>],
+        masks: &[FieldMask],
+    ) -> io::Result<Vec<Option<AnyCol>>> {
         let slots: Vec<Option<u32>> = keys
             .iter()
             .map(|k| k.as_ref().and_then(|k| self.by_key.get(k)).copied())
             .collect();
-        let union = masks.iter().fold(0u64, |acc, &m| acc | m);
+        let union = masks.iter().fold(FieldMask::NONE, |acc, &m| acc | m);
+        let mut records: HashMap<u32, Vec<u8>> = HashMap::new();
+        if let Some(spill) = &self.spill {
+            for (i, slot) in slots.iter().enumerate() {
+                let Some(slot) = *slot else { continue };
+                if let Some(row) = spill.rows.get(&slot) {
+                    if row.fields.intersects(masks[i]) && !records.contains_key(&slot) {
+                        records.insert(slot, spill.file.read(row.at)?);
+                    }
+                }
+            }
+        }
         (0..self.n_fields)
             .map(|f| {
-                let bit = 1u64 << f;
-                if union & bit == 0 {
-                    return None;
+                if !union.has(f) {
+                    return Ok(None);
                 }
-                self.cols[f].as_ref().map(|col| {
-                    let mut out = col.new_scratch();
-                    for (i, slot) in slots.iter().enumerate() {
-                        let present = masks[i] & bit != 0
-                            && slot.is_some_and(|s| self.masks[s as usize] & bit != 0);
-                        match (present, slot) {
-                            (true, Some(s)) => col.copy_to(&mut out, *s as usize),
-                            _ => out.push_missing(),
+                let Some(col) = self.cols[f].as_ref() else {
+                    return Ok(None);
+                };
+                let mut out = col.new_scratch();
+                for (i, slot) in slots.iter().enumerate() {
+                    let present =
+                        masks[i].has(f) && slot.is_some_and(|s| self.masks[s as usize].has(f));
+                    let Some(s) = slot.filter(|_| present) else {
+                        out.push_missing();
+                        continue;
+                    };
+                    let spilled = self
+                        .spill
+                        .as_ref()
+                        .is_some_and(|spill| spill.spilled_fields(s).has(f));
+                    if spilled {
+                        let cell = records.get(&s).and_then(|record| record_cell(record, f));
+                        match cell {
+                            Some(cell) => out.push_encoded(cell)?,
+                            None => out.push_missing(),
                         }
+                    } else {
+                        col.copy_to(&mut out, s as usize);
                     }
-                    out
-                })
+                }
+                Ok(Some(out))
             })
             .collect()
     }
@@ -815,7 +1240,7 @@ impl Table<(u64, u32, Box<str>)> {
     /// Raw string bytes for one account-activity field at `slot`, or `None` if
     /// the field was never populated for that row.
     pub(crate) fn var_cell(&self, field: usize, slot: u32) -> Option<&[u8]> {
-        if self.masks[slot as usize] & (1u64 << field) == 0 {
+        if !self.masks[slot as usize].has(field) {
             return None;
         }
         self.cols[field]
@@ -826,7 +1251,7 @@ impl Table<(u64, u32, Box<str>)> {
     /// One numeric field at `slot`, or `None` if it was never populated for
     /// that row.
     pub(crate) fn u64_cell(&self, field: usize, slot: u32) -> Option<u64> {
-        if self.masks[slot as usize] & (1u64 << field) == 0 {
+        if !self.masks[slot as usize].has(field) {
             return None;
         }
         self.cols[field].as_ref().map(|c| c.u64_cell(slot as usize))
@@ -835,7 +1260,7 @@ impl Table<(u64, u32, Box<str>)> {
     /// One boolean field at `slot`, or `None` if it was never populated for
     /// that row.
     pub(crate) fn bool_cell(&self, field: usize, slot: u32) -> Option<bool> {
-        if self.masks[slot as usize] & (1u64 << field) == 0 {
+        if !self.masks[slot as usize].has(field) {
             return None;
         }
         self.cols[field]
@@ -1092,8 +1517,8 @@ mod tests {
 
     fn gathered_u64(table: &Table<u64>, keys: &[u64]) -> Vec<Option<u64>> {
         let keys: Vec<Option<u64>> = keys.iter().map(|&k| Some(k)).collect();
-        let masks = vec![1u64; keys.len()];
-        match &table.gather_scratch(&keys, &masks)[0] {
+        let masks = vec![FieldMask::bit(0); keys.len()];
+        match &table.gather_scratch(&keys, &masks).unwrap()[0] {
             Some(AnyCol::U64(c)) => (0..keys.len()).map(|i| c.get(i)).collect(),
             Some(_) => panic!("wrong column kind"),
             None => vec![None; keys.len()],
@@ -1201,8 +1626,8 @@ mod tests {
         table.merge_batch(vec![7], vec![Some(AnyCol::U64(num)), None]);
 
         let keys = vec![Some(7u64)];
-        let masks = vec![0b11u64];
-        let scratch = table.gather_scratch(&keys, &masks);
+        let masks = vec![FieldMask::of([0, 1])];
+        let scratch = table.gather_scratch(&keys, &masks).unwrap();
         let num_cell = match &scratch[0] {
             Some(AnyCol::U64(c)) => c.get(0),
             _ => panic!("expected num column"),
@@ -1250,8 +1675,8 @@ mod tests {
         table.merge_batch(keys, cols);
 
         let keys = vec![Some(1u64), Some(2), Some(9)];
-        let masks = vec![1u64, 0, 1];
-        match &table.gather_scratch(&keys, &masks)[0] {
+        let masks = vec![FieldMask::bit(0), FieldMask::NONE, FieldMask::bit(0)];
+        match &table.gather_scratch(&keys, &masks).unwrap()[0] {
             Some(AnyCol::U64(c)) => {
                 assert_eq!((c.get(0), c.get(1), c.get(2)), (Some(10), None, None))
             }
@@ -1331,4 +1756,135 @@ mod tests {
         table.merge_batch(keys, cols);
         assert_eq!((table.len, gathered_u64(&table, &[9])), (1, vec![Some(90)]));
     }
+
+    #[test]
+    fn fields_past_the_first_mask_word_round_trip() {
+        let mut table = Table::new(70);
+        let mut cols: Vec<Option<AnyCol>> = (0..70).map(|_| None).collect();
+        let mut num = NumCol::new();
+        num.push(Some(66));
+        cols[66] = Some(AnyCol::U64(num));
+        table.merge_batch(vec![1u64], cols);
+
+        let keys = vec![Some(1u64), Some(1)];
+        let masks = vec![FieldMask::bit(66), FieldMask::bit(2)];
+        match &table.gather_scratch(&keys, &masks).unwrap()[66] {
+            Some(AnyCol::U64(c)) => assert_eq!((c.get(0), c.get(1)), (Some(66), None)),
+            _ => panic!("expected num column"),
+        }
+    }
+
+    #[test]
+    fn field_mask_from_words_rejects_bits_past_the_table_width() {
+        assert_eq!(
+            FieldMask::from_words(&[1, 1 << 3]),
+            Some(FieldMask::of([0, 67]))
+        );
+        assert_eq!(FieldMask::from_words(&[]), Some(FieldMask::NONE));
+        assert_eq!(FieldMask::from_words(&[0, 0, 1]), None);
+    }
+
+    fn gathered_var(table: &Table<u64>, keys: &[u64]) -> Vec<Option<Vec<u8>>> {
+        let keys: Vec<Option<u64>> = keys.iter().map(|&k| Some(k)).collect();
+        let masks = vec![FieldMask::bit(1); keys.len()];
+        match &table.gather_scratch(&keys, &masks).unwrap()[1] {
+            Some(AnyCol::Var(c)) => (0..keys.len())
+                .map(|i| c.get(i).map(<[u8]>::to_vec))
+                .collect(),
+            _ => panic!("expected var column"),
+        }
+    }
+
+    #[test]
+    fn spill_pages_out_the_highest_keys_and_gather_reads_them_back() {
+        let dir = tempdir::TempDir::new("field_spill").unwrap();
+        let mut table = Table::new(2);
+        table
+            .enable_spill(dir.path().join("rows.bin"), 250, &[])
+            .unwrap();
+        let payload = |k: u8| vec![k; 100];
+        let (p1, p2, p3, p4) = (payload(1), payload(2), payload(3), payload(4));
+        let (keys, cols) = u64_batch(&[
+            (1, Some(10), Some(&p1)),
+            (2, Some(20), Some(&p2)),
+            (3, Some(30), Some(&p3)),
+            (4, Some(40), Some(&p4)),
+        ]);
+        table.merge_batch(keys, cols);
+        table.spill_cold_rows().unwrap();
+
+        // Blocks 4 then 3 go out until 200 of the 400 bytes are left; the
+        // dense num column never spills.
+        let spill = table.spill.as_ref().unwrap();
+        assert_eq!(spill.mem_bytes, 200);
+        let mut spilled: Vec<u64> = table
+            .order
+            .iter()
+            .filter(|(_, slot)| spill.rows.contains_key(slot))
+            .map(|(&k, _)| k)
+            .collect();
+        spilled.sort();
+        assert_eq!(spilled, vec![3, 4]);
+        assert_eq!(
+            gathered_u64(&table, &[1, 2, 3, 4]),
+            vec![Some(10), Some(20), Some(30), Some(40)]
+        );
+        assert_eq!(
+            gathered_var(&table, &[1, 2, 3, 4, 9]),
+            vec![Some(p1), Some(p2), Some(p3.clone()), Some(p4), None]
+        );
+
+        // A newer write to a spilled field lives in memory and kills the record.
+        let fresh = b"fresh".to_vec();
+        let (keys, cols) = u64_batch(&[(4, None, Some(&fresh))]);
+        table.merge_batch(keys, cols);
+        assert_eq!(gathered_var(&table, &[3, 4]), vec![Some(p3), Some(fresh)]);
+        let slot4 = table.order[&4];
+        assert!(!table.spill.as_ref().unwrap().rows.contains_key(&slot4));
+
+        table.prune(3);
+        let spill = table.spill.as_ref().unwrap();
+        assert_eq!((spill.rows.len(), spill.mem_bytes), (0, 5));
+    }
+
+    #[test]
+    fn spill_round_trips_the_highest_field_code() {
+        let dir = tempdir::TempDir::new("field_spill").unwrap();
+        let mut table = Table::new(MAX_FIELDS);
+        table
+            .enable_spill(dir.path().join("rows.bin"), 0, &[])
+            .unwrap();
+        let last = MAX_FIELDS - 1;
+        let mut cols: Vec<Option<AnyCol>> = (0..MAX_FIELDS).map(|_| None).collect();
+        let mut var = VarCol::new();
+        var.push(Some(b"last".as_slice()));
+        cols[last] = Some(AnyCol::Var(var));
+        table.merge_batch(vec![1u64], cols);
+        table.spill_cold_rows().unwrap();
+
+        assert_eq!(table.spill.as_ref().unwrap().rows.len(), 1);
+        match &table
+            .gather_scratch(&[Some(1u64)], &[FieldMask::bit(last)])
+            .unwrap()[last]
+        {
+            Some(AnyCol::Var(c)) => assert_eq!(c.get(0), Some(b"last".as_slice())),
+            _ => panic!("expected var column"),
+        }
+    }
+
+    #[test]
+    fn pinned_fields_never_spill() {
+        let dir = tempdir::TempDir::new("field_spill").unwrap();
+        let mut table = Table::new(2);
+        table
+            .enable_spill(dir.path().join("rows.bin"), 0, &[1])
+            .unwrap();
+        let hash = b"hash".to_vec();
+        let (keys, cols) = u64_batch(&[(1, Some(10), Some(&hash))]);
+        table.merge_batch(keys, cols);
+        table.spill_cold_rows().unwrap();
+
+        assert!(table.spill.as_ref().unwrap().rows.is_empty());
+        assert_eq!(table.field_bytes(&1, 1), Some(hash.as_slice()));
+    }
 }
@@ -7,6 +7,9 @@ use napi_derive::napi;
 pub struct ClientConfig {
     pub url: String,
     pub api_token: String,
+    /// Debug mode: every response page is written under this directory as
+    /// Arrow IPC files, one per table. See `response_capture`.
+    pub capture_dir: Option<String>,
 }
 
 impl TryFrom<ClientConfig> for hyperfuel_client::ClientConfig {
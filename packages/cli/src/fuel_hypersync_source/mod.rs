@@ -11,6 +11,7 @@ mod types;
 use crate::address_store::{AddressSet, AddressStore, SetCache, StoreInner};
 use crate::block_store::{BlockStore, FuelBlockRow};
 use crate::hex::decode_prefixed;
+use crate::response_capture::ResponseCapture;
 use config::ClientConfig;
 use hyperfuel_client::format::{Hash, Hex};
 use hyperfuel_client::net_types;
@@ -27,6 +28,7 @@ pub struct FuelHyperSyncClient {
     /// The chain's address index, shared with the fetch state. Read by the
     /// per-receipt owner gate.
     address_store: std::sync::Arc<std::sync::RwLock<StoreInner>>,
+    capture: Option<ResponseCapture>,
 }
 
 #[napi]
@@ -45,6 +47,7 @@ impl FuelHyperSyncClient {
                 .context("build selection builder")
                 .map_err(map_err)?
         };
+        let capture = ResponseCapture::new(cfg.capture_dir.clone());
         let client_config: hyperfuel_client::ClientConfig =
             cfg.try_into().context("build config").map_err(map_err)?;
         let inner = hyperfuel_client::Client::new_with_agent(client_config, user_agent)
@@ -54,6 +57,7 @@ impl FuelHyperSyncClient {
             inner,
             selection_builder,
             address_store: handle,
+            capture,
         })
     }
 
@@ -89,6 +93,20 @@ impl FuelHyperSyncClient {
             .get_arrow(&query)
             .await
             .map_err(|e| request_err("Failed to get data from HyperFuel", e))?;
+        if let Some(capture) = &self.capture {
+            let data = &res.data;
+            capture.write_polars(
+                query.from_block,
+                res.next_block,
+                [
+                    ("blocks", data.blocks.as_slice()),
+                    ("transactions", data.transactions.as_slice()),
+                    ("receipts", data.receipts.as_slice()),
+                    ("inputs", data.inputs.as_slice()),
+                    ("outputs", data.outputs.as_slice()),
+                ],
+            );
+        }
         let raw = convert_response(res).map_err(convert_error_to_napi)?;
 
         // The page's raw blocks, keyed by height — merged into the per-chain
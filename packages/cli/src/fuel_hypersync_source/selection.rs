@@ -685,11 +685,13 @@ mod tests {
             name: "Owned".to_string(),
             start_block: None,
             depends_on_addresses: true,
+            ttl_blocks: None,
         }]);
         store.register_seed(vec![crate::address_store::AddressRegistration {
             address: ADDR_1.to_string(),
             contract_name: "Owned".to_string(),
             registration_block: 50,
+            end_block: None,
         }]);
         let set = set_of(&store, &["Owned"]);
         let builder = SelectionBuilder::from_registrations(
@@ -558,7 +558,9 @@ impl EventTemplate {
     ) -> Result<Self> {
         let event_name = config_event.name.capitalize();
         match &config_event.kind {
-            EventKind::Params(params) => {
+            EventKind::Params(params)
+            | EventKind::Call(params)
+            | EventKind::Transaction(params) => {
                 // Solidity structs render as ReScript JS object types
                 // (`{"funder": Address.t, ...}`) via `TypeIdent::Record`, which
                 // IS inlinable inside a nominal record. The top-level `type
@@ -658,7 +660,9 @@ impl EventTemplate {
                     }
                 }
             }
-            EventKind::Svm(_) => Err(anyhow!("ReScript is not supported for SVM indexers")),
+            EventKind::Svm(_) | EventKind::SvmEvent(_) | EventKind::SvmAccount(_) => {
+                Err(anyhow!("ReScript is not supported for SVM indexers"))
+            }
         }
     }
 }
@@ -1128,7 +1132,11 @@ impl ProjectTemplate {
     ) -> String {
         // Build params TS type
         let params_ts = match &event.kind {
-            system_config::EventKind::Params(params) if !params.is_empty() => {
+            system_config::EventKind::Params(params)
+            | system_config::EventKind::Call(params)
+            | system_config::EventKind::Transaction(params)
+                if !params.is_empty() =>
+            {
                 let fields: Vec<String> = params
                     .iter()
                     .map(|p| {
@@ -1640,7 +1648,7 @@ type handlerContext = {{
             )
         };
 
-        // Generate contractRegisterContext type with chain.ContractName.add() pattern
+        // Generate contractRegisterContext type with chain.ContractName.add()/remove() pattern
         let contract_register_chain_fields: String = codegen_contracts
             .iter()
             .map(|c| format!("  \\\"{}\": contractRegisterContract,", c.name.capitalized))
@@ -1749,7 +1757,7 @@ module Entities = {{
 
 {handler_context_code}
 
-type contractRegisterContract = {{ add: Address.t => unit }}
+type contractRegisterContract = {{ add: Address.t => unit, remove: Address.t => unit }}
 
 type contractRegisterChain = {{
   id: chainId,
@@ -1997,6 +2005,8 @@ type testIndexer = {{
         let fuel_event_filters_body: String;
         let svm_chains_body: String;
         let svm_programs_body: String;
+        let svm_program_events_body: String;
+        let svm_program_accounts_body: String;
         let entities_body: String;
         let enums_body: String;
         {
@@ -2295,24 +2305,7 @@ type testIndexer = {{
                             EventKind::Svm(k) => k,
                             _ => continue,
                         };
-                        let args_ts = if svm_kind.args.is_empty() {
-                            "{}".to_string()
-                        } else {
-                            let fields = svm_kind
-                                .args
-                                .iter()
-                                .map(|f| {
-                                    let ts = field_type_to_ts_type(
-                                        &f.ty,
-                                        &svm_abi.defined_types,
-                                        &mut Vec::new(),
-                                    );
-                                    format!("readonly {}: {}", ts_safe_property_name(&f.name), ts)
-                                })
-                                .collect::<Vec<_>>()
-                                .join("; ");
-                            format!("{{ {fields} }}")
-                        };
+                        let args_ts = svm_fields_ts_type(&svm_kind.args, &svm_abi.defined_types);
                         let accounts_ts = if svm_kind.accounts.is_empty() {
                             "Readonly<Record<string, string>>".to_string()
                         } else {
@@ -2351,6 +2344,52 @@ type testIndexer = {{
                 "{}".to_string()
             };
 
+            // SVM program events and accounts tables: per-program records of
+            // per-event `{ params }` and per-account `{ state }` shapes, read
+            // by the `onEvent` and `onAccount` overloads. Kept apart from
+            // `programs` so their names never show up as instructions.
+            let svm_layout_table = |value_key: &str, wants_account: bool| -> String {
+                use crate::config_parsing::system_config::{Abi, EventKind};
+                if cfg.get_ecosystem() != Ecosystem::Svm {
+                    return "{}".to_string();
+                }
+                let mut program_entries: Vec<String> = Vec::new();
+                for contract in cfg.contracts.values() {
+                    let svm_abi = match &contract.abi {
+                        Abi::Svm(abi) => abi,
+                        _ => continue,
+                    };
+                    let entries: Vec<String> = contract
+                        .events
+                        .iter()
+                        .filter_map(|event| match (&event.kind, wants_account) {
+                            (EventKind::SvmEvent(k), false) | (EventKind::SvmAccount(k), true) => {
+                                Some(format!(
+                                    "          \"{name}\": {{ readonly {value_key}: {fields} }};",
+                                    name = event.name,
+                                    fields = svm_fields_ts_type(&k.fields, &svm_abi.defined_types),
+                                ))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    if !entries.is_empty() {
+                        program_entries.push(format!(
+                            "        \"{name}\": {{\n{body}\n        }};",
+                            name = contract.name,
+                            body = entries.join("\n"),
+                        ));
+                    }
+                }
+                if program_entries.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{\n{}\n      }}", program_entries.join("\n"))
+                }
+            };
+            svm_program_events_body = svm_layout_table("params", false);
+            svm_program_accounts_body = svm_layout_table("state", true);
+
             // File-level Enums and Entities tables. They reference each
             // other (entity field types use `Enums["Foo"]`), so they must
             // be named — but no prefix needed since neither name collides
@@ -2440,6 +2479,8 @@ type testIndexer = {{
                     fuel_event_filters: &fuel_event_filters_body,
                     svm_chains: &svm_chains_body,
                     svm_programs: &svm_programs_body,
+                    svm_program_events: &svm_program_events_body,
+                    svm_program_accounts: &svm_program_accounts_body,
                     entities: &entities_body,
                     per_chain_entities: {
                         let names: Vec<String> = entities
@@ -2504,9 +2545,12 @@ type testIndexer = {{
                 filters = bodies.fuel_event_filters,
             ),
             Ecosystem::Svm => format!(
-                "svm: {{ chains: {chains}; programs: {programs} }};",
+                "svm: {{ chains: {chains}; programs: {programs}; programEvents: {events}; \
+                 programAccounts: {accounts} }};",
                 chains = bodies.svm_chains,
                 programs = bodies.svm_programs,
+                events = bodies.svm_program_events,
+                accounts = bodies.svm_program_accounts,
             ),
         };
         let config_block = [
@@ -2563,12 +2607,34 @@ struct ConfigBodies<'a> {
     fuel_event_filters: &'a str,
     svm_chains: &'a str,
     svm_programs: &'a str,
+    svm_program_events: &'a str,
+    svm_program_accounts: &'a str,
     entities: &'a str,
     // Union of the names whose rows belong to a single chain, or `never`.
     per_chain_entities: String,
     enums: &'a str,
 }
 
+/// Render a Borsh field list (instruction args, event fields) as a TypeScript
+/// object type; `{}` when there are none.
+fn svm_fields_ts_type(
+    fields: &[hypersync_client_solana::decode::NamedField],
+    defined_types: &std::collections::BTreeMap<String, hypersync_client_solana::decode::FieldType>,
+) -> String {
+    if fields.is_empty() {
+        return "{}".to_string();
+    }
+    let fields = fields
+        .iter()
+        .map(|f| {
+            let ts = field_type_to_ts_type(&f.ty, defined_types, &mut Vec::new());
+            format!("readonly {}: {}", ts_safe_property_name(&f.name), ts)
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    format!("{{ {fields} }}")
+}
+
 /// Render an upstream `FieldType` as a TypeScript type. `defined_types` is
 /// the program-level nominal-type registry, used when the field is
 /// `Defined("Name")`. `seen` tracks the recursion stack to break cycles.
@@ -2645,7 +2711,14 @@ fn field_type_to_ts_type(
                 .join(" | ")
         }
         F::Defined(name) => {
-            if seen.iter().any(|n| n == name) {
+            if let Some(inner) =
+                crate::config_parsing::anchor_idl::coption_value(name, defined_types)
+            {
+                format!(
+                    "({}) | null",
+                    field_type_to_ts_type(inner, defined_types, seen)
+                )
+            } else if seen.iter().any(|n| n == name) {
                 // Cycle: avoid infinite expansion; users can refine via cast.
                 "unknown".to_string()
             } else if let Some(resolved) = defined_types.get(name) {
@@ -2967,6 +3040,9 @@ mod test {
                     .to_string(),
                 event_signature: String::new(),
                 field_selection: None,
+                transaction_where: None,
+                params_where: vec![],
+                register: None,
             },
             None,
             &"Gravatar".to_string().to_capitalized_options(),
@@ -3071,6 +3147,9 @@ mod test {
                 .to_string(),
             event_signature: String::new(),
             field_selection: None,
+            transaction_where: None,
+            params_where: vec![],
+            register: None,
         }
     }
 
@@ -3130,6 +3209,9 @@ mod test {
                         data_type: TypeIdent::option(TypeIdent::Address),
                     }],
                 }),
+                transaction_where: None,
+                params_where: vec![],
+                register: None,
             },
             all_ecosystem_fields,
             &"Gravatar".to_string().to_capitalized_options(),
@@ -3768,6 +3850,8 @@ type Swap {
                 fuel_event_filters: "{}",
                 svm_chains: "{}",
                 svm_programs: "{}",
+                svm_program_events: "{}",
+                svm_program_accounts: "{}",
                 entities: "Entities",
                 per_chain_entities: "never".to_string(),
                 enums: "Enums",
@@ -610,12 +610,14 @@ impl Event {
     ) -> Result<Option<Self>> {
         let empty_params = vec![];
         let params = match &event.kind {
-            EventKind::Params(params) => params,
+            EventKind::Params(params)
+            | EventKind::Call(params)
+            | EventKind::Transaction(params) => params,
             EventKind::Fuel(_) => &empty_params,
             // `contract_import` only drives the EVM/Fuel ABI-driven import flow.
             // Solana programs declare instructions explicitly in the YAML — no
             // params shape exists here.
-            EventKind::Svm(_) => &empty_params,
+            EventKind::Svm(_) | EventKind::SvmEvent(_) | EventKind::SvmAccount(_) => &empty_params,
         };
 
         // Try to convert each parameter, collecting results and errors
@@ -1,3 +1,4 @@
+mod address_snapshot;
 mod address_store;
 mod block_hash_pagination;
 mod block_store;
@@ -9,11 +10,13 @@ mod commands;
 pub mod config_parsing;
 pub mod constants;
 pub mod docker_env;
+mod event_item_store;
 mod evm;
 mod evm_hypersync_source;
 mod evm_rpc_source;
 pub mod executor;
 mod field_columns;
+mod field_spill;
 mod field_table;
 mod fuel;
 mod fuel_hypersync_source;
@@ -25,6 +28,7 @@ mod napi;
 mod project_paths;
 mod registration_start_block;
 mod request_stats;
+mod response_capture;
 pub mod scripts;
 mod service_health;
 mod svm_hypersync_source;
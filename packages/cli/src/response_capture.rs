@@ -0,0 +1,172 @@
+//! Debug capture of raw HyperSync response pages. A client built with a capture
+//! directory writes every page it fetches there, before any conversion, as one
+//! Arrow IPC file per table — readable with DuckDB or Polars, so fetched data
+//! can be analysed and a decoding bug reproduced without the server.
+//!
+//! Each page gets its own directory, `<from>-<next>-<seq>`: the query's first
+//! block (slot on SVM), the response's next block, and a per-process counter
+//! telling apart pages fetched over the same range (other partitions, retries).
+//! A capture that fails is logged as a warning and skipped; it never fails the
+//! fetch.
+
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use arrow::array::RecordBatch;
+
+use crate::evm_hypersync_source::init_logger;
+
+static PAGE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) struct ResponseCapture {
+    dir: PathBuf,
+}
+
+impl ResponseCapture {
+    /// `None` when the client was built without a capture directory. Failures
+    /// are logged, so a capturing client sets up the logger at `warn` if no
+    /// earlier client did — the SVM and Fuel configs carry no log level.
+    pub(crate) fn new(dir: Option<String>) -> Option<Self> {
+        let dir = dir?;
+        init_logger(Some("warn"));
+        Some(Self { dir: dir.into() })
+    }
+
+    /// Writes one page whose tables are `arrow-rs` batches (EVM and SVM).
+    pub(crate) fn write_arrow<'a>(
+        &self,
+        from_block: u64,
+        next_block: u64,
+        tables: impl IntoIterator<Item = (&'a str, &'a [RecordBatch])>,
+    ) {
+        self.report(self.page_dir(from_block, next_block).and_then(|dir| {
+            for (name, batches) in tables {
+                let Some(first) = batches.first() else {
+                    continue;
+                };
+                let path = dir.join(format!("{name}.arrow"));
+                let file =
+                    File::create(&path).with_context(|| format!("create {}", path.display()))?;
+                let mut writer =
+                    arrow::ipc::writer::FileWriter::try_new(BufWriter::new(file), &first.schema())
+                        .context("create arrow ipc writer")?;
+                for batch in batches {
+                    writer.write(batch).context("write arrow record batch")?;
+                }
+                writer.finish().context("finish arrow ipc file")?;
+            }
+            Ok(())
+        }));
+    }
+
+    /// Writes one page whose tables are `polars-arrow` batches (Fuel).
+    pub(crate) fn write_polars<'a>(
+        &self,
+        from_block: u64,
+        next_block: u64,
+        tables: impl IntoIterator<Item = (&'a str, &'a [hyperfuel_client::ArrowBatch])>,
+    ) {
+        use polars_arrow::io::ipc::write::{FileWriter, WriteOptions};
+
+        self.report(self.page_dir(from_block, next_block).and_then(|dir| {
+            for (name, batches) in tables {
+                let Some(first) = batches.first() else {
+                    continue;
+                };
+                let path = dir.join(format!("{name}.arrow"));
+                let file =
+                    File::create(&path).with_context(|| format!("create {}", path.display()))?;
+                let mut writer = FileWriter::try_new(
+                    BufWriter::new(file),
+                    first.schema.clone(),
+                    None,
+                    WriteOptions { compression: None },
+                )
+                .context("create arrow ipc writer")?;
+                for batch in batches {
+                    writer
+                        .write(&batch.chunk, None)
+                        .context("write arrow record batch")?;
+                }
+                writer.finish().context("finish arrow ipc file")?;
+            }
+            Ok(())
+        }));
+    }
+
+    fn page_dir(&self, from_block: u64, next_block: u64) -> Result<PathBuf> {
+        let seq = PAGE_SEQ.fetch_add(1, Ordering::Relaxed);
+        let dir = self.dir.join(format!("{from_block}-{next_block}-{seq}"));
+        fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+        Ok(dir)
+    }
+
+    fn report(&self, result: Result<()>) {
+        if let Err(err) = result {
+            log::warn!(
+                "Failed to capture a HyperSync response under {}: {err:#}",
+                self.dir.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    use super::*;
+
+    #[test]
+    fn pages_are_written_as_readable_ipc_files() {
+        let dir = tempdir::TempDir::new("response_capture").unwrap();
+        let capture =
+            ResponseCapture::new(Some(dir.path().to_string_lossy().into_owned())).unwrap();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("number", DataType::Int64, false),
+            Field::new("hash", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![10, 11])),
+                Arc::new(StringArray::from(vec!["0xaa", "0xbb"])),
+            ],
+        )
+        .unwrap();
+
+        capture.write_arrow(
+            10,
+            12,
+            [("blocks", std::slice::from_ref(&batch)), ("logs", &[][..])],
+        );
+
+        let pages: Vec<PathBuf> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(pages.len(), 1);
+        let page = &pages[0];
+        assert!(page
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("10-12-"));
+        // A table without batches has no schema to write, so no file.
+        assert!(!page.join("logs.arrow").exists());
+
+        let reader = arrow::ipc::reader::FileReader::try_new(
+            File::open(page.join("blocks.arrow")).unwrap(),
+            None,
+        )
+        .unwrap();
+        let read: Vec<RecordBatch> = reader.map(|b| b.unwrap()).collect();
+        assert_eq!(read, vec![batch]);
+    }
+}
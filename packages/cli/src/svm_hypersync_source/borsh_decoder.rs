@@ -13,13 +13,15 @@ use std::collections::BTreeMap;
 use anyhow::{Context, Result};
 
 use hypersync_client_solana::decode::{
-    decode_instruction as upstream_decode, DecodedInstruction as UpstreamDecoded,
-    EnumVariant as UpstreamEnumVariant, FieldType as SvmFieldType,
-    InstructionSchema as UpstreamIxSchema, NamedAccount as UpstreamAccount,
-    NamedField as UpstreamNamedField, ProgramSchema as UpstreamSchema,
+    decode_field, decode_instruction as upstream_decode, decode_top_level, DecodeError,
+    DecodedInstruction as UpstreamDecoded, EnumVariant as UpstreamEnumVariant,
+    FieldType as SvmFieldType, InstructionSchema as UpstreamIxSchema,
+    NamedAccount as UpstreamAccount, NamedField as UpstreamNamedField,
+    ProgramSchema as UpstreamSchema,
 };
 use hypersync_client_solana::simple_types::InstructionCall as UpstreamInstructionCall;
 
+use crate::config_parsing::anchor_idl::collapse_coptions;
 use crate::config_parsing::human_config::svm::{ArgComposite, ArgDef, ArgPrimitive, ArgType};
 
 use super::mod_helpers::hex_to_bytes;
@@ -41,23 +43,117 @@ pub(crate) struct InstructionSchemaInput {
 /// query response instead of crossing the napi boundary one instruction at a
 /// time.
 ///
-/// POC policy: any decode failure (unknown discriminator, account-count
-/// mismatch, trailing bytes, unresolved type) yields `None` so the indexer
-/// keeps running. Real on-chain calls drift from schemas in small ways
-/// (Metaplex `rent` slot was optional in some versions, etc.); a single bad
-/// row should not kill the worker.
+/// A failure (unknown discriminator, account-count mismatch, trailing bytes,
+/// unresolved type) never fails the query: it rides back as a `DecodeFailure`
+/// and the program's `decode_errors` mode decides on the JS side whether the
+/// item still reaches its handler. Real on-chain calls drift from schemas in
+/// small ways (Metaplex `rent` slot was optional in some versions, etc.).
 pub(crate) fn decode_with_schema(
     schema: &UpstreamSchema,
     instruction: &UpstreamInstructionCall,
-) -> Option<DecodedInstructionJson> {
-    upstream_decode(schema, instruction).ok().map(Into::into)
+) -> std::result::Result<DecodedInstructionJson, DecodeFailure> {
+    let mut decoded = upstream_decode(schema, instruction)
+        .map_err(|err| locate_instruction_failure(schema, instruction, err))?;
+    let data = instruction.data.as_deref().unwrap_or_default();
+    if let Some((ix, _)) = instruction_schema(schema, data) {
+        for arg in &ix.args {
+            if let Some(value) = decoded.args.get_mut(&arg.name) {
+                collapse_coptions(&arg.ty, &schema.defined_types, value);
+            }
+        }
+    }
+    Ok(decoded.into())
+}
+
+/// The instruction `data` starts with the discriminator of, and that
+/// discriminator's length.
+fn instruction_schema<'a>(
+    schema: &'a UpstreamSchema,
+    data: &[u8],
+) -> Option<(&'a UpstreamIxSchema, usize)> {
+    schema
+        .disc_lens
+        .iter()
+        .filter(|&&n| data.len() >= n)
+        .find_map(|&n| schema.instructions.get(&data[..n]).map(|ix| (ix, n)))
+}
+
+/// Why an instruction or event didn't decode, and where. Offsets count from
+/// the start of the item's `data`, discriminator included.
+#[napi_derive::napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodeFailure {
+    pub reason: String,
+    /// Byte offset the decoder stopped at. `None` when the failure isn't about
+    /// the data (too few accounts).
+    pub byte_offset: Option<i64>,
+    /// The top-level arg or field being read when decoding stopped.
+    pub field: Option<String>,
+}
+
+impl DecodeFailure {
+    fn new(err: &DecodeError, byte_offset: Option<usize>, field: Option<&str>) -> Self {
+        DecodeFailure {
+            reason: err.to_string(),
+            byte_offset: byte_offset.map(|offset| offset as i64),
+            field: field.map(str::to_string),
+        }
+    }
+}
+
+/// The upstream error carries no position, so replay the decode field by
+/// field to find the one it stopped in. Only runs for failed items.
+fn locate_instruction_failure(
+    schema: &UpstreamSchema,
+    instruction: &UpstreamInstructionCall,
+    err: DecodeError,
+) -> DecodeFailure {
+    let data = instruction.data.as_deref().unwrap_or_default();
+    match (&err, instruction_schema(schema, data)) {
+        (DecodeError::AccountCountTooFew { .. }, _) => DecodeFailure::new(&err, None, None),
+        (_, Some((ix, disc_len))) => locate_field_failure(
+            ix.args.iter().map(|f| (f.name.as_str(), &f.ty)),
+            &schema.defined_types,
+            data,
+            disc_len,
+        )
+        .unwrap_or_else(|| DecodeFailure::new(&err, Some(0), None)),
+        _ => DecodeFailure::new(&err, Some(0), None),
+    }
+}
+
+/// Walk `fields` over `data[start..]`, returning the first one that fails to
+/// decode, or trailing bytes once all of them did.
+fn locate_field_failure<'a>(
+    fields: impl Iterator<Item = (&'a str, &'a SvmFieldType)>,
+    defined_types: &BTreeMap<String, SvmFieldType>,
+    data: &[u8],
+    start: usize,
+) -> Option<DecodeFailure> {
+    let mut buf = &data[start..];
+    for (name, ty) in fields {
+        if let Err(err) = decode_field(ty, defined_types, &mut buf) {
+            return Some(DecodeFailure::new(
+                &err,
+                Some(data.len() - buf.len()),
+                Some(name),
+            ));
+        }
+    }
+    (!buf.is_empty()).then(|| {
+        DecodeFailure::new(
+            &DecodeError::TrailingBytes(buf.len()),
+            Some(data.len() - buf.len()),
+            None,
+        )
+    })
 }
 
 /// JS-facing shape of `DecodedInstruction`. Args + named accounts are passed
 /// as JSON strings to side-step napi-rs's lack of native `serde_json::Value`
 /// support; the runtime `JSON.parse`s them once into the per-handler shape.
 #[napi_derive::napi(object)]
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct DecodedInstructionJson {
     pub name: String,
     /// `JSON.stringify`-able args object. Always a JSON object literal even
@@ -83,6 +179,70 @@ impl From<UpstreamDecoded> for DecodedInstructionJson {
     }
 }
 
+/// Anchor event discriminators are always 8 bytes.
+const EVENT_DISCRIMINATOR_LEN: usize = 8;
+
+/// One Anchor event's layout: its fields as a struct, resolved against the
+/// program's nominal-type registry. Built once per event registration.
+pub(crate) struct EventSchema {
+    name: String,
+    layout: SvmFieldType,
+    defined_types: BTreeMap<String, SvmFieldType>,
+}
+
+pub(crate) fn build_event_schema(
+    name: String,
+    defined_types: &BTreeMap<String, ArgType>,
+    fields: &[ArgDef],
+) -> Result<EventSchema> {
+    let defined_types = defined_types
+        .iter()
+        .map(|(name, ty)| {
+            arg_type_to_field_type(ty)
+                .map(|ft| (name.clone(), ft))
+                .with_context(|| format!("translating defined type '{name}'"))
+        })
+        .collect::<Result<_>>()?;
+    let layout = arg_type_to_field_type(&ArgType::Composite(ArgComposite::Struct(fields.to_vec())))
+        .with_context(|| format!("event '{name}' fields"))?;
+    Ok(EventSchema {
+        name,
+        layout,
+        defined_types,
+    })
+}
+
+/// Decode an event's payload, discriminator included. Same policy as
+/// instructions: a payload that doesn't fit the layout yields a
+/// `DecodeFailure`.
+pub(crate) fn decode_event(
+    schema: &EventSchema,
+    payload: &[u8],
+) -> std::result::Result<DecodedInstructionJson, DecodeFailure> {
+    let mut args = decode_top_level(
+        &schema.layout,
+        &schema.defined_types,
+        &payload[EVENT_DISCRIMINATOR_LEN..],
+    )
+    .map_err(|err| match &schema.layout {
+        SvmFieldType::Struct(fields) => locate_field_failure(
+            fields.iter().map(|f| (f.name.as_str(), &f.ty)),
+            &schema.defined_types,
+            payload,
+            EVENT_DISCRIMINATOR_LEN,
+        )
+        .unwrap_or_else(|| DecodeFailure::new(&err, Some(EVENT_DISCRIMINATOR_LEN), None)),
+        _ => DecodeFailure::new(&err, Some(EVENT_DISCRIMINATOR_LEN), None),
+    })?;
+    collapse_coptions(&schema.layout, &schema.defined_types, &mut args);
+    Ok(DecodedInstructionJson {
+        name: schema.name.clone(),
+        args_json: serde_json::to_string(&args).unwrap_or_else(|_| "{}".to_string()),
+        accounts_json: "{}".to_string(),
+        extra_accounts: Vec::new(),
+    })
+}
+
 pub(crate) fn build_program_schema(
     program_id: String,
     defined_types: &BTreeMap<String, ArgType>,
@@ -217,3 +377,100 @@ fn arg_type_to_field_type(ty: &ArgType) -> Result<SvmFieldType> {
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arg(name: &str, ty: ArgPrimitive) -> ArgDef {
+        ArgDef {
+            name: name.to_string(),
+            ty: ArgType::Primitive(ty),
+        }
+    }
+
+    fn swap_schema() -> UpstreamSchema {
+        build_program_schema(
+            "11111111111111111111111111111111".to_string(),
+            &BTreeMap::new(),
+            vec![InstructionSchemaInput {
+                name: "swap".to_string(),
+                discriminator: "0x0102".to_string(),
+                accounts: vec![],
+                args: vec![
+                    arg("amount", ArgPrimitive::U64),
+                    arg("memo", ArgPrimitive::String),
+                ],
+            }],
+        )
+        .expect("schema")
+    }
+
+    fn call(data: Vec<u8>) -> UpstreamInstructionCall {
+        UpstreamInstructionCall {
+            data: Some(data),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn locates_the_arg_an_instruction_stops_in() {
+        // amount decodes; memo claims 10 bytes but only 2 follow.
+        let mut data = vec![1, 2];
+        data.extend(7u64.to_le_bytes());
+        data.extend(10u32.to_le_bytes());
+        data.extend([b'h', b'i']);
+        let failure = decode_with_schema(&swap_schema(), &call(data)).expect_err("underflow");
+        assert_eq!(failure.field.as_deref(), Some("memo"));
+        assert_eq!(failure.byte_offset, Some(14));
+        assert!(failure.reason.contains("underflow"), "{}", failure.reason);
+    }
+
+    #[test]
+    fn reports_trailing_bytes_where_the_schema_ends() {
+        let mut data = vec![1, 2];
+        data.extend(7u64.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend([0xff, 0xff, 0xff]);
+        let failure = decode_with_schema(&swap_schema(), &call(data)).expect_err("trailing");
+        assert_eq!(
+            failure,
+            DecodeFailure {
+                reason: "trailing 3 byte(s) after decode".to_string(),
+                byte_offset: Some(14),
+                field: None,
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_discriminator_fails_at_the_start() {
+        let failure = decode_with_schema(&swap_schema(), &call(vec![9, 9, 9])).expect_err("miss");
+        assert_eq!(failure.byte_offset, Some(0));
+        assert_eq!(failure.field, None);
+        assert!(failure.reason.starts_with("unknown discriminator"));
+    }
+
+    #[test]
+    fn event_offsets_include_the_discriminator() {
+        let schema = build_event_schema(
+            "Swapped".to_string(),
+            &BTreeMap::new(),
+            &[
+                arg("amount", ArgPrimitive::U64),
+                arg("filled", ArgPrimitive::Bool),
+            ],
+        )
+        .expect("event schema");
+        let mut payload = vec![0u8; EVENT_DISCRIMINATOR_LEN];
+        payload.extend(5u64.to_le_bytes());
+        payload.push(2);
+        let failure = decode_event(&schema, &payload).expect_err("invalid bool");
+        assert_eq!(failure.field.as_deref(), Some("filled"));
+        assert_eq!(failure.byte_offset, Some(17));
+
+        payload[16] = 1;
+        let decoded = decode_event(&schema, &payload).expect("decodes");
+        assert_eq!(decoded.args_json, r#"{"amount":"5","filled":true}"#);
+    }
+}
@@ -13,6 +13,9 @@ pub struct SvmClientConfig {
     pub max_num_retries: Option<i64>,
     pub retry_base_ms: Option<i64>,
     pub retry_ceiling_ms: Option<i64>,
+    /// Debug mode: every response page is written under this directory as
+    /// Arrow IPC files, one per table. See `response_capture`.
+    pub capture_dir: Option<String>,
 }
 
 impl From<SvmClientConfig> for hypersync_client_solana::config::ClientConfig {
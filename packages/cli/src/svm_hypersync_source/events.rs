@@ -0,0 +1,94 @@
+//! Anchor event extraction. An event is its 8-byte discriminator followed by
+//! the Borsh-encoded event struct, published one of two ways:
+//!
+//! - `emit!` writes it base64-encoded as a `Program data:` log line of the
+//!   emitting instruction;
+//! - `emit_cpi!` invokes the program itself, the inner instruction's data being
+//!   Anchor's event-instruction tag followed by the event.
+//!
+//! Both surface under an instruction of the emitting program, so event
+//! registrations fetch the program's instructions (with their logs) and the
+//! payloads are read off each one here.
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+
+use super::selection::InstructionCall;
+use super::LogItem;
+
+/// `sha256("anchor:event")[..8]`, little-endian on the wire: the prefix of
+/// every `emit_cpi!` instruction.
+const EVENT_IX_TAG: [u8; 8] = 0x1d9a_cb51_2ea5_45e4u64.to_le_bytes();
+
+/// The event payloads an instruction carries: its own data when it is an
+/// `emit_cpi!` self-invocation, its `Program data:` lines otherwise. Lines that
+/// aren't valid base64 are skipped; `sol_log_data` separates several slices
+/// with spaces, so each is a payload of its own.
+pub(crate) fn event_payloads(instr: &InstructionCall, logs: Option<&[LogItem]>) -> Vec<Vec<u8>> {
+    if instr.is_inner {
+        if let Some(event) = instr.data.strip_prefix(EVENT_IX_TAG.as_slice()) {
+            return vec![event.to_vec()];
+        }
+    }
+    logs.unwrap_or_default()
+        .iter()
+        .filter(|log| log.kind.as_deref() == Some("data"))
+        .filter_map(|log| log.message.as_deref())
+        .flat_map(str::split_whitespace)
+        .filter_map(|chunk| BASE64_STANDARD.decode(chunk).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(data: Vec<u8>, is_inner: bool) -> InstructionCall {
+        InstructionCall {
+            slot: 1,
+            transaction_index: 0,
+            instruction_address: vec![0],
+            executing_account: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".to_string(),
+            account_arguments: vec![],
+            data,
+            is_inner,
+            tx_success: true,
+        }
+    }
+
+    fn log(kind: &str, message: &str) -> LogItem {
+        LogItem {
+            kind: Some(kind.to_string()),
+            message: Some(message.to_string()),
+        }
+    }
+
+    #[test]
+    fn program_data_lines_decode_to_payloads() {
+        let event = [1u8, 2, 3, 4, 5, 6, 7, 8, 42];
+        let logs = [
+            log("log", "Instruction: Swap"),
+            log("data", &BASE64_STANDARD.encode(event)),
+            log("data", "not base64!"),
+        ];
+        assert_eq!(
+            event_payloads(&instruction(vec![9], false), Some(&logs)),
+            vec![event.to_vec()]
+        );
+    }
+
+    #[test]
+    fn self_cpi_payload_follows_the_event_tag() {
+        let event = [1u8, 2, 3, 4, 5, 6, 7, 8, 42];
+        let data = [EVENT_IX_TAG.as_slice(), &event].concat();
+        assert_eq!(
+            EVENT_IX_TAG,
+            [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]
+        );
+        assert_eq!(
+            event_payloads(&instruction(data.clone(), true), None),
+            vec![event.to_vec()]
+        );
+        // An outer instruction is never an event CPI, whatever its data.
+        assert!(event_payloads(&instruction(data, false), None).is_empty());
+    }
+}
@@ -202,11 +202,34 @@ pub const INSTRUCTION: &[HandlerField] = &[
     },
 ];
 
-pub const BLOCK_KEYS: &[&str] = &["slot", "blockhash", "block_time"];
+/// The parent link backs the block store's parent-hash check, so a reorg
+/// shows on the first page that crosses it.
+pub const BLOCK_KEYS: &[&str] = &[
+    "slot",
+    "blockhash",
+    "block_time",
+    "parent_slot",
+    "parent_blockhash",
+];
 pub const TX_KEYS: &[&str] = &["slot", "transaction_index"];
 pub const ACTIVITY_KEYS: &[&str] = &["slot", "transaction_index", "account"];
 pub const LOG_KEYS: &[&str] = &["slot", "transaction_index", "instruction_address"];
 
+/// Fetched whenever an Anchor event registration is in the selection: `emit!`
+/// events are `data`-kind log lines of the emitting instruction.
+pub const EVENT_LOG_COLUMNS: &[&str] = &[
+    "slot",
+    "transaction_index",
+    "instruction_address",
+    "kind",
+    "message",
+];
+
+/// Fetched whenever a program-account registration is in the selection: the
+/// accounts an instruction writes are its account arguments that the
+/// transaction marks writable.
+pub const ACCOUNT_WRITE_COLUMNS: &[&str] = &["slot", "transaction_index", "account", "is_writable"];
+
 /// Always fetched for routing and join keys. Handler `fields.instruction`
 /// then decides which of `programId` / `data` / `path` / `isInner` land on
 /// the payload. HyperSync column is `instruction_address`; handler field is `path`.
@@ -6,6 +6,7 @@ use napi_derive::napi;
 
 mod borsh_decoder;
 mod config;
+mod events;
 mod fields;
 mod query;
 mod selection;
@@ -29,11 +30,15 @@ use crate::block_hash_pagination::{paginate_block_hashes, HashPage};
 use crate::block_store::BlockStore;
 use crate::config_parsing::human_config::svm::{ArgDef, ArgType};
 use crate::request_stats::{rate_limited_err, source_behind_head_err, RequestStat};
+use crate::response_capture::ResponseCapture;
 use crate::transaction_store::TransactionStore;
-use borsh_decoder::{DecodedInstructionJson, InstructionSchemaInput};
+use borsh_decoder::{DecodeFailure, DecodedInstructionJson, InstructionSchemaInput};
 use config::SvmClientConfig;
 use query::SvmQuery;
-use selection::{route_instruction, SelectionBuilder, SvmOnEventRegistrationInput};
+use selection::{
+    account_registrations_in_scope, event_registrations_in_scope, route_instruction,
+    SelectionBuilder, SvmOnEventRegistrationInput,
+};
 use types::{to_hex, QueryResponse};
 
 /// Move the response's transactions and account activity into a
@@ -80,7 +85,9 @@ fn build_schemas(
     let mut parts_by_program: Vec<(String, ProgramParts)> = Vec::new();
 
     for reg in registrations {
-        if reg.program_id.is_empty() {
+        // Events and accounts decode against their own layouts (see
+        // `selection::Registration`).
+        if reg.program_id.is_empty() || reg.is_event == Some(true) || reg.is_account == Some(true) {
             continue;
         }
         let has_schema = !reg.accounts.is_empty() || reg.args_json.is_some();
@@ -167,12 +174,27 @@ fn map_query_error(error: anyhow::Error, rate_limit: Option<RateLimitInfo>) -> n
 impl SvmHyperSyncClient {
     /// Run one query, surfacing a rate limit to the source manager rather than
     /// sleeping it out inside the call.
+    /// The page arrives as Arrow, so it's captured as received before
+    /// converting to rows.
     async fn run_query(&self, query: &SolanaQuery) -> napi::Result<simple::SolanaResponse> {
-        self.inner
-            .get(query)
+        let response = self
+            .inner
+            .get_arrow(query)
             .await
             .context("solana get")
-            .map_err(|e| map_query_error(e, self.inner.rate_limit_info()))
+            .map_err(|e| map_query_error(e, self.inner.rate_limit_info()))?;
+        if let Some(capture) = &self.capture {
+            capture.write_arrow(
+                query.from_slot,
+                response.next_slot,
+                response
+                    .data
+                    .tables
+                    .iter()
+                    .map(|(name, batch)| (*name, std::slice::from_ref(batch))),
+            );
+        }
+        decode_tables(response).map_err(map_err)
     }
 
     /// Execute one raw Solana HyperSync page. Event queries and block-hash
@@ -186,6 +208,46 @@ impl SvmHyperSyncClient {
     }
 }
 
+/// The typed rows of an Arrow page, table for table — what the client's own
+/// `get` does after fetching.
+fn decode_tables(
+    response: hypersync_client_solana::types::QueryResponse,
+) -> Result<simple::SolanaResponse> {
+    use hypersync_client_solana::from_arrow;
+
+    let mut decoded = simple::SolanaResponse {
+        next_slot: response.next_slot,
+        rollback_guard: response.rollback_guard,
+        response_bytes: response.response_bytes,
+        ..Default::default()
+    };
+    for (name, batch) in &response.data.tables {
+        match *name {
+            "blocks" => {
+                decoded.blocks = from_arrow::blocks_from_arrow(batch).context("decode blocks")?
+            }
+            "transactions" => {
+                decoded.transactions =
+                    from_arrow::transactions_from_arrow(batch).context("decode transactions")?
+            }
+            "instruction_calls" => {
+                decoded.instruction_calls = from_arrow::instruction_calls_from_arrow(batch)
+                    .context("decode instruction_calls")?
+            }
+            "logs" => decoded.logs = from_arrow::logs_from_arrow(batch).context("decode logs")?,
+            "account_activity" => {
+                decoded.account_activity = from_arrow::account_activity_from_arrow(batch)
+                    .context("decode account_activity")?
+            }
+            "rewards" => {
+                decoded.rewards = from_arrow::rewards_from_arrow(batch).context("decode rewards")?
+            }
+            _ => {}
+        }
+    }
+    Ok(decoded)
+}
+
 #[napi]
 pub struct SvmHyperSyncClient {
     inner: Arc<hypersync_client_solana::Client>,
@@ -194,6 +256,7 @@ pub struct SvmHyperSyncClient {
     /// The chain's address index, shared with the fetch state. Read by the
     /// per-instruction owner gate.
     address_store: Arc<std::sync::RwLock<StoreInner>>,
+    capture: Option<ResponseCapture>,
 }
 
 #[napi]
@@ -228,6 +291,7 @@ impl SvmHyperSyncClient {
                 .context("build selection builder")
                 .map_err(map_err)?
         };
+        let capture = ResponseCapture::new(cfg.capture_dir.clone());
         let inner = hypersync_client_solana::Client::new_with_agent(cfg.into(), user_agent)
             .context("build solana client")
             .map_err(map_err)?;
@@ -236,6 +300,7 @@ impl SvmHyperSyncClient {
             schemas,
             selection_builder,
             address_store: handle,
+            capture,
         })
     }
 
@@ -408,8 +473,7 @@ impl SvmHyperSyncClient {
         let items = {
             let store = self.address_store.read().unwrap();
             build_event_items(
-                &resp.instruction_calls,
-                std::mem::take(&mut resp.logs),
+                &mut resp,
                 &built,
                 &self.schemas,
                 &set_cache,
@@ -450,6 +514,47 @@ impl SvmHyperSyncClient {
         };
         Ok((response, store, block_store))
     }
+
+    /// Decode an account's data, read from the cluster by the caller, against
+    /// a program-account registration's layout. `None` when the data doesn't
+    /// start with the registration's discriminator: the account is of another
+    /// of the program's types.
+    #[napi]
+    pub fn decode_account(
+        &self,
+        registration_index: i64,
+        data_base64: String,
+    ) -> napi::Result<Option<DecodedAccount>> {
+        use base64::prelude::{Engine, BASE64_STANDARD};
+
+        let reg = self
+            .selection_builder
+            .registration(registration_index)
+            .with_context(|| format!("Unknown registration index {registration_index}"))
+            .map_err(map_err)?;
+        let (Some(schema), Some(discriminator)) = (&reg.account, &reg.discriminator) else {
+            return Err(map_err(anyhow::anyhow!(
+                "Registration {registration_index} isn't a program-account registration"
+            )));
+        };
+        let data = BASE64_STANDARD
+            .decode(&data_base64)
+            .context("decode account data base64")
+            .map_err(map_err)?;
+        if !data.starts_with(discriminator) {
+            return Ok(None);
+        }
+        Ok(Some(match borsh_decoder::decode_event(schema, &data) {
+            Ok(decoded) => DecodedAccount {
+                decoded: Some(decoded),
+                decode_error: None,
+            },
+            Err(failure) => DecodedAccount {
+                decoded: None,
+                decode_error: Some(failure),
+            },
+        }))
+    }
 }
 
 /// Convert only the values needed by the block-hash paginator: the advancing
@@ -495,9 +600,9 @@ pub struct LogItem {
     pub message: Option<String>,
 }
 
-/// One routed instruction. Carries everything JS needs to build the handler
-/// payload; the parent transaction and block are materialised from the
-/// per-chain stores at batch prep.
+/// One routed instruction, or one Anchor event an instruction carries. Carries
+/// everything JS needs to build the handler payload; the parent transaction
+/// and block are materialised from the per-chain stores at batch prep.
 #[napi(object)]
 pub struct EventItem {
     /// The registration this instruction routed to, as passed to the client
@@ -507,17 +612,33 @@ pub struct EventItem {
     pub transaction_index: i64,
     pub path: Vec<i64>,
     pub program_id: String,
+    /// For a program-account registration, the accounts the instruction
+    /// wrote that weren't already reported for the transaction.
     pub accounts: Vec<String>,
     /// Raw instruction data, `0x`-prefixed hex; decoded params ride on
-    /// `decoded` when the registration carries a Borsh schema.
+    /// `decoded` when the registration carries a Borsh schema. For an event,
+    /// the event's bytes (discriminator included) and its decoded fields. An
+    /// account item carries no data: HyperSync doesn't serve account state, so
+    /// it's read from the cluster and passed to `decode_account`.
     pub data: String,
     pub is_inner: bool,
     pub decoded: Option<DecodedInstructionJson>,
+    /// Set instead of `decoded` when a decode was attempted and the bytes
+    /// didn't fit the schema.
+    pub decode_error: Option<DecodeFailure>,
     /// Logs scoped to this instruction; `Some` only when the routed
     /// registration selected `fields.log`.
     pub logs: Option<Vec<LogItem>>,
 }
 
+/// An account's state decoded against its registration's layout.
+#[napi(object)]
+pub struct DecodedAccount {
+    pub decoded: Option<DecodedInstructionJson>,
+    /// Set instead of `decoded` when the data didn't fit the layout.
+    pub decode_error: Option<DecodeFailure>,
+}
+
 #[napi(object)]
 pub struct EventItemsResponse {
     pub next_slot: i64,
@@ -545,10 +666,12 @@ fn project_logs(logs: &[LogItem], columns: &[&str]) -> Vec<LogItem> {
 /// instruction address attach to no instruction (rare; usually only system
 /// messages). Borsh decoding runs once per instruction against its program's
 /// schema, and only when a routed registration selected `fields.instruction`
-/// `args`.
+/// `args`. Program-account registrations follow its own items, each with one
+/// item listing the accounts the instruction wrote (an account is reported
+/// once per transaction), then the Anchor events the instruction carries, one
+/// per matching event registration, at the instruction's path.
 fn build_event_items(
-    instruction_calls: &[simple::InstructionCall],
-    logs: Vec<simple::Log>,
+    resp: &mut simple::SolanaResponse,
     built: &selection::BuiltSelection,
     schemas: &HashMap<String, UpstreamSchema>,
     set_cache: &SetCache,
@@ -556,7 +679,7 @@ fn build_event_items(
     address_store: &StoreInner,
 ) -> Result<Vec<EventItem>> {
     let mut logs_by_key: HashMap<(u64, u32, Vec<u32>), Vec<LogItem>> = HashMap::new();
-    for log in logs {
+    for log in std::mem::take(&mut resp.logs) {
         if let (Some(slot), Some(transaction_index), Some(instruction_address)) =
             (log.slot, log.transaction_index, log.instruction_address)
         {
@@ -574,8 +697,24 @@ fn build_event_items(
         }
     }
 
-    let mut items: Vec<EventItem> = Vec::with_capacity(instruction_calls.len());
-    for raw in instruction_calls {
+    // Account activity rows are per transaction, so an account an instruction
+    // passes is written when the transaction marks it writable.
+    let written_by_tx: HashSet<(u64, u32, String)> =
+        if built.registrations.iter().any(|reg| reg.account.is_some()) {
+            resp.account_activity
+                .iter()
+                .filter(|row| row.is_writable == Some(true))
+                .filter_map(|row| {
+                    Some((row.slot?, row.transaction_index?, row.account?.to_string()))
+                })
+                .collect()
+        } else {
+            HashSet::new()
+        };
+    let mut reported_writes: HashSet<(i64, u64, u32, String)> = HashSet::new();
+
+    let mut items: Vec<EventItem> = Vec::with_capacity(resp.instruction_calls.len());
+    for raw in &resp.instruction_calls {
         let instr = &selection::InstructionCall::try_from(raw)?;
         // The query filters on `tx_success`, so a failed transaction's
         // instructions shouldn't arrive at all; the guard keeps a store that
@@ -598,54 +737,131 @@ fn build_event_items(
             client_filtered,
             address_store,
         );
-        if routed.is_empty() {
+        let event_registrations = event_registrations_in_scope(
+            &built.registrations,
+            instr,
+            &address,
+            client_filtered,
+            address_store,
+        );
+        let account_registrations = account_registrations_in_scope(
+            &built.registrations,
+            instr,
+            &address,
+            client_filtered,
+            address_store,
+        );
+        if routed.is_empty() && event_registrations.is_empty() && account_registrations.is_empty() {
             continue;
         }
+        let path: Vec<i64> = instr
+            .instruction_address
+            .iter()
+            .map(|&v| i64::from(v))
+            .collect();
+        let instruction_logs = logs_by_key
+            .get(&(
+                instr.slot,
+                instr.transaction_index,
+                instr.instruction_address.clone(),
+            ))
+            .map(Vec::as_slice);
         let decoded = if routed.iter().any(|reg| reg.selects_args) {
             schemas
                 .get(&instr.executing_account)
-                .and_then(|schema| borsh_decoder::decode_with_schema(schema, raw))
-        } else {
-            None
-        };
-        let logs = if routed.iter().any(|reg| !reg.log_columns.is_empty()) {
-            logs_by_key
-                .get(&(
-                    instr.slot,
-                    instr.transaction_index,
-                    instr.instruction_address.clone(),
-                ))
-                .cloned()
+                .map(|schema| borsh_decoder::decode_with_schema(schema, raw))
         } else {
             None
         };
         for reg in routed {
+            let (decoded, decode_error) = match decoded.as_ref().filter(|_| reg.selects_args) {
+                Some(Ok(decoded)) => (Some(decoded.clone()), None),
+                Some(Err(failure)) => (None, Some(failure.clone())),
+                None => (None, None),
+            };
             items.push(EventItem {
                 on_event_registration_index: reg.index,
                 slot,
                 transaction_index: i64::from(instr.transaction_index),
-                path: instr
-                    .instruction_address
-                    .iter()
-                    .map(|&v| i64::from(v))
-                    .collect(),
+                path: path.clone(),
                 program_id: instr.executing_account.clone(),
                 accounts: instr.account_arguments.clone(),
                 data: to_hex(&instr.data),
                 is_inner: instr.is_inner,
-                decoded: if reg.selects_args {
-                    decoded.clone()
+                decoded,
+                decode_error,
+                logs: if !reg.log_columns.is_empty() {
+                    instruction_logs.map(|logs| project_logs(logs, &reg.log_columns))
                 } else {
                     None
                 },
+            });
+        }
+        for reg in &account_registrations {
+            let mut written = Vec::new();
+            for account in &instr.account_arguments {
+                let key = (instr.slot, instr.transaction_index, account.clone());
+                if written_by_tx.contains(&key)
+                    && reported_writes.insert((reg.index, key.0, key.1, key.2))
+                {
+                    written.push(account.clone());
+                }
+            }
+            if written.is_empty() {
+                continue;
+            }
+            items.push(EventItem {
+                on_event_registration_index: reg.index,
+                slot,
+                transaction_index: i64::from(instr.transaction_index),
+                path: path.clone(),
+                program_id: instr.executing_account.clone(),
+                accounts: written,
+                data: "0x".to_string(),
+                is_inner: instr.is_inner,
+                decoded: None,
+                decode_error: None,
                 logs: if !reg.log_columns.is_empty() {
-                    logs.as_deref()
-                        .map(|logs| project_logs(logs, &reg.log_columns))
+                    instruction_logs.map(|logs| project_logs(logs, &reg.log_columns))
                 } else {
                     None
                 },
             });
         }
+        if event_registrations.is_empty() {
+            continue;
+        }
+        for payload in events::event_payloads(instr, instruction_logs) {
+            for reg in &event_registrations {
+                let (Some(schema), Some(discriminator)) = (&reg.event, &reg.discriminator) else {
+                    continue;
+                };
+                if !payload.starts_with(discriminator) {
+                    continue;
+                }
+                let (decoded, decode_error) = match borsh_decoder::decode_event(schema, &payload) {
+                    Ok(decoded) => (Some(decoded), None),
+                    Err(failure) => (None, Some(failure)),
+                };
+                items.push(EventItem {
+                    on_event_registration_index: reg.index,
+                    slot,
+                    transaction_index: i64::from(instr.transaction_index),
+                    path: path.clone(),
+                    program_id: instr.executing_account.clone(),
+                    accounts: Vec::new(),
+                    data: to_hex(&payload),
+                    is_inner: instr.is_inner,
+                    decoded,
+                    decode_error,
+                    logs: if !reg.log_columns.is_empty() {
+                        instruction_logs.map(|logs| project_logs(logs, &reg.log_columns))
+                    } else {
+                        None
+                    },
+                });
+            }
+        }
     }
     Ok(items)
 }
@@ -684,12 +900,15 @@ fn take_blocks(
         .context("mapping solana block headers")?;
     if let Some(slots) = slots {
         // Slots whose instructions were all dropped by client-side routing keep
-        // a slot+hash row so every returned header still backs reorg detection.
+        // a slot+hash row, with its parent link, so every returned header still
+        // backs reorg detection.
         for b in raw_blocks.iter_mut() {
             if !b.slot.is_some_and(|slot| slots.contains(&slot)) {
                 *b = simple::Block {
                     slot: b.slot,
                     blockhash: b.blockhash.take(),
+                    parent_slot: b.parent_slot,
+                    parent_blockhash: b.parent_blockhash.take(),
                     ..Default::default()
                 };
             }
@@ -708,6 +927,7 @@ pub(crate) fn map_err(e: anyhow::Error) -> napi::Error {
 mod tests {
     use super::*;
     use crate::address_store::test_support::{set_of, svm_store};
+    use crate::field_columns::js_masks;
     use query::{InstructionSelection, SvmQuery};
 
     const TOKEN_METADATA_PROGRAM: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
@@ -831,6 +1051,8 @@ mod tests {
             accounts: vec![],
             args_json: None,
             defined_types_json: None,
+            is_event: None,
+            is_account: None,
         }
     }
 
@@ -863,8 +1085,11 @@ mod tests {
         let address_store = store.handle();
         let address_store = address_store.read().unwrap();
         build_event_items(
-            instructions,
-            logs,
+            &mut simple::SolanaResponse {
+                instruction_calls: instructions.to_vec(),
+                logs,
+                ..Default::default()
+            },
             built,
             &HashMap::new(),
             set.cache(),
@@ -972,6 +1197,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn anchor_events_are_read_off_the_program_instructions() {
+        use base64::prelude::{Engine, BASE64_STANDARD};
+
+        let (store, set) = fixture(&["TokenMetadata"]);
+        let mut event = reg_input(1, "0x0102030405060708", false);
+        event.is_event = Some(true);
+        event.instruction_name = "Traded".to_string();
+        event.args_json = Some(r#"[{"name":"amount","type":"u8"}]"#.to_string());
+        let built = SelectionBuilder::from_registrations(
+            &[reg_input(0, "0x21", false), event],
+            &store.handle().read().unwrap(),
+        )
+        .unwrap()
+        .build(&[0, 1])
+        .unwrap();
+        // The event needs the whole program's instructions and their logs.
+        assert_eq!(built.instruction_selections.len(), 2);
+        assert!(built
+            .instruction_selections
+            .iter()
+            .any(|selection| selection.d1.is_empty() && selection.d8.is_empty()));
+        assert!(built.log_columns.contains(&"message"));
+
+        let event_bytes = [1u8, 2, 3, 4, 5, 6, 7, 8, 42];
+        let logs = vec![
+            simple::Log {
+                slot: Some(42),
+                transaction_index: Some(7),
+                instruction_address: Some(vec![1]),
+                kind: Some(simple::LogKind::Data),
+                message: Some(BASE64_STANDARD.encode(event_bytes)),
+                ..Default::default()
+            },
+            // Another event of the program, not registered.
+            simple::Log {
+                slot: Some(42),
+                transaction_index: Some(7),
+                instruction_address: Some(vec![1]),
+                kind: Some(simple::LogKind::Data),
+                message: Some(BASE64_STANDARD.encode([9u8; 9])),
+                ..Default::default()
+            },
+        ];
+        let items = route(
+            &store,
+            &set,
+            &[
+                committed_instruction(&[0x21]),
+                // No instruction registration matches, the event still does.
+                {
+                    let mut other = committed_instruction(&[0x33]);
+                    other.transaction_index = Some(8);
+                    other
+                },
+            ],
+            logs,
+            &built,
+        )
+        .unwrap();
+        assert_eq!(
+            items
+                .iter()
+                .map(|i| (
+                    i.on_event_registration_index,
+                    i.transaction_index,
+                    i.data.as_str(),
+                    i.decoded.as_ref().map(|d| d.args_json.as_str()),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 7, "0x21", None),
+                (1, 7, "0x01020304050607082a", Some(r#"{"amount":42}"#)),
+            ]
+        );
+    }
+
+    #[test]
+    fn program_accounts_are_the_writable_accounts_the_program_passes() {
+        const POOL: &str = "11111111111111111111111111111112";
+        const USER: &str = "11111111111111111111111111111113";
+
+        let (store, set) = fixture(&["TokenMetadata"]);
+        let mut account = reg_input(0, "0x0102030405060708", false);
+        account.is_account = Some(true);
+        account.instruction_name = "Pool".to_string();
+        account.args_json = Some(r#"[{"name":"amount","type":"u8"}]"#.to_string());
+        let built =
+            SelectionBuilder::from_registrations(&[account], &store.handle().read().unwrap())
+                .unwrap()
+                .build(&[0])
+                .unwrap();
+        // The whole program's instructions, with their accounts and which of
+        // them the transaction writes.
+        assert!(built.instruction_selections[0].d8.is_empty());
+        assert!(built.instruction_columns.contains(&"account_arguments"));
+        assert!(built.account_activity_columns.contains(&"is_writable"));
+
+        let activity =
+            |transaction_index: u32, account: &str, is_writable: bool| simple::AccountActivity {
+                slot: Some(42),
+                transaction_index: Some(transaction_index),
+                account: Some(account.parse().unwrap()),
+                is_writable: Some(is_writable),
+                ..Default::default()
+            };
+        let instruction = |transaction_index: u32, path: u32| {
+            let mut instr = committed_instruction(&[0x21]);
+            instr.transaction_index = Some(transaction_index);
+            instr.instruction_address = Some(vec![path]);
+            instr.account_arguments = Some(vec![USER.parse().unwrap(), POOL.parse().unwrap()]);
+            instr
+        };
+        let address_store = store.handle();
+        let address_store = address_store.read().unwrap();
+        let items = build_event_items(
+            &mut simple::SolanaResponse {
+                // The second instruction writes the pool again in the same
+                // transaction, so only the first reports it.
+                instruction_calls: vec![instruction(7, 0), instruction(7, 1), instruction(8, 0)],
+                account_activity: vec![
+                    activity(7, USER, false),
+                    activity(7, POOL, true),
+                    activity(8, USER, true),
+                    activity(8, POOL, true),
+                ],
+                ..Default::default()
+            },
+            &built,
+            &HashMap::new(),
+            set.cache(),
+            &Default::default(),
+            &address_store,
+        )
+        .unwrap();
+        assert_eq!(
+            items
+                .iter()
+                .map(|i| (i.transaction_index, i.path.clone(), i.accounts.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (7, vec![0], vec![POOL.to_string()]),
+                (8, vec![0], vec![USER.to_string(), POOL.to_string()]),
+            ]
+        );
+    }
+
     #[test]
     fn kind_only_log_selection_omits_message() {
         let (store, set) = fixture(&["TokenMetadata"]);
@@ -1028,8 +1400,10 @@ mod tests {
         let address_store = store.handle();
         let address_store = address_store.read().unwrap();
         let items = build_event_items(
-            std::slice::from_ref(&instr),
-            vec![],
+            &mut simple::SolanaResponse {
+                instruction_calls: vec![instr],
+                ..Default::default()
+            },
             &built,
             &schemas,
             set.cache(),
@@ -1065,6 +1439,8 @@ mod tests {
                 accounts: vec!["metadata".to_string()],
                 args_json: Some(r#"[{"name":"amount","type":"u64"}]"#.to_string()),
                 defined_types_json: None,
+                is_event: None,
+                is_account: None,
             };
         let mut schemaless = with_schema(2, "NoSchema", "0x03");
         schemaless.accounts = vec![];
@@ -1133,11 +1509,10 @@ mod tests {
             Some(&referenced),
         );
 
-        let mask = ((1u64 << (crate::transaction_store::SvmTxField::FeePayer as u32))
-            | (1u64 << (crate::transaction_store::SvmTxField::AccountActivities as u32)))
-            as f64;
+        let mask = (1u64 << (crate::transaction_store::SvmTxField::FeePayer as u32))
+            | (1u64 << (crate::transaction_store::SvmTxField::AccountActivities as u32));
         let cols = store
-            .materialize(vec![42, 43], vec![7, 7], vec![mask, mask])
+            .materialize(vec![42, 43], vec![7, 7], js_masks(&[mask, mask]))
             .await
             .expect("materialize");
 
@@ -1183,10 +1558,9 @@ mod tests {
             vec![activity(1, None), activity(2, Some(mint(43)))],
             None,
         );
-        let mask =
-            (1u64 << (crate::transaction_store::SvmTxField::AccountActivities as u32)) as f64;
+        let mask = 1u64 << (crate::transaction_store::SvmTxField::AccountActivities as u32);
         let cols = store
-            .materialize(vec![43], vec![7], vec![mask])
+            .materialize(vec![43], vec![7], js_masks(&[mask]))
             .await
             .expect("materialize");
         match column(&cols, "accountActivities") {
@@ -1237,14 +1611,14 @@ mod tests {
             .materialize(
                 vec![43],
                 vec![7],
-                vec![(1u64 << (crate::transaction_store::SvmTxField::FeePayer as u32)) as f64],
+                js_masks(&[(1u64 << (crate::transaction_store::SvmTxField::FeePayer as u32))]),
             )
             .await
             .expect("materialize transactions");
         let blocks = block_store
             .materialize(
                 vec![43],
-                vec![(1u64 << (crate::block_store::SvmBlockField::Hash as u32)) as f64],
+                js_masks(&[(1u64 << (crate::block_store::SvmBlockField::Hash as u32))]),
             )
             .await
             .expect("materialize blocks");
@@ -1279,9 +1653,9 @@ mod tests {
         let (headers, block_store) =
             take_blocks(&mut resp, Some(&referenced)).expect("take blocks");
 
-        let mask = (1u64 << (crate::block_store::SvmBlockField::Hash as u32)) as f64;
+        let mask = 1u64 << (crate::block_store::SvmBlockField::Hash as u32);
         let cols = block_store
-            .materialize(vec![42, 43], vec![mask, mask])
+            .materialize(vec![42, 43], js_masks(&[mask, mask]))
             .await
             .expect("materialize");
 
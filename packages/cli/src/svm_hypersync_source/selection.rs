@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -7,10 +7,12 @@ use hypersync_solana_net_types::query as net;
 use hypersync_solana_net_types::types::Address;
 use napi_derive::napi;
 
+use super::borsh_decoder::{build_event_schema, EventSchema};
 use super::fields;
 use super::mod_helpers::hex_to_bytes;
 use super::types::required;
 use crate::address_store::StoreInner;
+use crate::config_parsing::human_config::svm::{ArgDef, ArgType};
 
 /// One instruction call with the fields routing and item building read, lifted
 /// out of the client's all-`Option` row once per instruction: base58 is
@@ -110,6 +112,16 @@ pub struct SvmOnEventRegistrationInput {
     /// Program-level nominal-type registry (`BTreeMap<String, ArgType>` JSON),
     /// duplicated on every instruction of the program.
     pub defined_types_json: Option<String>,
+    /// `Some(true)` for an Anchor event rather than an instruction:
+    /// `discriminator` is then the event's 8-byte one and `args_json` its
+    /// field layout. See `super::events`.
+    pub is_event: Option<bool>,
+    /// `Some(true)` for an Anchor account type the program owns:
+    /// `discriminator` is then the one its data starts with and `args_json`
+    /// its field layout. Items carry the accounts an instruction of the
+    /// program wrote; the state itself is read and decoded separately (see
+    /// `SvmHyperSyncClient::decode_account`).
+    pub is_account: Option<bool>,
 }
 
 pub(crate) struct Registration {
@@ -136,6 +148,13 @@ pub(crate) struct Registration {
     pub instruction_columns: Vec<&'static str>,
     /// `fields.instruction` contains `args` — Borsh decode is skipped otherwise.
     pub selects_args: bool,
+    /// Set for an Anchor event registration, which never routes an
+    /// instruction itself: its events are read off the program's instructions.
+    pub event: Option<EventSchema>,
+    /// Set for a program-account registration, which likewise never routes an
+    /// instruction: its items are the accounts the program's instructions
+    /// write.
+    pub account: Option<EventSchema>,
 }
 
 impl Registration {
@@ -185,6 +204,40 @@ impl Registration {
             !account_filters.is_empty(),
         )?;
         let selects_args = fields::selects(&input.instruction_fields, "args");
+        let is_account = input.is_account == Some(true);
+        let layout = if input.is_event == Some(true) || is_account {
+            let what = if is_account { "account" } else { "event" };
+            anyhow::ensure!(
+                byte_len == 8,
+                "an {what} discriminator must be 8 bytes, got {byte_len}"
+            );
+            let defined_types: BTreeMap<String, ArgType> = input
+                .defined_types_json
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .context("parse defined types")?
+                .unwrap_or_default();
+            let fields: Vec<ArgDef> = input
+                .args_json
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .with_context(|| format!("parse {what} fields"))?
+                .unwrap_or_default();
+            Some(build_event_schema(
+                input.instruction_name.clone(),
+                &defined_types,
+                &fields,
+            )?)
+        } else {
+            None
+        };
+        let (event, account) = if is_account {
+            (None, layout)
+        } else {
+            (layout, None)
+        };
         let contract_idx = store.contract_idx(&input.contract_name).with_context(|| {
             format!(
                 "Program {} is missing from the chain's address store",
@@ -199,7 +252,13 @@ impl Registration {
             is_wildcard: input.is_wildcard,
             start_block: input.start_block,
             discriminator,
-            discriminator_hex: input.discriminator.clone().filter(|d| !d.is_empty()),
+            // An event's or account's discriminator never prefixes the
+            // instructions carrying or writing it, so the query selects the
+            // whole program.
+            discriminator_hex: input
+                .discriminator
+                .clone()
+                .filter(|d| !d.is_empty() && event.is_none() && account.is_none()),
             byte_len,
             is_inner: input.is_inner,
             account_filters,
@@ -209,6 +268,8 @@ impl Registration {
             log_columns,
             instruction_columns,
             selects_args,
+            event,
+            account,
         })
     }
 
@@ -361,11 +422,15 @@ impl SelectionBuilder {
         Ok(Self { registrations: map })
     }
 
+    pub(crate) fn registration(&self, index: i64) -> Option<&Arc<Registration>> {
+        self.registrations.get(&index)
+    }
+
     pub(crate) fn build(&self, registration_indexes: &[i64]) -> Result<BuiltSelection> {
         let mut selections: Vec<BuiltInstructionSelection> = Vec::new();
-        // The always-fetched trio: `slot` keys the page's blocks, and the
-        // consumer reads time/hash off every block (reorg detection, item
-        // timestamps).
+        // The always-fetched keys: `slot` keys the page's blocks, the consumer
+        // reads time/hash off every block (reorg detection, item timestamps),
+        // and the parent link is checked against the stored parent.
         let mut block_columns = fields::BLOCK_KEYS.to_vec();
         let mut transaction_columns: Vec<&'static str> = Vec::new();
         let mut account_activity_columns: Vec<&'static str> = Vec::new();
@@ -395,6 +460,21 @@ impl SelectionBuilder {
             for &column in &reg.instruction_columns {
                 fields::push_unique(&mut instruction_columns, column);
             }
+            // `emit!` events are read off `Program data:` lines, whether or not
+            // the handler selected logs.
+            if reg.event.is_some() {
+                for &column in fields::EVENT_LOG_COLUMNS {
+                    fields::push_unique(&mut log_columns, column);
+                }
+            }
+            // Written accounts are the instruction's account arguments the
+            // transaction marks writable.
+            if reg.account.is_some() {
+                fields::push_unique(&mut instruction_columns, "account_arguments");
+                for &column in fields::ACCOUNT_WRITE_COLUMNS {
+                    fields::push_unique(&mut account_activity_columns, column);
+                }
+            }
 
             // Placeholder configs carry no real program — skip rather than
             // ship a degenerate match-all selection.
@@ -458,12 +538,14 @@ pub(crate) fn route_instruction(
     store: &StoreInner,
 ) -> Vec<Arc<Registration>> {
     let scoped = |reg: &Registration| {
-        reg.matches_scope(
-            instr,
-            address,
-            client_filtered.applies(&reg.contract_name),
-            store,
-        )
+        reg.event.is_none()
+            && reg.account.is_none()
+            && reg.matches_scope(
+                instr,
+                address,
+                client_filtered.applies(&reg.contract_name),
+                store,
+            )
     };
     for byte_len in [8usize, 4, 2, 1] {
         let matched: Vec<Arc<Registration>> = registrations
@@ -484,6 +566,67 @@ pub(crate) fn route_instruction(
         .collect()
 }
 
+/// The selection's event registrations whose program an instruction belongs
+/// to, under the same owner gate as instructions.
+pub(crate) fn event_registrations_in_scope(
+    registrations: &[Arc<Registration>],
+    instr: &InstructionCall,
+    address: &InstructionAddress,
+    client_filtered: &crate::client_filtered_contracts::ClientFilteredContracts,
+    store: &StoreInner,
+) -> Vec<Arc<Registration>> {
+    registrations_in_scope(
+        registrations,
+        instr,
+        address,
+        client_filtered,
+        store,
+        |reg| reg.event.is_some(),
+    )
+}
+
+/// The selection's program-account registrations whose program an
+/// instruction belongs to, under the same owner gate as instructions.
+pub(crate) fn account_registrations_in_scope(
+    registrations: &[Arc<Registration>],
+    instr: &InstructionCall,
+    address: &InstructionAddress,
+    client_filtered: &crate::client_filtered_contracts::ClientFilteredContracts,
+    store: &StoreInner,
+) -> Vec<Arc<Registration>> {
+    registrations_in_scope(
+        registrations,
+        instr,
+        address,
+        client_filtered,
+        store,
+        |reg| reg.account.is_some(),
+    )
+}
+
+fn registrations_in_scope(
+    registrations: &[Arc<Registration>],
+    instr: &InstructionCall,
+    address: &InstructionAddress,
+    client_filtered: &crate::client_filtered_contracts::ClientFilteredContracts,
+    store: &StoreInner,
+    kind: impl Fn(&Registration) -> bool,
+) -> Vec<Arc<Registration>> {
+    registrations
+        .iter()
+        .filter(|reg| {
+            kind(reg)
+                && reg.matches_scope(
+                    instr,
+                    address,
+                    client_filtered.applies(&reg.contract_name),
+                    store,
+                )
+        })
+        .cloned()
+        .collect()
+}
+
 /// The emitter facts an instruction's owner gate reads: the program id's store
 /// key (its base58 bytes), the contract this partition's set says owns it, and
 /// the slot the instruction sits at.
@@ -528,6 +671,8 @@ mod tests {
             accounts: vec![],
             args_json: None,
             defined_types_json: None,
+            is_event: None,
+            is_account: None,
         }
     }
 
@@ -708,7 +853,14 @@ mod tests {
                 built.instruction_columns.clone(),
             ),
             (
-                vec!["slot", "blockhash", "block_time", "block_height"],
+                vec![
+                    "slot",
+                    "blockhash",
+                    "block_time",
+                    "parent_slot",
+                    "parent_blockhash",
+                    "block_height"
+                ],
                 vec!["slot", "transaction_index", "transaction_id"],
                 vec!["slot", "transaction_index", "account", "mint"],
                 vec![
@@ -854,11 +1006,13 @@ mod tests {
             name: "Owned".to_string(),
             start_block: None,
             depends_on_addresses: true,
+            ttl_blocks: None,
         }]);
         store.register_seed(vec![crate::address_store::AddressRegistration {
             address: PROG_A.to_string(),
             contract_name: "Owned".to_string(),
             registration_block: 70,
+            end_block: None,
         }]);
         let set = set_of(&store, &["Owned"]);
         let built = SelectionBuilder::from_registrations(
@@ -10,7 +10,8 @@
 
 use std::sync::Mutex;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use hypersync_client::format::Quantity;
 use hypersync_client::simple_types;
 use hypersync_client_solana::simple_types as solana_simple;
 use napi::bindgen_prelude::BigInt;
@@ -22,14 +23,14 @@ use crate::evm_hypersync_source::types::{
     encode_address, map_bigint, AccessList as AccessListItem, Authorization as AuthorizationItem,
 };
 use crate::field_columns::{
-    build_columns, bytes, field_names, Column, Columns, Ecosystem, SvmAccountActivityOut,
-    SvmAccountTokenOut, SvmLamportsOut,
+    build_columns, bytes, field_names, masks_from_js, Column, Columns, Ecosystem,
+    SvmAccountActivityOut, SvmAccountTokenOut, SvmLamportsOut,
 };
 use crate::field_table::{
     access_lists_cells, access_lists_from, auth_lists_cells, auth_lists_from, bool_cells,
     bool_from, bytes_cells, f64_cells, f64_from, fixed_from, hash_list_cells, hash_list_from,
     hex_full, hex_quantity, str_list_cells, str_list_from, u64_cells, u64_from, utf8, var_from,
-    AnyCol, Table,
+    AnyCol, FieldMask, Table,
 };
 use crate::svm_hypersync_source::types::bigint_u64;
 
@@ -72,6 +73,15 @@ pub enum EvmTxField {
     GasUsedForL1 = 29,
     AccessList = 30,
     AuthorizationList = 31,
+    SourceHash = 32,
+    Mint = 33,
+    IsSystemTx = 34,
+    L1BaseFeeScalar = 35,
+    L1BlobBaseFee = 36,
+    L1BlobBaseFeeScalar = 37,
+    OperatorFeeScalar = 38,
+    L1BatchNumber = 39,
+    L1BatchTxIndex = 40,
 }
 
 impl EvmTxField {
@@ -112,6 +122,15 @@ impl EvmTxField {
             GasUsedForL1 => "gasUsedForL1",
             AccessList => "accessList",
             AuthorizationList => "authorizationList",
+            SourceHash => "sourceHash",
+            Mint => "mint",
+            IsSystemTx => "isSystemTx",
+            L1BaseFeeScalar => "l1BaseFeeScalar",
+            L1BlobBaseFee => "l1BlobBaseFee",
+            L1BlobBaseFeeScalar => "l1BlobBaseFeeScalar",
+            OperatorFeeScalar => "operatorFeeScalar",
+            L1BatchNumber => "l1BatchNumber",
+            L1BatchTxIndex => "l1BatchTxIndex",
         }
     }
 }
@@ -156,11 +175,27 @@ impl SvmTxField {
     }
 }
 
-/// Build one EVM field's column from a response's transactions. `None` for the
+/// The transaction fields `simple_types::Transaction` has no slot for. Only the
+/// RPC source reads them (off the transaction and receipt responses); HyperSync
+/// doesn't serve them, so over HyperSync they're always null.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EvmTxExtras {
+    pub(crate) is_system_tx: Option<bool>,
+    pub(crate) operator_fee_scalar: Option<Quantity>,
+    pub(crate) l1_batch_number: Option<u64>,
+    pub(crate) l1_batch_tx_index: Option<u64>,
+}
+
+/// Build one EVM field's column from a response's transactions. `extras` is
+/// either aligned with `txs` or empty when the source has none. `None` for the
 /// key-derived `transactionIndex` and for fields no transaction carries.
 /// Exhaustive match: adding an `EvmTxField` variant fails to compile until it
 /// is filled here and decoded below.
-fn evm_tx_col(field: EvmTxField, txs: &[simple_types::Transaction]) -> Option<AnyCol> {
+fn evm_tx_col(
+    field: EvmTxField,
+    txs: &[simple_types::Transaction],
+    extras: &[EvmTxExtras],
+) -> Option<AnyCol> {
     use EvmTxField::*;
     match field {
         // The within-block index is part of the table key, not a column.
@@ -202,6 +237,15 @@ fn evm_tx_col(field: EvmTxField, txs: &[simple_types::Transaction]) -> Option<An
         GasUsedForL1 => var_from(txs, |t| t.gas_used_for_l1.as_ref().map(bytes)),
         AccessList => access_lists_from(txs, |t| t.access_list.clone()),
         AuthorizationList => auth_lists_from(txs, |t| t.authorization_list.clone()),
+        SourceHash => fixed_from(txs, 32, |t| t.source_hash.as_ref().map(bytes)),
+        Mint => var_from(txs, |t| t.mint.as_ref().map(bytes)),
+        L1BaseFeeScalar => var_from(txs, |t| t.l1_base_fee_scalar.as_ref().map(bytes)),
+        L1BlobBaseFee => var_from(txs, |t| t.l1_blob_base_fee.as_ref().map(bytes)),
+        L1BlobBaseFeeScalar => var_from(txs, |t| t.l1_blob_base_fee_scalar.as_ref().map(bytes)),
+        IsSystemTx => bool_from(extras, |e| e.is_system_tx),
+        OperatorFeeScalar => var_from(extras, |e| e.operator_fee_scalar.as_ref().map(bytes)),
+        L1BatchNumber => u64_from(extras, |e| e.l1_batch_number),
+        L1BatchTxIndex => u64_from(extras, |e| e.l1_batch_tx_index),
     }
 }
 
@@ -211,11 +255,10 @@ fn decode_evm_field(
     field: EvmTxField,
     scratch: &[Option<AnyCol>],
     transaction_indices: &[u32],
-    masks: &[u64],
+    masks: &[FieldMask],
     should_checksum: bool,
 ) -> Result<Column> {
     use EvmTxField::*;
-    let bit = 1u64 << (field as u32);
     let col = scratch[field as usize].as_ref();
     let len = transaction_indices.len();
     Ok(match field {
@@ -225,10 +268,10 @@ fn decode_evm_field(
             transaction_indices
                 .iter()
                 .zip(masks)
-                .map(|(&i, &m)| (m & bit != 0).then_some(i as i64))
+                .map(|(&i, &m)| m.has(field as usize).then_some(i as i64))
                 .collect(),
         ),
-        Hash | Input | LogsBloom | Root => {
+        Hash | Input | LogsBloom | Root | SourceHash => {
             Column::Str(bytes_cells(col, len, |b| Ok(Some(hex_full(b))))?)
         }
         From | To | ContractAddress => Column::Str(bytes_cells(col, len, |b| {
@@ -237,11 +280,15 @@ fn decode_evm_field(
         })?),
         Gas | GasPrice | MaxPriorityFeePerGas | MaxFeePerGas | CumulativeGasUsed
         | EffectiveGasPrice | GasUsed | Nonce | Value | MaxFeePerBlobGas | L1Fee | L1GasPrice
-        | L1GasUsed | GasUsedForL1 => {
+        | L1GasUsed | GasUsedForL1 | Mint | L1BaseFeeScalar | L1BlobBaseFee
+        | L1BlobBaseFeeScalar | OperatorFeeScalar => {
             Column::Big(bytes_cells(col, len, |b| Ok(map_bigint(&Some(b))))?)
         }
         V | R | S | YParity => Column::Str(bytes_cells(col, len, |b| Ok(Some(hex_quantity(b))))?),
-        Status | Type => Column::I64(u64_cells(col, len, |v| Ok(Some(v as i64)))?),
+        Status | Type | L1BatchNumber | L1BatchTxIndex => {
+            Column::I64(u64_cells(col, len, |v| Ok(Some(v as i64)))?)
+        }
+        IsSystemTx => Column::Bool(bool_cells(col, len)),
         BlobVersionedHashes => Column::StrVec(hash_list_cells(col, len, |h| hex_full(h))),
         L1FeeScalar => Column::F64(f64_cells(col, len)),
         AccessList => Column::AccessList(access_lists_cells(col, len, |a| AccessListItem::from(a))),
@@ -254,7 +301,7 @@ fn decode_evm_field(
 fn decode_evm_columns(
     scratch: &[Option<AnyCol>],
     transaction_indices: &[u32],
-    masks: &[u64],
+    masks: &[FieldMask],
     should_checksum: bool,
 ) -> Result<Columns> {
     build_columns(
@@ -313,10 +360,9 @@ fn decode_svm_field(
     scratch: &[Option<AnyCol>],
     account_activities: &[Option<Vec<SvmAccountActivityOut>>],
     transaction_indices: &[u32],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Result<Column> {
     use SvmTxField::*;
-    let bit = 1u64 << (field as u32);
     let col = scratch[field as usize].as_ref();
     let len = transaction_indices.len();
     Ok(match field {
@@ -326,7 +372,7 @@ fn decode_svm_field(
             transaction_indices
                 .iter()
                 .zip(masks)
-                .map(|(&i, &m)| (m & bit != 0).then_some(i as i64))
+                .map(|(&i, &m)| m.has(field as usize).then_some(i as i64))
                 .collect(),
         ),
         AllSignatures | AccountKeys => Column::StrVec(str_list_cells(col, len)),
@@ -345,7 +391,7 @@ fn decode_svm_columns(
     scratch: &[Option<AnyCol>],
     account_activities: &[Option<Vec<SvmAccountActivityOut>>],
     transaction_indices: &[u32],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Result<Columns> {
     build_columns(
         SvmTxField::VARIANTS,
@@ -414,13 +460,12 @@ fn activity_row(
 fn gather_account_activities(
     table: &Table<(u64, u32, Box<str>)>,
     keys: &[Option<(u64, u32)>],
-    masks: &[u64],
+    masks: &[FieldMask],
 ) -> Vec<Option<Vec<SvmAccountActivityOut>>> {
-    let bit = 1u64 << (SvmTxField::AccountActivities as u32);
     keys.iter()
         .zip(masks)
         .map(|(key, &m)| {
-            if m & bit == 0 {
+            if !m.has(SvmTxField::AccountActivities as usize) {
                 return None;
             }
             let Some(key) = key else {
@@ -568,10 +613,10 @@ impl TransactionStore {
     /// Move every row from `page` into this store (merging a fetch-response
     /// page into the persistent per-chain store).
     #[napi]
-    pub fn merge(&self, page: &TransactionStore) {
+    pub fn merge(&self, page: &TransactionStore) -> napi::Result<()> {
         // Merging a store into itself would lock the same Mutex twice (deadlock).
         if std::ptr::eq(self, page) {
-            return;
+            return Ok(());
         }
         // A page and its persistent store are the same per-chain ecosystem (both
         // derive it from the one chain config), so the decoder is unaffected by the merge.
@@ -580,6 +625,25 @@ impl TransactionStore {
         let mut src = page.inner.lock().unwrap();
         dst.txs.append_from(&mut src.txs);
         dst.account_activity.append_from(&mut src.account_activity);
+        dst.txs
+            .spill_cold_rows()
+            .context("spilling transactions to disk")
+            .map_err(map_err)
+    }
+
+    /// Page the persistent store's cold transactions out to the file at `path`
+    /// once they take more than `budget_bytes` in memory; `materialize` reads
+    /// them back. SVM account activity stays in memory. The file is recreated
+    /// empty and removed when the store is dropped.
+    #[napi]
+    pub fn enable_spill(&self, path: String, budget_bytes: i64) -> napi::Result<()> {
+        self.inner
+            .lock()
+            .unwrap()
+            .txs
+            .enable_spill(path.into(), usize::try_from(budget_bytes).unwrap_or(0), &[])
+            .context("opening the transaction spill file")
+            .map_err(map_err)
     }
 
     /// Bulk-materialise transactions in columnar form, one row per
@@ -587,8 +651,8 @@ impl TransactionStore {
     /// whose bit is set in that row's own `masks[i]`. Per-row masks let each event
     /// pull just the transaction fields it selected, so a large field (e.g.
     /// `input`) is materialised only on the rows that asked for it. Each mask is a
-    /// JS number (`f64`) carrying a selection bitmask over field codes 0..31 (so
-    /// it fits in 32 bits). The lock is held only to gather the requested cells;
+    /// JS `BigInt` carrying a selection bitmask over field codes (see
+    /// `masks_from_js`). The lock is held only to gather the requested cells;
     /// decoding runs after it is released, off the JS thread via
     /// `block_in_place`. Missing keys yield an empty object. Result is aligned
     /// with input.
@@ -597,7 +661,7 @@ impl TransactionStore {
         &self,
         block_numbers: Vec<i64>,
         transaction_indices: Vec<u32>,
-        masks: Vec<f64>,
+        masks: Vec<BigInt>,
     ) -> napi::Result<Columns> {
         // The three columns are zipped row-wise into the output; a length mismatch
         // would silently truncate and misalign the result with the caller's items.
@@ -610,7 +674,7 @@ impl TransactionStore {
                 masks.len()
             )));
         }
-        let masks: Vec<u64> = masks.iter().map(|&m| m as u64).collect();
+        let masks = masks_from_js(&masks)?;
         let keys: Vec<Option<(u64, u32)>> = block_numbers
             .iter()
             .zip(&transaction_indices)
@@ -619,7 +683,14 @@ impl TransactionStore {
 
         match self.ecosystem {
             Ecosystem::Evm { should_checksum } => {
-                let scratch = self.inner.lock().unwrap().txs.gather_scratch(&keys, &masks);
+                let scratch = self
+                    .inner
+                    .lock()
+                    .unwrap()
+                    .txs
+                    .gather_scratch(&keys, &masks)
+                    .context("reading spilled transactions")
+                    .map_err(map_err)?;
                 tokio::task::block_in_place(|| {
                     decode_evm_columns(&scratch, &transaction_indices, &masks, should_checksum)
                 })
@@ -629,7 +700,11 @@ impl TransactionStore {
                 let (scratch, account_activities) = {
                     let stores = self.inner.lock().unwrap();
                     (
-                        stores.txs.gather_scratch(&keys, &masks),
+                        stores
+                            .txs
+                            .gather_scratch(&keys, &masks)
+                            .context("reading spilled transactions")
+                            .map_err(map_err)?,
                         gather_account_activities(&stores.account_activity, &keys, &masks),
                     )
                 };
@@ -765,6 +840,10 @@ impl TransactionStore {
     }
 }
 
+fn has_evm_tx_key(t: &simple_types::Transaction) -> bool {
+    t.block_number.is_some() && t.transaction_index.is_some()
+}
+
 impl TransactionStore {
     fn with_ecosystem(ecosystem: Ecosystem) -> Self {
         let n_fields = match ecosystem {
@@ -785,7 +864,22 @@ impl TransactionStore {
     /// HyperSync source while building a page). Rows without a (block, index)
     /// key are dropped. Not exposed to JS.
     pub(crate) fn insert_evm_txs(&self, mut txs: Vec<simple_types::Transaction>) {
-        txs.retain(|t| t.block_number.is_some() && t.transaction_index.is_some());
+        txs.retain(has_evm_tx_key);
+        self.merge_evm_txs(txs, &[]);
+    }
+
+    /// Like `insert_evm_txs`, for the RPC source, whose rows also carry the
+    /// fields only its responses have. Not exposed to JS.
+    pub(crate) fn insert_evm_rpc_txs(
+        &self,
+        mut rows: Vec<(simple_types::Transaction, EvmTxExtras)>,
+    ) {
+        rows.retain(|(t, _)| has_evm_tx_key(t));
+        let (txs, extras): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+        self.merge_evm_txs(txs, &extras);
+    }
+
+    fn merge_evm_txs(&self, txs: Vec<simple_types::Transaction>, extras: &[EvmTxExtras]) {
         if txs.is_empty() {
             return;
         }
@@ -798,7 +892,7 @@ impl TransactionStore {
         let keys = txs.iter().map(key).collect();
         let cols = EvmTxField::VARIANTS
             .iter()
-            .map(|&f| evm_tx_col(f, &txs))
+            .map(|&f| evm_tx_col(f, &txs, extras))
             .collect();
         self.inner.lock().unwrap().txs.merge_batch(keys, cols);
     }
@@ -903,6 +997,7 @@ pub fn svm_transaction_field_names() -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field_columns::js_masks;
 
     fn raw_tx(block: u64, index: u64) -> simple_types::Transaction {
         simple_types::Transaction {
@@ -932,8 +1027,8 @@ mod tests {
         1u64 << (field as u32)
     }
 
-    fn svm_mask(field: SvmTxField) -> f64 {
-        (1u64 << (field as u32)) as f64
+    fn svm_mask(field: SvmTxField) -> u64 {
+        1u64 << (field as u32)
     }
 
     type TokenView = (String, Option<String>, Option<u8>, Option<u64>, Option<u64>);
@@ -997,9 +1092,9 @@ mod tests {
         store.insert_evm_txs(vec![tx]);
 
         // Select only `input` via the bitmask.
-        let mask = bit(EvmTxField::Input) as f64;
+        let mask = bit(EvmTxField::Input);
         let cols = store
-            .materialize(vec![1], vec![0], vec![mask])
+            .materialize(vec![1], vec![0], js_masks(&[mask]))
             .await
             .expect("materialize");
 
@@ -1014,6 +1109,72 @@ mod tests {
         assert_eq!(summary, (vec![Some("0xabcd".to_string())], false, false));
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rpc_only_fields_decode_and_stay_null_for_hypersync_rows() {
+        let store = TransactionStore::new_evm(false);
+        let mut deposit = raw_tx(1, 0);
+        deposit.source_hash = Some(hypersync_client::format::Hash::from([0x11; 32]));
+        deposit.mint = Some(Quantity::from(7u64));
+        store.insert_evm_txs(vec![deposit]);
+        store.insert_evm_rpc_txs(vec![(
+            raw_tx(1, 1),
+            EvmTxExtras {
+                is_system_tx: Some(true),
+                operator_fee_scalar: Some(Quantity::from(3u64)),
+                l1_batch_number: Some(42),
+                l1_batch_tx_index: Some(5),
+            },
+        )]);
+
+        let mask = bit(EvmTxField::SourceHash)
+            | bit(EvmTxField::Mint)
+            | bit(EvmTxField::IsSystemTx)
+            | bit(EvmTxField::OperatorFeeScalar)
+            | bit(EvmTxField::L1BatchNumber)
+            | bit(EvmTxField::L1BatchTxIndex);
+        let cols = store
+            .materialize(vec![1, 1], vec![0, 1], js_masks(&[mask, mask]))
+            .await
+            .expect("materialize");
+
+        let big = |name| match column(&cols, name) {
+            Some(Column::Big(v)) => v
+                .iter()
+                .map(|b| b.as_ref().map(|b| b.clone().get_u64().1))
+                .collect::<Vec<_>>(),
+            _ => panic!("expected {name} column"),
+        };
+        let int = |name| match column(&cols, name) {
+            Some(Column::I64(v)) => v.clone(),
+            _ => panic!("expected {name} column"),
+        };
+        let summary = (
+            match column(&cols, "sourceHash") {
+                Some(Column::Str(v)) => v.clone(),
+                _ => panic!("expected sourceHash column"),
+            },
+            big("mint"),
+            match column(&cols, "isSystemTx") {
+                Some(Column::Bool(v)) => v.clone(),
+                _ => panic!("expected isSystemTx column"),
+            },
+            big("operatorFeeScalar"),
+            int("l1BatchNumber"),
+            int("l1BatchTxIndex"),
+        );
+        assert_eq!(
+            summary,
+            (
+                vec![Some(format!("0x{}", "11".repeat(32))), None],
+                vec![Some(7), None],
+                vec![None, Some(true)],
+                vec![None, Some(3)],
+                vec![None, Some(42)],
+                vec![None, Some(5)],
+            )
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn decode_applies_each_rows_own_mask() {
         // Row 0 selects `input`; row 1 selects only `transactionIndex`. The union
@@ -1033,10 +1194,7 @@ mod tests {
             .materialize(
                 vec![1, 1],
                 vec![0, 1],
-                vec![
-                    bit(EvmTxField::Input) as f64,
-                    bit(EvmTxField::TransactionIndex) as f64,
-                ],
+                js_masks(&[bit(EvmTxField::Input), bit(EvmTxField::TransactionIndex)]),
             )
             .await
             .expect("materialize");
@@ -1064,9 +1222,9 @@ mod tests {
         let store = TransactionStore::new_evm(false);
         store.insert_evm_txs(vec![raw_tx(1, 3)]);
 
-        let mask = bit(EvmTxField::TransactionIndex) as f64;
+        let mask = bit(EvmTxField::TransactionIndex);
         let cols = store
-            .materialize(vec![9, 1], vec![7, 3], vec![mask, mask])
+            .materialize(vec![9, 1], vec![7, 3], js_masks(&[mask, mask]))
             .await
             .expect("materialize");
         match column(&cols, "transactionIndex") {
@@ -1088,9 +1246,9 @@ mod tests {
         store.insert_svm_txs(vec![tx]);
 
         // Select only accountKeys.
-        let mask = (1u64 << (SvmTxField::AccountKeys as u32)) as f64;
+        let mask = 1u64 << (SvmTxField::AccountKeys as u32);
         let cols = store
-            .materialize(vec![5], vec![0], vec![mask])
+            .materialize(vec![5], vec![0], js_masks(&[mask]))
             .await
             .expect("materialize");
 
@@ -1136,7 +1294,7 @@ mod tests {
 
         let mask = svm_mask(SvmTxField::AccountActivities);
         let cols = store
-            .materialize(vec![5, 5, 5], vec![0, 1, 2], vec![mask, mask, mask])
+            .materialize(vec![5, 5, 5], vec![0, 1, 2], js_masks(&[mask, mask, mask]))
             .await
             .expect("materialize");
 
@@ -1214,7 +1372,7 @@ mod tests {
             .materialize(
                 vec![5],
                 vec![0],
-                vec![svm_mask(SvmTxField::AccountActivities)],
+                js_masks(&[svm_mask(SvmTxField::AccountActivities)]),
             )
             .await
             .expect("materialize");
@@ -1269,7 +1427,7 @@ mod tests {
             .materialize(
                 vec![5],
                 vec![0],
-                vec![svm_mask(SvmTxField::AccountActivities)],
+                js_masks(&[svm_mask(SvmTxField::AccountActivities)]),
             )
             .await
             .expect("materialize");
@@ -1289,15 +1447,23 @@ mod tests {
             .collect();
         store.insert_evm_txs(txs);
 
-        let mask = bit(EvmTxField::Nonce) as f64;
+        let mask = bit(EvmTxField::Nonce);
         store.prune(10);
         let after_prune = store
-            .materialize(vec![10, 20, 30], vec![0, 0, 0], vec![mask, mask, mask])
+            .materialize(
+                vec![10, 20, 30],
+                vec![0, 0, 0],
+                js_masks(&[mask, mask, mask]),
+            )
             .await
             .expect("materialize");
         store.rollback(20);
         let after_rollback = store
-            .materialize(vec![10, 20, 30], vec![0, 0, 0], vec![mask, mask, mask])
+            .materialize(
+                vec![10, 20, 30],
+                vec![0, 0, 0],
+                js_masks(&[mask, mask, mask]),
+            )
             .await
             .expect("materialize");
 
@@ -1324,7 +1490,7 @@ mod tests {
             vec![0xaa].into_boxed_slice(),
         ));
         page1.insert_evm_txs(vec![first]);
-        persistent.merge(&page1);
+        persistent.merge(&page1).unwrap();
 
         let page2 = TransactionStore::new_evm(false);
         let mut second = raw_tx(1, 0);
@@ -1332,11 +1498,11 @@ mod tests {
             vec![0xbb].into_boxed_slice(),
         ));
         page2.insert_evm_txs(vec![second]);
-        persistent.merge(&page2);
+        persistent.merge(&page2).unwrap();
 
-        let mask = bit(EvmTxField::Input) as f64;
+        let mask = bit(EvmTxField::Input);
         let cols = persistent
-            .materialize(vec![1], vec![0], vec![mask])
+            .materialize(vec![1], vec![0], js_masks(&[mask]))
             .await
             .expect("materialize");
         match column(&cols, "input") {
@@ -1392,6 +1558,15 @@ mod tests {
                 "gasUsedForL1",
                 "accessList",
                 "authorizationList",
+                "sourceHash",
+                "mint",
+                "isSystemTx",
+                "l1BaseFeeScalar",
+                "l1BlobBaseFee",
+                "l1BlobBaseFeeScalar",
+                "operatorFeeScalar",
+                "l1BatchNumber",
+                "l1BatchTxIndex",
             ]
         );
 
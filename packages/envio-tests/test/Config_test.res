@@ -70,6 +70,15 @@ let transactionFieldToInt = (field: Internal.evmTransactionField) =>
   | L1FeeScalar => 30
   | GasUsedForL1 => 31
   | AuthorizationList => 32
+  | SourceHash => 33
+  | Mint => 34
+  | IsSystemTx => 35
+  | L1BaseFeeScalar => 36
+  | L1BlobBaseFee => 37
+  | L1BlobBaseFeeScalar => 38
+  | OperatorFeeScalar => 39
+  | L1BatchNumber => 40
+  | L1BatchTxIndex => 41
   }
 
 // Compile-time exhaustiveness check: evmBlockConstructor must have a field for every evmBlockField variant.
@@ -146,6 +155,15 @@ let _assertTransactionConstructorCoversAllFields = (
   | L1FeeScalar => tx.l1FeeScalar->ignore
   | GasUsedForL1 => tx.gasUsedForL1->ignore
   | AuthorizationList => tx.authorizationList->ignore
+  | SourceHash => tx.sourceHash->ignore
+  | Mint => tx.mint->ignore
+  | IsSystemTx => tx.isSystemTx->ignore
+  | L1BaseFeeScalar => tx.l1BaseFeeScalar->ignore
+  | L1BlobBaseFee => tx.l1BlobBaseFee->ignore
+  | L1BlobBaseFeeScalar => tx.l1BlobBaseFeeScalar->ignore
+  | OperatorFeeScalar => tx.operatorFeeScalar->ignore
+  | L1BatchNumber => tx.l1BatchNumber->ignore
+  | L1BatchTxIndex => tx.l1BatchTxIndex->ignore
   }
 
 describe("Field selection enum schemas", () => {
@@ -171,8 +189,8 @@ describe("Field selection enum schemas", () => {
         sum := sum.contents + transactionFieldToInt(parsed)
       },
     )
-    // n*(n+1)/2 where n = 32
-    t.expect(sum.contents).toBe(32 * 33 / 2)
+    // n*(n+1)/2 where n = 41
+    t.expect(sum.contents).toBe(41 * 42 / 2)
   })
 })
 
@@ -66,7 +66,7 @@ let fromBlock = 23_500_000
 let toBlock = 23_500_004
 
 let runQuery = async (~client: HyperSyncClient.t, ~registrationIndexes=[42]) => {
-  let (res, _txStore, _blockStore) = await client.getEventItems(
+  let (res, itemStore, _txStore, _blockStore, _registrationPage) = await client.getEventItems(
     ~query={
       fromBlock,
       toBlock: Some(toBlock),
@@ -76,27 +76,24 @@ let runQuery = async (~client: HyperSyncClient.t, ~registrationIndexes=[42]) =>
     },
     ~addressSet=usdcSet,
   )
-  res
+  (res, itemStore->EventItemStore.popBatch)
 }
 
 describe("HyperSync client getEventItems (live)", () => {
   Async.itWithOptions("returns decoded event items for a real block range", {retry: 3}, async t => {
     let client = makeClient(~eventRegistrations=[transferEventRegistration])
-    let res = await runQuery(~client)
+    let (res, batch) = await runQuery(~client)
 
     let summary = {
-      "hasItems": res.items->Array.length > 0,
-      "everyItemRouted": res.items->Array.every(item => item.onEventRegistrationIndex == 42),
-      "everySrcAddressIsUsdc": res.items->Array.every(item =>
-        item.srcAddress->Address.toString->String.toLowerCase ==
+      "hasItems": batch.blockNumbers->Array.length > 0,
+      "everyItemRouted": batch.registrationIndexes->Array.every(index => index == 42),
+      "everySrcAddressIsUsdc": batch.srcAddresses->Array.every(srcAddress =>
+        srcAddress->Address.toString->String.toLowerCase ==
           usdcAddress->Address.toString->String.toLowerCase
       ),
-      "everyBlockInRange": res.items->Array.every(item => {
-        let n = item.blockNumber
-        n >= fromBlock && n <= toBlock
-      }),
-      "everyParamsDecoded": res.items->Array.every(item => {
-        let obj = item.params->(Utils.magic: Internal.eventParams => {..})
+      "everyBlockInRange": batch.blockNumbers->Array.every(n => n >= fromBlock && n <= toBlock),
+      "everyParamsDecoded": batch.params->Array.every(params => {
+        let obj = params->(Utils.magic: Internal.eventParams => {..})
         obj["from"]->typeof == #string &&
         obj["to"]->typeof == #string &&
         obj["value"]->typeof == #bigint
@@ -151,9 +148,9 @@ describe("HyperSync client getEventItems (live)", () => {
       transactionFields: [],
     }
     let client = makeClient(~eventRegistrations=[unrelatedEventRegistration])
-    let res = await runQuery(~client, ~registrationIndexes=[0])
+    let (_res, batch) = await runQuery(~client, ~registrationIndexes=[0])
 
-    t.expect(res.items->Array.length).toEqual(0)
+    t.expect(batch.blockNumbers->Array.length).toEqual(0)
   })
 })
 
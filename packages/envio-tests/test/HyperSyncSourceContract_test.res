@@ -243,12 +243,26 @@ describe("HyperSync source contract", () => {
       let (source, addressSet) = makeSource(~url=server->MockHyperSyncServer.url)
       server->MockHyperSyncServer.pushResponse(page)
       let page = await source->fetch(~addressSet)
+      // The page's items stay in its item store until a batch pops them.
+      t.expect(page.parsedQueueItems).toEqual([])
+      let items =
+        page.itemStore
+        ->Option.getOrThrow
+        ->EventItemStore.popBatch
+        ->EventItemStore.toItems(
+          ~chainId=1->ChainId.fromInt,
+          ~onEventRegistrations=onEventRegistrations()->(
+            Utils.magic: array<Internal.evmOnEventRegistration> => array<
+              Internal.onEventRegistration,
+            >
+          ),
+        )
       await ChainState.materializePageItems(
-        ~items=page.parsedQueueItems,
+        ~items,
         ~transactionStore=page.transactionStore,
         ~blockStore=page.blockStore,
       )
-      page.parsedQueueItems
+      items
     })
 
     // The Approval item shows the selection is per (block, transaction) group,
@@ -313,6 +327,95 @@ describe("HyperSync source contract", () => {
     ])
   })
 
+  Async.it("batches the page's items from the chain's store, merged with the buffer", async t => {
+    let pageItems = await MockHyperSyncServer.withServer(~height=100, async server => {
+      let (source, addressSet) = makeSource(~url=server->MockHyperSyncServer.url)
+      server->MockHyperSyncServer.pushResponse(page)
+      let page = await source->fetch(~addressSet)
+      page.itemStore->Option.getOrThrow
+    })
+    let chainId = 1->ChainId.fromInt
+    let onBlockRegistration: Internal.onBlockRegistration = {
+      index: 0,
+      name: "store-merge",
+      chainId,
+      startBlock: None,
+      endBlock: None,
+      interval: 1,
+      handler: "mock onBlock handler"->(
+        Utils.magic: string => Internal.onBlockArgs => promise<unit>
+      ),
+    }
+    let fetchState = FetchState.make(
+      ~onEventRegistrations=[],
+      ~addressStore=TestAddresses.makeStore(),
+      ~addresses=[],
+      ~onBlockRegistrations=[onBlockRegistration],
+      ~startBlock=0,
+      ~endBlock=None,
+      ~maxAddrInPartition=3,
+      ~maxOnBlockBufferSize=10000,
+      ~chainId,
+      ~knownHeight=0,
+    )
+    let eventItemStore = EventItemStore.make()
+    eventItemStore->EventItemStore.merge(pageItems)
+    let chainState = ChainState.make(
+      ~chainConfig={
+        ...TestConfig.default.chainMap->ChainMap.values->Utils.Array.firstUnsafe,
+        id: chainId,
+      },
+      // Fetched up to block 11, with the handlers of blocks 10 and 11 buffered.
+      ~fetchState={
+        ...fetchState,
+        knownHeight: 100,
+        latestOnBlockBlockNumber: 11,
+        buffer: [10, 11]->Array.map(blockNumber =>
+          Internal.Block({onBlockRegistration, blockNumber})
+        ),
+      },
+      ~onEventRegistrations=onEventRegistrations()->(
+        Utils.magic: array<Internal.evmOnEventRegistration> => array<Internal.onEventRegistration>
+      ),
+      ~addressStore=TestAddresses.makeStore(),
+      ~sourceManager=SourceManager.make(
+        ~sources=[(MockSource.make([], ~chainId=1)).source],
+        ~isRealtime=false,
+      ),
+      ~eventItemStore,
+      ~maxReorgDepth=200,
+      ~shouldRollbackOnReorg=false,
+      ~committedProgressBlockNumber=9,
+      ~logger=Logging.getLogger(),
+    )
+    let chainsBeforeBatch = Dict.make()
+    chainsBeforeBatch->ChainId.Dict.set(chainId, chainState->ChainState.toChainBeforeBatch)
+
+    let batch = Batch.make(
+      ~checkpointIdBeforeBatch=0n,
+      ~chainsBeforeBatch,
+      ~batchSizeTarget=100,
+      ~isInReorgThreshold=false,
+    )
+
+    // Each block's events come out of the store ahead of its handler.
+    t.expect(
+      batch.items->Array.map(item =>
+        switch item {
+        | Internal.Event({blockNumber, logIndex, onEventRegistration: {index}}) =>
+          `event ${blockNumber->Int.toString}:${logIndex->Int.toString}:${index->Int.toString}`
+        | Internal.Block({blockNumber}) => `block ${blockNumber->Int.toString}`
+        }
+      ),
+    ).toEqual(["event 10:7:0", "event 10:7:1", "block 10", "event 11:0:2", "block 11"])
+    t.expect(eventItemStore->EventItemStore.len).toBe(0)
+    t.expect(
+      batch.progressedChainsById
+      ->ChainId.Dict.dangerouslyGetNonOption(chainId)
+      ->Option.map(chain => chain.progressBlockNumber),
+    ).toEqual(Some(11))
+  })
+
   Async.it("reads the height off the server", async t => {
     let height = await MockHyperSyncServer.withServer(~height=42, async server => {
       let (source, _) = makeSource(~url=server->MockHyperSyncServer.url)
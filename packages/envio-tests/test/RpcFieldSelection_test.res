@@ -1,13 +1,12 @@
 open Vitest
 
 // `field_selection` on an RPC-synced chain is validated at config parse, while
-// the fields an RPC source can actually deliver are decided by the parsers in
-// `RpcSource`. This pins the two together: every field the source can parse
-// must be accepted by the config, and every field it can't must be rejected.
+// the fields an RPC source can actually deliver are listed in `RpcSource`. This
+// pins the two together: every field the source delivers must be accepted by
+// the config, and every field it doesn't must be rejected.
 
 // `number`/`timestamp`/`hash` are always on the block, so they aren't
-// selectable through `block_fields` — the config enum has no variant for them,
-// and the RPC block registry has no parser for them either.
+// selectable through `block_fields` — the config enum has no variant for them.
 let selectableBlockFields =
   Evm.blockFields->Array.filter(name =>
     switch name {
@@ -16,11 +15,6 @@ let selectableBlockFields =
     }
   )
 
-let hasRpcBlockParser = (name: string) =>
-  RpcSource.blockFieldRegistryChecksum
-  ->Utils.Record.get(name->(Utils.magic: string => Internal.evmBlockField))
-  ->Option.isSome
-
 let selectableTransactionFields =
   Internal.allEvmTransactionFields->(
     Utils.magic: array<Internal.evmTransactionField> => array<string>
@@ -48,18 +42,15 @@ let parseError = yaml =>
   }
 
 describe("RPC field_selection validation", () => {
-  it("accepts every field the RPC source can parse", t => {
+  it("accepts every field the RPC source delivers", t => {
     let yaml = rpcChainConfig(
       ~blockFields=selectableBlockFields,
       ~transactionFields=selectableTransactionFields->Array.filter(RpcSource.isRpcTransactionField),
     )
-    t.expect((
-      selectableBlockFields->Array.filter(f => !hasRpcBlockParser(f)),
-      parseError(yaml),
-    )).toEqual(([], None))
+    t.expect(parseError(yaml)).toBe(None)
   })
 
-  it("rejects every field the RPC source can't parse", t => {
+  it("rejects every field the RPC source doesn't deliver", t => {
     let unsupported =
       selectableTransactionFields->Array.filter(f => !RpcSource.isRpcTransactionField(f))
     let errors =
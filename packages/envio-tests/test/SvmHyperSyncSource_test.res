@@ -117,6 +117,8 @@ let makeMockClient = (~response=mockResponse): SvmHyperSyncClient.t => {
       BlockStore.make(~ecosystem=Ecosystem.Svm, ~shouldChecksum=false),
     ))
   },
+  decodeAccount: (~registrationIndex as _, ~dataBase64 as _) =>
+    JsError.throwWithMessage("decodeAccount should only be used for program accounts in tests"),
 }
 
 let mockClient = makeMockClient()
@@ -277,6 +279,67 @@ describe("SvmHyperSyncSource.getItemsOrThrow (mocked client)", () => {
     ])
   })
 
+  // Rust reports a failed decode on the item; the program's mode decides
+  // whether it's delivered (and counted) or stops the indexer.
+  let undecodableResponse = {
+    ...mockResponse,
+    items: mockResponse.items->Array.map(item => {
+      ...item,
+      decoded: ?None,
+      decodeError: {
+        reason: "buffer underflow reading u64: need 8, have 3",
+        byteOffset: 1,
+        field: "amount",
+      },
+    }),
+  }
+  let getUndecodableItems = (~decodeErrors) => {
+    let reg = makeReg(~eventConfig={...makeEventConfig(), decodeErrors})
+    let source = makeSource(
+      ~onEventRegistrations=[reg],
+      ~client=makeMockClient(~response=undecodableResponse),
+    )
+    source.getItemsOrThrow(
+      ~fromBlock=slot - 10,
+      ~toBlock=Some(slot + 10),
+      ~addressSet=programSet,
+      ~knownHeight=slot + 1000,
+      ~partitionId="0",
+      ~itemsTarget=None,
+      ~selection={
+        onEventRegistrations: [reg],
+        dependsOnAddresses: true,
+      },
+      ~retry=0,
+      ~logger=Logging.createChild(~params={"test": "SvmHyperSyncSource"}),
+    )
+  }
+
+  Async.it("delivers and counts items that fail to decode in warn mode", async t => {
+    let response = await getUndecodableItems(~decodeErrors=Internal.Warn)
+    t.expect({
+      "items": response.parsedQueueItems->Array.length,
+      "decodeErrors": response.decodeErrors,
+    }).toEqual({
+      "items": 1,
+      "decodeErrors": Some([
+        {Source.contract: "TokenMetadata", event: "CreateMetadataAccountV3"},
+      ]),
+    })
+  })
+
+  Async.it("fails the fetch on an item that fails to decode in fail mode", async t => {
+    let message = try {
+      let _ = await getUndecodableItems(~decodeErrors=Internal.Fail)
+      "fetched"
+    } catch {
+    | exn => exn->JsExn.fromException->Option.flatMap(JsExn.message)->Option.getOr("unknown error")
+    }
+    t.expect(message).toBe(
+      `Failed to decode the instruction TokenMetadata.CreateMetadataAccountV3 against its schema (slot ${slot->Int.toString}, path [1]) in "amount" at byte 1: buffer underflow reading u64: need 8, have 3. The program's IDL likely doesn't match what ran on-chain. Update the IDL, or set the program's decode_errors to warn or ignore to deliver such items without decoded args.`,
+    )
+  })
+
   it("stringifies schema pieces and field selections onto registration inputs", t => {
     let eventConfig = makeEventConfig(
       ~selectedBlockFields=[Height, ParentHash],
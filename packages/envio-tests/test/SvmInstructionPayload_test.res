@@ -21,6 +21,10 @@ chains:
               accounts:
                 - source
                 - destination
+          events:
+            - name: Swapped
+              fields:
+                - { name: amountOut, type: u64 }
 `
 
 let parsed = InternalTestIndexer.fromUserApi(
@@ -58,6 +62,11 @@ indexer.onInstruction(
   { program: "Swapper", instruction: "swap", fields: { log: ["kind"] } },
   async () => {},
 );
+
+indexer.onEvent(
+  { program: "Swapper", event: "Swapped", fields: { instruction: ["path"] } },
+  async () => {},
+);
 `,
 )
 
@@ -249,3 +258,62 @@ describe("SVM instruction payload assembly", () => {
   })
 })
 
+describe("SVM Anchor event payload assembly", () => {
+  let item: SvmHyperSyncClient.EventItems.item = {
+    onEventRegistrationIndex: 2,
+    slot: 10,
+    transactionIndex: 1,
+    path: [0],
+    programId: "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+    accounts: [],
+    data: "0x",
+    isInner: false,
+  }
+
+  it("registers the event apart from the program's instructions", t => {
+    let reg = registrations()->Array.getUnsafe(2)
+    let eventConfig =
+      reg.eventConfig->(Utils.magic: Internal.eventConfig => Internal.svmInstructionEventConfig)
+    t.expect({
+      "isEvent": eventConfig.isEvent,
+      "idIsPrefixed": eventConfig.id->String.startsWith("event:"),
+      "discriminatorByteLen": eventConfig.discriminatorByteLen,
+    }).toEqual({
+      "isEvent": Some(true),
+      "idIsPrefixed": true,
+      "discriminatorByteLen": 8,
+    })
+  })
+
+  it("puts decoded fields under params and keeps undecodable events with empty params", t => {
+    let reg = registrations()->Array.getUnsafe(2)
+    let eventConfig =
+      reg.eventConfig->(Utils.magic: Internal.eventConfig => Internal.svmInstructionEventConfig)
+    let decoded = SvmHyperSyncSource.toSvmEvent(
+      {
+        ...item,
+        decoded: {name: "Swapped", argsJson: `{"amountOut":"7"}`, accountsJson: "{}", extraAccounts: []},
+      },
+      ~eventConfig,
+      ~fieldSelection=reg.fieldSelection,
+    )
+    let failed = SvmHyperSyncSource.toSvmEvent(
+      item,
+      ~eventConfig,
+      ~fieldSelection=reg.fieldSelection,
+    )
+    t.expect({
+      "eventName": decoded.eventName,
+      "params": decoded.params,
+      "failedParams": failed.params,
+      "path": decoded.path,
+      "hasProgramId": %raw(`Object.prototype.hasOwnProperty.call(decoded, "programId")`),
+    }).toEqual({
+      "eventName": "Swapped",
+      "params": %raw(`{"amountOut":"7"}`),
+      "failedParams": %raw(`{}`),
+      "path": Some([0]),
+      "hasProgramId": false,
+    })
+  })
+})
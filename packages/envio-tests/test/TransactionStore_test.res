@@ -1,37 +1,27 @@
 open Vitest
 
-// `FieldMask.orMask` is `(a | b) >>> 0`, so a field whose code is 32 or higher
-// is silently dropped the moment two selections merge or two items group — the
-// mask itself stays exact (it sums `2**code` in f64), which is what makes the
-// loss quiet. EVM transactions already sit exactly on the ceiling, so this is
-// the test that has to fail before a 33rd field lands.
+// Masks are BigInts, so JS holds any field code; the ceiling is the Rust
+// store's, whose tables hold at most `field_table::MAX_FIELDS` (128) fields and
+// reject a mask selecting more.
 describe("field-code mask ceiling", () => {
-  it("no ecosystem exceeds the 32 field codes a mask can hold", t => {
-    t.expect({
-      // Pinned because it sits exactly on the ceiling — the next EVM
-      // transaction field is the one that breaks `orMask`.
-      "evmTransaction": Evm.transactionFields->Array.length,
-      "overCeiling": [
+  it("no ecosystem exceeds the 128 field codes a store table can hold", t => {
+    t.expect(
+      [
         Evm.transactionFields,
         Evm.blockFields,
         Svm.transactionFields,
         Svm.blockFields,
         Fuel.blockFields,
-      ]->Array.filter(fields => fields->Array.length > 32),
-    }).toEqual({
-      "evmTransaction": 32,
-      // Widen `orMask` (BigInt, or a pair of floats) before adding the 33rd.
-      "overCeiling": [],
-    })
+      ]->Array.filter(fields => fields->Array.length > 128),
+    ).toEqual([])
   })
 
-  it("orMask drops a field code at the ceiling", t =>
-    // Pins the failure mode the count guards against, so the reason the ceiling
-    // is 32 stays legible.
+  it("orMask keeps field codes past 32", t =>
     t.expect((
-      FieldMask.orMask(FieldMask.pow2(31), 1.),
-      FieldMask.orMask(FieldMask.pow2(32), 1.),
-    )).toEqual((2147483649., 1.))
+      FieldMask.orMask(FieldMask.fieldBit(31), 1n),
+      FieldMask.orMask(FieldMask.fieldBit(32), 1n),
+      FieldMask.orMask(FieldMask.fieldBit(100), FieldMask.fieldBit(64)),
+    )).toEqual((2147483649n, 4294967297n, 1267650600246676145570412756992n))
   )
 })
 
@@ -62,20 +52,15 @@ describe("TransactionStore field-code contract", () => {
     )
   })
 
-  it("orMask combines field masks as unsigned 32-bit values", t => {
-    // The highest EVM field code is 31, so the highest mask bit is 2^31. A plain
-    // JS `|` renders that bit negative; orMask's `>>> 0` recovers the unsigned
-    // value. These pin both the disjoint/overlapping cases and the bit-31 edge.
+  it("orMask combines field masks", t => {
     t.expect({
-      "disjoint": TransactionStore.orMask(1., 2.),
-      "overlapping": TransactionStore.orMask(3., 6.),
-      "bit31WithLowBit": TransactionStore.orMask(2147483648., 1.),
-      "allBits": TransactionStore.orMask(4294967295., 2147483648.),
+      "disjoint": TransactionStore.orMask(1n, 2n),
+      "overlapping": TransactionStore.orMask(3n, 6n),
+      "bit31WithLowBit": TransactionStore.orMask(2147483648n, 1n),
     }).toEqual({
-      "disjoint": 3.,
-      "overlapping": 7.,
-      "bit31WithLowBit": 2147483649.,
-      "allBits": 4294967295.,
+      "disjoint": 3n,
+      "overlapping": 7n,
+      "bit31WithLowBit": 2147483649n,
     })
   })
 })
@@ -109,6 +109,8 @@ function makeFakeSet(unordered) {
       return makeFakeSet(merged);
     },
     startBlockGroups: function () { return [{startBlock: 0, count: entries.length}] },
+    // Fixture addresses never end.
+    firstEndBlock: function () { return null },
   };
   var descriptors = {__entries: {value: entries}};
   Object.keys(methods).forEach(function (key) { descriptors[key] = {value: methods[key]} });
@@ -152,7 +152,7 @@ describe("ChainIdMode Postgres schema", () => {
       maxInt32Config->rawEventsDdl,
     )).toEqual((
       `CREATE TABLE IF NOT EXISTS "test_schema"."envio_chains"("id" INTEGER NOT NULL, "ecosystem" TEXT NOT NULL, "start_block" INTEGER NOT NULL, "end_block" INTEGER, "max_reorg_depth" INTEGER NOT NULL, "buffer_block" INTEGER NOT NULL, "source_block" INTEGER NOT NULL, "first_event_block" INTEGER, "ready_at" TIMESTAMP WITH TIME ZONE NULL, "events_processed" BIGINT NOT NULL, "_is_hyper_sync" BOOLEAN NOT NULL, "progress_block" INTEGER NOT NULL, PRIMARY KEY("id"));`,
-      `CREATE TABLE IF NOT EXISTS "test_schema"."envio_addresses"("id" TEXT NOT NULL, "chain_id" INTEGER NOT NULL, "registration_block" INTEGER NOT NULL, "registration_log_index" INTEGER NOT NULL, "contract_name" TEXT NOT NULL, PRIMARY KEY("id"));`,
+      `CREATE TABLE IF NOT EXISTS "test_schema"."envio_addresses"("id" TEXT NOT NULL, "chain_id" INTEGER NOT NULL, "registration_block" INTEGER NOT NULL, "registration_log_index" INTEGER NOT NULL, "contract_name" TEXT NOT NULL, "end_block" INTEGER, PRIMARY KEY("id"));`,
       `CREATE TABLE IF NOT EXISTS "test_schema"."raw_events"("chain_id" INTEGER NOT NULL, "event_id" BIGINT NOT NULL, "event_name" TEXT NOT NULL, "contract_name" TEXT NOT NULL, "block_number" INTEGER NOT NULL, "log_index" INTEGER NOT NULL, "src_address" TEXT NOT NULL, "block_hash" TEXT NOT NULL, "block_timestamp" INTEGER NOT NULL, "block_fields" JSONB NOT NULL, "transaction_fields" JSONB NOT NULL, "params" JSONB NOT NULL, "serial" BIGSERIAL, PRIMARY KEY("serial"));`,
     ))
   })
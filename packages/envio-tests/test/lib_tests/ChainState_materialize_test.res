@@ -9,8 +9,8 @@ let materializeChainId = 987->ChainId.fromInt
 let makeItem = (
   ~blockNumber,
   ~transactionIndex=0,
-  ~transactionMask=0.,
-  ~blockMask=0.,
+  ~transactionMask=0n,
+  ~blockMask=0n,
   ~inlineTransaction: option<Internal.eventTransaction>=?,
   ~inlineBlock: option<Internal.eventBlock>=?,
 ): Internal.item => {
@@ -63,10 +63,10 @@ describe("ChainState.materializePageItems: single-pass transaction/block groupin
       // c: same block as a/b but a different tx -> starts a new transaction
       //    group while continuing a/b's block group.
       // d: a different block (and tx) -> its own group on both dimensions.
-      let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2., ~blockMask=2.)
-      let b = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2., ~blockMask=2.)
-      let c = makeItem(~blockNumber=1, ~transactionIndex=2, ~transactionMask=2., ~blockMask=2.)
-      let d = makeItem(~blockNumber=2, ~transactionIndex=1, ~transactionMask=2., ~blockMask=2.)
+      let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2n, ~blockMask=2n)
+      let b = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2n, ~blockMask=2n)
+      let c = makeItem(~blockNumber=1, ~transactionIndex=2, ~transactionMask=2n, ~blockMask=2n)
+      let d = makeItem(~blockNumber=2, ~transactionIndex=1, ~transactionMask=2n, ~blockMask=2n)
 
       await ChainState.materializePageItems(
         ~items=[a, b, c, d],
@@ -93,7 +93,7 @@ describe("ChainState.materializePageItems: single-pass transaction/block groupin
   )
 
   Async.it("materializePageItems skips the transaction side for inline sources", async t => {
-    let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2., ~blockMask=2.)
+    let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2n, ~blockMask=2n)
     await ChainState.materializePageItems(
       ~items=[a],
       ~transactionStore=None,
@@ -192,7 +192,7 @@ describe("ChainState.materializePageItems: block materialization", () => {
 describe("ChainState.materializePageItems: transaction materialization", () => {
   Async.it("stamps an empty transaction object when the mask is 0", async t => {
     let transactionStore = TransactionStore.make(~ecosystem=Ecosystem.Evm, ~shouldChecksum=false)
-    let item = makeItem(~blockNumber=1, ~transactionIndex=0, ~transactionMask=0.)
+    let item = makeItem(~blockNumber=1, ~transactionIndex=0, ~transactionMask=0n)
     await ChainState.materializePageItems(
       ~items=[item],
       ~transactionStore=Some(transactionStore),
@@ -211,9 +211,9 @@ describe("ChainState.materializePageItems: transaction materialization", () => {
       let transactionStore = TransactionStore.make(~ecosystem=Ecosystem.Evm, ~shouldChecksum=false)
       let inlineTx = {"hash": "0xinline"}->(Utils.magic: {..} => Internal.eventTransaction)
       let inline = makeItem(~blockNumber=1, ~transactionIndex=0, ~inlineTransaction=inlineTx)
-      let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2.)
-      let b = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2.)
-      let c = makeItem(~blockNumber=1, ~transactionIndex=2, ~transactionMask=2.)
+      let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2n)
+      let b = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2n)
+      let c = makeItem(~blockNumber=1, ~transactionIndex=2, ~transactionMask=2n)
 
       await ChainState.materializePageItems(
         ~items=[inline, a, b, c],
@@ -241,9 +241,9 @@ describe("ChainState.materializePageItems: transaction materialization", () => {
     // tx stays separate. Exercises the orMask union path (the empty store yields
     // one distinct empty object per row).
     let transactionStore = TransactionStore.make(~ecosystem=Ecosystem.Evm, ~shouldChecksum=false)
-    let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2.)
-    let b = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=4.)
-    let c = makeItem(~blockNumber=1, ~transactionIndex=2, ~transactionMask=0.)
+    let a = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=2n)
+    let b = makeItem(~blockNumber=1, ~transactionIndex=1, ~transactionMask=4n)
+    let c = makeItem(~blockNumber=1, ~transactionIndex=2, ~transactionMask=0n)
 
     await ChainState.materializePageItems(
       ~items=[a, b, c],
@@ -222,3 +222,37 @@ describe("ChainState chain density EMA (per batch)", () => {
     t.expect(cs->ChainState.chainDensity, ~message="full-window batch replaces").toEqual(Some(25.))
   })
 })
+
+describe("ChainState safe block number", () => {
+  let makeWithFinality = finality =>
+    ChainState.makeFromDbState(
+      {...baseChainConfig, finality},
+      ~resumedChainState=makeResumedChainState(
+        ~progressBlockNumber=500,
+        ~numEventsProcessed=0.,
+        ~firstEventBlockNumber=None,
+      ),
+      ~reorgCheckpoints=[],
+      ~isInReorgThreshold=false,
+      ~isRealtime=false,
+      ~config=TestConfig.default,
+      ~registrationsByChainId,
+    )
+
+  it("follows the rollback guard's finalized height once reported", t => {
+    let cs = makeWithFinality(Config.RollbackGuard)
+    t.expect(cs->ChainState.safeBlockNumber, ~message="maxReorgDepth until reported").toBe(800)
+
+    cs->ChainState.recordResponseFinalizedBlock(~blockNumber=950)
+    t.expect(cs->ChainState.safeBlockNumber).toBe(950)
+
+    cs->ChainState.recordResponseFinalizedBlock(~blockNumber=900)
+    t.expect(cs->ChainState.safeBlockNumber, ~message="never moves back").toBe(950)
+  })
+
+  it("ignores a response's finalized height for depth finality", t => {
+    let cs = makeWithFinality(Config.Depth)
+    cs->ChainState.recordResponseFinalizedBlock(~blockNumber=950)
+    t.expect(cs->ChainState.safeBlockNumber).toBe(800)
+  })
+})
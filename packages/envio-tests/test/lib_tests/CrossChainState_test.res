@@ -61,6 +61,7 @@ let makeChainState = (
     latestOnBlockBlockNumber: frontier,
     firstEventBlock: Some(firstEventBlock),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
     buffer: bufferBlocks->Array.map(blockNumber => mockEvent(~blockNumber)),
   }
   let mockSource = MockSource.make([], ~chainId=1)
@@ -120,6 +121,7 @@ let makeFetchingChainState = (
       ~nextPartitionIndex=1,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock,
@@ -133,6 +135,7 @@ let makeFetchingChainState = (
     knownHeight,
     firstEventBlock,
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
   let mockSource = MockSource.make([], ~chainId=1)
   ChainState.make(
@@ -449,6 +452,7 @@ describe("CrossChainState fetch control", () => {
           ~nextPartitionIndex=1,
           ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
         ),
         startBlock: 0,
         endBlock: Some(20),
@@ -462,6 +466,7 @@ describe("CrossChainState fetch control", () => {
         knownHeight: 1000,
         firstEventBlock: Some(0),
         clientFilterAddressThreshold: None,
+        bloomFilterMinAddresses: None,
       }
       let mockSource1 = MockSource.make([], ~chainId=1)
       let a = ChainState.make(
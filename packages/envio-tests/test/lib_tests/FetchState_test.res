@@ -181,6 +181,7 @@ let makeFs = (
   ~blockLag=?,
   ~firstEventBlock=?,
   ~clientFilterAddressThreshold=?,
+  ~bloomFilterMinAddresses=?,
   ~configContractNames=?,
 ) => {
   let addressStore = TestAddresses.makeStore(~onEventRegistrations, ~addresses, ~configContractNames?)
@@ -199,6 +200,7 @@ let makeFs = (
     ~blockLag=?blockLag,
     ~firstEventBlock=?firstEventBlock,
     ~clientFilterAddressThreshold=?clientFilterAddressThreshold,
+    ~bloomFilterMinAddresses=?bloomFilterMinAddresses,
   )
   (fetchState, addressStore)
 }
@@ -264,6 +266,7 @@ describe("FetchState.make", () => {
         ~maxAddrInPartition=3,
         ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
       ),
       startBlock: 0,
       endBlock: None,
@@ -277,6 +280,7 @@ describe("FetchState.make", () => {
       knownHeight,
       firstEventBlock: None,
       clientFilterAddressThreshold: None,
+      bloomFilterMinAddresses: None,
     })->TestAddresses.fetchState)
   })
 
@@ -378,6 +382,7 @@ describe("FetchState.make", () => {
         ~maxAddrInPartition=2,
         ~dynamicContracts=Utils.Set.fromArray(["Gravatar"]),
         ~clientFilteredContracts=Utils.Set.make(),
+        ~bloomFilteredContracts=Utils.Set.make(),
       ),
       maxOnBlockBufferSize: targetBufferSize,
       latestOnBlockBlockNumber: -1,
@@ -391,6 +396,7 @@ describe("FetchState.make", () => {
       knownHeight,
       firstEventBlock: None,
       clientFilterAddressThreshold: None,
+      bloomFilterMinAddresses: None,
     })->TestAddresses.fetchState)
   })
 
@@ -446,6 +452,7 @@ describe("FetchState.make", () => {
           ~maxAddrInPartition=1,
           ~dynamicContracts=Utils.Set.fromArray(["Gravatar"]),
           ~clientFilteredContracts=Utils.Set.make(),
+          ~bloomFilteredContracts=Utils.Set.make(),
         ),
         maxOnBlockBufferSize: targetBufferSize,
         latestOnBlockBlockNumber: -1,
@@ -459,6 +466,7 @@ describe("FetchState.make", () => {
         knownHeight,
         firstEventBlock: None,
         clientFilterAddressThreshold: None,
+        bloomFilterMinAddresses: None,
       })->TestAddresses.fetchState)
 
       t.expect(
@@ -553,6 +561,7 @@ describe("FetchState.make", () => {
           ~maxAddrInPartition=1,
           ~dynamicContracts=Utils.Set.fromArray(["Gravatar"]),
           ~clientFilteredContracts=Utils.Set.make(),
+          ~bloomFilteredContracts=Utils.Set.make(),
         ),
         maxOnBlockBufferSize: targetBufferSize,
         latestOnBlockBlockNumber: -1,
@@ -566,6 +575,7 @@ describe("FetchState.make", () => {
         knownHeight,
         firstEventBlock: None,
         clientFilterAddressThreshold: None,
+        bloomFilterMinAddresses: None,
       })->TestAddresses.fetchState)
     },
   )
@@ -1654,6 +1664,7 @@ describe("FetchState.registerDynamicContracts", () => {
           ~maxAddrInPartition=1000,
           ~dynamicContracts=Utils.Set.fromArray(["NftFactory"]),
           ~clientFilteredContracts=Utils.Set.make(),
+          ~bloomFilteredContracts=Utils.Set.make(),
         ),
         startBlock: 0,
         endBlock: None,
@@ -1667,6 +1678,7 @@ describe("FetchState.registerDynamicContracts", () => {
         knownHeight,
         firstEventBlock: None,
         clientFilterAddressThreshold: None,
+        bloomFilterMinAddresses: None,
       })->TestAddresses.fetchState)
     },
   )
@@ -1716,6 +1728,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition=3,
         ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
       ),
       latestOnBlockBlockNumber: knownHeight,
       maxOnBlockBufferSize: targetBufferSize,
@@ -1729,6 +1742,7 @@ describe("FetchState.getNextQuery & integration", () => {
       knownHeight,
       firstEventBlock: None,
       clientFilterAddressThreshold: None,
+      bloomFilterMinAddresses: None,
     }
   }
 
@@ -1776,6 +1790,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition,
         ~dynamicContracts=Utils.Set.fromArray(["Gravatar"]),
         ~clientFilteredContracts=Utils.Set.make(),
+        ~bloomFilteredContracts=Utils.Set.make(),
       ),
       latestOnBlockBlockNumber: knownHeight,
       maxOnBlockBufferSize: targetBufferSize,
@@ -1789,6 +1804,7 @@ describe("FetchState.getNextQuery & integration", () => {
       knownHeight,
       firstEventBlock: None,
       clientFilterAddressThreshold: None,
+      bloomFilterMinAddresses: None,
     }
   }
 
@@ -2281,6 +2297,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition=fetchStateWithResponse1.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=fetchStateWithResponse1.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=fetchStateWithResponse1.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=fetchStateWithResponse1.optimizedPartitions.bloomFilteredContracts,
       ),
     })->TestAddresses.fetchState)
   })
@@ -2509,6 +2526,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition=fetchState.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=fetchState.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=fetchState.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=fetchState.optimizedPartitions.bloomFilteredContracts,
       ),
     })->TestAddresses.fetchState)
 
@@ -2541,6 +2559,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition=fetchState.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=fetchState.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=fetchState.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=fetchState.optimizedPartitions.bloomFilteredContracts,
       ),
       // Removed an item here
 
@@ -2573,6 +2592,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition=fetchState.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=fetchState.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=fetchState.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=fetchState.optimizedPartitions.bloomFilteredContracts,
       ),
       buffer: [],
     })->TestAddresses.fetchState)
@@ -2661,6 +2681,7 @@ describe("FetchState.getNextQuery & integration", () => {
         ~maxAddrInPartition=fetchState.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=fetchState.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=fetchState.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=fetchState.optimizedPartitions.bloomFilteredContracts,
       ),
       buffer: [],
     })->TestAddresses.fetchState)
@@ -2705,6 +2726,7 @@ describe("FetchState unit tests for specific cases", () => {
         ~maxAddrInPartition=base.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=base.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=base.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=base.optimizedPartitions.bloomFilteredContracts,
       ),
       ~mutItems=[
         mockEvent(~blockNumber=4, ~logIndex=2),
@@ -3009,6 +3031,7 @@ describe("FetchState unit tests for specific cases", () => {
         ~maxAddrInPartition=base.optimizedPartitions.maxAddrInPartition,
         ~dynamicContracts=base.optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=base.optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=base.optimizedPartitions.bloomFilteredContracts,
       ),
       ~mutItems=[
         mockEvent(~blockNumber=6, ~logIndex=1),
@@ -3631,6 +3654,62 @@ describe("Dynamic contracts with start blocks", () => {
   })
 })
 
+describe("FetchState.deregisterDynamicContracts", () => {
+  it("Should retire an ended address's partition and bring it back on rollback", t => {
+    let (fs0, addressStore) = makeInitial(~knownHeight=1000)
+    let partitionsCount = fs0->FetchState.partitionsCount
+
+    let fs1 =
+      fs0->FetchState.deregisterDynamicContracts(
+        ~addressStore,
+        [{address: mockAddress0, contractName: "Gravatar", block: 50}],
+      )
+    t.expect(
+      (
+        addressStore->AddressStore.isIndexedAt(mockAddress0, "Gravatar", 50),
+        addressStore->AddressStore.isIndexedAt(mockAddress0, "Gravatar", 51),
+      ),
+      ~message="The deregistration block is the last one indexed",
+    ).toEqual((true, false))
+
+    let query = {
+      FetchState.partitionId: "0",
+      itemsTarget: Some(5000),
+      itemsEst: 5000,
+      toBlock: None,
+      isChunk: false,
+      selection: fs1.normalSelection,
+      addresses: TestAddresses.setOf([]),
+      fromBlock: 0,
+    }
+    fs1->FetchState.startFetchingQueries(~queries=[query])
+    let fs2 = fs1->FetchState.handleQueryResult(~query, ~latestFetchedBlock=100, ~newItems=[])
+    t.expect(
+      fs2->FetchState.partitionsCount,
+      ~message="Fetched past its only address's end, the partition retires",
+    ).toBe(partitionsCount - 1)
+
+    let fs3 = fs2->FetchState.rollback(~addressStore, ~targetBlockNumber=40)
+    t.expect(
+      (
+        fs3->FetchState.partitionsCount,
+        addressStore->AddressStore.isIndexedAt(mockAddress0, "Gravatar", 51),
+      ),
+      ~message="Rolling back below the deregistration fetches the address again",
+    ).toEqual((partitionsCount, true))
+  })
+
+  it("Should leave the fetch state alone for an address that isn't registered", t => {
+    let (fs0, addressStore) = makeInitial()
+    let fs1 =
+      fs0->FetchState.deregisterDynamicContracts(
+        ~addressStore,
+        [{address: mockAddress1, contractName: "Gravatar", block: 50}],
+      )
+    t.expect(fs1 === fs0, ~message="Nothing ended, so nothing to prune").toBe(true)
+  })
+})
+
 describe("FetchState progress tracking", () => {
   let makeFetchStateWith = (~latestBlock: int, ~queueBlocks: array<(int, int)>): FetchState.t => {
     let (fs0, _) = makeInitial(~knownHeight=1000)
@@ -4072,6 +4151,7 @@ describe("FetchState.getNextQuery water-fill round is order-independent", () =>
         ~nextPartitionIndex=2,
         ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
       ),
       startBlock: 0,
       endBlock: None,
@@ -4085,6 +4165,7 @@ describe("FetchState.getNextQuery water-fill round is order-independent", () =>
       knownHeight: 10000,
       firstEventBlock: Some(0),
       clientFilterAddressThreshold: None,
+      bloomFilterMinAddresses: None,
     }
   }
 
@@ -4149,6 +4230,7 @@ describe("FetchState.getNextQuery greedy budget pass fills partitions toward the
       ~nextPartitionIndex=2,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock: None,
@@ -4162,6 +4244,7 @@ describe("FetchState.getNextQuery greedy budget pass fills partitions toward the
     knownHeight: 100000,
     firstEventBlock: Some(0),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
 
   it("fills each partition toward the shared target, then stops at its range end", t => {
@@ -4235,6 +4318,7 @@ describe("FetchState.getNextQuery with uneven in-flight reservations", () => {
       ~nextPartitionIndex=2,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock: None,
@@ -4248,6 +4332,7 @@ describe("FetchState.getNextQuery with uneven in-flight reservations", () => {
     knownHeight: 10000,
     firstEventBlock: Some(0),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
 
   it("hands a known-density partition only the fresh budget, not the mean footprint", t => {
@@ -4432,6 +4517,7 @@ describe("FetchState.getNextQuery target containment", () => {
       ~nextPartitionIndex=1,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock: None,
@@ -4445,6 +4531,7 @@ describe("FetchState.getNextQuery target containment", () => {
     knownHeight: 10000,
     firstEventBlock: Some(0),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
 
   it("gates chunk starts at the target block even when a far mergeBlock allows more", t => {
@@ -4556,6 +4643,7 @@ describe("FetchState.getNextQuery chunk headroom and budget-driven emit", () =>
       ~nextPartitionIndex=1,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock: None,
@@ -4569,6 +4657,7 @@ describe("FetchState.getNextQuery chunk headroom and budget-driven emit", () =>
     knownHeight: 100000,
     firstEventBlock: Some(0),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
 
   let getChunks = (fetchState: FetchState.t, ~chainTargetItems) =>
@@ -4659,6 +4748,7 @@ describe("Response density and source range capacity update independently", () =
       ~nextPartitionIndex=1,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock: None,
@@ -4672,6 +4762,7 @@ describe("Response density and source range capacity update independently", () =
     knownHeight: 100000,
     firstEventBlock: Some(0),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
 
   let makeQuery = (~isChunk): FetchState.query => {
@@ -4851,6 +4942,7 @@ describe("FetchState.getNextQuery caps per-chain concurrency", () => {
       ~nextPartitionIndex=partitionsCount,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     ),
     startBlock: 0,
     endBlock: None,
@@ -4864,6 +4956,7 @@ describe("FetchState.getNextQuery caps per-chain concurrency", () => {
     knownHeight: 100000,
     firstEventBlock: Some(0),
     clientFilterAddressThreshold: None,
+    bloomFilterMinAddresses: None,
   }
 
   let countQueries = (fetchState: FetchState.t) =>
@@ -4929,6 +5022,7 @@ describe("FetchState.getNextQuery caps per-chain concurrency", () => {
         ~nextPartitionIndex=1,
         ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
       ),
     }
     t.expect((fetchState->FetchState.getNextQuery(~chainTargetBlock=100000, ~chainTargetItems=1000.))->TestAddresses.nextQuery,
@@ -4964,6 +5058,7 @@ describe("FetchState.getNextQuery caps per-chain concurrency", () => {
         ~nextPartitionIndex=2,
         ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
       ),
     }
     let query: FetchState.query = {
@@ -5532,6 +5627,62 @@ describe("FetchState client-side address filtering", () => {
     ).toEqual(([(false, Some(["Gravatar"]), [])], ["0"], 1))
   })
 
+  it("keeps a bloom-filtered config contract server-side in one partition", t => {
+    // Over both maxAddrInPartition and the client-filter threshold, but big
+    // enough to be sent as a bloom filter: neither cap applies.
+    let (fetchState, _addressStore) = makeFs(
+      ~onEventRegistrations=[baseEventConfig],
+      ~addresses=[
+        makeConfigContract("Gravatar", mockAddress0),
+        makeConfigContract("Gravatar", mockAddress1),
+        makeConfigContract("Gravatar", mockAddress2),
+      ],
+      ~startBlock=0,
+      ~endBlock=None,
+      ~maxAddrInPartition=1,
+      ~chainId,
+      ~maxOnBlockBufferSize=targetBufferSize,
+      ~knownHeight=100,
+      ~clientFilterAddressThreshold=Some(2),
+      ~bloomFilterMinAddresses=Some(3),
+    )
+    t.expect(
+      (
+        fetchState->partitionShape,
+        fetchState.optimizedPartitions.clientFilteredContracts->Utils.Set.toArray,
+        fetchState.optimizedPartitions.bloomFilteredContracts->Utils.Set.toArray,
+      ),
+      ~message="one address-bound partition holding all 3 addresses; nothing client-filtered",
+    ).toEqual(([(true, None, ["Gravatar"])], [], ["Gravatar"]))
+  })
+
+  it("exempts a dynamic contract from the client-side switch once it reaches the bloom size", t => {
+    let (fetchState, addressStore) = makeFs(
+      ~onEventRegistrations=[baseEventConfig],
+      ~addresses=[makeConfigContract("Gravatar", mockAddress0)],
+      ~startBlock=0,
+      ~endBlock=None,
+      ~maxAddrInPartition=10,
+      ~chainId,
+      ~maxOnBlockBufferSize=targetBufferSize,
+      ~knownHeight=100,
+      ~clientFilterAddressThreshold=Some(1),
+      ~bloomFilterMinAddresses=Some(3),
+    )
+    let updated =
+      fetchState->FetchState.registerDynamicContracts(~addressStore, [
+        makeDynContractRegistration(~blockNumber=3, ~contractAddress=mockAddress1)->dcToRegistration,
+        makeDynContractRegistration(~blockNumber=4, ~contractAddress=mockAddress2)->dcToRegistration,
+      ])
+    t.expect(
+      (
+        updated.optimizedPartitions.clientFilteredContracts->Utils.Set.toArray,
+        updated.optimizedPartitions.bloomFilteredContracts->Utils.Set.toArray,
+      ),
+      ~message="3 addresses reach the bloom size before the threshold switch is checked",
+    ).toEqual(([], ["Gravatar"]))
+  })
+
   it("keeps a contract client-side filtered across rollback", t => {
     let (fetchState, addressStore) = makeGravatarFs(~clientFilterAddressThreshold=Some(1))
     let collapsed =
@@ -103,6 +103,7 @@ envio_info{version="${Utils.EnvioPackage.value.version}"} 1
       historyPrunes: [],
       sourceRequests: [],
       sourceHeights: [],
+      sourceDecodeErrors: [],
     }
 
     t.expect(
@@ -243,6 +244,15 @@ envio_info{version="${Utils.EnvioPackage.value.version}"} 1
           height: 305,
         },
       ],
+      sourceDecodeErrors: [
+        {
+          source: "SvmHyperSync",
+          chainId: 1->ChainId.fromInt,
+          contract: "Amm",
+          event: "Swap",
+          count: 3,
+        },
+      ],
     }
 
     t.expect(Metrics.collect(~metrics=Some(metrics))).toBe(`# HELP envio_info Information about the indexer
@@ -378,6 +388,10 @@ envio_source_request_seconds_total{source="HyperSync",chainId="1",method="getLog
 # TYPE envio_source_known_height gauge
 envio_source_known_height{source="HyperSync",chainId="1"} 305
 
+# HELP envio_source_decode_errors_total The number of fetched items whose data didn't fit the schema they were decoded against, eg after a program upgrade the IDL doesn't reflect.
+# TYPE envio_source_decode_errors_total counter
+envio_source_decode_errors_total{source="SvmHyperSync",chainId="1",contract="Amm",event="Swap"} 3
+
 # HELP envio_reorg_detected_total Total number of reorgs detected
 # TYPE envio_reorg_detected_total counter
 envio_reorg_detected_total{chainId="1"} 2
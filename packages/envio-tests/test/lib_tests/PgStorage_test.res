@@ -915,12 +915,33 @@ FROM "test_schema"."envio_chains";`
         let expectedQuery = `SELECT "chain_id" as "chainId",
 SUBSTRING("id" FROM POSITION('-' IN "id") + 1) as "address",
 "contract_name" as "contractName",
-"registration_block" as "registrationBlock"
+"registration_block" as "registrationBlock",
+"end_block" as "endBlock"
 FROM "test_schema"."envio_addresses";`
 
         t.expect(query, ~message="Indexing addresses SQL should match exactly").toBe(expectedQuery)
       },
     )
+
+    Async.it(
+      "Should skip the rows an address snapshot already holds",
+      async t => {
+        let query = InternalTable.Chains.makeGetIndexingAddressesQuery(
+          ~pgSchema="test_schema",
+          ~snapshotBlocks=[(ChainId.fromInt(1), 100), (ChainId.fromInt(137), 250)],
+        )
+
+        let expectedQuery = `SELECT "chain_id" as "chainId",
+SUBSTRING("id" FROM POSITION('-' IN "id") + 1) as "address",
+"contract_name" as "contractName",
+"registration_block" as "registrationBlock",
+"end_block" as "endBlock"
+FROM "test_schema"."envio_addresses"
+WHERE NOT (("chain_id" = 1 AND "registration_block" <= 100 AND ("end_block" IS NULL OR "end_block" <= 100)) OR ("chain_id" = 137 AND "registration_block" <= 250 AND ("end_block" IS NULL OR "end_block" <= 250)));`
+
+        t.expect(query, ~message="Indexing addresses SQL should match exactly").toBe(expectedQuery)
+      },
+    )
   })
 
   describe("InternalTable.Checkpoints.makeCommitedCheckpointIdQuery", () => {
@@ -468,6 +468,7 @@ describe("SourceManager fetchNext", () => {
       ~nextPartitionIndex=partitions->Array.length,
       ~dynamicContracts=Utils.Set.make(),
       ~clientFilteredContracts=Utils.Set.make(),
+      ~bloomFilteredContracts=Utils.Set.make(),
     )
 
     {
@@ -484,6 +485,7 @@ describe("SourceManager fetchNext", () => {
       knownHeight,
       firstEventBlock: None,
       clientFilterAddressThreshold: None,
+      bloomFilterMinAddresses: None,
     }
   }
 
@@ -377,6 +377,8 @@ type EvmChainConfig<Id extends number = number> = {
   readonly endBlock?: number;
   /** Number of blocks to keep for reorg handling (default: 200). */
   readonly maxReorgDepth?: number;
+  /** Which blocks are treated as no longer reorgable (default: "depth"). */
+  readonly finality?: "depth" | "safe" | "finalized" | "rollback_guard";
   /** Number of blocks behind the chain head to lag (default: 0). */
   readonly blockLag?: number;
 };
@@ -607,6 +609,8 @@ type EntityOperations<Entity> = {
 type ContractRegistration = {
   /** Register a new contract address for dynamic indexing. */
   readonly add: (address: Address) => void;
+  /** Stop indexing a registered address after the current event's block. */
+  readonly remove: (address: Address) => void;
 };
 
 /** Context for contractRegister handlers. Chain object includes contract registration methods.
@@ -797,6 +801,15 @@ export type EvmAllTransactionFields = {
   readonly l1FeeScalar: number | undefined;
   readonly gasUsedForL1: bigint | undefined;
   readonly authorizationList: readonly unknown[] | undefined;
+  readonly sourceHash: string | undefined;
+  readonly mint: bigint | undefined;
+  readonly isSystemTx: boolean | undefined;
+  readonly l1BaseFeeScalar: bigint | undefined;
+  readonly l1BlobBaseFee: bigint | undefined;
+  readonly l1BlobBaseFeeScalar: bigint | undefined;
+  readonly operatorFeeScalar: bigint | undefined;
+  readonly l1BatchNumber: number | undefined;
+  readonly l1BatchTxIndex: number | undefined;
 };
 
 export type EvmBlockFieldName = keyof EvmAllBlockFields & string;
@@ -1457,6 +1470,100 @@ export type SvmOnInstructionHandler<
   Config extends IndexerConfigTypes = GlobalConfig,
 > = (args: SvmOnInstructionHandlerArgs<Config>) => Promise<void>;
 
+/** An Anchor event (`emit!` / `emit_cpi!`) decoded from a program's
+ * `Program data:` logs or self-CPI instructions. `programId`, `data` and `path`
+ * describe the emitting instruction and are selected under `fields.instruction`.
+ * `params` is empty when the payload doesn't match the declared layout. */
+export type SvmEvent<
+  ProgEvent = { readonly params: unknown },
+  Fields = {},
+> = {
+  readonly programName: string;
+  readonly eventName: string;
+  /** Hex-encoded 8-byte event discriminator. */
+  readonly discriminator: string;
+  readonly params: ProgEvent extends { readonly params: infer P } ? P : unknown;
+  readonly programId: SvmInstrField<Fields, "programId", string>;
+  /** Hex-encoded event payload, discriminator included. */
+  readonly data: SvmInstrField<Fields, "data", string>;
+  readonly path: SvmInstrField<Fields, "path", readonly number[]>;
+  readonly transaction: SvmSelectedTransaction<Fields>;
+  readonly block: SvmSelectedBlock<Fields>;
+};
+
+/** Arguments passed to handlers registered via the SVM `indexer.onEvent`. */
+export type SvmOnEventHandlerArgs<
+  Config extends IndexerConfigTypes = GlobalConfig,
+  Event = SvmEvent,
+> = {
+  readonly event: Event;
+  readonly context: SvmOnSlotContext<Config>;
+};
+
+/** Options for an SVM `indexer.onEvent` registration. */
+export type SvmOnEventOptions<
+  P extends string = string,
+  E extends string = string,
+  Fields extends SvmFieldsSelection | undefined = undefined,
+> = {
+  /** Program name as declared under `chains[].programs[].name` in
+   * `config.yaml`. */
+  readonly program: P;
+  /** Event name as declared under `chains[].programs[].events[].name` in
+   * `config.yaml`. */
+  readonly event: E;
+  readonly fields?: Fields & SvmFieldsLiteralCheck<Fields>;
+};
+
+/** An account of one of a program's Anchor account types, written by one of
+ * the program's instructions. HyperSync doesn't serve account data, so the
+ * state is read over the chain's `rpc`: `after` is the account's state when
+ * the batch was fetched (not necessarily right after this write) and `before`
+ * is absent. `after` is also absent when the data doesn't match the declared
+ * layout. `programId` and `path` describe the writing instruction and are
+ * selected under `fields.instruction`. */
+export type SvmAccountChange<
+  ProgAccount = { readonly state: unknown },
+  Fields = {},
+> = {
+  readonly programName: string;
+  readonly accountName: string;
+  /** Base58 address of the written account. */
+  readonly address: string;
+  /** Hex-encoded 8-byte account discriminator. */
+  readonly discriminator: string;
+  readonly before?: ProgAccount extends { readonly state: infer S } ? S : unknown;
+  readonly after?: ProgAccount extends { readonly state: infer S } ? S : unknown;
+  readonly programId: SvmInstrField<Fields, "programId", string>;
+  readonly path: SvmInstrField<Fields, "path", readonly number[]>;
+  readonly transaction: SvmSelectedTransaction<Fields>;
+  readonly block: SvmSelectedBlock<Fields>;
+};
+
+/** Arguments passed to handlers registered via `indexer.onAccount`. */
+export type SvmOnAccountHandlerArgs<
+  Config extends IndexerConfigTypes = GlobalConfig,
+  Account = SvmAccountChange,
+> = {
+  readonly account: Account;
+  readonly context: SvmOnSlotContext<Config>;
+};
+
+/** Options for an SVM `indexer.onAccount` registration. */
+export type SvmOnAccountOptions<
+  P extends string = string,
+  A extends string = string,
+  Fields extends SvmFieldsSelection | undefined = undefined,
+> = {
+  /** Program name as declared under `chains[].programs[].name` in
+   * `config.yaml`. */
+  readonly program: P;
+  /** Account type name as declared under `chains[].programs[].accounts[].name`
+   * in `config.yaml`. */
+  readonly account: A;
+  readonly fields?: Fields & SvmFieldsLiteralCheck<Fields>;
+};
+
 // ============== Indexer Types ==============
 
 // Helper: Check if an ecosystem is configured. Single-ecosystem indexers only
@@ -1674,6 +1781,57 @@ type SvmEcosystem<Config extends IndexerConfigTypes = GlobalConfig> =
               handler: SvmOnSlotHandler<Config>,
             ) => void;
           } & (Config["svm"] extends {
+            programEvents: infer ProgramEvents extends Record<string, Record<string, any>>;
+          }
+            ? {
+                /**
+                 * Register an Anchor event handler. Dispatch matches on
+                 * `(programId, event discriminator)` from the YAML config.
+                 */
+                readonly onEvent: <
+                  P extends keyof ProgramEvents & string,
+                  E extends keyof ProgramEvents[P] & string,
+                  const F extends SvmFieldsSelection | undefined,
+                >(
+                  options: SvmOnEventOptions<P, E, F>,
+                  handler: (
+                    args: SvmOnEventHandlerArgs<
+                      Config,
+                      SvmEvent<
+                        ProgramEvents[P][E],
+                        [F] extends [undefined] ? {} : F
+                      >
+                    >,
+                  ) => Promise<void>,
+                ) => void;
+              }
+            : {}) & (Config["svm"] extends {
+            programAccounts: infer ProgramAccounts extends Record<string, Record<string, any>>;
+          }
+            ? {
+                /**
+                 * Register a handler for writes to a program's Anchor account
+                 * type. Dispatch matches on `(programId, account discriminator)`
+                 * from the YAML config.
+                 */
+                readonly onAccount: <
+                  P extends keyof ProgramAccounts & string,
+                  A extends keyof ProgramAccounts[P] & string,
+                  const F extends SvmFieldsSelection | undefined,
+                >(
+                  options: SvmOnAccountOptions<P, A, F>,
+                  handler: (
+                    args: SvmOnAccountHandlerArgs<
+                      Config,
+                      SvmAccountChange<
+                        ProgramAccounts[P][A],
+                        [F] extends [undefined] ? {} : F
+                      >
+                    >,
+                  ) => Promise<void>,
+                ) => void;
+              }
+            : {}) & (Config["svm"] extends {
             programs: infer Programs extends Record<string, Record<string, any>>;
           }
             ? {
@@ -22,6 +22,10 @@ type reorgHashSnapshot = {
 
 type chainBeforeBatch = {
   fetchState: FetchState.t,
+  // The chain's item store, popped alongside `fetchState.buffer`, and the
+  // registrations its items index into.
+  eventItemStore: EventItemStore.t,
+  onEventRegistrations: array<Internal.onEventRegistration>,
   scannedHashes: reorgHashSnapshot,
   shouldRollbackOnReorg: bool,
   progressBlockNumber: int,
@@ -77,6 +81,9 @@ let getProgressedChainsById = {
   (
     ~chainsBeforeBatch: dict<chainBeforeBatch>,
     ~batchSizePerChain: dict<int>,
+    // How many of each chain's batch items came from its buffer rather than
+    // its item store.
+    ~bufferItemsPerChain: dict<int>,
     ~progressBlockNumberPerChain: dict<int>,
   ) => {
     let progressedChainsById = Dict.make()
@@ -101,7 +108,11 @@ let getProgressedChainsById = {
         fetchState.chainId->ChainId.toString,
       ) {
       | Some(batchSize) =>
-        let leftItems = fetchState.buffer->Array.slice(~start=batchSize)
+        let bufferItems =
+          bufferItemsPerChain
+          ->Utils.Dict.dangerouslyGetNonOption(fetchState.chainId->ChainId.toString)
+          ->Option.getOr(0)
+        let leftItems = fetchState.buffer->Array.slice(~start=bufferItems)
         getChainAfterBatchIfProgressed(
           ~chainBeforeBatch,
           ~batchSize,
@@ -188,6 +199,56 @@ let addReorgCheckpoints = (
   }
 }
 
+// The chain's next ready items in processing order: the buffer's merged with
+// what the item store pops up to the same block. Neither side splits a block,
+// and each one's cut bounds the other's, so the items still end on a whole
+// block. Also returns how many of them came from the buffer.
+let takeReadyItems = (chainBeforeBatch: chainBeforeBatch, ~targetSize) => {
+  let fetchState = chainBeforeBatch.fetchState
+  let buffer = fetchState.buffer
+  let bufferCount = fetchState->FetchState.getReadyItemsCount(~targetSize, ~fromItem=0)
+  let isBufferFull = bufferCount >= targetSize
+  let maxStoredItems = targetSize - bufferCount
+  let stored =
+    chainBeforeBatch.eventItemStore
+    ->EventItemStore.popBatch(
+      ~upToBlock=isBufferFull
+        ? buffer->Array.getUnsafe(bufferCount - 1)->Internal.getItemBlockNumber
+        : fetchState->FetchState.bufferBlockNumber,
+      // A full buffer ends on its last block, and the store's items up to it
+      // are taken whatever their number.
+      ~maxItems=?(isBufferFull ? None : Some(maxStoredItems)),
+    )
+    ->EventItemStore.toItems(
+      ~chainId=fetchState.chainId,
+      ~onEventRegistrations=chainBeforeBatch.onEventRegistrations,
+    )
+  let storedCount = stored->Array.length
+  let bufferCount = if !isBufferFull && storedCount >= maxStoredItems {
+    // The store filled the rest and ended on its own last block, which the
+    // buffer's share can't pass.
+    let lastBlockNumber = stored->Array.getUnsafe(storedCount - 1)->Internal.getItemBlockNumber
+    let count = ref(0)
+    while (
+      count.contents < bufferCount &&
+        buffer->Array.getUnsafe(count.contents)->Internal.getItemBlockNumber <= lastBlockNumber
+    ) {
+      count := count.contents + 1
+    }
+    count.contents
+  } else {
+    bufferCount
+  }
+  let bufferItems = buffer->Array.slice(~start=0, ~end=bufferCount)
+  (
+    switch stored {
+    | [] => bufferItems
+    | _ => bufferItems->FetchState.mergeIntoBuffer(stored)
+    },
+    bufferCount,
+  )
+}
+
 let prepareBatch = (
   ~checkpointIdBeforeBatch,
   ~chainsBeforeBatch: dict<chainBeforeBatch>,
@@ -198,7 +259,12 @@ let prepareBatch = (
     chainsBeforeBatch
     ->Dict.valuesToArray
     ->Array.map(chainBeforeBatch => chainBeforeBatch.fetchState)
-    ->FetchState.sortForBatch(~batchSizeTarget)
+    ->FetchState.sortForBatch(
+      ~batchSizeTarget,
+      ~itemStores=chainsBeforeBatch->Utils.Dict.mapValues(chainBeforeBatch =>
+        chainBeforeBatch.eventItemStore
+      ),
+    )
 
   let chainIdx = ref(0)
   let preparedNumber = preparedFetchStates->Array.length
@@ -206,6 +272,7 @@ let prepareBatch = (
 
   let prevCheckpointId = ref(checkpointIdBeforeBatch)
   let mutBatchSizePerChain = Dict.make()
+  let mutBufferItemsPerChain = Dict.make()
   let mutProgressBlockNumberPerChain = Dict.make()
 
   let items = []
@@ -220,20 +287,18 @@ let prepareBatch = (
   // This way the loaders optimisations will hit more often
   while totalBatchSize.contents < batchSizeTarget && chainIdx.contents < preparedNumber {
     let fetchState = preparedFetchStates->Array.getUnsafe(chainIdx.contents)
-    let chainBatchSize =
-      fetchState->FetchState.getReadyItemsCount(
-        ~targetSize=batchSizeTarget - totalBatchSize.contents,
-        ~fromItem=0,
-      )
     let chainBeforeBatch =
       chainsBeforeBatch
       ->ChainId.Dict.dangerouslyGetNonOption(fetchState.chainId)
       ->Option.getUnsafe
+    let (chainItems, bufferItemsCount) =
+      chainBeforeBatch->takeReadyItems(~targetSize=batchSizeTarget - totalBatchSize.contents)
+    let chainBatchSize = chainItems->Array.length
 
     let prevBlockNumber = ref(chainBeforeBatch.progressBlockNumber)
     if chainBatchSize > 0 {
       for idx in 0 to chainBatchSize - 1 {
-        let item = fetchState.buffer->Array.getUnsafe(idx)
+        let item = chainItems->Array.getUnsafe(idx)
         let blockNumber = item->Internal.getItemBlockNumber
 
         // Every new block we should create a new checkpoint
@@ -284,10 +349,14 @@ let prepareBatch = (
 
       totalBatchSize := totalBatchSize.contents + chainBatchSize
       mutBatchSizePerChain->ChainId.Dict.set(fetchState.chainId, chainBatchSize)
+      mutBufferItemsPerChain->ChainId.Dict.set(fetchState.chainId, bufferItemsCount)
     }
 
     let progressBlockNumberAfterBatch =
-      fetchState->FetchState.getProgressBlockNumberAt(~index=chainBatchSize)
+      fetchState->FetchState.getProgressBlockNumberAt(
+        ~index=bufferItemsCount,
+        ~itemStore=chainBeforeBatch.eventItemStore,
+      )
 
     prevCheckpointId :=
       addReorgCheckpoints(
@@ -318,6 +387,7 @@ let prepareBatch = (
     progressedChainsById: getProgressedChainsById(
       ~chainsBeforeBatch,
       ~batchSizePerChain=mutBatchSizePerChain,
+      ~bufferItemsPerChain=mutBufferItemsPerChain,
       ~progressBlockNumberPerChain=mutProgressBlockNumberPerChain,
     ),
     isInReorgThreshold,
@@ -25,6 +25,7 @@ let runContractRegistersOrThrow = async (
   )
 
   let registrations: array<AddressStore.registration> = []
+  let deregistrations: array<AddressStore.deregistration> = []
 
   let onRegister = (~item: Internal.item, ~contractAddress, ~contractName) => {
     let eventItem = item->Internal.castUnsafeEventItem
@@ -35,6 +36,17 @@ let runContractRegistersOrThrow = async (
     })
   }
 
+  // The deregistering event's own block is the last one indexed, so the event
+  // itself and its block's other events still reach the handlers.
+  let onDeregister = (~item: Internal.item, ~contractAddress, ~contractName) => {
+    let eventItem = item->Internal.castUnsafeEventItem
+    deregistrations->Array.push({
+      address: contractAddress,
+      contractName,
+      block: eventItem.blockNumber,
+    })
+  }
+
   let promises = []
   for idx in 0 to itemsWithContractRegister->Array.length - 1 {
     let item = itemsWithContractRegister->Array.getUnsafe(idx)
@@ -53,6 +65,7 @@ let runContractRegistersOrThrow = async (
       let params: ContractRegisterContext.contractRegisterParams = {
         item,
         onRegister,
+        onDeregister,
         config,
         isResolved: false,
       }
@@ -91,7 +104,7 @@ let runContractRegistersOrThrow = async (
     let _ = await Promise.all(promises)
   }
 
-  registrations
+  (registrations, deregistrations)
 }
 
 let rec onQueryResponse = async (
@@ -108,25 +121,48 @@ let rec onQueryResponse = async (
     let chainState = state->IndexerState.getChainState(~chainId)
     let {
       parsedQueueItems,
+      ?itemStore,
       transactionStore,
       blockStore,
+      ?registrationPage,
       latestFetchedBlockNumber,
+      ?finalizedBlock,
       stats,
       knownHeight,
       fromBlockQueried,
     } = response
 
+    let numEvents =
+      parsedQueueItems->Array.length +
+        switch itemStore {
+        | Some(page) => page->EventItemStore.len
+        | None => 0
+        }
     chainState->ChainState.recordBlockRangeFetch(
       ~totalTimeElapsed=stats.totalTimeElapsed,
       ~parsingTimeElapsed=stats.parsingTimeElapsed->Option.getOr(0.),
-      ~numEvents=parsedQueueItems->Array.length,
+      ~numEvents,
       ~blockRangeSize=latestFetchedBlockNumber - fromBlockQueried + 1,
     )
 
-    let numContractRegisterEvents = parsedQueueItems->Array.reduce(0, (count, item) => {
+    // Over-fetched events (a merged partition returning an address before its
+    // effectiveStartBlock, a wildcard param referencing an address registered
+    // after the log's block, or a registration whose own start block is later
+    // than its contract's) are already dropped by the source's native gates,
+    // so everything here is indexable.
+    let itemsWithContractRegister = switch itemStore {
+    | Some(page) => chainState->ChainState.contractRegisterItems(~itemStore=page)
+    | None => []
+    }
+    for idx in 0 to parsedQueueItems->Array.length - 1 {
+      let item = parsedQueueItems->Array.getUnsafe(idx)
       let eventItem = item->Internal.castUnsafeEventItem
-      eventItem.onEventRegistration.contractRegister !== None ? count + 1 : count
-    })
+      if eventItem.onEventRegistration.contractRegister !== None {
+        itemsWithContractRegister->Array.push(item)
+      }
+    }
+
+    let numContractRegisterEvents = itemsWithContractRegister->Array.length
     if numContractRegisterEvents === 0 {
       Logging.trace({
         "msg": "Finished querying",
@@ -134,7 +170,7 @@ let rec onQueryResponse = async (
         "partitionId": query.partitionId,
         "fromBlock": fromBlockQueried,
         "toBlock": latestFetchedBlockNumber,
-        "numEvents": parsedQueueItems->Array.length,
+        "numEvents": numEvents,
       })
     } else {
       Logging.trace({
@@ -143,11 +179,17 @@ let rec onQueryResponse = async (
         "partitionId": query.partitionId,
         "fromBlock": fromBlockQueried,
         "toBlock": latestFetchedBlockNumber,
-        "numEvents": parsedQueueItems->Array.length,
+        "numEvents": numEvents,
         "numContractRegisterEvents": numContractRegisterEvents,
       })
     }
 
+    // Before the guard, so the window it compares already starts at the
+    // response's finalized block.
+    switch finalizedBlock {
+    | Some(blockNumber) => chainState->ChainState.recordResponseFinalizedBlock(~blockNumber)
+    | None => ()
+    }
     let reorgResult = chainState->ChainState.registerReorgGuard(~blockStore, ~knownHeight)
 
     let rollbackWithReorgDetectedBlockNumber = switch reorgResult {
@@ -201,34 +243,22 @@ let rec onQueryResponse = async (
       // kick (eg from the processing loop quiescing) collapses into this one.
       scheduleRollback()
     | None =>
-      // Over-fetched events (a merged partition returning an address before its
-      // effectiveStartBlock, a wildcard param referencing an address registered
-      // after the log's block, or a registration whose own start block is later
-      // than its contract's) are already dropped by the source's native gates,
-      // so everything here is indexable.
-      let newItems = parsedQueueItems
-      let itemsWithContractRegister = []
-      for idx in 0 to newItems->Array.length - 1 {
-        let item = newItems->Array.getUnsafe(idx)
-        let eventItem = item->Internal.castUnsafeEventItem
-        if eventItem.onEventRegistration.contractRegister !== None {
-          itemsWithContractRegister->Array.push(item)
-        }
-      }
-
       // Re-check staleness: contract registration is async, so the chain state
       // may have rolled back by the time we apply the fetched items.
-      let proceed = (~newRegistrations) =>
+      let proceed = (~newRegistrations, ~newDeregistrations) =>
         if !(state->IndexerState.isStale(~stateId)) {
           applyQueryResponse(
             state,
             ~chainId,
-            ~newItems,
+            ~newItems=parsedQueueItems,
+            ~itemStore?,
             ~newRegistrations,
+            ~newDeregistrations,
             ~knownHeight,
             ~latestFetchedBlock=latestFetchedBlockNumber,
             ~query,
             ~transactionStore,
+            ~registrationPage?,
           )
           ChainMetadata.stage(state)
           scheduleFetch()
@@ -236,7 +266,7 @@ let rec onQueryResponse = async (
         }
 
       switch itemsWithContractRegister {
-      | [] => proceed(~newRegistrations=[])
+      | [] => proceed(~newRegistrations=[], ~newDeregistrations=[])
       | _ =>
         switch await runContractRegistersOrThrow(
           ~itemsWithContractRegister,
@@ -245,7 +275,8 @@ let rec onQueryResponse = async (
           ~transactionStore,
         ) {
         | exception exn => IndexerState.errorExit(state, exn->ErrorHandling.make)
-        | newRegistrations => proceed(~newRegistrations)
+        | (newRegistrations, newDeregistrations) =>
+          proceed(~newRegistrations, ~newDeregistrations)
         }
       }
     }
@@ -254,30 +285,47 @@ let rec onQueryResponse = async (
 and applyQueryResponse = (
   state: IndexerState.t,
   ~chainId,
-  ~newItems,
+  ~newItems: array<Internal.item>,
+  ~itemStore=?,
   ~newRegistrations,
+  ~newDeregistrations,
   ~knownHeight,
   ~latestFetchedBlock,
   ~query,
   ~transactionStore,
+  ~registrationPage=?,
 ) => {
   let chainState = state->IndexerState.getChainState(~chainId)
   let wasFetchingAtHead = chainState->ChainState.isFetchingAtHead
+  // Read before the page is merged away into the chain's store.
+  let firstItemBlockNumber = {
+    let inItems = newItems->Array.get(0)->Option.map(Internal.getItemBlockNumber)
+    let inPage =
+      itemStore->Option.flatMap(page => page->EventItemStore.firstBlockNumber->Null.toOption)
+    switch (inItems, inPage) {
+    | (Some(a), Some(b)) => Some(Pervasives.min(a, b))
+    | (Some(_) as first, None) | (None, Some(_) as first) => first
+    | (None, None) => None
+    }
+  }
 
   chainState->ChainState.handleQueryResult(
     ~query,
     ~latestFetchedBlock,
     ~newItems,
     ~newRegistrations,
+    ~newDeregistrations,
     ~knownHeight,
     ~transactionStore,
+    ~itemStore?,
+    ~registrationPage?,
   )
 
   // In auto-exit mode, set endBlock to the first event's block when events arrive.
-  if state->IndexerState.exitAfterFirstEventBlock && newItems->Array.length > 0 {
-    chainState->ChainState.setEndBlockToFirstEvent(
-      ~blockNumber=newItems->Array.getUnsafe(0)->Internal.getItemBlockNumber,
-    )
+  switch firstItemBlockNumber {
+  | Some(blockNumber) if state->IndexerState.exitAfterFirstEventBlock =>
+    chainState->ChainState.setEndBlockToFirstEvent(~blockNumber)
+  | _ => ()
   }
 
   // Log the backfill→head transition once: this response brought the fetch
@@ -305,6 +353,7 @@ let finishWaitingForNewBlock = (
   } else {
     let chainState = state->IndexerState.getChainState(~chainId)
     chainState->ChainState.updateKnownHeight(~knownHeight)
+    chainState->ChainState.refreshFinalizedBlock
 
     // No reorg-threshold check here: scheduleProcessing always runs at least one
     // processNextBatch (even with no items), which owns the entry decision.
@@ -41,6 +41,13 @@ type t = {
   // detected reorg either rolls back or is only logged.
   shouldRollbackOnReorg: bool,
   maxReorgDepth: int,
+  // The highest block the chain's finality source last reported final, for a
+  // chain whose `finality` isn't `Depth`. None until the first report; the
+  // depth applies until then.
+  mutable finalizedBlock: option<int>,
+  // When the block tag behind `finality: safe | finalized` was last requested,
+  // so a fast chain's new blocks don't each cost an RPC call.
+  mutable finalizedBlockRequestedAt: float,
   // Holds this chain's transactions (kept in Rust) keyed by (blockNumber,
   // transactionIndex). Fetch responses merge their page in; entries are pruned
   // as the chain progresses and dropped above the target on rollback.
@@ -48,7 +55,17 @@ type t = {
   // Holds this chain's blocks (kept in Rust) keyed by block number. Same merge /
   // prune / rollback lifecycle as the transaction store.
   blockStore: BlockStore.t,
+  // Holds this chain's fetched items that a source keeps in Rust (EVM
+  // HyperSync), in processing order. Fetch responses merge their page in, and
+  // batch creation pops from it together with the fetch state's buffer. Same
+  // prune / rollback lifecycle as the stores above.
+  eventItemStore: EventItemStore.t,
   reorgThresholdReadyTolerance: int,
+  // The store's `nextId` when its address snapshot was last saved, or -1 when
+  // no snapshot on disk reflects this run. Unchanged ids mean nothing was
+  // registered since, so the save is skipped.
+  mutable addressSnapshotNextId: int,
+  mutable addressSnapshotSavedAt: float,
   // --- Per-chain metric counters, rendered by Metrics at scrape time.
   // Floats: cumulative counters outgrow int32. ---
   mutable blockRangeFetchSeconds: float,
@@ -104,6 +121,7 @@ let make = (
   ~transactionStore=TransactionStore.make(~ecosystem=Ecosystem.Evm, ~shouldChecksum=false),
   ~chainDensity=None,
   ~blockStore=BlockStore.make(~ecosystem=Ecosystem.Evm, ~shouldChecksum=false),
+  ~eventItemStore=EventItemStore.make(),
   ~reorgThresholdReadyTolerance=100,
   ~perChainEntities: array<Internal.entityConfig>=[],
   ~logger: Pino.t,
@@ -127,9 +145,14 @@ let make = (
     safeCheckpointTracking,
     shouldRollbackOnReorg,
     maxReorgDepth,
+    finalizedBlock: None,
+    finalizedBlockRequestedAt: 0.,
     transactionStore,
     blockStore,
+    eventItemStore,
     reorgThresholdReadyTolerance,
+    addressSnapshotNextId: -1,
+    addressSnapshotSavedAt: Date.now(),
     blockRangeFetchSeconds: 0.,
     blockRangeParseSeconds: 0.,
     blockRangeFetchCount: 0.,
@@ -173,6 +196,7 @@ let makeInternal = (
   ~maxReorgDepth,
   ~knownHeight=0,
   ~isResumed=false,
+  ~addressSnapshotBlock=?,
   ~reducedPollingInterval=?,
 ): t => {
   // Handler binding + `where`-derived fetch state, and onBlock registrations,
@@ -202,13 +226,53 @@ let makeInternal = (
     ~contracts=AddressStore.contractsOf(
       ~onEventRegistrations,
       ~configContractNames=config->configContractNames,
+      ~ttlBlocksByContract=chainConfig.contracts
+      ->Array.filterMap(contract =>
+        contract.ttlBlocks->Option.map(ttlBlocks => (contract.name, ttlBlocks))
+      )
+      ->Dict.fromArray,
     ),
   )
 
+  // Restored before `FetchState.make` seeds `indexingAddresses` — the rows
+  // registered above the snapshot's block — since loading needs an empty store.
+  // The resume query skipped the rows the file holds, so one that can't be
+  // loaded is removed and the indexer stopped: the restart then reads every
+  // address from the database.
+  let restoredContracts = switch addressSnapshotBlock {
+  | Some(block) =>
+    let chainId = chainConfig.id
+    try {
+      let restored =
+        addressStore->AddressStore.loadSnapshot(
+          AddressStore.snapshotPath(~chainId),
+          ~scope=Config.addressSnapshotScope(),
+        )
+      logger->Logging.childInfo({
+        "msg": "Restored addresses from snapshot",
+        "snapshotBlock": block,
+        "addresses": addressStore->AddressStore.size,
+      })
+      restored.contracts
+    } catch {
+    | JsExn(e) =>
+      AddressStore.removeSnapshot(~chainId)
+      JsError.throwWithMessage(
+        `${e
+          ->JsExn.message
+          ->Option.getOr(
+            "Failed to restore addresses from snapshot",
+          )}. The snapshot was removed; restart the indexer to load chain ${chainId->ChainId.toString}'s addresses from the database instead.`,
+      )
+    }
+  | None => []
+  }
+
   let fetchState = FetchState.make(
     ~maxAddrInPartition=config.maxAddrInPartition,
     ~addressStore,
     ~addresses=indexingAddresses,
+    ~restoredContracts,
     ~progressBlockNumber,
     ~startBlock,
     ~endBlock,
@@ -235,6 +299,13 @@ let makeInternal = (
     | Evm => Some(config.clientFilterAddressThreshold)
     | Fuel | Svm => None
     },
+    // Only HyperSync takes an address bloom filter. An RPC-only chain lists
+    // every address, so it keeps both caps.
+    ~bloomFilterMinAddresses=switch chainConfig.sourceConfig {
+    | Config.EvmSourceConfig({hypersync: Some(_)}) =>
+      Some(Core.getAddon().addressFilterMinAddresses())
+    | _ => None
+    },
     ~isResumed,
   )
 
@@ -248,17 +319,26 @@ let makeInternal = (
 
   // Create sources lazily here - this is where API token validation happens
   let chainId = chainConfig.id
+  let captureDir = if Env.captureResponses {
+    Some(
+      NodeJs.Path.resolve([".envio", "capture", chainId->ChainId.toString])->NodeJs.Path.toString,
+    )
+  } else {
+    None
+  }
   let sources = switch chainConfig.sourceConfig {
   | Config.EvmSourceConfig({hypersync, rpcs}) =>
     let evmRpcs: array<EvmChain.rpc> = rpcs->Array.map((rpc): EvmChain.rpc => {
       let syncConfig = rpc.syncConfig
       let ws = rpc.ws
+      let additionalUrls = rpc.additionalUrls
       let headers = rpc.headers
       {
         url: rpc.url,
         sourceFor: rpc.sourceFor,
         ?syncConfig,
         ?ws,
+        ?additionalUrls,
         ?headers,
       }
     })
@@ -271,6 +351,7 @@ let makeInternal = (
       ~rpcs=evmRpcs,
       ~lowercaseAddresses,
       ~addressStore,
+      ~captureDir?,
     )
   | Config.FuelSourceConfig({hypersync}) => [
       FuelHyperSyncSource.make({
@@ -279,6 +360,7 @@ let makeInternal = (
         apiToken: Env.envioApiToken,
         onEventRegistrations,
         addressStore,
+        ?captureDir,
       }),
     ]
   | Config.SvmSourceConfig({hypersync, rpc}) =>
@@ -286,9 +368,11 @@ let makeInternal = (
     | (None, None) =>
       JsError.throwWithMessage(`Chain ${chainId->ChainId.toString} has no SVM data source`)
     | (None, Some(rpc)) => [Svm.makeRPCSource(~chainId, ~rpc)]
-    | (Some(hypersyncUrl), _) =>
-      // HyperSync drives instruction sync. A configured RPC is ignored for now
-      // (RPC fallback isn't wired up yet).
+    | (Some(hypersyncUrl), rpc) =>
+      // HyperSync drives instruction sync. A configured RPC only reads the
+      // state of program accounts, which HyperSync doesn't serve, and only when
+      // that development stand-in is switched on (RPC fallback isn't wired up
+      // yet).
       let apiToken = Env.envioApiToken
       [
         SvmHyperSyncSource.make({
@@ -298,6 +382,8 @@ let makeInternal = (
           onEventRegistrations,
           clientTimeoutMillis: Env.hyperSyncClientTimeoutMillis,
           addressStore,
+          ?captureDir,
+          accountsRpcUrl: ?(Env.svmAccountStateFromRpc ? rpc : None),
         }),
       ]
     }
@@ -320,6 +406,24 @@ let makeInternal = (
     ~ecosystem=config.ecosystem.name,
     ~shouldChecksum=!lowercaseAddresses,
   )
+  let transactionStore = TransactionStore.make(
+    ~ecosystem=config.ecosystem.name,
+    ~shouldChecksum=!lowercaseAddresses,
+  )
+
+  switch Env.storeSpillBudgetMb {
+  | Some(budgetMb) =>
+    let spillPath = fileName =>
+      NodeJs.Path.resolve([".envio", "spill", chainId->ChainId.toString, fileName])
+      ->NodeJs.Path.toString
+    let budgetBytes = budgetMb->Int.toFloat *. 1024. *. 1024.
+    blockStore->BlockStore.enableSpill(~path=spillPath("blocks.bin"), ~budgetBytes)
+    transactionStore->TransactionStore.enableSpill(
+      ~path=spillPath("transactions.bin"),
+      ~budgetBytes,
+    )
+  | None => ()
+  }
 
   // Seed the stored reorg checkpoints (hash-only rows) so detection resumes
   // against the hashes scanned before the restart.
@@ -362,10 +466,7 @@ let makeInternal = (
     ~perChainEntities=config.allEntities->EntityTables.perChain,
     ~timestampCaughtUpToHeadOrEndblock,
     ~numEventsProcessed,
-    ~transactionStore=TransactionStore.make(
-      ~ecosystem=config.ecosystem.name,
-      ~shouldChecksum=!lowercaseAddresses,
-    ),
+    ~transactionStore,
     ~chainDensity,
     ~blockStore,
     ~reorgThresholdReadyTolerance=config.reorgThresholdReadyTolerance,
@@ -440,6 +541,7 @@ let makeFromDbState = (
     ~isRealtime,
     ~knownHeight=resumedChainState.sourceBlockNumber,
     ~isResumed=true,
+    ~addressSnapshotBlock=?resumedChainState.addressSnapshotBlock,
     ~reducedPollingInterval?,
   )
 }
@@ -456,12 +558,26 @@ let resetEntities = (cs: t, ~perChainEntities) => cs.entities = EntityTables.mak
 let sourceManager = (cs: t) => cs.sourceManager
 let chainConfig = (cs: t) => cs.chainConfig
 let shouldRollbackOnReorg = (cs: t) => cs.shouldRollbackOnReorg
+
+// The highest block treated as no longer reorgable at `knownHeight`: the
+// finalized height the chain's finality source reported, or `maxReorgDepth`
+// below the head for `Depth` and until the first report. Stored hashes and
+// rollback checkpoints at or below it are dropped.
+let safeBlockNumberAt = (cs: t, ~knownHeight) =>
+  switch (cs.chainConfig.finality, cs.finalizedBlock) {
+  | (Some(Config.Safe | Finalized | RollbackGuard), Some(finalizedBlock)) =>
+    Pervasives.min(finalizedBlock, knownHeight)
+  | _ => knownHeight - cs.maxReorgDepth
+  }
+
+let safeBlockNumber = (cs: t) => cs->safeBlockNumberAt(~knownHeight=cs.fetchState.knownHeight)
+
 // Reorg-scan reads over the block store, for the rollback flow: the scanned
 // block numbers still inside the reorg threshold, and the highest of them whose
 // re-fetched hash still matches.
 let getReorgThresholdBlockNumbersBelow = (cs: t, ~blockNumber) =>
   cs.blockStore->BlockStore.getHashedBlockNumbers(
-    ~fromBlock=Pervasives.max(cs.fetchState.knownHeight - cs.maxReorgDepth, 0),
+    ~fromBlock=Pervasives.max(cs->safeBlockNumber, 0),
     ~belowBlock=blockNumber,
   )
 
@@ -476,6 +592,55 @@ let numEventsProcessed = (cs: t) => cs.numEventsProcessed
 let pendingBudget = (cs: t) => cs.pendingBudget
 let timestampCaughtUpToHeadOrEndblock = (cs: t) => cs.timestampCaughtUpToHeadOrEndblock
 
+// Saves the chain's addresses to its snapshot once `intervalMs` has passed since
+// the last save and something was registered since. The caller only calls this
+// with every processed batch written and no rollback underway, so the committed
+// progress block is exactly what `envio_addresses` holds. A failed save is
+// logged and retried at the next interval — the snapshot only speeds up a
+// restart, it's never what correctness rests on.
+let saveAddressSnapshot = (cs: t, ~intervalMs) => {
+  let nextId = cs.addressStore->AddressStore.nextId
+  let now = Date.now()
+  if nextId !== cs.addressSnapshotNextId && now -. cs.addressSnapshotSavedAt >= intervalMs {
+    cs.addressSnapshotSavedAt = now
+    let chainId = cs.chainConfig.id
+    let snapshotBlock = cs.committedProgressBlockNumber
+    try {
+      let count =
+        cs.addressStore->AddressStore.saveSnapshot(
+          AddressStore.snapshotPath(~chainId),
+          ~scope=Config.addressSnapshotScope(),
+          snapshotBlock,
+        )
+      cs.addressSnapshotNextId = nextId
+      cs.logger->Logging.childDebug({
+        "msg": "Saved address snapshot",
+        "snapshotBlock": snapshotBlock,
+        "addresses": count,
+      })
+    } catch {
+    | JsExn(e) =>
+      cs.logger->Logging.childWarn({
+        "msg": "Failed to save address snapshot",
+        "err": e->JsExn.message,
+      })
+    }
+  }
+}
+
+// A rollback deletes the `envio_addresses` rows above its target, so a snapshot
+// covering any of those blocks is removed before the rollback is written —
+// restoring it after a restart would bring the deleted addresses back.
+let dropAddressSnapshotAbove = (cs: t, ~targetBlock) => {
+  let chainId = cs.chainConfig.id
+  switch AddressStore.snapshotBlock(~chainId, ~scope=Config.addressSnapshotScope()) {
+  | Some(snapshotBlock) if snapshotBlock > targetBlock =>
+    AddressStore.removeSnapshot(~chainId)
+    cs.addressSnapshotNextId = -1
+  | _ => ()
+  }
+}
+
 // --- Metric counters. ---
 
 let recordBlockRangeFetch = (
@@ -513,12 +678,15 @@ let contractAddresses = (cs: t, ~contractName) =>
 let drainAddressesForWrite = (cs: t, ~toBlockInclusive, ~checkpointBlockNumbers) =>
   cs.addressStore->AddressStore.drainForWrite(toBlockInclusive, checkpointBlockNumbers)
 let hasAddressesToWrite = (cs: t) => cs.addressStore->AddressStore.pendingCount > 0
-let bufferSize = (cs: t) => cs.fetchState->FetchState.bufferSize
-let bufferReadyCount = (cs: t) => cs.fetchState->FetchState.bufferReadyCount
-let getProgressPercentage = (cs: t) => cs.fetchState->FetchState.getProgressPercentage
+let bufferSize = (cs: t) => cs.fetchState->FetchState.bufferSize(~itemStore=cs.eventItemStore)
+let bufferReadyCount = (cs: t) =>
+  cs.fetchState->FetchState.bufferReadyCount(~itemStore=cs.eventItemStore)
+let getProgressPercentage = (cs: t) =>
+  cs.fetchState->FetchState.getProgressPercentage(~itemStore=cs.eventItemStore)
 let chainDensity = (cs: t) => cs.chainDensity
 let hasReadyItem = (cs: t) =>
-  cs.fetchState->FetchState.isActivelyIndexing && cs.fetchState->FetchState.hasReadyItem
+  cs.fetchState->FetchState.isActivelyIndexing(~itemStore=cs.eventItemStore) &&
+    cs.fetchState->FetchState.hasReadyItem(~itemStore=cs.eventItemStore)
 
 // Mark queries as in flight and reserve their estimated size against the shared
 // buffer budget in one step, so the counter stays in sync with the pending
@@ -562,7 +730,7 @@ let fetchCeiling = (cs: t) => {
 // to what fetching just found, unlike the processing EMA which only moves as
 // batches commit.
 let readyBufferDensity = (cs: t) => {
-  let readyCount = cs.fetchState->FetchState.bufferReadyCount
+  let readyCount = cs->bufferReadyCount
   let span = cs.fetchState->FetchState.bufferBlockNumber - cs.processingBlockNumber
   if readyCount > 0 && span > 0 {
     Some(readyCount->Int.toFloat /. span->Int.toFloat)
@@ -722,11 +890,12 @@ let isDurablyCaughtUp = (cs: t) => {
 }
 
 let getHighestBlockBelowThreshold = (cs: t): int => {
-  let highestBlockBelowThreshold = cs.fetchState.knownHeight - cs.maxReorgDepth
+  let highestBlockBelowThreshold = cs->safeBlockNumber
   highestBlockBelowThreshold < 0 ? 0 : highestBlockBelowThreshold
 }
 
-let isActivelyIndexing = (cs: t) => cs.fetchState->FetchState.isActivelyIndexing
+let isActivelyIndexing = (cs: t) =>
+  cs.fetchState->FetchState.isActivelyIndexing(~itemStore=cs.eventItemStore)
 
 // True once the fetch frontier has reached the head/endBlock for this chain.
 let isFetchingAtHead = (cs: t) => cs.fetchState->FetchState.isFetchingAtHead
@@ -781,7 +950,7 @@ let groupBatchItems = (items: array<Internal.item>): (transactionGroups, blockGr
       | None =>
         let {transactionIndex} = eventItem
         let mask = eventItem.onEventRegistration.fieldSelection.transactionMask
-        if mask != 0. {
+        if mask != 0n {
           anyTransactionFieldSelected := true
         }
         let last = payloadGroups->Array.length - 1
@@ -914,14 +1083,34 @@ let materializePageItems = async (
   ))
 }
 
+// Copies of a response page's stored items whose registration has a
+// contractRegister, for the handlers to run on before the page is applied. The
+// items stay in the page and reach processing through the chain's item store.
+let contractRegisterItems = (cs: t, ~itemStore: EventItemStore.t) =>
+  switch cs.onEventRegistrations->Array.filterMap(registration =>
+    registration.contractRegister !== None ? Some(registration.index) : None
+  ) {
+  | [] => []
+  | registrationIndexes =>
+    itemStore
+    ->EventItemStore.peekRegistrations(registrationIndexes)
+    ->EventItemStore.toItems(
+      ~chainId=cs.chainConfig.id,
+      ~onEventRegistrations=cs.onEventRegistrations,
+    )
+  }
+
 let handleQueryResult = (
   cs: t,
   ~query: FetchState.query,
   ~newItems,
   ~newRegistrations,
+  ~newDeregistrations=[],
   ~latestFetchedBlock: int,
   ~knownHeight,
   ~transactionStore as txPage: option<TransactionStore.t>,
+  ~itemStore as itemPage: option<EventItemStore.t>=?,
+  ~registrationPage: option<AddressStore.registrationPage>=?,
 ) => {
   // Merge this response's transaction page into the chain store in lockstep
   // with appending its items to the buffer. Inline sources contribute no page;
@@ -930,11 +1119,25 @@ let handleQueryResult = (
   | Some(page) => cs.transactionStore->TransactionStore.merge(page)
   | None => ()
   }
+  // Same for the items a source kept in Rust: they go to the chain's item
+  // store rather than the buffer.
+  let storedItemsCount = switch itemPage {
+  | Some(page) =>
+    let count = page->EventItemStore.len
+    cs.eventItemStore->EventItemStore.merge(page)
+    count
+  | None => 0
+  }
 
+  let hasPageRegistrations = switch registrationPage {
+  | Some(page) => page->AddressStore.pageLength > 0
+  | None => false
+  }
   let fs = switch newRegistrations {
-  | [] => cs.fetchState
+  | [] if !hasPageRegistrations => cs.fetchState
   | _ =>
     cs.fetchState->FetchState.registerDynamicContracts(
+      ~registrationPage?,
       ~addressStore=cs.addressStore,
       // This response is applied below, after the addresses land. It was routed
       // before they existed, so whatever it claims has to be inside the
@@ -945,10 +1148,39 @@ let handleQueryResult = (
     )
   }
 
-  cs.fetchState =
+  let fs = fs->FetchState.handleQueryResult(
+    ~query,
+    ~latestFetchedBlock,
+    ~newItems,
+    ~storedItemsCount,
+  )
+  // After the response's items are in the buffer, so the ones a deregistration
+  // ends are pruned with the rest.
+  let fs = switch newDeregistrations {
+  | [] => fs
+  | _ =>
+    // The item store can't be filtered by address, so its items above the
+    // earliest deregistering block move to the buffer, where the pruning
+    // reaches them.
+    let fromBlock = newDeregistrations->Array.reduce(Int.Constants.maxValue, (
+      fromBlock,
+      deregistration: AddressStore.deregistration,
+    ) => Pervasives.min(fromBlock, deregistration.block))
     fs
-    ->FetchState.handleQueryResult(~query, ~latestFetchedBlock, ~newItems)
-    ->FetchState.updateKnownHeight(~knownHeight)
+    ->FetchState.mergeItems(
+      cs.eventItemStore
+      ->EventItemStore.popAbove(~afterBlock=fromBlock)
+      ->EventItemStore.toItems(
+        ~chainId=cs.chainConfig.id,
+        ~onEventRegistrations=cs.onEventRegistrations,
+      ),
+    )
+    ->FetchState.deregisterDynamicContracts(
+      ~addressStore=cs.addressStore,
+      newDeregistrations,
+    )
+  }
+  cs.fetchState = fs->FetchState.updateKnownHeight(~knownHeight)
 
   // The query is no longer in flight, so release its reservation.
   cs.pendingBudget = Pervasives.max(0., cs.pendingBudget -. query.itemsEst->Int.toFloat)
@@ -963,7 +1195,7 @@ let handleQueryResult = (
 let registerReorgGuard = (cs: t, ~blockStore, ~knownHeight): ReorgDetection.reorgResult => {
   switch cs.blockStore->BlockStore.merge(
     blockStore,
-    ~fromBlock=Pervasives.max(knownHeight - cs.maxReorgDepth, 0),
+    ~fromBlock=Pervasives.max(cs->safeBlockNumberAt(~knownHeight), 0),
     ~reportOnly=!cs.shouldRollbackOnReorg,
   ) {
   | Null.Null => NoReorg
@@ -989,6 +1221,47 @@ let prepareReorg = (cs: t, ~eventsProcessedDiff) => {
 let updateKnownHeight = (cs: t, ~knownHeight) =>
   cs.fetchState = cs.fetchState->FetchState.updateKnownHeight(~knownHeight)
 
+let advanceFinalizedBlock = (cs: t, ~blockNumber) =>
+  switch cs.finalizedBlock {
+  | Some(current) if current >= blockNumber => ()
+  | _ => cs.finalizedBlock = Some(blockNumber)
+  }
+
+// A fetch response's finalized height (HyperSync's rollback guard). Only a
+// chain with `finality: rollback_guard` follows it.
+let recordResponseFinalizedBlock = (cs: t, ~blockNumber) =>
+  switch cs.chainConfig.finality {
+  | Some(Config.RollbackGuard) => cs->advanceFinalizedBlock(~blockNumber)
+  | None | Some(Depth | Safe | Finalized) => ()
+  }
+
+// Minimum time between two requests for a chain's `safe`/`finalized` block.
+let finalizedBlockRefreshIntervalMs = 5_000.
+
+// Re-read the block tag behind `finality: safe | finalized` in the background,
+// at most once per interval. A failed read keeps the last known height.
+let refreshFinalizedBlock = (cs: t) => {
+  let tag = switch cs.chainConfig.finality {
+  | Some(Config.Safe) => Some(#safe)
+  | Some(Finalized) => Some(#finalized)
+  | None | Some(Depth | RollbackGuard) => None
+  }
+  switch tag {
+  | Some(tag) if Date.now() -. cs.finalizedBlockRequestedAt >= finalizedBlockRefreshIntervalMs =>
+    cs.finalizedBlockRequestedAt = Date.now()
+    cs.sourceManager
+    ->SourceManager.getBlockTagHeight(~tag)
+    ->Promise.thenResolve(height =>
+      switch height {
+      | Some(blockNumber) => cs->advanceFinalizedBlock(~blockNumber)
+      | None => ()
+      }
+    )
+    ->Promise.ignore
+  | _ => ()
+  }
+}
+
 // In auto-exit mode, pin the endBlock to the earliest observed event block.
 let setEndBlockToFirstEvent = (cs: t, ~blockNumber) =>
   switch cs.fetchState.endBlock {
@@ -1033,7 +1306,7 @@ let toMetrics = (cs: t): Metrics.chainMetrics => {
   progressLatencyMs: cs.progressLatencyMs,
   concurrency: cs.sourceManager->SourceManager.inFlightCount,
   partitionsCount: cs.fetchState->FetchState.partitionsCount,
-  bufferSize: cs.fetchState->FetchState.bufferSize,
+  bufferSize: cs->bufferSize,
   bufferBlockNumber: cs.fetchState->FetchState.bufferBlockNumber,
   idleSeconds: cs.sourceManager->SourceManager.idleSeconds,
   waitingForNewBlockSeconds: cs.sourceManager->SourceManager.waitingForNewBlockSeconds,
@@ -1054,7 +1327,7 @@ let toMetrics = (cs: t): Metrics.chainMetrics => {
 let toChainBeforeBatch = (cs: t): Batch.chainBeforeBatch => {
   let {blockNumbers, hashes} =
     cs.blockStore->BlockStore.getHashes(
-      ~fromBlock=Pervasives.max(cs.fetchState.knownHeight - cs.maxReorgDepth, 0),
+      ~fromBlock=Pervasives.max(cs->safeBlockNumber, 0),
       ~belowBlock=cs.fetchState.knownHeight + 1,
     )
   let hashByBlockNumber = Dict.make()
@@ -1063,6 +1336,8 @@ let toChainBeforeBatch = (cs: t): Batch.chainBeforeBatch => {
   )
   {
     fetchState: cs.fetchState,
+    eventItemStore: cs.eventItemStore,
+    onEventRegistrations: cs.onEventRegistrations,
     progressBlockNumber: cs.committedProgressBlockNumber,
     totalEventsProcessed: cs.numEventsProcessed,
     sourceBlockNumber: cs.fetchState.knownHeight,
@@ -1081,7 +1356,10 @@ let isReadyToEnterReorgThresholdAfterBatch = (cs: t, ~batch: Batch.t) => {
   | Some(chainAfterBatch) => chainAfterBatch.fetchState
   | None => cs.fetchState
   }
-  fetchState->FetchState.isReadyToEnterReorgThreshold(~tolerance=cs.reorgThresholdReadyTolerance)
+  fetchState->FetchState.isReadyToEnterReorgThreshold(
+    ~tolerance=cs.reorgThresholdReadyTolerance,
+    ~itemStore=cs.eventItemStore,
+  )
 }
 
 // Commit the post-batch fetch frontier for a chain that progressed in the batch,
@@ -1174,20 +1452,23 @@ let applyBatchProgress = (cs: t, ~batch: Batch.t, ~blockTimestampName: string) =
         chainAfterBatch.progressBlockNumber,
       )
       cs.numEventsProcessed = chainAfterBatch.totalEventsProcessed
-      // Processed blocks' transactions and blocks are no longer needed.
+      // Processed blocks' items, transactions and blocks are no longer needed.
+      // Batch creation popped the items already; this drops any a later
+      // response delivered at or below the progress.
+      cs.eventItemStore->EventItemStore.prune(~upToBlock=chainAfterBatch.progressBlockNumber)
       cs.transactionStore->TransactionStore.prune(chainAfterBatch.progressBlockNumber)
       // Processed blocks' other fields are dropped, but hashes still inside the
       // reorg threshold stay for reorg detection.
       cs.blockStore->BlockStore.prune(
         chainAfterBatch.progressBlockNumber,
-        ~keepHashesFrom=cs.fetchState.knownHeight - cs.maxReorgDepth,
+        ~keepHashesFrom=cs->safeBlockNumber,
       )
       cs.isProgressAtHead = cs.isProgressAtHead || chainAfterBatch.isProgressAtHeadWhenBatchCreated
       switch cs.safeCheckpointTracking {
       | Some(safeCheckpointTracking) =>
         cs.safeCheckpointTracking = Some(
           safeCheckpointTracking->SafeCheckpointTracking.updateOnNewBatch(
-            ~sourceBlockNumber=cs.fetchState.knownHeight,
+            ~safeBlockNumber=cs->safeBlockNumber,
             ~chainId,
             ~batchCheckpointIds=batch.checkpointIds,
             ~batchCheckpointBlockNumbers=batch.checkpointBlockNumbers,
@@ -1248,6 +1529,7 @@ let rollback = (
         ~addressStore=cs.addressStore,
         ~targetBlockNumber=newProgressBlockNumber,
       )
+    cs.eventItemStore->EventItemStore.rollback(~targetBlock=newProgressBlockNumber)
     cs.transactionStore->TransactionStore.rollback(newProgressBlockNumber)
     cs.blockStore->BlockStore.rollback(newProgressBlockNumber)
     cs.committedProgressBlockNumber = newProgressBlockNumber
@@ -1260,6 +1542,7 @@ let rollback = (
           ~addressStore=cs.addressStore,
           ~targetBlockNumber=rollbackTargetBlockNumber,
         )
+      cs.eventItemStore->EventItemStore.rollback(~targetBlock=rollbackTargetBlockNumber)
       cs.transactionStore->TransactionStore.rollback(rollbackTargetBlockNumber)
       cs.blockStore->BlockStore.rollback(rollbackTargetBlockNumber)
       cs.committedProgressBlockNumber = Pervasives.min(
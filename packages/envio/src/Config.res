@@ -7,6 +7,8 @@ type sourceSyncOptions = {
   queryTimeoutMillis?: int,
   fallbackStallTimeout?: int,
   pollingInterval?: int,
+  maxBatchSize?: int,
+  hedgeAfterMillis?: int,
 }
 
 type contract = {
@@ -15,6 +17,9 @@ type contract = {
   addresses: array<Address.t>,
   events: array<Internal.eventConfig>,
   startBlock: option<int>,
+  // How many blocks a dynamically registered address stays indexed for after
+  // the block that registered it. None keeps it until deregistered.
+  ttlBlocks: option<int>,
 }
 
 // Sources are instantiated lazily in ChainState from this config.
@@ -23,6 +28,7 @@ type evmRpcConfig = {
   sourceFor: Source.sourceFor,
   syncConfig: option<sourceSyncOptions>,
   ws: option<string>,
+  additionalUrls: option<array<string>>,
   headers: option<dict<string>>,
 }
 
@@ -42,6 +48,17 @@ type sourceConfig =
   // For tests: pass custom sources directly
   | CustomSources(array<Source.t>)
 
+// Which blocks a chain treats as no longer reorgable. `Depth` counts
+// `maxReorgDepth` back from the head; the others follow a height the chain
+// reports - an RPC block tag, or HyperSync's rollback guard.
+type finality =
+  | @as("depth") Depth
+  | @as("safe") Safe
+  | @as("finalized") Finalized
+  | @as("rollback_guard") RollbackGuard
+
+let finalitySchema = S.enum([Depth, Safe, Finalized, RollbackGuard])
+
 type chain = {
   name: string,
   id: ChainId.t,
@@ -49,6 +66,8 @@ type chain = {
   startBlock: int,
   endBlock?: int,
   maxReorgDepth: int,
+  // Absent means `Depth`.
+  finality?: finality,
   blockLag: int,
   contracts: array<contract>,
   sourceConfig: sourceConfig,
@@ -63,6 +82,8 @@ type sourceSync = {
   queryTimeoutMillis: int,
   fallbackStallTimeout: int,
   pollingInterval: int,
+  maxBatchSize: int,
+  hedgeAfterMillis: option<int>,
 }
 
 // How a backend spells column names, mirroring `column_name_format` in
@@ -143,6 +164,9 @@ module EnvioAddresses = {
     // an existing schema doesn't need a migration.
     @as("registration_log_index") registrationLogIndex: int,
     @as("contract_name") contractName: string,
+    // The last block the address is indexed at: its TTL's or a
+    // deregistration's. Absent for an address indexed indefinitely.
+    @as("end_block") endBlock?: int,
   }
 
   // Extract the raw contract address from the composite id ({chainId}-{address}).
@@ -161,6 +185,7 @@ module EnvioAddresses = {
     registrationBlock: s.matches(S.int),
     registrationLogIndex: s.matches(S.int),
     contractName: s.matches(S.string),
+    endBlock: ?s.matches(S.null(S.int)),
   })
 
   let table = Table.mkTable(
@@ -172,6 +197,7 @@ module EnvioAddresses = {
       // -1 sentinel when registered from a block handler (no log index)
       Table.mkField("registration_log_index", Int32, ~fieldSchema=S.int),
       Table.mkField("contract_name", String, ~fieldSchema=S.string),
+      Table.mkField("end_block", Int32, ~fieldSchema=S.null(S.int), ~isNullable=true),
     ],
   )
 
@@ -202,6 +228,7 @@ let rpcConfigSchema = S.schema(s =>
     "url": s.matches(S.string),
     "for": s.matches(rpcSourceForSchema),
     "ws": s.matches(S.option(S.string)),
+    "additionalUrls": s.matches(S.option(S.array(S.string))),
     "headers": s.matches(S.option(S.dict(S.string))),
     "initialBlockInterval": s.matches(S.option(S.int)),
     "backoffMultiplicative": s.matches(S.option(S.float)),
@@ -211,6 +238,8 @@ let rpcConfigSchema = S.schema(s =>
     "fallbackStallTimeout": s.matches(S.option(S.int)),
     "queryTimeoutMillis": s.matches(S.option(S.int)),
     "pollingInterval": s.matches(S.option(S.int)),
+    "maxBatchSize": s.matches(S.option(S.int)),
+    "hedgeAfterMillis": s.matches(S.option(S.int)),
   }
 )
 
@@ -218,6 +247,7 @@ let chainContractSchema = S.schema(s =>
   {
     "addresses": s.matches(S.option(S.array(S.string))),
     "startBlock": s.matches(S.option(S.int)),
+    "ttlBlocks": s.matches(S.option(S.int)),
   }
 )
 
@@ -227,6 +257,7 @@ let publicConfigChainSchema = S.schema(s =>
     "startBlock": s.matches(S.int),
     "endBlock": s.matches(S.option(S.int)),
     "maxReorgDepth": s.matches(S.option(S.int)),
+    "finality": s.matches(S.option(finalitySchema)),
     "blockLag": s.matches(S.option(S.int)),
     // EVM/Fuel source config (hypersync for EVM, hyperfuel for Fuel)
     "hypersync": s.matches(S.option(S.string)),
@@ -269,6 +300,7 @@ let svmAbiSchema = S.schema(s =>
     "programId": s.matches(S.string),
     "definedTypes": s.matches(S.json(~validate=false)),
     "source": s.matches(S.string),
+    "decodeErrors": s.matches(S.option(S.enum([Internal.Ignore, Warn, Fail]))),
   }
 )
 
@@ -280,6 +312,10 @@ let contractEventItemSchema = S.schema(s =>
     "kind": s.matches(S.option(S.string)),
     "blockFields": s.matches(S.option(S.array(Internal.evmBlockFieldSchema))),
     "transactionFields": s.matches(S.option(S.array(Internal.evmTransactionFieldSchema))),
+    // Passed through to the Rust clients as is.
+    "transactionWhere": s.matches(S.option(S.json(~validate=false))),
+    "paramsWhere": s.matches(S.option(S.json(~validate=false))),
+    "register": s.matches(S.option(S.json(~validate=false))),
     "svm": s.matches(S.option(svmEventDescriptorSchema)),
   }
 )
@@ -716,6 +752,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
       "programId": string,
       "definedTypes": JSON.t,
       "source": string,
+      "decodeErrors": option<Internal.svmDecodeErrors>,
     }>,
   }> = Dict.make()
   switch publicContractsConfig {
@@ -736,6 +773,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
               "programId": string,
               "definedTypes": JSON.t,
               "source": string,
+              "decodeErrors": option<Internal.svmDecodeErrors>,
             }>,
           }
         )
@@ -766,6 +804,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
     ~chainId: ChainId.t,
     ~addresses: array<string>,
     ~svmDefinedTypes: JSON.t=JSON.Null,
+    ~svmDecodeErrors: option<Internal.svmDecodeErrors>=?,
   ) => {
     switch events {
     | None => []
@@ -823,7 +862,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
           | Some(s) => s
           | None =>
             JsError.throwWithMessage(
-              `SVM instruction ${contractName}.${eventName} is missing the "svm" descriptor in internal config`,
+              `SVM program ${contractName}.${eventName} is missing the "svm" descriptor in internal config`,
             )
           }
           let accountFilters =
@@ -848,15 +887,39 @@ let fromPublic = (publicConfigJson: JSON.t) => {
             ~accounts=svm["accounts"]->Option.getOr([]),
             ~args=svm["args"]->Option.getOr(JSON.Null),
             ~definedTypes=svmDefinedTypes,
+            ~isEvent=kind == Some("svmEvent"),
+            ~isAccount=kind == Some("svmAccount"),
+            ~decodeErrors=?svmDecodeErrors,
           ) :> Internal.eventConfig)
         | _ =>
+          let kind: Internal.evmEventKind = switch kind {
+          | None | Some("log") => Log
+          | Some("call") => Call
+          | Some("transaction") => Transaction
+          | Some("create") => Create
+          | Some("deploy") => Deploy
+          | Some(other) =>
+            JsError.throwWithMessage(
+              `EVM event ${contractName}.${eventName} has an unknown kind "${other}" in internal config`,
+            )
+          }
           (EventConfigBuilder.buildEvmEventConfig(
             ~contractName,
             ~eventName,
             ~sighash,
             ~params,
+            ~kind,
             ~blockFields=?eventItem["blockFields"],
             ~transactionFields=?eventItem["transactionFields"],
+            ~transactionWhere=?eventItem["transactionWhere"]->Option.map(json =>
+              json->(Utils.magic: JSON.t => Internal.evmTransactionWhere)
+            ),
+            ~paramsWhere=?eventItem["paramsWhere"]->Option.map(json =>
+              json->(Utils.magic: JSON.t => array<Internal.evmParamWhere>)
+            ),
+            ~register=?eventItem["register"]->Option.map(json =>
+              json->(Utils.magic: JSON.t => Internal.evmEventRegister)
+            ),
             ~globalBlockFieldsSet,
             ~globalTransactionFieldsSet,
           ) :> Internal.eventConfig)
@@ -907,6 +970,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
             ->Option.getOr([])
           let addresses = rawAddresses->Array.map(parseAddress)
           let startBlock = chainContract->Option.flatMap(cc => cc["startBlock"])
+          let ttlBlocks = chainContract->Option.flatMap(cc => cc["ttlBlocks"])
 
           // Build event definitions from JSON (field selections resolved
           // inline). Handlers and per-chain `where` filters are layered on
@@ -920,6 +984,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
             ~svmDefinedTypes=contractData["svmAbi"]
             ->Option.map(a => a["definedTypes"])
             ->Option.getOr(JSON.Null),
+            ~svmDecodeErrors=?contractData["svmAbi"]->Option.flatMap(a => a["decodeErrors"]),
           )
 
           {
@@ -928,6 +993,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
             addresses,
             events,
             startBlock,
+            ttlBlocks,
           }
         })
 
@@ -968,6 +1034,8 @@ let fromPublic = (publicConfigJson: JSON.t) => {
             let queryTimeoutMillis = rpcConfig["queryTimeoutMillis"]
             let fallbackStallTimeout = rpcConfig["fallbackStallTimeout"]
             let pollingInterval = rpcConfig["pollingInterval"]
+            let maxBatchSize = rpcConfig["maxBatchSize"]
+            let hedgeAfterMillis = rpcConfig["hedgeAfterMillis"]
             let hasSyncConfig =
               initialBlockInterval->Option.isSome ||
               backoffMultiplicative->Option.isSome ||
@@ -976,7 +1044,9 @@ let fromPublic = (publicConfigJson: JSON.t) => {
               backoffMillis->Option.isSome ||
               queryTimeoutMillis->Option.isSome ||
               fallbackStallTimeout->Option.isSome ||
-              pollingInterval->Option.isSome
+              pollingInterval->Option.isSome ||
+              maxBatchSize->Option.isSome ||
+              hedgeAfterMillis->Option.isSome
             let syncConfig: option<sourceSyncOptions> = if hasSyncConfig {
               Some({
                 ?initialBlockInterval,
@@ -987,6 +1057,8 @@ let fromPublic = (publicConfigJson: JSON.t) => {
                 ?queryTimeoutMillis,
                 ?fallbackStallTimeout,
                 ?pollingInterval,
+                ?maxBatchSize,
+                ?hedgeAfterMillis,
               })
             } else {
               None
@@ -996,6 +1068,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
               sourceFor: parseRpcSourceFor(rpcConfig["for"]),
               syncConfig,
               ws: rpcConfig["ws"],
+              additionalUrls: rpcConfig["additionalUrls"],
               headers: rpcConfig["headers"],
             }
           })
@@ -1035,6 +1108,7 @@ let fromPublic = (publicConfigJson: JSON.t) => {
         | Ecosystem.Svm => publicChainConfig["maxReorgDepth"]->Option.getOr(200)
         | Ecosystem.Fuel => 0
         },
+        finality: ?publicChainConfig["finality"],
         blockLag: publicChainConfig["blockLag"]->Option.getOr(0),
         contracts,
         sourceConfig,
@@ -1284,6 +1358,14 @@ let rec canonicalJson = (json: JSON.t): JSON.t =>
   | _ => json
   }
 
+// What a chain's address snapshot is only valid for: the schema its addresses
+// were read out of and the config that declared their contracts, with the same
+// stripping as the resume-time compat check. Every indexer run from one
+// directory shares the snapshot path, so a file saved under another scope is
+// ignored rather than restored.
+let addressSnapshotScope = () =>
+  `${Env.Db.publicSchema}\n${getPublicConfigJson()->stripSensitiveData->canonicalJson->JSON.stringify}`
+
 // Returns dotted leaf paths (`a.b[i].c`) where `stored` differs from
 // `current`, restricted to the highest-priority top-level tier with any
 // diff. Tiers in order: version → name → storage → ecosystem
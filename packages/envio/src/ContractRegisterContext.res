@@ -1,21 +1,26 @@
-// The contractRegister handler context: context.chain.ContractName.add(address).
+// The contractRegister handler context: context.chain.ContractName.add(address)
+// and context.chain.ContractName.remove(address).
 // Independent of the in-memory store, so it stays off IndexerState and the
 // fetch-time contract registration in ChainState doesn't pull the state in.
 
 type contractRegisterParams = {
   item: Internal.item,
   onRegister: (~item: Internal.item, ~contractAddress: Address.t, ~contractName: string) => unit,
+  onDeregister: (~item: Internal.item, ~contractAddress: Address.t, ~contractName: string) => unit,
   config: Config.t,
   mutable isResolved: bool,
 }
 
-// Helper to create a validated add function for contract registration.
+// Helper to create a validated add or remove function, calling `apply` (one of
+// the params' callbacks) with the normalized address.
 // The isResolved check has to live inside the returned closure (not just in the
 // outer proxy trap) because users can capture `const add = context.chain.X.add`
 // before awaiting — a later call would otherwise bypass the resolved guard.
-let makeAddFunction = (~params: contractRegisterParams, ~contractName: string): (
-  Address.t => unit
-) => {
+let makeAddressFunction = (
+  ~params: contractRegisterParams,
+  ~contractName: string,
+  ~apply: (~item: Internal.item, ~contractAddress: Address.t, ~contractName: string) => unit,
+): (Address.t => unit) => {
   (contractAddress: Address.t) => {
     if params.isResolved {
       Utils.Error.make(`Impossible to access context.chain after the contract register is resolved. Make sure you didn't miss an await in the handler.`)->ErrorHandling.mkLogAndRaise(
@@ -25,11 +30,12 @@ let makeAddFunction = (~params: contractRegisterParams, ~contractName: string):
     // The value is passed from user-land, so validate and checksum/lowercase it.
     let validatedAddress = params.config->Config.normalizeUserAddress(contractAddress)
 
-    params.onRegister(~item=params.item, ~contractAddress=validatedAddress, ~contractName)
+    apply(~item=params.item, ~contractAddress=validatedAddress, ~contractName)
   }
 }
 
 // Chain proxy for contractRegister context: context.chain.ContractName.add(address)
+// and .remove(address)
 let contractRegisterChainTraps: Utils.Proxy.traps<contractRegisterParams> = {
   get: (~target as params, ~prop: unknown) => {
     let prop = prop->(Utils.magic: unknown => string)
@@ -45,8 +51,11 @@ let contractRegisterChainTraps: Utils.Proxy.traps<contractRegisterParams> = {
         ->ChainMap.values
         ->Array.some(chain => chain.contracts->Array.some(c => c.name === contractName))
       if isValidContract {
-        let addFn = makeAddFunction(~params, ~contractName)
-        {"add": addFn}->(Utils.magic: {"add": Address.t => unit} => unknown)
+        let addFn = makeAddressFunction(~params, ~contractName, ~apply=params.onRegister)
+        let removeFn = makeAddressFunction(~params, ~contractName, ~apply=params.onDeregister)
+        {"add": addFn, "remove": removeFn}->(
+          Utils.magic: {"add": Address.t => unit, "remove": Address.t => unit} => unknown
+        )
       } else {
         JsError.throwWithMessage(
           `Invalid contract name '${prop}' on context.chain. ${EntityFilter.codegenHelpMessage}`,
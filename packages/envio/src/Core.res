@@ -8,6 +8,7 @@ type evmHyperSyncClientCtor
 type evmRpcClientCtor
 type svmHyperSyncClientCtor
 type fuelHyperSyncClientCtor
+type eventItemStoreCtor
 type transactionStoreCtor
 type blockStoreCtor
 type addressStoreCtor
@@ -39,6 +40,8 @@ type addon = {
   svmHyperSyncClient: svmHyperSyncClientCtor,
   @as("FuelHyperSyncClient")
   fuelHyperSyncClient: fuelHyperSyncClientCtor,
+  @as("EventItemStore")
+  eventItemStore: eventItemStoreCtor,
   @as("TransactionStore")
   transactionStore: transactionStoreCtor,
   @as("BlockStore")
@@ -56,6 +59,12 @@ type addon = {
   evmBlockFieldNames: unit => array<string>,
   svmBlockFieldNames: unit => array<string>,
   fuelBlockFieldNames: unit => array<string>,
+  // The block an address snapshot file covers, read from its header alone.
+  // Null when the file was saved under another scope.
+  addressSnapshotBlock: (string, string) => Null.t<int>,
+  // The address count at which a contract's HyperSync query sends a bloom
+  // filter instead of listing its addresses.
+  addressFilterMinAddresses: unit => int,
 }
 
 @module("node:module") external createRequire: string => {..} = "createRequire"
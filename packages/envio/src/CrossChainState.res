@@ -82,7 +82,7 @@ let getSafeCheckpointId = (crossChainState: t) => {
     | Some(safeCheckpointTracking) =>
       let safeCheckpointId =
         safeCheckpointTracking->SafeCheckpointTracking.getSafeCheckpointId(
-          ~sourceBlockNumber=cs->ChainState.knownHeight,
+          ~safeBlockNumber=cs->ChainState.safeBlockNumber,
         )
       switch result.contents {
       | None => result := Some(safeCheckpointId)
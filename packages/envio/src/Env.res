@@ -4,6 +4,8 @@
 %%private(let envSafe = EnvSafe.make())
 
 let targetBufferSize = envSafe->EnvSafe.get("ENVIO_INDEXING_MAX_BUFFER_SIZE", S.option(S.int))
+// Contracts big enough for HyperSync's address bloom filter aren't capped: the
+// filter's size doesn't grow with the address count the way a list does.
 let maxAddrInPartition = envSafe->EnvSafe.get("MAX_PARTITION_SIZE", S.int, ~fallback=5_000)
 
 // Most parallel in-flight queries a single chain may have at once, across all
@@ -15,6 +17,8 @@ let maxChainConcurrency = 100
 // server-side spreads the contract across ceil(count / maxAddrInPartition)
 // partitions, each holding an in-flight query slot; capping a contract at half
 // the chain's concurrency budget stops one busy contract from monopolising them.
+// A contract sent as a bloom filter is one partition whatever its size, so the
+// threshold doesn't apply to it.
 let clientFilterAddressThreshold =
   envSafe->EnvSafe.get(
     "ENVIO_CLIENT_FILTER_ADDRESS_THRESHOLD",
@@ -28,6 +32,17 @@ let clientFilterAddressThreshold =
 let inMemoryObjectsTarget =
   envSafe->EnvSafe.get("ENVIO_IN_MEMORY_OBJECTS_TARGET", S.int, ~fallback=100_000)->Int.toFloat
 
+// Megabytes of fetched-but-unprocessed block and transaction data each chain's
+// store keeps in memory before paging the furthest-ahead rows out to
+// `.envio/spill`. Unset keeps everything in memory.
+let storeSpillBudgetMb = envSafe->EnvSafe.get("ENVIO_STORE_SPILL_BUDGET_MB", S.option(S.int))
+
+// Minimum seconds between saves of a chain's addresses to `.envio/addresses`,
+// which a restart restores instead of reading every address back from the
+// database. Unset disables the snapshots.
+let addressSnapshotIntervalSeconds =
+  envSafe->EnvSafe.get("ENVIO_ADDRESS_SNAPSHOT_INTERVAL_SECONDS", S.option(S.int))
+
 // FIXME: This broke HS grafana dashboard. Should investigate it later. Maybe we should use :: as a default value?
 // We want to be able to set it to 0.0.0.0
 // to allow to passthrough the port from a Docker container
@@ -77,6 +92,18 @@ let hypersyncClientEnableQueryCaching =
 let hypersyncLogLevel =
   envSafe->EnvSafe.get("ENVIO_HYPERSYNC_LOG_LEVEL", HyperSyncClient.logLevelSchema, ~fallback=#info)
 
+// Debug mode: the HyperSync clients write every response page they fetch as
+// Arrow IPC files under .envio/capture/<chainId>/, for analysis with DuckDB or
+// Polars and for reproducing decoding bugs offline.
+let captureResponses = envSafe->EnvSafe.get("ENVIO_CAPTURE_RESPONSES", S.bool, ~fallback=false)
+
+// Development only: SVM program-account items read their state over the chain's
+// rpc. HyperSync doesn't serve account data, and the RPC only answers with the
+// state at the time of the call, so a backfilled write would see today's data
+// and today's owner. Off, a chain indexing program accounts fails instead.
+let svmAccountStateFromRpc =
+  envSafe->EnvSafe.get("ENVIO_SVM_ACCOUNT_STATE_FROM_RPC", S.bool, ~fallback=false)
+
 let logStrategy =
   envSafe->EnvSafe.get(
     "LOG_STRATEGY",
@@ -94,6 +94,43 @@ type svmInstruction = {
   block?: svmBlock,
 }
 
+/** An Anchor event (`emit!` / `emit_cpi!`) decoded from the program's logs or
+ self-CPI instructions. */
+type svmEvent = {
+  programName: string,
+  eventName: string,
+  discriminator: string,
+  params: JSON.t,
+  programId?: SvmTypes.Pubkey.t,
+  data?: string,
+  path?: array<int>,
+  transaction?: svmTransaction,
+  block?: svmBlock,
+}
+
+/** An account of one of a program's Anchor account types, written by an
+ instruction of the program. `before`/`after` are the decoded states around the
+ write when the source has them; `after` is absent when the account's data
+ doesn't decode. */
+type svmAccountChange = {
+  programName: string,
+  accountName: string,
+  address: SvmTypes.Pubkey.t,
+  discriminator: string,
+  before?: JSON.t,
+  after?: JSON.t,
+  programId?: SvmTypes.Pubkey.t,
+  path?: array<int>,
+  transaction?: svmTransaction,
+  block?: svmBlock,
+}
+
+/** Arguments passed to handlers registered via `indexer.onAccount`. */
+type svmOnAccountArgs<'context> = {
+  account: svmAccountChange,
+  context: 'context,
+}
+
 /** Arguments passed to handlers registered via `indexer.onInstruction`. */
 type svmOnInstructionArgs<'context> = {
   instruction: svmInstruction,
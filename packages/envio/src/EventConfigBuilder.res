@@ -440,15 +440,30 @@ let buildEvmEventConfig = (
   ~eventName: string,
   ~sighash: string,
   ~params: array<paramMeta>,
+  ~kind: Internal.evmEventKind=Log,
   ~blockFields: option<array<Internal.evmBlockField>>=?,
   ~transactionFields: option<array<Internal.evmTransactionField>>=?,
+  ~transactionWhere: option<Internal.evmTransactionWhere>=?,
+  ~paramsWhere: option<array<Internal.evmParamWhere>>=?,
+  ~register: option<Internal.evmEventRegister>=?,
   ~globalBlockFieldsSet: Utils.Set.t<Internal.evmBlockField>=Utils.Set.make(),
   ~globalTransactionFieldsSet: Utils.Set.t<Internal.evmTransactionField>=Utils.Set.make(),
 ): Internal.evmEventConfig => {
-  let topicCount = params->Array.reduce(1, (acc, p) => p.indexed ? acc + 1 : acc)
+  // Calls and transactions have no topics; each gets its own id key space so
+  // a selector can't collide with an event's topic0 or with each other.
+  let (topicCount, id) = switch kind {
+  | Log =>
+    let topicCount = params->Array.reduce(1, (acc, p) => p.indexed ? acc + 1 : acc)
+    (topicCount, sighash ++ "_" ++ topicCount->Int.toString)
+  | Call => (0, sighash ++ "_call")
+  | Transaction => (0, sighash ++ "_transaction")
+  // Creations have no selector; the event name (`create(Pair)`) keys them.
+  | Create => (0, eventName ++ "_create")
+  | Deploy => (0, eventName ++ "_deploy")
+  }
 
   {
-    id: sighash ++ "_" ++ topicCount->Int.toString,
+    id,
     name: eventName,
     contractName,
     paramsRawEventSchema: buildParamsSchema(params),
@@ -462,6 +477,10 @@ let buildEvmEventConfig = (
     sighash,
     topicCount,
     paramsMetadata: params,
+    kind,
+    ?transactionWhere,
+    ?paramsWhere,
+    ?register,
   }
 }
 
@@ -646,6 +665,9 @@ let buildSvmInstructionEventConfig = (
   ~accounts: array<string>=[],
   ~args: JSON.t=JSON.Null,
   ~definedTypes: JSON.t=JSON.Null,
+  ~isEvent=false,
+  ~isAccount=false,
+  ~decodeErrors: option<Internal.svmDecodeErrors>=?,
 ): Internal.svmInstructionEventConfig => {
   let paramsSchema =
     S.json(~validate=false)
@@ -659,9 +681,14 @@ let buildSvmInstructionEventConfig = (
     ~transactionMaskFn=Svm.eventTransactionFieldMask,
   )
   {
-    id: switch discriminator {
-    | Some(d) => d
-    | None => "none"
+    // Events, accounts and instructions share a program; prefix so an event
+    // or account can't collide with an instruction that happens to use the
+    // same bytes.
+    id: switch (isEvent, isAccount, discriminator) {
+    | (true, _, Some(d)) => `event:${d}`
+    | (_, true, Some(d)) => `account:${d}`
+    | (_, _, Some(d)) => d
+    | (_, _, None) => "none"
     },
     name: instructionName,
     contractName,
@@ -676,6 +703,9 @@ let buildSvmInstructionEventConfig = (
     accounts,
     args,
     definedTypes,
+    isEvent,
+    isAccount,
+    ?decodeErrors,
   }
 }
 
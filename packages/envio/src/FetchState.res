@@ -236,6 +236,11 @@ module OptimizedPartitions = {
     // by the single address-free partition, and new dynamic addresses get a
     // bounded backfill up to its frontier instead of a standing partition.
     clientFilteredContracts: Utils.Set.t<string>,
+    // Contract names big enough that the source sends their addresses as one
+    // bloom filter rather than a list. Sticky like clientFilteredContracts.
+    // Their partitions aren't capped at maxAddrInPartition: the cap bounds a
+    // query's address list, and a filter grows by bytes, not by entries.
+    bloomFilteredContracts: Utils.Set.t<string>,
   }
 
   @inline
@@ -249,6 +254,11 @@ module OptimizedPartitions = {
     }
   }
 
+  // The most addresses of `contractName` one partition may hold.
+  @inline
+  let maxAddrFor = (~maxAddrInPartition, ~bloomFilteredContracts, contractName) =>
+    bloomFilteredContracts->Utils.Set.has(contractName) ? Int.Constants.maxValue : maxAddrInPartition
+
   // Merges two partitions at a given potentialMergeBlock.
   // Returns array<partition> where the last element is the continuing partition
   // and all preceding elements are completed (have mergeBlock set).
@@ -397,6 +407,7 @@ module OptimizedPartitions = {
     ~nextPartitionIndex: int,
     ~dynamicContracts: Utils.Set.t<string>,
     ~clientFilteredContracts: Utils.Set.t<string>,
+    ~bloomFilteredContracts: Utils.Set.t<string>,
   ) => {
     let newPartitions = []
     let mergingPartitions = Dict.make()
@@ -449,6 +460,11 @@ module OptimizedPartitions = {
         newPartitions->Array.push(p)->ignore
       | {dynamicContract: Some(contractName)} =>
         let pAddressesCount = p.addresses->AddressSet.countFor(contractName)
+        let maxAddrInPartition = maxAddrFor(
+          ~maxAddrInPartition,
+          ~bloomFilteredContracts,
+          contractName,
+        )
         // Compute merge block: last pending query's toBlock, or lfb if idle
         let potentialMergeBlock = switch p.mutPendingQueries->Utils.Array.last {
         | Some({isChunk: true, toBlock: Some(toBlock)}) => Some(toBlock)
@@ -527,7 +543,11 @@ module OptimizedPartitions = {
             ~p2=currentP,
             ~potentialMergeBlock=nextPMergeBlock,
             ~contractName,
-            ~maxAddrInPartition,
+            ~maxAddrInPartition=maxAddrFor(
+              ~maxAddrInPartition,
+              ~bloomFilteredContracts,
+              contractName,
+            ),
             ~nextPartitionIndexRef,
           )
           for i in 0 to result->Array.length - 2 {
@@ -594,6 +614,7 @@ module OptimizedPartitions = {
       nextPartitionIndex: nextPartitionIndexRef.contents,
       dynamicContracts,
       clientFilteredContracts,
+      bloomFilteredContracts,
     }
   }
 
@@ -735,6 +756,32 @@ module OptimizedPartitions = {
       ~initialLatestFetchedBlock=p.latestFetchedBlock,
     )
 
+    // An address that ended at or below the new frontier only costs query
+    // bandwidth from here on, so it's dropped. A partition left with none
+    // retires like a merged one, once its in-flight queries land. A rollback
+    // below an end brings the address back (see `rollback`).
+    let firstEndBlock = p.selection.dependsOnAddresses
+      ? p.addresses->AddressSet.firstEndBlock->Null.toOption
+      : None
+    let p = switch firstEndBlock {
+    | Some(firstEndBlock) if firstEndBlock <= updatedLatestFetchedBlock =>
+      let liveAddresses = p.addresses->AddressSet.filterEndedBefore(updatedLatestFetchedBlock + 1)
+      if liveAddresses->AddressSet.isEmpty {
+        {
+          ...p,
+          mergeBlock: Some(
+            switch p.mergeBlock {
+            | Some(mergeBlock) => Pervasives.min(mergeBlock, updatedLatestFetchedBlock)
+            | None => updatedLatestFetchedBlock
+            },
+          ),
+        }
+      } else {
+        p->withAddresses(liveAddresses)
+      }
+    | _ => p
+    }
+
     // Check if partition reached its mergeBlock and should be removed. A
     // retired partition can hold several queries at once, so it goes only when
     // the last of them has landed.
@@ -755,6 +802,7 @@ module OptimizedPartitions = {
         ~nextPartitionIndex=optimizedPartitions.nextPartitionIndex,
         ~dynamicContracts=optimizedPartitions.dynamicContracts,
         ~clientFilteredContracts=optimizedPartitions.clientFilteredContracts,
+        ~bloomFilteredContracts=optimizedPartitions.bloomFilteredContracts,
       )
     } else {
       let updatedMainPartition = {
@@ -803,6 +851,7 @@ module OptimizedPartitions = {
           ~nextPartitionIndex=optimizedPartitions.nextPartitionIndex,
           ~dynamicContracts=optimizedPartitions.dynamicContracts,
           ~clientFilteredContracts=optimizedPartitions.clientFilteredContracts,
+          ~bloomFilteredContracts=optimizedPartitions.bloomFilteredContracts,
         )
       }
     }
@@ -845,6 +894,11 @@ type t = {
   // switched to client-side filtering. None disables the switch, leaving every
   // contract filtered server-side.
   clientFilterAddressThreshold: option<int>,
+  // Per-contract registered-address count at which the source sends a
+  // contract's addresses as a bloom filter. Such a contract skips both the
+  // partition cap and the client-filter switch. None where the source has no
+  // filter to send.
+  bloomFilterMinAddresses: option<int>,
 }
 
 @inline
@@ -872,10 +926,15 @@ let bufferBlock = ({optimizedPartitions, latestOnBlockBlockNumber}: t) => {
   }
 }
 
+// Items a source keeps in Rust sit in the chain's item store rather than in
+// `buffer` (see ChainState's `eventItemStore`). The buffer reads below take that
+// store as `~itemStore` and count its items in; without it they see `buffer`
+// alone.
+
 // Number of buffered items at or below the ready frontier (processable now,
 // i.e. not stuck behind a gap from a lagging partition or out-of-order chunk).
 // The buffer is kept sorted, so binary-search the frontier in O(log n).
-let bufferReadyCount = (fetchState: t) => {
+let bufferReadyCount = (fetchState: t, ~itemStore=?) => {
   let frontier = fetchState->bufferBlockNumber
   let buffer = fetchState.buffer
   let lo = ref(0)
@@ -888,7 +947,22 @@ let bufferReadyCount = (fetchState: t) => {
       hi := mid
     }
   }
-  lo.contents
+  switch itemStore {
+  | Some(store) => lo.contents + store->EventItemStore.readyCount(~upToBlock=frontier)
+  | None => lo.contents
+  }
+}
+
+// Block of the first buffered item, in `buffer` or the item store.
+let firstItemBlockNumber = ({buffer}: t, ~itemStore=?, ~fromItem=0) => {
+  let inBuffer = buffer->Array.get(fromItem)->Option.map(Internal.getItemBlockNumber)
+  let inStore =
+    itemStore->Option.flatMap(store => store->EventItemStore.firstBlockNumber->Null.toOption)
+  switch (inBuffer, inStore) {
+  | (Some(a), Some(b)) => Some(Pervasives.min(a, b))
+  | (Some(_) as first, None) | (None, Some(_) as first) => first
+  | (None, None) => None
+  }
 }
 
 /*
@@ -931,7 +1005,8 @@ let comparePath = (a: array<int>, b: array<int>): int => {
 // comparison that every merge step runs stays small enough for V8 to inline.
 let compareTiebreak = (a: Internal.item, b: Internal.item): int => {
   // Two items an ecosystem's scalar key can't separate: instructions of one
-  // Solana transaction, ordered by their position in its CPI tree.
+  // Solana transaction, ordered by their position in its CPI tree, or EVM
+  // calls of one transaction, ordered by their trace address.
   let byPath = switch (a->Internal.getItemOrderPath, b->Internal.getItemOrderPath) {
   | (Value(pa), Value(pb)) => comparePath(pa, pb)
   | _ => 0
@@ -1160,6 +1235,7 @@ let updateInternal = (
     },
     firstEventBlock: fetchState.firstEventBlock,
     clientFilterAddressThreshold: fetchState.clientFilterAddressThreshold,
+    bloomFilterMinAddresses: fetchState.bloomFilterMinAddresses,
   }
 
   updatedFetchState
@@ -1526,6 +1602,7 @@ let createPartitions = (
   ~addressStore: AddressStore.t,
   ~dynamicContracts: Utils.Set.t<string>,
   ~clientFilteredContracts: Utils.Set.t<string>,
+  ~bloomFilteredContracts: Utils.Set.t<string>,
   ~normalSelection: selection,
   ~maxAddrInPartition: int,
   ~nextPartitionIndex: int,
@@ -1560,6 +1637,11 @@ OptimizedPartitions.t => {
     // A set is ordered by effectiveStartBlock, so its start-block groups are
     // ascending and each group's addresses are a contiguous slice.
     let groups = contractSet->AddressSet.startBlockGroups
+    let contractMaxAddr = OptimizedPartitions.maxAddrFor(
+      ~maxAddrInPartition,
+      ~bloomFilteredContracts,
+      contractName,
+    )
 
     if clientFilteredContracts->Utils.Set.has(contractName) && !isAnchored {
       // The contract is switching to client-side filtering in this very call, so
@@ -1599,7 +1681,7 @@ OptimizedPartitions.t => {
         let remainingRef = ref(countRef.contents)
         let chunkOffsetRef = ref(offsetRef.contents)
         while remainingRef.contents > 0 {
-          let take = Pervasives.min(remainingRef.contents, maxAddrInPartition)
+          let take = Pervasives.min(remainingRef.contents, contractMaxAddr)
           let pAddresses =
             contractSet->AddressSet.slice(~offset=chunkOffsetRef.contents, ~limit=Some(take))
           partitions->Array.push({
@@ -1695,6 +1777,7 @@ OptimizedPartitions.t => {
     ~nextPartitionIndex=nextPartitionIndexRef.contents,
     ~dynamicContracts,
     ~clientFilteredContracts,
+    ~bloomFilteredContracts,
   )
 }
 
@@ -1708,6 +1791,9 @@ let registerDynamicContracts = (
   // height in here. Defaults to what the fetch state knows, which is all
   // there is to go on when no response is being applied.
   ~claimCeiling=fetchState.knownHeight,
+  // Registrations the source made while routing the response (factory
+  // creations, events' `register`), applied after the handlers' ones.
+  ~registrationPage: option<AddressStore.registrationPage>=?,
   // Registrations collected from contractRegister calls, in the order the
   // handlers made them. May contain duplicates, which the store filters out.
   registrations: array<AddressStore.registration>,
@@ -1729,11 +1815,10 @@ let registerDynamicContracts = (
   let verdicts = addressStore->AddressStore.registerBatch(registrations)
 
   let registeringContractNames = []
-  for idx in 0 to verdicts->Array.length - 1 {
-    let registration = registrations->Array.getUnsafe(idx)
-    let verdict = verdicts->Array.getUnsafe(idx)
+  let applyVerdict = (verdict: AddressStore.verdict, ~getRegistration) =>
     switch verdict {
     | Added({fetchable: true}) =>
+      let registration: AddressStore.registration = getRegistration()
       if !(registeringContractNames->Array.includes(registration.contractName)) {
         registeringContractNames->Array.push(registration.contractName)->ignore
       }
@@ -1742,12 +1827,29 @@ let registerDynamicContracts = (
     // config that later adds address-dependent events picks it up on restart.
     | Added({fetchable: false}) => ()
     | Conflict(_) | Duplicate(_) | Invalid =>
+      let registration: AddressStore.registration = getRegistration()
       verdict->warnRejectedRegistration(
         ~chainId=fetchState.chainId,
         ~contractAddress=registration.address,
         ~contractName=registration.contractName,
       )
     }
+  for idx in 0 to verdicts->Array.length - 1 {
+    verdicts
+    ->Array.getUnsafe(idx)
+    ->applyVerdict(~getRegistration=() => registrations->Array.getUnsafe(idx))
+  }
+  switch registrationPage {
+  | Some(page) =>
+    // The page's registrations stay on the Rust side; only the ones a verdict
+    // needs are read back.
+    let pageVerdicts = addressStore->AddressStore.registerPage(page)
+    for idx in 0 to pageVerdicts->Array.length - 1 {
+      pageVerdicts
+      ->Array.getUnsafe(idx)
+      ->applyVerdict(~getRegistration=() => page->AddressStore.pageGet(idx))
+    }
+  | None => ()
   }
 
   switch registeringContractNames {
@@ -1835,6 +1937,26 @@ let registerDynamicContracts = (
         }
       }
 
+      // A dynamic contract that has just grown big enough for a bloom filter
+      // stops being capped, and so never needs the client-side switch below.
+      // Cloned before mutating for the same reason as clientFilteredContracts.
+      let bloomFilteredContracts = switch fetchState.bloomFilterMinAddresses {
+      | Some(minAddresses) =>
+        let bloomFilteredContracts =
+          fetchState.optimizedPartitions.bloomFilteredContracts
+          ->Utils.Set.toArray
+          ->Utils.Set.fromArray
+        dynamicContractsRef.contents
+        ->Utils.Set.toArray
+        ->Array.forEach(contractName => {
+          if addressStore->AddressStore.contractCount(contractName) >= minAddresses {
+            bloomFilteredContracts->Utils.Set.add(contractName)->ignore
+          }
+        })
+        bloomFilteredContracts
+      | None => fetchState.optimizedPartitions.bloomFilteredContracts
+      }
+
       // Switch any dynamic contract that has just crossed the server-side
       // address threshold to client-side filtering. Sticky: the set only grows, and
       // collapse in createPartitions folds the contract's
@@ -1851,7 +1973,11 @@ let registerDynamicContracts = (
         ->Utils.Set.toArray
         ->Array.forEach(contractName => {
           let addressCount = addressStore->AddressStore.contractCount(contractName)
-          if !(clientFilteredContracts->Utils.Set.has(contractName)) && addressCount > threshold {
+          if (
+            !(clientFilteredContracts->Utils.Set.has(contractName)) &&
+            !(bloomFilteredContracts->Utils.Set.has(contractName)) &&
+            addressCount > threshold
+          ) {
             clientFilteredContracts->addClientFilteredContract(
               ~contractName,
               ~chainId=fetchState.chainId,
@@ -1879,6 +2005,7 @@ let registerDynamicContracts = (
         ~addressStore,
         ~dynamicContracts=dynamicContractsRef.contents,
         ~clientFilteredContracts,
+        ~bloomFilteredContracts,
         ~normalSelection=fetchState.normalSelection,
         ~maxAddrInPartition=fetchState.optimizedPartitions.maxAddrInPartition,
         ~nextPartitionIndex=fetchState.optimizedPartitions.nextPartitionIndex +
@@ -1893,18 +2020,91 @@ let registerDynamicContracts = (
   }
 }
 
+// Ends the addresses contractRegister handlers removed, each after the block of
+// the event that removed it. The sources' gates drop an ended address's items
+// from then on, but the buffer already holds what was routed before the end
+// existed, so those items are pruned here. Partitions go on querying an ended
+// address until they've fetched past its end; `OptimizedPartitions` drops it
+// then.
+let deregisterDynamicContracts = (
+  fetchState: t,
+  ~addressStore: AddressStore.t,
+  deregistrations: array<AddressStore.deregistration>,
+) => {
+  let verdicts = addressStore->AddressStore.deregisterBatch(deregistrations)
+
+  let minEndBlockRef = ref(None)
+  for idx in 0 to verdicts->Array.length - 1 {
+    let deregistration = deregistrations->Array.getUnsafe(idx)
+    switch verdicts->Array.getUnsafe(idx) {
+    | Ended =>
+      minEndBlockRef :=
+        Some(
+          switch minEndBlockRef.contents {
+          | Some(minEndBlock) => Pervasives.min(minEndBlock, deregistration.block)
+          | None => deregistration.block
+          },
+        )
+    | AlreadyEnded(_) => ()
+    | NotRegistered =>
+      warnAddressRegistration(
+        ~chainId=fetchState.chainId,
+        ~contractAddress=deregistration.address,
+        ~params={"contractName": deregistration.contractName},
+        `Skipping contract deregistration: Contract address isn't registered for this contract.`,
+      )
+    }
+  }
+
+  switch minEndBlockRef.contents {
+  | None => fetchState
+  | Some(minEndBlock) =>
+    fetchState->updateInternal(
+      // Filtering the sorted buffer keeps it sorted and deduped.
+      ~mutItemsSorted=true,
+      ~mutItems=fetchState.buffer->Array.filter(item =>
+        switch item {
+        | Event({blockNumber, onEventRegistration: {isWildcard: false} as onEventRegistration, payload})
+          if blockNumber > minEndBlock =>
+          switch payload
+          ->Internal.getPayloadSrcAddress
+          ->(Utils.magic: Address.t => Nullable.t<Address.t>)
+          ->Nullable.toOption {
+          | Some(srcAddress) =>
+            addressStore->AddressStore.isIndexedAt(
+              srcAddress,
+              onEventRegistration.eventConfig.contractName,
+              blockNumber,
+            )
+          | None => true
+          }
+        | _ => true
+        }
+      ),
+    )
+  }
+}
+
 /*
 Updates fetchState with a response for a given query.
 Throws if the partition with given query cannot be found (unexpected)
 
 newItems are ordered earliest to latest (as they are returned from the worker)
 */
-let handleQueryResult = (fetchState: t, ~query: query, ~latestFetchedBlock: int, ~newItems): t => {
+let handleQueryResult = (
+  fetchState: t,
+  ~query: query,
+  ~latestFetchedBlock: int,
+  ~newItems,
+  // Items of the response the caller merged into the chain's item store
+  // instead of passing them as `newItems`.
+  ~storedItemsCount=0,
+): t => {
   fetchState->updateInternal(
     ~optimizedPartitions=fetchState.optimizedPartitions->OptimizedPartitions.handleQueryResponse(
       ~query,
       ~knownHeight=fetchState.knownHeight,
-      ~itemsCount=newItems->Array.length,
+      ~itemsCount=newItems->Array.length + storedItemsCount,
       ~latestFetchedBlock,
     ),
     // Merge the response into the sorted buffer, dropping duplicates an
@@ -1920,6 +2120,17 @@ let handleQueryResult = (fetchState: t, ~query: query, ~latestFetchedBlock: int,
   )
 }
 
+// Merges items taken out of the chain's item store into the buffer.
+let mergeItems = (fetchState: t, items) =>
+  switch items {
+  | [] => fetchState
+  | _ =>
+    fetchState->updateInternal(
+      ~mutItemsSorted=true,
+      ~mutItems=fetchState.buffer->mergeIntoBuffer(items),
+    )
+  }
+
 type nextQuery =
   | WaitingForNewBlock
   | NothingToQuery
@@ -2589,9 +2800,9 @@ let getNextQuery = (
   }
 }
 
-let hasReadyItem = ({buffer} as fetchState: t) => {
-  switch buffer->Array.get(0) {
-  | Some(item) => item->Internal.getItemBlockNumber <= fetchState->bufferBlockNumber
+let hasReadyItem = (fetchState: t, ~itemStore=?) => {
+  switch fetchState->firstItemBlockNumber(~itemStore?) {
+  | Some(blockNumber) => blockNumber <= fetchState->bufferBlockNumber
   | None => false
   }
 }
@@ -2628,6 +2839,9 @@ let make = (
   ~onEventRegistrations: array<Internal.onEventRegistration>,
   ~addressStore: AddressStore.t,
   ~addresses: array<Internal.indexingAddress>,
+  // Contracts whose addresses were restored into the store from a snapshot
+  // rather than passed in `addresses`.
+  ~restoredContracts: array<AddressStore.restoredContract>=[],
   ~maxAddrInPartition,
   ~chainId: ChainId.t,
   ~maxOnBlockBufferSize,
@@ -2637,6 +2851,7 @@ let make = (
   ~blockLag=0,
   ~firstEventBlock=None,
   ~clientFilterAddressThreshold=None,
+  ~bloomFilterMinAddresses=None,
   ~isResumed=false,
 ): t => {
   let latestFetchedBlock = progressBlockNumber
@@ -2691,6 +2906,7 @@ let make = (
       address: contract.address,
       contractName: contract.contractName,
       registrationBlock: contract.registrationBlock,
+      endBlock: ?contract.endBlock,
     }),
   )
   // Verdicts are in the batch's order, so they line up with `addresses`. A
@@ -2707,6 +2923,7 @@ let make = (
 
   let dynamicContracts = Utils.Set.make()
   let clientFilteredContracts = Utils.Set.make()
+  let bloomFilteredContracts = Utils.Set.make()
   let registeringSetsByContract = Dict.make()
 
   addresses->Array.forEach(contract => {
@@ -2729,6 +2946,32 @@ let make = (
     }
   })
 
+  restoredContracts->Array.forEach(({contractName, dynamicCount}) => {
+    if contractNamesWithNormalEvents->Utils.Set.has(contractName) {
+      if !(registeringSetsByContract->Dict.has(contractName)) {
+        registeringSetsByContract->Dict.set(
+          contractName,
+          addressStore->AddressStore.makeSet(~contractName),
+        )
+      }
+      if dynamicCount > 0 {
+        dynamicContracts->Utils.Set.add(contractName)->ignore
+      }
+    }
+  })
+
+  // A contract big enough to be sent as a bloom filter needs neither chunking
+  // nor the client-side switch below.
+  switch bloomFilterMinAddresses {
+  | Some(minAddresses) =>
+    registeringSetsByContract->Utils.Dict.forEachWithKey((set, contractName) => {
+      if set->AddressSet.size >= minAddresses {
+        bloomFilteredContracts->Utils.Set.add(contractName)->ignore
+      }
+    })
+  | None => ()
+  }
+
   // Switch any contract already over the server-side address threshold to
   // client-side filtering at creation — a config contract with a large static
   // address list, or a dynamic contract restored from a large persisted set.
@@ -2736,7 +2979,7 @@ let make = (
   | Some(threshold) =>
     registeringSetsByContract->Utils.Dict.forEachWithKey((set, contractName) => {
       let addressCount = set->AddressSet.size
-      if addressCount > threshold {
+      if addressCount > threshold && !(bloomFilteredContracts->Utils.Set.has(contractName)) {
         clientFilteredContracts->addClientFilteredContract(
           ~contractName,
           ~chainId,
@@ -2754,6 +2997,7 @@ let make = (
     ~addressStore,
     ~dynamicContracts,
     ~clientFilteredContracts,
+    ~bloomFilteredContracts,
     ~normalSelection,
     ~maxAddrInPartition,
     ~nextPartitionIndex=partitions->Array.length,
@@ -2812,12 +3056,17 @@ let make = (
     buffer,
     firstEventBlock,
     clientFilterAddressThreshold,
+    bloomFilterMinAddresses,
   }
 
   fetchState
 }
 
-let bufferSize = ({buffer}: t) => buffer->Array.length
+let bufferSize = ({buffer}: t, ~itemStore=?) =>
+  switch itemStore {
+  | Some(store) => buffer->Array.length + store->EventItemStore.len
+  | None => buffer->Array.length
+  }
 
 let partitionsCount = ({optimizedPartitions}: t) => optimizedPartitions->OptimizedPartitions.count
 
@@ -2855,7 +3104,9 @@ Always recreates optimized partitions to avoid duplicate addresses:
 let rollback = (fetchState: t, ~addressStore: AddressStore.t, ~targetBlockNumber) => {
   // Step 1: Prune addresses registered after the target block. The pruned store
   // is then the source of truth for partition cleanup below — an address
-  // survives iff `filterByRegistrationBlock` keeps it.
+  // survives iff `filterByRegistrationBlock` keeps it. The addresses whose end
+  // the rollback undoes are taken first, while their ends still say so.
+  let endingAfterTarget = addressStore->AddressStore.makeSetEndingAfter(targetBlockNumber)
   addressStore->AddressStore.rollback(targetBlockNumber)->ignore
 
   // Step 2: Categorize partitions
@@ -2935,12 +3186,24 @@ let rollback = (fetchState: t, ~addressStore: AddressStore.t, ~targetBlockNumber
     }
   }
 
+  // Addresses ending after the target may have been dropped from a partition
+  // that had fetched past their end, or retired with it. The kept partitions
+  // still hold theirs; the rest are fetched again from the target.
+  if !(endingAfterTarget->AddressSet.isEmpty) {
+    collectForRecreation(
+      keptPartitions->Array.reduce(endingAfterTarget, (remaining, p: partition) =>
+        p.selection.dependsOnAddresses ? remaining->AddressSet.exclude(p.addresses) : remaining
+      ),
+    )
+  }
+
   // Step 3: Recreate partitions from deleted partition addresses
   let optimizedPartitions = createPartitions(
     ~registeringSetsByContract,
     ~addressStore,
     ~dynamicContracts=fetchState.optimizedPartitions.dynamicContracts,
     ~clientFilteredContracts=fetchState.optimizedPartitions.clientFilteredContracts,
+    ~bloomFilteredContracts=fetchState.optimizedPartitions.bloomFilteredContracts,
     ~normalSelection=fetchState.normalSelection,
     ~maxAddrInPartition=fetchState.optimizedPartitions.maxAddrInPartition,
     ~nextPartitionIndex=nextKeptIdRef.contents,
@@ -3001,12 +3264,12 @@ let resetPendingQueries = (fetchState: t) => {
 * Returns a boolean indicating whether the fetch state is actively indexing
 * used for comparing event queues in the chain manager
 */
-let isActivelyIndexing = ({endBlock} as fetchState: t) => {
+let isActivelyIndexing = ({endBlock} as fetchState: t, ~itemStore=?) => {
   switch endBlock {
   | Some(endBlock) =>
     let isPastEndblock = fetchState->bufferBlockNumber >= endBlock
     if isPastEndblock {
-      fetchState->bufferSize > 0
+      fetchState->bufferSize(~itemStore?) > 0
     } else {
       true
     }
@@ -3036,6 +3299,7 @@ let isFetchingAtHead = ({endBlock, blockLag, knownHeight} as fetchState: t) => {
 // blockLag) and must not defer entry, or a many-partition chain never enters.
 let isReadyToEnterReorgThreshold = (
   ~tolerance,
+  ~itemStore=?,
   {endBlock, blockLag, knownHeight} as fetchState: t,
 ) => {
   let bufferBlockNumber = fetchState->bufferBlockNumber
@@ -3045,14 +3309,14 @@ let isReadyToEnterReorgThreshold = (
   | Some(endBlock) if endBlock <= laggedHead => bufferBlockNumber >= endBlock
   | _ => bufferBlockNumber >= laggedHead - tolerance
   } &&
-  fetchState->bufferReadyCount == 0
+  fetchState->bufferReadyCount(~itemStore?) == 0
 }
 
 // Lower progress percentage = further behind = higher priority. Progress is
 // relative to the head this chain can actually fetch, so a chain at its lagged
 // head does not look behind relative to unavailable blocks. Shared by the batch
 // ordering and the cross-chain fetch priority.
-let getProgressPercentage = (fetchState: t) => {
+let getProgressPercentage = (fetchState: t, ~itemStore=?) => {
   switch fetchState.firstEventBlock {
   | None => 0.
   | Some(firstEventBlock) =>
@@ -3060,8 +3324,8 @@ let getProgressPercentage = (fetchState: t) => {
     if totalRange <= 0 {
       0.
     } else {
-      let progress = switch fetchState.buffer->Array.get(0) {
-      | Some(item) => item->Internal.getItemBlockNumber - firstEventBlock
+      let progress = switch fetchState->firstItemBlockNumber(~itemStore?) {
+      | Some(blockNumber) => blockNumber - firstEventBlock
       | None => fetchState->bufferBlockNumber - firstEventBlock
       }
       progress->Int.toFloat /. totalRange->Int.toFloat
@@ -3070,21 +3334,27 @@ let getProgressPercentage = (fetchState: t) => {
 }
 
 let sortForBatch = {
-  let hasFullBatch = ({buffer} as fetchState: t, ~batchSizeTarget) => {
-    switch buffer->Array.get(batchSizeTarget - 1) {
-    | Some(item) => item->Internal.getItemBlockNumber <= fetchState->bufferBlockNumber
-    | None => false
-    }
-  }
-
-  (fetchStates: array<t>, ~batchSizeTarget: int) => {
+  let hasFullBatch = (fetchState: t, ~batchSizeTarget, ~itemStore=?) =>
+    fetchState->bufferReadyCount(~itemStore?) >= batchSizeTarget
+
+  (
+    fetchStates: array<t>,
+    ~batchSizeTarget: int,
+    // Each chain's item store, by chain id.
+    ~itemStores: dict<EventItemStore.t>=Dict.make(),
+  ) => {
+    let itemStoreOf = (fetchState: t) =>
+      itemStores->ChainId.Dict.dangerouslyGetNonOption(fetchState.chainId)
     let copied = fetchStates->Array.copy
     copied->Array.sort((a: t, b: t) => {
-      switch (a->hasFullBatch(~batchSizeTarget), b->hasFullBatch(~batchSizeTarget)) {
+      switch (
+        a->hasFullBatch(~batchSizeTarget, ~itemStore=?a->itemStoreOf),
+        b->hasFullBatch(~batchSizeTarget, ~itemStore=?b->itemStoreOf),
+      ) {
       | (true, true)
       | (false, false) => {
-          let aProgress = a->getProgressPercentage
-          let bProgress = b->getProgressPercentage
+          let aProgress = a->getProgressPercentage(~itemStore=?a->itemStoreOf)
+          let bProgress = b->getProgressPercentage(~itemStore=?b->itemStoreOf)
           if aProgress < bProgress {
             Ordering.less
           } else if aProgress > bProgress {
@@ -3101,11 +3371,12 @@ let sortForBatch = {
   }
 }
 
-let getProgressBlockNumberAt = ({buffer} as fetchState: t, ~index) => {
+// `index` counts `buffer` items only; the item store's taken items are already
+// gone from it.
+let getProgressBlockNumberAt = (fetchState: t, ~index, ~itemStore=?) => {
   let bufferBlockNumber = fetchState->bufferBlockNumber
-  switch buffer->Array.get(index) {
-  | Some(item) if bufferBlockNumber >= item->Internal.getItemBlockNumber =>
-    item->Internal.getItemBlockNumber - 1
+  switch fetchState->firstItemBlockNumber(~itemStore?, ~fromItem=index) {
+  | Some(blockNumber) if bufferBlockNumber >= blockNumber => blockNumber - 1
   | _ => bufferBlockNumber
   }
 }
@@ -538,7 +538,9 @@ let finishRegistration = (~config: Config.t): registrationsByChainId => {
 
         // Events with no handler/contractRegister aren't fetched or dispatched
         // unless raw events are enabled, in which case a bare registration is
-        // added to fetch them. Otherwise they're reported once below. Keyed on
+        // added to fetch them. An event with a declarative `register` (a
+        // factory's creations) always gets one — registering is what it's
+        // fetched for. Otherwise they're reported once below. Keyed on
         // the resolved registrations (before the where-empty drop) so a
         // `where: false` event still counts as registered — its handler opted
         // out of this chain, so it gets no raw-event registration either.
@@ -553,7 +555,11 @@ let finishRegistration = (~config: Config.t): registrationsByChainId => {
                   )
                 )
               ) {
-                if config.enableRawEvents {
+                let registersContracts =
+                  (
+                    eventConfig->(Utils.magic: Internal.eventConfig => Internal.evmEventConfig)
+                  ).register !== None
+                if config.enableRawEvents || registersContracts {
                   rawEventRegs
                   ->Array.push(
                     buildOnEventRegistrationWith(
@@ -233,6 +233,7 @@ let setBatchDcs = (state: IndexerState.t, ~batch: Batch.t) => {
           // Only ever written, never read back. Kept on the table so the column
           // doesn't need a migration.
           registrationLogIndex: -1,
+          endBlock: ?dc.endBlock->Null.toOption,
         }
 
         inMemTable->InMemoryTable.Entity.set(
@@ -515,6 +515,7 @@ let toMetrics = (state: t): Metrics.t => {
   let chainStates = state.crossChainState->CrossChainState.chainStates
   let sourceRequests = []
   let sourceHeights = []
+  let sourceDecodeErrors = []
   chainStates->Utils.Dict.forEach(cs => {
     let sourceManager = cs->ChainState.sourceManager
     sourceManager
@@ -537,6 +538,17 @@ let toMetrics = (state: t): Metrics.t => {
         height: s.height,
       })
     )
+    sourceManager
+    ->SourceManager.getDecodeErrorSamples
+    ->Array.forEach(s =>
+      sourceDecodeErrors->Array.push({
+        Metrics.source: s.sourceName,
+        chainId: s.chainId,
+        contract: s.contract,
+        event: s.event,
+        count: s.count,
+      })
+    )
   })
   let historyPrunes = []
   state.historyPruneStats->Utils.Dict.forEachWithKey((s, entityName) =>
@@ -599,6 +611,7 @@ let toMetrics = (state: t): Metrics.t => {
     historyPrunes,
     sourceRequests,
     sourceHeights,
+    sourceDecodeErrors,
   }
 }
 
@@ -796,6 +809,9 @@ let takeRollback = (state: t): option<Persistence.rollback> => {
   rollback
 }
 
+// Whether a rollback diff is armed and still waiting for its write.
+let hasPendingRollback = (state: t) => state.rollback->Option.isSome
+
 // Advance the committed (durably persisted) frontier after a successful write.
 let markCommitted = (state: t, ~upToCheckpointId) => state.committedCheckpointId = upToCheckpointId
 
@@ -68,6 +68,15 @@ type evmTransactionField =
   | @as("gasUsedForL1") GasUsedForL1
   | @as("accessList") AccessList
   | @as("authorizationList") AuthorizationList
+  | @as("sourceHash") SourceHash
+  | @as("mint") Mint
+  | @as("isSystemTx") IsSystemTx
+  | @as("l1BaseFeeScalar") L1BaseFeeScalar
+  | @as("l1BlobBaseFee") L1BlobBaseFee
+  | @as("l1BlobBaseFeeScalar") L1BlobBaseFeeScalar
+  | @as("operatorFeeScalar") OperatorFeeScalar
+  | @as("l1BatchNumber") L1BatchNumber
+  | @as("l1BatchTxIndex") L1BatchTxIndex
 
 let allEvmBlockFields: array<evmBlockField> = [
   Number,
@@ -133,6 +142,15 @@ let allEvmTransactionFields: array<evmTransactionField> = [
   GasUsedForL1,
   AccessList,
   AuthorizationList,
+  SourceHash,
+  Mint,
+  IsSystemTx,
+  L1BaseFeeScalar,
+  L1BlobBaseFee,
+  L1BlobBaseFeeScalar,
+  OperatorFeeScalar,
+  L1BatchNumber,
+  L1BatchTxIndex,
 ]
 let evmTransactionFieldSchema = S.enum(allEvmTransactionFields)
 
@@ -220,6 +238,15 @@ let evmNullableTransactionFields = Utils.Set.fromArray(
       From,
       To,
       Type,
+      SourceHash,
+      Mint,
+      IsSystemTx,
+      L1BaseFeeScalar,
+      L1BlobBaseFee,
+      L1BlobBaseFeeScalar,
+      OperatorFeeScalar,
+      L1BatchNumber,
+      L1BatchTxIndex,
     ]: array<evmTransactionField>
   ),
 )
@@ -293,6 +320,18 @@ type evmTransactionInput = {
   l1FeeScalar?: float,
   gasUsedForL1?: bigint,
   authorizationList?: JSON.t,
+  // OP Stack deposit transactions
+  sourceHash?: string,
+  mint?: bigint,
+  isSystemTx?: bool,
+  // OP Stack Ecotone/Fjord/Isthmus fee parameters
+  l1BaseFeeScalar?: bigint,
+  l1BlobBaseFee?: bigint,
+  l1BlobBaseFeeScalar?: bigint,
+  operatorFeeScalar?: bigint,
+  // zkSync
+  l1BatchNumber?: int,
+  l1BatchTxIndex?: int,
 }
 
 type genericEvent<'params, 'block, 'transaction> = {
@@ -427,8 +466,8 @@ type fieldSelection = {
   accountActivityFields: Utils.Set.t<string>,
   logFields: Utils.Set.t<string>,
   // The sets precompiled to the store selections `ChainState` materialises with.
-  blockMask: float,
-  transactionMask: float,
+  blockMask: bigint,
+  transactionMask: bigint,
 }
 
 // `~blockMaskFn`/`~transactionMaskFn` are the ecosystem's `Evm`/`Svm`/`Fuel`
@@ -440,8 +479,8 @@ let makeFieldSelection = (
   ~instructionFields: Utils.Set.t<string>=Utils.Set.make(),
   ~accountActivityFields: Utils.Set.t<string>=Utils.Set.make(),
   ~logFields: Utils.Set.t<string>=Utils.Set.make(),
-  ~blockMaskFn: Utils.Set.t<string> => float,
-  ~transactionMaskFn: Utils.Set.t<string> => float,
+  ~blockMaskFn: Utils.Set.t<string> => bigint,
+  ~transactionMaskFn: Utils.Set.t<string> => bigint,
 ): fieldSelection => {
   blockFields,
   transactionFields,
@@ -540,11 +579,59 @@ type resolvedWhere = {
 // types subtype-coerce into it cleanly.
 type onEventWhereArgs<'chain> = {chain: 'chain}
 
+// What an EVM event is read from. Values match the Rust `EvmEventKind`
+// string enum. `Create`/`Deploy` are the synthetic contract-creation events a
+// factory config adds to its deployer, from traces or deploy transactions.
+type evmEventKind =
+  | @as("Log") Log
+  | @as("Call") Call
+  | @as("Transaction") Transaction
+  | @as("Create") Create
+  | @as("Deploy") Deploy
+
+// An event's declarative registration: each of its items registers the
+// address in `param` as a `contract`, while its page is applied.
+type evmEventRegister = {
+  contract: string,
+  param: string,
+}
+
+// Inclusive bounds as decimal strings — wei values overflow JS numbers.
+type evmQuantityRange = {
+  gte?: string,
+  lte?: string,
+}
+
+// `where.transaction` from `config.yaml`: conditions on the transaction an
+// event's items belong to. `status` is the receipt status: 1 succeeded,
+// 0 reverted.
+type evmTransactionWhere = {
+  status?: int,
+  gasUsed?: evmQuantityRange,
+  value?: evmQuantityRange,
+}
+
+// One `where.params` condition from `config.yaml`, on a decoded non-indexed
+// param. Values are strings the Rust decoder parses as the param's type.
+type evmParamWhere = {
+  name: string,
+  eq?: string,
+  oneOf?: array<string>,
+  gte?: string,
+  lte?: string,
+}
+
 type evmEventConfig = {
   ...eventConfig,
   sighash: string,
   topicCount: int,
   paramsMetadata: array<paramMeta>,
+  // Absent is a log. A call's or transaction's `sighash` is the 4-byte
+  // function selector and its params are the decoded call inputs.
+  kind?: evmEventKind,
+  transactionWhere?: evmTransactionWhere,
+  paramsWhere?: array<evmParamWhere>,
+  register?: evmEventRegister,
 }
 
 // Shared formula for a registration's `dependsOnAddresses`. Kept here so the
@@ -567,6 +654,10 @@ type svmAccountFilter = {
 /** AND-group: every entry must match the same instruction. */
 type svmAccountFilterGroup = array<svmAccountFilter>
 
+/** What the source does with an item whose bytes don't fit the program's
+ Borsh schema. Mirrors `human_config::svm::DecodeErrors`. */
+type svmDecodeErrors = | @as("ignore") Ignore | @as("warn") Warn | @as("fail") Fail
+
 type svmInstructionEventConfig = {
   ...eventConfig,
   /** Base58 Solana program id this instruction belongs to. */
@@ -592,6 +683,16 @@ type svmInstructionEventConfig = {
    Duplicated on every event of the same program — the runtime dedups by
    `programId` when registering. `JSON.Null` when empty. */
   definedTypes: JSON.t,
+  /** `true` for an Anchor event (`emit!` / `emit_cpi!`) rather than an
+   instruction. `args` then holds the event's fields, and the 8-byte
+   discriminator prefixes the event payload instead of instruction data. */
+  isEvent?: bool,
+  /** `true` for an Anchor account type the program owns. `args` then holds
+   the account's fields, and the 8-byte discriminator prefixes the account's
+   data. */
+  isAccount?: bool,
+  /** The program's `decode_errors` mode. Absent means `Ignore`. */
+  decodeErrors?: svmDecodeErrors,
 }
 
 // Per-(event, chain) registration produced when user handler code registers an
@@ -657,6 +758,8 @@ type indexingAddress = {
   // Needed for rollback.
   // -1 for config addresses that shouldn't be rolled back.
   registrationBlock: int,
+  // The last block the address is indexed at, when it ends.
+  endBlock?: int,
 }
 
 // Duplicate the type from item to keep item properly unboxed. Runtime event
@@ -725,8 +828,10 @@ type item =
       // Ordering tiebreak for ecosystems whose within-block order isn't a
       // scalar. SVM keys an instruction by `(transactionIndex, path)`: the
       // logIndex above is the transaction, this is its position in that
-      // transaction's CPI tree. Absent on EVM and Fuel, whose log/receipt
-      // index already totally orders a block.
+      // transaction's CPI tree. EVM calls are keyed the same way by
+      // `(transactionIndex, traceAddress)`, and EVM transactions as the root
+      // of their call tree, with an empty path. Absent on EVM logs and Fuel,
+      // whose log/receipt index already totally orders a block.
       orderPath?: array<int>,
       transactionIndex: int,
       payload: eventPayload,
@@ -186,6 +186,9 @@ let buildChainsObject = (~config: Config.t) => {
             // Before the global state is available (eg during handler
             // module load after resume), combine static addresses from config
             // with dynamic contracts persisted in the database.
+            // A chain resuming from an address snapshot only read the rows
+            // registered above the snapshot's block, so the ones it holds
+            // show up once the chain state is built.
             | None =>
               switch getInitialChainState(~chainId=chainConfig.id) {
               | Some(chainState) =>
@@ -345,6 +348,74 @@ let getGlobalIndexer = (): 'indexer => {
     )
   }
 
+  // SVM onEvent: Anchor events, identified by `{program, event}` from TS or
+  // `{event: GADT{contract, _0}}` from ReScript. Events take `fields` but no
+  // `where`, and the generic `{event, context}` handler shape is already the
+  // SVM one.
+  let onSvmEventFn = (identityConfig: 'a, handler: 'b) => {
+    HandlerRegister.throwIfFinishedRegistration(~methodName="onEvent")
+    let raw =
+      identityConfig->(
+        Utils.magic: 'a => {"program": unknown, "event": unknown, "fields": option<unknown>}
+      )
+    let (programName, eventName) = if typeof(raw["program"]) === #string {
+      (
+        raw["program"]->(Utils.magic: unknown => string),
+        raw["event"]->(Utils.magic: unknown => string),
+      )
+    } else {
+      let event = raw["event"]->(Utils.magic: unknown => {"contract": string, "_0": string})
+      (event["contract"], event["_0"])
+    }
+    HandlerRegister.setHandler(
+      ~contractName=programName,
+      ~eventName,
+      handler->(
+        Utils.magic: 'b => Internal.genericHandler<
+          Internal.genericHandlerArgs<Internal.event, Internal.handlerContext>,
+        >
+      ),
+      ~eventOptions=raw["fields"]->Option.map(
+        (fields): Internal.eventOptions<_> => {fields: fields},
+      ),
+    )
+  }
+
+  // SVM onAccount: writes to a program's Anchor account types, identified by
+  // `{program, account}` from TS or `{account: GADT{contract, _0}}` from
+  // ReScript. Like instructions, the handler receives the payload under its
+  // own key, `account`, so the generic `{event, context}` shape is remapped.
+  let onAccountFn = (identityConfig: 'a, handler: 'b) => {
+    HandlerRegister.throwIfFinishedRegistration(~methodName="onAccount")
+    let raw =
+      identityConfig->(
+        Utils.magic: 'a => {"program": unknown, "account": unknown, "fields": option<unknown>}
+      )
+    let (programName, accountName) = if typeof(raw["program"]) === #string {
+      (
+        raw["program"]->(Utils.magic: unknown => string),
+        raw["account"]->(Utils.magic: unknown => string),
+      )
+    } else {
+      let account = raw["account"]->(Utils.magic: unknown => {"contract": string, "_0": string})
+      (account["contract"], account["_0"])
+    }
+    let userHandler =
+      handler->(Utils.magic: 'b => Envio.svmOnAccountArgs<Internal.handlerContext> => promise<unit>)
+    HandlerRegister.setHandler(
+      ~contractName=programName,
+      ~eventName=accountName,
+      (args: Internal.genericHandlerArgs<Internal.event, Internal.handlerContext>) =>
+        userHandler({
+          account: args.event->(Utils.magic: Internal.event => Envio.svmAccountChange),
+          context: args.context,
+        }),
+      ~eventOptions=raw["fields"]->Option.map(
+        (fields): Internal.eventOptions<_> => {fields: fields},
+      ),
+    )
+  }
+
   // contractRegister: delegates to HandlerRegister.setContractRegister
   let contractRegisterFn = (identityConfig: 'a, handler: 'b) => {
     HandlerRegister.throwIfFinishedRegistration(~methodName="contractRegister")
@@ -414,6 +485,8 @@ let getGlobalIndexer = (): 'indexer => {
             "chainIds",
             "chains",
             "onInstruction",
+            "onEvent",
+            "onAccount",
             "onSlot",
             "~internalAndWillBeRemovedSoon_onRollbackCommit",
           ]
@@ -435,8 +508,13 @@ let getGlobalIndexer = (): 'indexer => {
         let (chains, _) = buildChainsObject(~config=Config.load())
         chains->(Utils.magic: {..} => unknown)
       }
-    | "onEvent" => onEventFn->Utils.magic
+    | "onEvent" =>
+      switch Config.load().ecosystem.name {
+      | Svm => onSvmEventFn->Utils.magic
+      | Evm | Fuel => onEventFn->Utils.magic
+      }
     | "onInstruction" => onInstructionFn->Utils.magic
+    | "onAccount" => onAccountFn->Utils.magic
     | "contractRegister" => contractRegisterFn->Utils.magic
     | "onBlock" | "onSlot" => onBlockFn->Utils.magic
     | "~internalAndWillBeRemovedSoon_onRollbackCommit" => onRollbackCommitFn->Utils.magic
@@ -143,6 +143,7 @@ let toIndexingAddress = (dc: InternalTable.EnvioAddresses.t): Internal.indexingA
   address: dc->Config.EnvioAddresses.getAddress,
   contractName: dc.contractName,
   registrationBlock: dc.registrationBlock,
+  endBlock: ?dc.endBlock,
 }
 
 // All indexing addresses (config-seeded + dynamically registered) grouped by
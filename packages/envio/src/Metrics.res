@@ -90,6 +90,14 @@ type sourceRequestMetrics = {
   seconds: float,
 }
 
+type sourceDecodeErrorMetrics = {
+  source: string,
+  chainId: ChainId.t,
+  contract: string,
+  event: string,
+  count: int,
+}
+
 type sourceHeightMetrics = {
   source: string,
   chainId: ChainId.t,
@@ -122,6 +130,7 @@ type t = {
   historyPrunes: array<historyPruneMetrics>,
   sourceRequests: array<sourceRequestMetrics>,
   sourceHeights: array<sourceHeightMetrics>,
+  sourceDecodeErrors: array<sourceDecodeErrorMetrics>,
 }
 
 // Prometheus floats keep at most 3 decimals; integral values render without a
@@ -247,6 +256,17 @@ let renderMetrics = (b: builder, metrics: t) => {
     })
     byLabels->Dict.toArray
   }
+  let sourceDecodeErrors = {
+    let byLabels: dict<int> = Dict.make()
+    metrics.sourceDecodeErrors->Array.forEach(s => {
+      let labels = `{source="${s.source->escapeLabelValue}",chainId="${s.chainId->ChainId.toString}",contract="${s.contract->escapeLabelValue}",event="${s.event->escapeLabelValue}"}`
+      byLabels->Dict.set(
+        labels,
+        byLabels->Utils.Dict.dangerouslyGetNonOption(labels)->Option.getOr(0) + s.count,
+      )
+    })
+    byLabels->Dict.toArray
+  }
 
   b->single(
     ~name="envio_process_start_time_seconds",
@@ -468,6 +488,13 @@ let renderMetrics = (b: builder, metrics: t) => {
     ~entries=sources,
     ~value=height => height->Int.toFloat,
   )
+  b->series(
+    ~name="envio_source_decode_errors_total",
+    ~help="The number of fetched items whose data didn't fit the schema they were decoded against, eg after a program upgrade the IDL doesn't reflect.",
+    ~kind="counter",
+    ~entries=sourceDecodeErrors,
+    ~value=count => count->Int.toFloat,
+  )
   b->seriesOpt(
     ~name="envio_reorg_detected_total",
     ~help="Total number of reorgs detected",
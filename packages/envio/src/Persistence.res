@@ -28,6 +28,10 @@ type initialChainState = {
   firstEventBlockNumber: option<int>,
   timestampCaughtUpToHeadOrEndblock: option<Date.t>,
   indexingAddresses: array<Internal.indexingAddress>,
+  // Set when the chain's addresses registered at or below this block are to be
+  // restored from its address snapshot — `indexingAddresses` then holds only
+  // the rows registered above it.
+  addressSnapshotBlock?: int,
   sourceBlockNumber: int,
 }
 
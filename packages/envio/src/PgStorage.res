@@ -1753,6 +1753,10 @@ SELECT id, chain_id, -1, -1, contract_name FROM unnest($1::text[],$2::${addrChai
       )
     }
 
+    // Snapshots of the schema just dropped would restore addresses it no
+    // longer holds.
+    AddressStore.removeAllSnapshots()
+
     let cache = await restoreEffectCache(~withUpload=true)
 
     await reloadIndexCatalog()
@@ -2169,11 +2173,20 @@ SELECT id, chain_id, -1, -1, contract_name FROM unnest($1::text[],$2::${addrChai
   }
 
   let resumeInitialState = async (): Persistence.initialState => {
+    // Schemas set up before addresses could end lack the column. Every row they
+    // hold is indexed indefinitely, which is what a null reads as.
+    await sql->Postgres.unsafe(
+      `ALTER TABLE "${pgSchema}"."${Config.EnvioAddresses.table.tableName}" ADD COLUMN IF NOT EXISTS "end_block" INTEGER;`,
+    )->Utils.Promise.ignoreValue
     let (cache, chains, checkpointIdResult, reorgCheckpoints, envioInfo) = await Promise.all5((
       restoreEffectCache(~withUpload=false),
       InternalTable.Chains.getInitialState(
         sql,
         ~pgSchema,
+        ~snapshotBlockOf=?Env.addressSnapshotIntervalSeconds->Option.map(_ => {
+          let scope = Config.addressSnapshotScope()
+          chainId => AddressStore.snapshotBlock(~chainId, ~scope)
+        }),
       )->Promise.thenResolve(rawInitialStates => {
         rawInitialStates->Array.map((rawInitialState): Persistence.initialChainState => {
           id: rawInitialState.id,
@@ -2185,6 +2198,7 @@ SELECT id, chain_id, -1, -1, contract_name FROM unnest($1::text[],$2::${addrChai
           numEventsProcessed: rawInitialState.numEventsProcessed,
           progressBlockNumber: rawInitialState.progressBlockNumber,
           indexingAddresses: rawInitialState.indexingAddresses,
+          addressSnapshotBlock: ?rawInitialState.addressSnapshotBlock,
           sourceBlockNumber: rawInitialState.sourceBlockNumber,
         })
       }),
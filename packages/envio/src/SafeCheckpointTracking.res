@@ -3,10 +3,11 @@
 // especially when save_full_history is enabled.
 // The safe checkpoint id can be used to optimize checkpoints traverse logic and
 // make pruning operation super cheap.
+// Which block counts as safe is the chain's call (see ChainState.safeBlockNumber):
+// its configured depth below the head, or the finalized height it reports.
 type t = {
   checkpointIds: array<bigint>,
   checkpointBlockNumbers: array<int>,
-  maxReorgDepth: int,
 }
 
 let make = (
@@ -26,16 +27,13 @@ let make = (
     Some({
       checkpointIds,
       checkpointBlockNumbers,
-      maxReorgDepth,
     })
   } else {
     None
   }
 }
 
-let getSafeCheckpointId = (safeCheckpointTracking: t, ~sourceBlockNumber: int) => {
-  let safeBlockNumber = sourceBlockNumber - safeCheckpointTracking.maxReorgDepth
-
+let getSafeCheckpointId = (safeCheckpointTracking: t, ~safeBlockNumber: int) => {
   switch safeCheckpointTracking.checkpointIds {
   | [] => 0n
   | _
@@ -69,13 +67,13 @@ let getSafeCheckpointId = (safeCheckpointTracking: t, ~sourceBlockNumber: int) =
 
 let updateOnNewBatch = (
   safeCheckpointTracking: t,
-  ~sourceBlockNumber: int,
+  ~safeBlockNumber: int,
   ~chainId: ChainId.t,
   ~batchCheckpointIds: array<bigint>,
   ~batchCheckpointBlockNumbers: array<int>,
   ~batchCheckpointChainIds: array<ChainId.t>,
 ) => {
-  let safeCheckpointId = getSafeCheckpointId(safeCheckpointTracking, ~sourceBlockNumber)
+  let safeCheckpointId = getSafeCheckpointId(safeCheckpointTracking, ~safeBlockNumber)
 
   let mutCheckpointIds = []
   let mutCheckpointBlockNumbers = []
@@ -104,7 +102,6 @@ let updateOnNewBatch = (
   {
     checkpointIds: mutCheckpointIds,
     checkpointBlockNumbers: mutCheckpointBlockNumbers,
-    maxReorgDepth: safeCheckpointTracking.maxReorgDepth,
   }
 }
 
@@ -127,6 +124,5 @@ let rollback = (safeCheckpointTracking: t, ~targetBlockNumber: int) => {
   {
     checkpointIds: mutCheckpointIds,
     checkpointBlockNumbers: mutCheckpointBlockNumbers,
-    maxReorgDepth: safeCheckpointTracking.maxReorgDepth,
   }
 }
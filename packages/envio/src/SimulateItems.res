@@ -102,6 +102,15 @@ let evmSimulateTransactionSchema = S.schema(s =>
     "l1FeeScalar": s.matches(S.null(S.float)),
     "gasUsedForL1": s.matches(S.null(S.bigint)),
     "authorizationList": s.matches(S.null(S.json(~validate=false))),
+    "sourceHash": s.matches(S.null(S.string)),
+    "mint": s.matches(S.null(S.bigint)),
+    "isSystemTx": s.matches(S.null(S.bool)),
+    "l1BaseFeeScalar": s.matches(S.null(S.bigint)),
+    "l1BlobBaseFee": s.matches(S.null(S.bigint)),
+    "l1BlobBaseFeeScalar": s.matches(S.null(S.bigint)),
+    "operatorFeeScalar": s.matches(S.null(S.bigint)),
+    "l1BatchNumber": s.matches(S.null(S.int)),
+    "l1BatchTxIndex": s.matches(S.null(S.int)),
   }
 )
 
@@ -79,6 +79,22 @@ let snapshotEffects = (state: IndexerState.t, ~cache): array<Persistence.updated
   acc
 }
 
+// Once a write leaves nothing processed-but-unwritten and no rollback underway,
+// every chain's committed progress matches the database, which is what an
+// address snapshot has to be taken against.
+let saveAddressSnapshots = (state: IndexerState.t) =>
+  switch Env.addressSnapshotIntervalSeconds {
+  | Some(intervalSeconds)
+    if state->IndexerState.processedBatches->Utils.Array.isEmpty &&
+    !(state->IndexerState.hasPendingRollback) &&
+    !(state->IndexerState.isResolvingReorg) =>
+    let intervalMs = intervalSeconds->Int.toFloat *. 1000.
+    state
+    ->IndexerState.chainStates
+    ->Utils.Dict.forEach(cs => cs->ChainState.saveAddressSnapshot(~intervalMs))
+  | _ => ()
+  }
+
 let runOneWrite = async (state: IndexerState.t) => {
   let persistence = state->IndexerState.persistence
   let config = state->IndexerState.config
@@ -110,6 +126,20 @@ let runOneWrite = async (state: IndexerState.t) => {
     }
 
     let rollback = state->IndexerState.takeRollback
+    switch rollback {
+    | Some({progressBlockNumberByChainId}) =>
+      state
+      ->IndexerState.chainStates
+      ->Utils.Dict.forEach(cs =>
+        switch progressBlockNumberByChainId->ChainId.Dict.dangerouslyGetNonOption(
+          (cs->ChainState.chainConfig).id,
+        ) {
+        | Some(targetBlock) => cs->ChainState.dropAddressSnapshotAbove(~targetBlock)
+        | None => ()
+        }
+      )
+    | None => ()
+    }
 
     let updatedEntities = []
     state->IndexerState.eachEntityTable((~entityConfig, ~scope, ~table) => {
@@ -154,6 +184,7 @@ let runOneWrite = async (state: IndexerState.t) => {
     ))
 
     state->IndexerState.markCommitted(~upToCheckpointId)
+    saveAddressSnapshots(state)
 
     switch rollback {
     | Some({progressBlockNumberByChainId}) if RollbackCommit.callbacks->Utils.Array.notEmpty =>
@@ -209,6 +209,13 @@ module Fs = {
     mode?: int,
   }
 
+  type rmOptions = {
+    recursive?: bool,
+    force?: bool,
+  }
+
+  @module("fs") external rmSync: (Path.t, rmOptions) => unit = "rmSync"
+
   module Promises = {
     @module("fs") @scope("promises")
     external writeFile: (
@@ -199,6 +199,7 @@ WHERE "${(#id: field :> string)}" = $2
     numEventsProcessed: float,
     progressBlockNumber: int,
     indexingAddresses: array<Internal.indexingAddress>,
+    addressSnapshotBlock: option<int>,
     sourceBlockNumber: int,
   }
 
@@ -220,32 +221,70 @@ FROM "${pgSchema}"."${table.tableName}";`
     address: Address.t,
     contractName: string,
     registrationBlock: int,
+    endBlock: Null.t<int>,
   }
 
   // Addresses are read as plain rows rather than aggregated per chain with
   // json_agg: a single chain's aggregate can exceed V8's max string length
   // (postgres.js decodes the column with Buffer.toString and throws
   // ERR_STRING_TOO_LONG). Grouping happens in JS instead — see getInitialState.
-  let makeGetIndexingAddressesQuery = (~pgSchema) => {
+  //
+  // A chain restoring from an address snapshot only needs the rows registered
+  // above the snapshot's block, plus those that were ended above it: the file
+  // holds the rest as they are.
+  let makeGetIndexingAddressesQuery = (~pgSchema, ~snapshotBlocks: array<(ChainId.t, int)>=[]) => {
+    let where = switch snapshotBlocks {
+    | [] => ""
+    | _ =>
+      `
+WHERE NOT (${snapshotBlocks
+        ->Array.map(((chainId, block)) =>
+          `("chain_id" = ${chainId->ChainId.toString} AND "registration_block" <= ${block->Int.toString} AND ("end_block" IS NULL OR "end_block" <= ${block->Int.toString}))`
+        )
+        ->Array.join(" OR ")})`
+    }
     // envio_addresses.id is a composite "{chainId}-{address}" string produced by
     // Config.EnvioAddresses.makeId; extract the address by taking everything
     // after the first '-'. Keep in sync with makeId / getAddress.
     `SELECT "chain_id" as "chainId",
 SUBSTRING("id" FROM POSITION('-' IN "id") + 1) as "address",
 "contract_name" as "contractName",
-"registration_block" as "registrationBlock"
-FROM "${pgSchema}"."${EnvioAddresses.table.tableName}";`
+"registration_block" as "registrationBlock",
+"end_block" as "endBlock"
+FROM "${pgSchema}"."${EnvioAddresses.table.tableName}"${where};`
   }
 
-  let getInitialState = async (sql, ~pgSchema) => {
-    let (rawInitialStates, rawIndexingAddresses) = await Promise.all2((
-      sql
+  // `snapshotBlockOf` answers, per chain, which snapshot block (if any) to
+  // restore from. A snapshot is only usable when the chain's committed
+  // progress has reached its block: the rows it holds are then still exactly
+  // the ones at or below it, since a rollback below a snapshot removes the
+  // file before it deletes any row.
+  let getInitialState = async (
+    sql,
+    ~pgSchema,
+    ~snapshotBlockOf: option<ChainId.t => option<int>>=?,
+  ) => {
+    let rawInitialStates: array<rawInitialState> =
+      await sql
       ->Postgres.unsafe(makeGetInitialStateQuery(~pgSchema))
-      ->(Utils.magic: promise<array<unknown>> => promise<array<rawInitialState>>),
-      sql
-      ->Postgres.unsafe(makeGetIndexingAddressesQuery(~pgSchema))
-      ->(Utils.magic: promise<array<unknown>> => promise<array<rawIndexingAddress>>),
-    ))
+      ->(Utils.magic: promise<array<unknown>> => promise<array<rawInitialState>>)
+
+    let snapshotBlockByChainId = Dict.make()
+    let snapshotBlocks = []
+    rawInitialStates->Array.forEach(rawInitialState => {
+      let id = rawInitialState.id->ChainId.normalizeOrThrow
+      switch snapshotBlockOf->Option.flatMap(snapshotBlockOf => snapshotBlockOf(id)) {
+      | Some(block) if block <= rawInitialState.progressBlockNumber =>
+        snapshotBlockByChainId->Dict.set(id->ChainId.toString, block)
+        snapshotBlocks->Array.push((id, block))->ignore
+      | _ => ()
+      }
+    })
+
+    let rawIndexingAddresses: array<rawIndexingAddress> =
+      await sql
+      ->Postgres.unsafe(makeGetIndexingAddressesQuery(~pgSchema, ~snapshotBlocks))
+      ->(Utils.magic: promise<array<unknown>> => promise<array<rawIndexingAddress>>)
 
     let indexingAddressesByChainId = Dict.make()
     rawIndexingAddresses->Array.forEach(row => {
@@ -264,6 +303,7 @@ FROM "${pgSchema}"."${EnvioAddresses.table.tableName}";`
         address: row.address,
         contractName: row.contractName,
         registrationBlock: row.registrationBlock,
+        endBlock: ?row.endBlock->Null.toOption,
       })
       ->ignore
     })
@@ -276,6 +316,7 @@ FROM "${pgSchema}"."${EnvioAddresses.table.tableName}";`
         indexingAddresses: indexingAddressesByChainId
         ->Dict.get(id->ChainId.toString)
         ->Option.getOr([]),
+        addressSnapshotBlock: snapshotBlockByChainId->Dict.get(id->ChainId.toString),
       }
     })
   }
@@ -36,6 +36,18 @@ type startBlockGroup = {startBlock: int, count: int}
 // tombstoned since this set was built.
 @send external filterByRegistrationBlock: (t, int) => t = "filterByRegistrationBlock"
 
+// The earliest end block among the set's addresses, null when none of them
+// ends. Cached inside Rust until an end in the store moves, so it's cheap to ask
+// after every response.
+@send external firstEndBlock: t => Null.t<int> = "firstEndBlock"
+
+// Drops addresses that ended before the block, and any the store has
+// tombstoned since this set was built.
+@send external filterEndedBefore: (t, int) => t = "filterEndedBefore"
+
+// The addresses this set holds that the other doesn't, in set order.
+@send external exclude: (t, t) => t = "exclude"
+
 // Union with another set of the same store, keeping set order; duplicates
 // collapse.
 @send external merge: (t, t) => t = "merge"
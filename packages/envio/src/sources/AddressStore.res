@@ -11,14 +11,43 @@ type t
 // chain, since `context.chain.<Contract>.add` validates against that whole set
 // — a name outside it makes the store throw. `dependsOnAddresses` is whether
 // this chain fetches for the contract by address, which is what makes the store
-// able to answer `fetchable` on a verdict.
-type contract = {name: string, startBlock: option<int>, dependsOnAddresses: bool}
+// able to answer `fetchable` on a verdict. `ttlBlocks` is how many blocks a
+// dynamically registered address stays indexed after its registration block.
+type contract = {
+  name: string,
+  startBlock: option<int>,
+  dependsOnAddresses: bool,
+  ttlBlocks?: int,
+}
 
 type registration = {
   address: Address.t,
   contractName: string,
   // -1 for a config address (not dynamically registered).
   registrationBlock: int,
+  // The end block the database holds, for a seeded address. A new registration
+  // leaves it out and gets its contract's TTL.
+  endBlock?: int,
+}
+
+// Stops indexing an address after `block` — the last block it's indexed at.
+type deregistration = {
+  address: Address.t,
+  contractName: string,
+  block: int,
+}
+
+type deregistrationVerdict =
+  // Its end block is now the deregistration's.
+  | Ended
+  // It already ends at or before the deregistration block.
+  | AlreadyEnded({existingEndBlock: int})
+  // Not registered for the contract.
+  | NotRegistered
+
+type rawDeregistrationVerdict = {
+  kind: string,
+  existingEndBlock: Null.t<int>,
 }
 
 type verdict =
@@ -41,13 +70,14 @@ type rawVerdict = {
   existingEffectiveStartBlock: Null.t<int>,
 }
 
-// A registration the store handed over for persistence, paired with the
+// A registration or end the store handed over for persistence, paired with the
 // checkpoint that owns its row. `checkpointIdx` indexes the block numbers
 // passed to `drainForWrite` — the ids stay on the JS side.
 type drainedAddress = {
   address: Address.t,
   contractName: string,
   registrationBlock: int,
+  endBlock: Null.t<int>,
   checkpointIdx: int,
 }
 
@@ -91,9 +121,13 @@ let make = (~ecosystem: Ecosystem.name, ~shouldChecksum: bool, ~contracts: array
 // carries `dependsOnAddresses: false` just like a contract named only by
 // `configContractNames`. Either way the addresses are stored and persisted,
 // never fetched.
+//
+// `ttlBlocksByContract` holds this chain's per-contract TTLs; other chains'
+// contracts have none here.
 let contractsOf = (
   ~onEventRegistrations: array<Internal.onEventRegistration>,
   ~configContractNames: array<string>,
+  ~ttlBlocksByContract: dict<int>=Dict.make(),
 ): array<contract> => {
   // Only ever holds a declared start block, so a missing key is unambiguous —
   // storing `None` in a dict would be indistinguishable from "not seen yet".
@@ -130,6 +164,7 @@ let contractsOf = (
       ? None
       : startBlocks->Utils.Dict.dangerouslyGetNonOption(name),
     dependsOnAddresses: addressDependent->Utils.Set.has(name),
+    ttlBlocks: ?ttlBlocksByContract->Utils.Dict.dangerouslyGetNonOption(name),
   })
 }
 
@@ -147,15 +182,27 @@ let contractsOf = (
 external registerBatchRaw: (t, array<registration>) => array<rawVerdict> = "registerBatch"
 @send external seedBatchRaw: (t, array<registration>) => array<rawVerdict> = "seedBatch"
 
+// The registrations a source made while routing one page — a factory's
+// creations, an event's `register` — held on the Rust side until the page's
+// response is applied with `registerPage`. Opaque; only a rejected
+// registration is read back, for its warning.
+type registrationPage
+
+@send
+external registerPageRaw: (t, registrationPage) => array<rawVerdict> = "registerPage"
+@send external pageLength: registrationPage => int = "length"
+@send external pageGetRaw: (registrationPage, int) => Null.t<registration> = "get"
+
 // Drains the registrations awaiting persistence at or below the given block —
 // what the batch being written covers — pairing each with the checkpoint at its
-// registration block. Later registrations stay pending. Throws, with the queue
-// untouched, when a drained registration's block has no checkpoint in the batch.
+// registration block, then the deregistrations the same way by their end block.
+// Later ones stay pending. Throws, with the queues untouched, when a drained
+// block has no checkpoint in the batch.
 @send
 external drainForWrite: (t, int, array<int>) => array<drainedAddress> = "drainForWrite"
 
-// How many registrations await persistence — lets a caller skip the work of
-// assembling what `drainForWrite` needs.
+// How many registrations and ends await persistence — lets a caller skip the
+// work of assembling what `drainForWrite` needs.
 @send external pendingCount: t => int = "pendingCount"
 
 // The registrations still awaiting persistence. For assertions.
@@ -169,10 +216,21 @@ external drainForWrite: (t, int, array<int>) => array<drainedAddress> = "drainFo
 @send external isIndexedAt: (t, Address.t, string, int) => bool = "isIndexedAt"
 
 // Drops every address registered after the target block, returning how many
-// were dropped. Ids are tombstoned rather than reused, so sets built before the
-// rollback still point at the right entries.
+// were dropped, and undoes every deregistration after it. Ids are tombstoned
+// rather than reused, so sets built before the rollback still point at the
+// right entries.
 @send external rollback: (t, int) => int = "rollback"
 
+// The fetchable addresses registered at or before the block that end after it:
+// what a rollback to the block has to fetch past it again, even where a
+// partition had already dropped them for having ended.
+// Taken before `rollback`, which resets a deregistered address's end.
+@send external makeSetEndingAfter: (t, int) => AddressSet.t = "makeSetEndingAfter"
+
+@send
+external deregisterBatchRaw: (t, array<deregistration>) => array<rawDeregistrationVerdict> =
+  "deregisterBatch"
+
 @send external getRaw: (t, Address.t) => Null.t<Internal.indexingContract> = "get"
 
 @send external contractAddresses: (t, string) => array<Address.t> = "contractAddresses"
@@ -200,15 +258,86 @@ let toVerdict = (raw: rawVerdict): verdict =>
 let registerBatch = (store: t, registrations: array<registration>): array<verdict> =>
   store->registerBatchRaw(registrations)->Array.map(toVerdict)
 
+// `registerBatch` for a page's own registrations, in page order. What it adds is
+// pending persistence like any dynamic registration.
+let registerPage = (store: t, page: registrationPage): array<verdict> =>
+  store->registerPageRaw(page)->Array.map(toVerdict)
+
+let pageGet = (page: registrationPage, idx) => page->pageGetRaw(idx)->Null.getUnsafe
+
 // `registerBatch` for addresses the database already holds — config addresses
 // and the dynamic ones a resume restores. Nothing is marked pending, so nothing
 // is ever written back.
 let seedBatch = (store: t, registrations: array<registration>): array<verdict> =>
   store->seedBatchRaw(registrations)->Array.map(toVerdict)
 
+// Ends each address after its deregistration block, so the routing gates drop
+// its items above it. The end is pending persistence until a batch write
+// drains it. Verdicts come back in the batch's order.
+//
+// An unknown contract name throws with none of the batch applied.
+let deregisterBatch = (store: t, deregistrations: array<deregistration>) =>
+  store
+  ->deregisterBatchRaw(deregistrations)
+  ->Array.map((raw): deregistrationVerdict =>
+    switch raw.kind {
+    | "ended" => Ended
+    | "alreadyEnded" => AlreadyEnded({existingEndBlock: raw.existingEndBlock->Null.getUnsafe})
+    | "notRegistered" => NotRegistered
+    | kind => JsError.throwWithMessage(`Unexpected address deregistration verdict "${kind}"`)
+    }
+  )
+
 let makeSet = (store: t, ~contractName, ~options={}: makeSetOptions) =>
   store->makeSetRaw(contractName, options)
 
 // The entry an address is registered under, whichever contract holds it —
 // addresses are unique chain-wide. `None` once rolled back.
 let get = (store: t, address) => store->getRaw(address)->Null.toOption
+
+// What `loadSnapshot` restored for one contract — enough for the fetch state to
+// build its partitions without the addresses crossing into JS.
+type restoredContract = {
+  contractName: string,
+  addressCount: int,
+  // Addresses a factory registered rather than the config declared.
+  dynamicCount: int,
+}
+
+type restoredSnapshot = {
+  // Every address registered at or below this block, and nothing above.
+  block: int,
+  // Only the contracts the snapshot held addresses for.
+  contracts: array<restoredContract>,
+}
+
+// Writes every address `envio_addresses` holds at the given block — live,
+// registered at or below it, not awaiting persistence — to a snapshot file,
+// returning how many were written. The scope (see Config.addressSnapshotScope)
+// goes into the file's header. Throws when the file can't be written.
+@send external saveSnapshot: (t, string, ~scope: string, int) => int = "saveSnapshot"
+
+// Restores a snapshot into a still-empty store. Restored addresses count as
+// persisted, like seeded ones. Throws, leaving the store empty, for a file of
+// another scope or ecosystem, or one holding addresses of a contract no longer
+// declared.
+@send external loadSnapshot: (t, string, ~scope: string) => restoredSnapshot = "loadSnapshot"
+
+let snapshotDir = () => NodeJs.Path.resolve([".envio", "addresses"])
+
+// Where a chain's address snapshot lives. One file per chain, overwritten in
+// place by every save.
+let snapshotFile = (~chainId: ChainId.t) =>
+  NodeJs.Path.join(snapshotDir(), `${chainId->ChainId.toString}.bin`)
+
+let snapshotPath = (~chainId) => snapshotFile(~chainId)->NodeJs.Path.toString
+
+// The block the chain's snapshot covers, or None when there's no readable one
+// of this scope.
+let snapshotBlock = (~chainId, ~scope) =>
+  Core.getAddon().addressSnapshotBlock(snapshotPath(~chainId), scope)->Null.toOption
+
+// Removes the chain's snapshot, if any.
+let removeSnapshot = (~chainId) => NodeJs.Fs.rmSync(snapshotFile(~chainId), {force: true})
+
+let removeAllSnapshots = () => NodeJs.Fs.rmSync(snapshotDir(), {recursive: true, force: true})
@@ -8,8 +8,8 @@
 // The store also owns response validation and reorg detection. A response page
 // records conflicts found while it is built; SourceManager rejects those pages
 // before the persistent store is touched. Merging a validated page compares
-// only persistent-vs-response hashes, and pruning keeps the hash of processed
-// blocks still inside the reorg threshold.
+// only persistent-vs-response hashes and parent links, and pruning keeps the
+// hash and parent hash of processed blocks still inside the reorg threshold.
 type t
 
 @send external newEvm: (Core.blockStoreCtor, ~shouldChecksum: bool) => t = "newEvm"
@@ -36,7 +36,7 @@ let makeMaskFn = FieldMask.makeMaskFn
 // optional except the key, so a page can carry anything from a full block to a
 // hash-only reorg observation. The Rust side re-encodes them through the same
 // column fill as fetched blocks.
-type evmBlockInput = {number: int, hash?: string, timestamp?: int}
+type evmBlockInput = {number: int, hash?: string, parentHash?: string, timestamp?: int}
 type svmBlockInput = {slot: int, hash?: string, time?: int}
 type fuelBlockInput = {height: int, id?: string, time?: int}
 
@@ -48,8 +48,14 @@ external fromJsSvm: (Core.blockStoreCtor, array<svmBlockInput>) => t = "fromJsSv
 external fromJsFuel: (Core.blockStoreCtor, array<fuelBlockInput>) => t = "fromJsFuel"
 
 // An ecosystem-agnostic (number, hash, timestamp) observation, mapped onto the
-// ecosystem's own field names when the page is built.
-type inputBlock = {blockNumber: int, blockHash?: string, blockTimestamp?: int}
+// ecosystem's own field names when the page is built. The parent hash is only
+// kept for EVM, whose parent is always the previous block number.
+type inputBlock = {
+  blockNumber: int,
+  blockHash?: string,
+  blockParentHash?: string,
+  blockTimestamp?: int,
+}
 
 // Build a page from JS-observed blocks (RPC responses, stored reorg
 // checkpoints) for merging into the per-chain store.
@@ -61,6 +67,7 @@ let fromJs = (blocks: array<inputBlock>, ~ecosystem: Ecosystem.name, ~shouldChec
       blocks->Array.map(b => {
         number: b.blockNumber,
         hash: ?b.blockHash,
+        parentHash: ?b.blockParentHash,
         timestamp: ?b.blockTimestamp,
       }),
       shouldChecksum,
@@ -85,7 +92,8 @@ let fromJs = (blocks: array<inputBlock>, ~ecosystem: Ecosystem.name, ~shouldChec
 }
 
 // The lowest merged block at or above the reorg threshold whose received hash
-// differed from the stored one.
+// differed from the stored one. For a broken parent link it's the parent: one
+// side's hash for it against the parent hash the other side's child carries.
 type hashMismatch = {
   blockNumber: int,
   storedHash: string,
@@ -106,13 +114,18 @@ external merge: (t, t, ~fromBlock: int, ~reportOnly: bool) => Null.t<hashMismatc
 @send
 external appendPage: (t, t) => unit = "appendPage"
 
+// Page cold blocks out to the file at `path` once they take more than
+// `budgetBytes` in memory; `materialize` reads them back. Hashes stay in memory.
+@send
+external enableSpill: (t, ~path: string, ~budgetBytes: float) => unit = "enableSpill"
+
 // A conflict observed within the response itself. Such a response is
 // discarded and retried, rather than treated as a chain reorg.
 @send external responseConflict: t => Null.t<hashMismatch> = "responseConflict"
 
 // Requested block numbers not covered by this response. SVM gaps count as
 // covered when HyperSync's cursor has fully processed their half-open range;
-// parent slot/hash links are validated separately as response consistency.
+// parent slot/hash links are checked as the page is built and merged.
 @send external missingHashes: (t, array<int>) => array<int> = "missingHashes"
 
 // Compare a validated response store against the persistent store in ascending
@@ -127,7 +140,7 @@ external latestValidBlockFromStore: (t, t, array<int>) => Null.t<int> = "latestV
 external materialize: (
   t,
   ~blockNumbers: array<int>,
-  ~masks: array<float>,
+  ~masks: array<bigint>,
 ) => promise<array<Internal.eventBlock>> = "materialize"
 
 // Drop blocks at or below the given block (already processed), keeping the
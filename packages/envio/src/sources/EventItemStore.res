@@ -0,0 +1,83 @@
+// Binding to the Rust `EventItemStore` napi class. A fetch response's routed
+// items stay in Rust, columnar and ordered by (block, logIndex, traceAddress,
+// registration); decoded params only become JS values when a batch is popped.
+// Each response page is merged into its chain's store, the same way as
+// `TransactionStore` pages, and batches pop from that store.
+type t
+
+@send external newEvm: Core.eventItemStoreCtor => t = "newEvm"
+
+let make = (): t => Core.getAddon().eventItemStore->newEvm
+
+// Popped items as aligned columns, in processing order.
+type batch = {
+  blockNumbers: array<int>,
+  logIndexes: array<int>,
+  transactionIndexes: array<int>,
+  registrationIndexes: array<int>,
+  srcAddresses: array<Address.t>,
+  params: array<Internal.eventParams>,
+  // Set on call and transaction items only: the call's position in its
+  // transaction's call tree, empty for the transaction itself.
+  traceAddresses: array<Null.t<array<int>>>,
+}
+
+// Drain another store (a fetch-response page) into this one. An item already
+// queued under the same position is replaced by the page's copy.
+@send external merge: (t, t) => unit = "merge"
+
+@send external len: t => int = "len"
+@send external firstBlockNumber: t => Null.t<int> = "firstBlockNumber"
+
+// Number of queued items at or below `upToBlock`.
+@send external readyCount: (t, ~upToBlock: int) => int = "readyCount"
+
+// Remove and return the first items at or below `upToBlock` (all when absent),
+// at most `maxItems` of them — except that a block is never split.
+@send
+external popBatch: (t, ~upToBlock: int=?, ~maxItems: int=?) => batch = "popBatch"
+
+// Copies of the items routed to any of `registrationIndexes`, in processing
+// order. The items stay queued.
+@send
+external peekRegistrations: (t, array<int>) => batch = "peekRegistrations"
+
+// Remove and return the items above `afterBlock`, in processing order.
+@send external popAbove: (t, ~afterBlock: int) => batch = "popAbove"
+
+// Drop items at or below `upToBlock`.
+@send external prune: (t, ~upToBlock: int) => unit = "prune"
+
+// Drop items above `targetBlock` (rolled back).
+@send external rollback: (t, ~targetBlock: int) => unit = "rollback"
+
+// Zips a popped batch into queue items. `onEventRegistrations` is the chain's
+// registrations, indexed by their sequential `index`.
+let toItems = (
+  batch: batch,
+  ~chainId,
+  ~onEventRegistrations: array<Internal.onEventRegistration>,
+): array<Internal.item> =>
+  batch.registrationIndexes->Array.mapWithIndex((registrationIndex, idx) => {
+    let onEventRegistration = onEventRegistrations->Array.getUnsafe(registrationIndex)
+    let logIndex = batch.logIndexes->Array.getUnsafe(idx)
+    Internal.Event({
+      onEventRegistration,
+      chainId,
+      blockNumber: batch.blockNumbers->Array.getUnsafe(idx),
+      logIndex,
+      // Calls carry their trace address; `logIndex` is then the transaction.
+      orderPath: ?(batch.traceAddresses->Array.getUnsafe(idx)->Null.toOption),
+      transactionIndex: batch.transactionIndexes->Array.getUnsafe(idx),
+      // `block` and `transaction` are omitted; they're materialised from the
+      // per-chain stores onto the payload at batch prep.
+      payload: {
+        contractName: onEventRegistration.eventConfig.contractName,
+        eventName: onEventRegistration.eventConfig.name,
+        chainId,
+        params: batch.params->Array.getUnsafe(idx),
+        srcAddress: batch.srcAddresses->Array.getUnsafe(idx),
+        logIndex,
+      }->Evm.fromPayload,
+    })
+  })
@@ -3,6 +3,7 @@ type rpc = {
   sourceFor: Source.sourceFor,
   syncConfig?: Config.sourceSyncOptions,
   ws?: string,
+  additionalUrls?: array<string>,
   headers?: dict<string>,
 }
 
@@ -16,6 +17,8 @@ let getSyncConfig = (
     ?queryTimeoutMillis,
     ?fallbackStallTimeout,
     ?pollingInterval,
+    ?maxBatchSize,
+    ?hedgeAfterMillis,
   }: Config.sourceSyncOptions,
 ): Config.sourceSync => {
   let queryTimeoutMillis = queryTimeoutMillis->Option.getOr(20_000)
@@ -36,6 +39,8 @@ let getSyncConfig = (
     queryTimeoutMillis,
     fallbackStallTimeout: fallbackStallTimeout->Option.getOr(queryTimeoutMillis / 2),
     pollingInterval: pollingInterval->Option.getOr(1000),
+    maxBatchSize: maxBatchSize->Option.getOr(100),
+    hedgeAfterMillis,
   }
 }
 
@@ -46,6 +51,7 @@ let makeSources = (
   ~rpcs: array<rpc>,
   ~lowercaseAddresses,
   ~addressStore,
+  ~captureDir=?,
 ) => {
   let sources = switch hyperSync {
   | Some(endpointUrl) => [
@@ -59,12 +65,13 @@ let makeSources = (
         serializationFormat: Env.hypersyncClientSerializationFormat,
         enableQueryCaching: Env.hypersyncClientEnableQueryCaching,
         logLevel: Env.hypersyncLogLevel,
+        ?captureDir,
         addressStore,
       }),
     ]
   | _ => []
   }
-  rpcs->Array.forEach(({?syncConfig, url, sourceFor, ?ws, ?headers}) => {
+  rpcs->Array.forEach(({?syncConfig, url, sourceFor, ?ws, ?additionalUrls, ?headers}) => {
     let source = RpcSource.make({
       chainId,
       sourceFor,
@@ -74,6 +81,7 @@ let makeSources = (
       lowercaseAddresses,
       addressStore,
       ?ws,
+      ?additionalUrls,
       ?headers,
     })
     let _ = sources->Array.push(source)
@@ -17,6 +17,8 @@ type options = {
   serializationFormat: HyperSyncClient.serializationFormat,
   enableQueryCaching: bool,
   logLevel: HyperSyncClient.logLevel,
+  // Set when response capture is on; see `Env.captureResponses`.
+  captureDir?: string,
   // The chain's address index; the client reads it while routing.
   addressStore: AddressStore.t,
 }
@@ -32,6 +34,7 @@ let make = (
     serializationFormat,
     enableQueryCaching,
     logLevel,
+    ?captureDir,
     addressStore,
   }: options,
 ): t => {
@@ -54,6 +57,7 @@ Learn more or get a free Envio API token at: https://envio.dev/app/api-tokens`)
     ~serializationFormat,
     ~enableQueryCaching,
     ~logLevel,
+    ~captureDir?,
     ~addressStore,
   ) {
   | client => client
@@ -63,31 +67,6 @@ Learn more or get a free Envio API token at: https://envio.dev/app/api-tokens`)
     )
   }
 
-  let makeEventBatchQueueItem = (
-    item: HyperSyncClient.EventItems.item,
-    ~onEventRegistration: Internal.evmOnEventRegistration,
-  ): Internal.item => {
-    let {transactionIndex, logIndex, srcAddress} = item
-
-    Internal.Event({
-      onEventRegistration: (onEventRegistration :> Internal.onEventRegistration),
-      chainId,
-      blockNumber: item.blockNumber,
-      logIndex,
-      transactionIndex,
-      // `block` and `transaction` are omitted; they're materialised from the
-      // per-chain stores onto the payload at batch prep.
-      payload: {
-        contractName: onEventRegistration.eventConfig.contractName,
-        eventName: onEventRegistration.eventConfig.name,
-        chainId,
-        params: item.params,
-        srcAddress,
-        logIndex,
-      }->Evm.fromPayload,
-    })
-  }
-
   let getItemsOrThrow = async (
     ~fromBlock,
     ~toBlock,
@@ -159,35 +138,25 @@ Learn more or get a free Envio API token at: https://envio.dev/app/api-tokens`)
     //In the query
     let heighestBlockQueried = pageUnsafe.nextBlock - 1
 
-    let parsingTimeRef = Performance.now()
-
-    //Parse page items into queue items
-    let parsedQueueItems = []
-
-    pageUnsafe.items->Array.forEach(item => {
-      let onEventRegistration = onEventRegistrations->Array.getUnsafe(item.onEventRegistrationIndex)
-      parsedQueueItems
-      ->Array.push(makeEventBatchQueueItem(item, ~onEventRegistration))
-      ->ignore
-    })
-
-    let parsingTimeElapsed = parsingTimeRef->Performance.secondsSince
-
     let totalTimeElapsed = totalTimeRef->Performance.secondsSince
 
+    // No parsing step: the page's items stay in Rust and only become queue
+    // items when a batch pops them from the chain's store.
     let stats = {
       totalTimeElapsed,
-      parsingTimeElapsed,
       pageFetchTime,
     }
 
     {
-      parsedQueueItems,
+      parsedQueueItems: [],
+      itemStore: pageUnsafe.itemStore,
       transactionStore: Some(pageUnsafe.transactionStore),
       // The page store also carries the rollbackGuard's blocks (head block and
       // parent of the range's first block), inserted on the Rust side.
       blockStore: pageUnsafe.blockStore,
+      registrationPage: pageUnsafe.registrationPage,
       latestFetchedBlockNumber: heighestBlockQueried,
+      finalizedBlock: ?pageUnsafe.finalizedBlock,
       stats,
       knownHeight,
       fromBlockQueried: fromBlock,
@@ -1,5 +1,8 @@
 type cfg = {
   url: string,
+  additionalUrls?: array<string>,
+  hedgeAfterMillis?: int,
+  ws?: string,
   httpReqTimeoutMillis?: int,
   headers?: dict<string>,
   initialBlockInterval: int,
@@ -8,14 +11,20 @@ type cfg = {
   intervalCeiling: int,
   backoffMillis: int,
   queryTimeoutMillis: int,
+  maxBatchSize: int,
 }
 
 // Only logs that resolved to a registration cross the boundary, each carrying
 // its registration's chain-scoped index.
 type rpcEventItem = {
+  // For a call or transaction item, `address` is the callee, `topics` is
+  // empty and `logIndex` is the transaction index.
   log: Rpc.GetLogs.log,
   onEventRegistrationIndex: int,
   params: Internal.eventParams,
+  // Set on call and transaction items only: the call's position in its
+  // transaction's call tree, empty for the transaction itself.
+  traceAddress?: array<int>,
 }
 
 type nextPageParams = {
@@ -38,10 +47,24 @@ type nextPageResponse = {
   requestStats: array<Source.requestStat>,
 }
 
+type headSubscription = {unsubscribe: unit => unit}
+
 // The caller provides a range; Rust decides the actual `toBlock` and returns it.
 type t = {
   getHeight: unit => promise<int>,
-  getNextPage: (nextPageParams, AddressSet.t) => promise<nextPageResponse>,
+  getNextPage: (
+    nextPageParams,
+    AddressSet.t,
+  ) => promise<(
+    nextPageResponse,
+    TransactionStore.t,
+    BlockStore.t,
+    AddressStore.registrationPage,
+  )>,
+  // Streams `eth_subscribe("newHeads")` from the `ws` endpoint: each head's
+  // height and a page holding its hash (and its parent's). Throws if no `ws`
+  // endpoint was configured.
+  subscribeHeads: ((int, BlockStore.t) => unit) => headSubscription,
 }
 
 @send
@@ -84,6 +107,8 @@ let coerceErrorOrThrow = exn =>
 
 let make = (
   ~url,
+  ~ws=?,
+  ~additionalUrls=?,
   ~checksumAddresses,
   ~syncConfig: Config.sourceSync,
   ~httpReqTimeoutMillis=?,
@@ -94,6 +119,9 @@ let make = (
   let client = Core.getAddon().evmRpcClient->classNew(
     {
       url,
+      ?additionalUrls,
+      hedgeAfterMillis: ?syncConfig.hedgeAfterMillis,
+      ?ws,
       ?httpReqTimeoutMillis,
       ?headers,
       initialBlockInterval: syncConfig.initialBlockInterval,
@@ -102,6 +130,7 @@ let make = (
       intervalCeiling: syncConfig.intervalCeiling,
       backoffMillis: syncConfig.backoffMillis,
       queryTimeoutMillis: syncConfig.queryTimeoutMillis,
+      maxBatchSize: syncConfig.maxBatchSize,
     },
     eventRegistrations,
     ~checksumAddresses,
@@ -111,5 +140,6 @@ let make = (
     getHeight: () => client.getHeight()->Promise.catch(coerceErrorOrThrow),
     getNextPage: (params, addressSet) =>
       client.getNextPage(params, addressSet)->Promise.catch(coerceErrorOrThrow),
+    subscribeHeads: onHead => client.subscribeHeads(onHead),
   }
 }
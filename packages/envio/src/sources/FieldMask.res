@@ -9,17 +9,19 @@ let fieldCodes = (fields: array<string>): dict<int> => {
   codes
 }
 
-let pow2: int => float = %raw(`c => Math.pow(2, c)`)
+// The mask with only field `code` selected.
+let fieldBit: int => bigint = %raw(`c => 1n << BigInt(c)`)
 
-// One event's selected fields as a bitmask float (bit `code` set ⇔ selected).
-// Each field appears once, so summing `pow2(code)` sets distinct bits with no
-// overlap; the result stays exact in f64 (codes span 0..31, so a mask is at most
-// 2^32-1).
-let maskFromFields = (selectedFields: Utils.Set.t<string>, ~codes: dict<int>): float => {
-  let mask = ref(0.)
+// Bitwise OR of two masks. Masks are BigInts, so they hold any number of field
+// codes; the Rust store rejects a code past what its tables hold.
+let orMask: (bigint, bigint) => bigint = %raw(`(a, b) => a | b`)
+
+// One event's selected fields as a BigInt bitmask (bit `code` set ⇔ selected).
+let maskFromFields = (selectedFields: Utils.Set.t<string>, ~codes: dict<int>): bigint => {
+  let mask = ref(0n)
   selectedFields->Utils.Set.forEach(name =>
     switch codes->Utils.Dict.dangerouslyGetNonOption(name) {
-    | Some(code) => mask := mask.contents +. pow2(code)
+    | Some(code) => mask := orMask(mask.contents, fieldBit(code))
     | None => ()
     }
   )
@@ -28,12 +30,7 @@ let maskFromFields = (selectedFields: Utils.Set.t<string>, ~codes: dict<int>): f
 
 // Build an ecosystem's per-event mask function from its ordered field-name
 // array. The field codes are derived once and closed over.
-let makeMaskFn = (fields: array<string>): (Utils.Set.t<string> => float) => {
+let makeMaskFn = (fields: array<string>): (Utils.Set.t<string> => bigint) => {
   let codes = fieldCodes(fields)
   selectedFields => selectedFields->maskFromFields(~codes)
 }
-
-// Bitwise OR of two per-event masks. Masks fit in 32 bits (≤32 fields), so
-// `>>> 0` recovers the unsigned value that a plain `|` renders negative once bit
-// 31 is set.
-let orMask: (float, float) => float = %raw(`(a, b) => (a | b) >>> 0`)
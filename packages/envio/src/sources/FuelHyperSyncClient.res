@@ -3,6 +3,8 @@ type t
 type cfg = {
   url: string,
   apiToken: string,
+  // Write every response page under this directory as Arrow IPC files.
+  captureDir?: string,
 }
 
 module Registration = {
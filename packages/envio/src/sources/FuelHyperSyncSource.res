@@ -10,9 +10,13 @@ type options = {
   onEventRegistrations: array<Internal.fuelOnEventRegistration>,
   // The chain's address index; the client reads it while routing.
   addressStore: AddressStore.t,
+  // Set when response capture is on; see `Env.captureResponses`.
+  captureDir?: string,
 }
 
-let make = ({chainId, endpointUrl, apiToken, onEventRegistrations, addressStore}: options): t => {
+let make = (
+  {chainId, endpointUrl, apiToken, onEventRegistrations, addressStore, ?captureDir}: options,
+): t => {
   let name = "HyperFuel"
 
   let apiToken = switch apiToken {
@@ -24,7 +28,7 @@ Learn more or get a free Envio API token at: https://envio.dev/app/api-tokens`)
   }
 
   let client = switch FuelHyperSyncClient.make(
-    {url: endpointUrl, apiToken},
+    {url: endpointUrl, apiToken, ?captureDir},
     ~eventRegistrations=FuelHyperSyncClient.Registration.fromOnEventRegistrations(
       onEventRegistrations,
     ),
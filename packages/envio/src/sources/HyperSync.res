@@ -51,13 +51,16 @@ let reraiseIfRecoverable = exn =>
   }
 
 type logsQueryPage = {
-  items: array<HyperSyncClient.EventItems.item>,
+  // Page store owning this page's routed items, in processing order.
+  itemStore: EventItemStore.t,
   nextBlock: int,
   archiveHeight: int,
+  finalizedBlock: option<int>,
   // Page store owning this page's raw transactions.
   transactionStore: TransactionStore.t,
   // Page store owning this page's raw blocks.
   blockStore: BlockStore.t,
+  registrationPage: AddressStore.registrationPage,
 }
 
 module GetLogs = {
@@ -109,7 +112,7 @@ module GetLogs = {
       clientFilteredContracts,
     }
 
-    let (res, transactionStore, blockStore) = switch await client.getEventItems(
+    let (res, itemStore, transactionStore, blockStore, registrationPage) = switch await client.getEventItems(
       ~query,
       ~addressSet,
     ) {
@@ -127,11 +130,13 @@ module GetLogs = {
     }
 
     {
-      items: res.items,
+      itemStore,
       nextBlock: res.nextBlock,
       archiveHeight: res.archiveHeight->Option.getOr(0), //Archive Height is only None if height is 0
+      finalizedBlock: res.finalizedBlock->Null.toOption,
       transactionStore,
       blockStore,
+      registrationPage,
     }
   }
 }
@@ -28,6 +28,8 @@ type cfg = {
   /** Whether to use query caching when using CapnProto serialization format. */
   enableQueryCaching?: bool,
   logLevel?: string,
+  /** Write every response page under this directory as Arrow IPC files. */
+  captureDir?: string,
 }
 
 module QueryTypes = {
@@ -97,6 +99,15 @@ module QueryTypes = {
     | L1FeeScalar
     | GasUsedForL1
     | AuthorizationList
+    | SourceHash
+    | Mint
+    | IsSystemTx
+    | L1BaseFeeScalar
+    | L1BlobBaseFee
+    | L1BlobBaseFeeScalar
+    | OperatorFeeScalar
+    | L1BatchNumber
+    | L1BatchTxIndex
 
   type logField =
     | Removed
@@ -183,12 +194,20 @@ module Registration = {
     topic3: topicFilterInput,
   }
 
+  type registerInput = {
+    contractName: string,
+    param: string,
+  }
+
   // The full per-(event, chain) registration passed to the Rust clients at
   // construction: decode metadata, routing identity, and the fetch state
   // queries are built from.
   type input = {
     // Chain-scoped sequential registration index, echoed back on routed items.
     index: int,
+    // Absent is a log. A call's or transaction's sighash is its 4-byte
+    // selector and its `topicSelections` are ignored.
+    kind?: Internal.evmEventKind,
     sighash: string,
     topicCount: int,
     eventName: string,
@@ -205,6 +224,14 @@ module Registration = {
     // string enums.
     blockFields: array<string>,
     transactionFields: array<string>,
+    // The event's `where.transaction`; absent matches any transaction (a
+    // transaction registration: any successful one).
+    transactionWhere?: Internal.evmTransactionWhere,
+    // The event's `where.params`, checked on the decoded params.
+    paramsWhere?: array<Internal.evmParamWhere>,
+    // The event's declarative registration, applied from the page's
+    // `registrationPage`.
+    register?: registerInput,
   }
 
   let toTopicFilterInput = (filter: Internal.topicFilter): topicFilterInput =>
@@ -220,6 +247,7 @@ module Registration = {
       let event = reg.eventConfig->(Utils.magic: Internal.eventConfig => Internal.evmEventConfig)
       {
         index: reg.index,
+        kind: ?event.kind,
         sighash: event.sighash,
         topicCount: event.topicCount,
         eventName: event.name,
@@ -242,6 +270,12 @@ module Registration = {
         transactionFields: reg.fieldSelection.transactionFields
         ->Utils.Set.toArray
         ->Array.map(Utils.String.capitalize),
+        transactionWhere: ?event.transactionWhere,
+        paramsWhere: ?event.paramsWhere,
+        register: ?event.register->Option.map(({contract, param}) => {
+          contractName: contract,
+          param,
+        }),
       }
     })
   }
@@ -264,25 +298,11 @@ module EventItems = {
     clientFilteredContracts: option<array<string>>,
   }
 
-  type item = {
-    logIndex: int,
-    srcAddress: Address.t,
-    // Number of the block this log belongs to; the block itself is resolved from
-    // `response.blocks`, deduplicated across items sharing a block.
-    blockNumber: int,
-    // Key (with the block number) into the transaction store; the transaction
-    // is resolved from the store on demand.
-    transactionIndex: int,
-    // The registration this log routed to, by chain-scoped index. Logs that
-    // route to no registration never cross the boundary.
-    onEventRegistrationIndex: int,
-    params: Internal.eventParams,
-  }
-
   type response = {
     archiveHeight: option<int>,
     nextBlock: int,
-    items: array<item>,
+    // One below the rollback guard's first block; null without a guard.
+    finalizedBlock: Null.t<int>,
   }
 }
 
@@ -292,12 +312,18 @@ type t = {
   getBlockHashes: (
     ~blockNumbers: array<int>,
   ) => promise<(BlockStore.t, array<RequestStat.t>)>,
-  // Returns the response plus page stores owning this page's raw transactions
-  // and blocks.
+  // Returns the response plus page stores owning this page's routed items, raw
+  // transactions and blocks, and the registrations its items make.
   getEventItems: (
     ~query: EventItems.query,
     ~addressSet: AddressSet.t,
-  ) => promise<(EventItems.response, TransactionStore.t, BlockStore.t)>,
+  ) => promise<(
+    EventItems.response,
+    EventItemStore.t,
+    TransactionStore.t,
+    BlockStore.t,
+    AddressStore.registrationPage,
+  )>,
   getHeight: unit => promise<int>,
 }
 
@@ -337,6 +363,7 @@ let make = (
   ~retryBackoffMs=?,
   ~retryCeilingMs=?,
   ~logLevel=#info,
+  ~captureDir=?,
   ~addressStore,
 ) => {
   let envioVersion = Utils.EnvioPackage.value.version
@@ -352,6 +379,7 @@ let make = (
       ?retryBackoffMs,
       ?retryCeilingMs,
       logLevel: logLevelToString(logLevel),
+      ?captureDir,
     },
     ~userAgent=`hyperindex/${envioVersion}`,
     ~eventRegistrations,
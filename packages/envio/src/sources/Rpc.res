@@ -154,6 +154,22 @@ module GetBlockByNumber = {
   let rawRoute = makeRpcRoute("eth_getBlockByNumber", paramsSchema, S.null(S.json(~validate=false)))
 }
 
+// Only the number of the block a tag (`safe`, `finalized`) points at.
+module GetBlockByTag = {
+  let paramsSchema = S.tuple(s =>
+    {
+      "tag": s.item(0, S.string),
+      "includeTransactions": s.item(1, S.bool),
+    }
+  )
+
+  let route = makeRpcRoute(
+    "eth_getBlockByNumber",
+    paramsSchema,
+    S.null(S.object(s => s.field("number", hexIntSchema))),
+  )
+}
+
 module GetTransactionByHash = {
   let rawRoute = makeRpcRoute(
     "eth_getTransactionByHash",
@@ -177,6 +193,10 @@ let getBlock = async (~client: Rest.client, ~blockNumber: int) => {
   )
 }
 
+let getBlockTagHeight = async (~client: Rest.client, ~tag: string) => {
+  await GetBlockByTag.route->Rest.fetch({"tag": tag, "includeTransactions": false}, ~client)
+}
+
 let getRawBlock = async (~client: Rest.client, ~blockNumber: int) => {
   await GetBlockByNumber.rawRoute->Rest.fetch(
     {"blockNumber": blockNumber, "includeTransactions": false},
@@ -12,6 +12,11 @@ type blockRangeFetchStats = {
 // per (source, method) into the envio_source_request_* metrics.
 type requestStat = RequestStat.t = {method: string, seconds: float}
 
+// An item the source fetched but whose bytes didn't fit its schema (SVM Borsh
+// decoding). SourceManager counts these per (source, contract, event) into
+// envio_source_decode_errors_total.
+type decodeErrorStat = {contract: string, event: string}
+
 // Native clients wrap a failure of a multi-request operation in a structured
 // payload, so the source can still return timings when SourceManager retries
 // it. `cause` carries the inner message as a plain error, ready for logging.
@@ -75,6 +80,10 @@ Thes response returned from a block range fetch
 type blockRangeFetchResponse = {
   knownHeight: int,
   parsedQueueItems: array<Internal.item>,
+  // Page of routed items a source keeps in Rust instead of `parsedQueueItems`;
+  // merged into the chain's item store on apply. Absent for sources that build
+  // their items in JS.
+  itemStore?: EventItemStore.t,
   // Page of transactions for this response's items, keyed by (blockNumber,
   // transactionIndex); merged into the chain's store on apply. `None` for
   // sources that keep the transaction inline on the payload (RPC/Fuel/Simulate).
@@ -84,14 +93,27 @@ type blockRangeFetchResponse = {
   // detection. Sources that keep the block inline on the payload (RPC/Simulate)
   // contribute hash-only rows built from the block hashes they saw.
   blockStore: BlockStore.t,
+  // Registrations the source made while routing this page's items (factory
+  // creations, events' `register`), applied with the response so a stale one registers nothing.
+  // Absent for sources that make none.
+  registrationPage?: AddressStore.registrationPage,
   fromBlockQueried: int,
   latestFetchedBlockNumber: int,
+  // The highest block the source reports it can no longer roll back, when the
+  // response carries one (HyperSync's rollback guard).
+  finalizedBlock?: int,
   stats: blockRangeFetchStats,
   requestStats: array<requestStat>,
+  // Items of this page that failed to decode and were delivered anyway.
+  // Absent for sources that don't decode.
+  decodeErrors?: array<decodeErrorStat>,
 }
 
 type getHeightResponse = {height: int, requestStats: array<requestStat>}
 
+// The `eth_getBlockByNumber` tags a chain's finality can follow.
+type blockTag = [#safe | #finalized]
+
 type getBlockHashesResponse = {
   result: result<BlockStore.t, exn>,
   requestStats: array<requestStat>,
@@ -139,6 +161,9 @@ type t = {
   pollingInterval: int,
   getBlockHashes: (~blockNumbers: array<int>, ~logger: Pino.t) => promise<getBlockHashesResponse>,
   getHeightOrThrow: unit => promise<getHeightResponse>,
+  // Height of the block a tag currently points at. Only sources that can
+  // answer it (RPC) provide it.
+  getBlockTagHeightOrThrow?: (~tag: blockTag) => promise<getHeightResponse>,
   getItemsOrThrow: (
     ~fromBlock: int,
     ~toBlock: option<int>,
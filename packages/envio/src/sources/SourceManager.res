@@ -5,6 +5,10 @@ type sourceManagerStatus = Idle | WaitingForNewBlock | Querying
 // envio_source_request_* by Metrics.renderSourceRequests.
 type requestStatAgg = {mutable count: int, mutable seconds: float}
 
+// Cumulative count of items a source failed to decode, per (contract, event).
+// Rendered into envio_source_decode_errors_total.
+type decodeErrorAgg = {contract: string, event: string, mutable count: int}
+
 type sourceState = {
   source: Source.t,
   mutable knownHeight: int,
@@ -15,6 +19,8 @@ type sourceState = {
   // Used to decide when to attempt recovery to this source.
   mutable lastFailedAt: option<float>,
   requestStats: dict<requestStatAgg>,
+  // Keyed by `${contract}.${event}`.
+  decodeErrors: dict<decodeErrorAgg>,
 }
 
 let recordRequestStats = (sourceState: sourceState, requestStats: array<Source.requestStat>) => {
@@ -28,6 +34,16 @@ let recordRequestStats = (sourceState: sourceState, requestStats: array<Source.r
   })
 }
 
+let recordDecodeErrors = (sourceState: sourceState, decodeErrors: array<Source.decodeErrorStat>) => {
+  decodeErrors->Array.forEach(({contract, event}) => {
+    let key = `${contract}.${event}`
+    switch sourceState.decodeErrors->Utils.Dict.dangerouslyGetNonOption(key) {
+    | Some(agg) => agg.count = agg.count + 1
+    | None => sourceState.decodeErrors->Dict.set(key, {contract, event, count: 1})
+    }
+  })
+}
+
 // Flattened (source, method) aggregates for Metrics.renderSourceRequests to
 // inline into the /metrics response.
 type requestStatSample = {
@@ -97,6 +113,34 @@ let getRequestStatSamples = (sourceManager: t): array<requestStatSample> => {
   samples
 }
 
+// Flattened (source, contract, event) decode-error counts for
+// Metrics.renderMetrics.
+type decodeErrorSample = {
+  sourceName: string,
+  chainId: ChainId.t,
+  contract: string,
+  event: string,
+  count: int,
+}
+
+let getDecodeErrorSamples = (sourceManager: t): array<decodeErrorSample> => {
+  let samples = []
+  sourceManager.sourcesState->Array.forEach(sourceState => {
+    sourceState.decodeErrors->Utils.Dict.forEach(agg => {
+      samples
+      ->Array.push({
+        sourceName: sourceState.source.name,
+        chainId: sourceState.source.chainId,
+        contract: agg.contract,
+        event: agg.event,
+        count: agg.count,
+      })
+      ->ignore
+    })
+  })
+  samples
+}
+
 // Per-source known heights for envio_source_known_height. Sources with no
 // observed height yet are skipped.
 type sourceHeightSample = {
@@ -315,6 +359,7 @@ let make = (
       disabled: false,
       lastFailedAt: None,
       requestStats: Dict.make(),
+      decodeErrors: Dict.make(),
     }),
     activeSource: initialActiveSource,
     waitingForNewBlockStateId: None,
@@ -942,6 +987,10 @@ let executeQuery = async (
         ~logger,
       )
       sourceState->recordRequestStats(response.requestStats)
+      switch response.decodeErrors {
+      | Some(decodeErrors) => sourceState->recordDecodeErrors(decodeErrors)
+      | None => ()
+      }
       validateResponseBlockStore(~method="getItems", ~blockStore=response.blockStore)
       sourceState.lastFailedAt = None
 
@@ -1157,3 +1206,39 @@ let getBlockHashes = async (sourceManager: t, ~blockNumbers: array<int>, ~isReal
 
   responseRef.contents->Option.getUnsafe
 }
+
+// The height a block tag points at, from the first enabled source that can
+// answer it. Not retried: a failure is logged and yields None, and the chain
+// keeps its last known finalized block until the next refresh.
+let getBlockTagHeight = async (sourceManager: t, ~tag: Source.blockTag) => {
+  let candidate = sourceManager.sourcesState->Array.findMap(sourceState =>
+    sourceState.disabled
+      ? None
+      : sourceState.source.getBlockTagHeightOrThrow->Option.map(getBlockTagHeightOrThrow => (
+          sourceState,
+          getBlockTagHeightOrThrow,
+        ))
+  )
+  switch candidate {
+  | None => None
+  | Some((sourceState, getBlockTagHeightOrThrow)) =>
+    try {
+      let res = await getBlockTagHeightOrThrow(~tag)
+      sourceState->recordRequestStats(res.requestStats)
+      Some(res.height)
+    } catch {
+    | exn =>
+      Logging.createChild(
+        ~params={
+          "chainId": sourceState.source.chainId,
+          "source": sourceState.source.name,
+          "tag": tag,
+        },
+      )->Logging.childWarn({
+        "msg": "Failed to fetch the block tag height for finality. Will retry on a later block.",
+        "err": exn->Utils.prettifyExn,
+      })
+      None
+    }
+  }
+}
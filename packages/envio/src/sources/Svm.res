@@ -90,6 +90,47 @@ module GetFinalizedSlot = {
   )
 }
 
+// The state of program-account items: HyperSync serves instructions but not
+// account data. `value` lines up with the requested pubkeys, `None` for an
+// account that doesn't exist (any more).
+module GetMultipleAccounts = {
+  type account = {
+    owner: string,
+    // Base64-encoded account data.
+    data: string,
+  }
+
+  // The most pubkeys one call accepts.
+  let maxPubkeys = 100
+
+  let route = Rpc.makeRpcRoute(
+    "getMultipleAccounts",
+    S.tuple(s => {
+      s.tag(1, {"encoding": "base64"})
+      s.item(0, S.array(S.string))
+    }),
+    S.object(s =>
+      s.field(
+        "value",
+        S.array(
+          S.null(
+            S.object(s => {
+              owner: s.field("owner", S.string),
+              data: s.field(
+                "data",
+                S.tuple(s => {
+                  s.tag(1, "base64")
+                  s.item(0, S.string)
+                }),
+              ),
+            }),
+          ),
+        ),
+      )
+    ),
+  )
+}
+
 let makeRPCSource = (~chainId, ~rpc: string, ~sourceFor: Source.sourceFor=Sync): Source.t => {
   let client = Rest.client(rpc)
 
@@ -6,6 +6,8 @@ type cfg = {
   httpReqTimeoutMillis?: int,
   retryBaseMs?: int,
   retryCeilingMs?: int,
+  /** Write every response page under this directory as Arrow IPC files. */
+  captureDir?: string,
 }
 
 module Registration = {
@@ -40,6 +42,10 @@ module Registration = {
     accounts: array<string>,
     argsJson?: string,
     definedTypesJson?: string,
+    // Anchor event rather than an instruction; `argsJson` holds its fields.
+    isEvent?: bool,
+    // Anchor account type the program owns; `argsJson` holds its fields.
+    isAccount?: bool,
   }
 
   let fromOnEventRegistrations = (
@@ -79,6 +85,8 @@ module Registration = {
         | JSON.Null => None
         | definedTypes => Some(definedTypes->JSON.stringify)
         },
+        isEvent: ?eventConfig.isEvent,
+        isAccount: ?eventConfig.isAccount,
       }
     })
 }
@@ -156,6 +164,22 @@ module ResponseTypes = {
     extraAccounts: array<string>,
   }
 
+  /// Why an item's bytes didn't fit its Borsh schema. `byteOffset` counts from
+  /// the start of the item's `data`; `field` is the top-level arg or event
+  /// field the decoder stopped in.
+  type decodeFailure = {
+    reason: string,
+    byteOffset?: int,
+    field?: string,
+  }
+
+  /// An account's data decoded against a program-account registration's
+  /// layout; `decodeError` is set instead of `decoded` when it doesn't fit.
+  type decodedAccount = {
+    decoded?: decodedInstruction,
+    decodeError?: decodeFailure,
+  }
+
   type instructionCall = {
     slot: int,
     transactionIndex: int,
@@ -223,6 +247,8 @@ module EventItems = {
     data: string,
     isInner: bool,
     decoded?: ResponseTypes.decodedInstruction,
+    // Set instead of `decoded` when a decode was attempted and failed.
+    decodeError?: ResponseTypes.decodeFailure,
     // Present only when the routed registration selected `fields.log`.
     logs?: array<log>,
   }
@@ -255,6 +281,12 @@ type t = {
     ~query: EventItems.query,
     ~addressSet: AddressSet.t,
   ) => promise<(EventItems.response, TransactionStore.t, BlockStore.t)>,
+  // Decodes an account's base64 data against a program-account registration's
+  // layout. Null when the data starts with another discriminator.
+  decodeAccount: (
+    ~registrationIndex: int,
+    ~dataBase64: string,
+  ) => Nullable.t<ResponseTypes.decodedAccount>,
 }
 
 @send
@@ -274,6 +306,7 @@ let make = (
   ~retryCeilingMs=?,
   ~eventRegistrations=[],
   ~addressStore,
+  ~captureDir=?,
 ) => {
   let envioVersion = Utils.EnvioPackage.value.version
   Core.getAddon().svmHyperSyncClient->classFromConfig(
@@ -283,6 +316,7 @@ let make = (
       ?httpReqTimeoutMillis,
       ?retryBaseMs,
       ?retryCeilingMs,
+      ?captureDir,
     },
     `hyperindex/${envioVersion}`,
     eventRegistrations,
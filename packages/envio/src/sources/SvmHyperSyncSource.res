@@ -8,6 +8,11 @@ type options = {
   clientTimeoutMillis: int,
   // The chain's address index; the client reads it while routing.
   addressStore: AddressStore.t,
+  // Set when response capture is on; see `Env.captureResponses`.
+  captureDir?: string,
+  // The chain's RPC, which program-account items read their state from. Only
+  // set under `Env.svmAccountStateFromRpc`; see `fetchAccountStates`.
+  accountsRpcUrl?: string,
 }
 
 let parseArgs = (d: SvmHyperSyncClient.ResponseTypes.decodedInstruction): JSON.t =>
@@ -111,6 +116,135 @@ let toSvmInstruction = (
   out->(Utils.magic: dict<unknown> => Envio.svmInstruction)
 }
 
+// Anchor events carry the emitting instruction's program id and path; `data`
+// is the raw event payload, discriminator included. A payload that fails to
+// decode still reaches the handler, with empty `params`.
+let toSvmEvent = (
+  item: SvmHyperSyncClient.EventItems.item,
+  ~eventConfig: Internal.svmInstructionEventConfig,
+  ~fieldSelection: Internal.fieldSelection,
+): Envio.svmEvent => {
+  let hasSelection = name => fieldSelection.instructionFields->Utils.Set.has(name)
+  let out = Dict.make()
+  out->setField("programName", eventConfig.contractName)
+  out->setField("eventName", eventConfig.name)
+  out->setField("discriminator", eventConfig.discriminator->Option.getOr(""))
+  out->setField("params", item.decoded->Option.mapOr(JSON.Object(Dict.make()), parseArgs))
+  if hasSelection("programId") {
+    out->setField("programId", item.programId->SvmTypes.Pubkey.fromStringUnsafe)
+  }
+  if hasSelection("data") {
+    out->setField("data", item.data)
+  }
+  if hasSelection("path") {
+    out->setField("path", item.path)
+  }
+  out->(Utils.magic: dict<unknown> => Envio.svmEvent)
+}
+
+// Program-account changes carry the writing instruction's program id and path.
+// `before` is never set: neither HyperSync nor the RPC stand-in has the state
+// from before the write.
+let toSvmAccountChange = (
+  item: SvmHyperSyncClient.EventItems.item,
+  ~address: string,
+  ~decoded: option<SvmHyperSyncClient.ResponseTypes.decodedInstruction>,
+  ~eventConfig: Internal.svmInstructionEventConfig,
+  ~fieldSelection: Internal.fieldSelection,
+): Envio.svmAccountChange => {
+  let hasSelection = name => fieldSelection.instructionFields->Utils.Set.has(name)
+  let out = Dict.make()
+  out->setField("programName", eventConfig.contractName)
+  out->setField("accountName", eventConfig.name)
+  out->setField("address", address->SvmTypes.Pubkey.fromStringUnsafe)
+  out->setField("discriminator", eventConfig.discriminator->Option.getOr(""))
+  switch decoded {
+  | Some(decoded) => out->setField("after", decoded->parseArgs)
+  | None => ()
+  }
+  if hasSelection("programId") {
+    out->setField("programId", item.programId->SvmTypes.Pubkey.fromStringUnsafe)
+  }
+  if hasSelection("path") {
+    out->setField("path", item.path)
+  }
+  out->(Utils.magic: dict<unknown> => Envio.svmAccountChange)
+}
+
+// HyperSync doesn't serve account data, so program-account items only name the
+// accounts an instruction wrote, and their state is read over the chain's RPC.
+// The RPC answers with the state at the time of the call rather than at the
+// slot of the write, so every write in a batch sees the latest state — and the
+// owner check runs against the latest owner. That's only fit for development,
+// so it's opt-in (`Env.svmAccountStateFromRpc`) until HyperSync serves account
+// data. Accounts that don't exist any more are left out.
+let fetchAccountStates = async (addresses: array<string>, ~client: Rest.client) => {
+  let states = Dict.make()
+  let chunks = []
+  let offset = ref(0)
+  while offset.contents < addresses->Array.length {
+    chunks->Array.push(
+      addresses->Array.slice(
+        ~start=offset.contents,
+        ~end=offset.contents + Svm.GetMultipleAccounts.maxPubkeys,
+      ),
+    )
+    offset := offset.contents + Svm.GetMultipleAccounts.maxPubkeys
+  }
+  let _ = await chunks
+  ->Array.map(async chunk => {
+    let accounts = await Svm.GetMultipleAccounts.route->Rest.fetch(chunk, ~client)
+    chunk->Array.forEachWithIndex((address, i) =>
+      switch accounts->Array.get(i) {
+      | Some(Some(account)) => states->Dict.set(address, account)
+      | _ => ()
+      }
+    )
+  })
+  ->Promise.all
+  states
+}
+
+// The program's `decode_errors` mode decides what a failed decode does: the
+// item still reaches its handler, undecoded, unless the mode is `fail`.
+// Every failure that doesn't stop the indexer counts into the response's
+// decodeErrors.
+let handleDecodeFailure = (
+  failure: SvmHyperSyncClient.ResponseTypes.decodeFailure,
+  ~item: SvmHyperSyncClient.EventItems.item,
+  ~eventConfig: Internal.svmInstructionEventConfig,
+  ~logger: Pino.t,
+) => {
+  let kind = switch (eventConfig.isEvent, eventConfig.isAccount) {
+  | (Some(true), _) => "event"
+  | (_, Some(true)) => "account"
+  | _ => "instruction"
+  }
+  let msg = `Failed to decode the ${kind} ${eventConfig.contractName}.${eventConfig.name} against its schema`
+  switch eventConfig.decodeErrors {
+  | Some(Fail) =>
+    let path = item.path->Array.map(Int.toString)->Array.join(", ")
+    let location = switch (failure.field, failure.byteOffset) {
+    | (Some(field), Some(offset)) => ` in "${field}" at byte ${offset->Int.toString}`
+    | (None, Some(offset)) => ` at byte ${offset->Int.toString}`
+    | _ => ""
+    }
+    JsError.throwWithMessage(
+      `${msg} (slot ${item.slot->Int.toString}, path [${path}])${location}: ${failure.reason}. The program's IDL likely doesn't match what ran on-chain. Update the IDL, or set the program's decode_errors to warn or ignore to deliver such items without decoded args.`,
+    )
+  | Some(Warn) =>
+    logger->Logging.childWarn({
+      "msg": msg,
+      "reason": failure.reason,
+      "byteOffset": failure.byteOffset,
+      "field": failure.field,
+      "slot": item.slot,
+      "path": item.path,
+    })
+  | Some(Ignore) | None => ()
+  }
+}
+
 let make = (
   {
     chainId,
@@ -119,6 +253,8 @@ let make = (
     onEventRegistrations,
     clientTimeoutMillis,
     addressStore,
+    ?captureDir,
+    ?accountsRpcUrl,
   }: options,
 ): t => {
   let name = "SvmHyperSync"
@@ -134,7 +270,14 @@ let make = (
       onEventRegistrations,
     ),
     ~addressStore,
+    ~captureDir?,
   )
+  let accountsRpcClient = accountsRpcUrl->Option.map(url => Rpc.makeClient(url))
+
+  let eventConfigOf = (item: SvmHyperSyncClient.EventItems.item) =>
+    (onEventRegistrations->Array.getUnsafe(item.onEventRegistrationIndex)).eventConfig->(
+      Utils.magic: Internal.eventConfig => Internal.svmInstructionEventConfig
+    )
 
   let getItemsOrThrow = async (
     ~fromBlock,
@@ -145,7 +288,7 @@ let make = (
     ~selection: FetchState.selection,
     ~itemsTarget,
     ~retry,
-    ~logger as _,
+    ~logger,
   ) => {
     let totalTimeRef = Performance.now()
     let pageFetchRef = Performance.now()
@@ -185,38 +328,141 @@ let make = (
     let pageFetchTime = pageFetchRef->Performance.secondsSince
     let requestStats = [{Source.method: "getInstructions", seconds: pageFetchTime}]
 
+    let writtenAccounts = Utils.Set.make()
+    resp.items->Array.forEach(item =>
+      if (item->eventConfigOf).isAccount == Some(true) {
+        writtenAccounts->Utils.Set.addMany(item.accounts)
+      }
+    )
+    let accountStates: dict<Svm.GetMultipleAccounts.account> = switch (
+      writtenAccounts->Utils.Set.size,
+      accountsRpcClient,
+    ) {
+    | (0, _) => Dict.make()
+    | (_, None) =>
+      JsError.throwWithMessage(
+        `Chain ${chainId->ChainId.toString} indexes program accounts, but HyperSync doesn't serve account data yet. Reading it over the chain's rpc returns each account's current state rather than its state at the write, so it's for development only: set ENVIO_SVM_ACCOUNT_STATE_FROM_RPC=true and the chain's rpc to use it.`,
+      )
+    | (_, Some(client)) =>
+      let accountsFetchRef = Performance.now()
+      let states = try await fetchAccountStates(
+        writtenAccounts->Utils.Set.toArray,
+        ~client,
+      ) catch {
+      | exn =>
+        throw(
+          Source.GetItemsError(
+            Source.FailedGettingItems({
+              exn,
+              attemptedToBlock: toBlock->Option.getOr(knownHeight),
+              retry: WithBackoff({
+                message: `Unexpected issue while reading program accounts from the SVM RPC. Attempt a retry.`,
+                backoffMillis: switch retry {
+                | 0 => 500
+                | _ => 1000 * retry
+                },
+              }),
+            }),
+          ),
+        )
+      }
+      requestStats
+      ->Array.push({
+        Source.method: "getMultipleAccounts",
+        seconds: accountsFetchRef->Performance.secondsSince,
+      })
+      ->ignore
+      states
+    }
+
     let parsingRef = Performance.now()
 
-    let parsedQueueItems = resp.items->Array.map(item => {
+    let decodeErrors = []
+    let countDecodeFailure = (failure, ~item, ~eventConfig: Internal.svmInstructionEventConfig) => {
+      handleDecodeFailure(failure, ~item, ~eventConfig, ~logger)
+      decodeErrors
+      ->Array.push({Source.contract: eventConfig.contractName, event: eventConfig.name})
+      ->ignore
+    }
+    let parsedQueueItems = []
+    resp.items->Array.forEach(item => {
       // Routing happened in Rust; the item references its registration by
       // chain-scoped index.
       let onEventRegistration = onEventRegistrations->Array.getUnsafe(item.onEventRegistrationIndex)
-      let eventConfig =
-        onEventRegistration.eventConfig->(
-          Utils.magic: Internal.eventConfig => Internal.svmInstructionEventConfig
+      let eventConfig = item->eventConfigOf
+      switch item.decodeError {
+      | Some(failure) => countDecodeFailure(failure, ~item, ~eventConfig)
+      | None => ()
+      }
+      let push = payload =>
+        parsedQueueItems
+        ->Array.push(
+          Internal.Event({
+            onEventRegistration,
+            chainId,
+            blockNumber: item.slot,
+            // A slot orders by `(transactionIndex, path)` — the
+            // transaction, then the instruction's position in its CPI tree. Both
+            // ride the item so the buffer comparator can order on the pair
+            // directly; no single integer can hold it (Solana allows a CPI depth
+            // of 5, which needs more bits than a JS integer is exact to).
+            logIndex: item.transactionIndex,
+            orderPath: item.path,
+            // The parent transaction is materialised from the store at batch prep.
+            transactionIndex: item.transactionIndex,
+            payload,
+          }),
         )
-      let payload = toSvmInstruction(
-        item,
-        ~programName=eventConfig.contractName,
-        ~instructionName=eventConfig.name,
-        ~eventConfig,
-        ~fieldSelection=onEventRegistration.fieldSelection,
-      )
-      Internal.Event({
-        onEventRegistration,
-        chainId,
-        blockNumber: item.slot,
-        // A slot orders by `(transactionIndex, path)` — the
-        // transaction, then the instruction's position in its CPI tree. Both
-        // ride the item so the buffer comparator can order on the pair
-        // directly; no single integer can hold it (Solana allows a CPI depth
-        // of 5, which needs more bits than a JS integer is exact to).
-        logIndex: item.transactionIndex,
-        orderPath: item.path,
-        // The parent transaction is materialised from the store at batch prep.
-        transactionIndex: item.transactionIndex,
-        payload: payload->(Utils.magic: Envio.svmInstruction => Internal.eventPayload),
-      })
+        ->ignore
+      switch (eventConfig.isEvent, eventConfig.isAccount) {
+      | (Some(true), _) =>
+        toSvmEvent(
+          item,
+          ~eventConfig,
+          ~fieldSelection=onEventRegistration.fieldSelection,
+        )
+        ->(Utils.magic: Envio.svmEvent => Internal.eventPayload)
+        ->push
+      | (_, Some(true)) =>
+        item.accounts->Array.forEach(address =>
+          switch accountStates->Dict.get(address) {
+          // Only the program's own accounts of this registration's type; the
+          // instruction may also write other programs' accounts.
+          | Some(account) if account.owner == item.programId =>
+            switch client.decodeAccount(
+              ~registrationIndex=item.onEventRegistrationIndex,
+              ~dataBase64=account.data,
+            )->Nullable.toOption {
+            | Some(decodedAccount) =>
+              switch decodedAccount.decodeError {
+              | Some(failure) => countDecodeFailure(failure, ~item, ~eventConfig)
+              | None => ()
+              }
+              toSvmAccountChange(
+                item,
+                ~address,
+                ~decoded=decodedAccount.decoded,
+                ~eventConfig,
+                ~fieldSelection=onEventRegistration.fieldSelection,
+              )
+              ->(Utils.magic: Envio.svmAccountChange => Internal.eventPayload)
+              ->push
+            | None => ()
+            }
+          | _ => ()
+          }
+        )
+      | _ =>
+        toSvmInstruction(
+          item,
+          ~programName=eventConfig.contractName,
+          ~instructionName=eventConfig.name,
+          ~eventConfig,
+          ~fieldSelection=onEventRegistration.fieldSelection,
+        )
+        ->(Utils.magic: Envio.svmInstruction => Internal.eventPayload)
+        ->push
+      }
     })
 
     let parsingTimeElapsed = parsingRef->Performance.secondsSince
@@ -236,6 +482,7 @@ let make = (
       knownHeight,
       fromBlockQueried: fromBlock,
       requestStats,
+      decodeErrors,
     }
   }
 
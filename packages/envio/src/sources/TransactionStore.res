@@ -31,6 +31,11 @@ let fieldCodes = FieldMask.fieldCodes
 // Drain another store (a fetch-response page) into this one.
 @send external merge: (t, t) => unit = "merge"
 
+// Page cold transactions out to the file at `path` once they take more than
+// `budgetBytes` in memory; `materialize` reads them back.
+@send
+external enableSpill: (t, ~path: string, ~budgetBytes: float) => unit = "enableSpill"
+
 type svmTxInput = {
   slot: int,
   transactionIndex: int,
@@ -77,7 +82,7 @@ external materialize: (
   t,
   ~blockNumbers: array<int>,
   ~transactionIndices: array<int>,
-  ~masks: array<float>,
+  ~masks: array<bigint>,
 ) => promise<array<Internal.eventTransaction>> = "materialize"
 
 // Drop transactions for blocks at or below the given block (already processed).